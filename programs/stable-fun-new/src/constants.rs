@@ -2,12 +2,98 @@
 pub const STABLECOIN_SEED: &[u8] = b"stablecoin";
 pub const VAULT_SEED: &[u8] = b"vault";
 pub const MINT_AUTHORITY_SEED: &[u8] = b"mint-authority";
+pub const FREEZE_AUTHORITY_SEED: &[u8] = b"freeze-authority";
+pub const FEE_VAULT_SEED: &[u8] = b"fee-vault";
+pub const POSITION_SEED: &[u8] = b"position";
+pub const REDEEM_COMMITMENT_SEED: &[u8] = b"redeem-commitment";
+pub const PROTOCOL_CONFIG_SEED: &[u8] = b"protocol-config";
+pub const ACCOUNTING_SEED: &[u8] = b"accounting";
+pub const RESERVE_REPORT_SEED: &[u8] = b"reserve-report";
+pub const SNAPSHOT_SEED: &[u8] = b"snapshot";
+pub const AIRDROP_SEED: &[u8] = b"airdrop";
+pub const AIRDROP_VAULT_SEED: &[u8] = b"airdrop-vault";
+pub const AIRDROP_BITMAP_SEED: &[u8] = b"airdrop-bitmap";
+pub const NTT_CONFIG_SEED: &[u8] = b"ntt-config";
+pub const REMOTE_CUSTODIAN_SEED: &[u8] = b"remote-custodian";
+pub const INVOICE_SEED: &[u8] = b"invoice";
+pub const STREAM_SEED: &[u8] = b"stream";
+pub const STREAM_VAULT_SEED: &[u8] = b"stream-vault";
+pub const OPERATION_LOG_SEED: &[u8] = b"operation-log";
+pub const RATE_MODEL_SEED: &[u8] = b"rate-model";
+pub const BUYBACK_SEED: &[u8] = b"buyback";
+pub const BUYBACK_COLLATERAL_SEED: &[u8] = b"buyback-collateral";
+pub const BUYBACK_TOKEN_SEED: &[u8] = b"buyback-token";
+pub const PEG_MONITOR_SEED: &[u8] = b"peg-monitor";
+pub const SHARE_VAULT_SEED: &[u8] = b"share-vault";
+pub const SHARE_MINT_SEED: &[u8] = b"share-mint";
+pub const SHARE_COLLATERAL_SEED: &[u8] = b"share-collateral";
+pub const JUNIOR_TRANCHE_SEED: &[u8] = b"junior-tranche";
+pub const JUNIOR_MINT_SEED: &[u8] = b"junior-mint";
+pub const BASKET_SEED: &[u8] = b"basket";
+pub const DEAD_MAN_SWITCH_SEED: &[u8] = b"dead-man-switch";
+pub const COUNCIL_SEED: &[u8] = b"council";
+pub const PROPOSAL_SEED: &[u8] = b"proposal";
+pub const TREASURY_SEED: &[u8] = b"treasury";
+pub const TREASURY_TOKEN_SEED: &[u8] = b"treasury-token";
+pub const SCHEDULED_TRANSFER_SEED: &[u8] = b"scheduled-transfer";
+pub const SCHEDULED_TRANSFER_VAULT_SEED: &[u8] = b"scheduled-transfer-vault";
+pub const SUBSCRIPTION_SEED: &[u8] = b"subscription";
+pub const RELAY_SEED: &[u8] = b"relay";
+pub const SPONSOR_SEED: &[u8] = b"sponsor";
+pub const DIVIDEND_SEED: &[u8] = b"dividend";
+pub const DIVIDEND_VAULT_SEED: &[u8] = b"dividend-vault";
+pub const DIVIDEND_BITMAP_SEED: &[u8] = b"dividend-bitmap";
+pub const LIQUIDITY_SEED: &[u8] = b"liquidity";
+pub const LIQUIDITY_TOKEN_SEED: &[u8] = b"liquidity-token";
+pub const LIQUIDITY_QUOTE_SEED: &[u8] = b"liquidity-quote";
+pub const LIQUIDITY_LP_SEED: &[u8] = b"liquidity-lp";
+pub const DEDUPE_SEED: &[u8] = b"dedupe";
+pub const LOCKED_LIQUIDITY_SEED: &[u8] = b"locked-liquidity";
+pub const LOCKED_LIQUIDITY_TOKEN_SEED: &[u8] = b"locked-liquidity-token";
+pub const COLLATERAL_MIGRATION_SEED: &[u8] = b"collateral-migration";
+pub const COLLATERAL_MIGRATION_ESCROW_SEED: &[u8] = b"collateral-migration-escrow";
+pub const RESERVED_NAMES_SEED: &[u8] = b"reserved-names";
+pub const EPOCH_STATS_SEED: &[u8] = b"epoch-stats";
+pub const COLLATERAL_LOCK_SEED: &[u8] = b"collateral-lock";
+pub const COLLATERAL_LOCK_TOKEN_SEED: &[u8] = b"collateral-lock-token";
+/// Seed of the PDA a lending program signs lock/release CPIs with
+pub const LENDER_AUTHORITY_SEED: &[u8] = b"stable-fun-lender";
+pub const OTC_OFFER_SEED: &[u8] = b"otc-offer";
+pub const OTC_ESCROW_SEED: &[u8] = b"otc-escrow";
+pub const REDEEM_ORDER_SEED: &[u8] = b"redeem-order";
+pub const REDEEM_ORDER_ESCROW_SEED: &[u8] = b"redeem-order-escrow";
+pub const SAVINGS_VAULT_SEED: &[u8] = b"savings-vault";
+pub const SAVINGS_SHARE_MINT_SEED: &[u8] = b"savings-share-mint";
+pub const SAVINGS_TOKEN_SEED: &[u8] = b"savings-token";
+pub const ELASTIC_SUPPLY_CAP_SEED: &[u8] = b"elastic-supply-cap";
+pub const PORTFOLIO_SEED: &[u8] = b"portfolio";
+pub const ISSUER_PROFILE_SEED: &[u8] = b"issuer-profile";
+pub const FEE_DISCOUNT_SEED: &[u8] = b"fee-discount";
+pub const STAKING_POOL_SEED: &[u8] = b"staking-pool";
+pub const STAKING_VAULT_SEED: &[u8] = b"staking-vault";
+pub const STAKING_REWARD_VAULT_SEED: &[u8] = b"staking-reward-vault";
+pub const STAKE_POSITION_SEED: &[u8] = b"stake-position";
+pub const ISSUER_BOND_SEED: &[u8] = b"issuer-bond";
+pub const ISSUER_BOND_VAULT_SEED: &[u8] = b"issuer-bond-vault";
+pub const AUCTION_LISTING_SEED: &[u8] = b"auction-listing";
+pub const AUCTION_ESCROW_SEED: &[u8] = b"auction-escrow";
+pub const AUCTION_PROCEEDS_SEED: &[u8] = b"auction-proceeds";
+pub const BACKSTOP_SEED: &[u8] = b"backstop";
+pub const BACKSTOP_COLLATERAL_SEED: &[u8] = b"backstop-collateral";
+pub const BACKSTOP_FEE_VAULT_SEED: &[u8] = b"backstop-fee-vault";
+pub const BACKSTOP_PROVIDER_SEED: &[u8] = b"backstop-provider";
+pub const BOND_TERMS_SEED: &[u8] = b"bond-terms";
+pub const COLLATERAL_REGISTRY_SEED: &[u8] = b"collateral-registry";
+pub const COLLATERAL_TYPE_SEED: &[u8] = b"collateral-type";
+pub const REBALANCE_ESCROW_SEED: &[u8] = b"rebalance-escrow";
+pub const MATURITY_POLICY_SEED: &[u8] = b"maturity-policy";
 
 // Validation constants
 pub const MIN_NAME_LENGTH: usize = 3;
 pub const MIN_SYMBOL_LENGTH: usize = 2;
 pub const MAX_NAME_LENGTH: usize = 32;
 pub const MAX_SYMBOL_LENGTH: usize = 10;
+pub const MAX_RESERVED_NAMES: usize = 128;
 
 // Financial constants
 pub const BASIS_POINTS_DIVISOR: u16 = 10000;
@@ -15,12 +101,27 @@ pub const DEFAULT_COLLATERAL_RATIO: u16 = 15000; // 150%
 pub const MIN_COLLATERAL_RATIO: u16 = 10000;     // 100%
 pub const MAX_COLLATERAL_RATIO: u16 = 30000;     // 300%
 pub const MAX_FEE_BPS: u16 = 1000;               // 10%
+pub const DEFAULT_LIQUIDATION_THRESHOLD: u16 = 12000; // 120%
+pub const MIN_LIQUIDATION_THRESHOLD: u16 = 10000;     // 100%
+pub const DEFAULT_LIQUIDATION_PENALTY_BPS: u16 = 300;  // 3%
+pub const DEFAULT_LIQUIDATOR_BONUS_BPS: u16 = 500;     // 5%
+pub const MAX_LIQUIDATION_INCENTIVE_BPS: u16 = 2000;   // 20%, hard cap for protocol caps
+pub const MAX_LIQUIDATION_GRACE_SECONDS: i64 = 604800; // 7 days
+pub const MAX_INTEREST_RATE_BPS: i16 = 2000;           // 20% APR shown by wallets
+pub const MAX_STABILITY_FEE_BPS: u32 = 10000;          // 100% APR at full utilization
+pub const BUYBACK_MAX_PRICE_BPS: u16 = 9950;           // only buy back below $0.995
+pub const MAX_REBASE_STEP_BPS: u16 = 100;              // 1% index move per rebase
+pub const MAX_DEMURRAGE_BPS: u16 = 1000;               // 10% per year, hard cap for protocol caps
+pub const MAX_YIELD_FEE_BPS: u16 = 5000;               // half the carry, hard cap for protocol caps
+pub const MAX_BACKSTOP_FEE_BPS: u16 = 1000;            // 10% per year on committed collateral
 
 // Oracle constants
 pub const PRICE_DECIMALS: u8 = 6;
 pub const PRICE_SCALE: u64 = 10_u64.pow(PRICE_DECIMALS as u32);
 pub const MAX_PRICE_AGE: i64 = 300;              // 5 minutes
 pub const MAX_PRICE_CONFIDENCE: u64 = PRICE_SCALE / 100; // 1%
+pub const PEG_DEVIATION_ALERT_BPS: u32 = 50;     // 0.5% off peg
+pub const MAX_CACHED_PRICE_AGE_SLOTS: u64 = 150; // ~1 minute
 
 // Supply limits
 pub const MIN_SUPPLY: u64 = 1_000;               // 1,000 units
@@ -28,4 +129,58 @@ pub const MAX_SUPPLY: u64 = 1_000_000_000;       // 1 billion units
 
 // Time constants
 pub const MIN_WITHDRAWAL_DELAY: i64 = 60;        // 1 minute
-pub const MAX_WITHDRAWAL_DELAY: i64 = 86400;     // 24 hours
\ No newline at end of file
+pub const MAX_WITHDRAWAL_DELAY: i64 = 86400;     // 24 hours
+pub const SECONDS_PER_DAY: i64 = 86_400;
+pub const SECONDS_PER_YEAR: i64 = 31_536_000;
+pub const MAX_UNPAUSE_COOLOFF_SECONDS: i64 = 604800; // 7 days
+pub const MIN_REBASE_INTERVAL: i64 = 3600;            // 1 hour
+pub const MIN_INACTIVITY_DAYS: u16 = 30;
+pub const MAX_INACTIVITY_DAYS: u16 = 3650;
+pub const PROPOSAL_EXPIRY_SECONDS: i64 = 604800;      // 7 days
+pub const MIN_SUBSCRIPTION_INTERVAL: i64 = 3600;      // 1 hour
+pub const ISSUER_BOND_UNBONDING_SECONDS: i64 = 1_209_600; // 14 days
+
+// Currency baskets
+pub const MAX_BASKET_COMPONENTS: usize = 5;
+pub const BASKET_REBALANCE_TIMELOCK: i64 = 172800; // 2 days
+
+// Price feed rotation
+pub const PRICE_FEED_TIMELOCK: i64 = 172800;       // 2 days
+pub const MAX_FEED_DEVIATION_BPS: u16 = 100;       // 1% between old and new feed
+
+// Airdrops
+pub const MAX_AIRDROP_RECIPIENTS: u32 = 64_000; // keeps the claimed bitmap under 10KB
+
+// Commit-reveal redemption
+pub const MAX_REVEAL_DELAY_SLOTS: u64 = 9_000;     // ~1 hour
+pub const REDEEM_REVEAL_WINDOW_SLOTS: u64 = 1_500; // ~10 minutes
+
+// Fee sponsorship
+pub const MAX_SPONSORED_LAMPORTS_PER_TX: u64 = 100_000; // base fee plus a priority fee
+
+// Lending integrations
+pub const MAX_LENDING_PROGRAMS: usize = 8;
+pub const MAX_CPI_CALLERS: usize = 16;
+
+// Treasury portfolios
+pub const MAX_PORTFOLIO_HOLDINGS: usize = 16;
+
+// Fee discounts
+pub const MAX_FEE_DISCOUNT_TIERS: usize = 4;
+
+// Staking
+pub const MAX_STAKING_REWARDS: usize = 8; // stablecoins sharing fees with stakers
+
+// Epoch analytics
+pub const EPOCH_STATS_HISTORY: usize = 64; // ~4 months of ~2 day epochs
+
+// Approval councils
+pub const MAX_COUNCIL_SIGNERS: usize = 16; // one bit each in `Proposal::approvals`
+pub const COUNCIL_FEE_CHANGE_BPS: u16 = 50; // larger fee moves need a proposal
+
+// Collateral rebalancing
+pub const MAX_COLLATERAL_TYPES: usize = 4;          // besides the coin's stablebond
+pub const MAX_REBALANCE_SLIPPAGE_BPS: u16 = 200;   // 2% against the oracles
+
+// Bond ladders
+pub const MAX_MATURITY_BUCKETS: usize = 6;
@@ -91,6 +91,495 @@ pub enum StableFunError {
 
     #[msg("Unauthorized mint operation")]
     UnauthorizedMint,
+
+    #[msg("Invalid URI")]
+    InvalidUri,
+
+    #[msg("Invalid description")]
+    InvalidDescription,
+
+    #[msg("Invalid withdrawal delay")]
+    InvalidWithdrawalDelay,
+
+    #[msg("Redeem cooldown after minting has not elapsed")]
+    WithdrawalCooldownActive,
+
+    #[msg("Mint and redeem of the same stablecoin in one transaction")]
+    SameTransactionMintRedeem,
+
+    #[msg("Redemption this large requires commit-reveal")]
+    RedeemCommitmentRequired,
+
+    #[msg("Revealed redemption does not match commitment")]
+    InvalidRedeemCommitment,
+
+    #[msg("Reveal delay has not elapsed")]
+    RevealTooEarly,
+
+    #[msg("Reveal window has expired")]
+    RevealWindowExpired,
+
+    #[msg("Invalid commit-reveal settings")]
+    InvalidCommitRevealSettings,
+
+    #[msg("Liquidation threshold must be at least 100% and below the minimum collateral ratio")]
+    InvalidLiquidationThreshold,
+
+    #[msg("Signer is not the protocol admin")]
+    UnauthorizedProtocolAdmin,
+
+    #[msg("Invalid protocol configuration")]
+    InvalidProtocolConfig,
+
+    #[msg("Liquidation penalty or bonus exceeds the protocol cap")]
+    LiquidationIncentiveTooHigh,
+
+    #[msg("Repay amount exceeds position debt")]
+    RepayExceedsDebt,
+
+    #[msg("Position is above the liquidation threshold")]
+    PositionNotLiquidatable,
+
+    #[msg("Invalid liquidation grace settings")]
+    InvalidLiquidationGrace,
+
+    #[msg("Position is still within its liquidation grace period")]
+    LiquidationGracePeriodActive,
+
+    #[msg("Withdrawal would leave surplus below the configured buffer")]
+    InsufficientSurplus,
+
+    #[msg("Snapshot slot must not be in the future")]
+    InvalidSnapshotSlot,
+
+    #[msg("Invalid airdrop parameters")]
+    InvalidAirdrop,
+
+    #[msg("Airdrop index out of range")]
+    InvalidAirdropIndex,
+
+    #[msg("Invalid airdrop Merkle proof")]
+    InvalidAirdropProof,
+
+    #[msg("Airdrop already claimed")]
+    AirdropAlreadyClaimed,
+
+    #[msg("Stablecoin and collateral tokens cannot be rescued")]
+    ProtectedTokenRescue,
+
+    #[msg("Vault token account is already the vault's associated token account")]
+    VaultAlreadyMigrated,
+
+    #[msg("Freeze authority is not held by the program")]
+    FreezeAuthorityNotProgram,
+
+    #[msg("Token extensions require the Token-2022 program")]
+    TokenExtensionsRequireToken2022,
+
+    #[msg("Invalid transfer fee configuration")]
+    InvalidTransferFee,
+
+    #[msg("Stablecoin is not in test mode")]
+    NotInTestMode,
+
+    #[msg("Test-mode stablecoins can only be promoted before any supply exists")]
+    PromotionBlockedBySupply,
+
+    #[msg("Production mints cannot be non-transferable")]
+    NonTransferableProductionMint,
+
+    #[msg("Invalid interest rate")]
+    InvalidInterestRate,
+
+    #[msg("No time has passed since the last interest rate update")]
+    InterestRatePeriodTooShort,
+
+    #[msg("Signer is not the registered NTT manager")]
+    UnauthorizedNttManager,
+
+    #[msg("Outbound transfer exceeds the NTT rate limit")]
+    NttRateLimitExceeded,
+
+    #[msg("Inbound transfer exceeds the supply bridged out")]
+    BridgedSupplyExceeded,
+
+    #[msg("Invalid NTT configuration")]
+    InvalidNttConfig,
+
+    #[msg("Missing or invalid secp256k1 signature for the remote redemption order")]
+    InvalidRemoteRedemptionSignature,
+
+    #[msg("Remote redemption nonce already used")]
+    RemoteRedemptionNonceUsed,
+
+    #[msg("Invalid remote custodian")]
+    InvalidRemoteCustodian,
+
+    #[msg("Invalid invoice")]
+    InvalidInvoice,
+
+    #[msg("Invoice is not open")]
+    InvoiceNotOpen,
+
+    #[msg("Invoice has expired")]
+    InvoiceExpired,
+
+    #[msg("Stream must end after it starts")]
+    InvalidStreamSchedule,
+
+    #[msg("Signer is not the stream recipient")]
+    UnauthorizedStreamRecipient,
+
+    #[msg("Signer does not hold the pauser role")]
+    UnauthorizedPauser,
+
+    #[msg("Invalid unpause cool-off")]
+    InvalidUnpauseCooloff,
+
+    #[msg("Pause cool-off period has not elapsed")]
+    UnpauseCooloffActive,
+
+    #[msg("Pauses can only be lifted through unpause_mint/unpause_redeem")]
+    UnpauseChecksRequired,
+
+    #[msg("Vault or supply accounting invariant violated")]
+    InvariantViolation,
+
+    #[msg("Recorded supply has drifted from the token mint supply")]
+    SupplyDrift,
+
+    #[msg("Invalid interest rate model parameters")]
+    InvalidInterestRateModel,
+
+    #[msg("Invalid buyback configuration")]
+    InvalidBuybackConfig,
+
+    #[msg("Buyback price is above the allowed band")]
+    BuybackPriceAboveBand,
+
+    #[msg("Buyback swap did not spend exactly the escrowed collateral")]
+    BuybackSwapMismatch,
+
+    #[msg("Cached price age is out of range")]
+    InvalidCachedPriceAge,
+
+    #[msg("Amount is too small to convert into any vault shares or assets")]
+    ZeroShares,
+
+    #[msg("Junior redemption would leave the senior tranche under-collateralized")]
+    JuniorRedemptionBlocked,
+
+    #[msg("Invalid tranche configuration")]
+    InvalidTrancheConfig,
+
+    #[msg("Invalid currency basket")]
+    InvalidBasket,
+
+    #[msg("Basket rebalance is still timelocked")]
+    BasketTimelockActive,
+
+    #[msg("Basket index price is stale")]
+    StaleBasketIndex,
+
+    #[msg("Rebasing is not enabled for this stablecoin")]
+    RebaseNotEnabled,
+
+    #[msg("Rebase interval has not elapsed")]
+    RebaseTooSoon,
+
+    #[msg("Invalid rebase mode")]
+    InvalidRebaseMode,
+
+    #[msg("Demurrage rate exceeds the protocol cap")]
+    DemurrageTooHigh,
+
+    #[msg("A valid attestation is required")]
+    MissingAttestation,
+
+    #[msg("Invalid attestation")]
+    InvalidAttestation,
+
+    #[msg("Attestation has expired")]
+    AttestationExpired,
+
+    #[msg("Holder jurisdiction is excluded by this stablecoin")]
+    JurisdictionExcluded,
+
+    #[msg("Jurisdiction flags need a regulated coin and a holder schema")]
+    InvalidJurisdictionFlags,
+
+    #[msg("Invalid dead-man switch configuration")]
+    InvalidDeadManSwitch,
+
+    #[msg("Issuer has not been inactive long enough")]
+    IssuerStillActive,
+
+    #[msg("Only the recovery key or the guardian may trigger the switch")]
+    UnauthorizedRecovery,
+
+    #[msg("Invalid approval council")]
+    InvalidCouncil,
+
+    #[msg("Signer is not a council member")]
+    NotCouncilSigner,
+
+    #[msg("Proposal does not have enough approvals")]
+    ProposalNotApproved,
+
+    #[msg("Proposal has already been executed")]
+    ProposalAlreadyExecuted,
+
+    #[msg("Proposal has expired")]
+    ProposalExpired,
+
+    #[msg("This change needs an approved council proposal")]
+    CouncilApprovalRequired,
+
+    #[msg("Signer is not the treasury operator")]
+    UnauthorizedTreasuryOperator,
+
+    #[msg("Treasury daily spending limit exceeded")]
+    SpendingLimitExceeded,
+
+    #[msg("Release time must be in the future")]
+    InvalidReleaseTime,
+
+    #[msg("Scheduled transfer is not released yet")]
+    TransferNotReleased,
+
+    #[msg("Scheduled transfer is already released and can no longer be cancelled")]
+    TransferAlreadyReleased,
+
+    #[msg("Invalid subscription parameters")]
+    InvalidSubscription,
+
+    #[msg("Subscription period is not due yet")]
+    SubscriptionNotDue,
+
+    #[msg("Missing or invalid ed25519 signature for the relayed order")]
+    InvalidRelayedOrderSignature,
+
+    #[msg("Relayed order has expired")]
+    RelayedOrderExpired,
+
+    #[msg("Relayed order nonce already used")]
+    RelayedOrderNonceUsed,
+
+    #[msg("Relayer fee exceeds what the order allows")]
+    RelayerFeeTooHigh,
+
+    #[msg("Invalid sponsor configuration")]
+    InvalidSponsorConfig,
+
+    #[msg("No sponsored instruction for this stablecoin ran in the transaction")]
+    NotSponsored,
+
+    #[msg("Sponsorship was already claimed in this transaction")]
+    SponsorshipAlreadyClaimed,
+
+    #[msg("Sponsor has no SOL left to reimburse")]
+    SponsorDepleted,
+
+    #[msg("Yield fee exceeds the protocol cap")]
+    YieldFeeTooHigh,
+
+    #[msg("Invalid dividend parameters")]
+    InvalidDividend,
+
+    #[msg("Invalid dividend snapshot proof")]
+    InvalidDividendProof,
+
+    #[msg("Dividend already claimed")]
+    DividendAlreadyClaimed,
+
+    #[msg("Invalid liquidity configuration")]
+    InvalidLiquidityConfig,
+
+    #[msg("AMM did not move exactly the expected liquidity")]
+    LiquidityDepositMismatch,
+
+    #[msg("Liquidity buffer needs a reveal delay and at most 10000 bps")]
+    InvalidLiquidityBuffer,
+
+    #[msg("Redemption would breach the vault liquidity buffer")]
+    LiquidityBufferBreached,
+
+    #[msg("Client order id was already filled")]
+    DuplicateClientOrder,
+
+    #[msg("The dedupe guard requires a client order id")]
+    MissingClientOrderId,
+
+    #[msg("The first mint must include the liquidity lock accounts")]
+    MissingLiquidityLock,
+
+    #[msg("Position debt would be below the coin's minimum")]
+    PositionBelowMinimum,
+
+    #[msg("The burn authority is not approved as delegate for this amount")]
+    BurnNotApproved,
+
+    #[msg("Price feed is not owned by the Switchboard program")]
+    InvalidOracleOwner,
+
+    #[msg("No price feed change is queued")]
+    NoPendingPriceFeed,
+
+    #[msg("Price feed change is still timelocked")]
+    PriceFeedTimelockActive,
+
+    #[msg("New price feed deviates too far from the current one")]
+    PriceFeedDeviation,
+
+    #[msg("Invalid collateral migration")]
+    InvalidCollateralMigration,
+
+    #[msg("Rollover returned fewer new bonds than the migration allows")]
+    RolloverBelowMinimum,
+
+    #[msg("Old collateral account still holds bonds")]
+    CollateralMigrationIncomplete,
+
+    #[msg("Max supply is above the cap of the issuer's reserve tier")]
+    SupplyCapExceedsTier,
+
+    #[msg("Launch fee must be paid to the protocol treasury")]
+    InvalidProtocolTreasury,
+
+    #[msg("Stablecoin still has supply or collateral outstanding")]
+    StablecoinNotEmpty,
+
+    #[msg("Name or symbol is reserved")]
+    ReservedName,
+
+    #[msg("Too many reserved names")]
+    TooManyReservedNames,
+
+    #[msg("Lending program is not approved")]
+    UnapprovedLendingProgram,
+
+    #[msg("Signer is not the lending program's authority")]
+    InvalidLenderAuthority,
+
+    #[msg("Offer is reserved for another counterparty")]
+    UnauthorizedCounterparty,
+
+    #[msg("Offer has expired")]
+    OfferExpired,
+
+    #[msg("Invalid OTC offer")]
+    InvalidOtcOffer,
+
+    #[msg("Redeem order has expired")]
+    RedeemOrderExpired,
+
+    #[msg("Oracle price has not reached the redeem order's limit")]
+    RedeemOrderPriceNotReached,
+
+    #[msg("Invalid elastic supply cap parameters")]
+    InvalidElasticSupplyCap,
+
+    #[msg("Supply cap was already adjusted this epoch")]
+    SupplyCapAlreadyAdjusted,
+
+    #[msg("Protocol is paused")]
+    ProtocolPaused,
+
+    #[msg("Coin may only be minted or redeemed at the top level of a transaction")]
+    CpiNotAllowed,
+
+    #[msg("Calling program is not approved to mint or redeem through CPI")]
+    UnapprovedCpiCaller,
+
+    #[msg("Too many mint and redeem operations; try again shortly")]
+    ThroughputLimitExceeded,
+
+    #[msg("Portfolio already tracks the maximum number of stablecoins")]
+    PortfolioFull,
+
+    #[msg("Invalid governance account")]
+    InvalidGovernance,
+
+    #[msg("Settings of a governed coin must be signed by its governance")]
+    GovernanceSignatureRequired,
+
+    #[msg("Invalid fee discount schedule or discount token account")]
+    InvalidFeeDiscount,
+
+    #[msg("Stablecoin is not a staking reward stream, or the pool has no room for it")]
+    InvalidRewardStream,
+
+    #[msg("Unstaked tokens are still cooling down")]
+    StakeCooldownActive,
+
+    #[msg("Supply above the bond threshold requires a posted issuer bond")]
+    IssuerBondRequired,
+
+    #[msg("Issuer bond is still unbonding")]
+    BondUnbonding,
+
+    #[msg("Not a seized bond certificate held by the vault")]
+    InvalidCertificate,
+
+    #[msg("Auction has not sold the certificate for the minimum proceeds")]
+    AuctionNotSettled,
+
+    #[msg("Backstop does not hold enough liquid collateral")]
+    InsufficientBackstop,
+
+    #[msg("Account layout version is not supported")]
+    UnsupportedLayoutVersion,
+
+    #[msg("Account already uses the current layout")]
+    LayoutAlreadyCurrent,
+
+    #[msg("Not allowed while a collateral migration is in progress")]
+    CollateralMigrationInProgress,
+
+    #[msg("Redeem commitment is not at the front of the queue")]
+    RedeemNotAtQueueHead,
+
+    #[msg("Redeem commitment can still be revealed")]
+    RevealWindowOpen,
+
+    #[msg("Dry run would create accounts; they must already exist")]
+    DryRunCreatesAccounts,
+
+    #[msg("Bond terms need a yield and a term between one day and one year")]
+    InvalidBondTerms,
+
+    #[msg("Bond terms are already set for this stablebond")]
+    BondTermsAlreadySet,
+
+    #[msg("Invalid collateral registry configuration")]
+    InvalidCollateralRegistry,
+
+    #[msg("Collateral type is not registered")]
+    InvalidCollateralType,
+
+    #[msg("Signer is not the collateral manager")]
+    UnauthorizedCollateralManager,
+
+    #[msg("Rebalance exceeds this epoch's volume cap")]
+    RebalanceVolumeExceeded,
+
+    #[msg("Rebalance swap lost more than the slippage limit")]
+    RebalanceSlippageExceeded,
+
+    #[msg("Rebalance does not move collateral toward its targets")]
+    RebalanceOffTarget,
+
+    #[msg("Rebalance swap did not spend exactly the collateral sold")]
+    RebalanceSwapMismatch,
+
+    #[msg("Maturity buckets must ascend and their targets sum to 100%")]
+    InvalidMaturityPolicy,
+
+    #[msg("Collateral is already within the maturity policy")]
+    LadderWithinPolicy,
+
+    #[msg("Rebalance does not move collateral toward the maturity policy")]
+    LadderOffTarget,
 }
 
 // Helper functions for common error checks
@@ -0,0 +1,321 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+use anchor_spl::token_interface;
+use switchboard_solana::AggregatorAccountData;
+
+use crate::state::{
+    JuniorTranche, ProtocolConfig, StablecoinMint, StablecoinVault, SystemAccounting,
+};
+use crate::constants::{ACCOUNTING_SEED, JUNIOR_TRANCHE_SEED, PROTOCOL_CONFIG_SEED, VAULT_SEED};
+use crate::error::StableFunError;
+use crate::utils::oracle::OracleService;
+use crate::utils::validation::ValidationService;
+use crate::utils::math;
+
+#[derive(Accounts)]
+pub struct SettleBadDebt<'info> {
+    pub caller: Signer<'info>,
+
+    pub stablecoin_mint: Account<'info, StablecoinMint>,
+
+    #[account(
+        mut,
+        seeds = [ACCOUNTING_SEED, stablecoin_mint.key().as_ref()],
+        bump = accounting.bump
+    )]
+    pub accounting: Account<'info, SystemAccounting>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+}
+
+/// Permissionless: nets realized surplus against realized bad debt.
+pub fn settle_bad_debt(ctx: Context<SettleBadDebt>) -> Result<()> {
+    let accounting = &mut ctx.accounts.accounting;
+    let settled = accounting.settle_bad_debt()?;
+
+    emit!(BadDebtSettled {
+        stablecoin_mint: ctx.accounts.stablecoin_mint.key(),
+        amount: settled,
+        remaining_surplus: accounting.surplus,
+        remaining_bad_debt: accounting.bad_debt,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+pub struct WithdrawFees<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        mut,
+        seeds = [ACCOUNTING_SEED, stablecoin_mint.key().as_ref()],
+        bump = accounting.bump
+    )]
+    pub accounting: Box<Account<'info, SystemAccounting>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        mut,
+        constraint = vault_stablebond_account.key() == vault.collateral_account @ StableFunError::InvalidVaultAccount
+    )]
+    pub vault_stablebond_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = authority_stablebond_account.mint == stablecoin_mint.stablebond_mint @ StableFunError::InvalidStablebond
+    )]
+    pub authority_stablebond_account: Box<Account<'info, TokenAccount>>,
+
+    /// The Switchboard V3 aggregator account
+    #[account(
+        constraint = price_feed.key() == stablecoin_mint.price_feed @ StableFunError::InvalidOracle
+    )]
+    pub price_feed: AccountLoader<'info, AggregatorAccountData>,
+
+    /// FX feed composed with the bond NAV `price_feed`, on coins that set one
+    #[account(
+        constraint = fx_feed.key() == stablecoin_mint.fx_feed @ StableFunError::InvalidOracle
+    )]
+    pub fx_feed: Option<AccountLoader<'info, AggregatorAccountData>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Pays the issuer `amount` of surplus (in stablecoin units) as collateral,
+/// provided the configured surplus buffer stays behind.
+pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+    require!(amount > 0, StableFunError::InvalidAmount);
+
+    ctx.accounts.accounting.withdraw_fees(
+        amount,
+        ctx.accounts.stablecoin_mint.settings.surplus_buffer,
+    )?;
+
+    let oracle_price = OracleService::collateral_price(
+        &ctx.accounts.stablecoin_mint,
+        &ctx.accounts.price_feed,
+        ctx.accounts.fx_feed.as_ref(),
+    )?;
+    let collateral_amount = math::calculate_token_amount(
+        amount,
+        oracle_price,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.vault_stablebond_account.to_account_info(),
+                to: ctx.accounts.authority_stablebond_account.to_account_info(),
+                authority: ctx.accounts.vault.to_account_info(),
+            },
+            &[&ctx.accounts.vault.signer_seeds()],
+        ),
+        collateral_amount,
+    )?;
+
+    let clock = Clock::get()?;
+    let vault = &mut ctx.accounts.vault;
+    vault.total_collateral = vault
+        .total_collateral
+        .checked_sub(collateral_amount)
+        .ok_or(error!(StableFunError::InsufficientCollateral))?;
+    vault.withdrawal_count = vault
+        .withdrawal_count
+        .checked_add(1)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    vault.last_withdrawal_time = clock.unix_timestamp;
+    ValidationService::update_collateral_ratio(vault, oracle_price, ctx.accounts.token_mint.decimals)?;
+
+    emit!(FeesWithdrawn {
+        stablecoin_mint: stablecoin_key,
+        authority: ctx.accounts.authority.key(),
+        amount,
+        collateral_amount,
+        remaining_surplus: ctx.accounts.accounting.surplus,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SyncVault<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(mut)]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        mut,
+        seeds = [ACCOUNTING_SEED, stablecoin_mint.key().as_ref()],
+        bump = accounting.bump
+    )]
+    pub accounting: Box<Account<'info, SystemAccounting>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        constraint = vault_stablebond_account.key() == vault.collateral_account @ StableFunError::InvalidVaultAccount
+    )]
+    pub vault_stablebond_account: Box<Account<'info, TokenAccount>>,
+
+    /// The Switchboard V3 aggregator account
+    #[account(
+        constraint = price_feed.key() == stablecoin_mint.price_feed @ StableFunError::InvalidOracle
+    )]
+    pub price_feed: AccountLoader<'info, AggregatorAccountData>,
+
+    /// FX feed composed with the bond NAV `price_feed`, on coins that set one
+    #[account(
+        constraint = fx_feed.key() == stablecoin_mint.fx_feed @ StableFunError::InvalidOracle
+    )]
+    pub fx_feed: Option<AccountLoader<'info, AggregatorAccountData>>,
+
+    /// When present, the junior tranche takes its share of the yield
+    #[account(
+        mut,
+        seeds = [JUNIOR_TRANCHE_SEED, stablecoin_mint.key().as_ref()],
+        bump = junior_tranche.bump
+    )]
+    pub junior_tranche: Option<Box<Account<'info, JuniorTranche>>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+}
+
+/// Permissionless: brings collateral sent straight to the vault token account
+/// onto the books, crediting its value as surplus and harvested bond yield
+/// (after the junior tranche's cut, if one exists). The coin's `yield_fee_bps`
+/// share of the senior yield stays with the issuer rather than the savings rate.
+pub fn sync_vault(ctx: Context<SyncVault>) -> Result<()> {
+    let vault_balance = ctx.accounts.vault_stablebond_account.amount;
+    let untracked = ctx.accounts.vault.untracked_collateral(vault_balance);
+
+    let untracked_value = if untracked > 0 {
+        let oracle_price = OracleService::collateral_price(
+            &ctx.accounts.stablecoin_mint,
+            &ctx.accounts.price_feed,
+            ctx.accounts.fx_feed.as_ref(),
+        )?;
+        math::calculate_collateral_value(
+            untracked,
+            oracle_price,
+            ctx.accounts.token_mint.decimals,
+        )?
+    } else {
+        0
+    };
+
+    let vault = &mut ctx.accounts.vault;
+    vault.total_collateral = vault
+        .total_collateral
+        .checked_add(untracked)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    // No fresh price here; re-rate at the last one
+    let ratio_price = vault.ratio_price;
+    ValidationService::update_collateral_ratio(vault, ratio_price, ctx.accounts.token_mint.decimals)?;
+
+    let senior_value = match ctx.accounts.junior_tranche.as_mut() {
+        Some(junior_tranche) => junior_tranche.capture_yield(untracked_value)?,
+        None => untracked_value,
+    };
+    let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
+    let yield_fee = ctx.accounts.accounting.record_harvested_yield_after_fee(
+        senior_value,
+        stablecoin_mint.settings.yield_fee_bps,
+    )?;
+    stablecoin_mint.stats.total_yield_fees = stablecoin_mint.stats.total_yield_fees
+        .checked_add(yield_fee)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+
+    emit!(VaultSynced {
+        stablecoin_mint: stablecoin_mint.key(),
+        vault_balance,
+        untracked_collateral: untracked,
+        credited_surplus: senior_value,
+        yield_fee,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct VaultSynced {
+    pub stablecoin_mint: Pubkey,
+    pub vault_balance: u64,
+    pub untracked_collateral: u64,
+    pub credited_surplus: u64,
+    pub yield_fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BadDebtSettled {
+    pub stablecoin_mint: Pubkey,
+    pub amount: u64,
+    pub remaining_surplus: u64,
+    pub remaining_bad_debt: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeesWithdrawn {
+    pub stablecoin_mint: Pubkey,
+    pub authority: Pubkey,
+    pub amount: u64,
+    pub collateral_amount: u64,
+    pub remaining_surplus: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,251 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+
+use crate::state::{
+    Airdrop, ClaimBitmap, ProtocolConfig, StablecoinMint, StateAccount, PAUSE_TRANSFERS,
+};
+use crate::constants::{
+    AIRDROP_BITMAP_SEED, AIRDROP_SEED, AIRDROP_VAULT_SEED, MAX_AIRDROP_RECIPIENTS,
+    PROTOCOL_CONFIG_SEED,
+};
+use crate::error::StableFunError;
+
+#[derive(Accounts)]
+#[instruction(airdrop_id: u64, merkle_root: [u8; 32], total_amount: u64, max_recipients: u32)]
+pub struct CreateAirdrop<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Airdrop::LEN,
+        seeds = [AIRDROP_SEED, stablecoin_mint.key().as_ref(), &airdrop_id.to_le_bytes()],
+        bump
+    )]
+    pub airdrop: Box<Account<'info, Airdrop>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = ClaimBitmap::space(max_recipients),
+        seeds = [AIRDROP_BITMAP_SEED, airdrop.key().as_ref()],
+        bump
+    )]
+    pub claim_bitmap: Box<Account<'info, ClaimBitmap>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [AIRDROP_VAULT_SEED, airdrop.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = airdrop,
+        token::token_program = token_program
+    )]
+    pub airdrop_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = authority_token_account.mint == token_mint.key() @ StableFunError::InvalidTokenAccount,
+        constraint = authority_token_account.owner == authority.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub authority_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_TRANSFERS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Funds a distributor with `total_amount` stablecoins claimable under `merkle_root`.
+pub fn create_airdrop(
+    ctx: Context<CreateAirdrop>,
+    airdrop_id: u64,
+    merkle_root: [u8; 32],
+    total_amount: u64,
+    max_recipients: u32,
+) -> Result<()> {
+    require!(
+        total_amount > 0
+            && max_recipients > 0
+            && max_recipients <= MAX_AIRDROP_RECIPIENTS
+            && merkle_root != [0; 32],
+        StableFunError::InvalidAirdrop
+    );
+
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.authority_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.airdrop_token_account.to_account_info(),
+                authority: ctx.accounts.authority.to_account_info(),
+            },
+        ),
+        total_amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    let clock = Clock::get()?;
+    let airdrop = &mut ctx.accounts.airdrop;
+    airdrop.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    airdrop.authority = ctx.accounts.authority.key();
+    airdrop.airdrop_id = airdrop_id;
+    airdrop.merkle_root = merkle_root;
+    airdrop.token_account = ctx.accounts.airdrop_token_account.key();
+    airdrop.max_recipients = max_recipients;
+    airdrop.total_amount = total_amount;
+    airdrop.created_at = clock.unix_timestamp;
+    airdrop.bump = ctx.bumps.airdrop;
+
+    let claim_bitmap = &mut ctx.accounts.claim_bitmap;
+    claim_bitmap.airdrop = airdrop.key();
+    claim_bitmap.bump = ctx.bumps.claim_bitmap;
+    claim_bitmap.bits = vec![0; ClaimBitmap::byte_len(max_recipients)];
+
+    emit!(AirdropCreated {
+        stablecoin_mint: airdrop.stablecoin_mint,
+        airdrop: airdrop.key(),
+        airdrop_id,
+        merkle_root,
+        total_amount,
+        max_recipients,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimAirdrop<'info> {
+    pub claimant: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [AIRDROP_SEED, airdrop.stablecoin_mint.as_ref(), &airdrop.airdrop_id.to_le_bytes()],
+        bump = airdrop.bump
+    )]
+    pub airdrop: Box<Account<'info, Airdrop>>,
+
+    #[account(
+        mut,
+        seeds = [AIRDROP_BITMAP_SEED, airdrop.key().as_ref()],
+        bump = claim_bitmap.bump
+    )]
+    pub claim_bitmap: Box<Account<'info, ClaimBitmap>>,
+
+    #[account(
+        constraint = token_mint.key() == airdrop_token_account.mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = airdrop_token_account.key() == airdrop.token_account @ StableFunError::InvalidTokenAccount
+    )]
+    pub airdrop_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = claimant_token_account.mint == airdrop_token_account.mint @ StableFunError::InvalidTokenAccount,
+        constraint = claimant_token_account.owner == claimant.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub claimant_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_TRANSFERS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn claim_airdrop(
+    ctx: Context<ClaimAirdrop>,
+    index: u64,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    require!(
+        index < ctx.accounts.airdrop.max_recipients as u64,
+        StableFunError::InvalidAirdropIndex
+    );
+    require!(
+        ctx.accounts.airdrop.verify_claim(index, &ctx.accounts.claimant.key(), amount, &proof),
+        StableFunError::InvalidAirdropProof
+    );
+
+    ctx.accounts.claim_bitmap.set_claimed(index)?;
+    ctx.accounts.airdrop.record_claim(amount)?;
+
+    let airdrop = &ctx.accounts.airdrop;
+    let airdrop_id = airdrop.airdrop_id.to_le_bytes();
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.airdrop_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.claimant_token_account.to_account_info(),
+                authority: airdrop.to_account_info(),
+            },
+            &[&[
+                AIRDROP_SEED,
+                airdrop.stablecoin_mint.as_ref(),
+                &airdrop_id,
+                &[airdrop.bump],
+            ]],
+        ),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    emit!(AirdropClaimed {
+        airdrop: airdrop.key(),
+        claimant: ctx.accounts.claimant.key(),
+        index,
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct AirdropCreated {
+    pub stablecoin_mint: Pubkey,
+    pub airdrop: Pubkey,
+    pub airdrop_id: u64,
+    pub merkle_root: [u8; 32],
+    pub total_amount: u64,
+    pub max_recipients: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AirdropClaimed {
+    pub airdrop: Pubkey,
+    pub claimant: Pubkey,
+    pub index: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
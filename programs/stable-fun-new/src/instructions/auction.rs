@@ -0,0 +1,425 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_interface;
+use switchboard_solana::AggregatorAccountData;
+
+use crate::state::{
+    AuctionListing, ProtocolConfig, StablecoinMint, StablecoinVault, StateAccount, SystemAccounting,
+    PAUSE_LIQUIDATION,
+};
+use crate::constants::{
+    ACCOUNTING_SEED, AUCTION_ESCROW_SEED, AUCTION_LISTING_SEED, AUCTION_PROCEEDS_SEED,
+    PROTOCOL_CONFIG_SEED, VAULT_SEED,
+};
+use crate::error::StableFunError;
+use crate::utils::oracle::OracleService;
+use crate::utils::validation::ValidationService;
+use crate::utils::math;
+
+#[derive(Accounts)]
+pub struct ListSeizedCertificate<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        constraint = certificate_mint.decimals == 0 && certificate_mint.supply == 1 @ StableFunError::InvalidCertificate,
+        constraint = certificate_mint.key() != stablecoin_mint.stablebond_mint @ StableFunError::InvalidCertificate
+    )]
+    pub certificate_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = vault_certificate_account.owner == vault.key() @ StableFunError::InvalidVaultAccount,
+        constraint = vault_certificate_account.mint == certificate_mint.key() @ StableFunError::InvalidCertificate
+    )]
+    pub vault_certificate_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = AuctionListing::LEN,
+        seeds = [AUCTION_LISTING_SEED, stablecoin_mint.key().as_ref(), certificate_mint.key().as_ref()],
+        bump
+    )]
+    pub listing: Box<Account<'info, AuctionListing>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [AUCTION_ESCROW_SEED, listing.key().as_ref()],
+        bump,
+        token::mint = certificate_mint,
+        token::authority = listing
+    )]
+    pub escrow_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        constraint = stablebond_mint.key() == stablecoin_mint.stablebond_mint @ StableFunError::InvalidStablebond
+    )]
+    pub stablebond_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [AUCTION_PROCEEDS_SEED, listing.key().as_ref()],
+        bump,
+        token::mint = stablebond_mint,
+        token::authority = listing
+    )]
+    pub proceeds_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Auction program the listing instruction is sent to
+    #[account(executable)]
+    pub auction_program: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_LIQUIDATION) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Moves a seized bond certificate out of the vault into the listing's
+/// escrow and lists it on `auction_program`. `listing_data` and the
+/// remaining accounts form the auction program's listing instruction, signed
+/// by the listing PDA; the winning bid must be paid to the proceeds account.
+pub fn list_seized_certificate<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ListSeizedCertificate<'info>>,
+    min_proceeds: u64,
+    listing_data: Vec<u8>,
+) -> Result<()> {
+    require!(min_proceeds > 0, StableFunError::InvalidAmount);
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.vault_certificate_account.to_account_info(),
+                to: ctx.accounts.escrow_account.to_account_info(),
+                authority: ctx.accounts.vault.to_account_info(),
+            },
+            &[&ctx.accounts.vault.signer_seeds()],
+        ),
+        1,
+    )?;
+
+    let clock = Clock::get()?;
+    let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+    let certificate_key = ctx.accounts.certificate_mint.key();
+    let listing = &mut ctx.accounts.listing;
+    listing.stablecoin_mint = stablecoin_key;
+    listing.certificate_mint = certificate_key;
+    listing.auction_program = ctx.accounts.auction_program.key();
+    listing.escrow_account = ctx.accounts.escrow_account.key();
+    listing.proceeds_account = ctx.accounts.proceeds_account.key();
+    listing.min_proceeds = min_proceeds;
+    listing.listed_at = clock.unix_timestamp;
+    listing.bump = ctx.bumps.listing;
+
+    // List on the auction program, signed by the listing PDA
+    let listing_key = listing.key();
+    let listing_ix = Instruction {
+        program_id: listing.auction_program,
+        accounts: ctx.remaining_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer || account.key() == listing_key,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data: listing_data,
+    };
+    invoke_signed(
+        &listing_ix,
+        ctx.remaining_accounts,
+        &[&[
+            AUCTION_LISTING_SEED,
+            stablecoin_key.as_ref(),
+            certificate_key.as_ref(),
+            &[listing.bump],
+        ]],
+    )?;
+
+    emit!(CertificateListed {
+        stablecoin_mint: stablecoin_key,
+        certificate_mint: certificate_key,
+        auction_program: listing.auction_program,
+        min_proceeds,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SettleCertificateAuction<'info> {
+    #[account(mut)]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        mut,
+        seeds = [ACCOUNTING_SEED, stablecoin_mint.key().as_ref()],
+        bump = accounting.bump
+    )]
+    pub accounting: Box<Account<'info, SystemAccounting>>,
+
+    #[account(
+        mut,
+        seeds = [AUCTION_LISTING_SEED, stablecoin_mint.key().as_ref(), listing.certificate_mint.as_ref()],
+        bump = listing.bump,
+        close = authority
+    )]
+    pub listing: Box<Account<'info, AuctionListing>>,
+
+    /// CHECK: The coin's authority, who gets the listing's rent back
+    #[account(
+        mut,
+        constraint = authority.key() == stablecoin_mint.authority @ StableFunError::UnauthorizedMint
+    )]
+    pub authority: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = escrow_account.key() == listing.escrow_account @ StableFunError::InvalidTokenAccount
+    )]
+    pub escrow_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = proceeds_account.key() == listing.proceeds_account @ StableFunError::InvalidTokenAccount
+    )]
+    pub proceeds_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = vault_stablebond_account.key() == vault.collateral_account @ StableFunError::InvalidVaultAccount
+    )]
+    pub vault_stablebond_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// The Switchboard V3 aggregator account
+    #[account(
+        constraint = price_feed.key() == stablecoin_mint.price_feed @ StableFunError::InvalidOracle
+    )]
+    pub price_feed: AccountLoader<'info, AggregatorAccountData>,
+
+    /// FX feed composed with the bond NAV `price_feed`, on coins that set one
+    #[account(
+        constraint = fx_feed.key() == stablecoin_mint.fx_feed @ StableFunError::InvalidOracle
+    )]
+    pub fx_feed: Option<AccountLoader<'info, AggregatorAccountData>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_LIQUIDATION) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Permissionless, once the auction has sold the certificate: moves the
+/// proceeds into the vault and books them as surplus, since the certificate
+/// never counted towards `total_collateral` after its seizure.
+pub fn settle_certificate_auction(ctx: Context<SettleCertificateAuction>) -> Result<()> {
+    let proceeds = ctx.accounts.proceeds_account.amount;
+    require!(
+        ctx.accounts.listing.is_sold(ctx.accounts.escrow_account.amount, proceeds),
+        StableFunError::AuctionNotSettled
+    );
+
+    let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+    let listing = &ctx.accounts.listing;
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.proceeds_account.to_account_info(),
+                to: ctx.accounts.vault_stablebond_account.to_account_info(),
+                authority: listing.to_account_info(),
+            },
+            &[&[
+                AUCTION_LISTING_SEED,
+                stablecoin_key.as_ref(),
+                listing.certificate_mint.as_ref(),
+                &[listing.bump],
+            ]],
+        ),
+        proceeds,
+    )?;
+
+    let oracle_price = OracleService::collateral_price(
+        &ctx.accounts.stablecoin_mint,
+        &ctx.accounts.price_feed,
+        ctx.accounts.fx_feed.as_ref(),
+    )?;
+    let decimals = ctx.accounts.token_mint.decimals;
+    let proceeds_value = math::calculate_collateral_value(proceeds, oracle_price, decimals)?;
+    ctx.accounts.accounting.record_surplus(proceeds_value)?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.total_collateral = vault
+        .total_collateral
+        .checked_add(proceeds)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    ValidationService::update_collateral_ratio(vault, oracle_price, decimals)?;
+
+    let clock = Clock::get()?;
+    ctx.accounts.stablecoin_mint.last_updated = clock.unix_timestamp;
+
+    emit!(CertificateAuctionSettled {
+        stablecoin_mint: stablecoin_key,
+        certificate_mint: ctx.accounts.listing.certificate_mint,
+        proceeds,
+        proceeds_value,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelCertificateListing<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        mut,
+        seeds = [AUCTION_LISTING_SEED, stablecoin_mint.key().as_ref(), listing.certificate_mint.as_ref()],
+        bump = listing.bump,
+        close = authority
+    )]
+    pub listing: Box<Account<'info, AuctionListing>>,
+
+    #[account(
+        mut,
+        constraint = escrow_account.key() == listing.escrow_account @ StableFunError::InvalidTokenAccount
+    )]
+    pub escrow_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        constraint = proceeds_account.key() == listing.proceeds_account @ StableFunError::InvalidTokenAccount,
+        constraint = proceeds_account.amount == 0 @ StableFunError::AuctionNotSettled
+    )]
+    pub proceeds_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = vault_certificate_account.owner == vault.key() @ StableFunError::InvalidVaultAccount,
+        constraint = vault_certificate_account.mint == listing.certificate_mint @ StableFunError::InvalidCertificate
+    )]
+    pub vault_certificate_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_LIQUIDATION) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Returns a certificate the auction handed back unsold to the vault. The
+/// auction program's own cancel instruction must have run first.
+pub fn cancel_certificate_listing(ctx: Context<CancelCertificateListing>) -> Result<()> {
+    require!(ctx.accounts.escrow_account.amount == 1, StableFunError::InvalidCertificate);
+
+    let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+    let listing = &ctx.accounts.listing;
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.escrow_account.to_account_info(),
+                to: ctx.accounts.vault_certificate_account.to_account_info(),
+                authority: listing.to_account_info(),
+            },
+            &[&[
+                AUCTION_LISTING_SEED,
+                stablecoin_key.as_ref(),
+                listing.certificate_mint.as_ref(),
+                &[listing.bump],
+            ]],
+        ),
+        1,
+    )?;
+
+    emit!(CertificateListingCancelled {
+        stablecoin_mint: stablecoin_key,
+        certificate_mint: listing.certificate_mint,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct CertificateListed {
+    pub stablecoin_mint: Pubkey,
+    pub certificate_mint: Pubkey,
+    pub auction_program: Pubkey,
+    pub min_proceeds: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CertificateAuctionSettled {
+    pub stablecoin_mint: Pubkey,
+    pub certificate_mint: Pubkey,
+    /// Collateral units paid into the vault
+    pub proceeds: u64,
+    /// Their value in stablecoin units, booked as surplus
+    pub proceeds_value: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CertificateListingCancelled {
+    pub stablecoin_mint: Pubkey,
+    pub certificate_mint: Pubkey,
+    pub timestamp: i64,
+}
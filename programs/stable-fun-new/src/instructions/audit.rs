@@ -0,0 +1,270 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use anchor_spl::token_interface::Mint;
+
+use crate::state::{
+    CollateralRegistry, NttConfig, ProtocolConfig, StablecoinMint, StablecoinVault, PRIMARY_COLLATERAL,
+};
+use crate::constants::{COLLATERAL_REGISTRY_SEED, NTT_CONFIG_SEED, PROTOCOL_CONFIG_SEED, VAULT_SEED};
+use crate::error::StableFunError;
+use crate::utils::math;
+
+/// Vault token balance is below the stablebonds `total_collateral` counts
+pub const COLLATERAL_SHORTFALL: u8 = 1 << 0;
+/// SPL supply plus bridged supply differs from `current_supply`
+pub const SUPPLY_MISMATCH: u8 = 1 << 1;
+/// Stored `current_ratio` is not what the vault totals give
+pub const RATIO_MISMATCH: u8 = 1 << 2;
+
+#[derive(Accounts)]
+pub struct VerifyInvariants<'info> {
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        constraint = vault_stablebond_account.key() == vault.collateral_account @ StableFunError::InvalidVaultAccount
+    )]
+    pub vault_stablebond_account: Box<Account<'info, TokenAccount>>,
+
+    /// Only needed once the coin is registered with NTT
+    #[account(
+        seeds = [NTT_CONFIG_SEED, stablecoin_mint.key().as_ref()],
+        bump = ntt_config.bump
+    )]
+    pub ntt_config: Option<Box<Account<'info, NttConfig>>>,
+
+    /// Only needed once the coin has a collateral registry
+    #[account(
+        seeds = [COLLATERAL_REGISTRY_SEED, stablecoin_mint.key().as_ref()],
+        bump = collateral_registry.bump
+    )]
+    pub collateral_registry: Option<Box<Account<'info, CollateralRegistry>>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct InvariantInputs {
+    pub vault_balance: u64,
+    pub total_collateral: u64,
+    /// Part of `total_collateral` held as stablebonds rather than other
+    /// registered collateral types
+    pub primary_collateral: u64,
+    pub mint_supply: u64,
+    pub bridged_supply: u64,
+    pub current_supply: u64,
+    pub total_value_locked: u64,
+    pub current_ratio: u16,
+    pub ratio_price: u64,
+    pub decimals: u8,
+}
+
+/// Returns the bitmask of violated invariants (0 when consistent).
+pub fn check_invariants(inputs: &InvariantInputs) -> u8 {
+    let mut violations = 0;
+
+    if inputs.vault_balance < inputs.primary_collateral {
+        violations |= COLLATERAL_SHORTFALL;
+    }
+
+    let circulating = inputs.mint_supply as u128 + inputs.bridged_supply as u128;
+    if circulating != inputs.current_supply as u128 {
+        violations |= SUPPLY_MISMATCH;
+    }
+
+    // Same formula as `StablecoinVault::update_collateral_ratio`, at the
+    // price the stored ratio was taken at
+    let expected_ratio = if inputs.ratio_price == 0 {
+        Some(0)
+    } else {
+        math::calculate_collateral_value(inputs.total_collateral, inputs.ratio_price, inputs.decimals)
+            .ok()
+            .map(|value| math::collateral_ratio_bps(value, inputs.total_value_locked).unwrap_or(0))
+            .map(|ratio| u16::try_from(ratio).unwrap_or(u16::MAX))
+    };
+    if expected_ratio != Some(inputs.current_ratio) {
+        violations |= RATIO_MISMATCH;
+    }
+
+    violations
+}
+
+/// Permissionless audit: recomputes the vault and supply books against the
+/// token program and fails, after logging a diagnostic event, on any mismatch.
+pub fn verify_invariants(ctx: Context<VerifyInvariants>) -> Result<()> {
+    let total_collateral = ctx.accounts.vault.total_collateral;
+    let inputs = InvariantInputs {
+        vault_balance: ctx.accounts.vault_stablebond_account.amount,
+        total_collateral,
+        primary_collateral: match ctx.accounts.collateral_registry.as_ref() {
+            Some(registry) => registry.book_value(PRIMARY_COLLATERAL, total_collateral)?,
+            None => total_collateral,
+        },
+        mint_supply: ctx.accounts.token_mint.supply,
+        bridged_supply: ctx.accounts.ntt_config
+            .as_ref()
+            .map_or(0, |config| config.bridged_supply),
+        current_supply: ctx.accounts.stablecoin_mint.current_supply,
+        total_value_locked: ctx.accounts.vault.total_value_locked,
+        current_ratio: ctx.accounts.vault.current_ratio,
+        ratio_price: ctx.accounts.vault.ratio_price,
+        decimals: ctx.accounts.token_mint.decimals,
+    };
+    let violations = check_invariants(&inputs);
+
+    emit!(InvariantReport {
+        stablecoin_mint: ctx.accounts.stablecoin_mint.key(),
+        vault_balance: inputs.vault_balance,
+        total_collateral: inputs.total_collateral,
+        mint_supply: inputs.mint_supply,
+        bridged_supply: inputs.bridged_supply,
+        current_supply: inputs.current_supply,
+        current_ratio: inputs.current_ratio,
+        violations,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    require!(violations == 0, StableFunError::InvariantViolation);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SyncSupply<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Only needed once the coin is registered with NTT
+    #[account(
+        seeds = [NTT_CONFIG_SEED, stablecoin_mint.key().as_ref()],
+        bump = ntt_config.bump
+    )]
+    pub ntt_config: Option<Box<Account<'info, NttConfig>>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+}
+
+/// Resets `current_supply` to what the token program accounts for, after
+/// external burns or accounting bugs have made the two drift apart.
+pub fn sync_supply(ctx: Context<SyncSupply>) -> Result<()> {
+    let bridged_supply = ctx.accounts.ntt_config
+        .as_ref()
+        .map_or(0, |config| config.bridged_supply);
+    let new_supply = StablecoinMint::expected_supply(ctx.accounts.token_mint.supply, bridged_supply)?;
+
+    let clock = Clock::get()?;
+    let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
+    let old_supply = stablecoin_mint.current_supply;
+    stablecoin_mint.current_supply = new_supply;
+    stablecoin_mint.record_admin_action(clock.unix_timestamp);
+
+    emit!(SupplySynced {
+        stablecoin_mint: stablecoin_mint.key(),
+        old_supply,
+        new_supply,
+        bridged_supply,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct SupplySynced {
+    pub stablecoin_mint: Pubkey,
+    pub old_supply: u64,
+    pub new_supply: u64,
+    pub bridged_supply: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InvariantReport {
+    pub stablecoin_mint: Pubkey,
+    pub vault_balance: u64,
+    pub total_collateral: u64,
+    pub mint_supply: u64,
+    pub bridged_supply: u64,
+    pub current_supply: u64,
+    pub current_ratio: u16,
+    /// Bitmask of `COLLATERAL_SHORTFALL`, `SUPPLY_MISMATCH`, `RATIO_MISMATCH`
+    pub violations: u8,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_invariants() {
+        let healthy = InvariantInputs {
+            vault_balance: 1_500,
+            total_collateral: 1_500,
+            primary_collateral: 1_500,
+            mint_supply: 800,
+            bridged_supply: 200,
+            current_supply: 1_000,
+            total_value_locked: 1_000,
+            current_ratio: 15000,
+            ratio_price: 1_000_000,
+            decimals: 6,
+        };
+        assert_eq!(check_invariants(&healthy), 0);
+
+        // Extra tokens sent to the vault are fine, missing ones are not
+        assert_eq!(check_invariants(&InvariantInputs { vault_balance: 2_000, ..healthy }), 0);
+        assert_eq!(
+            check_invariants(&InvariantInputs { vault_balance: 1_499, ..healthy }),
+            COLLATERAL_SHORTFALL
+        );
+        // Collateral rebalanced into other types is not in the stablebond account
+        assert_eq!(
+            check_invariants(&InvariantInputs { vault_balance: 1_000, primary_collateral: 1_000, ..healthy }),
+            0
+        );
+        assert_eq!(
+            check_invariants(&InvariantInputs { bridged_supply: 0, ..healthy }),
+            SUPPLY_MISMATCH
+        );
+        assert_eq!(
+            check_invariants(&InvariantInputs { current_ratio: 6666, ..healthy }),
+            RATIO_MISMATCH
+        );
+        // A different price gives a different ratio for the same books
+        assert_eq!(
+            check_invariants(&InvariantInputs { ratio_price: 800_000, ..healthy }),
+            RATIO_MISMATCH
+        );
+    }
+}
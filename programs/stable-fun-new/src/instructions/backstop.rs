@@ -0,0 +1,669 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{self, TokenInterface};
+use switchboard_solana::AggregatorAccountData;
+
+use crate::state::{
+    Backstop, BackstopProvider, ProtocolConfig, StablecoinMint, StablecoinVault, StateAccount,
+    SystemAccounting, PAUSE_VAULTS,
+};
+use crate::constants::{
+    ACCOUNTING_SEED, BACKSTOP_COLLATERAL_SEED, BACKSTOP_FEE_VAULT_SEED, BACKSTOP_PROVIDER_SEED,
+    BACKSTOP_SEED, MAX_BACKSTOP_FEE_BPS, MINT_AUTHORITY_SEED, PROTOCOL_CONFIG_SEED, VAULT_SEED,
+};
+use crate::error::StableFunError;
+use crate::utils::math;
+use crate::utils::oracle::OracleService;
+use crate::utils::validation::ValidationService;
+
+#[derive(Accounts)]
+pub struct InitBackstop<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Backstop::LEN,
+        seeds = [BACKSTOP_SEED, stablecoin_mint.key().as_ref()],
+        bump
+    )]
+    pub backstop: Box<Account<'info, Backstop>>,
+
+    #[account(
+        constraint = stablebond_mint.key() == stablecoin_mint.stablebond_mint @ StableFunError::InvalidStablebond
+    )]
+    pub stablebond_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [BACKSTOP_COLLATERAL_SEED, stablecoin_mint.key().as_ref()],
+        bump,
+        token::mint = stablebond_mint,
+        token::authority = backstop
+    )]
+    pub collateral_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [BACKSTOP_FEE_VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = backstop,
+        token::token_program = stablecoin_token_program
+    )]
+    pub fee_vault: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_VAULTS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token>,
+    /// Token program owning the stablecoin mint (SPL Token or Token-2022)
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Opens a backstop for the coin. Providers it registers earn
+/// `standing_fee_bps` a year on the collateral they commit.
+pub fn init_backstop(ctx: Context<InitBackstop>, standing_fee_bps: u16) -> Result<()> {
+    require!(standing_fee_bps <= MAX_BACKSTOP_FEE_BPS, StableFunError::FeeTooHigh);
+
+    let now = Clock::get()?.unix_timestamp;
+    let backstop = &mut ctx.accounts.backstop;
+    backstop.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    backstop.collateral_account = ctx.accounts.collateral_account.key();
+    backstop.fee_vault = ctx.accounts.fee_vault.key();
+    backstop.standing_fee_bps = standing_fee_bps;
+    backstop.last_fee_at = now;
+    backstop.bump = ctx.bumps.backstop;
+
+    emit!(BackstopInitialized {
+        stablecoin_mint: backstop.stablecoin_mint,
+        standing_fee_bps,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(provider: Pubkey)]
+pub struct RegisterBackstopProvider<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        seeds = [BACKSTOP_SEED, stablecoin_mint.key().as_ref()],
+        bump = backstop.bump
+    )]
+    pub backstop: Box<Account<'info, Backstop>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = BackstopProvider::LEN,
+        seeds = [BACKSTOP_PROVIDER_SEED, stablecoin_mint.key().as_ref(), provider.as_ref()],
+        bump
+    )]
+    pub backstop_provider: Box<Account<'info, BackstopProvider>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_VAULTS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Designates `provider` as an LP allowed to commit to the backstop.
+pub fn register_backstop_provider(ctx: Context<RegisterBackstopProvider>, provider: Pubkey) -> Result<()> {
+    let backstop_provider = &mut ctx.accounts.backstop_provider;
+    backstop_provider.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    backstop_provider.provider = provider;
+    backstop_provider.fee_per_unit_paid = ctx.accounts.backstop.fee_per_unit;
+    backstop_provider.bump = ctx.bumps.backstop_provider;
+
+    emit!(BackstopProviderRegistered {
+        stablecoin_mint: backstop_provider.stablecoin_mint,
+        provider,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct BackstopOperation<'info> {
+    pub provider: Signer<'info>,
+
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [BACKSTOP_SEED, stablecoin_mint.key().as_ref()],
+        bump = backstop.bump
+    )]
+    pub backstop: Box<Account<'info, Backstop>>,
+
+    #[account(
+        mut,
+        seeds = [BACKSTOP_PROVIDER_SEED, stablecoin_mint.key().as_ref(), provider.key().as_ref()],
+        bump = backstop_provider.bump
+    )]
+    pub backstop_provider: Box<Account<'info, BackstopProvider>>,
+
+    #[account(
+        mut,
+        constraint = collateral_account.key() == backstop.collateral_account @ StableFunError::InvalidTokenAccount
+    )]
+    pub collateral_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = provider_stablebond_account.mint == stablecoin_mint.stablebond_mint @ StableFunError::InvalidStablebond,
+        constraint = provider_stablebond_account.owner == provider.key() @ StableFunError::InvalidStablebond
+    )]
+    pub provider_stablebond_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_VAULTS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Commits `amount` stablebonds; they earn the standing fee from the next
+/// payment on.
+pub fn commit_backstop(ctx: Context<BackstopOperation>, amount: u64) -> Result<()> {
+    require!(amount > 0, StableFunError::InvalidAmount);
+    ctx.accounts.backstop_provider.settle(&ctx.accounts.backstop)?;
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.provider_stablebond_account.to_account_info(),
+                to: ctx.accounts.collateral_account.to_account_info(),
+                authority: ctx.accounts.provider.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let backstop_provider = &mut ctx.accounts.backstop_provider;
+    backstop_provider.committed = backstop_provider.committed
+        .checked_add(amount)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    let backstop = &mut ctx.accounts.backstop;
+    backstop.total_committed = backstop.total_committed
+        .checked_add(amount)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+
+    emit!(BackstopCommitted {
+        stablecoin_mint: backstop.stablecoin_mint,
+        provider: backstop_provider.provider,
+        amount,
+        committed: backstop_provider.committed,
+        total_committed: backstop.total_committed,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Withdraws `amount` of the provider's commitment. Only collateral still in
+/// the backstop can leave; drawn collateral comes back as the vault repays.
+pub fn withdraw_backstop(ctx: Context<BackstopOperation>, amount: u64) -> Result<()> {
+    require!(amount > 0, StableFunError::InvalidAmount);
+    require!(
+        amount <= ctx.accounts.backstop_provider.committed,
+        StableFunError::InsufficientBalance
+    );
+    require!(amount <= ctx.accounts.backstop.liquid(), StableFunError::InsufficientBackstop);
+    ctx.accounts.backstop_provider.settle(&ctx.accounts.backstop)?;
+
+    let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.collateral_account.to_account_info(),
+                to: ctx.accounts.provider_stablebond_account.to_account_info(),
+                authority: ctx.accounts.backstop.to_account_info(),
+            },
+            &[&[BACKSTOP_SEED, stablecoin_key.as_ref(), &[ctx.accounts.backstop.bump]]],
+        ),
+        amount,
+    )?;
+
+    let backstop_provider = &mut ctx.accounts.backstop_provider;
+    backstop_provider.committed -= amount;
+    let backstop = &mut ctx.accounts.backstop;
+    backstop.total_committed -= amount;
+
+    emit!(BackstopWithdrawn {
+        stablecoin_mint: stablecoin_key,
+        provider: backstop_provider.provider,
+        amount,
+        committed: backstop_provider.committed,
+        total_committed: backstop.total_committed,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimBackstopFees<'info> {
+    pub provider: Signer<'info>,
+
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        seeds = [BACKSTOP_SEED, stablecoin_mint.key().as_ref()],
+        bump = backstop.bump
+    )]
+    pub backstop: Box<Account<'info, Backstop>>,
+
+    #[account(
+        mut,
+        seeds = [BACKSTOP_PROVIDER_SEED, stablecoin_mint.key().as_ref(), provider.key().as_ref()],
+        bump = backstop_provider.bump
+    )]
+    pub backstop_provider: Box<Account<'info, BackstopProvider>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        mut,
+        constraint = fee_vault.key() == backstop.fee_vault @ StableFunError::InvalidTokenAccount
+    )]
+    pub fee_vault: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = provider_token_account.mint == token_mint.key() @ StableFunError::InvalidTokenAccount,
+        constraint = provider_token_account.owner == provider.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub provider_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_VAULTS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    /// Token program owning the stablecoin mint (SPL Token or Token-2022)
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Pays out every standing fee the provider has earned.
+pub fn claim_backstop_fees(ctx: Context<ClaimBackstopFees>) -> Result<()> {
+    let backstop = &ctx.accounts.backstop;
+    let backstop_provider = &mut ctx.accounts.backstop_provider;
+    backstop_provider.settle(backstop)?;
+    let amount = std::mem::take(&mut backstop_provider.accrued_fees);
+    require!(amount > 0, StableFunError::InvalidAmount);
+
+    let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.stablecoin_token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.fee_vault.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.provider_token_account.to_account_info(),
+                authority: backstop.to_account_info(),
+            },
+            &[&[BACKSTOP_SEED, stablecoin_key.as_ref(), &[backstop.bump]]],
+        ),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    emit!(BackstopFeesClaimed {
+        stablecoin_mint: stablecoin_key,
+        provider: ctx.accounts.provider.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct PayBackstopFee<'info> {
+    #[account(mut)]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [BACKSTOP_SEED, stablecoin_mint.key().as_ref()],
+        bump = backstop.bump
+    )]
+    pub backstop: Box<Account<'info, Backstop>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        mut,
+        seeds = [ACCOUNTING_SEED, stablecoin_mint.key().as_ref()],
+        bump = accounting.bump
+    )]
+    pub accounting: Box<Account<'info, SystemAccounting>>,
+
+    #[account(
+        mut,
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        mut,
+        constraint = fee_vault.key() == backstop.fee_vault @ StableFunError::InvalidTokenAccount
+    )]
+    pub fee_vault: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// The Switchboard V3 aggregator account
+    #[account(
+        constraint = price_feed.key() == stablecoin_mint.price_feed @ StableFunError::InvalidOracle
+    )]
+    pub price_feed: AccountLoader<'info, AggregatorAccountData>,
+
+    /// FX feed composed with the bond NAV `price_feed`, on coins that set one
+    #[account(
+        constraint = fx_feed.key() == stablecoin_mint.fx_feed @ StableFunError::InvalidOracle
+    )]
+    pub fx_feed: Option<AccountLoader<'info, AggregatorAccountData>>,
+
+    /// CHECK: PDA used as mint authority
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, stablecoin_mint.key().as_ref()],
+        bump = stablecoin_mint.mint_authority_bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_VAULTS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    /// Token program owning the stablecoin mint (SPL Token or Token-2022)
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Permissionless: pays providers the standing fee accrued on the value of
+/// the committed collateral since the last payment. Like staking rewards,
+/// the coins are minted against surplus, within the issuer's surplus buffer
+/// and the supply cap.
+pub fn pay_backstop_fee(ctx: Context<PayBackstopFee>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let total_committed = ctx.accounts.backstop.total_committed;
+    let owed = if total_committed > 0 {
+        let oracle_price = OracleService::collateral_price(
+            &ctx.accounts.stablecoin_mint,
+            &ctx.accounts.price_feed,
+            ctx.accounts.fx_feed.as_ref(),
+        )?;
+        let committed_value = math::calculate_collateral_value(
+            total_committed,
+            oracle_price,
+            ctx.accounts.token_mint.decimals,
+        )?;
+        ctx.accounts.backstop.standing_fee(committed_value, now)
+    } else {
+        0
+    };
+
+    let stablecoin_mint = &ctx.accounts.stablecoin_mint;
+    let max_mintable = stablecoin_mint.settings.max_supply.saturating_sub(stablecoin_mint.current_supply);
+    let available = ctx.accounts.accounting.withdrawable(stablecoin_mint.settings.surplus_buffer);
+    let amount = owed.min(available).min(stablecoin_mint.to_underlying_amount(max_mintable)?);
+    let raw_amount = stablecoin_mint.to_raw_amount(amount, false)?;
+
+    let stablecoin_key = stablecoin_mint.key();
+    if raw_amount > 0 {
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.stablecoin_token_program.to_account_info(),
+                token_interface::MintTo {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.fee_vault.to_account_info(),
+                    authority: ctx.accounts.mint_authority.to_account_info(),
+                },
+                &[&[
+                    MINT_AUTHORITY_SEED,
+                    stablecoin_key.as_ref(),
+                    &[stablecoin_mint.mint_authority_bump],
+                ]],
+            ),
+            raw_amount,
+        )?;
+
+        ctx.accounts.accounting.surplus -= amount;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.total_value_locked = vault
+            .total_value_locked
+            .checked_add(amount)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        // Re-rate at the last ratio price, as other surplus mints do
+        let ratio_price = vault.ratio_price;
+        ValidationService::update_collateral_ratio(vault, ratio_price, ctx.accounts.token_mint.decimals)?;
+
+        let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
+        stablecoin_mint.current_supply += raw_amount;
+        stablecoin_mint.last_updated = now;
+
+        ctx.accounts.backstop.distribute(raw_amount)?;
+    }
+
+    // A fee surplus could not cover is forfeited rather than carried over
+    ctx.accounts.backstop.last_fee_at = now;
+
+    emit!(BackstopFeePaid {
+        stablecoin_mint: stablecoin_key,
+        owed,
+        amount: raw_amount,
+        fee_per_unit: ctx.accounts.backstop.fee_per_unit,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RepayBackstop<'info> {
+    pub caller: Signer<'info>,
+
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        mut,
+        constraint = vault_stablebond_account.key() == vault.collateral_account @ StableFunError::InvalidVaultAccount
+    )]
+    pub vault_stablebond_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [BACKSTOP_SEED, stablecoin_mint.key().as_ref()],
+        bump = backstop.bump
+    )]
+    pub backstop: Box<Account<'info, Backstop>>,
+
+    #[account(
+        mut,
+        constraint = collateral_account.key() == backstop.collateral_account @ StableFunError::InvalidTokenAccount
+    )]
+    pub collateral_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_VAULTS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Permissionless: repays the backstop out of vault liquidity above the
+/// coin's buffer, as collateral sales such as certificate auctions bring it
+/// back.
+pub fn repay_backstop(ctx: Context<RepayBackstop>) -> Result<()> {
+    let amount = ctx.accounts.vault.backstop_repayable(
+        ctx.accounts.vault_stablebond_account.amount,
+        ctx.accounts.stablecoin_mint.settings.liquidity_buffer_bps,
+    );
+    require!(amount > 0, StableFunError::InvalidAmount);
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.vault_stablebond_account.to_account_info(),
+                to: ctx.accounts.collateral_account.to_account_info(),
+                authority: ctx.accounts.vault.to_account_info(),
+            },
+            &[&ctx.accounts.vault.signer_seeds()],
+        ),
+        amount,
+    )?;
+
+    ctx.accounts.vault.backstop_owed -= amount;
+    let repaid = ctx.accounts.backstop.repay(amount);
+
+    emit!(BackstopRepaid {
+        stablecoin_mint: ctx.accounts.stablecoin_mint.key(),
+        amount: repaid,
+        drawn: ctx.accounts.backstop.drawn,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct BackstopInitialized {
+    pub stablecoin_mint: Pubkey,
+    pub standing_fee_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BackstopProviderRegistered {
+    pub stablecoin_mint: Pubkey,
+    pub provider: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BackstopCommitted {
+    pub stablecoin_mint: Pubkey,
+    pub provider: Pubkey,
+    pub amount: u64,
+    pub committed: u64,
+    pub total_committed: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BackstopWithdrawn {
+    pub stablecoin_mint: Pubkey,
+    pub provider: Pubkey,
+    pub amount: u64,
+    pub committed: u64,
+    pub total_committed: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BackstopFeesClaimed {
+    pub stablecoin_mint: Pubkey,
+    pub provider: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BackstopFeePaid {
+    pub stablecoin_mint: Pubkey,
+    /// Fee accrued since the last payment, in underlying units
+    pub owed: u64,
+    /// Raw stablecoin units paid into the fee vault
+    pub amount: u64,
+    pub fee_per_unit: u128,
+    pub timestamp: i64,
+}
+
+/// An instant redeem was paid from the backstop.
+#[event]
+pub struct BackstopDrawn {
+    pub stablecoin_mint: Pubkey,
+    pub user: Pubkey,
+    pub collateral_amount: u64,
+    pub drawn: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BackstopRepaid {
+    pub stablecoin_mint: Pubkey,
+    pub amount: u64,
+    pub drawn: u64,
+    pub timestamp: i64,
+}
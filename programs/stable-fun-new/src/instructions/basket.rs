@@ -0,0 +1,253 @@
+use anchor_lang::prelude::*;
+use switchboard_solana::AggregatorAccountData;
+
+use crate::state::{BasketComponent, CurrencyBasket, ProtocolConfig, StablecoinMint, StateAccount};
+use crate::constants::{BASKET_REBALANCE_TIMELOCK, BASKET_SEED, PRICE_SCALE, PROTOCOL_CONFIG_SEED};
+use crate::error::StableFunError;
+use crate::utils::currency::normalize_currency;
+use crate::utils::oracle::OracleService;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct BasketComponentParams {
+    pub currency: String,
+    pub weight_bps: u16,
+    /// FX feed for `currency`; default pubkey for the collateral feed's quote currency
+    pub fx_feed: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct CreateBasket<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint,
+        constraint = stablecoin_mint.basket == Pubkey::default() @ StableFunError::InvalidBasket
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = CurrencyBasket::LEN,
+        seeds = [BASKET_SEED, stablecoin_mint.key().as_ref()],
+        bump
+    )]
+    pub basket: Box<Account<'info, CurrencyBasket>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Pegs the stablecoin to a weighted currency basket. Mint and redeem then
+/// price collateral in basket units once `update_basket_index` has run.
+pub fn create_basket(ctx: Context<CreateBasket>, components: Vec<BasketComponentParams>) -> Result<()> {
+    let weights: Vec<u16> = components.iter().map(|component| component.weight_bps).collect();
+    CurrencyBasket::validate_weights(&weights)?;
+
+    let mut basket_components = Vec::with_capacity(components.len());
+    for component in components {
+        let currency = normalize_currency(&component.currency)?;
+        let currency: [u8; 3] = currency.as_bytes().try_into()
+            .map_err(|_| error!(StableFunError::InvalidCurrency))?;
+        require!(
+            basket_components.iter().all(|existing: &BasketComponent| existing.currency != currency),
+            StableFunError::InvalidBasket
+        );
+        basket_components.push(BasketComponent {
+            currency,
+            weight_bps: component.weight_bps,
+            fx_feed: component.fx_feed,
+        });
+    }
+
+    let basket = &mut ctx.accounts.basket;
+    basket.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    basket.components = basket_components;
+    basket.bump = ctx.bumps.basket;
+
+    let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
+    stablecoin_mint.basket = basket.key();
+    stablecoin_mint.last_updated = Clock::get()?.unix_timestamp;
+
+    emit!(BasketCreated {
+        stablecoin_mint: stablecoin_mint.key(),
+        basket: basket.key(),
+        components: basket.components.len() as u8,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateBasketIndex<'info> {
+    pub caller: Signer<'info>,
+
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [BASKET_SEED, stablecoin_mint.key().as_ref()],
+        bump = basket.bump
+    )]
+    pub basket: Box<Account<'info, CurrencyBasket>>,
+    // remaining_accounts: the FX feed of every component that has one, in component order
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+}
+
+/// Permissionless crank: recomputes the basket index from each component's
+/// FX feed. Mint and redeem refuse an index older than `MAX_PRICE_STALENESS`.
+pub fn update_basket_index<'info>(
+    ctx: Context<'_, '_, 'info, 'info, UpdateBasketIndex<'info>>,
+) -> Result<()> {
+    let mut feeds = ctx.remaining_accounts.iter();
+    let mut fx_prices = Vec::with_capacity(ctx.accounts.basket.components.len());
+    for component in &ctx.accounts.basket.components {
+        if component.fx_feed == Pubkey::default() {
+            fx_prices.push(PRICE_SCALE);
+            continue;
+        }
+        let feed = feeds.next().ok_or(StableFunError::InvalidOracle)?;
+        require_keys_eq!(feed.key(), component.fx_feed, StableFunError::InvalidOracle);
+        let feed = AccountLoader::<AggregatorAccountData>::try_from(feed)?;
+        fx_prices.push(OracleService::verify_oracle_price(&feed)?);
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let basket = &mut ctx.accounts.basket;
+    basket.index_price = basket.compute_index(&fx_prices)?;
+    basket.index_updated_at = now;
+
+    emit!(BasketIndexUpdated {
+        stablecoin_mint: basket.stablecoin_mint,
+        index_price: basket.index_price,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ProposeBasketWeights<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [BASKET_SEED, stablecoin_mint.key().as_ref()],
+        bump = basket.bump
+    )]
+    pub basket: Box<Account<'info, CurrencyBasket>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+}
+
+/// Queues new component weights, applicable after `BASKET_REBALANCE_TIMELOCK`
+/// so holders can exit before the peg they hold changes. Replaces any
+/// previously queued proposal and restarts the timelock.
+pub fn propose_basket_weights(ctx: Context<ProposeBasketWeights>, weights: Vec<u16>) -> Result<()> {
+    let basket = &mut ctx.accounts.basket;
+    require!(weights.len() == basket.components.len(), StableFunError::InvalidBasket);
+    CurrencyBasket::validate_weights(&weights)?;
+
+    let effective_at = Clock::get()?.unix_timestamp
+        .checked_add(BASKET_REBALANCE_TIMELOCK)
+        .ok_or(StableFunError::MathOverflow)?;
+    basket.pending_weights = weights.clone();
+    basket.pending_effective_at = effective_at;
+
+    emit!(BasketRebalanceProposed {
+        stablecoin_mint: basket.stablecoin_mint,
+        weights,
+        effective_at,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ApplyBasketWeights<'info> {
+    pub caller: Signer<'info>,
+
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [BASKET_SEED, stablecoin_mint.key().as_ref()],
+        bump = basket.bump
+    )]
+    pub basket: Box<Account<'info, CurrencyBasket>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+}
+
+/// Permissionless once the timelock has elapsed. Pauses basket pricing until
+/// the next `update_basket_index` so no operation mixes old and new weights.
+pub fn apply_basket_weights(ctx: Context<ApplyBasketWeights>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let basket = &mut ctx.accounts.basket;
+    basket.apply_pending_weights(now)?;
+
+    emit!(BasketRebalanced {
+        stablecoin_mint: basket.stablecoin_mint,
+        weights: basket.components.iter().map(|component| component.weight_bps).collect(),
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct BasketCreated {
+    pub stablecoin_mint: Pubkey,
+    pub basket: Pubkey,
+    pub components: u8,
+}
+
+#[event]
+pub struct BasketIndexUpdated {
+    pub stablecoin_mint: Pubkey,
+    pub index_price: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BasketRebalanceProposed {
+    pub stablecoin_mint: Pubkey,
+    pub weights: Vec<u16>,
+    pub effective_at: i64,
+}
+
+#[event]
+pub struct BasketRebalanced {
+    pub stablecoin_mint: Pubkey,
+    pub weights: Vec<u16>,
+    pub timestamp: i64,
+}
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::state::{BondTerms, ProtocolConfig, StablecoinMint, StateAccount};
+use crate::constants::{BOND_TERMS_SEED, PROTOCOL_CONFIG_SEED};
+use crate::error::StableFunError;
+
+#[derive(Accounts)]
+pub struct SetBondTerms<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = BondTerms::LEN,
+        seeds = [BOND_TERMS_SEED, stablecoin_mint.key().as_ref()],
+        bump,
+        constraint = !bond_terms.is_set_for(&stablebond_mint.key()) @ StableFunError::BondTermsAlreadySet
+    )]
+    pub bond_terms: Box<Account<'info, BondTerms>>,
+
+    #[account(
+        constraint = stablebond_mint.key() == stablecoin_mint.stablebond_mint @ StableFunError::InvalidStablebond
+    )]
+    pub stablebond_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Records the yield terms of the coin's current stablebond. Terms are
+/// fixed once set; after a collateral migration they are set afresh for
+/// the new bond.
+pub fn set_bond_terms(
+    ctx: Context<SetBondTerms>,
+    annual_yield: u64,
+    issued_at: i64,
+    maturity_timestamp: i64,
+) -> Result<()> {
+    BondTerms::validate(annual_yield, issued_at, maturity_timestamp)?;
+
+    let bond_terms = &mut ctx.accounts.bond_terms;
+    bond_terms.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    bond_terms.stablebond_mint = ctx.accounts.stablebond_mint.key();
+    bond_terms.annual_yield = annual_yield;
+    bond_terms.issued_at = issued_at;
+    bond_terms.maturity_timestamp = maturity_timestamp;
+    bond_terms.decimals = ctx.accounts.stablebond_mint.decimals;
+    bond_terms.bump = ctx.bumps.bond_terms;
+
+    emit!(BondTermsSet {
+        stablecoin_mint: bond_terms.stablecoin_mint,
+        stablebond_mint: bond_terms.stablebond_mint,
+        annual_yield,
+        issued_at,
+        maturity_timestamp,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct BondTermsSet {
+    pub stablecoin_mint: Pubkey,
+    pub stablebond_mint: Pubkey,
+    pub annual_yield: u64,
+    pub issued_at: i64,
+    pub maturity_timestamp: i64,
+    pub timestamp: i64,
+}
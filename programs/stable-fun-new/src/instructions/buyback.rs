@@ -0,0 +1,355 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token::{self, Token, TokenAccount};
+use anchor_spl::token_interface::{self, TokenInterface};
+use switchboard_solana::AggregatorAccountData;
+
+use crate::state::{
+    BuybackConfig, ProtocolConfig, StablecoinMint, StablecoinVault, StateAccount, SystemAccounting,
+};
+use crate::constants::{
+    ACCOUNTING_SEED, BUYBACK_COLLATERAL_SEED, BUYBACK_MAX_PRICE_BPS, BUYBACK_SEED,
+    BUYBACK_TOKEN_SEED, PROTOCOL_CONFIG_SEED, VAULT_SEED,
+};
+use crate::error::StableFunError;
+use crate::utils::oracle::OracleService;
+use crate::utils::validation::ValidationService;
+use crate::utils::math;
+
+#[derive(Accounts)]
+pub struct ConfigureBuyback<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = BuybackConfig::LEN,
+        seeds = [BUYBACK_SEED, stablecoin_mint.key().as_ref()],
+        bump
+    )]
+    pub buyback_config: Box<Account<'info, BuybackConfig>>,
+
+    #[account(
+        constraint = stablebond_mint.key() == stablecoin_mint.stablebond_mint @ StableFunError::InvalidStablebond
+    )]
+    pub stablebond_mint: Box<Account<'info, token::Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [BUYBACK_COLLATERAL_SEED, stablecoin_mint.key().as_ref()],
+        bump,
+        token::mint = stablebond_mint,
+        token::authority = buyback_config
+    )]
+    pub buyback_collateral_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [BUYBACK_TOKEN_SEED, stablecoin_mint.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = buyback_config,
+        token::token_program = stablecoin_token_program
+    )]
+    pub buyback_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token>,
+    /// Token program owning the stablecoin mint (SPL Token or Token-2022)
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Points buybacks at `amm_program`; `max_price_bps` may only tighten the
+/// protocol band of `BUYBACK_MAX_PRICE_BPS`.
+pub fn configure_buyback(
+    ctx: Context<ConfigureBuyback>,
+    amm_program: Pubkey,
+    max_price_bps: u16,
+) -> Result<()> {
+    require!(
+        amm_program != Pubkey::default() && max_price_bps > 0 && max_price_bps <= BUYBACK_MAX_PRICE_BPS,
+        StableFunError::InvalidBuybackConfig
+    );
+
+    let config = &mut ctx.accounts.buyback_config;
+    config.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    config.amm_program = amm_program;
+    config.max_price_bps = max_price_bps;
+    config.collateral_account = ctx.accounts.buyback_collateral_account.key();
+    config.token_account = ctx.accounts.buyback_token_account.key();
+    config.bump = ctx.bumps.buyback_config;
+
+    emit!(BuybackConfigured {
+        stablecoin_mint: config.stablecoin_mint,
+        amm_program,
+        max_price_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExecuteBuyback<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        mut,
+        seeds = [ACCOUNTING_SEED, stablecoin_mint.key().as_ref()],
+        bump = accounting.bump
+    )]
+    pub accounting: Box<Account<'info, SystemAccounting>>,
+
+    #[account(
+        mut,
+        seeds = [BUYBACK_SEED, stablecoin_mint.key().as_ref()],
+        bump = buyback_config.bump
+    )]
+    pub buyback_config: Box<Account<'info, BuybackConfig>>,
+
+    #[account(
+        mut,
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        mut,
+        constraint = vault_stablebond_account.key() == vault.collateral_account @ StableFunError::InvalidVaultAccount
+    )]
+    pub vault_stablebond_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = buyback_collateral_account.key() == buyback_config.collateral_account @ StableFunError::InvalidTokenAccount
+    )]
+    pub buyback_collateral_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = buyback_token_account.key() == buyback_config.token_account @ StableFunError::InvalidTokenAccount
+    )]
+    pub buyback_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// The Switchboard V3 aggregator account
+    #[account(
+        constraint = price_feed.key() == stablecoin_mint.price_feed @ StableFunError::InvalidOracle
+    )]
+    pub price_feed: AccountLoader<'info, AggregatorAccountData>,
+
+    /// FX feed composed with the bond NAV `price_feed`, on coins that set one
+    #[account(
+        constraint = fx_feed.key() == stablecoin_mint.fx_feed @ StableFunError::InvalidOracle
+    )]
+    pub fx_feed: Option<AccountLoader<'info, AggregatorAccountData>>,
+
+    /// CHECK: Must be the AMM program fixed in the buyback config
+    #[account(
+        executable,
+        constraint = amm_program.key() == buyback_config.amm_program @ StableFunError::InvalidBuybackConfig
+    )]
+    pub amm_program: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token>,
+    /// Token program owning the stablecoin mint (SPL Token or Token-2022)
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Spends `amount` of surplus (stablecoin units) buying the coin back on the
+/// configured AMM and burns what it receives. `swap_data` and the remaining
+/// accounts form the AMM swap instruction, signed by the buyback PDA; the
+/// whole buyback fails unless the coins were bought within the price band.
+pub fn execute_buyback<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteBuyback<'info>>,
+    amount: u64,
+    swap_data: Vec<u8>,
+) -> Result<()> {
+    require!(amount > 0, StableFunError::InvalidAmount);
+
+    ctx.accounts.accounting.withdraw_fees(
+        amount,
+        ctx.accounts.stablecoin_mint.settings.surplus_buffer,
+    )?;
+
+    let oracle_price = OracleService::collateral_price(
+        &ctx.accounts.stablecoin_mint,
+        &ctx.accounts.price_feed,
+        ctx.accounts.fx_feed.as_ref(),
+    )?;
+    let decimals = ctx.accounts.token_mint.decimals;
+    let collateral_amount = math::calculate_token_amount(amount, oracle_price, decimals)?;
+
+    // Move the collateral out of the vault into the buyback escrow
+    let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+    let escrow_before = ctx.accounts.buyback_collateral_account.amount;
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.vault_stablebond_account.to_account_info(),
+                to: ctx.accounts.buyback_collateral_account.to_account_info(),
+                authority: ctx.accounts.vault.to_account_info(),
+            },
+            &[&ctx.accounts.vault.signer_seeds()],
+        ),
+        collateral_amount,
+    )?;
+
+    // Swap on the AMM, signed by the buyback PDA
+    let buyback_key = ctx.accounts.buyback_config.key();
+    let tokens_before = ctx.accounts.buyback_token_account.amount;
+    let swap_ix = Instruction {
+        program_id: ctx.accounts.amm_program.key(),
+        accounts: ctx.remaining_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer || account.key() == buyback_key,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data: swap_data,
+    };
+    invoke_signed(
+        &swap_ix,
+        ctx.remaining_accounts,
+        &[&[
+            BUYBACK_SEED,
+            stablecoin_key.as_ref(),
+            &[ctx.accounts.buyback_config.bump],
+        ]],
+    )?;
+
+    ctx.accounts.buyback_collateral_account.reload()?;
+    ctx.accounts.buyback_token_account.reload()?;
+    require!(
+        ctx.accounts.buyback_collateral_account.amount == escrow_before,
+        StableFunError::BuybackSwapMismatch
+    );
+    let received = ctx.accounts.buyback_token_account.amount.saturating_sub(tokens_before);
+    require!(
+        BuybackConfig::within_band(amount, received, ctx.accounts.buyback_config.max_price_bps),
+        StableFunError::BuybackPriceAboveBand
+    );
+
+    token_interface::burn(
+        CpiContext::new_with_signer(
+            ctx.accounts.stablecoin_token_program.to_account_info(),
+            token_interface::Burn {
+                mint: ctx.accounts.token_mint.to_account_info(),
+                from: ctx.accounts.buyback_token_account.to_account_info(),
+                authority: ctx.accounts.buyback_config.to_account_info(),
+            },
+            &[&[
+                BUYBACK_SEED,
+                stablecoin_key.as_ref(),
+                &[ctx.accounts.buyback_config.bump],
+            ]],
+        ),
+        received,
+    )?;
+
+    let clock = Clock::get()?;
+    let vault = &mut ctx.accounts.vault;
+    vault.total_collateral = vault
+        .total_collateral
+        .checked_sub(collateral_amount)
+        .ok_or(error!(StableFunError::InsufficientCollateral))?;
+    vault.total_value_locked = vault.total_value_locked.saturating_sub(received);
+    vault.withdrawal_count = vault
+        .withdrawal_count
+        .checked_add(1)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    vault.last_withdrawal_time = clock.unix_timestamp;
+    ValidationService::update_collateral_ratio(vault, oracle_price, decimals)?;
+
+    let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
+    stablecoin_mint.current_supply = stablecoin_mint
+        .current_supply
+        .checked_sub(received)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    stablecoin_mint.stats.total_burned = stablecoin_mint
+        .stats
+        .total_burned
+        .checked_add(received)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    stablecoin_mint.last_updated = clock.unix_timestamp;
+
+    let config = &mut ctx.accounts.buyback_config;
+    config.total_spent = config.total_spent
+        .checked_add(amount)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    config.total_burned = config.total_burned
+        .checked_add(received)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+
+    emit!(BuybackExecuted {
+        stablecoin_mint: stablecoin_key,
+        spent: amount,
+        collateral_amount,
+        burned: received,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct BuybackConfigured {
+    pub stablecoin_mint: Pubkey,
+    pub amm_program: Pubkey,
+    pub max_price_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BuybackExecuted {
+    pub stablecoin_mint: Pubkey,
+    /// Surplus spent, in stablecoin units
+    pub spent: u64,
+    pub collateral_amount: u64,
+    pub burned: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+use anchor_spl::token_interface::Mint;
+
+use crate::state::{ProtocolConfig, StablecoinMint, StablecoinVault};
+use crate::constants::{PROTOCOL_CONFIG_SEED, VAULT_SEED};
+use crate::error::StableFunError;
+
+#[derive(Accounts)]
+pub struct CloseStablecoin<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        close = authority,
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint,
+        constraint = stablecoin_mint.current_supply == 0 @ StableFunError::StablecoinNotEmpty
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault,
+        constraint = vault.total_collateral == 0 @ StableFunError::StablecoinNotEmpty
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        mut,
+        constraint = vault_token_account.key() == vault.collateral_account @ StableFunError::InvalidVaultAccount,
+        constraint = vault_token_account.amount == 0 @ StableFunError::StablecoinNotEmpty
+    )]
+    pub vault_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint,
+        constraint = token_mint.supply == 0 @ StableFunError::StablecoinNotEmpty
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Closes a coin with nothing outstanding: its collateral account, vault and
+/// stablecoin account go back to the authority, refunding the launch deposit
+/// along with their rent.
+pub fn close_stablecoin(ctx: Context<CloseStablecoin>) -> Result<()> {
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token::CloseAccount {
+            account: ctx.accounts.vault_token_account.to_account_info(),
+            destination: ctx.accounts.authority.to_account_info(),
+            authority: ctx.accounts.vault.to_account_info(),
+        },
+        &[&ctx.accounts.vault.signer_seeds()],
+    ))?;
+
+    emit!(StablecoinClosed {
+        stablecoin_mint: ctx.accounts.stablecoin_mint.key(),
+        authority: ctx.accounts.authority.key(),
+        refunded_deposit: ctx.accounts.stablecoin_mint.launch_deposit,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct StablecoinClosed {
+    pub stablecoin_mint: Pubkey,
+    pub authority: Pubkey,
+    pub refunded_deposit: u64,
+    pub timestamp: i64,
+}
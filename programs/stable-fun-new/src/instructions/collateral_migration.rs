@@ -0,0 +1,407 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Token, TokenAccount};
+use anchor_spl::token_interface;
+
+use crate::state::{
+    CollateralMigration, ProtocolConfig, StablecoinMint, StablecoinVault, StateAccount,
+};
+use crate::constants::{
+    BASIS_POINTS_DIVISOR, COLLATERAL_MIGRATION_ESCROW_SEED, COLLATERAL_MIGRATION_SEED,
+    PROTOCOL_CONFIG_SEED, VAULT_SEED,
+};
+use crate::error::StableFunError;
+use crate::utils::validation::ValidationService;
+
+#[derive(Accounts)]
+pub struct BeginCollateralMigration<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = CollateralMigration::LEN,
+        seeds = [COLLATERAL_MIGRATION_SEED, stablecoin_mint.key().as_ref()],
+        bump
+    )]
+    pub migration: Box<Account<'info, CollateralMigration>>,
+
+    #[account(
+        constraint = stablebond_mint.key() == stablecoin_mint.stablebond_mint @ StableFunError::InvalidStablebond
+    )]
+    pub stablebond_mint: Box<Account<'info, token::Mint>>,
+
+    #[account(
+        constraint = new_stablebond_mint.key() != stablecoin_mint.stablebond_mint @ StableFunError::InvalidCollateralMigration
+    )]
+    pub new_stablebond_mint: Box<Account<'info, token::Mint>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [COLLATERAL_MIGRATION_ESCROW_SEED, stablecoin_mint.key().as_ref()],
+        bump,
+        token::mint = stablebond_mint,
+        token::authority = migration
+    )]
+    pub escrow_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = new_stablebond_mint,
+        associated_token::authority = vault,
+    )]
+    pub new_collateral_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Registers the stablebond series the collateral moves to. Old bonds are
+/// then rolled over in batches through `rollover_program`, each batch
+/// returning at least `min_out_bps` new bonds per old one.
+pub fn begin_collateral_migration(
+    ctx: Context<BeginCollateralMigration>,
+    rollover_program: Pubkey,
+    min_out_bps: u16,
+) -> Result<()> {
+    require!(
+        rollover_program != Pubkey::default() && min_out_bps > 0,
+        StableFunError::InvalidCollateralMigration
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let migration = &mut ctx.accounts.migration;
+    migration.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    migration.new_stablebond_mint = ctx.accounts.new_stablebond_mint.key();
+    migration.new_collateral_account = ctx.accounts.new_collateral_account.key();
+    migration.escrow_account = ctx.accounts.escrow_account.key();
+    migration.rollover_program = rollover_program;
+    migration.min_out_bps = min_out_bps;
+    migration.started_at = now;
+    migration.bump = ctx.bumps.migration;
+
+    emit!(CollateralMigrationStarted {
+        stablecoin_mint: migration.stablecoin_mint,
+        old_stablebond_mint: ctx.accounts.stablecoin_mint.stablebond_mint,
+        new_stablebond_mint: migration.new_stablebond_mint,
+        rollover_program,
+        min_out_bps,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateCollateral<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        mut,
+        seeds = [COLLATERAL_MIGRATION_SEED, stablecoin_mint.key().as_ref()],
+        bump = migration.bump
+    )]
+    pub migration: Box<Account<'info, CollateralMigration>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        mut,
+        constraint = vault_stablebond_account.key() == vault.collateral_account @ StableFunError::InvalidVaultAccount
+    )]
+    pub vault_stablebond_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = escrow_account.key() == migration.escrow_account @ StableFunError::InvalidTokenAccount
+    )]
+    pub escrow_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = new_collateral_account.key() == migration.new_collateral_account @ StableFunError::InvalidVaultAccount
+    )]
+    pub new_collateral_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Must be the rollover program fixed when the migration began
+    #[account(
+        executable,
+        constraint = rollover_program.key() == migration.rollover_program @ StableFunError::InvalidCollateralMigration
+    )]
+    pub rollover_program: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Rolls `amount` old bonds over into the new series. `rollover_data` and
+/// the remaining accounts form the rollover instruction, signed by the
+/// migration PDA; it must spend the whole escrowed batch and pay the new
+/// bonds into the vault's new collateral account.
+pub fn migrate_collateral<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MigrateCollateral<'info>>,
+    amount: u64,
+    rollover_data: Vec<u8>,
+) -> Result<()> {
+    require!(amount > 0, StableFunError::InvalidAmount);
+
+    // Move the batch out of the vault into the migration escrow
+    let escrow_before = ctx.accounts.escrow_account.amount;
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.vault_stablebond_account.to_account_info(),
+                to: ctx.accounts.escrow_account.to_account_info(),
+                authority: ctx.accounts.vault.to_account_info(),
+            },
+            &[&ctx.accounts.vault.signer_seeds()],
+        ),
+        amount,
+    )?;
+
+    // Roll over, signed by the migration PDA
+    let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+    let migration_key = ctx.accounts.migration.key();
+    let received_before = ctx.accounts.new_collateral_account.amount;
+    let rollover_ix = Instruction {
+        program_id: ctx.accounts.rollover_program.key(),
+        accounts: ctx.remaining_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer || account.key() == migration_key,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data: rollover_data,
+    };
+    invoke_signed(
+        &rollover_ix,
+        ctx.remaining_accounts,
+        &[&[
+            COLLATERAL_MIGRATION_SEED,
+            stablecoin_key.as_ref(),
+            &[ctx.accounts.migration.bump],
+        ]],
+    )?;
+
+    ctx.accounts.escrow_account.reload()?;
+    ctx.accounts.new_collateral_account.reload()?;
+    require!(
+        ctx.accounts.escrow_account.amount == escrow_before,
+        StableFunError::InvalidCollateralMigration
+    );
+    let received = ctx.accounts.new_collateral_account.amount.saturating_sub(received_before);
+    require!(
+        CollateralMigration::within_min_out(amount, received, ctx.accounts.migration.min_out_bps),
+        StableFunError::RolloverBelowMinimum
+    );
+
+    // Both series count as collateral until the migration completes
+    let vault = &mut ctx.accounts.vault;
+    vault.total_collateral = vault
+        .total_collateral
+        .checked_sub(amount)
+        .and_then(|collateral| collateral.checked_add(received))
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    // No fresh price here; re-rate at the last one
+    let ratio_price = vault.ratio_price;
+    ValidationService::update_collateral_ratio(vault, ratio_price, ctx.accounts.token_mint.decimals)?;
+
+    let migration = &mut ctx.accounts.migration;
+    migration.total_rolled = migration.total_rolled
+        .checked_add(amount)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    migration.total_received = migration.total_received
+        .checked_add(received)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+
+    emit!(CollateralRolledOver {
+        stablecoin_mint: stablecoin_key,
+        rolled: amount,
+        received,
+        rate_bps: (received as u128 * BASIS_POINTS_DIVISOR as u128 / amount as u128) as u64,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CompleteCollateralMigration<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [COLLATERAL_MIGRATION_SEED, stablecoin_mint.key().as_ref()],
+        bump = migration.bump
+    )]
+    pub migration: Box<Account<'info, CollateralMigration>>,
+
+    #[account(
+        mut,
+        constraint = vault_stablebond_account.key() == vault.collateral_account @ StableFunError::InvalidVaultAccount,
+        constraint = vault_stablebond_account.amount == 0 @ StableFunError::CollateralMigrationIncomplete
+    )]
+    pub vault_stablebond_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = escrow_account.key() == migration.escrow_account @ StableFunError::InvalidTokenAccount
+    )]
+    pub escrow_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Once every old bond has been rolled over, points the coin at the new
+/// series and closes the old collateral account, the escrow and the
+/// migration, returning their rent to the authority.
+pub fn complete_collateral_migration(ctx: Context<CompleteCollateralMigration>) -> Result<()> {
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token::CloseAccount {
+            account: ctx.accounts.vault_stablebond_account.to_account_info(),
+            destination: ctx.accounts.authority.to_account_info(),
+            authority: ctx.accounts.vault.to_account_info(),
+        },
+        &[&ctx.accounts.vault.signer_seeds()],
+    ))?;
+
+    let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token::CloseAccount {
+            account: ctx.accounts.escrow_account.to_account_info(),
+            destination: ctx.accounts.authority.to_account_info(),
+            authority: ctx.accounts.migration.to_account_info(),
+        },
+        &[&[
+            COLLATERAL_MIGRATION_SEED,
+            stablecoin_key.as_ref(),
+            &[ctx.accounts.migration.bump],
+        ]],
+    ))?;
+
+    let migration = &ctx.accounts.migration;
+    let old_stablebond_mint = std::mem::replace(
+        &mut ctx.accounts.stablecoin_mint.stablebond_mint,
+        migration.new_stablebond_mint,
+    );
+    ctx.accounts.vault.collateral_account = migration.new_collateral_account;
+
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.stablecoin_mint.record_admin_action(now);
+
+    emit!(CollateralMigrated {
+        stablecoin_mint: stablecoin_key,
+        old_stablebond_mint,
+        new_stablebond_mint: migration.new_stablebond_mint,
+        total_rolled: migration.total_rolled,
+        total_received: migration.total_received,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct CollateralMigrationStarted {
+    pub stablecoin_mint: Pubkey,
+    pub old_stablebond_mint: Pubkey,
+    pub new_stablebond_mint: Pubkey,
+    pub rollover_program: Pubkey,
+    pub min_out_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CollateralRolledOver {
+    pub stablecoin_mint: Pubkey,
+    pub rolled: u64,
+    pub received: u64,
+    /// New bonds per old bond, in bps
+    pub rate_bps: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CollateralMigrated {
+    pub stablecoin_mint: Pubkey,
+    pub old_stablebond_mint: Pubkey,
+    pub new_stablebond_mint: Pubkey,
+    pub total_rolled: u64,
+    pub total_received: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,589 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_interface;
+use switchboard_solana::AggregatorAccountData;
+
+use crate::state::{
+    CollateralRegistry, CollateralType, MaturityPolicy, ProtocolConfig, StablecoinMint, StablecoinVault,
+    StateAccount, PRIMARY_COLLATERAL,
+};
+use crate::constants::{
+    BOND_TERMS_SEED, COLLATERAL_REGISTRY_SEED, COLLATERAL_TYPE_SEED, MATURITY_POLICY_SEED,
+    MAX_COLLATERAL_TYPES, MAX_REBALANCE_SLIPPAGE_BPS, PROTOCOL_CONFIG_SEED, REBALANCE_ESCROW_SEED,
+    VAULT_SEED,
+};
+use crate::error::StableFunError;
+use crate::utils::oracle::OracleService;
+use crate::utils::validation::ValidationService;
+use crate::utils::math;
+
+#[derive(Accounts)]
+pub struct ConfigureCollateralRegistry<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = CollateralRegistry::LEN,
+        seeds = [COLLATERAL_REGISTRY_SEED, stablecoin_mint.key().as_ref()],
+        bump
+    )]
+    pub collateral_registry: Box<Account<'info, CollateralRegistry>>,
+
+    #[account(
+        constraint = stablebond_mint.key() == stablecoin_mint.stablebond_mint @ StableFunError::InvalidStablebond
+    )]
+    pub stablebond_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [REBALANCE_ESCROW_SEED, stablecoin_mint.key().as_ref(), stablebond_mint.key().as_ref()],
+        bump,
+        token::mint = stablebond_mint,
+        token::authority = collateral_registry
+    )]
+    pub primary_escrow: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates or updates the coin's collateral registry: who rebalances, the
+/// swap adapter they go through, and the limits they go under. Rerun after
+/// a collateral migration to move the stablebond escrow to the new series.
+pub fn configure_collateral_registry(
+    ctx: Context<ConfigureCollateralRegistry>,
+    manager: Pubkey,
+    swap_program: Pubkey,
+    max_slippage_bps: u16,
+    epoch_volume_cap: u64,
+) -> Result<()> {
+    require!(
+        manager != Pubkey::default()
+            && swap_program != Pubkey::default()
+            && max_slippage_bps <= MAX_REBALANCE_SLIPPAGE_BPS,
+        StableFunError::InvalidCollateralRegistry
+    );
+
+    let registry = &mut ctx.accounts.collateral_registry;
+    registry.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    registry.manager = manager;
+    registry.swap_program = swap_program;
+    registry.primary_escrow = ctx.accounts.primary_escrow.key();
+    registry.max_slippage_bps = max_slippage_bps;
+    registry.epoch_volume_cap = epoch_volume_cap;
+    registry.bump = ctx.bumps.collateral_registry;
+
+    emit!(CollateralRegistryConfigured {
+        stablecoin_mint: registry.stablecoin_mint,
+        manager,
+        swap_program,
+        max_slippage_bps,
+        epoch_volume_cap,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RegisterCollateralType<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        mut,
+        seeds = [COLLATERAL_REGISTRY_SEED, stablecoin_mint.key().as_ref()],
+        bump = collateral_registry.bump
+    )]
+    pub collateral_registry: Box<Account<'info, CollateralRegistry>>,
+
+    #[account(
+        constraint = collateral_mint.key() != stablecoin_mint.stablebond_mint @ StableFunError::InvalidCollateralRegistry
+    )]
+    pub collateral_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [COLLATERAL_TYPE_SEED, stablecoin_mint.key().as_ref(), collateral_mint.key().as_ref()],
+        bump,
+        token::mint = collateral_mint,
+        token::authority = vault
+    )]
+    pub vault_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [REBALANCE_ESCROW_SEED, stablecoin_mint.key().as_ref(), collateral_mint.key().as_ref()],
+        bump,
+        token::mint = collateral_mint,
+        token::authority = collateral_registry
+    )]
+    pub escrow_account: Box<Account<'info, TokenAccount>>,
+
+    /// Switchboard V3 aggregator quoting the new type per stablecoin unit
+    pub price_feed: AccountLoader<'info, AggregatorAccountData>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Lets the vault hold `collateral_mint`, aiming for `target_weight_bps` of
+/// its collateral. The stablebond's target is what the registered types
+/// leave. Holdings of the type are tagged with `maturity_timestamp`, zero
+/// for collateral that does not mature.
+pub fn register_collateral_type(
+    ctx: Context<RegisterCollateralType>,
+    target_weight_bps: u16,
+    maturity_timestamp: i64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        maturity_timestamp == 0 || maturity_timestamp > now,
+        StableFunError::InvalidCollateralRegistry
+    );
+    OracleService::verify_oracle_price(&ctx.accounts.price_feed)?;
+
+    let registry = &mut ctx.accounts.collateral_registry;
+    require!(
+        registry.collateral_types.len() < MAX_COLLATERAL_TYPES,
+        StableFunError::InvalidCollateralRegistry
+    );
+    let mut targets: Vec<u16> = registry.collateral_types
+        .iter()
+        .map(|collateral| collateral.target_weight_bps)
+        .collect();
+    targets.push(target_weight_bps);
+    CollateralRegistry::validate_targets(&targets)?;
+
+    registry.collateral_types.push(CollateralType {
+        mint: ctx.accounts.collateral_mint.key(),
+        vault_account: ctx.accounts.vault_account.key(),
+        escrow_account: ctx.accounts.escrow_account.key(),
+        price_feed: ctx.accounts.price_feed.key(),
+        target_weight_bps,
+        balance: 0,
+        book_value: 0,
+        maturity_timestamp,
+    });
+
+    emit!(CollateralTypeRegistered {
+        stablecoin_mint: registry.stablecoin_mint,
+        collateral_mint: ctx.accounts.collateral_mint.key(),
+        index: registry.collateral_types.len() as u8,
+        target_weight_bps,
+        maturity_timestamp,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetCollateralTargets<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [COLLATERAL_REGISTRY_SEED, stablecoin_mint.key().as_ref()],
+        bump = collateral_registry.bump
+    )]
+    pub collateral_registry: Box<Account<'info, CollateralRegistry>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+}
+
+/// Replaces the target weight of every registered type, in registration order.
+pub fn set_collateral_targets(ctx: Context<SetCollateralTargets>, targets: Vec<u16>) -> Result<()> {
+    let registry = &mut ctx.accounts.collateral_registry;
+    require!(
+        targets.len() == registry.collateral_types.len(),
+        StableFunError::InvalidCollateralRegistry
+    );
+    CollateralRegistry::validate_targets(&targets)?;
+    for (collateral, target) in registry.collateral_types.iter_mut().zip(&targets) {
+        collateral.target_weight_bps = *target;
+    }
+
+    emit!(CollateralTargetsUpdated {
+        stablecoin_mint: registry.stablecoin_mint,
+        targets,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RebalanceCollateral<'info> {
+    /// The registry's manager, or any keeper cranking `rebalance_ladder`
+    pub caller: Signer<'info>,
+
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        mut,
+        seeds = [COLLATERAL_REGISTRY_SEED, stablecoin_mint.key().as_ref()],
+        bump = collateral_registry.bump
+    )]
+    pub collateral_registry: Box<Account<'info, CollateralRegistry>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// Vault account of the type being sold
+    #[account(mut)]
+    pub from_vault_account: Box<Account<'info, TokenAccount>>,
+
+    /// Escrow of the type being sold
+    #[account(mut)]
+    pub from_escrow: Box<Account<'info, TokenAccount>>,
+
+    /// Escrow of the type being bought
+    #[account(mut)]
+    pub to_escrow: Box<Account<'info, TokenAccount>>,
+
+    /// Vault account of the type being bought
+    #[account(mut)]
+    pub to_vault_account: Box<Account<'info, TokenAccount>>,
+
+    /// The Switchboard V3 aggregator account
+    #[account(
+        constraint = price_feed.key() == stablecoin_mint.price_feed @ StableFunError::InvalidOracle
+    )]
+    pub price_feed: AccountLoader<'info, AggregatorAccountData>,
+
+    /// FX feed composed with the bond NAV `price_feed`, on coins that set one
+    #[account(
+        constraint = fx_feed.key() == stablecoin_mint.fx_feed @ StableFunError::InvalidOracle
+    )]
+    pub fx_feed: Option<AccountLoader<'info, AggregatorAccountData>>,
+
+    /// Feed of the registered type being sold; omitted when selling the stablebond
+    pub from_price_feed: Option<AccountLoader<'info, AggregatorAccountData>>,
+
+    /// Feed of the registered type being bought; omitted when buying the stablebond
+    pub to_price_feed: Option<AccountLoader<'info, AggregatorAccountData>>,
+
+    /// Only needed by `rebalance_ladder`
+    #[account(
+        mut,
+        seeds = [MATURITY_POLICY_SEED, stablecoin_mint.key().as_ref()],
+        bump = maturity_policy.bump
+    )]
+    pub maturity_policy: Option<Box<Account<'info, MaturityPolicy>>>,
+
+    /// CHECK: The coin's `BondTerms` PDA, giving `rebalance_ladder` the
+    /// stablebond's maturity; left uninitialized when no terms are set
+    #[account(seeds = [BOND_TERMS_SEED, stablecoin_mint.key().as_ref()], bump)]
+    pub bond_terms: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Must be the swap adapter fixed in the registry
+    #[account(
+        executable,
+        constraint = swap_program.key() == collateral_registry.swap_program @ StableFunError::InvalidCollateralRegistry
+    )]
+    pub swap_program: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> RebalanceCollateral<'info> {
+    /// Vault account, escrow and collateral price of type `index`.
+    fn leg(
+        &self,
+        index: u8,
+        price_feed: Option<&AccountLoader<'info, AggregatorAccountData>>,
+        primary_price: u64,
+    ) -> Result<(Pubkey, Pubkey, u64)> {
+        if index == PRIMARY_COLLATERAL {
+            return Ok((
+                self.vault.collateral_account,
+                self.collateral_registry.primary_escrow,
+                primary_price,
+            ));
+        }
+        let collateral = self.collateral_registry.collateral_type(index)?;
+        let price_feed = price_feed.ok_or(error!(StableFunError::InvalidOracle))?;
+        require_keys_eq!(price_feed.key(), collateral.price_feed, StableFunError::InvalidOracle);
+        Ok((
+            collateral.vault_account,
+            collateral.escrow_account,
+            OracleService::verify_oracle_price(price_feed)?,
+        ))
+    }
+}
+
+/// Sells `amount` of collateral type `from` for type `to` (0 is the
+/// stablebond, registered types count from 1) through the swap adapter.
+/// `swap_data` and the remaining accounts form the adapter's swap
+/// instruction, signed by the registry PDA. The rebalance fails unless it
+/// stays within the epoch's volume cap, loses no more than the slippage
+/// limit against the oracles, and leaves both types no further from their
+/// targets than it found them.
+pub fn rebalance_collateral<'info>(
+    mut ctx: Context<'_, '_, 'info, 'info, RebalanceCollateral<'info>>,
+    from: u8,
+    to: u8,
+    amount: u64,
+    swap_data: Vec<u8>,
+) -> Result<()> {
+    require_keys_eq!(
+        ctx.accounts.caller.key(),
+        ctx.accounts.collateral_registry.manager,
+        StableFunError::UnauthorizedCollateralManager
+    );
+    execute_rebalance(&mut ctx, from, to, amount, swap_data)?;
+    require!(
+        ctx.accounts.collateral_registry.within_targets(from, to, ctx.accounts.vault.total_collateral)?,
+        StableFunError::RebalanceOffTarget
+    );
+    Ok(())
+}
+
+/// The swap and booking behind `rebalance_collateral` and `rebalance_ladder`,
+/// which each check the result against their own targets.
+pub fn execute_rebalance<'info>(
+    ctx: &mut Context<'_, '_, 'info, 'info, RebalanceCollateral<'info>>,
+    from: u8,
+    to: u8,
+    amount: u64,
+    swap_data: Vec<u8>,
+) -> Result<()> {
+    require!(amount > 0, StableFunError::InvalidAmount);
+    require!(from != to, StableFunError::InvalidCollateralType);
+
+    let primary_price = OracleService::collateral_price(
+        &ctx.accounts.stablecoin_mint,
+        &ctx.accounts.price_feed,
+        ctx.accounts.fx_feed.as_ref(),
+    )?;
+    let (from_account, from_escrow, from_price) =
+        ctx.accounts.leg(from, ctx.accounts.from_price_feed.as_ref(), primary_price)?;
+    let (to_account, to_escrow, to_price) =
+        ctx.accounts.leg(to, ctx.accounts.to_price_feed.as_ref(), primary_price)?;
+    require!(
+        ctx.accounts.from_vault_account.key() == from_account
+            && ctx.accounts.from_escrow.key() == from_escrow
+            && ctx.accounts.to_vault_account.key() == to_account
+            && ctx.accounts.to_escrow.key() == to_escrow,
+        StableFunError::InvalidTokenAccount
+    );
+
+    let clock = Clock::get()?;
+    let decimals = ctx.accounts.token_mint.decimals;
+    let sold_value = math::calculate_collateral_value(amount, from_price, decimals)?;
+    ctx.accounts.collateral_registry.consume_volume(sold_value, clock.epoch)?;
+
+    // Move the collateral being sold out of the vault into its escrow
+    let escrow_before = ctx.accounts.from_escrow.amount;
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.from_vault_account.to_account_info(),
+                to: ctx.accounts.from_escrow.to_account_info(),
+                authority: ctx.accounts.vault.to_account_info(),
+            },
+            &[&ctx.accounts.vault.signer_seeds()],
+        ),
+        amount,
+    )?;
+
+    // Swap through the adapter, signed by the registry PDA
+    let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+    let registry_key = ctx.accounts.collateral_registry.key();
+    let registry_seeds: &[&[u8]] = &[
+        COLLATERAL_REGISTRY_SEED,
+        stablecoin_key.as_ref(),
+        &[ctx.accounts.collateral_registry.bump],
+    ];
+    let received_before = ctx.accounts.to_escrow.amount;
+    let swap_ix = Instruction {
+        program_id: ctx.accounts.swap_program.key(),
+        accounts: ctx.remaining_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer || account.key() == registry_key,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data: swap_data,
+    };
+    invoke_signed(&swap_ix, ctx.remaining_accounts, &[registry_seeds])?;
+
+    ctx.accounts.from_escrow.reload()?;
+    ctx.accounts.to_escrow.reload()?;
+    require!(
+        ctx.accounts.from_escrow.amount == escrow_before,
+        StableFunError::RebalanceSwapMismatch
+    );
+    let received = ctx.accounts.to_escrow.amount.saturating_sub(received_before);
+    let received_value = math::calculate_collateral_value(received, to_price, decimals)?;
+    require!(
+        CollateralRegistry::within_slippage(
+            sold_value,
+            received_value,
+            ctx.accounts.collateral_registry.max_slippage_bps,
+        ),
+        StableFunError::RebalanceSlippageExceeded
+    );
+
+    // Bring what was bought into the vault
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.to_escrow.to_account_info(),
+                to: ctx.accounts.to_vault_account.to_account_info(),
+                authority: ctx.accounts.collateral_registry.to_account_info(),
+            },
+            &[registry_seeds],
+        ),
+        received,
+    )?;
+
+    // Other types are booked at what they were worth in stablebonds when bought
+    let received_book = if to == PRIMARY_COLLATERAL {
+        received
+    } else {
+        math::calculate_token_amount(received_value, primary_price, decimals)?
+    };
+    let registry = &mut ctx.accounts.collateral_registry;
+    let total_collateral = registry.book_rebalance(
+        from,
+        to,
+        amount,
+        received,
+        received_book,
+        ctx.accounts.vault.total_collateral,
+    )?;
+    registry.total_rebalanced = registry.total_rebalanced
+        .checked_add(sold_value)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.total_collateral = total_collateral;
+    ValidationService::update_collateral_ratio(vault, primary_price, decimals)?;
+
+    emit!(CollateralRebalanced {
+        stablecoin_mint: stablecoin_key,
+        from,
+        to,
+        sold: amount,
+        received,
+        sold_value,
+        received_value,
+        total_collateral,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct CollateralRegistryConfigured {
+    pub stablecoin_mint: Pubkey,
+    pub manager: Pubkey,
+    pub swap_program: Pubkey,
+    pub max_slippage_bps: u16,
+    pub epoch_volume_cap: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CollateralTypeRegistered {
+    pub stablecoin_mint: Pubkey,
+    pub collateral_mint: Pubkey,
+    /// Index rebalances refer to the type by
+    pub index: u8,
+    pub target_weight_bps: u16,
+    pub maturity_timestamp: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CollateralTargetsUpdated {
+    pub stablecoin_mint: Pubkey,
+    pub targets: Vec<u16>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CollateralRebalanced {
+    pub stablecoin_mint: Pubkey,
+    pub from: u8,
+    pub to: u8,
+    pub sold: u64,
+    pub received: u64,
+    /// Oracle values of both legs, in stablecoin units
+    pub sold_value: u64,
+    pub received_value: u64,
+    pub total_collateral: u64,
+    pub timestamp: i64,
+}
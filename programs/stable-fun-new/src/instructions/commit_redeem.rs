@@ -0,0 +1,198 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{
+    ProtocolConfig, RedeemCommitment, StablecoinMint, StablecoinVault, StateAccount, PAUSE_REDEEM,
+};
+use crate::constants::{PROTOCOL_CONFIG_SEED, REDEEM_COMMITMENT_SEED, REDEEM_REVEAL_WINDOW_SLOTS, VAULT_SEED};
+use crate::error::StableFunError;
+
+#[derive(Accounts)]
+pub struct CommitRedeem<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub stablecoin_mint: Account<'info, StablecoinMint>,
+
+    /// Hands out the commitment's place in the redeem queue
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        init,
+        payer = user,
+        space = RedeemCommitment::LEN,
+        seeds = [REDEEM_COMMITMENT_SEED, stablecoin_mint.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub commitment: Account<'info, RedeemCommitment>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_REDEEM) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn commit_redeem(ctx: Context<CommitRedeem>, hash: [u8; 32]) -> Result<()> {
+    require!(
+        !ctx.accounts.stablecoin_mint.settings.redeem_paused,
+        StableFunError::RedeemingPaused
+    );
+
+    let clock = Clock::get()?;
+    let commitment = &mut ctx.accounts.commitment;
+    commitment.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    commitment.owner = ctx.accounts.user.key();
+    commitment.hash = hash;
+    commitment.commit_slot = clock.slot;
+    commitment.bump = ctx.bumps.commitment;
+    commitment.ticket = ctx.accounts.vault.enqueue_redeem()?;
+
+    emit!(RedeemCommitted {
+        stablecoin_mint: commitment.stablecoin_mint,
+        user: commitment.owner,
+        hash,
+        commit_slot: clock.slot,
+        ticket: commitment.ticket,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelRedeemCommitment<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub stablecoin_mint: Account<'info, StablecoinMint>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [REDEEM_COMMITMENT_SEED, stablecoin_mint.key().as_ref(), user.key().as_ref()],
+        bump = commitment.bump
+    )]
+    pub commitment: Account<'info, RedeemCommitment>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_REDEEM) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+}
+
+/// Leaves the redeem queue. Tickets leave from the front so none is ever
+/// skipped over; a commitment still waiting behind others can only be
+/// cancelled once it reaches the head.
+pub fn cancel_redeem_commitment(ctx: Context<CancelRedeemCommitment>) -> Result<()> {
+    ctx.accounts.vault.dequeue_redeem(ctx.accounts.commitment.ticket, Clock::get()?.slot)?;
+
+    emit!(RedeemCommitmentCancelled {
+        stablecoin_mint: ctx.accounts.stablecoin_mint.key(),
+        user: ctx.accounts.user.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SkipExpiredRedeem<'info> {
+    pub keeper: Signer<'info>,
+
+    pub stablecoin_mint: Account<'info, StablecoinMint>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [REDEEM_COMMITMENT_SEED, stablecoin_mint.key().as_ref(), commitment.owner.as_ref()],
+        bump = commitment.bump
+    )]
+    pub commitment: Account<'info, RedeemCommitment>,
+
+    /// CHECK: Receives the commitment's rent back
+    #[account(mut, address = commitment.owner @ StableFunError::InvalidRedeemCommitment)]
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+}
+
+/// Drops the commitment at the head of the redeem queue once its reveal
+/// window has passed, so an abandoned commitment cannot hold up the ones
+/// behind it. Callable by anyone; the rent goes back to the owner.
+pub fn skip_expired_redeem(ctx: Context<SkipExpiredRedeem>) -> Result<()> {
+    let slot = Clock::get()?.slot;
+    let (_, closes_at) = ctx.accounts.commitment.reveal_window(
+        ctx.accounts.stablecoin_mint.settings.reveal_delay_slots,
+        REDEEM_REVEAL_WINDOW_SLOTS,
+        ctx.accounts.vault.redeem_queue_head_since,
+    )?;
+    require!(slot > closes_at, StableFunError::RevealWindowOpen);
+    ctx.accounts.vault.dequeue_redeem(ctx.accounts.commitment.ticket, slot)?;
+
+    emit!(ExpiredRedeemSkipped {
+        stablecoin_mint: ctx.accounts.stablecoin_mint.key(),
+        user: ctx.accounts.commitment.owner,
+        ticket: ctx.accounts.commitment.ticket,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct RedeemCommitted {
+    pub stablecoin_mint: Pubkey,
+    pub user: Pubkey,
+    pub hash: [u8; 32],
+    pub commit_slot: u64,
+    /// Place in the redeem queue
+    pub ticket: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RedeemCommitmentCancelled {
+    pub stablecoin_mint: Pubkey,
+    pub user: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ExpiredRedeemSkipped {
+    pub stablecoin_mint: Pubkey,
+    pub user: Pubkey,
+    pub ticket: u64,
+    pub timestamp: i64,
+}
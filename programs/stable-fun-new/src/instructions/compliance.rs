@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+
+use crate::state::{FreezeAuthorityMode, StablecoinMint};
+use crate::constants::FREEZE_AUTHORITY_SEED;
+use crate::error::StableFunError;
+
+#[derive(Accounts)]
+pub struct SetTokenAccountFrozen<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint,
+        constraint = stablecoin_mint.freeze_authority_mode == FreezeAuthorityMode::Program @ StableFunError::FreezeAuthorityNotProgram
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = token_account.mint == token_mint.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: PDA holding the mint's freeze authority
+    #[account(
+        seeds = [FREEZE_AUTHORITY_SEED, stablecoin_mint.key().as_ref()],
+        bump = stablecoin_mint.freeze_authority_bump
+    )]
+    pub freeze_authority: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn freeze(ctx: Context<SetTokenAccountFrozen>) -> Result<()> {
+    let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+    token_interface::freeze_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token_interface::FreezeAccount {
+            account: ctx.accounts.token_account.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+            authority: ctx.accounts.freeze_authority.to_account_info(),
+        },
+        &[&[
+            FREEZE_AUTHORITY_SEED,
+            stablecoin_key.as_ref(),
+            &[ctx.accounts.stablecoin_mint.freeze_authority_bump],
+        ]],
+    ))?;
+
+    emit!(TokenAccountFrozen {
+        stablecoin_mint: stablecoin_key,
+        token_account: ctx.accounts.token_account.key(),
+        frozen: true,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn thaw(ctx: Context<SetTokenAccountFrozen>) -> Result<()> {
+    let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+    token_interface::thaw_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token_interface::ThawAccount {
+            account: ctx.accounts.token_account.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+            authority: ctx.accounts.freeze_authority.to_account_info(),
+        },
+        &[&[
+            FREEZE_AUTHORITY_SEED,
+            stablecoin_key.as_ref(),
+            &[ctx.accounts.stablecoin_mint.freeze_authority_bump],
+        ]],
+    ))?;
+
+    emit!(TokenAccountFrozen {
+        stablecoin_mint: stablecoin_key,
+        token_account: ctx.accounts.token_account.key(),
+        frozen: false,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct TokenAccountFrozen {
+    pub stablecoin_mint: Pubkey,
+    pub token_account: Pubkey,
+    pub frozen: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted alongside the mint or redeem event when the amount exceeds the
+/// coin's `reporting_threshold`, naming the counterparty accounts.
+#[event]
+pub struct LargeOperation {
+    pub stablecoin_mint: Pubkey,
+    /// `OperationType` discriminant
+    pub operation: u8,
+    pub user: Pubkey,
+    /// Stablecoin account minted to or burned from
+    pub token_account: Pubkey,
+    /// Stablebond account collateral came from or was paid to
+    pub collateral_account: Pubkey,
+    pub amount: u64,
+    pub collateral_amount: u64,
+    pub timestamp: i64,
+}
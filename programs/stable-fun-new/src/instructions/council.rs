@@ -0,0 +1,296 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{
+    ApprovalCouncil, Proposal, ProposalAction, ProtocolConfig, StablecoinMint, StateAccount,
+};
+use crate::constants::{
+    COUNCIL_SEED, MAX_FEE_BPS, PROPOSAL_EXPIRY_SECONDS, PROPOSAL_SEED, PROTOCOL_CONFIG_SEED,
+};
+use crate::error::StableFunError;
+
+#[derive(Accounts)]
+pub struct CreateCouncil<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint,
+        constraint = stablecoin_mint.council == Pubkey::default() @ StableFunError::InvalidCouncil
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = ApprovalCouncil::LEN,
+        seeds = [COUNCIL_SEED, stablecoin_mint.key().as_ref()],
+        bump
+    )]
+    pub council: Box<Account<'info, ApprovalCouncil>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Puts critical changes under M-of-N approval. Irreversible, so a single
+/// compromised authority key cannot lift it.
+pub fn create_council(ctx: Context<CreateCouncil>, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+    ApprovalCouncil::validate(&signers, threshold)?;
+
+    let council = &mut ctx.accounts.council;
+    council.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    council.signers = signers;
+    council.threshold = threshold;
+    council.bump = ctx.bumps.council;
+
+    let now = Clock::get()?.unix_timestamp;
+    let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
+    stablecoin_mint.council = council.key();
+    stablecoin_mint.record_admin_action(now);
+
+    emit!(CouncilCreated {
+        stablecoin_mint: stablecoin_mint.key(),
+        council: council.key(),
+        signers: council.signers.clone(),
+        threshold,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [COUNCIL_SEED, stablecoin_mint.key().as_ref()],
+        bump = council.bump
+    )]
+    pub council: Box<Account<'info, ApprovalCouncil>>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = Proposal::LEN,
+        seeds = [PROPOSAL_SEED, council.key().as_ref(), &council.proposal_count.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Opens a proposal, counting the proposer's approval.
+pub fn create_proposal(ctx: Context<CreateProposal>, action: ProposalAction) -> Result<()> {
+    if let ProposalAction::SetFee { fee_basis_points } = action {
+        require!(fee_basis_points <= MAX_FEE_BPS, StableFunError::FeeTooHigh);
+    }
+    if let ProposalAction::TransferAuthority { new_authority } = action {
+        require!(new_authority != Pubkey::default(), StableFunError::InvalidCouncil);
+    }
+
+    let council = &mut ctx.accounts.council;
+    let signer_index = council
+        .signer_index(&ctx.accounts.proposer.key())
+        .ok_or(StableFunError::NotCouncilSigner)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.council = council.key();
+    proposal.index = council.proposal_count;
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.action = action;
+    proposal.approvals = 0;
+    proposal.approve(signer_index);
+    proposal.created_at = now;
+    proposal.executed = false;
+    proposal.bump = ctx.bumps.proposal;
+
+    council.proposal_count = council.proposal_count
+        .checked_add(1)
+        .ok_or(StableFunError::MathOverflow)?;
+
+    emit!(ProposalCreated {
+        council: council.key(),
+        proposal: proposal.key(),
+        index: proposal.index,
+        proposer: proposal.proposer,
+        action,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ApproveProposal<'info> {
+    pub signer: Signer<'info>,
+
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        seeds = [COUNCIL_SEED, stablecoin_mint.key().as_ref()],
+        bump = council.bump
+    )]
+    pub council: Box<Account<'info, ApprovalCouncil>>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, council.key().as_ref(), &proposal.index.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+}
+
+pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+    let signer_index = ctx.accounts.council
+        .signer_index(&ctx.accounts.signer.key())
+        .ok_or(StableFunError::NotCouncilSigner)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let proposal = &mut ctx.accounts.proposal;
+    require!(!proposal.executed, StableFunError::ProposalAlreadyExecuted);
+    require!(
+        now.saturating_sub(proposal.created_at) < PROPOSAL_EXPIRY_SECONDS,
+        StableFunError::ProposalExpired
+    );
+    proposal.approve(signer_index);
+
+    emit!(ProposalApproved {
+        proposal: proposal.key(),
+        signer: ctx.accounts.signer.key(),
+        approvals: proposal.approval_count() as u8,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_mint.council == council.key() @ StableFunError::InvalidCouncil
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        seeds = [COUNCIL_SEED, stablecoin_mint.key().as_ref()],
+        bump = council.bump
+    )]
+    pub council: Box<Account<'info, ApprovalCouncil>>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, council.key().as_ref(), &proposal.index.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+}
+
+/// Permissionless once the proposal holds `threshold` approvals.
+pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let proposal = &mut ctx.accounts.proposal;
+    require!(!proposal.executed, StableFunError::ProposalAlreadyExecuted);
+    require!(
+        now.saturating_sub(proposal.created_at) < PROPOSAL_EXPIRY_SECONDS,
+        StableFunError::ProposalExpired
+    );
+    require!(
+        proposal.approval_count() >= ctx.accounts.council.threshold as u32,
+        StableFunError::ProposalNotApproved
+    );
+
+    let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
+    match proposal.action {
+        ProposalAction::TransferAuthority { new_authority } => {
+            stablecoin_mint.authority = new_authority;
+            stablecoin_mint.pauser = Pubkey::default();
+            stablecoin_mint.verified = false;
+        }
+        ProposalAction::SetFee { fee_basis_points } => {
+            stablecoin_mint.settings.fee_basis_points = fee_basis_points;
+        }
+        ProposalAction::Settle => stablecoin_mint.enter_settlement(),
+    }
+    stablecoin_mint.record_admin_action(now);
+    proposal.executed = true;
+
+    emit!(ProposalExecuted {
+        proposal: proposal.key(),
+        action: proposal.action,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct CouncilCreated {
+    pub stablecoin_mint: Pubkey,
+    pub council: Pubkey,
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalCreated {
+    pub council: Pubkey,
+    pub proposal: Pubkey,
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub action: ProposalAction,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalApproved {
+    pub proposal: Pubkey,
+    pub signer: Pubkey,
+    pub approvals: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalExecuted {
+    pub proposal: Pubkey,
+    pub action: ProposalAction,
+    pub timestamp: i64,
+}
@@ -0,0 +1,173 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{DeadManAction, DeadManSwitch, ProtocolConfig, StablecoinMint, StateAccount};
+use crate::constants::{
+    DEAD_MAN_SWITCH_SEED, MAX_INACTIVITY_DAYS, MIN_INACTIVITY_DAYS, PROTOCOL_CONFIG_SEED,
+    SECONDS_PER_DAY,
+};
+use crate::error::StableFunError;
+
+#[derive(Accounts)]
+pub struct ConfigureDeadManSwitch<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = DeadManSwitch::LEN,
+        seeds = [DEAD_MAN_SWITCH_SEED, stablecoin_mint.key().as_ref()],
+        bump
+    )]
+    pub dead_man_switch: Box<Account<'info, DeadManSwitch>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Names the recovery key and how many days of silence arm the switch.
+pub fn configure_dead_man_switch(
+    ctx: Context<ConfigureDeadManSwitch>,
+    recovery_key: Pubkey,
+    inactivity_days: u16,
+) -> Result<()> {
+    require!(
+        (MIN_INACTIVITY_DAYS..=MAX_INACTIVITY_DAYS).contains(&inactivity_days)
+            && recovery_key != Pubkey::default()
+            && recovery_key != ctx.accounts.authority.key(),
+        StableFunError::InvalidDeadManSwitch
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let dead_man_switch = &mut ctx.accounts.dead_man_switch;
+    dead_man_switch.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    dead_man_switch.recovery_key = recovery_key;
+    dead_man_switch.inactivity_period = inactivity_days as i64 * SECONDS_PER_DAY;
+    dead_man_switch.bump = ctx.bumps.dead_man_switch;
+    ctx.accounts.stablecoin_mint.record_admin_action(now);
+
+    emit!(DeadManSwitchConfigured {
+        stablecoin_mint: dead_man_switch.stablecoin_mint,
+        recovery_key,
+        inactivity_period: dead_man_switch.inactivity_period,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct Heartbeat<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+}
+
+/// Proof of life for issuers with nothing else to do.
+pub fn heartbeat(ctx: Context<Heartbeat>) -> Result<()> {
+    ctx.accounts.stablecoin_mint.record_admin_action(Clock::get()?.unix_timestamp);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct TriggerDeadManSwitch<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(mut)]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [DEAD_MAN_SWITCH_SEED, stablecoin_mint.key().as_ref()],
+        bump = dead_man_switch.bump
+    )]
+    pub dead_man_switch: Box<Account<'info, DeadManSwitch>>,
+
+    /// Its admin acts as the guardian
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+}
+
+/// Once the authority has been silent for the configured period, the
+/// recovery key or the guardian takes over the coin or winds it down.
+pub fn trigger_dead_man_switch(ctx: Context<TriggerDeadManSwitch>, action: DeadManAction) -> Result<()> {
+    let caller = ctx.accounts.caller.key();
+    require!(
+        caller == ctx.accounts.dead_man_switch.recovery_key || caller == ctx.accounts.protocol_config.admin,
+        StableFunError::UnauthorizedRecovery
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
+    require!(
+        ctx.accounts.dead_man_switch.is_expired(stablecoin_mint.last_admin_action, now),
+        StableFunError::IssuerStillActive
+    );
+
+    let previous_authority = stablecoin_mint.authority;
+    match action {
+        DeadManAction::AssumeControl => {
+            stablecoin_mint.authority = caller;
+            // The absent issuer's delegate and verification go with them
+            stablecoin_mint.pauser = Pubkey::default();
+            stablecoin_mint.verified = false;
+        }
+        DeadManAction::Settle => stablecoin_mint.enter_settlement(),
+    }
+    stablecoin_mint.record_admin_action(now);
+    ctx.accounts.dead_man_switch.triggered_at = now;
+
+    emit!(DeadManSwitchTriggered {
+        stablecoin_mint: stablecoin_mint.key(),
+        caller,
+        previous_authority,
+        action,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct DeadManSwitchConfigured {
+    pub stablecoin_mint: Pubkey,
+    pub recovery_key: Pubkey,
+    pub inactivity_period: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DeadManSwitchTriggered {
+    pub stablecoin_mint: Pubkey,
+    pub caller: Pubkey,
+    pub previous_authority: Pubkey,
+    pub action: DeadManAction,
+    pub timestamp: i64,
+}
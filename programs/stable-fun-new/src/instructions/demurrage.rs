@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+
+use crate::state::{ProtocolConfig, RebaseMode, StablecoinMint};
+use crate::constants::{FEE_VAULT_SEED, MINT_AUTHORITY_SEED, PROTOCOL_CONFIG_SEED};
+use crate::error::StableFunError;
+
+#[derive(Accounts)]
+pub struct CollectDemurrage<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: PDA used as mint authority
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, stablecoin_mint.key().as_ref()],
+        bump = stablecoin_mint.mint_authority_bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// Stablecoin account collecting demurrage, shared with harvested transfer fees
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [FEE_VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = mint_authority,
+        token::token_program = stablecoin_token_program
+    )]
+    pub fee_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    /// Token program owning the stablecoin mint (SPL Token or Token-2022)
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless crank: accrues demurrage up to now and mints the value
+/// taken from holders into the fee vault, so the underlying supply is
+/// unchanged and only moves from holders to the issuer.
+pub fn collect_demurrage(ctx: Context<CollectDemurrage>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
+    require!(
+        stablecoin_mint.rebase_mode == RebaseMode::Demurrage,
+        StableFunError::RebaseNotEnabled
+    );
+    stablecoin_mint.accrue_demurrage(now)?;
+
+    // Sub-token remainders stay pending for the next collection
+    let raw_amount = stablecoin_mint.to_raw_amount(stablecoin_mint.pending_demurrage, false)?;
+    let collected = stablecoin_mint.to_underlying_amount(raw_amount)?;
+    stablecoin_mint.pending_demurrage = stablecoin_mint.pending_demurrage.saturating_sub(collected);
+    stablecoin_mint.current_supply = stablecoin_mint.current_supply
+        .checked_add(raw_amount)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    stablecoin_mint.last_updated = now;
+
+    if raw_amount > 0 {
+        let stablecoin_key = stablecoin_mint.key();
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.stablecoin_token_program.to_account_info(),
+                token_interface::MintTo {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.fee_vault.to_account_info(),
+                    authority: ctx.accounts.mint_authority.to_account_info(),
+                },
+                &[&[
+                    MINT_AUTHORITY_SEED,
+                    stablecoin_key.as_ref(),
+                    &[ctx.accounts.stablecoin_mint.mint_authority_bump],
+                ]],
+            ),
+            raw_amount,
+        )?;
+    }
+
+    emit!(DemurrageCollected {
+        stablecoin_mint: ctx.accounts.stablecoin_mint.key(),
+        fee_vault: ctx.accounts.fee_vault.key(),
+        amount: collected,
+        raw_amount,
+        rebase_index: ctx.accounts.stablecoin_mint.rebase_index,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct DemurrageCollected {
+    pub stablecoin_mint: Pubkey,
+    pub fee_vault: Pubkey,
+    /// Underlying units moved from holders to the fee vault
+    pub amount: u64,
+    pub raw_amount: u64,
+    pub rebase_index: u128,
+    pub timestamp: i64,
+}
@@ -0,0 +1,259 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+
+use crate::state::{
+    ClaimBitmap, Dividend, ProtocolConfig, Snapshot, StablecoinMint, StateAccount, PAUSE_TRANSFERS,
+};
+use crate::constants::{
+    DIVIDEND_BITMAP_SEED, DIVIDEND_SEED, DIVIDEND_VAULT_SEED, PROTOCOL_CONFIG_SEED,
+};
+use crate::error::StableFunError;
+
+#[derive(Accounts)]
+#[instruction(dividend_id: u64)]
+pub struct CreateDividend<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        constraint = snapshot.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidDividend
+    )]
+    pub snapshot: Box<Account<'info, Snapshot>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Dividend::LEN,
+        seeds = [DIVIDEND_SEED, stablecoin_mint.key().as_ref(), &dividend_id.to_le_bytes()],
+        bump
+    )]
+    pub dividend: Box<Account<'info, Dividend>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = ClaimBitmap::space(snapshot.holder_count),
+        seeds = [DIVIDEND_BITMAP_SEED, dividend.key().as_ref()],
+        bump
+    )]
+    pub claim_bitmap: Box<Account<'info, ClaimBitmap>>,
+
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [DIVIDEND_VAULT_SEED, dividend.key().as_ref()],
+        bump,
+        token::mint = reward_mint,
+        token::authority = dividend,
+        token::token_program = token_program
+    )]
+    pub dividend_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = authority_token_account.mint == reward_mint.key() @ StableFunError::InvalidTokenAccount,
+        constraint = authority_token_account.owner == authority.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub authority_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_TRANSFERS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Funds a distribution of `total_amount` reward tokens, split over the
+/// snapshot's balances. Supply is not rebased; holders pull their share.
+pub fn create_dividend(ctx: Context<CreateDividend>, dividend_id: u64, total_amount: u64) -> Result<()> {
+    let snapshot = &ctx.accounts.snapshot;
+    require!(
+        total_amount > 0 && snapshot.total_balance > 0 && snapshot.holder_count > 0,
+        StableFunError::InvalidDividend
+    );
+
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.authority_token_account.to_account_info(),
+                mint: ctx.accounts.reward_mint.to_account_info(),
+                to: ctx.accounts.dividend_token_account.to_account_info(),
+                authority: ctx.accounts.authority.to_account_info(),
+            },
+        ),
+        total_amount,
+        ctx.accounts.reward_mint.decimals,
+    )?;
+
+    let clock = Clock::get()?;
+    let dividend = &mut ctx.accounts.dividend;
+    dividend.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    dividend.authority = ctx.accounts.authority.key();
+    dividend.dividend_id = dividend_id;
+    dividend.snapshot = snapshot.key();
+    dividend.reward_mint = ctx.accounts.reward_mint.key();
+    dividend.token_account = ctx.accounts.dividend_token_account.key();
+    dividend.total_amount = total_amount;
+    dividend.total_balance = snapshot.total_balance;
+    dividend.created_at = clock.unix_timestamp;
+    dividend.bump = ctx.bumps.dividend;
+
+    let claim_bitmap = &mut ctx.accounts.claim_bitmap;
+    claim_bitmap.airdrop = dividend.key();
+    claim_bitmap.bump = ctx.bumps.claim_bitmap;
+    claim_bitmap.bits = vec![0; ClaimBitmap::byte_len(snapshot.holder_count)];
+
+    emit!(DividendCreated {
+        stablecoin_mint: dividend.stablecoin_mint,
+        dividend: dividend.key(),
+        dividend_id,
+        snapshot: dividend.snapshot,
+        reward_mint: dividend.reward_mint,
+        total_amount,
+        total_balance: dividend.total_balance,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimDividend<'info> {
+    pub holder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [DIVIDEND_SEED, dividend.stablecoin_mint.as_ref(), &dividend.dividend_id.to_le_bytes()],
+        bump = dividend.bump
+    )]
+    pub dividend: Box<Account<'info, Dividend>>,
+
+    #[account(
+        constraint = snapshot.key() == dividend.snapshot @ StableFunError::InvalidDividend
+    )]
+    pub snapshot: Box<Account<'info, Snapshot>>,
+
+    #[account(
+        mut,
+        seeds = [DIVIDEND_BITMAP_SEED, dividend.key().as_ref()],
+        bump = claim_bitmap.bump
+    )]
+    pub claim_bitmap: Box<Account<'info, ClaimBitmap>>,
+
+    #[account(
+        constraint = reward_mint.key() == dividend.reward_mint @ StableFunError::InvalidMint
+    )]
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = dividend_token_account.key() == dividend.token_account @ StableFunError::InvalidTokenAccount
+    )]
+    pub dividend_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = holder_token_account.mint == dividend.reward_mint @ StableFunError::InvalidTokenAccount,
+        constraint = holder_token_account.owner == holder.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub holder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_TRANSFERS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Pays the holder's pro-rata share for the snapshot leaf `index`; the claim
+/// bitmap makes each leaf claimable once.
+pub fn claim_dividend(
+    ctx: Context<ClaimDividend>,
+    index: u64,
+    balance: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    require!(
+        index < ctx.accounts.snapshot.holder_count as u64
+            && ctx.accounts.snapshot.verify_snapshot_claim(index, &ctx.accounts.holder.key(), balance, &proof),
+        StableFunError::InvalidDividendProof
+    );
+    require!(
+        !ctx.accounts.claim_bitmap.is_claimed(index),
+        StableFunError::DividendAlreadyClaimed
+    );
+
+    let amount = ctx.accounts.dividend.share_of(balance)?;
+    ctx.accounts.claim_bitmap.set_claimed(index)?;
+    ctx.accounts.dividend.record_claim(amount)?;
+
+    let dividend = &ctx.accounts.dividend;
+    let dividend_id = dividend.dividend_id.to_le_bytes();
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.dividend_token_account.to_account_info(),
+                mint: ctx.accounts.reward_mint.to_account_info(),
+                to: ctx.accounts.holder_token_account.to_account_info(),
+                authority: dividend.to_account_info(),
+            },
+            &[&[
+                DIVIDEND_SEED,
+                dividend.stablecoin_mint.as_ref(),
+                &dividend_id,
+                &[dividend.bump],
+            ]],
+        ),
+        amount,
+        ctx.accounts.reward_mint.decimals,
+    )?;
+
+    emit!(DividendClaimed {
+        dividend: dividend.key(),
+        holder: ctx.accounts.holder.key(),
+        index,
+        balance,
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct DividendCreated {
+    pub stablecoin_mint: Pubkey,
+    pub dividend: Pubkey,
+    pub dividend_id: u64,
+    pub snapshot: Pubkey,
+    pub reward_mint: Pubkey,
+    pub total_amount: u64,
+    pub total_balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DividendClaimed {
+    pub dividend: Pubkey,
+    pub holder: Pubkey,
+    pub index: u64,
+    pub balance: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
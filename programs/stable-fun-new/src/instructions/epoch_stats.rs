@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{EpochStats, ProtocolConfig, StablecoinMint, StablecoinVault, StateAccount};
+use crate::constants::{EPOCH_STATS_SEED, PROTOCOL_CONFIG_SEED, VAULT_SEED};
+use crate::error::StableFunError;
+
+#[derive(Accounts)]
+pub struct RollEpochStats<'info> {
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        init_if_needed,
+        payer = caller,
+        space = EpochStats::LEN,
+        seeds = [EPOCH_STATS_SEED, stablecoin_mint.key().as_ref()],
+        bump
+    )]
+    pub epoch_stats: Box<Account<'info, EpochStats>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless crank: samples the collateral ratio and, on the first call
+/// of a new epoch, closes the previous one into the history.
+pub fn roll_epoch_stats(ctx: Context<RollEpochStats>) -> Result<()> {
+    let clock = Clock::get()?;
+    let stablecoin_mint = &ctx.accounts.stablecoin_mint;
+    let vault = &ctx.accounts.vault;
+    let epoch_stats = &mut ctx.accounts.epoch_stats;
+
+    if epoch_stats.stablecoin_mint == Pubkey::default() {
+        epoch_stats.stablecoin_mint = stablecoin_mint.key();
+        epoch_stats.bump = ctx.bumps.epoch_stats;
+        epoch_stats.open_epoch(clock.epoch, &stablecoin_mint.stats);
+    }
+
+    let closed = epoch_stats.roll(
+        clock.epoch,
+        &stablecoin_mint.stats,
+        stablecoin_mint.current_supply,
+        vault.total_value_locked,
+    );
+    epoch_stats.sample_ratio(vault.current_ratio);
+
+    if let Some(snapshot) = closed {
+        emit!(EpochStatsRolled {
+            stablecoin_mint: stablecoin_mint.key(),
+            epoch: snapshot.epoch,
+            supply: snapshot.supply,
+            total_value_locked: snapshot.total_value_locked,
+            volume: snapshot.volume,
+            fee_revenue: snapshot.fee_revenue,
+            average_ratio: snapshot.average_ratio,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+#[event]
+pub struct EpochStatsRolled {
+    pub stablecoin_mint: Pubkey,
+    pub epoch: u64,
+    pub supply: u64,
+    pub total_value_locked: u64,
+    pub volume: u64,
+    pub fee_revenue: u64,
+    pub average_ratio: u16,
+    pub timestamp: i64,
+}
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::state::{FeeDiscountConfig, FeeDiscountTier, ProtocolConfig, StablecoinMint, StateAccount};
+use crate::constants::{FEE_DISCOUNT_SEED, PROTOCOL_CONFIG_SEED};
+use crate::error::StableFunError;
+
+#[derive(Accounts)]
+pub struct SetFeeDiscounts<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = FeeDiscountConfig::LEN,
+        seeds = [FEE_DISCOUNT_SEED, stablecoin_mint.key().as_ref()],
+        bump
+    )]
+    pub fee_discount: Box<Account<'info, FeeDiscountConfig>>,
+
+    /// Governance or partner token whose holders get the discount
+    pub discount_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates or replaces the coin's fee discount schedule; an empty `tiers`
+/// turns discounts off.
+pub fn set_fee_discounts(ctx: Context<SetFeeDiscounts>, tiers: Vec<FeeDiscountTier>) -> Result<()> {
+    FeeDiscountConfig::validate_tiers(&tiers)?;
+
+    let fee_discount = &mut ctx.accounts.fee_discount;
+    fee_discount.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    fee_discount.discount_mint = ctx.accounts.discount_mint.key();
+    fee_discount.tiers = tiers;
+    fee_discount.bump = ctx.bumps.fee_discount;
+
+    emit!(FeeDiscountsUpdated {
+        stablecoin_mint: fee_discount.stablecoin_mint,
+        discount_mint: fee_discount.discount_mint,
+        tiers: fee_discount.tiers.clone(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// The mint/redeem fee a user pays: the coin's fee, discounted when the
+/// user shows an account of the discount token. Without a schedule the
+/// account is ignored.
+pub fn discounted_fee_bps(
+    fee_bps: u16,
+    fee_discount: Option<&FeeDiscountConfig>,
+    discount_token_account: Option<&TokenAccount>,
+) -> Result<u16> {
+    match (fee_discount, discount_token_account) {
+        (Some(fee_discount), Some(token_account)) => {
+            require_keys_eq!(
+                token_account.mint,
+                fee_discount.discount_mint,
+                StableFunError::InvalidFeeDiscount
+            );
+            Ok(fee_discount.discounted_fee_bps(fee_bps, token_account.amount))
+        }
+        _ => Ok(fee_bps),
+    }
+}
+
+#[event]
+pub struct FeeDiscountsUpdated {
+    pub stablecoin_mint: Pubkey,
+    pub discount_mint: Pubkey,
+    pub tiers: Vec<FeeDiscountTier>,
+    pub timestamp: i64,
+}
@@ -0,0 +1,237 @@
+use anchor_lang::prelude::*;
+use switchboard_solana::AggregatorAccountData;
+
+use crate::state::{
+    weighted_average_maturity, CollateralRegistry, IssuerProfile, MaturityHolding, SavingsVault,
+    StablecoinMint, StablecoinVault, REBASE_INDEX_SCALE,
+};
+use crate::constants::{
+    BOND_TERMS_SEED, COLLATERAL_REGISTRY_SEED, ISSUER_PROFILE_SEED, SAVINGS_VAULT_SEED, VAULT_SEED,
+};
+use crate::error::StableFunError;
+use crate::utils::oracle::OracleService;
+use crate::utils::stablebond::StablebondService;
+
+/// Bumped whenever the `StablecoinSummary` layout changes
+pub const SUMMARY_VERSION: u8 = 5;
+
+pub const FLAG_MINT_PAUSED: u8 = 1 << 0;
+pub const FLAG_REDEEM_PAUSED: u8 = 1 << 1;
+pub const FLAG_TEST_MODE: u8 = 1 << 2;
+pub const FLAG_PRICE_VALID: u8 = 1 << 3;
+pub const FLAG_VERIFIED: u8 = 1 << 4;
+
+#[derive(Accounts)]
+pub struct GetState<'info> {
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    /// The Switchboard V3 aggregator account
+    #[account(
+        constraint = price_feed.key() == stablecoin_mint.price_feed @ StableFunError::InvalidOracle
+    )]
+    pub price_feed: AccountLoader<'info, AggregatorAccountData>,
+
+    /// FX feed composed with the bond NAV `price_feed`, on coins that set one
+    #[account(
+        constraint = fx_feed.key() == stablecoin_mint.fx_feed @ StableFunError::InvalidOracle
+    )]
+    pub fx_feed: Option<AccountLoader<'info, AggregatorAccountData>>,
+
+    /// Only needed for coins with a savings pool
+    #[account(
+        seeds = [SAVINGS_VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = savings_vault.bump
+    )]
+    pub savings_vault: Option<Box<Account<'info, SavingsVault>>>,
+
+    /// Only needed when the coin's issuer has published a profile
+    #[account(
+        seeds = [ISSUER_PROFILE_SEED, stablecoin_mint.authority.as_ref()],
+        bump = issuer_profile.bump
+    )]
+    pub issuer_profile: Option<Box<Account<'info, IssuerProfile>>>,
+
+    /// Only needed once the coin has a collateral registry
+    #[account(
+        seeds = [COLLATERAL_REGISTRY_SEED, stablecoin_mint.key().as_ref()],
+        bump = collateral_registry.bump
+    )]
+    pub collateral_registry: Option<Box<Account<'info, CollateralRegistry>>>,
+
+    /// CHECK: The coin's `BondTerms` PDA, giving the stablebond's maturity;
+    /// only needed once terms are set
+    #[account(seeds = [BOND_TERMS_SEED, stablecoin_mint.key().as_ref()], bump)]
+    pub bond_terms: Option<UncheckedAccount<'info>>,
+}
+
+/// Fixed layout snapshot of a stablecoin, returned as instruction return data.
+/// New fields are only ever appended, together with a `SUMMARY_VERSION` bump.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct StablecoinSummary {
+    pub version: u8,
+    pub stablecoin_mint: Pubkey,
+    pub token_mint: Pubkey,
+    pub current_supply: u64,
+    pub max_supply: u64,
+    pub total_collateral: u64,
+    pub total_value_locked: u64,
+    /// Collateral ratio in basis points
+    pub current_ratio: u16,
+    pub min_collateral_ratio: u16,
+    pub liquidation_threshold_bps: u16,
+    pub fee_basis_points: u16,
+    /// Bitmask of the `FLAG_*` constants
+    pub flags: u8,
+    /// Last oracle price with `PRICE_DECIMALS` decimals (0 if unreadable)
+    pub last_price: u64,
+    pub last_price_updated: i64,
+    /// Underlying units per token scaled by `REBASE_INDEX_SCALE`
+    /// (exactly the scale for non-rebasing coins)
+    pub rebase_index: u128,
+    /// `current_supply` in underlying units
+    pub underlying_supply: u64,
+    /// Savings pool tokens per share scaled by `SAVINGS_RATE_SCALE`
+    /// (0 without a savings pool)
+    pub savings_exchange_rate: u64,
+    /// The issuer's `IssuerProfile` (default pubkey when none was passed)
+    pub issuer_profile: Pubkey,
+    /// Collateral-weighted average time to maturity, in seconds (0 when
+    /// no bond terms were passed)
+    pub weighted_average_maturity: i64,
+}
+
+impl StablecoinSummary {
+    pub fn new(
+        stablecoin_key: Pubkey,
+        stablecoin_mint: &StablecoinMint,
+        vault: &StablecoinVault,
+        price: Option<(u64, i64)>,
+        savings_vault: Option<&SavingsVault>,
+        issuer_profile: Option<Pubkey>,
+    ) -> Self {
+        let settings = &stablecoin_mint.settings;
+        let mut flags = 0;
+        if settings.mint_paused {
+            flags |= FLAG_MINT_PAUSED;
+        }
+        if settings.redeem_paused {
+            flags |= FLAG_REDEEM_PAUSED;
+        }
+        if stablecoin_mint.test_mode {
+            flags |= FLAG_TEST_MODE;
+        }
+        if price.is_some() {
+            flags |= FLAG_PRICE_VALID;
+        }
+        if stablecoin_mint.verified {
+            flags |= FLAG_VERIFIED;
+        }
+        let (last_price, last_price_updated) = price.unwrap_or_default();
+        let rebase_index = if stablecoin_mint.is_rebasing() {
+            stablecoin_mint.rebase_index
+        } else {
+            REBASE_INDEX_SCALE
+        };
+        let underlying_supply = stablecoin_mint
+            .to_underlying_amount(stablecoin_mint.current_supply)
+            .unwrap_or(u64::MAX);
+
+        Self {
+            version: SUMMARY_VERSION,
+            stablecoin_mint: stablecoin_key,
+            token_mint: stablecoin_mint.token_mint,
+            current_supply: stablecoin_mint.current_supply,
+            max_supply: settings.max_supply,
+            total_collateral: vault.total_collateral,
+            total_value_locked: vault.total_value_locked,
+            current_ratio: vault.current_ratio,
+            min_collateral_ratio: settings.min_collateral_ratio,
+            liquidation_threshold_bps: settings.liquidation_threshold_bps,
+            fee_basis_points: settings.fee_basis_points,
+            flags,
+            last_price,
+            last_price_updated,
+            rebase_index,
+            underlying_supply,
+            savings_exchange_rate: savings_vault.map_or(0, SavingsVault::exchange_rate),
+            issuer_profile: issuer_profile.unwrap_or_default(),
+            weighted_average_maturity: 0,
+        }
+    }
+}
+
+/// Read-only: returns the summary through return data. An unreadable or
+/// stale feed does not fail the call, it clears `FLAG_PRICE_VALID` instead.
+pub fn get_state(ctx: Context<GetState>) -> Result<StablecoinSummary> {
+    let price = OracleService::read_collateral_price(
+        &ctx.accounts.stablecoin_mint,
+        &ctx.accounts.price_feed,
+        ctx.accounts.fx_feed.as_ref(),
+    )
+    .ok();
+
+    let mut summary = StablecoinSummary::new(
+        ctx.accounts.stablecoin_mint.key(),
+        &ctx.accounts.stablecoin_mint,
+        &ctx.accounts.vault,
+        price,
+        ctx.accounts.savings_vault.as_deref().map(|savings_vault| &**savings_vault),
+        ctx.accounts.issuer_profile.as_ref().map(|issuer_profile| issuer_profile.key()),
+    );
+
+    // The stablebond plus every registered type, each at its maturity
+    if let Some(bond_terms) = ctx.accounts.bond_terms.as_ref() {
+        let primary_maturity = StablebondService::get_bond_terms_data(
+            bond_terms,
+            &ctx.accounts.stablecoin_mint.stablebond_mint,
+        )?
+        .map_or(0, |stablebond| stablebond.maturity_timestamp);
+        let total_collateral = ctx.accounts.vault.total_collateral;
+        let holdings = match ctx.accounts.collateral_registry.as_ref() {
+            Some(registry) => registry.maturity_holdings(total_collateral, primary_maturity)?,
+            None => vec![MaturityHolding {
+                book_value: total_collateral,
+                maturity_timestamp: primary_maturity,
+            }],
+        };
+        summary.weighted_average_maturity = weighted_average_maturity(&holdings, Clock::get()?.unix_timestamp);
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_flags() {
+        let mut stablecoin_mint = StablecoinMint::default();
+        stablecoin_mint.settings.redeem_paused = true;
+        stablecoin_mint.test_mode = true;
+        let vault = StablecoinVault::new(Pubkey::default(), Pubkey::default(), Pubkey::default(), 255);
+
+        let summary = StablecoinSummary::new(Pubkey::default(), &stablecoin_mint, &vault, None, None, None);
+        assert_eq!(summary.flags, FLAG_REDEEM_PAUSED | FLAG_TEST_MODE);
+        assert_eq!(summary.last_price, 0);
+
+        let summary = StablecoinSummary::new(Pubkey::default(), &stablecoin_mint, &vault, Some((1_000_000, 42)), None, None);
+        assert_eq!(summary.flags & FLAG_PRICE_VALID, FLAG_PRICE_VALID);
+        assert_eq!(summary.flags & FLAG_VERIFIED, 0);
+        assert_eq!(summary.version, SUMMARY_VERSION);
+        assert_eq!(summary.rebase_index, REBASE_INDEX_SCALE);
+        assert_eq!(summary.savings_exchange_rate, 0);
+        assert_eq!(summary.issuer_profile, Pubkey::default());
+        assert_eq!(summary.weighted_average_maturity, 0);
+
+        let bytes = summary.try_to_vec().unwrap();
+        assert_eq!(StablecoinSummary::try_from_slice(&bytes).unwrap(), summary);
+    }
+}
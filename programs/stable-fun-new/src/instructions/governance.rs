@@ -0,0 +1,175 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{ProtocolConfig, StablecoinMint};
+use crate::constants::PROTOCOL_CONFIG_SEED;
+use crate::error::StableFunError;
+
+#[derive(Accounts)]
+pub struct ProposeGovernanceHandover<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint,
+        constraint = !stablecoin_mint.governance_enabled @ StableFunError::InvalidGovernance,
+        constraint = stablecoin_mint.council == Pubkey::default() @ StableFunError::CouncilApprovalRequired
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    /// CHECK: Governance account of the realm taking over; only its owner is
+    /// checked here, it proves itself by signing `accept_governance`
+    #[account(
+        owner = governance_program.key() @ StableFunError::InvalidGovernance
+    )]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: The SPL-Governance deployment the realm lives in
+    #[account(executable)]
+    pub governance_program: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Queues a handover of the coin to an SPL-Governance governance account.
+/// Nothing changes until the realm passes a proposal executing
+/// `accept_governance`, so a mistyped address cannot strand the coin. A
+/// governed coin moves realms by releasing to a key first.
+pub fn propose_governance_handover(ctx: Context<ProposeGovernanceHandover>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
+    stablecoin_mint.pending_governance = ctx.accounts.governance.key();
+    stablecoin_mint.governance_program = ctx.accounts.governance_program.key();
+    stablecoin_mint.record_admin_action(now);
+
+    emit!(GovernanceHandoverProposed {
+        stablecoin_mint: stablecoin_mint.key(),
+        authority: stablecoin_mint.authority,
+        governance: stablecoin_mint.pending_governance,
+        governance_program: stablecoin_mint.governance_program,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AcceptGovernance<'info> {
+    #[account(
+        constraint = governance.key() == stablecoin_mint.pending_governance @ StableFunError::InvalidGovernance,
+        constraint = *governance.owner == stablecoin_mint.governance_program @ StableFunError::InvalidGovernance
+    )]
+    pub governance: Signer<'info>,
+
+    #[account(mut)]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+}
+
+/// Executed by the realm's proposal: the governance account becomes the
+/// authority and settings changes require its signature from then on.
+pub fn accept_governance(ctx: Context<AcceptGovernance>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
+    let previous_authority = stablecoin_mint.authority;
+    stablecoin_mint.authority = ctx.accounts.governance.key();
+    stablecoin_mint.governance_enabled = true;
+    stablecoin_mint.pending_governance = Pubkey::default();
+    // The previous issuer's delegate and verification go with them
+    stablecoin_mint.pauser = Pubkey::default();
+    stablecoin_mint.verified = false;
+    stablecoin_mint.record_admin_action(now);
+
+    emit!(GovernanceAccepted {
+        stablecoin_mint: stablecoin_mint.key(),
+        previous_authority,
+        governance: stablecoin_mint.authority,
+        governance_program: stablecoin_mint.governance_program,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ReleaseGovernance<'info> {
+    pub governance: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_mint.governance_enabled @ StableFunError::InvalidGovernance,
+        constraint = stablecoin_mint.authority == governance.key() @ StableFunError::UnauthorizedMint,
+        constraint = stablecoin_mint.accepts_settings_signer(governance.owner) @ StableFunError::GovernanceSignatureRequired
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+}
+
+/// Executed by the realm's proposal: hands the coin back to a plain key.
+pub fn release_governance(ctx: Context<ReleaseGovernance>, new_authority: Pubkey) -> Result<()> {
+    require!(new_authority != Pubkey::default(), StableFunError::InvalidGovernance);
+
+    let now = Clock::get()?.unix_timestamp;
+    let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
+    let governance = stablecoin_mint.authority;
+    stablecoin_mint.authority = new_authority;
+    stablecoin_mint.governance_enabled = false;
+    stablecoin_mint.governance_program = Pubkey::default();
+    stablecoin_mint.pauser = Pubkey::default();
+    stablecoin_mint.verified = false;
+    stablecoin_mint.record_admin_action(now);
+
+    emit!(GovernanceReleased {
+        stablecoin_mint: stablecoin_mint.key(),
+        governance,
+        new_authority,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct GovernanceHandoverProposed {
+    pub stablecoin_mint: Pubkey,
+    pub authority: Pubkey,
+    pub governance: Pubkey,
+    pub governance_program: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GovernanceAccepted {
+    pub stablecoin_mint: Pubkey,
+    pub previous_authority: Pubkey,
+    pub governance: Pubkey,
+    pub governance_program: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GovernanceReleased {
+    pub stablecoin_mint: Pubkey,
+    pub governance: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
@@ -1,15 +1,43 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{self, Token, TokenAccount};
+use anchor_spl::token_2022::spl_token_2022::{self, extension::ExtensionType};
+use anchor_spl::token_2022_extensions::{
+    interest_bearing_mint_initialize,
+    non_transferable_mint_initialize,
+    transfer_fee,
+    InterestBearingMintInitialize,
+    NonTransferableMintInitialize,
+};
+use anchor_spl::token_interface::{self, TokenInterface};
 use switchboard_solana::AggregatorAccountData;
 
-use crate::state::{StablecoinMint, StablecoinVault, StateAccount};  // Added StateAccount
-use crate::state::stablecoin::{StablecoinSettings, StablecoinStats};
+use crate::state::{ProtocolConfig, RebaseMode, ReservedNames, StablecoinMint, StablecoinVault, StateAccount, REBASE_INDEX_SCALE};  // Added StateAccount
+use crate::state::stablecoin::{
+    FreezeAuthorityMode, StablecoinSettings, StablecoinStats, STABLECOIN_VERSION,
+};
 use crate::error::StableFunError;
+use crate::constants::{
+    DEFAULT_LIQUIDATION_PENALTY_BPS,
+    DEFAULT_LIQUIDATION_THRESHOLD,
+    DEFAULT_LIQUIDATOR_BONUS_BPS,
+    FEE_VAULT_SEED,
+    FREEZE_AUTHORITY_SEED,
+    LOCKED_LIQUIDITY_SEED,
+    MAX_FEE_BPS,
+    MAX_INTEREST_RATE_BPS,
+    MIN_WITHDRAWAL_DELAY,
+    MINT_AUTHORITY_SEED,
+    PROTOCOL_CONFIG_SEED,
+    RESERVED_NAMES_SEED,
+};
+use crate::utils::attestation;
+use crate::utils::validation::ValidationService;
 
 // Constants
 pub const STABLECOIN_SEED: &[u8] = b"stablecoin";
 pub const VAULT_SEED: &[u8] = b"vault";
-pub const MINT_AUTHORITY_SEED: &[u8] = b"mint-authority";
 pub const MIN_NAME_LENGTH: usize = 3;
 pub const MIN_SYMBOL_LENGTH: usize = 2;
 pub const DEFAULT_COLLATERAL_RATIO: u16 = 15000; // 150%
@@ -38,13 +66,10 @@ pub struct Initialize<'info> {
     )]
     pub stablecoin_mint: Account<'info, StablecoinMint>,
 
-    #[account(
-        init,
-        payer = authority,
-        mint::decimals = 6,
-        mint::authority = mint_authority,
-    )]
-    pub token_mint: Box<Account<'info, token::Mint>>,
+    /// New mint keypair; created in the handler so Token-2022 extensions can
+    /// be initialized before the mint itself
+    #[account(mut)]
+    pub token_mint: Signer<'info>,
 
     #[account(
         seeds = [
@@ -56,6 +81,16 @@ pub struct Initialize<'info> {
     /// CHECK: PDA used as mint authority
     pub mint_authority: UncheckedAccount<'info>,
 
+    #[account(
+        seeds = [
+            FREEZE_AUTHORITY_SEED,
+            stablecoin_mint.key().as_ref()
+        ],
+        bump
+    )]
+    /// CHECK: PDA used as freeze authority in `FreezeAuthorityMode::Program`
+    pub freeze_authority: UncheckedAccount<'info>,
+
     #[account(mut)]
     pub stablebond_mint: Box<Account<'info, token::Mint>>,
 
@@ -71,46 +106,155 @@ pub struct Initialize<'info> {
     )]
     pub vault: Account<'info, StablecoinVault>,
 
+    /// Associated token account of the vault PDA, so clients can derive it offline
     #[account(
         init,
         payer = authority,
-        token::mint = stablebond_mint,
-        token::authority = vault,
+        associated_token::mint = stablebond_mint,
+        associated_token::authority = vault,
     )]
     pub vault_token_account: Box<Account<'info, TokenAccount>>,
 
-    /// Switchboard V3 aggregator account
+    /// Switchboard V3 aggregator account; the loader checks it is owned by
+    /// the Switchboard program
     #[account(
         constraint = 
             price_feed.load()?.get_result().is_ok() @ StableFunError::InvalidOracle
     )]
     pub price_feed: AccountLoader<'info, AggregatorAccountData>,
 
+    /// Caps demurrage and decides whether issuers must be attested
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    /// CHECK: Receives the launch fee; only needed when the protocol charges one
+    #[account(
+        mut,
+        address = protocol_config.protocol_treasury @ StableFunError::InvalidProtocolTreasury
+    )]
+    pub protocol_treasury: Option<UncheckedAccount<'info>>,
+
+    /// Names and symbols coins can't launch under; skipped when omitted
+    #[account(
+        seeds = [RESERVED_NAMES_SEED],
+        bump = reserved_names.bump
+    )]
+    pub reserved_names: Option<Box<Account<'info, ReservedNames>>>,
+
+    /// CHECK: Registry attestation of the authority, verified in the handler;
+    /// only needed when the protocol requires issuer attestations
+    pub issuer_attestation: Option<UncheckedAccount<'info>>,
+
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    /// Token program for the stablecoin mint (SPL Token or Token-2022)
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
     pub rent: Sysvar<'info, Rent>,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct TransferFeeParams {
+    pub transfer_fee_basis_points: u16,
+    pub maximum_fee: u64,
+}
+
+/// Token-2022 mint extensions; all must be unset for SPL Token mints.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct TokenExtensionParams {
+    pub transfer_fee: Option<TransferFeeParams>,
+    /// Test-mode coin: the mint is NonTransferable and the name is watermarked
+    pub non_transferable: bool,
+    /// Savings-enabled coin: InterestBearingMint with this starting rate,
+    /// later driven by harvested bond yield via `update_interest_rate`
+    pub interest_rate_bps: Option<i16>,
+}
+
+/// Coin options beyond the name, symbol and currency the PDA is derived from.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct InitializeParams {
+    pub icon_uri: String,
+    pub metadata_uri: String,
+    pub description: String,
+    pub freeze_authority_mode: FreezeAuthorityMode,
+    pub token_extensions: TokenExtensionParams,
+    pub demurrage_rate_bps: u16,
+    pub jurisdiction_flags: u32,
+}
+
+impl TokenExtensionParams {
+    pub fn extension_types(&self) -> Vec<ExtensionType> {
+        let mut extensions = Vec::new();
+        if self.transfer_fee.is_some() {
+            extensions.push(ExtensionType::TransferFeeConfig);
+        }
+        if self.non_transferable {
+            extensions.push(ExtensionType::NonTransferable);
+        }
+        if self.interest_rate_bps.is_some() {
+            extensions.push(ExtensionType::InterestBearingConfig);
+        }
+        extensions
+    }
+
+    pub fn validate(&self, token_program: &Pubkey) -> Result<()> {
+        require!(
+            self.extension_types().is_empty() || *token_program == spl_token_2022::ID,
+            StableFunError::TokenExtensionsRequireToken2022
+        );
+        if let Some(fee) = &self.transfer_fee {
+            require!(
+                fee.transfer_fee_basis_points <= MAX_FEE_BPS,
+                StableFunError::InvalidTransferFee
+            );
+        }
+        if let Some(rate) = self.interest_rate_bps {
+            require!(
+                (0..=MAX_INTEREST_RATE_BPS).contains(&rate),
+                StableFunError::InvalidInterestRate
+            );
+        }
+        Ok(())
+    }
+}
+
 pub fn handler(
     ctx: Context<Initialize>,
     name: String,
     symbol: String,
     target_currency: String,
     _initial_supply: u64,
+    params: InitializeParams,
 ) -> Result<()> {
-    // Validate inputs
-    require!(
-        name.len() >= MIN_NAME_LENGTH,
-        StableFunError::NameTooShort
-    );
-    require!(
-        symbol.len() >= MIN_SYMBOL_LENGTH,
-        StableFunError::SymbolTooShort
-    );
+    let InitializeParams {
+        icon_uri,
+        metadata_uri,
+        description,
+        freeze_authority_mode,
+        token_extensions,
+        demurrage_rate_bps,
+        jurisdiction_flags,
+    } = params;
+    // Validate inputs; name and symbol lengths are checked in characters by
+    // `validate_metadata`
     require!(
         !target_currency.is_empty(),
         StableFunError::InvalidCurrency
     );
+    let (name, normalized_symbol, target_currency) =
+        ValidationService::validate_metadata(&name, &symbol, &target_currency)?;
+    // The symbol is part of the PDA seeds, so it must already be canonical
+    require!(normalized_symbol == symbol, StableFunError::InvalidSymbol);
+    if let Some(reserved_names) = ctx.accounts.reserved_names.as_ref() {
+        reserved_names.check(&name, &symbol)?;
+    }
+    StablecoinMint::validate_uri(&icon_uri)?;
+    StablecoinMint::validate_uri(&metadata_uri)?;
+    StablecoinMint::validate_description(&description)?;
+    ValidationService::validate_demurrage(demurrage_rate_bps, &ctx.accounts.protocol_config)?;
 
     // Verify oracle with V3 validation
     let oracle = ctx.accounts.price_feed.load()?;
@@ -123,11 +267,58 @@ pub fn handler(
     );
 
     let clock = Clock::get()?;
+
+    // Regulated deployments only let attested issuers create coins
+    let protocol_config = &ctx.accounts.protocol_config;
+    let regulated = protocol_config.requires_issuer_attestation();
+    if regulated {
+        let attestation = ctx.accounts.issuer_attestation
+            .as_ref()
+            .ok_or(StableFunError::MissingAttestation)?;
+        attestation::verify_attestation(
+            attestation,
+            &protocol_config.attestation_program,
+            &protocol_config.attestation_credential,
+            &protocol_config.issuer_schema,
+            &ctx.accounts.authority.key(),
+            clock.unix_timestamp,
+        )?;
+    }
+    // Exclusions are enforced through holder attestations
+    require!(
+        jurisdiction_flags == 0 || (regulated && protocol_config.holder_schema != Pubkey::default()),
+        StableFunError::InvalidJurisdictionFlags
+    );
+    let launch_deposit = pay_launch_costs(&ctx)?;
     
+    // Create the SPL mint
+    let freeze_authority = match freeze_authority_mode {
+        FreezeAuthorityMode::None => None,
+        FreezeAuthorityMode::Program => Some(ctx.accounts.freeze_authority.key()),
+        FreezeAuthorityMode::Issuer => Some(ctx.accounts.authority.key()),
+    };
+    create_token_mint(
+        TokenMintAccounts {
+            payer: ctx.accounts.authority.to_account_info(),
+            token_mint: ctx.accounts.token_mint.to_account_info(),
+            token_program: ctx.accounts.stablecoin_token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        },
+        &token_extensions,
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.mint_authority.key(),
+        freeze_authority.as_ref(),
+    )?;
+
     // Initialize stablecoin mint account
     let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
     stablecoin_mint.authority = ctx.accounts.authority.key();
-    stablecoin_mint.name = name.clone();
+    stablecoin_mint.test_mode = token_extensions.non_transferable;
+    stablecoin_mint.name = if stablecoin_mint.test_mode {
+        StablecoinMint::watermark_name(&name)?
+    } else {
+        name.clone()
+    };
     stablecoin_mint.symbol = symbol.clone();
     stablecoin_mint.target_currency = target_currency.clone();
     stablecoin_mint.token_mint = ctx.accounts.token_mint.key();
@@ -136,15 +327,66 @@ pub fn handler(
     stablecoin_mint.vault = ctx.accounts.vault.key();
     stablecoin_mint.current_supply = 0;
     stablecoin_mint.created_at = clock.unix_timestamp;
-    stablecoin_mint.last_updated = clock.unix_timestamp;
+    stablecoin_mint.record_admin_action(clock.unix_timestamp);
+    stablecoin_mint.version = STABLECOIN_VERSION;
+    stablecoin_mint.icon_uri = icon_uri;
+    stablecoin_mint.metadata_uri = metadata_uri;
+    stablecoin_mint.description = description;
+    stablecoin_mint.freeze_authority_mode = freeze_authority_mode;
+    stablecoin_mint.regulated = regulated;
+    stablecoin_mint.jurisdiction_flags = jurisdiction_flags;
+    stablecoin_mint.launch_deposit = launch_deposit;
+    stablecoin_mint.bump = ctx.bumps.stablecoin_mint;
+    stablecoin_mint.mint_authority_bump = ctx.bumps.mint_authority;
+    stablecoin_mint.fee_vault_bump = Pubkey::find_program_address(
+        &[FEE_VAULT_SEED, stablecoin_mint.key().as_ref()],
+        &crate::ID,
+    ).1;
+    stablecoin_mint.freeze_authority_bump = ctx.bumps.freeze_authority;
+    stablecoin_mint.liquidity_lock_bump = Pubkey::find_program_address(
+        &[LOCKED_LIQUIDITY_SEED, stablecoin_mint.key().as_ref()],
+        &crate::ID,
+    ).1;
+    if demurrage_rate_bps > 0 {
+        stablecoin_mint.rebase_mode = RebaseMode::Demurrage;
+        stablecoin_mint.rebase_index = REBASE_INDEX_SCALE;
+        stablecoin_mint.last_rebase = clock.unix_timestamp;
+        stablecoin_mint.demurrage_rate_bps = demurrage_rate_bps;
+    }
 
     // Initialize settings with default values
     stablecoin_mint.settings = StablecoinSettings {
         min_collateral_ratio: DEFAULT_COLLATERAL_RATIO,
         fee_basis_points: 30, // 0.3% fee
-        max_supply: u64::MAX,
+        // New coins start in the unverified supply tier, without a bond
+        max_supply: ctx.accounts.protocol_config
+            .supply_cap(None, clock.unix_timestamp)
+            .min(ctx.accounts.protocol_config.bonded_supply_cap(None)),
         mint_paused: false,
         redeem_paused: false,
+        withdrawal_delay: MIN_WITHDRAWAL_DELAY,
+        same_tx_guard: false,
+        large_redeem_threshold_bps: 0,
+        reveal_delay_slots: 0,
+        liquidation_threshold_bps: DEFAULT_LIQUIDATION_THRESHOLD,
+        liquidation_penalty_bps: DEFAULT_LIQUIDATION_PENALTY_BPS,
+        liquidator_bonus_bps: DEFAULT_LIQUIDATOR_BONUS_BPS,
+        liquidation_grace_seconds: 0,
+        grace_override_bps: 0,
+        surplus_buffer: 0,
+        oracle_failure_threshold: 0,
+        unpause_cooloff_seconds: 0,
+        supply_drift_tolerance: 0,
+        cached_price_max_amount: 0,
+        cached_price_max_age_slots: 0,
+        yield_fee_bps: 0,
+        liquidity_buffer_bps: 0,
+        soft_fail_rejections: false,
+        min_position_debt: 0,
+        top_level_only: false,
+        throughput_capacity: 0,
+        throughput_refill_per_second: 0,
+        reporting_threshold: 0,
     };
 
     // Initialize statistics
@@ -176,6 +418,138 @@ pub fn handler(
     Ok(())
 }
 
+/// Pays the protocol's launch fee to the treasury and its refundable deposit
+/// onto the stablecoin account, returning the deposit held.
+#[inline(never)]
+fn pay_launch_costs(ctx: &Context<Initialize>) -> Result<u64> {
+    let protocol_config = &ctx.accounts.protocol_config;
+
+    if protocol_config.launch_fee_lamports > 0 {
+        let treasury = ctx.accounts.protocol_treasury
+            .as_ref()
+            .ok_or(StableFunError::InvalidProtocolTreasury)?;
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: treasury.to_account_info(),
+                },
+            ),
+            protocol_config.launch_fee_lamports,
+        )?;
+    }
+
+    let deposit = protocol_config.launch_deposit_lamports;
+    if deposit > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: ctx.accounts.stablecoin_mint.to_account_info(),
+                },
+            ),
+            deposit,
+        )?;
+    }
+
+    Ok(deposit)
+}
+
+/// Accounts needed to create a stablecoin mint.
+pub struct TokenMintAccounts<'info> {
+    pub payer: AccountInfo<'info>,
+    pub token_mint: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+/// Creates the stablecoin mint account, initializes any requested extensions
+/// and then the mint itself. The issuer controls extension configuration; the
+/// program's mint authority PDA mints, withdraws withheld fees and sets the
+/// interest rate.
+pub(crate) fn create_token_mint<'info>(
+    accounts: TokenMintAccounts<'info>,
+    token_extensions: &TokenExtensionParams,
+    issuer: &Pubkey,
+    mint_authority: &Pubkey,
+    freeze_authority: Option<&Pubkey>,
+) -> Result<()> {
+    let token_program = accounts.token_program;
+    token_extensions.validate(token_program.key)?;
+
+    let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(
+        &token_extensions.extension_types(),
+    )?;
+    system_program::create_account(
+        CpiContext::new(
+            accounts.system_program,
+            system_program::CreateAccount {
+                from: accounts.payer,
+                to: accounts.token_mint.clone(),
+            },
+        ),
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        token_program.key,
+    )?;
+
+    if let Some(fee) = &token_extensions.transfer_fee {
+        // Withheld fees can only be withdrawn by the program, into the fee vault
+        transfer_fee::transfer_fee_initialize(
+            CpiContext::new(
+                token_program.clone(),
+                transfer_fee::TransferFeeInitialize {
+                    token_program_id: token_program.clone(),
+                    mint: accounts.token_mint.clone(),
+                },
+            ),
+            Some(issuer),
+            Some(mint_authority),
+            fee.transfer_fee_basis_points,
+            fee.maximum_fee,
+        )?;
+    }
+
+    if token_extensions.non_transferable {
+        non_transferable_mint_initialize(CpiContext::new(
+            token_program.clone(),
+            NonTransferableMintInitialize {
+                token_program_id: token_program.clone(),
+                mint: accounts.token_mint.clone(),
+            },
+        ))?;
+    }
+
+    if let Some(rate) = token_extensions.interest_rate_bps {
+        // Only the program may move the rate, and only from harvested yield
+        interest_bearing_mint_initialize(
+            CpiContext::new(
+                token_program.clone(),
+                InterestBearingMintInitialize {
+                    token_program_id: token_program.clone(),
+                    mint: accounts.token_mint.clone(),
+                },
+            ),
+            Some(*mint_authority),
+            rate,
+        )?;
+    }
+
+    token_interface::initialize_mint2(
+        CpiContext::new(
+            token_program,
+            token_interface::InitializeMint2 {
+                mint: accounts.token_mint,
+            },
+        ),
+        6,
+        mint_authority,
+        freeze_authority,
+    )
+}
+
 #[event]
 pub struct StablecoinInitialized {
     pub stablecoin_mint: Pubkey,
@@ -219,11 +593,86 @@ mod tests {
             max_supply: u64::MAX,
             mint_paused: false,
             redeem_paused: false,
+            withdrawal_delay: MIN_WITHDRAWAL_DELAY,
+            same_tx_guard: false,
+            large_redeem_threshold_bps: 0,
+            reveal_delay_slots: 0,
+            liquidation_threshold_bps: DEFAULT_LIQUIDATION_THRESHOLD,
+            liquidation_penalty_bps: DEFAULT_LIQUIDATION_PENALTY_BPS,
+            liquidator_bonus_bps: DEFAULT_LIQUIDATOR_BONUS_BPS,
+            liquidation_grace_seconds: 0,
+            grace_override_bps: 0,
+            surplus_buffer: 0,
+            oracle_failure_threshold: 0,
+            unpause_cooloff_seconds: 0,
+            supply_drift_tolerance: 0,
+            cached_price_max_amount: 0,
+            cached_price_max_age_slots: 0,
+            yield_fee_bps: 0,
+            liquidity_buffer_bps: 0,
+            soft_fail_rejections: false,
+            min_position_debt: 0,
+            top_level_only: false,
+            throughput_capacity: 0,
+            throughput_refill_per_second: 0,
+            reporting_threshold: 0,
         };
 
         assert_eq!(settings.min_collateral_ratio, 15000);
         assert_eq!(settings.fee_basis_points, 30);
         assert!(!settings.mint_paused);
         assert!(!settings.redeem_paused);
+        assert_eq!(settings.withdrawal_delay, 60);
+        assert!(settings.liquidation_threshold_bps < settings.min_collateral_ratio);
+    }
+
+    #[test]
+    fn test_token_extension_params() {
+        let legacy = TokenExtensionParams::default();
+        assert!(legacy.extension_types().is_empty());
+        assert!(legacy.validate(&token::ID).is_ok());
+
+        let with_fee = TokenExtensionParams {
+            transfer_fee: Some(TransferFeeParams {
+                transfer_fee_basis_points: 25,
+                maximum_fee: 1_000_000,
+            }),
+            non_transferable: false,
+            interest_rate_bps: None,
+        };
+        assert_eq!(with_fee.extension_types(), vec![ExtensionType::TransferFeeConfig]);
+        assert!(with_fee.validate(&spl_token_2022::ID).is_ok());
+        assert!(with_fee.validate(&token::ID).is_err());
+
+        let too_high = TokenExtensionParams {
+            transfer_fee: Some(TransferFeeParams {
+                transfer_fee_basis_points: MAX_FEE_BPS + 1,
+                maximum_fee: 0,
+            }),
+            non_transferable: false,
+            interest_rate_bps: None,
+        };
+        assert!(too_high.validate(&spl_token_2022::ID).is_err());
+
+        let test_mode = TokenExtensionParams {
+            non_transferable: true,
+            ..Default::default()
+        };
+        assert_eq!(test_mode.extension_types(), vec![ExtensionType::NonTransferable]);
+        assert!(test_mode.validate(&token::ID).is_err());
+
+        let savings = TokenExtensionParams {
+            interest_rate_bps: Some(450),
+            ..Default::default()
+        };
+        assert_eq!(savings.extension_types(), vec![ExtensionType::InterestBearingConfig]);
+        assert!(savings.validate(&spl_token_2022::ID).is_ok());
+        for rate in [-1, MAX_INTEREST_RATE_BPS + 1] {
+            let invalid = TokenExtensionParams {
+                interest_rate_bps: Some(rate),
+                ..Default::default()
+            };
+            assert!(invalid.validate(&spl_token_2022::ID).is_err());
+        }
     }
 }
\ No newline at end of file
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_2022_extensions::{
+    interest_bearing_mint_update_rate,
+    InterestBearingMintUpdateRate,
+};
+use anchor_spl::token_interface::Mint;
+
+use crate::state::{ProtocolConfig, StablecoinMint, SystemAccounting};
+use crate::constants::{
+    ACCOUNTING_SEED, MAX_INTEREST_RATE_BPS, MINT_AUTHORITY_SEED, PROTOCOL_CONFIG_SEED,
+};
+use crate::error::StableFunError;
+
+#[derive(Accounts)]
+pub struct UpdateInterestRate<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [ACCOUNTING_SEED, stablecoin_mint.key().as_ref()],
+        bump = accounting.bump
+    )]
+    pub accounting: Box<Account<'info, SystemAccounting>>,
+
+    #[account(
+        mut,
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: PDA set as the mint's interest rate authority
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, stablecoin_mint.key().as_ref()],
+        bump = stablecoin_mint.mint_authority_bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+/// Sets the mint's displayed interest rate to the bond yield harvested since
+/// the previous update, annualized over the outstanding supply.
+pub fn update_interest_rate(ctx: Context<UpdateInterestRate>) -> Result<()> {
+    let clock = Clock::get()?;
+    let stablecoin_mint = &ctx.accounts.stablecoin_mint;
+    let accounting = &mut ctx.accounts.accounting;
+
+    let yield_bps = accounting.annualized_yield_bps(
+        stablecoin_mint.current_supply,
+        stablecoin_mint.created_at,
+        clock.unix_timestamp,
+    )?;
+    let rate = yield_bps.min(MAX_INTEREST_RATE_BPS as u64) as i16;
+
+    let stablecoin_key = stablecoin_mint.key();
+    interest_bearing_mint_update_rate(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            InterestBearingMintUpdateRate {
+                token_program_id: ctx.accounts.token_program.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                rate_authority: ctx.accounts.mint_authority.to_account_info(),
+            },
+            &[&[
+                MINT_AUTHORITY_SEED,
+                stablecoin_key.as_ref(),
+                &[ctx.accounts.stablecoin_mint.mint_authority_bump],
+            ]],
+        ),
+        rate,
+    )?;
+
+    let harvested = accounting.harvested_yield - accounting.yield_checkpoint;
+    accounting.checkpoint_yield(clock.unix_timestamp);
+
+    emit!(InterestRateUpdated {
+        stablecoin_mint: stablecoin_key,
+        rate_bps: rate,
+        harvested_yield: harvested,
+        supply: stablecoin_mint.current_supply,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct InterestRateUpdated {
+    pub stablecoin_mint: Pubkey,
+    pub rate_bps: i16,
+    pub harvested_yield: u64,
+    pub supply: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,226 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+
+use crate::state::{
+    Invoice, InvoiceStatus, ProtocolConfig, StablecoinMint, StateAccount, PAUSE_TRANSFERS,
+};
+use crate::constants::{INVOICE_SEED, PROTOCOL_CONFIG_SEED};
+use crate::error::StableFunError;
+
+#[derive(Accounts)]
+#[instruction(invoice_id: u64)]
+pub struct CreateInvoice<'info> {
+    #[account(mut)]
+    pub merchant: Signer<'info>,
+
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        init,
+        payer = merchant,
+        space = Invoice::LEN,
+        seeds = [INVOICE_SEED, stablecoin_mint.key().as_ref(), merchant.key().as_ref(), &invoice_id.to_le_bytes()],
+        bump
+    )]
+    pub invoice: Box<Account<'info, Invoice>>,
+
+    #[account(
+        constraint = merchant_token_account.mint == stablecoin_mint.token_mint @ StableFunError::InvalidTokenAccount
+    )]
+    pub merchant_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_TRANSFERS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_invoice(
+    ctx: Context<CreateInvoice>,
+    invoice_id: u64,
+    amount: u64,
+    expires_at: i64,
+    memo_hash: [u8; 32],
+) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(amount > 0, StableFunError::InvalidAmount);
+    require!(expires_at > clock.unix_timestamp, StableFunError::InvalidInvoice);
+
+    let invoice = &mut ctx.accounts.invoice;
+    invoice.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    invoice.merchant = ctx.accounts.merchant.key();
+    invoice.invoice_id = invoice_id;
+    invoice.merchant_token_account = ctx.accounts.merchant_token_account.key();
+    invoice.amount = amount;
+    invoice.memo_hash = memo_hash;
+    invoice.created_at = clock.unix_timestamp;
+    invoice.expires_at = expires_at;
+    invoice.status = InvoiceStatus::Open;
+    invoice.bump = ctx.bumps.invoice;
+
+    emit!(InvoiceCreated {
+        stablecoin_mint: invoice.stablecoin_mint,
+        invoice: invoice.key(),
+        merchant: invoice.merchant,
+        invoice_id,
+        amount,
+        expires_at,
+        memo_hash,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct PayInvoice<'info> {
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [INVOICE_SEED, invoice.stablecoin_mint.as_ref(), invoice.merchant.as_ref(), &invoice.invoice_id.to_le_bytes()],
+        bump = invoice.bump
+    )]
+    pub invoice: Box<Account<'info, Invoice>>,
+
+    #[account(
+        constraint = stablecoin_mint.key() == invoice.stablecoin_mint @ StableFunError::InvalidInvoice
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = payer_token_account.mint == token_mint.key() @ StableFunError::InvalidTokenAccount,
+        constraint = payer_token_account.owner == payer.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub payer_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = merchant_token_account.key() == invoice.merchant_token_account @ StableFunError::InvalidTokenAccount
+    )]
+    pub merchant_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_TRANSFERS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Settles an open, unexpired invoice in full.
+pub fn pay(ctx: Context<PayInvoice>) -> Result<()> {
+    let clock = Clock::get()?;
+    let payer = ctx.accounts.payer.key();
+    ctx.accounts.invoice.mark_paid(payer, clock.unix_timestamp)?;
+
+    let invoice = &ctx.accounts.invoice;
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.payer_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.merchant_token_account.to_account_info(),
+                authority: ctx.accounts.payer.to_account_info(),
+            },
+        ),
+        invoice.amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    emit!(InvoicePaid {
+        stablecoin_mint: invoice.stablecoin_mint,
+        invoice: invoice.key(),
+        merchant: invoice.merchant,
+        invoice_id: invoice.invoice_id,
+        payer,
+        amount: invoice.amount,
+        memo_hash: invoice.memo_hash,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelInvoice<'info> {
+    #[account(mut)]
+    pub merchant: Signer<'info>,
+
+    #[account(
+        mut,
+        close = merchant,
+        seeds = [INVOICE_SEED, invoice.stablecoin_mint.as_ref(), merchant.key().as_ref(), &invoice.invoice_id.to_le_bytes()],
+        bump = invoice.bump,
+        constraint = invoice.is_open() @ StableFunError::InvoiceNotOpen
+    )]
+    pub invoice: Box<Account<'info, Invoice>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_TRANSFERS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+}
+
+/// Withdraws an unpaid invoice and returns its rent to the merchant.
+pub fn cancel_invoice(ctx: Context<CancelInvoice>) -> Result<()> {
+    let invoice = &ctx.accounts.invoice;
+
+    emit!(InvoiceCancelled {
+        stablecoin_mint: invoice.stablecoin_mint,
+        invoice: invoice.key(),
+        merchant: invoice.merchant,
+        invoice_id: invoice.invoice_id,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct InvoiceCreated {
+    pub stablecoin_mint: Pubkey,
+    pub invoice: Pubkey,
+    pub merchant: Pubkey,
+    pub invoice_id: u64,
+    pub amount: u64,
+    pub expires_at: i64,
+    pub memo_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InvoicePaid {
+    pub stablecoin_mint: Pubkey,
+    pub invoice: Pubkey,
+    pub merchant: Pubkey,
+    pub invoice_id: u64,
+    pub payer: Pubkey,
+    pub amount: u64,
+    pub memo_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InvoiceCancelled {
+    pub stablecoin_mint: Pubkey,
+    pub invoice: Pubkey,
+    pub merchant: Pubkey,
+    pub invoice_id: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,352 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+use crate::state::{IssuerBond, ProtocolConfig, StablecoinMint, StateAccount};
+use crate::constants::{
+    ISSUER_BOND_SEED, ISSUER_BOND_UNBONDING_SECONDS, ISSUER_BOND_VAULT_SEED, PROTOCOL_CONFIG_SEED,
+};
+use crate::error::StableFunError;
+use crate::utils::validation::ValidationService;
+
+#[derive(Accounts)]
+pub struct PostBond<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = IssuerBond::LEN,
+        seeds = [ISSUER_BOND_SEED, stablecoin_mint.key().as_ref()],
+        bump
+    )]
+    pub issuer_bond: Box<Account<'info, IssuerBond>>,
+
+    #[account(
+        constraint = bond_mint.key() == protocol_config.bond_mint @ StableFunError::InvalidMint
+    )]
+    pub bond_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [ISSUER_BOND_VAULT_SEED, stablecoin_mint.key().as_ref(), bond_mint.key().as_ref()],
+        bump,
+        token::mint = bond_mint,
+        token::authority = issuer_bond
+    )]
+    pub bond_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = authority_token_account.mint == bond_mint.key() @ StableFunError::InvalidTokenAccount,
+        constraint = authority_token_account.owner == authority.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub authority_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Adds `amount` of the protocol's bond token to the coin's bond. An empty
+/// bond takes on the current bond token; a funded one keeps its own.
+pub fn post_bond(ctx: Context<PostBond>, amount: u64) -> Result<()> {
+    require!(amount > 0, StableFunError::InvalidAmount);
+
+    let issuer_bond = &mut ctx.accounts.issuer_bond;
+    if !issuer_bond.is_initialized() {
+        issuer_bond.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+        issuer_bond.bump = ctx.bumps.issuer_bond;
+    }
+    if issuer_bond.bonded == 0 && issuer_bond.unbonding == 0 {
+        issuer_bond.bond_mint = ctx.accounts.bond_mint.key();
+        issuer_bond.bond_vault = ctx.accounts.bond_vault.key();
+    }
+    require_keys_eq!(
+        issuer_bond.bond_vault,
+        ctx.accounts.bond_vault.key(),
+        StableFunError::InvalidMint
+    );
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.authority_token_account.to_account_info(),
+                to: ctx.accounts.bond_vault.to_account_info(),
+                authority: ctx.accounts.authority.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let issuer_bond = &mut ctx.accounts.issuer_bond;
+    issuer_bond.bonded = issuer_bond.bonded
+        .checked_add(amount)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+
+    emit!(IssuerBondPosted {
+        stablecoin_mint: issuer_bond.stablecoin_mint,
+        bond_mint: issuer_bond.bond_mint,
+        amount,
+        bonded: issuer_bond.bonded,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RequestBondWithdrawal<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [ISSUER_BOND_SEED, stablecoin_mint.key().as_ref()],
+        bump = issuer_bond.bump
+    )]
+    pub issuer_bond: Box<Account<'info, IssuerBond>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+}
+
+/// Starts unbonding `amount`. The rest must still cover the coin's
+/// `max_supply`, so an issuer lowers the cap before pulling the bond. The
+/// unbonding period leaves time to slash for misconduct found meanwhile.
+pub fn request_bond_withdrawal(ctx: Context<RequestBondWithdrawal>, amount: u64) -> Result<()> {
+    let issuer_bond: &mut IssuerBond = &mut ctx.accounts.issuer_bond;
+    require!(amount > 0 && amount <= issuer_bond.bonded, StableFunError::InvalidAmount);
+    issuer_bond.bonded -= amount;
+    ValidationService::validate_bonded_supply(
+        ctx.accounts.stablecoin_mint.settings.max_supply,
+        &ctx.accounts.protocol_config,
+        Some(&*issuer_bond),
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    issuer_bond.unbonding += amount;
+    issuer_bond.unbonding_ends_at = now.saturating_add(ISSUER_BOND_UNBONDING_SECONDS);
+
+    emit!(IssuerBondUnbonding {
+        stablecoin_mint: issuer_bond.stablecoin_mint,
+        amount,
+        unbonding: issuer_bond.unbonding,
+        unbonding_ends_at: issuer_bond.unbonding_ends_at,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawBond<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [ISSUER_BOND_SEED, stablecoin_mint.key().as_ref()],
+        bump = issuer_bond.bump
+    )]
+    pub issuer_bond: Box<Account<'info, IssuerBond>>,
+
+    #[account(
+        mut,
+        constraint = bond_vault.key() == issuer_bond.bond_vault @ StableFunError::InvalidTokenAccount
+    )]
+    pub bond_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = authority_token_account.mint == issuer_bond.bond_mint @ StableFunError::InvalidTokenAccount,
+        constraint = authority_token_account.owner == authority.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub authority_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Returns every unbonded token to the issuer.
+pub fn withdraw_bond(ctx: Context<WithdrawBond>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let issuer_bond = &ctx.accounts.issuer_bond;
+    let amount = issuer_bond.unbonding;
+    require!(amount > 0, StableFunError::InvalidAmount);
+    require!(now >= issuer_bond.unbonding_ends_at, StableFunError::BondUnbonding);
+
+    let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.bond_vault.to_account_info(),
+                to: ctx.accounts.authority_token_account.to_account_info(),
+                authority: issuer_bond.to_account_info(),
+            },
+            &[&[ISSUER_BOND_SEED, stablecoin_key.as_ref(), &[issuer_bond.bump]]],
+        ),
+        amount,
+    )?;
+    ctx.accounts.issuer_bond.unbonding = 0;
+
+    emit!(IssuerBondWithdrawn {
+        stablecoin_mint: stablecoin_key,
+        authority: ctx.accounts.authority.key(),
+        amount,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SlashBond<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        has_one = admin @ StableFunError::UnauthorizedProtocolAdmin
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(mut)]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [ISSUER_BOND_SEED, stablecoin_mint.key().as_ref()],
+        bump = issuer_bond.bump
+    )]
+    pub issuer_bond: Box<Account<'info, IssuerBond>>,
+
+    #[account(
+        mut,
+        constraint = bond_vault.key() == issuer_bond.bond_vault @ StableFunError::InvalidTokenAccount
+    )]
+    pub bond_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = insurance_fund.key() == protocol_config.insurance_fund @ StableFunError::InvalidTokenAccount,
+        constraint = insurance_fund.mint == issuer_bond.bond_mint @ StableFunError::InvalidTokenAccount
+    )]
+    pub insurance_fund: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Executed by protocol governance once an issuer is found to have broken
+/// its peg or reserve commitments: moves up to `amount` of the bond into
+/// the insurance fund and revokes the verified badge. `reason` is the hash
+/// of the published findings. An underbonded coin's `max_supply` falls back
+/// to the bond threshold, or its current supply if that is higher.
+pub fn slash_bond(ctx: Context<SlashBond>, amount: u64, reason: [u8; 32]) -> Result<()> {
+    let slashed = ctx.accounts.issuer_bond.slash(amount);
+    require!(slashed > 0, StableFunError::InvalidAmount);
+
+    let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.bond_vault.to_account_info(),
+                to: ctx.accounts.insurance_fund.to_account_info(),
+                authority: ctx.accounts.issuer_bond.to_account_info(),
+            },
+            &[&[ISSUER_BOND_SEED, stablecoin_key.as_ref(), &[ctx.accounts.issuer_bond.bump]]],
+        ),
+        slashed,
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let bond_cap = ctx.accounts.protocol_config.bonded_supply_cap(Some(&**ctx.accounts.issuer_bond));
+    let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
+    stablecoin_mint.settings.max_supply = stablecoin_mint.settings.max_supply
+        .min(bond_cap)
+        .max(stablecoin_mint.current_supply);
+    stablecoin_mint.verified = false;
+    stablecoin_mint.last_updated = now;
+
+    emit!(IssuerBondSlashed {
+        stablecoin_mint: stablecoin_key,
+        amount: slashed,
+        bonded: ctx.accounts.issuer_bond.bonded,
+        insurance_fund: ctx.accounts.insurance_fund.key(),
+        reason,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct IssuerBondPosted {
+    pub stablecoin_mint: Pubkey,
+    pub bond_mint: Pubkey,
+    pub amount: u64,
+    pub bonded: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct IssuerBondUnbonding {
+    pub stablecoin_mint: Pubkey,
+    pub amount: u64,
+    pub unbonding: u64,
+    pub unbonding_ends_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct IssuerBondWithdrawn {
+    pub stablecoin_mint: Pubkey,
+    pub authority: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct IssuerBondSlashed {
+    pub stablecoin_mint: Pubkey,
+    pub amount: u64,
+    /// Left bonded after the slash
+    pub bonded: u64,
+    pub insurance_fund: Pubkey,
+    /// Hash of the published findings
+    pub reason: [u8; 32],
+    pub timestamp: i64,
+}
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{IssuerProfile, ProtocolConfig, StablecoinMint, StateAccount};
+use crate::constants::{ISSUER_PROFILE_SEED, PROTOCOL_CONFIG_SEED};
+use crate::error::StableFunError;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct IssuerProfileParams {
+    pub website: Option<String>,
+    pub twitter_handle_hash: Option<[u8; 32]>,
+    pub logo_uri: Option<String>,
+    pub contact: Option<String>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateIssuerProfile<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        space = IssuerProfile::LEN,
+        seeds = [ISSUER_PROFILE_SEED, issuer.key().as_ref()],
+        bump
+    )]
+    pub issuer_profile: Box<Account<'info, IssuerProfile>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the issuer's profile on first use and updates the fields
+/// provided.
+pub fn update_issuer_profile(
+    ctx: Context<UpdateIssuerProfile>,
+    params: IssuerProfileParams,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let profile = &mut ctx.accounts.issuer_profile;
+    profile.issuer = ctx.accounts.issuer.key();
+    profile.bump = ctx.bumps.issuer_profile;
+
+    if let Some(website) = params.website {
+        StablecoinMint::validate_uri(&website)?;
+        profile.website = website;
+    }
+
+    if let Some(twitter_handle_hash) = params.twitter_handle_hash {
+        profile.twitter_handle_hash = twitter_handle_hash;
+    }
+
+    if let Some(logo_uri) = params.logo_uri {
+        StablecoinMint::validate_uri(&logo_uri)?;
+        profile.logo_uri = logo_uri;
+    }
+
+    if let Some(contact) = params.contact {
+        StablecoinMint::validate_uri(&contact)?;
+        profile.contact = contact;
+    }
+
+    profile.updated_at = now;
+
+    emit!(IssuerProfileUpdated {
+        issuer: profile.issuer,
+        issuer_profile: profile.key(),
+        website: profile.website.clone(),
+        twitter_handle_hash: profile.twitter_handle_hash,
+        logo_uri: profile.logo_uri.clone(),
+        contact: profile.contact.clone(),
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct IssuerProfileUpdated {
+    pub issuer: Pubkey,
+    pub issuer_profile: Pubkey,
+    pub website: String,
+    pub twitter_handle_hash: [u8; 32],
+    pub logo_uri: String,
+    pub contact: String,
+    pub timestamp: i64,
+}
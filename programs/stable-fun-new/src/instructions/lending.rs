@@ -0,0 +1,239 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+
+use crate::state::{CollateralLock, ProtocolConfig, StablecoinMint, StateAccount, PAUSE_TRANSFERS};
+use crate::constants::{COLLATERAL_LOCK_SEED, COLLATERAL_LOCK_TOKEN_SEED, PROTOCOL_CONFIG_SEED};
+use crate::error::StableFunError;
+
+#[derive(Accounts)]
+pub struct LockForCollateral<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The lending program's `LENDER_AUTHORITY_SEED` PDA, signing through its CPI
+    #[account(
+        constraint = lender_authority.key() == CollateralLock::lender_authority(&lending_program.key())
+            @ StableFunError::InvalidLenderAuthority
+    )]
+    pub lender_authority: Signer<'info>,
+
+    /// CHECK: Only its key is used, checked against the approved lenders
+    #[account(
+        constraint = protocol_config.is_approved_lender(&lending_program.key())
+            @ StableFunError::UnapprovedLendingProgram
+    )]
+    pub lending_program: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_TRANSFERS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = CollateralLock::LEN,
+        seeds = [
+            COLLATERAL_LOCK_SEED,
+            stablecoin_mint.key().as_ref(),
+            lending_program.key().as_ref(),
+            owner.key().as_ref(),
+        ],
+        bump
+    )]
+    pub collateral_lock: Box<Account<'info, CollateralLock>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [COLLATERAL_LOCK_TOKEN_SEED, collateral_lock.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = collateral_lock,
+        token::token_program = token_program
+    )]
+    pub lock_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = owner_token_account.mint == token_mint.key() @ StableFunError::InvalidTokenAccount,
+        constraint = owner_token_account.owner == owner.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub owner_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// CPI for approved lending programs: moves `amount` of the owner's coins
+/// into an escrow only the lending program can release.
+pub fn lock_for_collateral(ctx: Context<LockForCollateral>, amount: u64) -> Result<()> {
+    require!(amount > 0, StableFunError::InvalidAmount);
+
+    let balance_before = ctx.accounts.lock_token_account.amount;
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.owner_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.lock_token_account.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+    // Transfer fees are withheld from the escrow, so lock what arrived
+    ctx.accounts.lock_token_account.reload()?;
+    let locked = ctx.accounts.lock_token_account.amount.saturating_sub(balance_before);
+
+    let collateral_lock = &mut ctx.accounts.collateral_lock;
+    if collateral_lock.owner == Pubkey::default() {
+        collateral_lock.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+        collateral_lock.owner = ctx.accounts.owner.key();
+        collateral_lock.lending_program = ctx.accounts.lending_program.key();
+        collateral_lock.token_account = ctx.accounts.lock_token_account.key();
+        collateral_lock.bump = ctx.bumps.collateral_lock;
+    }
+    collateral_lock.amount = collateral_lock.amount
+        .checked_add(locked)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+
+    emit!(CollateralLocked {
+        stablecoin_mint: collateral_lock.stablecoin_mint,
+        owner: collateral_lock.owner,
+        lending_program: collateral_lock.lending_program,
+        amount: locked,
+        total_locked: collateral_lock.amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ReleaseCollateral<'info> {
+    /// The lending program's `LENDER_AUTHORITY_SEED` PDA, signing through its CPI
+    #[account(
+        constraint = lender_authority.key() == CollateralLock::lender_authority(&collateral_lock.lending_program)
+            @ StableFunError::InvalidLenderAuthority
+    )]
+    pub lender_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            COLLATERAL_LOCK_SEED,
+            collateral_lock.stablecoin_mint.as_ref(),
+            collateral_lock.lending_program.as_ref(),
+            collateral_lock.owner.as_ref(),
+        ],
+        bump = collateral_lock.bump
+    )]
+    pub collateral_lock: Box<Account<'info, CollateralLock>>,
+
+    #[account(
+        constraint = stablecoin_mint.key() == collateral_lock.stablecoin_mint @ StableFunError::InvalidMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = lock_token_account.key() == collateral_lock.token_account @ StableFunError::InvalidTokenAccount
+    )]
+    pub lock_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The owner's account on repayment, a liquidator's on default
+    #[account(
+        mut,
+        constraint = destination_token_account.mint == token_mint.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub destination_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_TRANSFERS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// CPI for the lending program that took a lock: releases `amount` to
+/// `destination_token_account`. Works even after the program has been
+/// removed from the approved list, so existing locks never get stuck.
+pub fn release_collateral(ctx: Context<ReleaseCollateral>, amount: u64) -> Result<()> {
+    require!(amount > 0, StableFunError::InvalidAmount);
+    ctx.accounts.collateral_lock.record_release(amount)?;
+
+    let collateral_lock = &ctx.accounts.collateral_lock;
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.lock_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.destination_token_account.to_account_info(),
+                authority: collateral_lock.to_account_info(),
+            },
+            &[&[
+                COLLATERAL_LOCK_SEED,
+                collateral_lock.stablecoin_mint.as_ref(),
+                collateral_lock.lending_program.as_ref(),
+                collateral_lock.owner.as_ref(),
+                &[collateral_lock.bump],
+            ]],
+        ),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    emit!(CollateralReleased {
+        stablecoin_mint: collateral_lock.stablecoin_mint,
+        owner: collateral_lock.owner,
+        lending_program: collateral_lock.lending_program,
+        destination: ctx.accounts.destination_token_account.key(),
+        amount,
+        total_locked: collateral_lock.amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct CollateralLocked {
+    pub stablecoin_mint: Pubkey,
+    pub owner: Pubkey,
+    pub lending_program: Pubkey,
+    pub amount: u64,
+    pub total_locked: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CollateralReleased {
+    pub stablecoin_mint: Pubkey,
+    pub owner: Pubkey,
+    pub lending_program: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub total_locked: u64,
+    pub timestamp: i64,
+}
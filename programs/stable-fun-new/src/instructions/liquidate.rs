@@ -0,0 +1,615 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+use anchor_spl::token_interface::{self, TokenInterface};
+use switchboard_solana::AggregatorAccountData;
+
+use crate::state::{
+    JuniorTranche, OperationLog, OperationRecord, OperationType, ProtocolConfig, StablecoinMint,
+    StablecoinVault, SystemAccounting, UserPosition, PAUSE_LIQUIDATION,
+};
+use crate::constants::{
+    ACCOUNTING_SEED, JUNIOR_TRANCHE_SEED, OPERATION_LOG_SEED, POSITION_SEED, PROTOCOL_CONFIG_SEED,
+    VAULT_SEED,
+};
+use crate::error::StableFunError;
+use crate::utils::liquidation::{calculate_liquidation_payout, LiquidationPayout};
+use crate::utils::oracle::OracleService;
+use crate::utils::validation::ValidationService;
+use crate::utils::math;
+
+#[derive(Accounts)]
+#[instruction(repay_amount: u64)]
+pub struct Liquidate<'info> {
+    #[account(mut)]
+    pub liquidator: Signer<'info>,
+
+    #[account(mut)]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        mut,
+        seeds = [POSITION_SEED, stablecoin_mint.key().as_ref(), position.owner.as_ref()],
+        bump = position.bump
+    )]
+    pub position: Box<Account<'info, UserPosition>>,
+
+    #[account(
+        mut,
+        seeds = [ACCOUNTING_SEED, stablecoin_mint.key().as_ref()],
+        bump = accounting.bump
+    )]
+    pub accounting: Box<Account<'info, SystemAccounting>>,
+
+    #[account(
+        mut,
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        mut,
+        constraint = liquidator_token_account.mint == token_mint.key() @ StableFunError::InvalidTokenAccount,
+        constraint = liquidator_token_account.owner == liquidator.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub liquidator_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = liquidator_stablebond_account.mint == stablecoin_mint.stablebond_mint @ StableFunError::InvalidStablebond
+    )]
+    pub liquidator_stablebond_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = vault_stablebond_account.key() == vault.collateral_account @ StableFunError::InvalidVaultAccount
+    )]
+    pub vault_stablebond_account: Box<Account<'info, TokenAccount>>,
+
+    /// The Switchboard V3 aggregator account
+    #[account(
+        constraint = price_feed.key() == stablecoin_mint.price_feed @ StableFunError::InvalidOracle
+    )]
+    pub price_feed: AccountLoader<'info, AggregatorAccountData>,
+
+    /// FX feed composed with the bond NAV `price_feed`, on coins that set one
+    #[account(
+        constraint = fx_feed.key() == stablecoin_mint.fx_feed @ StableFunError::InvalidOracle
+    )]
+    pub fx_feed: Option<AccountLoader<'info, AggregatorAccountData>>,
+
+    /// Appended to when the stablecoin has opted into operation logging
+    #[account(
+        mut,
+        seeds = [OPERATION_LOG_SEED, stablecoin_mint.key().as_ref()],
+        bump = operation_log.load()?.bump
+    )]
+    pub operation_log: Option<AccountLoader<'info, OperationLog>>,
+
+    /// When present, junior capital absorbs bad debt before the senior side
+    #[account(
+        mut,
+        seeds = [JUNIOR_TRANCHE_SEED, stablecoin_mint.key().as_ref()],
+        bump = junior_tranche.bump
+    )]
+    pub junior_tranche: Option<Box<Account<'info, JuniorTranche>>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_LIQUIDATION) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token>,
+    /// Token program owning the stablecoin mint (SPL Token or Token-2022)
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
+}
+
+/// What liquidating a position would do at the current price. Returned by
+/// `preview_liquidation` so keepers run the exact waterfall `liquidate` does.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LiquidationPreview {
+    /// Below the liquidation threshold
+    pub liquidatable: bool,
+    /// Out of the grace period, or deteriorated enough to skip it
+    pub grace_elapsed: bool,
+    /// Collateral ratio in basis points (0 without collateral)
+    pub collateral_ratio: u64,
+    /// Most debt one liquidation may repay
+    pub max_repay_amount: u64,
+    /// Debt repaid; the whole debt when the remainder would be dust
+    pub repay_amount: u64,
+    /// Collateral equivalent of the repaid debt, sent to the liquidator
+    pub base_collateral: u64,
+    /// Extra collateral sent to the liquidator
+    pub liquidator_bonus: u64,
+    /// Collateral kept by the protocol as a penalty
+    pub protocol_penalty: u64,
+    /// Debt written off because no collateral is left behind it
+    pub bad_debt: u64,
+    /// Price with `PRICE_DECIMALS` decimals
+    pub price: u64,
+}
+
+impl LiquidationPreview {
+    /// Whether `liquidate` would go through.
+    pub fn is_executable(&self) -> bool {
+        self.liquidatable && self.grace_elapsed
+    }
+
+    pub fn payout(&self) -> LiquidationPayout {
+        LiquidationPayout {
+            base_collateral: self.base_collateral,
+            liquidator_bonus: self.liquidator_bonus,
+            protocol_penalty: self.protocol_penalty,
+        }
+    }
+}
+
+/// Runs the liquidation waterfall for repaying up to `repay_amount` of the
+/// position's debt at `oracle_price`, without checking it may proceed.
+pub fn quote_liquidation(
+    position: &UserPosition,
+    stablecoin_mint: &StablecoinMint,
+    oracle_price: u64,
+    decimals: u8,
+    repay_amount: u64,
+    now: i64,
+) -> Result<LiquidationPreview> {
+    let settings = &stablecoin_mint.settings;
+
+    // Rather than leave dust nobody would liquidate, take the whole position
+    let repay_amount = repay_amount.min(position.debt_amount);
+    let repay_amount = if position.leaves_dust(repay_amount, stablecoin_mint.min_position_debt()) {
+        position.debt_amount
+    } else {
+        repay_amount
+    };
+
+    // Price the position's collateral against the threshold
+    let collateral_value = math::calculate_collateral_value(
+        position.collateral_amount,
+        oracle_price,
+        decimals,
+    )?;
+    let collateral_ratio = position.collateral_ratio(collateral_value).unwrap_or(0);
+
+    // Work out how much collateral goes where
+    let base_collateral = math::calculate_token_amount(repay_amount, oracle_price, decimals)?;
+    let payout = calculate_liquidation_payout(
+        base_collateral,
+        settings.liquidation_penalty_bps,
+        settings.liquidator_bonus_bps,
+        position.collateral_amount,
+    )?;
+    let bad_debt = if payout.seized_total() == position.collateral_amount {
+        position.debt_amount - repay_amount
+    } else {
+        0
+    };
+
+    Ok(LiquidationPreview {
+        liquidatable: position.is_liquidatable(collateral_value, settings.liquidation_threshold_bps),
+        // Respect the grace period unless the position has deteriorated further
+        grace_elapsed: position.grace_elapsed(
+            now,
+            collateral_ratio,
+            settings.liquidation_grace_seconds,
+            settings.grace_override_bps,
+        ),
+        collateral_ratio,
+        max_repay_amount: position.debt_amount,
+        repay_amount,
+        base_collateral: payout.base_collateral,
+        liquidator_bonus: payout.liquidator_bonus,
+        protocol_penalty: payout.protocol_penalty,
+        bad_debt,
+        price: oracle_price,
+    })
+}
+
+#[inline(never)]
+pub fn liquidate(ctx: Context<Liquidate>, repay_amount: u64) -> Result<()> {
+    let decimals = ctx.accounts.token_mint.decimals;
+    let settings = ctx.accounts.stablecoin_mint.settings.clone();
+    let position = &ctx.accounts.position;
+
+    require!(repay_amount > 0, StableFunError::InvalidAmount);
+    require!(
+        repay_amount <= position.debt_amount,
+        StableFunError::RepayExceedsDebt
+    );
+
+    let oracle_price = OracleService::collateral_price(
+        &ctx.accounts.stablecoin_mint,
+        &ctx.accounts.price_feed,
+        ctx.accounts.fx_feed.as_ref(),
+    )?;
+    let clock = Clock::get()?;
+    let quote = quote_liquidation(
+        position,
+        &ctx.accounts.stablecoin_mint,
+        oracle_price,
+        decimals,
+        repay_amount,
+        clock.unix_timestamp,
+    )?;
+    require!(quote.liquidatable, StableFunError::PositionNotLiquidatable);
+    require!(quote.grace_elapsed, StableFunError::LiquidationGracePeriodActive);
+    let repay_amount = quote.repay_amount;
+    let payout = quote.payout();
+
+    // Burn the repaid stablecoins from the liquidator
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.stablecoin_token_program.to_account_info(),
+            token_interface::Burn {
+                mint: ctx.accounts.token_mint.to_account_info(),
+                from: ctx.accounts.liquidator_token_account.to_account_info(),
+                authority: ctx.accounts.liquidator.to_account_info(),
+            },
+        ),
+        repay_amount,
+    )?;
+
+    // Pay the liquidator out of the vault
+    let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.vault_stablebond_account.to_account_info(),
+                to: ctx.accounts.liquidator_stablebond_account.to_account_info(),
+                authority: ctx.accounts.vault.to_account_info(),
+            },
+            &[&ctx.accounts.vault.signer_seeds()],
+        ),
+        payout.liquidator_total(),
+    )?;
+
+    // Update position; the penalty stays in the vault as surplus
+    let position = &mut ctx.accounts.position;
+    position.debt_amount = position.debt_amount
+        .checked_sub(repay_amount)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    position.collateral_amount = position.collateral_amount
+        .checked_sub(payout.seized_total())
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    let remaining_value = math::calculate_collateral_value(
+        position.collateral_amount,
+        oracle_price,
+        decimals,
+    )?;
+    if !position.is_liquidatable(remaining_value, settings.liquidation_threshold_bps) {
+        position.clear_flag();
+    }
+
+    // Debt left without any collateral behind it is written off as bad debt
+    let bad_debt = if position.collateral_amount == 0 {
+        std::mem::take(&mut position.debt_amount)
+    } else {
+        0
+    };
+
+    let penalty_value = math::calculate_collateral_value(
+        payout.protocol_penalty,
+        oracle_price,
+        decimals,
+    )?;
+    let senior_bad_debt = match ctx.accounts.junior_tranche.as_mut() {
+        Some(junior_tranche) => junior_tranche.absorb_loss(bad_debt)?,
+        None => bad_debt,
+    };
+    let accounting = &mut ctx.accounts.accounting;
+    accounting.record_surplus(penalty_value)?;
+    accounting.record_bad_debt(senior_bad_debt)?;
+
+    // Update vault state
+    let vault = &mut ctx.accounts.vault;
+    vault.total_collateral = vault
+        .total_collateral
+        .checked_sub(payout.liquidator_total())
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    vault.total_value_locked = vault.total_value_locked.saturating_sub(repay_amount);
+    vault.withdrawal_count = vault
+        .withdrawal_count
+        .checked_add(1)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    vault.last_withdrawal_time = clock.unix_timestamp;
+    ValidationService::update_collateral_ratio(vault, oracle_price, decimals)?;
+
+    // Update stablecoin state
+    let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
+    stablecoin_mint.current_supply = stablecoin_mint
+        .current_supply
+        .checked_sub(repay_amount)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    stablecoin_mint.stats.total_burned = stablecoin_mint
+        .stats
+        .total_burned
+        .checked_add(repay_amount)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    stablecoin_mint.last_updated = clock.unix_timestamp;
+
+    // The penalty is the fee a liquidation charges the position owner
+    if let Some(operation_log) = &ctx.accounts.operation_log {
+        operation_log.load_mut()?.append(OperationRecord::new(
+            OperationType::Liquidate,
+            ctx.accounts.position.owner,
+            repay_amount,
+            oracle_price,
+            penalty_value,
+            clock.slot,
+        ));
+    }
+
+    emit!(LiquidationEvent {
+        stablecoin_mint: stablecoin_key,
+        position_owner: ctx.accounts.position.owner,
+        liquidator: ctx.accounts.liquidator.key(),
+        repay_amount,
+        collateral_to_liquidator: payout.liquidator_total(),
+        liquidator_bonus: payout.liquidator_bonus,
+        protocol_penalty: payout.protocol_penalty,
+        bad_debt,
+        price: oracle_price,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct PreviewLiquidation<'info> {
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        seeds = [POSITION_SEED, stablecoin_mint.key().as_ref(), position.owner.as_ref()],
+        bump = position.bump
+    )]
+    pub position: Box<Account<'info, UserPosition>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// The Switchboard V3 aggregator account
+    #[account(
+        constraint = price_feed.key() == stablecoin_mint.price_feed @ StableFunError::InvalidOracle
+    )]
+    pub price_feed: AccountLoader<'info, AggregatorAccountData>,
+
+    /// FX feed composed with the bond NAV `price_feed`, on coins that set one
+    #[account(
+        constraint = fx_feed.key() == stablecoin_mint.fx_feed @ StableFunError::InvalidOracle
+    )]
+    pub fx_feed: Option<AccountLoader<'info, AggregatorAccountData>>,
+}
+
+/// Read-only: what `liquidate` would do right now, as return data. Without
+/// `repay_amount` the preview repays the whole debt.
+pub fn preview(ctx: Context<PreviewLiquidation>, repay_amount: Option<u64>) -> Result<LiquidationPreview> {
+    let oracle_price = OracleService::collateral_price(
+        &ctx.accounts.stablecoin_mint,
+        &ctx.accounts.price_feed,
+        ctx.accounts.fx_feed.as_ref(),
+    )?;
+    let position = &ctx.accounts.position;
+    quote_liquidation(
+        position,
+        &ctx.accounts.stablecoin_mint,
+        oracle_price,
+        ctx.accounts.token_mint.decimals,
+        repay_amount.unwrap_or(position.debt_amount),
+        Clock::get()?.unix_timestamp,
+    )
+}
+
+#[derive(Accounts)]
+pub struct FlagPosition<'info> {
+    pub caller: Signer<'info>,
+
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [POSITION_SEED, stablecoin_mint.key().as_ref(), position.owner.as_ref()],
+        bump = position.bump
+    )]
+    pub position: Box<Account<'info, UserPosition>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// The Switchboard V3 aggregator account
+    #[account(
+        constraint = price_feed.key() == stablecoin_mint.price_feed @ StableFunError::InvalidOracle
+    )]
+    pub price_feed: AccountLoader<'info, AggregatorAccountData>,
+
+    /// FX feed composed with the bond NAV `price_feed`, on coins that set one
+    #[account(
+        constraint = fx_feed.key() == stablecoin_mint.fx_feed @ StableFunError::InvalidOracle
+    )]
+    pub fx_feed: Option<AccountLoader<'info, AggregatorAccountData>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_LIQUIDATION) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+}
+
+/// Permissionless: records when a position first breaches the liquidation
+/// threshold, starting its grace period, or clears the flag once it recovers.
+#[inline(never)]
+pub fn flag(ctx: Context<FlagPosition>) -> Result<()> {
+    let oracle_price = OracleService::collateral_price(
+        &ctx.accounts.stablecoin_mint,
+        &ctx.accounts.price_feed,
+        ctx.accounts.fx_feed.as_ref(),
+    )?;
+    let position = &mut ctx.accounts.position;
+    let collateral_value = math::calculate_collateral_value(
+        position.collateral_amount,
+        oracle_price,
+        ctx.accounts.token_mint.decimals,
+    )?;
+    let threshold = ctx.accounts.stablecoin_mint.settings.liquidation_threshold_bps;
+    let clock = Clock::get()?;
+
+    if !position.is_liquidatable(collateral_value, threshold) {
+        require!(position.is_flagged(), StableFunError::PositionNotLiquidatable);
+        position.clear_flag();
+    } else if !position.is_flagged() {
+        let ratio = position.collateral_ratio(collateral_value).unwrap_or(0);
+        position.flag(clock.unix_timestamp, ratio);
+    }
+
+    emit!(PositionFlagEvent {
+        stablecoin_mint: ctx.accounts.stablecoin_mint.key(),
+        position_owner: position.owner,
+        flagged: position.is_flagged(),
+        flagged_at: position.flagged_at,
+        flagged_ratio: position.flagged_ratio,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PositionFlagEvent {
+    pub stablecoin_mint: Pubkey,
+    pub position_owner: Pubkey,
+    pub flagged: bool,
+    pub flagged_at: i64,
+    pub flagged_ratio: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LiquidationEvent {
+    pub stablecoin_mint: Pubkey,
+    pub position_owner: Pubkey,
+    pub liquidator: Pubkey,
+    pub repay_amount: u64,
+    pub collateral_to_liquidator: u64,
+    pub liquidator_bonus: u64,
+    pub protocol_penalty: u64,
+    pub bad_debt: u64,
+    pub price: u64,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{
+        DEFAULT_COLLATERAL_RATIO, DEFAULT_LIQUIDATION_PENALTY_BPS, DEFAULT_LIQUIDATION_THRESHOLD,
+        DEFAULT_LIQUIDATOR_BONUS_BPS,
+    };
+    use crate::instructions::mint::quote_mint;
+
+    /// A coin with the settings `initialize` gives it.
+    fn default_stablecoin_mint() -> StablecoinMint {
+        let mut stablecoin_mint = StablecoinMint::default();
+        stablecoin_mint.settings.min_collateral_ratio = DEFAULT_COLLATERAL_RATIO;
+        stablecoin_mint.settings.fee_basis_points = 30;
+        stablecoin_mint.settings.liquidation_threshold_bps = DEFAULT_LIQUIDATION_THRESHOLD;
+        stablecoin_mint.settings.liquidation_penalty_bps = DEFAULT_LIQUIDATION_PENALTY_BPS;
+        stablecoin_mint.settings.liquidator_bonus_bps = DEFAULT_LIQUIDATOR_BONUS_BPS;
+        stablecoin_mint
+    }
+
+    /// A position holding exactly what one mint of `amount` leaves behind.
+    fn freshly_minted(stablecoin_mint: &StablecoinMint, amount: u64, oracle_price: u64) -> UserPosition {
+        let quote = quote_mint(
+            amount,
+            oracle_price,
+            6,
+            stablecoin_mint.settings.fee_basis_points,
+            stablecoin_mint.settings.min_collateral_ratio,
+        ).unwrap();
+        let mut position = UserPosition::default();
+        position.record_mint(quote.collateral_amount, quote.total_amount, 100).unwrap();
+        position
+    }
+
+    #[test]
+    fn test_fresh_position_not_liquidatable() {
+        let stablecoin_mint = default_stablecoin_mint();
+        for (amount, oracle_price) in [(1_000_000, 1_000_000), (1_234_567, 987_654), (1_000, 2_000_003)] {
+            let position = freshly_minted(&stablecoin_mint, amount, oracle_price);
+            let quote = quote_liquidation(&position, &stablecoin_mint, oracle_price, 6, amount, 100).unwrap();
+            assert!(!quote.liquidatable);
+            assert!(!quote.is_executable());
+        }
+    }
+
+    #[test]
+    fn test_fresh_position_inside_buffer() {
+        let stablecoin_mint = default_stablecoin_mint();
+        let settings = &stablecoin_mint.settings;
+        let position = freshly_minted(&stablecoin_mint, 1_000_000, 1_000_000);
+
+        // A new position opens at the minimum ratio, above the threshold
+        let quote = quote_liquidation(&position, &stablecoin_mint, 1_000_000, 6, 0, 100).unwrap();
+        assert!(quote.collateral_ratio >= settings.min_collateral_ratio as u64);
+        assert!(quote.collateral_ratio > settings.liquidation_threshold_bps as u64);
+
+        // A 20% drop in the collateral leaves it below the minimum but
+        // still short of liquidation
+        let quote = quote_liquidation(&position, &stablecoin_mint, 1_250_000, 6, 0, 100).unwrap();
+        assert!(quote.collateral_ratio < settings.min_collateral_ratio as u64);
+        assert!(!quote.liquidatable);
+
+        // Only past the threshold does it open up
+        let quote = quote_liquidation(&position, &stablecoin_mint, 1_300_000, 6, 0, 100).unwrap();
+        assert!(quote.collateral_ratio < settings.liquidation_threshold_bps as u64);
+        assert!(quote.is_executable());
+    }
+
+    #[test]
+    fn test_quote_liquidation() {
+        let mut stablecoin_mint = StablecoinMint::default();
+        stablecoin_mint.settings.liquidation_threshold_bps = 11_000;
+        stablecoin_mint.settings.liquidation_penalty_bps = 300;
+        stablecoin_mint.settings.liquidator_bonus_bps = 500;
+        let position = UserPosition {
+            collateral_amount: 1_000_000,
+            debt_amount: 1_000_000,
+            ..Default::default()
+        };
+
+        let quote = quote_liquidation(&position, &stablecoin_mint, 1_000_000, 6, 500_000, 0).unwrap();
+        assert!(quote.is_executable());
+        assert_eq!(quote.collateral_ratio, 10_000);
+        assert_eq!(quote.max_repay_amount, 1_000_000);
+        assert_eq!(quote.payout().liquidator_total(), 525_000);
+        assert_eq!(quote.protocol_penalty, 15_000);
+        assert_eq!(quote.bad_debt, 0);
+
+        // At half the collateral price, a partial repay seizes everything
+        // and writes the rest of the debt off
+        let quote = quote_liquidation(&position, &stablecoin_mint, 2_000_000, 6, 600_000, 0).unwrap();
+        assert_eq!(quote.base_collateral, 1_000_000);
+        assert_eq!(quote.bad_debt, 400_000);
+
+        stablecoin_mint.settings.liquidation_threshold_bps = 9_000;
+        let quote = quote_liquidation(&position, &stablecoin_mint, 1_000_000, 6, 500_000, 0).unwrap();
+        assert!(!quote.liquidatable);
+    }
+}
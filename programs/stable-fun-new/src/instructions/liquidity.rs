@@ -0,0 +1,419 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token::{self, Token, TokenAccount};
+use anchor_spl::token_interface::{self, TokenInterface};
+
+use crate::state::{
+    LiquidityPosition, ProtocolConfig, StablecoinMint, StablecoinVault, StateAccount,
+    SystemAccounting,
+};
+use crate::constants::{
+    ACCOUNTING_SEED, LIQUIDITY_LP_SEED, LIQUIDITY_QUOTE_SEED, LIQUIDITY_SEED, LIQUIDITY_TOKEN_SEED,
+    MINT_AUTHORITY_SEED, PROTOCOL_CONFIG_SEED, VAULT_SEED,
+};
+use crate::error::StableFunError;
+use crate::utils::validation::ValidationService;
+
+#[derive(Accounts)]
+pub struct ConfigureLiquidity<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = LiquidityPosition::LEN,
+        seeds = [LIQUIDITY_SEED, stablecoin_mint.key().as_ref()],
+        bump
+    )]
+    pub liquidity_position: Box<Account<'info, LiquidityPosition>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [LIQUIDITY_TOKEN_SEED, stablecoin_mint.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = liquidity_position,
+        token::token_program = stablecoin_token_program
+    )]
+    pub liquidity_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// Quote side of the pool, e.g. USDC
+    pub quote_mint: Box<Account<'info, token::Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [LIQUIDITY_QUOTE_SEED, stablecoin_mint.key().as_ref()],
+        bump,
+        token::mint = quote_mint,
+        token::authority = liquidity_position
+    )]
+    pub liquidity_quote_account: Box<Account<'info, TokenAccount>>,
+
+    pub lp_mint: Box<Account<'info, token::Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [LIQUIDITY_LP_SEED, stablecoin_mint.key().as_ref()],
+        bump,
+        token::mint = lp_mint,
+        token::authority = liquidity_position
+    )]
+    pub liquidity_lp_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token>,
+    /// Token program owning the stablecoin mint (SPL Token or Token-2022)
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Registers the pool protocol-owned liquidity goes into. The escrows are
+/// fixed per stablecoin, so the pool can only change to one with the same
+/// quote and LP mints.
+pub fn configure_liquidity(
+    ctx: Context<ConfigureLiquidity>,
+    amm_program: Pubkey,
+    pool: Pubkey,
+) -> Result<()> {
+    require!(
+        amm_program != Pubkey::default() && pool != Pubkey::default(),
+        StableFunError::InvalidLiquidityConfig
+    );
+
+    let position = &mut ctx.accounts.liquidity_position;
+    position.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    position.amm_program = amm_program;
+    position.pool = pool;
+    position.lp_mint = ctx.accounts.lp_mint.key();
+    position.lp_token_account = ctx.accounts.liquidity_lp_account.key();
+    position.token_account = ctx.accounts.liquidity_token_account.key();
+    position.quote_token_account = ctx.accounts.liquidity_quote_account.key();
+    position.bump = ctx.bumps.liquidity_position;
+
+    emit!(LiquidityConfigured {
+        stablecoin_mint: position.stablecoin_mint,
+        amm_program,
+        pool,
+        lp_mint: position.lp_mint,
+        quote_mint: ctx.accounts.quote_mint.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ManageLiquidity<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        mut,
+        seeds = [ACCOUNTING_SEED, stablecoin_mint.key().as_ref()],
+        bump = accounting.bump
+    )]
+    pub accounting: Box<Account<'info, SystemAccounting>>,
+
+    #[account(
+        mut,
+        seeds = [LIQUIDITY_SEED, stablecoin_mint.key().as_ref()],
+        bump = liquidity_position.bump
+    )]
+    pub liquidity_position: Box<Account<'info, LiquidityPosition>>,
+
+    #[account(
+        mut,
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        mut,
+        constraint = liquidity_token_account.key() == liquidity_position.token_account @ StableFunError::InvalidTokenAccount
+    )]
+    pub liquidity_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = liquidity_lp_account.key() == liquidity_position.lp_token_account @ StableFunError::InvalidTokenAccount
+    )]
+    pub liquidity_lp_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: PDA used as mint authority
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, stablecoin_mint.key().as_ref()],
+        bump = stablecoin_mint.mint_authority_bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Must be the AMM program fixed in the liquidity position
+    #[account(
+        executable,
+        constraint = amm_program.key() == liquidity_position.amm_program @ StableFunError::InvalidLiquidityConfig
+    )]
+    pub amm_program: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    /// Token program owning the stablecoin mint (SPL Token or Token-2022)
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ManageLiquidity<'info> {
+    /// Sends an AMM instruction built from `data` and the remaining accounts,
+    /// signed by the liquidity position PDA.
+    fn invoke_amm(&self, remaining_accounts: &[AccountInfo<'info>], data: Vec<u8>) -> Result<()> {
+        let position_key = self.liquidity_position.key();
+        let amm_ix = Instruction {
+            program_id: self.amm_program.key(),
+            accounts: remaining_accounts
+                .iter()
+                .map(|account| AccountMeta {
+                    pubkey: account.key(),
+                    is_signer: account.is_signer || account.key() == position_key,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data,
+        };
+        invoke_signed(
+            &amm_ix,
+            remaining_accounts,
+            &[&[
+                LIQUIDITY_SEED,
+                self.liquidity_position.stablecoin_mint.as_ref(),
+                &[self.liquidity_position.bump],
+            ]],
+        )?;
+        Ok(())
+    }
+}
+
+/// Deploys `amount` of surplus into the registered pool. The stablecoin side
+/// is minted against the surplus collateral already in the vault; the quote
+/// side comes from the position's quote escrow. Fails unless the deposit
+/// takes every minted coin and returns LP tokens.
+pub fn deploy_liquidity<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ManageLiquidity<'info>>,
+    amount: u64,
+    deposit_data: Vec<u8>,
+) -> Result<()> {
+    require!(amount > 0, StableFunError::InvalidAmount);
+
+    let new_supply = ctx.accounts.stablecoin_mint.current_supply
+        .checked_add(amount)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    require!(
+        new_supply <= ctx.accounts.stablecoin_mint.settings.max_supply,
+        StableFunError::MaxSupplyExceeded
+    );
+    ctx.accounts.accounting.deploy_liquidity(
+        amount,
+        ctx.accounts.stablecoin_mint.settings.surplus_buffer,
+    )?;
+
+    let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+    let escrow_before = ctx.accounts.liquidity_token_account.amount;
+    let lp_before = ctx.accounts.liquidity_lp_account.amount;
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.stablecoin_token_program.to_account_info(),
+            token_interface::MintTo {
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.liquidity_token_account.to_account_info(),
+                authority: ctx.accounts.mint_authority.to_account_info(),
+            },
+            &[&[
+                MINT_AUTHORITY_SEED,
+                stablecoin_key.as_ref(),
+                &[ctx.accounts.stablecoin_mint.mint_authority_bump],
+            ]],
+        ),
+        amount,
+    )?;
+
+    ctx.accounts.invoke_amm(ctx.remaining_accounts, deposit_data)?;
+
+    ctx.accounts.liquidity_token_account.reload()?;
+    ctx.accounts.liquidity_lp_account.reload()?;
+    let lp_received = ctx.accounts.liquidity_lp_account.amount.saturating_sub(lp_before);
+    require!(
+        ctx.accounts.liquidity_token_account.amount == escrow_before && lp_received > 0,
+        StableFunError::LiquidityDepositMismatch
+    );
+
+    let clock = Clock::get()?;
+    let vault = &mut ctx.accounts.vault;
+    vault.total_value_locked = vault
+        .total_value_locked
+        .checked_add(amount)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    // No fresh price here; re-rate at the last one
+    let ratio_price = vault.ratio_price;
+    ValidationService::update_collateral_ratio(vault, ratio_price, ctx.accounts.token_mint.decimals)?;
+
+    let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
+    stablecoin_mint.current_supply = new_supply;
+    stablecoin_mint.last_updated = clock.unix_timestamp;
+
+    let position = &mut ctx.accounts.liquidity_position;
+    position.total_deployed = position.total_deployed
+        .checked_add(amount)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+
+    emit!(LiquidityDeployed {
+        stablecoin_mint: stablecoin_key,
+        pool: position.pool,
+        amount,
+        lp_received,
+        protocol_owned_liquidity: ctx.accounts.accounting.protocol_owned_liquidity,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Pulls `lp_amount` LP tokens out of the pool. The stablecoins that come
+/// back are burned and their collateral returns to surplus; the quote side
+/// stays in the position's quote escrow for redeployment.
+pub fn withdraw_liquidity<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ManageLiquidity<'info>>,
+    lp_amount: u64,
+    withdraw_data: Vec<u8>,
+) -> Result<()> {
+    require!(lp_amount > 0, StableFunError::InvalidAmount);
+
+    let lp_before = ctx.accounts.liquidity_lp_account.amount;
+    let tokens_before = ctx.accounts.liquidity_token_account.amount;
+
+    ctx.accounts.invoke_amm(ctx.remaining_accounts, withdraw_data)?;
+
+    ctx.accounts.liquidity_lp_account.reload()?;
+    ctx.accounts.liquidity_token_account.reload()?;
+    require!(
+        lp_before.saturating_sub(ctx.accounts.liquidity_lp_account.amount) == lp_amount,
+        StableFunError::LiquidityDepositMismatch
+    );
+    let received = ctx.accounts.liquidity_token_account.amount.saturating_sub(tokens_before);
+
+    let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+    token_interface::burn(
+        CpiContext::new_with_signer(
+            ctx.accounts.stablecoin_token_program.to_account_info(),
+            token_interface::Burn {
+                mint: ctx.accounts.token_mint.to_account_info(),
+                from: ctx.accounts.liquidity_token_account.to_account_info(),
+                authority: ctx.accounts.liquidity_position.to_account_info(),
+            },
+            &[&[
+                LIQUIDITY_SEED,
+                stablecoin_key.as_ref(),
+                &[ctx.accounts.liquidity_position.bump],
+            ]],
+        ),
+        received,
+    )?;
+
+    ctx.accounts.accounting.recall_liquidity(received)?;
+
+    let clock = Clock::get()?;
+    let vault = &mut ctx.accounts.vault;
+    vault.total_value_locked = vault.total_value_locked.saturating_sub(received);
+    // No fresh price here; re-rate at the last one
+    let ratio_price = vault.ratio_price;
+    ValidationService::update_collateral_ratio(vault, ratio_price, ctx.accounts.token_mint.decimals)?;
+
+    let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
+    stablecoin_mint.current_supply = stablecoin_mint
+        .current_supply
+        .checked_sub(received)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    stablecoin_mint.last_updated = clock.unix_timestamp;
+
+    let position = &mut ctx.accounts.liquidity_position;
+    position.total_withdrawn = position.total_withdrawn
+        .checked_add(received)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+
+    emit!(LiquidityWithdrawn {
+        stablecoin_mint: stablecoin_key,
+        pool: position.pool,
+        lp_amount,
+        amount: received,
+        protocol_owned_liquidity: ctx.accounts.accounting.protocol_owned_liquidity,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct LiquidityConfigured {
+    pub stablecoin_mint: Pubkey,
+    pub amm_program: Pubkey,
+    pub pool: Pubkey,
+    pub lp_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LiquidityDeployed {
+    pub stablecoin_mint: Pubkey,
+    pub pool: Pubkey,
+    /// Stablecoins minted from surplus into the pool
+    pub amount: u64,
+    pub lp_received: u64,
+    pub protocol_owned_liquidity: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LiquidityWithdrawn {
+    pub stablecoin_mint: Pubkey,
+    pub pool: Pubkey,
+    pub lp_amount: u64,
+    /// Stablecoins returned by the pool and burned
+    pub amount: u64,
+    pub protocol_owned_liquidity: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::address_lookup_table::{self, instruction as alt_instruction};
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+
+use crate::state::{ProtocolConfig, StablecoinMint};
+use crate::constants::{
+    ACCOUNTING_SEED, FREEZE_AUTHORITY_SEED, MINT_AUTHORITY_SEED, NTT_CONFIG_SEED,
+    OPERATION_LOG_SEED, PROTOCOL_CONFIG_SEED, VAULT_SEED,
+};
+use crate::error::StableFunError;
+
+#[derive(Accounts)]
+#[instruction(recent_slot: u64)]
+pub struct CreateLookupTable<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    /// CHECK: Derived from the authority and `recent_slot`, created by the ALT program
+    #[account(
+        mut,
+        address = alt_instruction::derive_lookup_table_address(&authority.key(), recent_slot).0
+    )]
+    pub lookup_table: UncheckedAccount<'info>,
+
+    /// CHECK: The address lookup table program, checked by address
+    #[account(address = address_lookup_table::program::ID)]
+    pub address_lookup_table_program: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Every account that is the same in each mint/redeem of `stablecoin_key`.
+pub fn lookup_table_addresses(stablecoin_key: &Pubkey, stablecoin_mint: &StablecoinMint) -> Vec<Pubkey> {
+    let pda = |seed: &[u8]| Pubkey::find_program_address(&[seed, stablecoin_key.as_ref()], &crate::ID).0;
+    vec![
+        *stablecoin_key,
+        stablecoin_mint.token_mint,
+        stablecoin_mint.stablebond_mint,
+        stablecoin_mint.price_feed,
+        stablecoin_mint.vault,
+        pda(VAULT_SEED),
+        pda(MINT_AUTHORITY_SEED),
+        pda(FREEZE_AUTHORITY_SEED),
+        pda(ACCOUNTING_SEED),
+        pda(NTT_CONFIG_SEED),
+        pda(OPERATION_LOG_SEED),
+        anchor_spl::token::ID,
+        anchor_spl::token_2022::ID,
+        anchor_lang::system_program::ID,
+        sysvar_instructions::ID,
+        crate::ID,
+    ]
+    .into_iter()
+    .fold(Vec::new(), |mut addresses, address| {
+        if !addresses.contains(&address) {
+            addresses.push(address);
+        }
+        addresses
+    })
+}
+
+/// Creates an address lookup table owned by the authority, fills it with the
+/// coin's static accounts and records it on the stablecoin for clients.
+pub fn create_lookup_table(ctx: Context<CreateLookupTable>, recent_slot: u64) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    let (create_ix, lookup_table) = alt_instruction::create_lookup_table(authority, authority, recent_slot);
+    invoke(
+        &create_ix,
+        &[
+            ctx.accounts.lookup_table.to_account_info(),
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+    )?;
+
+    let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+    let addresses = lookup_table_addresses(&stablecoin_key, &ctx.accounts.stablecoin_mint);
+    let extend_ix = alt_instruction::extend_lookup_table(
+        lookup_table,
+        authority,
+        Some(authority),
+        addresses.clone(),
+    );
+    invoke(
+        &extend_ix,
+        &[
+            ctx.accounts.lookup_table.to_account_info(),
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+    )?;
+
+    ctx.accounts.stablecoin_mint.lookup_table = lookup_table;
+
+    emit!(LookupTableCreated {
+        stablecoin_mint: stablecoin_key,
+        lookup_table,
+        addresses: addresses.len() as u8,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct LookupTableCreated {
+    pub stablecoin_mint: Pubkey,
+    pub lookup_table: Pubkey,
+    pub addresses: u8,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_table_addresses() {
+        let stablecoin_key = Pubkey::new_unique();
+        let stablecoin_mint = StablecoinMint {
+            token_mint: Pubkey::new_unique(),
+            stablebond_mint: Pubkey::new_unique(),
+            price_feed: Pubkey::new_unique(),
+            vault: Pubkey::find_program_address(&[VAULT_SEED, stablecoin_key.as_ref()], &crate::ID).0,
+            ..Default::default()
+        };
+
+        let addresses = lookup_table_addresses(&stablecoin_key, &stablecoin_mint);
+        // The stored vault is the vault PDA, so it only appears once
+        assert_eq!(addresses.len(), 15);
+        assert!(addresses.contains(&stablecoin_mint.token_mint));
+        assert!(addresses.contains(&sysvar_instructions::ID));
+    }
+}
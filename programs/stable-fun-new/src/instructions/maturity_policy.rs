@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::collateral_registry::{execute_rebalance, RebalanceCollateral};
+use crate::state::{
+    weighted_average_maturity, MaturityBucket, MaturityPolicy, ProtocolConfig, StablecoinMint, StateAccount,
+};
+use crate::constants::{MATURITY_POLICY_SEED, PROTOCOL_CONFIG_SEED};
+use crate::error::StableFunError;
+use crate::utils::stablebond::StablebondService;
+
+#[derive(Accounts)]
+pub struct SetMaturityPolicy<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = MaturityPolicy::LEN,
+        seeds = [MATURITY_POLICY_SEED, stablecoin_mint.key().as_ref()],
+        bump
+    )]
+    pub maturity_policy: Box<Account<'info, MaturityPolicy>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates or replaces the coin's bond ladder: target weights by time to
+/// maturity, and how far a bucket may drift before `rebalance_ladder` acts.
+pub fn set_maturity_policy(
+    ctx: Context<SetMaturityPolicy>,
+    buckets: Vec<MaturityBucket>,
+    tolerance_bps: u16,
+) -> Result<()> {
+    MaturityPolicy::validate(&buckets, tolerance_bps)?;
+
+    let maturity_policy = &mut ctx.accounts.maturity_policy;
+    maturity_policy.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    maturity_policy.buckets = buckets;
+    maturity_policy.tolerance_bps = tolerance_bps;
+    maturity_policy.bump = ctx.bumps.maturity_policy;
+
+    emit!(MaturityPolicySet {
+        stablecoin_mint: maturity_policy.stablecoin_mint,
+        buckets: maturity_policy.buckets.clone(),
+        tolerance_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// The stablebond's maturity from its bond terms; zero without terms.
+fn primary_maturity(accounts: &RebalanceCollateral) -> Result<i64> {
+    let bond_terms = accounts.bond_terms
+        .as_ref()
+        .ok_or(error!(StableFunError::InvalidMaturityPolicy))?;
+    Ok(StablebondService::get_bond_terms_data(bond_terms, &accounts.stablecoin_mint.stablebond_mint)?
+        .map_or(0, |stablebond| stablebond.maturity_timestamp))
+}
+
+/// Permissionless crank: sells `amount` of collateral type `from` for type
+/// `to` like `rebalance_collateral`, under the same volume cap and slippage
+/// limit, while some maturity bucket has drifted past the policy's
+/// tolerance. The swap must take collateral from an overweight bucket to an
+/// underweight one without carrying either past its target; the types'
+/// own targets do not apply.
+pub fn rebalance_ladder<'info>(
+    mut ctx: Context<'_, '_, 'info, 'info, RebalanceCollateral<'info>>,
+    from: u8,
+    to: u8,
+    amount: u64,
+    swap_data: Vec<u8>,
+) -> Result<()> {
+    let policy = MaturityPolicy::clone(
+        ctx.accounts.maturity_policy
+            .as_ref()
+            .ok_or(error!(StableFunError::InvalidMaturityPolicy))?,
+    );
+    let now = Clock::get()?.unix_timestamp;
+    let primary_maturity = primary_maturity(ctx.accounts)?;
+
+    let holdings = ctx.accounts.collateral_registry
+        .maturity_holdings(ctx.accounts.vault.total_collateral, primary_maturity)?;
+    let before = policy.allocation(&holdings, now);
+    require!(!policy.is_within(&before), StableFunError::LadderWithinPolicy);
+    let bucket_of = |index: u8| {
+        holdings
+            .get(index as usize)
+            .map(|holding| policy.bucket_of(holding.time_to_maturity(now)))
+            .ok_or(error!(StableFunError::InvalidCollateralType))
+    };
+    let (from_bucket, to_bucket) = (bucket_of(from)?, bucket_of(to)?);
+
+    execute_rebalance(&mut ctx, from, to, amount, swap_data)?;
+
+    let holdings = ctx.accounts.collateral_registry
+        .maturity_holdings(ctx.accounts.vault.total_collateral, primary_maturity)?;
+    let after = policy.allocation(&holdings, now);
+    require!(
+        policy.moves_toward_targets(from_bucket, to_bucket, &before, &after),
+        StableFunError::LadderOffTarget
+    );
+    if let Some(maturity_policy) = ctx.accounts.maturity_policy.as_mut() {
+        maturity_policy.last_rebalanced_at = now;
+    }
+
+    emit!(LadderRebalanced {
+        stablecoin_mint: ctx.accounts.stablecoin_mint.key(),
+        from_bucket: from_bucket as u8,
+        to_bucket: to_bucket as u8,
+        allocation: after,
+        weighted_average_maturity: weighted_average_maturity(&holdings, now),
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct MaturityPolicySet {
+    pub stablecoin_mint: Pubkey,
+    pub buckets: Vec<MaturityBucket>,
+    pub tolerance_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LadderRebalanced {
+    pub stablecoin_mint: Pubkey,
+    pub from_bucket: u8,
+    pub to_bucket: u8,
+    /// Share of the collateral in each bucket after the swap, in bps
+    pub allocation: Vec<u64>,
+    /// In seconds
+    pub weighted_average_maturity: i64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,259 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+use crate::state::{ProtocolConfig, StablecoinMint, StablecoinVault, StateAccount};
+use crate::constants::{
+    FEE_VAULT_SEED, FREEZE_AUTHORITY_SEED, LOCKED_LIQUIDITY_SEED, MINT_AUTHORITY_SEED,
+    PROTOCOL_CONFIG_SEED, STABLECOIN_SEED, VAULT_SEED,
+};
+use crate::error::StableFunError;
+
+#[derive(Accounts)]
+pub struct MigrateVaultTokenAccount<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        constraint = stablebond_mint.key() == stablecoin_mint.stablebond_mint @ StableFunError::InvalidStablebond
+    )]
+    pub stablebond_mint: Box<Account<'info, token::Mint>>,
+
+    /// The keypair token account created by earlier versions of `initialize`
+    #[account(
+        mut,
+        constraint = old_vault_token_account.key() == vault.collateral_account @ StableFunError::InvalidVaultAccount,
+        constraint = old_vault_token_account.key() != new_vault_token_account.key() @ StableFunError::VaultAlreadyMigrated
+    )]
+    pub old_vault_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = stablebond_mint,
+        associated_token::authority = vault,
+    )]
+    pub new_vault_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Moves the vault's collateral into its associated token account and closes
+/// the old account, returning its rent to the authority.
+pub fn migrate_vault_token_account(ctx: Context<MigrateVaultTokenAccount>) -> Result<()> {
+    let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+    let vault_seeds: &[&[u8]] = &[
+        VAULT_SEED,
+        stablecoin_key.as_ref(),
+        &[ctx.accounts.vault.bump],
+    ];
+    let balance = ctx.accounts.old_vault_token_account.amount;
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.old_vault_token_account.to_account_info(),
+                to: ctx.accounts.new_vault_token_account.to_account_info(),
+                authority: ctx.accounts.vault.to_account_info(),
+            },
+            &[vault_seeds],
+        ),
+        balance,
+    )?;
+
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token::CloseAccount {
+            account: ctx.accounts.old_vault_token_account.to_account_info(),
+            destination: ctx.accounts.authority.to_account_info(),
+            authority: ctx.accounts.vault.to_account_info(),
+        },
+        &[vault_seeds],
+    ))?;
+
+    let old_account = ctx.accounts.vault.collateral_account;
+    ctx.accounts.vault.collateral_account = ctx.accounts.new_vault_token_account.key();
+
+    emit!(VaultTokenAccountMigrated {
+        stablecoin_mint: stablecoin_key,
+        old_account,
+        new_account: ctx.accounts.vault.collateral_account,
+        amount: balance,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct VaultTokenAccountMigrated {
+    pub stablecoin_mint: Pubkey,
+    pub old_account: Pubkey,
+    pub new_account: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct MigrateStablecoinLayout<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: older layouts don't deserialize as `StablecoinMint`; the
+    /// handler checks the discriminator, PDA and authority itself
+    #[account(mut, owner = crate::ID @ StableFunError::InvalidMint)]
+    pub stablecoin_mint: UncheckedAccount<'info>,
+
+    /// CHECK: the vault layout was only ever appended to, so it is
+    /// zero-extended rather than decoded
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump,
+        owner = crate::ID @ StableFunError::InvalidVault
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Rewrites a stablecoin created in an older layout version, and its vault,
+/// into the current layout, growing both accounts at the authority's expense.
+pub fn migrate_stablecoin_layout(ctx: Context<MigrateStablecoinLayout>) -> Result<()> {
+    let stablecoin_info = ctx.accounts.stablecoin_mint.to_account_info();
+    let stablecoin_key = stablecoin_info.key();
+    require!(
+        stablecoin_info.data_len() < StablecoinMint::LEN,
+        StableFunError::LayoutAlreadyCurrent
+    );
+
+    let mut stablecoin_mint = {
+        let data = stablecoin_info.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && data[..8] == StablecoinMint::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        StablecoinMint::from_legacy_layout(&data[8..])?
+    };
+    require_keys_eq!(
+        stablecoin_mint.authority,
+        ctx.accounts.authority.key(),
+        StableFunError::UnauthorizedMint
+    );
+
+    let (expected_key, bump) = Pubkey::find_program_address(
+        &[
+            STABLECOIN_SEED,
+            stablecoin_mint.authority.as_ref(),
+            stablecoin_mint.symbol.as_bytes(),
+        ],
+        &crate::ID,
+    );
+    require_keys_eq!(expected_key, stablecoin_key, StableFunError::InvalidMint);
+    require_keys_eq!(
+        stablecoin_mint.vault,
+        ctx.accounts.vault.key(),
+        StableFunError::InvalidVault
+    );
+    stablecoin_mint.bump = bump;
+    stablecoin_mint.mint_authority_bump = Pubkey::find_program_address(
+        &[MINT_AUTHORITY_SEED, stablecoin_key.as_ref()],
+        &crate::ID,
+    ).1;
+    stablecoin_mint.fee_vault_bump = Pubkey::find_program_address(
+        &[FEE_VAULT_SEED, stablecoin_key.as_ref()],
+        &crate::ID,
+    ).1;
+    stablecoin_mint.freeze_authority_bump = Pubkey::find_program_address(
+        &[FREEZE_AUTHORITY_SEED, stablecoin_key.as_ref()],
+        &crate::ID,
+    ).1;
+    stablecoin_mint.liquidity_lock_bump = Pubkey::find_program_address(
+        &[LOCKED_LIQUIDITY_SEED, stablecoin_key.as_ref()],
+        &crate::ID,
+    ).1;
+
+    grow_account(&ctx, &stablecoin_info, StablecoinMint::LEN)?;
+    {
+        let mut data = stablecoin_info.try_borrow_mut_data()?;
+        data.fill(0);
+        stablecoin_mint.try_serialize(&mut &mut data[..])?;
+    }
+
+    let vault_info = ctx.accounts.vault.to_account_info();
+    if vault_info.data_len() < StablecoinVault::LEN {
+        grow_account(&ctx, &vault_info, StablecoinVault::LEN)?;
+    }
+
+    emit!(StablecoinLayoutMigrated {
+        stablecoin_mint: stablecoin_key,
+        version: stablecoin_mint.version,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Tops `account` up to rent exemption at `len` bytes and zero-extends it.
+fn grow_account<'info>(
+    ctx: &Context<MigrateStablecoinLayout<'info>>,
+    account: &AccountInfo<'info>,
+    len: usize,
+) -> Result<()> {
+    let shortfall = Rent::get()?
+        .minimum_balance(len)
+        .saturating_sub(account.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    account.realloc(len, true)?;
+    Ok(())
+}
+
+#[event]
+pub struct StablecoinLayoutMigrated {
+    pub stablecoin_mint: Pubkey,
+    pub version: u8,
+    pub timestamp: i64,
+}
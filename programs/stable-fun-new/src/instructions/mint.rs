@@ -1,15 +1,33 @@
 use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
 use anchor_spl::token::{self, Token, TokenAccount, Mint};
+use anchor_spl::token_interface::{self, TokenInterface};
 use switchboard_solana::AggregatorAccountData;
 
-use crate::state::{StablecoinMint, StablecoinVault};
+use crate::state::{
+    CurrencyBasket, FeeDiscountConfig, NttConfig, OperationLog, OperationRecord, OperationType,
+    OrderDedupe, Portfolio, ProtocolConfig, StablecoinMint, StablecoinVault, StateAccount,
+    SystemAccounting, UserPosition, PAUSE_MINT,
+};
+use crate::constants::{
+    ACCOUNTING_SEED, BASKET_SEED, BOND_TERMS_SEED, DEDUPE_SEED, FEE_DISCOUNT_SEED,
+    LOCKED_LIQUIDITY_SEED, LOCKED_LIQUIDITY_TOKEN_SEED, NTT_CONFIG_SEED, OPERATION_LOG_SEED,
+    PORTFOLIO_SEED, POSITION_SEED, PROTOCOL_CONFIG_SEED,
+};
 use crate::error::StableFunError;
+use crate::instructions::compliance::LargeOperation;
+use crate::instructions::fee_discount::discounted_fee_bps;
+use crate::instructions::rejection;
 use crate::utils::oracle::OracleService;
 use crate::utils::validation::ValidationService;
+use crate::utils::attestation;
+use crate::utils::introspection;
 use crate::utils::math;
+use crate::utils::stablebond::StablebondService;
 
 #[derive(Accounts)]
-#[instruction(amount: u64)]
+#[instruction(amount: u64, client_order_id: Option<[u8; 32]>)]
 pub struct MintStablecoin<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
@@ -30,14 +48,14 @@ pub struct MintStablecoin<'info> {
         mut,
         constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
     )]
-    pub token_mint: Box<Account<'info, token::Mint>>,
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
 
     #[account(
         mut,
         constraint = user_token_account.mint == token_mint.key() @ StableFunError::InvalidTokenAccount,
         constraint = user_token_account.owner == user.key() @ StableFunError::InvalidTokenAccount
     )]
-    pub user_token_account: Box<Account<'info, TokenAccount>>,
+    pub user_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
 
     #[account(
         mut,
@@ -58,50 +76,446 @@ pub struct MintStablecoin<'info> {
     )]
     pub price_feed: AccountLoader<'info, AggregatorAccountData>,
 
+    /// FX feed composed with the bond NAV `price_feed`, on coins that set one
+    #[account(
+        constraint = fx_feed.key() == stablecoin_mint.fx_feed @ StableFunError::InvalidOracle
+    )]
+    pub fx_feed: Option<AccountLoader<'info, AggregatorAccountData>>,
+
+    /// CHECK: The coin's `BondTerms` PDA, used to price out the yield the
+    /// collateral has accrued; left uninitialized when no terms are set
+    #[account(seeds = [BOND_TERMS_SEED, stablecoin_mint.key().as_ref()], bump)]
+    pub bond_terms: UncheckedAccount<'info>,
+
     /// CHECK: PDA used as mint authority
     #[account(
         seeds = [b"mint-authority", stablecoin_mint.key().as_ref()],
-        bump
+        bump = stablecoin_mint.mint_authority_bump
     )]
     pub mint_authority: UncheckedAccount<'info>,
 
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserPosition::LEN,
+        seeds = [POSITION_SEED, stablecoin_mint.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub position: Box<Account<'info, UserPosition>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = SystemAccounting::LEN,
+        seeds = [ACCOUNTING_SEED, stablecoin_mint.key().as_ref()],
+        bump
+    )]
+    pub accounting: Box<Account<'info, SystemAccounting>>,
+
+    /// Only needed once the coin is registered with NTT
+    #[account(
+        seeds = [NTT_CONFIG_SEED, stablecoin_mint.key().as_ref()],
+        bump = ntt_config.bump
+    )]
+    pub ntt_config: Option<Box<Account<'info, NttConfig>>>,
+
+    /// Appended to when the stablecoin has opted into operation logging
+    #[account(
+        mut,
+        seeds = [OPERATION_LOG_SEED, stablecoin_mint.key().as_ref()],
+        bump = operation_log.load()?.bump
+    )]
+    pub operation_log: Option<AccountLoader<'info, OperationLog>>,
+
+    /// Required for basket-pegged coins
+    #[account(
+        seeds = [BASKET_SEED, stablecoin_mint.key().as_ref()],
+        bump = basket.bump
+    )]
+    pub basket: Option<Box<Account<'info, CurrencyBasket>>>,
+
+    /// Gates regulated coins and carries the protocol-wide pause
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_MINT) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    /// CHECK: Registry attestation of the user, verified in the handler;
+    /// only needed when the protocol gates regulated coins on holders
+    pub holder_attestation: Option<UncheckedAccount<'info>>,
+
+    /// Idempotency guard for integrators that retry; requires a client order id
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = OrderDedupe::LEN,
+        seeds = [
+            DEDUPE_SEED,
+            stablecoin_mint.key().as_ref(),
+            user.key().as_ref(),
+            &client_order_id.unwrap_or_default(),
+        ],
+        bump
+    )]
+    pub order_dedupe: Option<Box<Account<'info, OrderDedupe>>>,
+
+    /// CHECK: PDA owning the locked supply; the program never signs for it
+    #[account(
+        seeds = [LOCKED_LIQUIDITY_SEED, stablecoin_mint.key().as_ref()],
+        bump = stablecoin_mint.liquidity_lock_bump
+    )]
+    pub liquidity_lock: Option<UncheckedAccount<'info>>,
+
+    /// Receives the supply locked on the coin's first mint; only required then
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [LOCKED_LIQUIDITY_TOKEN_SEED, stablecoin_mint.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = liquidity_lock,
+        token::token_program = stablecoin_token_program
+    )]
+    pub locked_liquidity_account: Option<Box<InterfaceAccount<'info, token_interface::TokenAccount>>>,
+
+    /// Fee discount schedule; only applied with the user's discount token account
+    #[account(
+        seeds = [FEE_DISCOUNT_SEED, stablecoin_mint.key().as_ref()],
+        bump = fee_discount.bump
+    )]
+    pub fee_discount: Option<Box<Account<'info, FeeDiscountConfig>>>,
+
+    #[account(
+        constraint = discount_token_account.owner == user.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub discount_token_account: Option<Box<InterfaceAccount<'info, token_interface::TokenAccount>>>,
+
+    /// Updated when the user keeps a treasury portfolio
+    #[account(
+        mut,
+        seeds = [PORTFOLIO_SEED, user.key().as_ref()],
+        bump = portfolio.bump
+    )]
+    pub portfolio: Option<Box<Account<'info, Portfolio>>>,
+
+    /// CHECK: Instructions sysvar, checked by address
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
     pub token_program: Program<'info, Token>,
+    /// Token program owning the stablecoin mint (SPL Token or Token-2022)
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<MintStablecoin>, amount: u64) -> Result<()> {
-    let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
-    let vault = &mut ctx.accounts.vault;
+impl<'info> MintStablecoin<'info> {
+    /// Whether `init_if_needed` created any account for this mint, given
+    /// the `lock_amount` a first mint locks. A mint that lands without
+    /// minting must not leave those behind.
+    fn creates_accounts(&self, lock_amount: u64) -> bool {
+        !self.position.is_initialized()
+            || !self.accounting.is_initialized()
+            || self.order_dedupe.as_ref().is_some_and(|order_dedupe| !order_dedupe.is_filled())
+            || (lock_amount != 0 && self.locked_liquidity_account.is_some())
+    }
+}
 
-    // Validate mint is not paused
-    require!(!stablecoin_mint.settings.mint_paused, StableFunError::MintingPaused);
+/// Collateral and fee owed for minting `amount` at `oracle_price`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MintQuote {
+    pub collateral_amount: u64,
+    pub fee_amount: u64,
+    /// `amount` plus the fee, minted to the user and added to their debt
+    /// (in underlying units for rebasing coins)
+    pub total_amount: u64,
+}
+
+impl MintQuote {
+    pub fn receipt(&self, amount: u64, price: u64) -> FeeReceipt {
+        FeeReceipt {
+            gross_amount: amount,
+            fee_amount: self.fee_amount,
+            // Owed as debt, not collected, so none of it is surplus
+            protocol_share: 0,
+            referral_share: 0,
+            collateral_amount: self.collateral_amount,
+            price,
+        }
+    }
+}
+
+/// Breakdown of a mint or redeem, set as the instruction's return data so
+/// wallets can show an exact receipt.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeeReceipt {
+    /// Amount requested, before the fee
+    pub gross_amount: u64,
+    pub fee_amount: u64,
+    /// Part of the fee booked as surplus
+    pub protocol_share: u64,
+    /// Part of the fee paid to a referrer (no referrals yet, always 0)
+    pub referral_share: u64,
+    /// Collateral moved into or out of the vault
+    pub collateral_amount: u64,
+    /// Price the operation executed at, with `PRICE_DECIMALS` decimals
+    pub price: u64,
+}
+
+/// Collateral that backs `amount` coins at `min_collateral_ratio`, rounded
+/// up so the deposit never falls short of the ratio.
+pub fn backing_collateral(
+    amount: u64,
+    oracle_price: u64,
+    decimals: u8,
+    min_collateral_ratio: u16,
+) -> Result<u64> {
+    let backed_amount = math::bps_of(amount, min_collateral_ratio, math::Flow::FromUser)?;
+    math::calculate_deposit_amount(backed_amount, oracle_price, decimals)
+}
 
-    // Validate amount
+pub fn quote_mint(
+    amount: u64,
+    oracle_price: u64,
+    decimals: u8,
+    fee_basis_points: u16,
+    min_collateral_ratio: u16,
+) -> Result<MintQuote> {
+    let fee_amount = math::bps_of(amount, fee_basis_points, math::Flow::FromUser)?;
+    let total_amount = amount
+        .checked_add(fee_amount)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    // The position owes the fee too, so the deposit covers all of it
+    let collateral_amount = backing_collateral(total_amount, oracle_price, decimals, min_collateral_ratio)?;
+    Ok(MintQuote {
+        collateral_amount,
+        fee_amount,
+        total_amount,
+    })
+}
+
+/// Checks that need no price.
+#[inline(never)]
+fn validate_mint(ctx: &Context<MintStablecoin>, amount: u64) -> Result<()> {
+    let stablecoin_mint = &ctx.accounts.stablecoin_mint;
+
+    require!(!stablecoin_mint.settings.mint_paused, StableFunError::MintingPaused);
     require!(amount > 0, StableFunError::InvalidAmount);
+    let minted_amount = amount
+        .checked_add(stablecoin_mint.liquidity_lock_amount())
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    let new_supply = stablecoin_mint.current_supply
+        .checked_add(stablecoin_mint.to_raw_amount(minted_amount, false)?)
+        .ok_or(error!(StableFunError::MathOverflow))?;
     require!(
-        stablecoin_mint.current_supply.checked_add(amount).unwrap() <= stablecoin_mint.settings.max_supply,
+        new_supply <= stablecoin_mint.settings.max_supply,
         StableFunError::MaxSupplyExceeded
     );
+    // No opening positions too small to be worth liquidating
+    require!(
+        ctx.accounts.position.debt_amount.saturating_add(stablecoin_mint.to_raw_amount(amount, false)?)
+            >= stablecoin_mint.min_position_debt(),
+        StableFunError::PositionBelowMinimum
+    );
+
+    // Refuse to build on books that disagree with the token program
+    let bridged_supply = ctx.accounts.ntt_config
+        .as_ref()
+        .map_or(0, |config| config.bridged_supply);
+    stablecoin_mint.check_supply_drift(ctx.accounts.token_mint.supply, bridged_supply)?;
+
+    attestation::check_holder_attestation(
+        stablecoin_mint,
+        &ctx.accounts.protocol_config,
+        ctx.accounts.holder_attestation.as_ref().map(|account| account.as_ref()),
+        &ctx.accounts.user.key(),
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    // Optionally refuse to run inside another program's instruction, e.g. a flash loan
+    require!(
+        !stablecoin_mint.settings.top_level_only || introspection::is_top_level(),
+        StableFunError::CpiNotAllowed
+    );
+    let cpi_caller = introspection::cpi_caller(&ctx.accounts.instructions)?;
+    require!(
+        ctx.accounts.protocol_config.allows_cpi_caller(cpi_caller.as_ref()),
+        StableFunError::UnapprovedCpiCaller
+    );
+
+    // Optionally reject a mint that follows a redeem of the same coin in this transaction
+    if stablecoin_mint.settings.same_tx_guard {
+        for discriminator in [
+            crate::instruction::Redeem::DISCRIMINATOR,
+            crate::instruction::RevealRedeem::DISCRIMINATOR,
+            crate::instruction::RelayedRedeem::DISCRIMINATOR,
+        ] {
+            require!(
+                !introspection::has_prior_instruction(
+                    &ctx.accounts.instructions,
+                    &discriminator,
+                    &stablecoin_mint.key(),
+                )?,
+                StableFunError::SameTransactionMintRedeem
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Cached or fresh oracle price for the mint, adjusted for basket coins.
+#[inline(never)]
+fn mint_price(ctx: &Context<MintStablecoin>, amount: u64, clock: &Clock) -> Result<u64> {
+    let settings = &ctx.accounts.stablecoin_mint.settings;
+
+    // Small mints may reuse a fresh cached price instead of reading the feed
+    let cached_price = ctx.accounts.vault.usable_cached_price(
+        amount,
+        settings.cached_price_max_amount,
+        settings.cached_price_max_age_slots,
+        clock.slot,
+    );
+    let oracle_price = match cached_price {
+        Some(price) => price,
+        None => OracleService::collateral_price(
+            &ctx.accounts.stablecoin_mint,
+            &ctx.accounts.price_feed,
+            ctx.accounts.fx_feed.as_ref(),
+        )?,
+    };
+    OracleService::basket_adjusted_price(
+        &ctx.accounts.stablecoin_mint,
+        ctx.accounts.basket.as_deref().map(|basket| &**basket),
+        oracle_price,
+        clock.unix_timestamp,
+    )
+}
 
-    // Get oracle price
-    let oracle_price = OracleService::verify_oracle_price(&ctx.accounts.price_feed)?;
+/// A `dry_run` stops after the quote and returns the receipt without
+/// writing anything, so clients and other programs can pre-check a mint.
+/// It fails rather than create the position, accounting or dedupe
+/// accounts, and quotes a first mint without the locked liquidity account.
+pub fn handler(
+    ctx: Context<MintStablecoin>,
+    amount: u64,
+    client_order_id: Option<[u8; 32]>,
+    dry_run: bool,
+) -> Result<FeeReceipt> {
+    let clock = Clock::get()?;
+    let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+
+    // Business rejections surface before anything is written, so soft-fail
+    // mode can report them and still land the transaction
+    let prechecks = validate_mint(&ctx, amount).and_then(|_| mint_price(&ctx, amount, &clock));
+    let oracle_price = match rejection::soft_fail(
+        prechecks,
+        stablecoin_key,
+        &ctx.accounts.stablecoin_mint,
+        ctx.accounts.user.key(),
+        OperationType::Mint,
+        amount,
+        client_order_id,
+        ctx.accounts.creates_accounts(ctx.accounts.stablecoin_mint.liquidity_lock_amount()),
+    )? {
+        Some(price) => price,
+        None => return Ok(FeeReceipt::default()),
+    };
 
-    // Calculate required collateral amount
-    let collateral_amount = math::calculate_token_amount(
+    let fee_basis_points = discounted_fee_bps(
+        ctx.accounts.stablecoin_mint.settings.fee_basis_points,
+        ctx.accounts.fee_discount.as_deref().map(|fee_discount| &**fee_discount),
+        ctx.accounts.discount_token_account.as_deref().map(|token_account| &**token_account),
+    )?;
+    let mut quote = quote_mint(
         amount,
         oracle_price,
         ctx.accounts.token_mint.decimals,
+        fee_basis_points,
+        ctx.accounts.stablecoin_mint.settings.min_collateral_ratio,
+    )?;
+    // Positions open at the ratio redeems must keep, never straight into
+    // liquidation range
+    ValidationService::validate_collateral_ratio(
+        math::calculate_collateral_value(quote.collateral_amount, oracle_price, ctx.accounts.token_mint.decimals)?,
+        quote.total_amount,
+        ctx.accounts.stablecoin_mint.settings.min_collateral_ratio,
+    )?;
+    // Appreciated bonds back each coin with fewer of them, priced as on redeem
+    let stablebond = StablebondService::get_bond_terms_data(
+        &ctx.accounts.bond_terms,
+        &ctx.accounts.stablecoin_mint.stablebond_mint,
+    )?;
+    quote.collateral_amount = StablebondService::yield_adjusted_collateral(
+        quote.collateral_amount,
+        stablebond.as_ref(),
+        clock.unix_timestamp,
+        math::Flow::FromUser,
     )?;
 
-    // Calculate fees
-    let fee_amount = amount
-        .checked_mul(stablecoin_mint.settings.fee_basis_points as u64)
-        .and_then(|v| v.checked_div(10000))
+    // The coin's first mint also locks a sliver of supply, backed by
+    // collateral the minter deposits on top of their own
+    let lock_amount = ctx.accounts.stablecoin_mint.liquidity_lock_amount();
+    require!(
+        lock_amount == 0 || dry_run || ctx.accounts.locked_liquidity_account.is_some(),
+        StableFunError::MissingLiquidityLock
+    );
+    let lock_collateral = StablebondService::yield_adjusted_collateral(
+        backing_collateral(
+            lock_amount,
+            oracle_price,
+            ctx.accounts.token_mint.decimals,
+            ctx.accounts.stablecoin_mint.settings.min_collateral_ratio,
+        )?,
+        stablebond.as_ref(),
+        clock.unix_timestamp,
+        math::Flow::FromUser,
+    )?;
+    let deposit_amount = quote.collateral_amount
+        .checked_add(lock_collateral)
         .ok_or(error!(StableFunError::MathOverflow))?;
+    let receipt = FeeReceipt {
+        collateral_amount: deposit_amount,
+        ..quote.receipt(amount, oracle_price)
+    };
 
-    let total_amount = amount
-        .checked_add(fee_amount)
-        .ok_or(error!(StableFunError::MathOverflow))?;
+    if dry_run {
+        require!(
+            !ctx.accounts.creates_accounts(lock_amount),
+            StableFunError::DryRunCreatesAccounts
+        );
+        require!(
+            deposit_amount <= ctx.accounts.user_stablebond_account.amount,
+            StableFunError::InsufficientBalance
+        );
+        if let Some(order_dedupe) = ctx.accounts.order_dedupe.as_ref() {
+            order_dedupe.check_fill(client_order_id)?;
+        }
+        return Ok(receipt);
+    }
+
+    // Price the mint against the index as of now
+    ctx.accounts.stablecoin_mint.accrue_demurrage(clock.unix_timestamp)?;
+
+    // A retried order id fails here instead of minting twice
+    if let Some(order_dedupe) = ctx.accounts.order_dedupe.as_mut() {
+        order_dedupe.record_fill(
+            stablecoin_key,
+            ctx.accounts.user.key(),
+            client_order_id,
+            clock.slot,
+            ctx.bumps.order_dedupe.unwrap_or_default(),
+        )?;
+    }
+    let MintQuote {
+        collateral_amount,
+        fee_amount,
+        total_amount,
+    } = quote;
+    // Rebasing coins mint tokens worth `total_amount` at the current index
+    let raw_amount = ctx.accounts.stablecoin_mint.to_raw_amount(total_amount, false)?;
+    let raw_lock_amount = ctx.accounts.stablecoin_mint.to_raw_amount(lock_amount, false)?;
 
     // Transfer stablebonds to vault
     token::transfer(
@@ -113,36 +527,61 @@ pub fn handler(ctx: Context<MintStablecoin>, amount: u64) -> Result<()> {
                 authority: ctx.accounts.user.to_account_info(),
             },
         ),
-        collateral_amount,
+        deposit_amount,
     )?;
 
     // Mint stablecoins to user
-    token::mint_to(
+    token_interface::mint_to(
         CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            token::MintTo {
+            ctx.accounts.stablecoin_token_program.to_account_info(),
+            token_interface::MintTo {
                 mint: ctx.accounts.token_mint.to_account_info(),
                 to: ctx.accounts.user_token_account.to_account_info(),
                 authority: ctx.accounts.mint_authority.to_account_info(),
             },
             &[&[
                 b"mint-authority",
-                stablecoin_mint.key().as_ref(),
-                &[ctx.bumps.mint_authority],
+                stablecoin_key.as_ref(),
+                &[ctx.accounts.stablecoin_mint.mint_authority_bump],
             ]],
         ),
-        total_amount,
+        raw_amount,
     )?;
 
+    // Lock the first mint's sliver of supply where nothing can move it
+    let locked_liquidity_account = ctx.accounts.locked_liquidity_account
+        .as_ref()
+        .filter(|_| raw_lock_amount > 0);
+    if let Some(locked_liquidity_account) = locked_liquidity_account {
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.stablecoin_token_program.to_account_info(),
+                token_interface::MintTo {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: locked_liquidity_account.to_account_info(),
+                    authority: ctx.accounts.mint_authority.to_account_info(),
+                },
+                &[&[
+                    b"mint-authority",
+                    stablecoin_key.as_ref(),
+                    &[ctx.accounts.stablecoin_mint.mint_authority_bump],
+                ]],
+            ),
+            raw_lock_amount,
+        )?;
+    }
+
     // Update vault state
+    let vault = &mut ctx.accounts.vault;
     vault.total_collateral = vault
         .total_collateral
-        .checked_add(collateral_amount)
+        .checked_add(deposit_amount)
         .ok_or(error!(StableFunError::MathOverflow))?;
     
     vault.total_value_locked = vault
         .total_value_locked
         .checked_add(amount)
+        .and_then(|v| v.checked_add(lock_amount))
         .ok_or(error!(StableFunError::MathOverflow))?;
     
     vault.deposit_count = vault
@@ -150,16 +589,22 @@ pub fn handler(ctx: Context<MintStablecoin>, amount: u64) -> Result<()> {
         .checked_add(1)
         .ok_or(error!(StableFunError::MathOverflow))?;
     
-    vault.last_deposit_time = Clock::get()?.unix_timestamp;
+    vault.last_deposit_time = clock.unix_timestamp;
     
     // Update collateral ratio
-    ValidationService::update_collateral_ratio(vault)?;
+    ValidationService::update_collateral_ratio(vault, oracle_price, ctx.accounts.token_mint.decimals)?;
 
     // Update stablecoin state
+    let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
+    stablecoin_mint.consume_throughput(clock.unix_timestamp)?;
     stablecoin_mint.current_supply = stablecoin_mint
         .current_supply
-        .checked_add(total_amount)
+        .checked_add(raw_amount)
+        .and_then(|v| v.checked_add(raw_lock_amount))
         .ok_or(error!(StableFunError::MathOverflow))?;
+    if raw_lock_amount > 0 {
+        stablecoin_mint.stats.locked_liquidity = raw_lock_amount;
+    }
     
     stablecoin_mint.stats.total_minted = stablecoin_mint
         .stats
@@ -173,18 +618,62 @@ pub fn handler(ctx: Context<MintStablecoin>, amount: u64) -> Result<()> {
         .checked_add(fee_amount)
         .ok_or(error!(StableFunError::MathOverflow))?;
 
-    stablecoin_mint.last_updated = Clock::get()?.unix_timestamp;
+    // Only the authority mints, so this also keeps the dead-man switch at bay
+    stablecoin_mint.record_admin_action(clock.unix_timestamp);
+
+    // Update user position
+    let position = &mut ctx.accounts.position;
+    if !position.is_initialized() {
+        position.initialize(stablecoin_key, ctx.accounts.user.key(), ctx.bumps.position);
+    }
+    position.record_mint(collateral_amount, raw_amount, clock.unix_timestamp)?;
+
+    // The mint fee is only added to the user's debt, nothing is collected,
+    // so it books no surplus; redeems book the fee they take
+    let accounting = &mut ctx.accounts.accounting;
+    if !accounting.is_initialized() {
+        accounting.initialize(stablecoin_key, ctx.bumps.accounting);
+    }
+
+    if let Some(operation_log) = &ctx.accounts.operation_log {
+        operation_log.load_mut()?.append(OperationRecord::new(
+            OperationType::Mint,
+            ctx.accounts.user.key(),
+            amount,
+            oracle_price,
+            fee_amount,
+            clock.slot,
+        ).with_client_order_id(client_order_id));
+    }
+
+    if let Some(portfolio) = &mut ctx.accounts.portfolio {
+        portfolio.record_mint(stablecoin_key, amount, oracle_price, fee_amount)?;
+    }
 
+    if ctx.accounts.stablecoin_mint.exceeds_reporting_threshold(amount) {
+        emit!(LargeOperation {
+            stablecoin_mint: stablecoin_key,
+            operation: OperationType::Mint as u8,
+            user: ctx.accounts.user.key(),
+            token_account: ctx.accounts.user_token_account.key(),
+            collateral_account: ctx.accounts.user_stablebond_account.key(),
+            amount,
+            collateral_amount,
+            timestamp: clock.unix_timestamp,
+        });
+    }
     emit!(MintEvent {
-        stablecoin_mint: stablecoin_mint.key(),
+        stablecoin_mint: stablecoin_key,
         user: ctx.accounts.user.key(),
         amount,
         fee_amount,
         collateral_amount,
-        timestamp: Clock::get()?.unix_timestamp,
+        client_order_id,
+        maturity_timestamp: stablebond.as_ref().map_or(0, |stablebond| stablebond.maturity_timestamp),
+        timestamp: clock.unix_timestamp,
     });
 
-    Ok(())
+    Ok(receipt)
 }
 
 #[event]
@@ -194,6 +683,10 @@ pub struct MintEvent {
     pub amount: u64,
     pub fee_amount: u64,
     pub collateral_amount: u64,
+    /// Reference the caller passed in, echoed for reconciliation
+    pub client_order_id: Option<[u8; 32]>,
+    /// Maturity of the stablebond deposited; zero when it has no bond terms
+    pub maturity_timestamp: i64,
     pub timestamp: i64,
 }
 
@@ -201,6 +694,7 @@ pub struct MintEvent {
 mod tests {
     use super::*;
     use anchor_lang::solana_program::system_program;
+    use crate::constants::{DEFAULT_COLLATERAL_RATIO, MIN_COLLATERAL_RATIO};
 
     #[test]
     fn test_fee_calculation() {
@@ -223,4 +717,78 @@ mod tests {
         let total = amount.checked_add(fee).unwrap();
         assert_eq!(total, 1_003_000);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_quote_mint() {
+        // 1.003 coins owed at 150%, two bonds per coin
+        let quote = quote_mint(1_000_000, 2_000_000, 6, 30, DEFAULT_COLLATERAL_RATIO).unwrap();
+        assert_eq!(quote, MintQuote {
+            collateral_amount: 3_009_000,
+            fee_amount: 3_000,
+            total_amount: 1_003_000,
+        });
+        assert!(quote_mint(u64::MAX, 2_000_000, 6, 30, DEFAULT_COLLATERAL_RATIO).is_err());
+
+        assert_eq!(quote.receipt(1_000_000, 500_000), FeeReceipt {
+            gross_amount: 1_000_000,
+            fee_amount: 3_000,
+            protocol_share: 0,
+            referral_share: 0,
+            collateral_amount: 3_009_000,
+            price: 500_000,
+        });
+    }
+
+    #[test]
+    fn test_quote_mint_rounding_audit() {
+        math::rounding_audit::take();
+        // Amounts whose fee and deposit both leave a remainder
+        for amount in [333, 7_777, 123_456_789] {
+            let quote = quote_mint(amount, 1_000_003, 6, 30, MIN_COLLATERAL_RATIO).unwrap();
+            assert!(quote.fee_amount as u128 * 10_000 >= amount as u128 * 30);
+        }
+        let ledger = math::rounding_audit::take();
+        assert_eq!(ledger.roundings, 9);
+        assert!(ledger.protocol_remainder > 0);
+        assert_eq!(ledger.user_remainder, 0);
+    }
+
+    #[test]
+    fn test_mint_and_redeem_with_fx_feed() {
+        // EUR coin backed by a 1.02 USD bond at 0.92 EUR per USD: each bond
+        // is worth 0.9384 EUR
+        let price = OracleService::compose_price(1_020_000, 920_000).unwrap();
+        let quote = quote_mint(1_000_000, price, 6, 0, MIN_COLLATERAL_RATIO).unwrap();
+        assert_eq!(quote.collateral_amount, 1_065_643);
+        assert_eq!(
+            math::calculate_collateral_value(quote.collateral_amount, price, 6).unwrap(),
+            1_000_000
+        );
+
+        // Redeeming the coins back releases what was deposited
+        let released = math::calculate_token_amount(1_000_000, price, 6).unwrap();
+        assert_eq!(released, quote.collateral_amount);
+
+        // A stronger USD means fewer bonds per EUR coin
+        let stronger_usd = OracleService::compose_price(1_020_000, 1_000_000).unwrap();
+        assert!(stronger_usd < price);
+    }
+
+    #[test]
+    fn test_mint_basket_coin() {
+        // 60% USD / 40% MXN basket worth 0.62 USD, collateral worth 0.5 USD
+        let basket = CurrencyBasket {
+            index_price: 620_000,
+            index_updated_at: 100,
+            ..Default::default()
+        };
+        let price = basket.price_in_basket(2_000_000, 100).unwrap();
+        let quote = quote_mint(1_000_000, price, 6, 0, MIN_COLLATERAL_RATIO).unwrap();
+        // One basket unit takes 0.62 USD of collateral
+        assert_eq!(quote.collateral_amount, 1_240_000);
+        assert_eq!(
+            math::calculate_collateral_value(quote.collateral_amount, price, 6).unwrap(),
+            1_000_000
+        );
+    }
+}
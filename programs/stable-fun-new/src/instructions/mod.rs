@@ -1,11 +1,133 @@
+pub mod accounting;
+pub mod airdrop;
+pub mod auction;
+pub mod backstop;
+pub mod audit;
+pub mod basket;
+pub mod bond_terms;
+pub mod buyback;
+pub mod close;
+pub mod collateral_migration;
+pub mod collateral_registry;
+pub mod commit_redeem;
+pub mod compliance;
+pub mod council;
+pub mod dead_man;
+pub mod demurrage;
+pub mod dividend;
+pub mod epoch_stats;
+pub mod fee_discount;
+pub mod get_state;
+pub mod governance;
 pub mod initialize;
+pub mod interest;
+pub mod invoice;
+pub mod issuer_bond;
+pub mod issuer_profile;
+pub mod lending;
+pub mod liquidate;
+pub mod liquidity;
+pub mod lookup_table;
+pub mod maturity_policy;
+pub mod migrate;
 pub mod mint;
+pub mod ntt;
+pub mod operation_log;
+pub mod oracle;
+pub mod otc;
+pub mod pause;
+pub mod peg;
+pub mod portfolio;
+pub mod protocol;
+pub mod rate_model;
+pub mod rebase;
 pub mod redeem;
+pub mod redeem_order;
+pub mod rejection;
+pub mod relay;
+pub mod remote_redeem;
+pub mod reserve;
+pub mod reserved_names;
+pub mod rescue;
+pub mod savings;
+pub mod scheduled_transfer;
+pub mod share_vault;
+pub mod snapshot;
+pub mod sponsor;
+pub mod staking;
+pub mod stream;
+pub mod subscription;
+pub mod supply_cap;
+pub mod test_mode;
+pub mod tranche;
+pub mod transfer_fee;
+pub mod treasury;
 pub mod update;
 
+pub use accounting::*;
+pub use airdrop::*;
+pub use auction::*;
+pub use backstop::*;
+pub use audit::*;
+pub use basket::*;
+pub use bond_terms::*;
+pub use buyback::*;
+pub use close::*;
+pub use collateral_migration::*;
+pub use collateral_registry::*;
+pub use commit_redeem::*;
+pub use compliance::*;
+pub use council::*;
+pub use dead_man::*;
+pub use demurrage::*;
+pub use dividend::*;
+pub use epoch_stats::*;
+pub use fee_discount::*;
+pub use get_state::*;
+pub use governance::*;
 pub use initialize::*;
+pub use interest::*;
+pub use invoice::*;
+pub use issuer_bond::*;
+pub use issuer_profile::*;
+pub use lending::*;
+pub use liquidate::*;
+pub use liquidity::*;
+pub use lookup_table::*;
+pub use maturity_policy::*;
+pub use migrate::*;
 pub use mint::*;
+pub use ntt::*;
+pub use operation_log::*;
+pub use oracle::*;
+pub use otc::*;
+pub use pause::*;
+pub use peg::*;
+pub use portfolio::*;
+pub use protocol::*;
+pub use rate_model::*;
+pub use rebase::*;
 pub use redeem::*;
+pub use redeem_order::*;
+pub use rejection::*;
+pub use relay::*;
+pub use remote_redeem::*;
+pub use reserve::*;
+pub use reserved_names::*;
+pub use rescue::*;
+pub use savings::*;
+pub use scheduled_transfer::*;
+pub use share_vault::*;
+pub use snapshot::*;
+pub use sponsor::*;
+pub use staking::*;
+pub use stream::*;
+pub use subscription::*;
+pub use supply_cap::*;
+pub use test_mode::*;
+pub use tranche::*;
+pub use transfer_fee::*;
+pub use treasury::*;
 pub use update::*;
 
 use anchor_lang::prelude::*;
@@ -54,13 +176,11 @@ pub fn calculate_token_amount(
 /// Helper function to validate collateral ratio
 #[inline(never)]
 pub fn validate_collateral_ratio(
-    collateral_amount: u64,
     collateral_value: u64,
+    debt: u64,
     min_ratio: u16,
 ) -> Result<()> {
-    let ratio = collateral_value
-        .checked_mul(BASIS_POINTS_DIVISOR as u64)
-        .and_then(|v| v.checked_div(collateral_amount))
+    let ratio = crate::utils::math::collateral_ratio_bps(collateral_value, debt)
         .ok_or(ProgramError::MathOverflow)?;
 
     require!(
@@ -0,0 +1,238 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+
+use crate::state::{
+    NttConfig, OutboundRateLimit, ProtocolConfig, StablecoinMint, StateAccount, PAUSE_BRIDGE,
+};
+use crate::constants::{MINT_AUTHORITY_SEED, NTT_CONFIG_SEED, PROTOCOL_CONFIG_SEED};
+use crate::error::StableFunError;
+
+#[derive(Accounts)]
+pub struct SetNttManager<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = NttConfig::LEN,
+        seeds = [NTT_CONFIG_SEED, stablecoin_mint.key().as_ref()],
+        bump
+    )]
+    pub ntt_config: Box<Account<'info, NttConfig>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_BRIDGE) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Registers (or replaces) the NTT manager allowed to burn and mint for
+/// cross-chain transfers, and sets the outbound rate limit.
+pub fn set_ntt_manager(
+    ctx: Context<SetNttManager>,
+    ntt_manager: Pubkey,
+    outbound_limit: u64,
+    window_seconds: i64,
+) -> Result<()> {
+    require!(
+        ntt_manager != Pubkey::default() && window_seconds > 0,
+        StableFunError::InvalidNttConfig
+    );
+
+    let config = &mut ctx.accounts.ntt_config;
+    config.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    config.ntt_manager = ntt_manager;
+    config.outbound_limit = OutboundRateLimit {
+        limit: outbound_limit,
+        window_seconds,
+        ..config.outbound_limit.clone()
+    };
+    config.bump = ctx.bumps.ntt_config;
+
+    emit!(NttManagerSet {
+        stablecoin_mint: config.stablecoin_mint,
+        ntt_manager,
+        outbound_limit,
+        window_seconds,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct NttOutbound<'info> {
+    pub ntt_manager: Signer<'info>,
+
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [NTT_CONFIG_SEED, stablecoin_mint.key().as_ref()],
+        bump = ntt_config.bump,
+        constraint = ntt_config.ntt_manager == ntt_manager.key() @ StableFunError::UnauthorizedNttManager
+    )]
+    pub ntt_config: Box<Account<'info, NttConfig>>,
+
+    #[account(
+        mut,
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Tokens being sent, owned or delegated to the NTT manager
+    #[account(
+        mut,
+        constraint = source_token_account.mint == token_mint.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub source_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_BRIDGE) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Outbound hook: burns the tokens leaving Solana, subject to the rate limit.
+pub fn outbound(ctx: Context<NttOutbound>, amount: u64) -> Result<()> {
+    require!(amount > 0, StableFunError::InvalidAmount);
+    let clock = Clock::get()?;
+
+    ctx.accounts.ntt_config.record_outbound(amount, clock.unix_timestamp)?;
+
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::Burn {
+                mint: ctx.accounts.token_mint.to_account_info(),
+                from: ctx.accounts.source_token_account.to_account_info(),
+                authority: ctx.accounts.ntt_manager.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    emit!(NttTransfer {
+        stablecoin_mint: ctx.accounts.stablecoin_mint.key(),
+        token_account: ctx.accounts.source_token_account.key(),
+        amount,
+        outbound: true,
+        bridged_supply: ctx.accounts.ntt_config.bridged_supply,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct NttInbound<'info> {
+    pub ntt_manager: Signer<'info>,
+
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [NTT_CONFIG_SEED, stablecoin_mint.key().as_ref()],
+        bump = ntt_config.bump,
+        constraint = ntt_config.ntt_manager == ntt_manager.key() @ StableFunError::UnauthorizedNttManager
+    )]
+    pub ntt_config: Box<Account<'info, NttConfig>>,
+
+    #[account(
+        mut,
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = recipient_token_account.mint == token_mint.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub recipient_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: PDA used as mint authority
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, stablecoin_mint.key().as_ref()],
+        bump = stablecoin_mint.mint_authority_bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_BRIDGE) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Inbound hook: re-mints supply returning from another chain. Never mints
+/// more than was bridged out, so collateral coverage is unchanged.
+pub fn inbound(ctx: Context<NttInbound>, amount: u64) -> Result<()> {
+    require!(amount > 0, StableFunError::InvalidAmount);
+
+    ctx.accounts.ntt_config.record_inbound(amount)?;
+
+    let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::MintTo {
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.recipient_token_account.to_account_info(),
+                authority: ctx.accounts.mint_authority.to_account_info(),
+            },
+            &[&[
+                MINT_AUTHORITY_SEED,
+                stablecoin_key.as_ref(),
+                &[ctx.accounts.stablecoin_mint.mint_authority_bump],
+            ]],
+        ),
+        amount,
+    )?;
+
+    emit!(NttTransfer {
+        stablecoin_mint: stablecoin_key,
+        token_account: ctx.accounts.recipient_token_account.key(),
+        amount,
+        outbound: false,
+        bridged_supply: ctx.accounts.ntt_config.bridged_supply,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct NttManagerSet {
+    pub stablecoin_mint: Pubkey,
+    pub ntt_manager: Pubkey,
+    pub outbound_limit: u64,
+    pub window_seconds: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NttTransfer {
+    pub stablecoin_mint: Pubkey,
+    pub token_account: Pubkey,
+    pub amount: u64,
+    pub outbound: bool,
+    pub bridged_supply: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{OperationLog, ProtocolConfig, StablecoinMint};
+use crate::constants::{OPERATION_LOG_SEED, PROTOCOL_CONFIG_SEED};
+use crate::error::StableFunError;
+
+#[derive(Accounts)]
+pub struct InitOperationLog<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = OperationLog::LEN,
+        seeds = [OPERATION_LOG_SEED, stablecoin_mint.key().as_ref()],
+        bump
+    )]
+    pub operation_log: AccountLoader<'info, OperationLog>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Opts the stablecoin into on-chain operation logging; mint, redeem and
+/// liquidate append to the log whenever it is passed in.
+pub fn init_operation_log(ctx: Context<InitOperationLog>) -> Result<()> {
+    let mut log = ctx.accounts.operation_log.load_init()?;
+    log.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    log.bump = ctx.bumps.operation_log;
+    Ok(())
+}
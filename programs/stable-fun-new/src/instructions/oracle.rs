@@ -0,0 +1,357 @@
+use anchor_lang::prelude::*;
+use switchboard_solana::AggregatorAccountData;
+
+use crate::state::{CachedPrice, ProtocolConfig, StablecoinMint, StablecoinVault};
+use crate::constants::{
+    MAX_FEED_DEVIATION_BPS, PRICE_FEED_TIMELOCK, PROTOCOL_CONFIG_SEED, VAULT_SEED,
+};
+use crate::error::StableFunError;
+use crate::utils::oracle::OracleService;
+
+#[derive(Accounts)]
+pub struct CheckOracle<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(mut)]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    /// The Switchboard V3 aggregator account
+    #[account(
+        constraint = price_feed.key() == stablecoin_mint.price_feed @ StableFunError::InvalidOracle
+    )]
+    pub price_feed: AccountLoader<'info, AggregatorAccountData>,
+
+    /// FX feed composed with the bond NAV `price_feed`, on coins that set one
+    #[account(
+        constraint = fx_feed.key() == stablecoin_mint.fx_feed @ StableFunError::InvalidOracle
+    )]
+    pub fx_feed: Option<AccountLoader<'info, AggregatorAccountData>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+}
+
+/// Permissionless crank: validates the price feed and records the outcome
+/// instead of failing, so a failure streak can trip the mint auto-pause.
+pub fn check_oracle(ctx: Context<CheckOracle>) -> Result<()> {
+    let clock = Clock::get()?;
+    let healthy = OracleService::collateral_price(
+        &ctx.accounts.stablecoin_mint,
+        &ctx.accounts.price_feed,
+        ctx.accounts.fx_feed.as_ref(),
+    ).is_ok();
+
+    let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
+    if healthy {
+        stablecoin_mint.record_oracle_success();
+    } else if stablecoin_mint.record_oracle_failure(clock.slot, clock.unix_timestamp) {
+        emit!(OracleAutoPaused {
+            stablecoin_mint: stablecoin_mint.key(),
+            price_feed: stablecoin_mint.price_feed,
+            failure_streak: stablecoin_mint.oracle_failure_streak,
+            slot: clock.slot,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    emit!(OracleChecked {
+        stablecoin_mint: stablecoin_mint.key(),
+        healthy,
+        failure_streak: stablecoin_mint.oracle_failure_streak,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RefreshPrice<'info> {
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    /// The Switchboard V3 aggregator account
+    #[account(
+        constraint = price_feed.key() == stablecoin_mint.price_feed @ StableFunError::InvalidOracle
+    )]
+    pub price_feed: AccountLoader<'info, AggregatorAccountData>,
+
+    /// FX feed composed with the bond NAV `price_feed`, on coins that set one
+    #[account(
+        constraint = fx_feed.key() == stablecoin_mint.fx_feed @ StableFunError::InvalidOracle
+    )]
+    pub fx_feed: Option<AccountLoader<'info, AggregatorAccountData>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless crank: validates the feed once and caches the price on the
+/// vault for small mints and redeems to reuse.
+pub fn refresh_price(ctx: Context<RefreshPrice>) -> Result<()> {
+    let price = OracleService::collateral_price(
+        &ctx.accounts.stablecoin_mint,
+        &ctx.accounts.price_feed,
+        ctx.accounts.fx_feed.as_ref(),
+    )?;
+    let slot = Clock::get()?.slot;
+    ctx.accounts.vault.cached_price = CachedPrice { price, slot };
+
+    emit!(PriceRefreshed {
+        stablecoin_mint: ctx.accounts.stablecoin_mint.key(),
+        price,
+        slot,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPriceFeed<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    /// The feed in use, which the new one is compared against
+    #[account(
+        constraint = price_feed.key() == stablecoin_mint.price_feed @ StableFunError::InvalidOracle
+    )]
+    pub price_feed: AccountLoader<'info, AggregatorAccountData>,
+
+    #[account(
+        constraint = fx_feed.key() == stablecoin_mint.fx_feed @ StableFunError::InvalidOracle
+    )]
+    pub fx_feed: Option<AccountLoader<'info, AggregatorAccountData>>,
+
+    /// Bond NAV feed, or the only feed when `new_fx_feed` is omitted
+    pub new_price_feed: AccountLoader<'info, AggregatorAccountData>,
+
+    /// FX feed from the bond's underlying currency to the coin's target;
+    /// omitting it prices the coin off `new_price_feed` alone
+    pub new_fx_feed: Option<AccountLoader<'info, AggregatorAccountData>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Reads both pricings once now and once at apply time; a replacement that
+/// is stale or disagrees with the current price never gets queued or applied.
+/// The NAV and FX feeds are compared as the composed price they produce.
+/// A current pricing that is stale or otherwise invalid has nothing to be
+/// compared against, so only the replacement is validated and the timelock
+/// alone protects holders.
+fn check_replacement_feed(
+    stablecoin_mint: &StablecoinMint,
+    price_feed: &AccountLoader<AggregatorAccountData>,
+    fx_feed: Option<&AccountLoader<AggregatorAccountData>>,
+    new_price_feed: &AccountLoader<AggregatorAccountData>,
+    new_fx_feed: Option<&AccountLoader<AggregatorAccountData>>,
+) -> Result<u64> {
+    // Omitting the current FX feed must not be a way around the comparison
+    require!(
+        stablecoin_mint.fx_feed == Pubkey::default() || fx_feed.is_some(),
+        StableFunError::InvalidOracle
+    );
+    let current_price = OracleService::collateral_price(stablecoin_mint, price_feed, fx_feed).ok();
+    OracleService::verify_feed_owner(&new_price_feed.to_account_info())?;
+    let mut new_price = OracleService::verify_oracle_price(new_price_feed)?;
+    if let Some(new_fx_feed) = new_fx_feed {
+        OracleService::verify_feed_owner(&new_fx_feed.to_account_info())?;
+        let fx_rate = OracleService::verify_oracle_price(new_fx_feed)?;
+        new_price = OracleService::compose_price(new_price, fx_rate)?;
+    }
+    if let Some(current_price) = current_price {
+        OracleService::check_feed_deviation(current_price, new_price, MAX_FEED_DEVIATION_BPS)?;
+    }
+    Ok(new_price)
+}
+
+/// Queues a new price feed, applicable after `PRICE_FEED_TIMELOCK` so holders
+/// can exit before the coin is priced from a different source. Replaces any
+/// previously queued feed and restarts the timelock.
+pub fn set_price_feed(ctx: Context<SetPriceFeed>) -> Result<()> {
+    let new_fx_feed = ctx.accounts.new_fx_feed.as_ref().map_or(Pubkey::default(), |feed| feed.key());
+    require!(
+        ctx.accounts.new_price_feed.key() != ctx.accounts.stablecoin_mint.price_feed
+            || new_fx_feed != ctx.accounts.stablecoin_mint.fx_feed,
+        StableFunError::InvalidOracle
+    );
+    let new_price = check_replacement_feed(
+        &ctx.accounts.stablecoin_mint,
+        &ctx.accounts.price_feed,
+        ctx.accounts.fx_feed.as_ref(),
+        &ctx.accounts.new_price_feed,
+        ctx.accounts.new_fx_feed.as_ref(),
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let effective_at = now
+        .checked_add(PRICE_FEED_TIMELOCK)
+        .ok_or(StableFunError::MathOverflow)?;
+    let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
+    stablecoin_mint.pending_price_feed = ctx.accounts.new_price_feed.key();
+    stablecoin_mint.pending_fx_feed = new_fx_feed;
+    stablecoin_mint.pending_price_feed_at = effective_at;
+    stablecoin_mint.record_admin_action(now);
+
+    emit!(PriceFeedProposed {
+        stablecoin_mint: stablecoin_mint.key(),
+        price_feed: stablecoin_mint.pending_price_feed,
+        fx_feed: stablecoin_mint.pending_fx_feed,
+        price: new_price,
+        effective_at,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ApplyPriceFeed<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(mut)]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        constraint = price_feed.key() == stablecoin_mint.price_feed @ StableFunError::InvalidOracle
+    )]
+    pub price_feed: AccountLoader<'info, AggregatorAccountData>,
+
+    #[account(
+        constraint = fx_feed.key() == stablecoin_mint.fx_feed @ StableFunError::InvalidOracle
+    )]
+    pub fx_feed: Option<AccountLoader<'info, AggregatorAccountData>>,
+
+    #[account(
+        constraint = new_price_feed.key() == stablecoin_mint.pending_price_feed @ StableFunError::NoPendingPriceFeed
+    )]
+    pub new_price_feed: AccountLoader<'info, AggregatorAccountData>,
+
+    /// Required when an FX feed was queued alongside `new_price_feed`
+    #[account(
+        constraint = new_fx_feed.key() == stablecoin_mint.pending_fx_feed @ StableFunError::NoPendingPriceFeed
+    )]
+    pub new_fx_feed: Option<AccountLoader<'info, AggregatorAccountData>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+}
+
+/// Permissionless once the timelock has elapsed. Drops the vault's cached
+/// price so nothing keeps trading on a price read from the old feed.
+pub fn apply_price_feed(ctx: Context<ApplyPriceFeed>) -> Result<()> {
+    require!(
+        ctx.accounts.stablecoin_mint.pending_fx_feed == Pubkey::default()
+            || ctx.accounts.new_fx_feed.is_some(),
+        StableFunError::NoPendingPriceFeed
+    );
+    check_replacement_feed(
+        &ctx.accounts.stablecoin_mint,
+        &ctx.accounts.price_feed,
+        ctx.accounts.fx_feed.as_ref(),
+        &ctx.accounts.new_price_feed,
+        ctx.accounts.new_fx_feed.as_ref(),
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
+    let old_feed = stablecoin_mint.apply_pending_price_feed(now)?;
+    ctx.accounts.vault.cached_price = CachedPrice::default();
+
+    emit!(PriceFeedRotated {
+        stablecoin_mint: stablecoin_mint.key(),
+        old_feed,
+        new_feed: stablecoin_mint.price_feed,
+        fx_feed: stablecoin_mint.fx_feed,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PriceRefreshed {
+    pub stablecoin_mint: Pubkey,
+    pub price: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct OracleChecked {
+    pub stablecoin_mint: Pubkey,
+    pub healthy: bool,
+    pub failure_streak: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PriceFeedProposed {
+    pub stablecoin_mint: Pubkey,
+    pub price_feed: Pubkey,
+    /// Default when the coin is priced off `price_feed` alone
+    pub fx_feed: Pubkey,
+    /// Composed price the new feeds reported when queued
+    pub price: u64,
+    pub effective_at: i64,
+}
+
+#[event]
+pub struct PriceFeedRotated {
+    pub stablecoin_mint: Pubkey,
+    pub old_feed: Pubkey,
+    pub new_feed: Pubkey,
+    pub fx_feed: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Alert: minting was paused after repeated oracle failures.
+#[event]
+pub struct OracleAutoPaused {
+    pub stablecoin_mint: Pubkey,
+    pub price_feed: Pubkey,
+    pub failure_streak: u16,
+    pub slot: u64,
+    pub timestamp: i64,
+}
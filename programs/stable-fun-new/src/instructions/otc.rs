@@ -0,0 +1,378 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+
+use crate::state::{OtcOffer, ProtocolConfig, StablecoinMint, StateAccount, PAUSE_TRANSFERS};
+use crate::constants::{OTC_ESCROW_SEED, OTC_OFFER_SEED, PROTOCOL_CONFIG_SEED};
+use crate::error::StableFunError;
+
+#[derive(Accounts)]
+#[instruction(offer_id: u64)]
+pub struct CreateOtcOffer<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    #[account(
+        init,
+        payer = maker,
+        space = OtcOffer::LEN,
+        seeds = [OTC_OFFER_SEED, maker.key().as_ref(), &offer_id.to_le_bytes()],
+        bump
+    )]
+    pub offer: Box<Account<'info, OtcOffer>>,
+
+    pub offered_stablecoin: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        constraint = requested_stablecoin.key() != offered_stablecoin.key() @ StableFunError::InvalidOtcOffer
+    )]
+    pub requested_stablecoin: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        constraint = offered_token_mint.key() == offered_stablecoin.token_mint @ StableFunError::InvalidMint
+    )]
+    pub offered_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = maker,
+        seeds = [OTC_ESCROW_SEED, offer.key().as_ref()],
+        bump,
+        token::mint = offered_token_mint,
+        token::authority = offer,
+        token::token_program = offered_token_program
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = maker_token_account.mint == offered_token_mint.key() @ StableFunError::InvalidTokenAccount,
+        constraint = maker_token_account.owner == maker.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub maker_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_TRANSFERS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub offered_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Escrows `offered_amount` of one stablecoin against `requested_amount` of
+/// another, optionally reserved for a single `counterparty`.
+pub fn create_otc_offer(
+    ctx: Context<CreateOtcOffer>,
+    offer_id: u64,
+    offered_amount: u64,
+    requested_amount: u64,
+    counterparty: Option<Pubkey>,
+    expires_at: i64,
+) -> Result<()> {
+    require!(offered_amount > 0 && requested_amount > 0, StableFunError::InvalidAmount);
+    let now = Clock::get()?.unix_timestamp;
+    require!(expires_at == 0 || expires_at > now, StableFunError::OfferExpired);
+
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.offered_token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.maker_token_account.to_account_info(),
+                mint: ctx.accounts.offered_token_mint.to_account_info(),
+                to: ctx.accounts.escrow_token_account.to_account_info(),
+                authority: ctx.accounts.maker.to_account_info(),
+            },
+        ),
+        offered_amount,
+        ctx.accounts.offered_token_mint.decimals,
+    )?;
+    // Transfer fees are withheld from the escrow, so offer what arrived
+    ctx.accounts.escrow_token_account.reload()?;
+
+    let offer = &mut ctx.accounts.offer;
+    offer.maker = ctx.accounts.maker.key();
+    offer.offer_id = offer_id;
+    offer.offered_stablecoin = ctx.accounts.offered_stablecoin.key();
+    offer.offered_amount = ctx.accounts.escrow_token_account.amount;
+    offer.requested_stablecoin = ctx.accounts.requested_stablecoin.key();
+    offer.requested_amount = requested_amount;
+    offer.counterparty = counterparty.unwrap_or_default();
+    offer.expires_at = expires_at;
+    offer.token_account = ctx.accounts.escrow_token_account.key();
+    offer.bump = ctx.bumps.offer;
+
+    emit!(OtcOfferCreated {
+        offer: offer.key(),
+        maker: offer.maker,
+        offered_stablecoin: offer.offered_stablecoin,
+        offered_amount: offer.offered_amount,
+        requested_stablecoin: offer.requested_stablecoin,
+        requested_amount,
+        counterparty: offer.counterparty,
+        expires_at,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AcceptOffer<'info> {
+    pub taker: Signer<'info>,
+
+    /// CHECK: Receives the offer's rent; must be its maker
+    #[account(
+        mut,
+        constraint = maker.key() == offer.maker @ StableFunError::InvalidOtcOffer
+    )]
+    pub maker: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = maker,
+        seeds = [OTC_OFFER_SEED, offer.maker.as_ref(), &offer.offer_id.to_le_bytes()],
+        bump = offer.bump
+    )]
+    pub offer: Box<Account<'info, OtcOffer>>,
+
+    #[account(
+        constraint = offered_stablecoin.key() == offer.offered_stablecoin @ StableFunError::InvalidMint
+    )]
+    pub offered_stablecoin: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        constraint = requested_stablecoin.key() == offer.requested_stablecoin @ StableFunError::InvalidMint
+    )]
+    pub requested_stablecoin: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        constraint = offered_token_mint.key() == offered_stablecoin.token_mint @ StableFunError::InvalidMint
+    )]
+    pub offered_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        constraint = requested_token_mint.key() == requested_stablecoin.token_mint @ StableFunError::InvalidMint
+    )]
+    pub requested_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = escrow_token_account.key() == offer.token_account @ StableFunError::InvalidTokenAccount
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Taker's account the offered coins go to
+    #[account(
+        mut,
+        constraint = taker_receive_account.mint == offered_token_mint.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub taker_receive_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Taker's account the requested coins are paid from
+    #[account(
+        mut,
+        constraint = taker_pay_account.mint == requested_token_mint.key() @ StableFunError::InvalidTokenAccount,
+        constraint = taker_pay_account.owner == taker.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub taker_pay_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Maker's account the requested coins go to
+    #[account(
+        mut,
+        constraint = maker_receive_account.mint == requested_token_mint.key() @ StableFunError::InvalidTokenAccount,
+        constraint = maker_receive_account.owner == offer.maker @ StableFunError::InvalidTokenAccount
+    )]
+    pub maker_receive_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_TRANSFERS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub offered_token_program: Interface<'info, TokenInterface>,
+    pub requested_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Settles the swap atomically: the taker pays the requested coins to the
+/// maker and receives the escrow, and the offer is closed.
+pub fn accept_offer(ctx: Context<AcceptOffer>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let offer = &ctx.accounts.offer;
+    offer.validate_acceptance(&ctx.accounts.taker.key(), now)?;
+
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.requested_token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.taker_pay_account.to_account_info(),
+                mint: ctx.accounts.requested_token_mint.to_account_info(),
+                to: ctx.accounts.maker_receive_account.to_account_info(),
+                authority: ctx.accounts.taker.to_account_info(),
+            },
+        ),
+        offer.requested_amount,
+        ctx.accounts.requested_token_mint.decimals,
+    )?;
+
+    release_escrow(
+        offer,
+        &ctx.accounts.offered_token_program,
+        &ctx.accounts.escrow_token_account,
+        &ctx.accounts.offered_token_mint,
+        ctx.accounts.taker_receive_account.to_account_info(),
+        ctx.accounts.maker.to_account_info(),
+    )?;
+
+    emit!(OtcOfferAccepted {
+        offer: offer.key(),
+        maker: offer.maker,
+        taker: ctx.accounts.taker.key(),
+        offered_amount: offer.offered_amount,
+        requested_amount: offer.requested_amount,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelOffer<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    #[account(
+        mut,
+        close = maker,
+        seeds = [OTC_OFFER_SEED, maker.key().as_ref(), &offer.offer_id.to_le_bytes()],
+        bump = offer.bump
+    )]
+    pub offer: Box<Account<'info, OtcOffer>>,
+
+    #[account(
+        constraint = offered_stablecoin.key() == offer.offered_stablecoin @ StableFunError::InvalidMint
+    )]
+    pub offered_stablecoin: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        constraint = offered_token_mint.key() == offered_stablecoin.token_mint @ StableFunError::InvalidMint
+    )]
+    pub offered_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = escrow_token_account.key() == offer.token_account @ StableFunError::InvalidTokenAccount
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = maker_token_account.mint == offered_token_mint.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub maker_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_TRANSFERS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub offered_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Returns the escrow to the maker and closes the offer. Expired offers are
+/// cancelled the same way.
+pub fn cancel_offer(ctx: Context<CancelOffer>) -> Result<()> {
+    let offer = &ctx.accounts.offer;
+    release_escrow(
+        offer,
+        &ctx.accounts.offered_token_program,
+        &ctx.accounts.escrow_token_account,
+        &ctx.accounts.offered_token_mint,
+        ctx.accounts.maker_token_account.to_account_info(),
+        ctx.accounts.maker.to_account_info(),
+    )?;
+
+    emit!(OtcOfferCancelled {
+        offer: offer.key(),
+        maker: offer.maker,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Empties the escrow into `destination` and closes it, its rent going to
+/// `rent_destination`.
+fn release_escrow<'info>(
+    offer: &Account<'info, OtcOffer>,
+    token_program: &Interface<'info, TokenInterface>,
+    escrow_token_account: &InterfaceAccount<'info, TokenAccount>,
+    token_mint: &InterfaceAccount<'info, Mint>,
+    destination: AccountInfo<'info>,
+    rent_destination: AccountInfo<'info>,
+) -> Result<()> {
+    let offer_id = offer.offer_id.to_le_bytes();
+    let seeds: &[&[u8]] = &[OTC_OFFER_SEED, offer.maker.as_ref(), &offer_id, &[offer.bump]];
+
+    if escrow_token_account.amount > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: escrow_token_account.to_account_info(),
+                    mint: token_mint.to_account_info(),
+                    to: destination,
+                    authority: offer.to_account_info(),
+                },
+                &[seeds],
+            ),
+            escrow_token_account.amount,
+            token_mint.decimals,
+        )?;
+    }
+
+    token_interface::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        token_interface::CloseAccount {
+            account: escrow_token_account.to_account_info(),
+            destination: rent_destination,
+            authority: offer.to_account_info(),
+        },
+        &[seeds],
+    ))
+}
+
+#[event]
+pub struct OtcOfferCreated {
+    pub offer: Pubkey,
+    pub maker: Pubkey,
+    pub offered_stablecoin: Pubkey,
+    pub offered_amount: u64,
+    pub requested_stablecoin: Pubkey,
+    pub requested_amount: u64,
+    pub counterparty: Pubkey,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OtcOfferAccepted {
+    pub offer: Pubkey,
+    pub maker: Pubkey,
+    pub taker: Pubkey,
+    pub offered_amount: u64,
+    pub requested_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OtcOfferCancelled {
+    pub offer: Pubkey,
+    pub maker: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,198 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use switchboard_solana::AggregatorAccountData;
+
+use crate::state::{StablecoinMint, StablecoinVault};
+use crate::constants::VAULT_SEED;
+use crate::error::StableFunError;
+use crate::utils::oracle::OracleService;
+use crate::utils::validation::ValidationService;
+use crate::utils::math;
+
+#[derive(Accounts)]
+pub struct SetPauser<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+}
+
+/// Delegates the pauser role; the default pubkey revokes it.
+pub fn set_pauser(ctx: Context<SetPauser>, pauser: Pubkey) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
+    let old_pauser = stablecoin_mint.pauser;
+    stablecoin_mint.pauser = pauser;
+    stablecoin_mint.record_admin_action(now);
+
+    emit!(PauserUpdated {
+        stablecoin_mint: stablecoin_mint.key(),
+        old_pauser,
+        new_pauser: pauser,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    pub pauser: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_mint.is_pauser(&pauser.key()) @ StableFunError::UnauthorizedPauser
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+}
+
+pub fn pause_mint(ctx: Context<SetPaused>) -> Result<()> {
+    ctx.accounts.stablecoin_mint.pause_mint(Clock::get()?.unix_timestamp);
+
+    emit!(MintPaused {
+        stablecoin_mint: ctx.accounts.stablecoin_mint.key(),
+        pauser: ctx.accounts.pauser.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct Unpause<'info> {
+    pub pauser: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_mint.is_pauser(&pauser.key()) @ StableFunError::UnauthorizedPauser
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The Switchboard V3 aggregator account
+    #[account(
+        constraint = price_feed.key() == stablecoin_mint.price_feed @ StableFunError::InvalidOracle
+    )]
+    pub price_feed: AccountLoader<'info, AggregatorAccountData>,
+
+    /// FX feed composed with the bond NAV `price_feed`, on coins that set one
+    #[account(
+        constraint = fx_feed.key() == stablecoin_mint.fx_feed @ StableFunError::InvalidOracle
+    )]
+    pub fx_feed: Option<AccountLoader<'info, AggregatorAccountData>>,
+}
+
+/// Refuses to lift a pause while the oracle is failing, the coin is
+/// under-collateralized or the cool-off since pausing is still running.
+fn check_unpause(ctx: &mut Context<Unpause>) -> Result<()> {
+    let oracle_price = OracleService::collateral_price(
+        &ctx.accounts.stablecoin_mint,
+        &ctx.accounts.price_feed,
+        ctx.accounts.fx_feed.as_ref(),
+    )?;
+    let collateral_value = math::calculate_collateral_value(
+        ctx.accounts.vault.total_collateral,
+        oracle_price,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
+    ValidationService::validate_unpause(
+        collateral_value,
+        stablecoin_mint.current_supply,
+        stablecoin_mint.settings.min_collateral_ratio,
+        stablecoin_mint.paused_at,
+        stablecoin_mint.settings.unpause_cooloff_seconds,
+        Clock::get()?.unix_timestamp,
+    )?;
+    stablecoin_mint.record_oracle_success();
+    Ok(())
+}
+
+pub fn unpause_mint(mut ctx: Context<Unpause>) -> Result<()> {
+    check_unpause(&mut ctx)?;
+    ctx.accounts.stablecoin_mint.settings.mint_paused = false;
+
+    emit!(MintUnpaused {
+        stablecoin_mint: ctx.accounts.stablecoin_mint.key(),
+        pauser: ctx.accounts.pauser.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn pause_redeem(ctx: Context<SetPaused>) -> Result<()> {
+    ctx.accounts.stablecoin_mint.pause_redeem(Clock::get()?.unix_timestamp);
+
+    emit!(RedeemPaused {
+        stablecoin_mint: ctx.accounts.stablecoin_mint.key(),
+        pauser: ctx.accounts.pauser.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn unpause_redeem(mut ctx: Context<Unpause>) -> Result<()> {
+    check_unpause(&mut ctx)?;
+    ctx.accounts.stablecoin_mint.settings.redeem_paused = false;
+
+    emit!(RedeemUnpaused {
+        stablecoin_mint: ctx.accounts.stablecoin_mint.key(),
+        pauser: ctx.accounts.pauser.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PauserUpdated {
+    pub stablecoin_mint: Pubkey,
+    pub old_pauser: Pubkey,
+    pub new_pauser: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MintPaused {
+    pub stablecoin_mint: Pubkey,
+    pub pauser: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MintUnpaused {
+    pub stablecoin_mint: Pubkey,
+    pub pauser: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RedeemPaused {
+    pub stablecoin_mint: Pubkey,
+    pub pauser: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RedeemUnpaused {
+    pub stablecoin_mint: Pubkey,
+    pub pauser: Pubkey,
+    pub timestamp: i64,
+}
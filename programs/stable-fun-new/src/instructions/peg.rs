@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use switchboard_solana::AggregatorAccountData;
+
+use crate::state::{PegMonitor, ProtocolConfig, StablecoinMint, StateAccount};
+use crate::constants::{PEG_DEVIATION_ALERT_BPS, PEG_MONITOR_SEED, PROTOCOL_CONFIG_SEED};
+use crate::error::StableFunError;
+use crate::utils::oracle::OracleService;
+
+#[derive(Accounts)]
+pub struct SetMarketFeed<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = PegMonitor::LEN,
+        seeds = [PEG_MONITOR_SEED, stablecoin_mint.key().as_ref()],
+        bump
+    )]
+    pub peg_monitor: Box<Account<'info, PegMonitor>>,
+
+    /// The Switchboard V3 aggregator quoting the stablecoin itself
+    pub market_feed: AccountLoader<'info, AggregatorAccountData>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_market_feed(ctx: Context<SetMarketFeed>) -> Result<()> {
+    let peg_monitor = &mut ctx.accounts.peg_monitor;
+    peg_monitor.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    peg_monitor.market_feed = ctx.accounts.market_feed.key();
+    peg_monitor.bump = ctx.bumps.peg_monitor;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RecordMarketPrice<'info> {
+    pub caller: Signer<'info>,
+
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [PEG_MONITOR_SEED, stablecoin_mint.key().as_ref()],
+        bump = peg_monitor.bump
+    )]
+    pub peg_monitor: Box<Account<'info, PegMonitor>>,
+
+    #[account(
+        constraint = market_feed.key() == peg_monitor.market_feed @ StableFunError::InvalidOracle
+    )]
+    pub market_feed: AccountLoader<'info, AggregatorAccountData>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+}
+
+/// Permissionless crank: records the stablecoin's own market price and
+/// raises an alert once it strays `PEG_DEVIATION_ALERT_BPS` from the peg.
+pub fn record_market_price(ctx: Context<RecordMarketPrice>) -> Result<()> {
+    let market_price = OracleService::verify_oracle_price(&ctx.accounts.market_feed)?;
+    let now = Clock::get()?.unix_timestamp;
+
+    let peg_monitor = &mut ctx.accounts.peg_monitor;
+    peg_monitor.record(market_price, now);
+
+    emit!(MarketPriceRecorded {
+        stablecoin_mint: peg_monitor.stablecoin_mint,
+        market_price,
+        deviation_bps: peg_monitor.deviation_bps,
+        worst_deviation_bps: peg_monitor.worst_deviation_bps,
+        timestamp: now,
+    });
+
+    if peg_monitor.deviation_bps.unsigned_abs() >= PEG_DEVIATION_ALERT_BPS {
+        emit!(PegDeviationAlert {
+            stablecoin_mint: peg_monitor.stablecoin_mint,
+            market_price,
+            deviation_bps: peg_monitor.deviation_bps,
+            timestamp: now,
+        });
+    }
+
+    Ok(())
+}
+
+#[event]
+pub struct MarketPriceRecorded {
+    pub stablecoin_mint: Pubkey,
+    pub market_price: u64,
+    pub deviation_bps: i32,
+    pub worst_deviation_bps: u32,
+    pub timestamp: i64,
+}
+
+/// Alert: the stablecoin trades materially away from its peg.
+#[event]
+pub struct PegDeviationAlert {
+    pub stablecoin_mint: Pubkey,
+    pub market_price: u64,
+    pub deviation_bps: i32,
+    pub timestamp: i64,
+}
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Portfolio, ProtocolConfig, StateAccount};
+use crate::constants::{PORTFOLIO_SEED, PROTOCOL_CONFIG_SEED};
+use crate::error::StableFunError;
+
+#[derive(Accounts)]
+pub struct OpenPortfolio<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = Portfolio::LEN,
+        seeds = [PORTFOLIO_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub portfolio: Box<Account<'info, Portfolio>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Opens the owner's portfolio. Mints and redeems the owner passes it to
+/// are recorded from then on, one holding per stablecoin.
+pub fn open_portfolio(ctx: Context<OpenPortfolio>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let portfolio = &mut ctx.accounts.portfolio;
+    portfolio.owner = ctx.accounts.owner.key();
+    portfolio.created_at = now;
+    portfolio.bump = ctx.bumps.portfolio;
+
+    emit!(PortfolioOpened {
+        owner: portfolio.owner,
+        portfolio: portfolio.key(),
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClosePortfolio<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [PORTFOLIO_SEED, owner.key().as_ref()],
+        bump = portfolio.bump
+    )]
+    pub portfolio: Box<Account<'info, Portfolio>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+}
+
+/// Closes the portfolio, returning its rent. Holdings are a record only, so
+/// nothing else moves.
+pub fn close_portfolio(ctx: Context<ClosePortfolio>) -> Result<()> {
+    emit!(PortfolioClosed {
+        owner: ctx.accounts.owner.key(),
+        portfolio: ctx.accounts.portfolio.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PortfolioOpened {
+    pub owner: Pubkey,
+    pub portfolio: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PortfolioClosed {
+    pub owner: Pubkey,
+    pub portfolio: Pubkey,
+    pub timestamp: i64,
+}
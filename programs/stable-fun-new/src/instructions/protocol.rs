@@ -0,0 +1,298 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{ProtocolConfig, StablecoinMint, StateAccount};
+use crate::constants::PROTOCOL_CONFIG_SEED;
+use crate::error::StableFunError;
+use crate::program::StableFunNew;
+use crate::utils::validation::ValidationService;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct ProtocolConfigParams {
+    pub max_liquidation_penalty_bps: Option<u16>,
+    pub max_liquidator_bonus_bps: Option<u16>,
+    pub max_demurrage_bps: Option<u16>,
+    pub attestation_program: Option<Pubkey>,
+    pub attestation_credential: Option<Pubkey>,
+    pub issuer_schema: Option<Pubkey>,
+    pub holder_schema: Option<Pubkey>,
+    pub require_holder_attestation: Option<bool>,
+    pub max_yield_fee_bps: Option<u16>,
+    pub unverified_supply_cap: Option<u64>,
+    pub reserve_attested_supply_cap: Option<u64>,
+    pub reserve_attestation_max_age: Option<i64>,
+    pub protocol_treasury: Option<Pubkey>,
+    pub launch_fee_lamports: Option<u64>,
+    pub launch_deposit_lamports: Option<u64>,
+    pub approved_lending_programs: Option<Vec<Pubkey>>,
+    pub approved_cpi_callers: Option<Vec<Pubkey>>,
+    pub staking_fee_share_bps: Option<u16>,
+    pub bond_mint: Option<Pubkey>,
+    pub bond_supply_threshold: Option<u64>,
+    pub min_issuer_bond: Option<u64>,
+    pub insurance_fund: Option<Pubkey>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeProtocol<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = ProtocolConfig::LEN,
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ StableFunError::UnauthorizedProtocolAdmin
+    )]
+    pub program: Program<'info, StableFunNew>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ StableFunError::UnauthorizedProtocolAdmin
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_protocol(
+    ctx: Context<InitializeProtocol>,
+    params: ProtocolConfigParams,
+) -> Result<()> {
+    let config = &mut ctx.accounts.protocol_config;
+    config.admin = ctx.accounts.admin.key();
+    config.bump = ctx.bumps.protocol_config;
+    apply_params(config, params)?;
+
+    emit!(ProtocolConfigUpdated {
+        admin: config.admin,
+        config: config.clone().into_inner(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateProtocolConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        has_one = admin @ StableFunError::UnauthorizedProtocolAdmin,
+        realloc = ProtocolConfig::LEN,
+        realloc::payer = admin,
+        realloc::zero = false
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn update_protocol_config(
+    ctx: Context<UpdateProtocolConfig>,
+    params: ProtocolConfigParams,
+) -> Result<()> {
+    let config = &mut ctx.accounts.protocol_config;
+    apply_params(config, params)?;
+
+    emit!(ProtocolConfigUpdated {
+        admin: config.admin,
+        config: config.clone().into_inner(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+fn apply_params(config: &mut ProtocolConfig, params: ProtocolConfigParams) -> Result<()> {
+    if let Some(max_penalty) = params.max_liquidation_penalty_bps {
+        config.max_liquidation_penalty_bps = max_penalty;
+    }
+
+    if let Some(max_bonus) = params.max_liquidator_bonus_bps {
+        config.max_liquidator_bonus_bps = max_bonus;
+    }
+
+    if let Some(max_demurrage) = params.max_demurrage_bps {
+        config.max_demurrage_bps = max_demurrage;
+    }
+
+    if let Some(program) = params.attestation_program {
+        config.attestation_program = program;
+    }
+
+    if let Some(credential) = params.attestation_credential {
+        config.attestation_credential = credential;
+    }
+
+    if let Some(schema) = params.issuer_schema {
+        config.issuer_schema = schema;
+    }
+
+    if let Some(schema) = params.holder_schema {
+        config.holder_schema = schema;
+    }
+
+    if let Some(required) = params.require_holder_attestation {
+        config.require_holder_attestation = required;
+    }
+
+    if let Some(max_yield_fee) = params.max_yield_fee_bps {
+        config.max_yield_fee_bps = max_yield_fee;
+    }
+
+    if let Some(cap) = params.unverified_supply_cap {
+        config.unverified_supply_cap = cap;
+    }
+
+    if let Some(cap) = params.reserve_attested_supply_cap {
+        config.reserve_attested_supply_cap = cap;
+    }
+
+    if let Some(max_age) = params.reserve_attestation_max_age {
+        config.reserve_attestation_max_age = max_age;
+    }
+
+    if let Some(treasury) = params.protocol_treasury {
+        config.protocol_treasury = treasury;
+    }
+
+    if let Some(fee) = params.launch_fee_lamports {
+        config.launch_fee_lamports = fee;
+    }
+
+    if let Some(deposit) = params.launch_deposit_lamports {
+        config.launch_deposit_lamports = deposit;
+    }
+
+    if let Some(programs) = params.approved_lending_programs {
+        config.approved_lending_programs = programs;
+    }
+
+    if let Some(programs) = params.approved_cpi_callers {
+        config.approved_cpi_callers = programs;
+    }
+
+    if let Some(share) = params.staking_fee_share_bps {
+        config.staking_fee_share_bps = share;
+    }
+
+    if let Some(mint) = params.bond_mint {
+        config.bond_mint = mint;
+    }
+
+    if let Some(threshold) = params.bond_supply_threshold {
+        config.bond_supply_threshold = threshold;
+    }
+
+    if let Some(min_bond) = params.min_issuer_bond {
+        config.min_issuer_bond = min_bond;
+    }
+
+    if let Some(fund) = params.insurance_fund {
+        config.insurance_fund = fund;
+    }
+
+    ValidationService::validate_protocol_config(config)
+}
+
+#[derive(Accounts)]
+pub struct VerifyIssuer<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        has_one = admin @ StableFunError::UnauthorizedProtocolAdmin
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(mut)]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+}
+
+/// Grants or revokes a coin's verified badge.
+pub fn verify_issuer(ctx: Context<VerifyIssuer>, verified: bool) -> Result<()> {
+    let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
+    stablecoin_mint.verified = verified;
+
+    emit!(IssuerVerificationChanged {
+        stablecoin_mint: stablecoin_mint.key(),
+        authority: stablecoin_mint.authority,
+        verified,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetProtocolPause<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        has_one = admin @ StableFunError::UnauthorizedProtocolAdmin,
+        realloc = ProtocolConfig::LEN,
+        realloc::payer = admin,
+        realloc::zero = false
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Sets the kill switch and the paused feature mask in one go; every coin
+/// inherits them on top of its own pause flags.
+pub fn set_protocol_pause(
+    ctx: Context<SetProtocolPause>,
+    paused: bool,
+    paused_features: u32,
+) -> Result<()> {
+    let config = &mut ctx.accounts.protocol_config;
+    config.paused = paused;
+    config.paused_features = paused_features;
+
+    emit!(ProtocolPauseChanged {
+        admin: config.admin,
+        paused,
+        paused_features,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ProtocolConfigUpdated {
+    pub admin: Pubkey,
+    pub config: ProtocolConfig,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolPauseChanged {
+    pub admin: Pubkey,
+    pub paused: bool,
+    pub paused_features: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct IssuerVerificationChanged {
+    pub stablecoin_mint: Pubkey,
+    pub authority: Pubkey,
+    pub verified: bool,
+    pub timestamp: i64,
+}
@@ -0,0 +1,172 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use switchboard_solana::AggregatorAccountData;
+
+use crate::state::{
+    InterestRateModel, ProtocolConfig, StablecoinMint, StablecoinVault, StateAccount,
+};
+use crate::constants::{PROTOCOL_CONFIG_SEED, RATE_MODEL_SEED, VAULT_SEED};
+use crate::error::StableFunError;
+use crate::utils::oracle::OracleService;
+use crate::utils::math;
+
+#[derive(Accounts)]
+pub struct SetInterestRateModel<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = InterestRateModel::LEN,
+        seeds = [RATE_MODEL_SEED, stablecoin_mint.key().as_ref()],
+        bump
+    )]
+    pub rate_model: Box<Account<'info, InterestRateModel>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates or reconfigures the stability-fee curve; new parameters apply
+/// from the next accrual.
+pub fn set_interest_rate_model(
+    ctx: Context<SetInterestRateModel>,
+    base_rate_bps: u16,
+    slope1_bps: u16,
+    slope2_bps: u16,
+    optimal_utilization_bps: u16,
+) -> Result<()> {
+    InterestRateModel::validate_params(base_rate_bps, slope1_bps, slope2_bps, optimal_utilization_bps)?;
+
+    let rate_model = &mut ctx.accounts.rate_model;
+    rate_model.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    rate_model.base_rate_bps = base_rate_bps;
+    rate_model.slope1_bps = slope1_bps;
+    rate_model.slope2_bps = slope2_bps;
+    rate_model.optimal_utilization_bps = optimal_utilization_bps;
+    rate_model.bump = ctx.bumps.rate_model;
+
+    emit!(InterestRateModelUpdated {
+        stablecoin_mint: rate_model.stablecoin_mint,
+        base_rate_bps,
+        slope1_bps,
+        slope2_bps,
+        optimal_utilization_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AccrueStabilityFee<'info> {
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        mut,
+        seeds = [RATE_MODEL_SEED, stablecoin_mint.key().as_ref()],
+        bump = rate_model.bump
+    )]
+    pub rate_model: Box<Account<'info, InterestRateModel>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The Switchboard V3 aggregator account
+    #[account(
+        constraint = price_feed.key() == stablecoin_mint.price_feed @ StableFunError::InvalidOracle
+    )]
+    pub price_feed: AccountLoader<'info, AggregatorAccountData>,
+
+    /// FX feed composed with the bond NAV `price_feed`, on coins that set one
+    #[account(
+        constraint = fx_feed.key() == stablecoin_mint.fx_feed @ StableFunError::InvalidOracle
+    )]
+    pub fx_feed: Option<AccountLoader<'info, AggregatorAccountData>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+}
+
+/// Permissionless crank: prices the collateral, reads the fee off the curve
+/// for the current utilization and accrues it on the outstanding supply.
+pub fn accrue_stability_fee(ctx: Context<AccrueStabilityFee>) -> Result<()> {
+    let oracle_price = OracleService::collateral_price(
+        &ctx.accounts.stablecoin_mint,
+        &ctx.accounts.price_feed,
+        ctx.accounts.fx_feed.as_ref(),
+    )?;
+    let collateral_value = math::calculate_collateral_value(
+        ctx.accounts.vault.total_collateral,
+        oracle_price,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    let stablecoin_mint = &ctx.accounts.stablecoin_mint;
+    let utilization_bps = InterestRateModel::utilization_bps(
+        stablecoin_mint.current_supply,
+        collateral_value,
+        stablecoin_mint.settings.min_collateral_ratio,
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let rate_model = &mut ctx.accounts.rate_model;
+    let rate_bps = rate_model.rate_bps(utilization_bps);
+    let fee = rate_model.accrue(stablecoin_mint.current_supply, rate_bps, now)?;
+
+    emit!(StabilityFeeAccrued {
+        stablecoin_mint: stablecoin_mint.key(),
+        utilization_bps,
+        rate_bps,
+        fee,
+        cumulative_index: rate_model.cumulative_index,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct InterestRateModelUpdated {
+    pub stablecoin_mint: Pubkey,
+    pub base_rate_bps: u16,
+    pub slope1_bps: u16,
+    pub slope2_bps: u16,
+    pub optimal_utilization_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StabilityFeeAccrued {
+    pub stablecoin_mint: Pubkey,
+    pub utilization_bps: u16,
+    pub rate_bps: u16,
+    pub fee: u64,
+    pub cumulative_index: u128,
+    pub timestamp: i64,
+}
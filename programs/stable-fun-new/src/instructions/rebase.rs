@@ -0,0 +1,184 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use switchboard_solana::AggregatorAccountData;
+
+use crate::state::{
+    PegMonitor, ProtocolConfig, RebaseMode, StablecoinMint, StablecoinVault, peg_rebase_index,
+    yield_rebase_index, REBASE_INDEX_SCALE,
+};
+use crate::constants::{MIN_REBASE_INTERVAL, PEG_MONITOR_SEED, PROTOCOL_CONFIG_SEED, VAULT_SEED};
+use crate::error::StableFunError;
+use crate::utils::math;
+use crate::utils::oracle::{OracleService, MAX_PRICE_STALENESS};
+
+#[derive(Accounts)]
+pub struct EnableRebase<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Opts the coin into elastic supply. One-way: once balances are scaled,
+/// turning the index off would silently reprice every holder. Demurrage can
+/// only be chosen at initialize.
+pub fn enable_rebase(ctx: Context<EnableRebase>, mode: RebaseMode) -> Result<()> {
+    let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
+    require!(!stablecoin_mint.is_rebasing(), StableFunError::InvalidRebaseMode);
+    require!(
+        matches!(mode, RebaseMode::PegDeviation | RebaseMode::Yield),
+        StableFunError::InvalidRebaseMode
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    stablecoin_mint.rebase_mode = mode;
+    stablecoin_mint.rebase_index = REBASE_INDEX_SCALE;
+    stablecoin_mint.last_rebase = now;
+    stablecoin_mint.last_updated = now;
+
+    emit!(RebaseEnabled {
+        stablecoin_mint: stablecoin_mint.key(),
+        mode,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct Rebase<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(mut)]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The Switchboard V3 aggregator account
+    #[account(
+        constraint = price_feed.key() == stablecoin_mint.price_feed @ StableFunError::InvalidOracle
+    )]
+    pub price_feed: AccountLoader<'info, AggregatorAccountData>,
+
+    /// FX feed composed with the bond NAV `price_feed`, on coins that set one
+    #[account(
+        constraint = fx_feed.key() == stablecoin_mint.fx_feed @ StableFunError::InvalidOracle
+    )]
+    pub fx_feed: Option<AccountLoader<'info, AggregatorAccountData>>,
+
+    /// Required in `PegDeviation` mode
+    #[account(
+        seeds = [PEG_MONITOR_SEED, stablecoin_mint.key().as_ref()],
+        bump = peg_monitor.bump
+    )]
+    pub peg_monitor: Option<Box<Account<'info, PegMonitor>>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+}
+
+/// Permissionless crank: moves the scaling index at most once per
+/// `MIN_REBASE_INTERVAL`. Token balances are untouched; what each token is
+/// worth in mint, redeem and the getter changes instead.
+pub fn rebase(ctx: Context<Rebase>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let stablecoin_mint = &ctx.accounts.stablecoin_mint;
+    require!(stablecoin_mint.is_rebasing(), StableFunError::RebaseNotEnabled);
+    require!(
+        now.saturating_sub(stablecoin_mint.last_rebase) >= MIN_REBASE_INTERVAL,
+        StableFunError::RebaseTooSoon
+    );
+
+    let old_index = stablecoin_mint.rebase_index;
+    let new_index = match stablecoin_mint.rebase_mode {
+        RebaseMode::PegDeviation => {
+            let peg_monitor = ctx.accounts.peg_monitor
+                .as_ref()
+                .ok_or(StableFunError::InvalidOracle)?;
+            require!(
+                now.saturating_sub(peg_monitor.last_updated) <= MAX_PRICE_STALENESS,
+                StableFunError::StaleOraclePrice
+            );
+            peg_rebase_index(old_index, peg_monitor.deviation_bps)
+        }
+        RebaseMode::Yield => {
+            let oracle_price = OracleService::collateral_price(
+                &ctx.accounts.stablecoin_mint,
+                &ctx.accounts.price_feed,
+                ctx.accounts.fx_feed.as_ref(),
+            )?;
+            let collateral_value = math::calculate_collateral_value(
+                ctx.accounts.vault.total_collateral,
+                oracle_price,
+                ctx.accounts.token_mint.decimals,
+            )?;
+            yield_rebase_index(
+                old_index,
+                stablecoin_mint.current_supply,
+                collateral_value,
+                stablecoin_mint.settings.min_collateral_ratio,
+            )
+        }
+        // Accrues continuously through `collect_demurrage` instead
+        RebaseMode::Demurrage => return err!(StableFunError::InvalidRebaseMode),
+        RebaseMode::Disabled => return err!(StableFunError::RebaseNotEnabled),
+    };
+
+    let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
+    stablecoin_mint.rebase_index = new_index;
+    stablecoin_mint.last_rebase = now;
+    stablecoin_mint.last_updated = now;
+
+    emit!(Rebased {
+        stablecoin_mint: stablecoin_mint.key(),
+        old_index,
+        new_index,
+        underlying_supply: stablecoin_mint.to_underlying_amount(stablecoin_mint.current_supply)?,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct RebaseEnabled {
+    pub stablecoin_mint: Pubkey,
+    pub mode: RebaseMode,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct Rebased {
+    pub stablecoin_mint: Pubkey,
+    pub old_index: u128,
+    pub new_index: u128,
+    pub underlying_supply: u64,
+    pub timestamp: i64,
+}
@@ -1,15 +1,35 @@
 use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
 use anchor_spl::token::{self, Token, TokenAccount};
+use anchor_spl::token_interface::{self, TokenInterface};
 use switchboard_solana::AggregatorAccountData;
 
-use crate::state::{StablecoinMint, StablecoinVault};
+use crate::state::{
+    Backstop, CurrencyBasket, FeeDiscountConfig, NttConfig, OperationLog, OperationRecord, OperationType,
+    OrderDedupe, Portfolio, ProtocolConfig, RedeemCommitment, StablecoinMint, StablecoinVault,
+    StateAccount, SystemAccounting, UserPosition, PAUSE_REDEEM,
+};
+use crate::constants::{
+    ACCOUNTING_SEED, BACKSTOP_SEED, BASIS_POINTS_DIVISOR, BASKET_SEED, BOND_TERMS_SEED, DEDUPE_SEED,
+    FEE_DISCOUNT_SEED, NTT_CONFIG_SEED, OPERATION_LOG_SEED, PORTFOLIO_SEED, POSITION_SEED,
+    PROTOCOL_CONFIG_SEED, REDEEM_COMMITMENT_SEED, REDEEM_REVEAL_WINDOW_SLOTS,
+};
 use crate::error::StableFunError;
+use crate::instructions::backstop::BackstopDrawn;
+use crate::instructions::compliance::LargeOperation;
+use crate::instructions::fee_discount::discounted_fee_bps;
+use crate::instructions::mint::FeeReceipt;
+use crate::instructions::rejection;
 use crate::utils::oracle::OracleService;
 use crate::utils::validation::ValidationService;
+use crate::utils::attestation;
+use crate::utils::introspection;
 use crate::utils::math;
+use crate::utils::stablebond::StablebondService;
 
 #[derive(Accounts)]
-#[instruction(amount: u64)]
+#[instruction(amount: u64, client_order_id: Option<[u8; 32]>)]
 pub struct RedeemStablecoin<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
@@ -20,7 +40,7 @@ pub struct RedeemStablecoin<'info> {
     #[account(
         mut,
         seeds = [b"vault", stablecoin_mint.key().as_ref()],
-        bump,
+        bump = vault.bump,
         constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
     )]
     pub vault: Account<'info, StablecoinVault>,
@@ -29,14 +49,14 @@ pub struct RedeemStablecoin<'info> {
         mut,
         constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
     )]
-    pub token_mint: Box<Account<'info, token::Mint>>,
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
 
     #[account(
         mut,
         constraint = user_token_account.mint == token_mint.key() @ StableFunError::InvalidTokenAccount,
         constraint = user_token_account.owner == user.key() @ StableFunError::InvalidTokenAccount
     )]
-    pub user_token_account: Box<Account<'info, TokenAccount>>,
+    pub user_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
 
     #[account(
         mut,
@@ -57,146 +77,689 @@ pub struct RedeemStablecoin<'info> {
     )]
     pub price_feed: AccountLoader<'info, AggregatorAccountData>,
 
-    /// CHECK: PDA used as burn authority
+    /// FX feed composed with the bond NAV `price_feed`, on coins that set one
     #[account(
-        seeds = [b"mint-authority", stablecoin_mint.key().as_ref()],
+        constraint = fx_feed.key() == stablecoin_mint.fx_feed @ StableFunError::InvalidOracle
+    )]
+    pub fx_feed: Option<AccountLoader<'info, AggregatorAccountData>>,
+
+    /// CHECK: The coin's `BondTerms` PDA, used to price out the yield the
+    /// collateral has accrued; left uninitialized when no terms are set
+    #[account(seeds = [BOND_TERMS_SEED, stablecoin_mint.key().as_ref()], bump)]
+    pub bond_terms: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserPosition::LEN,
+        seeds = [POSITION_SEED, stablecoin_mint.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub position: Box<Account<'info, UserPosition>>,
+
+    /// Only required by `reveal_redeem`
+    #[account(
+        mut,
+        seeds = [REDEEM_COMMITMENT_SEED, stablecoin_mint.key().as_ref(), user.key().as_ref()],
+        bump = commitment.bump
+    )]
+    pub commitment: Option<Box<Account<'info, RedeemCommitment>>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = SystemAccounting::LEN,
+        seeds = [ACCOUNTING_SEED, stablecoin_mint.key().as_ref()],
+        bump
+    )]
+    pub accounting: Box<Account<'info, SystemAccounting>>,
+
+    /// Only needed once the coin is registered with NTT
+    #[account(
+        seeds = [NTT_CONFIG_SEED, stablecoin_mint.key().as_ref()],
+        bump = ntt_config.bump
+    )]
+    pub ntt_config: Option<Box<Account<'info, NttConfig>>>,
+
+    /// Appended to when the stablecoin has opted into operation logging
+    #[account(
+        mut,
+        seeds = [OPERATION_LOG_SEED, stablecoin_mint.key().as_ref()],
+        bump = operation_log.load()?.bump
+    )]
+    pub operation_log: Option<AccountLoader<'info, OperationLog>>,
+
+    /// Required for basket-pegged coins
+    #[account(
+        seeds = [BASKET_SEED, stablecoin_mint.key().as_ref()],
+        bump = basket.bump
+    )]
+    pub basket: Option<Box<Account<'info, CurrencyBasket>>>,
+
+    /// Gates regulated coins and carries the protocol-wide pause
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_REDEEM) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    /// CHECK: Registry attestation of the user, verified in the handler;
+    /// only needed when the protocol gates regulated coins on holders
+    pub holder_attestation: Option<UncheckedAccount<'info>>,
+
+    /// Idempotency guard for integrators that retry; requires a client order id
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = OrderDedupe::LEN,
+        seeds = [
+            DEDUPE_SEED,
+            stablecoin_mint.key().as_ref(),
+            user.key().as_ref(),
+            &client_order_id.unwrap_or_default(),
+        ],
         bump
     )]
-    pub burn_authority: UncheckedAccount<'info>,
+    pub order_dedupe: Option<Box<Account<'info, OrderDedupe>>>,
+
+    /// Fee discount schedule; only applied with the user's discount token account
+    #[account(
+        seeds = [FEE_DISCOUNT_SEED, stablecoin_mint.key().as_ref()],
+        bump = fee_discount.bump
+    )]
+    pub fee_discount: Option<Box<Account<'info, FeeDiscountConfig>>>,
+
+    #[account(
+        constraint = discount_token_account.owner == user.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub discount_token_account: Option<Box<InterfaceAccount<'info, token_interface::TokenAccount>>>,
+
+    /// Updated when the user keeps a treasury portfolio
+    #[account(
+        mut,
+        seeds = [PORTFOLIO_SEED, user.key().as_ref()],
+        bump = portfolio.bump
+    )]
+    pub portfolio: Option<Box<Account<'info, Portfolio>>>,
+
+    /// Pays instant redeems the liquidity buffer cannot
+    #[account(
+        mut,
+        seeds = [BACKSTOP_SEED, stablecoin_mint.key().as_ref()],
+        bump = backstop.bump
+    )]
+    pub backstop: Option<Box<Account<'info, Backstop>>>,
+
+    /// The backstop's collateral account, checked against it in the handler
+    #[account(mut)]
+    pub backstop_collateral_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: Instructions sysvar, checked by address
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
 
     pub token_program: Program<'info, Token>,
+    /// Token program owning the stablecoin mint (SPL Token or Token-2022)
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
+impl<'info> RedeemStablecoin<'info> {
+    /// Whether `init_if_needed` created any account for this redeem. A
+    /// redeem that lands without redeeming must not leave those behind.
+    fn creates_accounts(&self) -> bool {
+        !self.position.is_initialized()
+            || !self.accounting.is_initialized()
+            || self.order_dedupe.as_ref().is_some_and(|order_dedupe| !order_dedupe.is_filled())
+    }
+}
+
 #[inline(never)]
-pub fn handler(ctx: Context<RedeemStablecoin>, amount: u64) -> Result<()> {
-    // Initial validations
-    require!(!ctx.accounts.stablecoin_mint.settings.redeem_paused, StableFunError::RedeemingPaused);
-    require!(amount > 0, StableFunError::InvalidAmount);
+pub fn handler(
+    ctx: Context<RedeemStablecoin>,
+    amount: u64,
+    client_order_id: Option<[u8; 32]>,
+    dry_run: bool,
+) -> Result<FeeReceipt> {
     require!(
-        amount <= ctx.accounts.user_token_account.amount,
-        StableFunError::InsufficientBalance
+        !ctx.accounts.stablecoin_mint.requires_redeem_commitment(amount),
+        StableFunError::RedeemCommitmentRequired
     );
+    process_redeem(ctx, amount, client_order_id, true, dry_run)
+}
 
-    // Validate amount is within bounds
-    ValidationService::validate_amount(amount)?;
-
-    // Get oracle price
-    let oracle_price = OracleService::verify_oracle_price(&ctx.accounts.price_feed)?;
+/// Executes the committed redemption at the front of the queue, priced at
+/// reveal time.
+#[inline(never)]
+pub fn reveal(
+    ctx: Context<RedeemStablecoin>,
+    amount: u64,
+    client_order_id: Option<[u8; 32]>,
+    salt: [u8; 32],
+) -> Result<FeeReceipt> {
+    let commitment = ctx.accounts.commitment
+        .as_ref()
+        .ok_or(error!(StableFunError::InvalidRedeemCommitment))?;
 
-    // Calculate collateral amount
-    let collateral_amount = math::calculate_token_amount(
+    commitment.verify_reveal(
         amount,
-        oracle_price,
-        ctx.accounts.token_mint.decimals,
+        &salt,
+        Clock::get()?.slot,
+        ctx.accounts.stablecoin_mint.settings.reveal_delay_slots,
+        REDEEM_REVEAL_WINDOW_SLOTS,
+        ctx.accounts.vault.redeem_queue_head_since,
     )?;
+    ctx.accounts.vault.dequeue_redeem(commitment.ticket, Clock::get()?.slot)?;
+    commitment.close(ctx.accounts.user.to_account_info())?;
 
-    // Calculate fee
-    let fee_amount = amount
-        .checked_mul(ctx.accounts.stablecoin_mint.settings.fee_basis_points as u64)
-        .and_then(|v| v.checked_div(10000))
-        .ok_or(error!(StableFunError::MathOverflow))?;
+    process_redeem(ctx, amount, client_order_id, false, false)
+}
 
-    let burn_amount = amount
-        .checked_add(fee_amount)
-        .ok_or(error!(StableFunError::MathOverflow))?;
+/// The pause check and the price, which are the rejections `soft_fail`
+/// reports; nothing is written here.
+#[inline(never)]
+fn redeem_price(ctx: &Context<RedeemStablecoin>, amount: u64) -> Result<u64> {
+    require!(!ctx.accounts.stablecoin_mint.settings.redeem_paused, StableFunError::RedeemingPaused);
 
-    // Calculate remaining amounts
-    let remaining_collateral = ctx.accounts.vault
-        .total_collateral
-        .checked_sub(collateral_amount)
-        .ok_or(error!(StableFunError::MathOverflow))?;
+    // Small redeems may reuse a fresh cached price instead of reading the feed
+    let settings = &ctx.accounts.stablecoin_mint.settings;
+    let cached_price = ctx.accounts.vault.usable_cached_price(
+        amount,
+        settings.cached_price_max_amount,
+        settings.cached_price_max_age_slots,
+        Clock::get()?.slot,
+    );
+    let oracle_price = match cached_price {
+        Some(price) => price,
+        None => OracleService::collateral_price(
+            &ctx.accounts.stablecoin_mint,
+            &ctx.accounts.price_feed,
+            ctx.accounts.fx_feed.as_ref(),
+        )?,
+    };
+    let oracle_price = OracleService::basket_adjusted_price(
+        &ctx.accounts.stablecoin_mint,
+        ctx.accounts.basket.as_deref().map(|basket| &**basket),
+        oracle_price,
+        Clock::get()?.unix_timestamp,
+    )?;
 
-    let remaining_supply = ctx.accounts.stablecoin_mint
-        .current_supply
-        .checked_sub(burn_amount)
-        .ok_or(error!(StableFunError::MathOverflow))?;
+    Ok(oracle_price)
+}
 
-    // Validate collateral ratio if there's remaining supply
-    if remaining_supply > 0 {
-        ValidationService::validate_collateral_ratio(
-            remaining_collateral,
-            remaining_supply,
-            ctx.accounts.stablecoin_mint.settings.min_collateral_ratio,
-        )?;
+/// `amount`, grown to repay the caller's whole position when redeeming just
+/// `amount` would leave its debt below the coin's minimum, and whether it
+/// closes the position. A close never burns more than the `balance` raw
+/// tokens the caller holds, even when rounding left the tracked debt above it.
+fn dust_free_amount(
+    stablecoin_mint: &StablecoinMint,
+    position: &UserPosition,
+    amount: u64,
+    balance: u64,
+) -> Result<(u64, bool)> {
+    if !position.leaves_dust(
+        stablecoin_mint.to_raw_amount(amount, true)?,
+        stablecoin_mint.min_position_debt(),
+    ) {
+        return Ok((amount, false));
     }
+    let amount = stablecoin_mint.to_underlying_amount(position.debt_amount.min(balance))?;
+    Ok((amount, true))
+}
 
-    // Burn stablecoins
-    token::burn(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            token::Burn {
-                mint: ctx.accounts.token_mint.to_account_info(),
-                from: ctx.accounts.user_token_account.to_account_info(),
-                authority: ctx.accounts.burn_authority.to_account_info(),
-            },
-            &[&[
-                b"mint-authority",
-                ctx.accounts.stablecoin_mint.key().as_ref(),
-                &[ctx.bumps.burn_authority],
-            ]],
-        ),
-        burn_amount,
-    )?;
+/// Collateral released by a redeem, split between the redeemer and the fee.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RedeemPayout {
+    /// Collateral transferred out of the vault to the redeemer
+    pub net_collateral: u64,
+    /// Collateral kept in the vault as the fee, booked as surplus
+    pub fee_collateral: u64,
+}
 
-    // Transfer collateral back to user
-    token::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            token::Transfer {
-                from: ctx.accounts.vault_stablebond_account.to_account_info(),
-                to: ctx.accounts.user_stablebond_account.to_account_info(),
-                authority: ctx.accounts.vault.to_account_info(),
-            },
-            &[&[
-                b"vault",
-                ctx.accounts.stablecoin_mint.key().as_ref(),
-                &[ctx.bumps.vault],
-            ]],
-        ),
-        collateral_amount,
+/// Redeems burn exactly the coins redeemed and take the fee out of the
+/// collateral they release. The fee is paid by the user, so it rounds up.
+pub fn split_redeem_collateral(gross_collateral: u64, fee_bps: u16) -> Result<RedeemPayout> {
+    let fee_collateral = math::mul_div(
+        gross_collateral,
+        fee_bps as u64,
+        BASIS_POINTS_DIVISOR as u64,
+        math::Flow::FromUser,
     )?;
+    Ok(RedeemPayout {
+        net_collateral: gross_collateral - fee_collateral,
+        fee_collateral,
+    })
+}
 
-    // Update vault state
-    ctx.accounts.vault.total_collateral = remaining_collateral;
-    ctx.accounts.vault.total_value_locked = ctx.accounts.vault
+/// A redeem of `amount` underlying units, priced and checked against the
+/// coin's books before any tokens move.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RedeemPlan {
+    pub amount: u64,
+    /// Raw tokens retired from supply for `amount`
+    pub raw_burn_amount: u64,
+    /// Collateral backing `amount`, before the fee
+    pub gross_collateral: u64,
+    pub fee_amount: u64,
+    pub payout: RedeemPayout,
+    pub remaining_collateral: u64,
+    pub remaining_value_locked: u64,
+    pub remaining_supply: u64,
+}
+
+/// Splits the `gross_collateral` backing a redeem of `amount` and checks
+/// the vault stays collateralized at `oracle_price` for the debt left.
+/// Every redeem path, local or remote, goes through here.
+pub fn plan_redeem(
+    stablecoin_mint: &StablecoinMint,
+    vault: &StablecoinVault,
+    amount: u64,
+    gross_collateral: u64,
+    oracle_price: u64,
+    decimals: u8,
+    fee_bps: u16,
+) -> Result<RedeemPlan> {
+    let fee_amount = math::bps_of(amount, fee_bps, math::Flow::FromUser)?;
+    let payout = split_redeem_collateral(gross_collateral, fee_bps)?;
+    let raw_burn_amount = stablecoin_mint.to_raw_amount(amount, true)?;
+
+    let remaining_collateral = vault
+        .total_collateral
+        .checked_sub(payout.net_collateral)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    let remaining_value_locked = vault
         .total_value_locked
         .checked_sub(amount)
         .ok_or(error!(StableFunError::MathOverflow))?;
-    
-    ctx.accounts.vault.withdrawal_count = ctx.accounts.vault
+    let remaining_supply = stablecoin_mint
+        .current_supply
+        .checked_sub(raw_burn_amount)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+
+    // The vault must stay collateralized for the debt left behind
+    ValidationService::validate_collateral_ratio(
+        math::calculate_collateral_value(remaining_collateral, oracle_price, decimals)?,
+        remaining_value_locked,
+        stablecoin_mint.settings.min_collateral_ratio,
+    )?;
+
+    Ok(RedeemPlan {
+        amount,
+        raw_burn_amount,
+        gross_collateral,
+        fee_amount,
+        payout,
+        remaining_collateral,
+        remaining_value_locked,
+        remaining_supply,
+    })
+}
+
+/// Books a redeem carried out as `plan`: it takes an operation from the
+/// throughput bucket, the net collateral and the debt leave the vault, the
+/// burned tokens leave supply, and the fee, which stayed in the vault, is
+/// booked as surplus.
+pub fn book_redeem(
+    stablecoin_mint: &mut StablecoinMint,
+    vault: &mut StablecoinVault,
+    accounting: &mut SystemAccounting,
+    plan: &RedeemPlan,
+    oracle_price: u64,
+    decimals: u8,
+    now: i64,
+) -> Result<()> {
+    stablecoin_mint.consume_throughput(now)?;
+
+    vault.total_collateral = plan.remaining_collateral;
+    vault.total_value_locked = plan.remaining_value_locked;
+    vault.withdrawal_count = vault
         .withdrawal_count
         .checked_add(1)
         .ok_or(error!(StableFunError::MathOverflow))?;
-    
-    ctx.accounts.vault.last_withdrawal_time = Clock::get()?.unix_timestamp;
+    vault.last_withdrawal_time = now;
+    vault.update_collateral_ratio(oracle_price, decimals)?;
 
-    // Update stablecoin state
-    ctx.accounts.stablecoin_mint.current_supply = remaining_supply;
-    ctx.accounts.stablecoin_mint.stats.total_burned = ctx.accounts.stablecoin_mint
+    stablecoin_mint.current_supply = plan.remaining_supply;
+    stablecoin_mint.stats.total_burned = stablecoin_mint
         .stats
         .total_burned
-        .checked_add(amount)
+        .checked_add(plan.amount)
         .ok_or(error!(StableFunError::MathOverflow))?;
-    
-    ctx.accounts.stablecoin_mint.stats.total_fees = ctx.accounts.stablecoin_mint
+    stablecoin_mint.stats.total_fees = stablecoin_mint
         .stats
         .total_fees
-        .checked_add(fee_amount)
+        .checked_add(plan.fee_amount)
         .ok_or(error!(StableFunError::MathOverflow))?;
-    
-    ctx.accounts.stablecoin_mint.last_updated = Clock::get()?.unix_timestamp;
+    stablecoin_mint.last_updated = now;
+
+    accounting.record_fee(plan.fee_amount)
+}
+
+/// `instant` redeems may soft-fail; revealed ones have already left the
+/// queue and closed their commitment. Both are held to the liquidity buffer.
+/// A `dry_run` runs every check, then puts the coin's books back and
+/// returns the receipt before the burn. It fails rather than create the
+/// position, accounting or dedupe accounts.
+#[inline(never)]
+fn process_redeem(
+    ctx: Context<RedeemStablecoin>,
+    amount: u64,
+    client_order_id: Option<[u8; 32]>,
+    instant: bool,
+    dry_run: bool,
+) -> Result<FeeReceipt> {
+    let prices = redeem_price(&ctx, amount);
+    let oracle_price = if instant {
+        match rejection::soft_fail(
+            prices,
+            ctx.accounts.stablecoin_mint.key(),
+            &ctx.accounts.stablecoin_mint,
+            ctx.accounts.user.key(),
+            OperationType::Redeem,
+            amount,
+            client_order_id,
+            ctx.accounts.creates_accounts(),
+        )? {
+            Some(price) => price,
+            None => return Ok(FeeReceipt::default()),
+        }
+    } else {
+        prices?
+    };
 
+    require!(
+        !dry_run || !ctx.accounts.creates_accounts(),
+        StableFunError::DryRunCreatesAccounts
+    );
+
+    // Price the redeem against the index as of now
+    let books_before = dry_run.then(|| ctx.accounts.stablecoin_mint.clone());
+    ctx.accounts.stablecoin_mint.accrue_demurrage(Clock::get()?.unix_timestamp)?;
+
+    // A retried order id fails here instead of redeeming twice
+    if let Some(order_dedupe) = ctx.accounts.order_dedupe.as_mut() {
+        if dry_run {
+            order_dedupe.check_fill(client_order_id)?;
+        } else {
+            order_dedupe.record_fill(
+                ctx.accounts.stablecoin_mint.key(),
+                ctx.accounts.user.key(),
+                client_order_id,
+                Clock::get()?.slot,
+                ctx.bumps.order_dedupe.unwrap_or_default(),
+            )?;
+        }
+    }
+
+    // A redeem that would leave the position as dust repays all of it instead
+    let (amount, closes_position) = dust_free_amount(
+        &ctx.accounts.stablecoin_mint,
+        &ctx.accounts.position,
+        amount,
+        ctx.accounts.user_token_account.amount,
+    )?;
+
+    // Initial validations
+    require!(amount > 0, StableFunError::InvalidAmount);
+    // Rebasing coins redeem underlying units; the balance holds tokens
+    require!(
+        ctx.accounts.stablecoin_mint.to_raw_amount(amount, true)? <= ctx.accounts.user_token_account.amount,
+        StableFunError::InsufficientBalance
+    );
+
+    // Validate amount is within bounds
+    ValidationService::validate_amount(amount)?;
+
+    // Refuse to build on books that disagree with the token program
+    let bridged_supply = ctx.accounts.ntt_config
+        .as_ref()
+        .map_or(0, |config| config.bridged_supply);
+    ctx.accounts.stablecoin_mint.check_supply_drift(ctx.accounts.token_mint.supply, bridged_supply)?;
+
+    attestation::check_holder_attestation(
+        &ctx.accounts.stablecoin_mint,
+        &ctx.accounts.protocol_config,
+        ctx.accounts.holder_attestation.as_ref().map(|account| account.as_ref()),
+        &ctx.accounts.user.key(),
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    // Enforce the cooldown between a user's mint and their redeem
+    require!(
+        ctx.accounts.position.cooldown_elapsed(
+            Clock::get()?.unix_timestamp,
+            ctx.accounts.stablecoin_mint.settings.withdrawal_delay,
+        ),
+        StableFunError::WithdrawalCooldownActive
+    );
+
+    // Optionally refuse to run inside another program's instruction, e.g. a flash loan
+    require!(
+        !ctx.accounts.stablecoin_mint.settings.top_level_only || introspection::is_top_level(),
+        StableFunError::CpiNotAllowed
+    );
+    let cpi_caller = introspection::cpi_caller(&ctx.accounts.instructions)?;
+    require!(
+        ctx.accounts.protocol_config.allows_cpi_caller(cpi_caller.as_ref()),
+        StableFunError::UnapprovedCpiCaller
+    );
+
+    // Optionally reject a redeem that follows a mint of the same coin in this transaction
+    if ctx.accounts.stablecoin_mint.settings.same_tx_guard {
+        let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+        for discriminator in [
+            crate::instruction::Mint::DISCRIMINATOR,
+            crate::instruction::RelayedMint::DISCRIMINATOR,
+        ] {
+            require!(
+                !introspection::has_prior_instruction(
+                    &ctx.accounts.instructions,
+                    &discriminator,
+                    &stablecoin_key,
+                )?,
+                StableFunError::SameTransactionMintRedeem
+            );
+        }
+    }
+
+    // Calculate collateral amount, net of the yield the bonds have accrued
+    let gross_collateral = StablebondService::yield_adjusted_collateral(
+        math::calculate_token_amount(amount, oracle_price, ctx.accounts.token_mint.decimals)?,
+        StablebondService::get_bond_terms_data(
+            &ctx.accounts.bond_terms,
+            &ctx.accounts.stablecoin_mint.stablebond_mint,
+        )?.as_ref(),
+        Clock::get()?.unix_timestamp,
+        math::Flow::ToUser,
+    )?;
+
+    // The fee stays in the vault; only the rest of the collateral leaves
+    let fee_bps = discounted_fee_bps(
+        ctx.accounts.stablecoin_mint.settings.fee_basis_points,
+        ctx.accounts.fee_discount.as_deref().map(|fee_discount| &**fee_discount),
+        ctx.accounts.discount_token_account.as_deref().map(|token_account| &**token_account),
+    )?;
+    let plan = plan_redeem(
+        &ctx.accounts.stablecoin_mint,
+        &ctx.accounts.vault,
+        amount,
+        gross_collateral,
+        oracle_price,
+        ctx.accounts.token_mint.decimals,
+        fee_bps,
+    )?;
+    let RedeemPlan { fee_amount, raw_burn_amount, .. } = plan;
+    let collateral_amount = plan.payout.net_collateral;
+
+    // Redeems that would drain the vault below its buffer are paid by the
+    // coin's backstop; without one they wait for liquidity to come back
+    let from_backstop = ctx.accounts.vault.breaches_liquidity_buffer(
+        ctx.accounts.vault_stablebond_account.amount,
+        collateral_amount,
+        ctx.accounts.stablecoin_mint.settings.liquidity_buffer_bps,
+    );
+    if from_backstop {
+        let backstop = ctx.accounts.backstop
+            .as_ref()
+            .ok_or(error!(StableFunError::LiquidityBufferBreached))?;
+        let collateral_account = ctx.accounts.backstop_collateral_account
+            .as_ref()
+            .ok_or(error!(StableFunError::InvalidTokenAccount))?;
+        require_keys_eq!(
+            collateral_account.key(),
+            backstop.collateral_account,
+            StableFunError::InvalidTokenAccount
+        );
+        require!(collateral_amount <= backstop.liquid(), StableFunError::InsufficientBackstop);
+    }
+
+
+    let receipt = FeeReceipt {
+        gross_amount: amount,
+        fee_amount,
+        protocol_share: fee_amount,
+        referral_share: 0,
+        collateral_amount,
+        price: oracle_price,
+    };
+    if let Some(books_before) = books_before {
+        ctx.accounts.stablecoin_mint.set_inner(books_before.into_inner());
+        return Ok(receipt);
+    }
+
+    // Burn stablecoins; the user owns the account and signs for the burn
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.stablecoin_token_program.to_account_info(),
+            token_interface::Burn {
+                mint: ctx.accounts.token_mint.to_account_info(),
+                from: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        raw_burn_amount,
+    )?;
+
+    // Transfer collateral back to user; a backstop draw is owed back by the vault
+    match ctx.accounts.backstop.as_mut().filter(|_| from_backstop) {
+        Some(backstop) => {
+            let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+            let bump = backstop.bump;
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::Transfer {
+                        from: ctx.accounts.backstop_collateral_account
+                            .as_ref()
+                            .ok_or(error!(StableFunError::InvalidTokenAccount))?
+                            .to_account_info(),
+                        to: ctx.accounts.user_stablebond_account.to_account_info(),
+                        authority: backstop.to_account_info(),
+                    },
+                    &[&[BACKSTOP_SEED, stablecoin_key.as_ref(), &[bump]]],
+                ),
+                collateral_amount,
+            )?;
+            backstop.draw(collateral_amount)?;
+            ctx.accounts.vault.backstop_owed = ctx.accounts.vault
+                .backstop_owed
+                .checked_add(collateral_amount)
+                .ok_or(error!(StableFunError::MathOverflow))?;
+
+            emit!(BackstopDrawn {
+                stablecoin_mint: stablecoin_key,
+                user: ctx.accounts.user.key(),
+                collateral_amount,
+                drawn: backstop.drawn,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
+        None => {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::Transfer {
+                        from: ctx.accounts.vault_stablebond_account.to_account_info(),
+                        to: ctx.accounts.user_stablebond_account.to_account_info(),
+                        authority: ctx.accounts.vault.to_account_info(),
+                    },
+                    &[&ctx.accounts.vault.signer_seeds()],
+                ),
+                collateral_amount,
+            )?;
+        }
+    }
+
+    // Update vault and stablecoin state; fees are realized surplus
+    if !ctx.accounts.accounting.is_initialized() {
+        let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+        ctx.accounts.accounting.initialize(stablecoin_key, ctx.bumps.accounting);
+    }
+    book_redeem(
+        &mut ctx.accounts.stablecoin_mint,
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.accounting,
+        &plan,
+        oracle_price,
+        ctx.accounts.token_mint.decimals,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    // Update user position
+    if !ctx.accounts.position.is_initialized() {
+        let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+        let user_key = ctx.accounts.user.key();
+        ctx.accounts.position.initialize(stablecoin_key, user_key, ctx.bumps.position);
+    }
+    ctx.accounts.position.record_redeem(
+        gross_collateral,
+        raw_burn_amount,
+        Clock::get()?.unix_timestamp,
+    )?;
+    if closes_position {
+        // Whatever rounding left behind is below the minimum; drop it
+        ctx.accounts.position.debt_amount = 0;
+    }
+
+    if let Some(operation_log) = &ctx.accounts.operation_log {
+        operation_log.load_mut()?.append(OperationRecord::new(
+            OperationType::Redeem,
+            ctx.accounts.user.key(),
+            amount,
+            oracle_price,
+            fee_amount,
+            Clock::get()?.slot,
+        ).with_client_order_id(client_order_id));
+    }
+
+    if let Some(portfolio) = &mut ctx.accounts.portfolio {
+        portfolio.record_redeem(ctx.accounts.stablecoin_mint.key(), amount, fee_amount)?;
+    }
+
+    if ctx.accounts.stablecoin_mint.exceeds_reporting_threshold(amount) {
+        emit!(LargeOperation {
+            stablecoin_mint: ctx.accounts.stablecoin_mint.key(),
+            operation: OperationType::Redeem as u8,
+            user: ctx.accounts.user.key(),
+            token_account: ctx.accounts.user_token_account.key(),
+            collateral_account: ctx.accounts.user_stablebond_account.key(),
+            amount,
+            collateral_amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
     emit!(RedeemEvent {
         stablecoin_mint: ctx.accounts.stablecoin_mint.key(),
         user: ctx.accounts.user.key(),
         amount,
         fee_amount,
         collateral_amount,
+        client_order_id,
         timestamp: Clock::get()?.unix_timestamp,
     });
 
-    Ok(())
+    Ok(receipt)
 }
 
 #[event]
@@ -206,12 +769,18 @@ pub struct RedeemEvent {
     pub amount: u64,
     pub fee_amount: u64,
     pub collateral_amount: u64,
+    /// Reference the caller passed in, echoed for reconciliation
+    pub client_order_id: Option<[u8; 32]>,
     pub timestamp: i64,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::constants::{DEFAULT_COLLATERAL_RATIO, MIN_COLLATERAL_RATIO};
+    use crate::instructions::mint::quote_mint;
+    use crate::state::{RebaseMode, REBASE_INDEX_SCALE};
+    use crate::utils::stablebond::{StablebondData, MAX_BOND_DURATION};
 
     #[test]
     fn test_fee_calculation() {
@@ -226,21 +795,202 @@ mod tests {
         assert_eq!(fee, 3_000);
     }
 
+    #[test]
+    fn test_redeem_payout_conserves_collateral() {
+        for (gross, fee_bps) in [(1_000_000, 30), (1_001, 30), (999, 10000), (12_345, 0), (0, 30)] {
+            let payout = split_redeem_collateral(gross, fee_bps).unwrap();
+            assert_eq!(payout.net_collateral + payout.fee_collateral, gross);
+            // The fee never rounds in the redeemer's favour
+            assert!(payout.fee_collateral as u128 * 10000 >= gross as u128 * fee_bps as u128);
+        }
+        assert_eq!(
+            split_redeem_collateral(1_000_000, 30).unwrap(),
+            RedeemPayout { net_collateral: 997_000, fee_collateral: 3_000 }
+        );
+    }
+
+    #[test]
+    fn test_redeem_books_balance() {
+        // 1500 collateral backing 1000 coins; redeem 100 at 1.0 with a 0.3% fee
+        let (total_collateral, total_value_locked) = (1_500_000u64, 1_000_000u64);
+        let amount = 100_000;
+        let gross = math::calculate_token_amount(amount, 1_000_000, 6).unwrap();
+        let payout = split_redeem_collateral(gross, 30).unwrap();
+
+        // Only the net payout leaves the vault; the fee is still in it
+        let remaining_collateral = total_collateral - payout.net_collateral;
+        assert_eq!(remaining_collateral, total_collateral - gross + payout.fee_collateral);
+        assert_eq!(remaining_collateral, 1_400_300);
+
+        // The burn matches the debt released, so the fee accrues to the
+        // remaining holders as extra backing
+        let remaining_value_locked = total_value_locked - amount;
+        let before = math::collateral_ratio_bps(total_collateral, total_value_locked).unwrap();
+        let after = math::collateral_ratio_bps(remaining_collateral, remaining_value_locked).unwrap();
+        assert!(after > before);
+    }
+
+    #[test]
+    fn test_plan_and_book_redeem() {
+        let mut stablecoin_mint = StablecoinMint {
+            current_supply: 1_000_000,
+            ..Default::default()
+        };
+        stablecoin_mint.settings.min_collateral_ratio = 15000;
+        let mut vault = StablecoinVault::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            255,
+        );
+        vault.total_collateral = 2_000_000;
+        vault.total_value_locked = 1_000_000;
+        let mut accounting = SystemAccounting::default();
+
+        let gross = math::calculate_token_amount(100_000, 1_000_000, 6).unwrap();
+        let plan = plan_redeem(&stablecoin_mint, &vault, 100_000, gross, 1_000_000, 6, 30).unwrap();
+        book_redeem(&mut stablecoin_mint, &mut vault, &mut accounting, &plan, 1_000_000, 6, 50).unwrap();
+
+        // Supply, debt and the burn counter all move by the amount redeemed
+        assert_eq!(stablecoin_mint.current_supply, 900_000);
+        assert_eq!(vault.total_value_locked, 900_000);
+        assert_eq!(stablecoin_mint.stats.total_burned, 100_000);
+        // Only the net collateral leaves; the fee is surplus
+        assert_eq!(vault.total_collateral, 2_000_000 - plan.payout.net_collateral);
+        assert_eq!(plan.payout.fee_collateral, 300);
+        assert_eq!(accounting.surplus, plan.fee_amount);
+        assert_eq!(stablecoin_mint.stats.total_fees, 300);
+
+        // At twice the price the collateral left no longer covers the debt
+        let gross = math::calculate_token_amount(100_000, 2_000_000, 6).unwrap();
+        assert!(plan_redeem(&stablecoin_mint, &vault, 100_000, gross, 2_000_000, 6, 30).is_err());
+    }
+
+    #[test]
+    fn test_partial_redeem_after_mint() {
+        let mut stablecoin_mint = StablecoinMint::default();
+        stablecoin_mint.settings.min_collateral_ratio = DEFAULT_COLLATERAL_RATIO;
+        stablecoin_mint.settings.fee_basis_points = 30;
+        let mut vault = StablecoinVault::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            255,
+        );
+
+        // Book a mint the way the handler does
+        let quote = quote_mint(
+            1_000_000,
+            1_000_000,
+            6,
+            stablecoin_mint.settings.fee_basis_points,
+            stablecoin_mint.settings.min_collateral_ratio,
+        ).unwrap();
+        vault.total_collateral = quote.collateral_amount;
+        vault.total_value_locked = 1_000_000;
+        stablecoin_mint.current_supply = quote.total_amount;
+
+        // Redeeming part of it leaves the vault above the minimum ratio
+        for amount in [1_000, 400_000, 900_000] {
+            let gross = math::calculate_token_amount(amount, 1_000_000, 6).unwrap();
+            let plan = plan_redeem(
+                &stablecoin_mint,
+                &vault,
+                amount,
+                gross,
+                1_000_000,
+                6,
+                stablecoin_mint.settings.fee_basis_points,
+            ).unwrap();
+            let remaining_value = math::calculate_collateral_value(plan.remaining_collateral, 1_000_000, 6).unwrap();
+            assert!(
+                math::collateral_ratio_bps(remaining_value, plan.remaining_value_locked).unwrap()
+                    >= DEFAULT_COLLATERAL_RATIO as u64
+            );
+        }
+    }
+
+    #[test]
+    fn test_full_close_clamped_to_balance() {
+        let mut stablecoin_mint = StablecoinMint::default();
+        stablecoin_mint.settings.min_position_debt = 10_000;
+        let mut position = UserPosition::default();
+        position.record_mint(0, 50_001, 10).unwrap();
+
+        // Partial redeems that leave enough debt behind are untouched
+        assert_eq!(dust_free_amount(&stablecoin_mint, &position, 20_000, 50_000).unwrap(), (20_000, false));
+        // Redeeming into dust closes the position, but only with what the
+        // redeemer holds when the tracked debt is a unit above it
+        assert_eq!(dust_free_amount(&stablecoin_mint, &position, 45_000, 50_000).unwrap(), (50_000, true));
+        assert_eq!(dust_free_amount(&stablecoin_mint, &position, 45_000, 60_000).unwrap(), (50_001, true));
+
+        // Rebased closes still burn no more raw tokens than the balance
+        stablecoin_mint.rebase_mode = RebaseMode::Demurrage;
+        stablecoin_mint.rebase_index = REBASE_INDEX_SCALE * 2 / 3;
+        let (amount, closes) = dust_free_amount(&stablecoin_mint, &position, 30_000, 50_000).unwrap();
+        assert!(closes);
+        assert!(stablecoin_mint.to_raw_amount(amount, true).unwrap() <= 50_000);
+    }
+
     #[test]
     fn test_remaining_collateral_ratio() {
-        let total_collateral = 1_500_000;
+        // 1500 collateral at 1.0 backing 1000 coins; redeem 500 of them
+        let (total_collateral, total_value_locked) = (1_500_000u64, 1_000_000u64);
         let redeem_amount = 500_000;
         let min_ratio = 15000; // 150%
-        
-        let remaining_collateral = total_collateral - redeem_amount;
-        let remaining_supply = 1_000_000;
-        
-        let ratio = (remaining_collateral as u128)
-            .checked_mul(10000)
-            .unwrap()
-            .checked_div(remaining_supply as u128)
-            .unwrap() as u16;
-            
-        assert!(ratio >= min_ratio);
+
+        let released = math::calculate_token_amount(redeem_amount, 1_000_000, 6).unwrap();
+        let remaining_collateral = total_collateral - released;
+        let remaining_value_locked = total_value_locked - redeem_amount;
+        let remaining_value = math::calculate_collateral_value(remaining_collateral, 1_000_000, 6).unwrap();
+        assert_eq!(math::collateral_ratio_bps(remaining_value, remaining_value_locked), Some(20000));
+        assert!(ValidationService::validate_collateral_ratio(remaining_value, remaining_value_locked, min_ratio).is_ok());
+
+        // The same collateral at half the price no longer covers the debt left
+        let remaining_value = math::calculate_collateral_value(remaining_collateral, 2_000_000, 6).unwrap();
+        assert!(ValidationService::validate_collateral_ratio(remaining_value, remaining_value_locked, min_ratio).is_err());
+    }
+
+    #[test]
+    fn test_bond_yield_round_trip() {
+        // 5% bond issued at 0, half a year in
+        let stablebond = StablebondData {
+            mint: Pubkey::new_unique(),
+            underlying_mint: Pubkey::default(),
+            current_yield: 500_000,
+            maturity_timestamp: MAX_BOND_DURATION,
+            total_supply: 0,
+            decimals: 6,
+            accrual_start: 0,
+        };
+        let now = MAX_BOND_DURATION / 2;
+
+        for (amount, price) in [(1_000_000u64, 1_000_000u64), (1_234_567, 987_654), (1, 1_000_001)] {
+            let deposit = StablebondService::yield_adjusted_collateral(
+                quote_mint(amount, price, 6, 0, MIN_COLLATERAL_RATIO).unwrap().collateral_amount,
+                Some(&stablebond),
+                now,
+                math::Flow::FromUser,
+            ).unwrap();
+            let payout = StablebondService::yield_adjusted_collateral(
+                math::calculate_token_amount(amount, price, 6).unwrap(),
+                Some(&stablebond),
+                now,
+                math::Flow::ToUser,
+            ).unwrap();
+            // Minting and redeeming the same coins never pays out more
+            // collateral than went in, and loses at most rounding
+            assert!(payout <= deposit);
+            assert!(deposit - payout <= 2);
+        }
+
+        // Appreciated bonds back a coin with fewer of them
+        let deposit = StablebondService::yield_adjusted_collateral(
+            1_000_000,
+            Some(&stablebond),
+            now,
+            math::Flow::FromUser,
+        ).unwrap();
+        assert!(deposit < 1_000_000);
     }
 }
\ No newline at end of file
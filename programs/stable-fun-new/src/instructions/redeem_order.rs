@@ -0,0 +1,687 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_spl::token::{self, Token, TokenAccount};
+use anchor_spl::token_interface::{self, TokenInterface};
+use switchboard_solana::AggregatorAccountData;
+
+use crate::state::{
+    CurrencyBasket, NttConfig, OperationLog, OperationRecord, OperationType, ProtocolConfig,
+    RedeemOrder, StablecoinMint, StablecoinVault, StateAccount, SystemAccounting, UserPosition,
+    PAUSE_REDEEM,
+};
+use crate::constants::{
+    ACCOUNTING_SEED, BASKET_SEED, BOND_TERMS_SEED, NTT_CONFIG_SEED, OPERATION_LOG_SEED,
+    POSITION_SEED, PROTOCOL_CONFIG_SEED, REDEEM_ORDER_ESCROW_SEED, REDEEM_ORDER_SEED, VAULT_SEED,
+};
+use crate::error::StableFunError;
+use crate::instructions::compliance::LargeOperation;
+use crate::instructions::redeem::{split_redeem_collateral, RedeemEvent};
+use crate::utils::attestation;
+use crate::utils::introspection;
+use crate::utils::math;
+use crate::utils::oracle::OracleService;
+use crate::utils::stablebond::StablebondService;
+use crate::utils::validation::ValidationService;
+
+#[derive(Accounts)]
+#[instruction(order_id: u64)]
+pub struct PlaceRedeemOrder<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = RedeemOrder::LEN,
+        seeds = [REDEEM_ORDER_SEED, stablecoin_mint.key().as_ref(), owner.key().as_ref(), &order_id.to_le_bytes()],
+        bump
+    )]
+    pub order: Box<Account<'info, RedeemOrder>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [REDEEM_ORDER_ESCROW_SEED, order.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = order,
+        token::token_program = stablecoin_token_program
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = owner_token_account.mint == token_mint.key() @ StableFunError::InvalidTokenAccount,
+        constraint = owner_token_account.owner == owner.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub owner_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_REDEEM) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    /// Token program owning the stablecoin mint (SPL Token or Token-2022)
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Escrows `amount` stablecoins to be redeemed by a keeper once the oracle
+/// price is at least `min_price`. The keeper is paid collateral worth
+/// `keeper_fee` stablecoins out of the redemption.
+pub fn place_redeem_order(
+    ctx: Context<PlaceRedeemOrder>,
+    order_id: u64,
+    amount: u64,
+    min_price: u64,
+    keeper_fee: u64,
+    expires_at: i64,
+) -> Result<()> {
+    require!(amount > 0 && keeper_fee <= amount, StableFunError::InvalidAmount);
+    ValidationService::validate_amount(amount)?;
+    let now = Clock::get()?.unix_timestamp;
+    require!(expires_at == 0 || expires_at > now, StableFunError::RedeemOrderExpired);
+
+    // Rebasing coins redeem underlying units; the escrow holds tokens
+    let raw_amount = ctx.accounts.stablecoin_mint.to_raw_amount(amount, true)?;
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.stablecoin_token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.owner_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.escrow_token_account.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        raw_amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    let order = &mut ctx.accounts.order;
+    order.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    order.owner = ctx.accounts.owner.key();
+    order.order_id = order_id;
+    order.token_account = ctx.accounts.escrow_token_account.key();
+    order.min_price = min_price;
+    order.keeper_fee = keeper_fee;
+    order.expires_at = expires_at;
+    order.bump = ctx.bumps.order;
+
+    emit!(RedeemOrderPlaced {
+        stablecoin_mint: order.stablecoin_mint,
+        order: order.key(),
+        owner: order.owner,
+        order_id,
+        amount,
+        min_price,
+        keeper_fee,
+        expires_at,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FillRedeemOrder<'info> {
+    /// Any keeper; pays for the position and accounting accounts if needed
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    /// CHECK: Receives the order's and escrow's rent; must be the order owner
+    #[account(
+        mut,
+        constraint = owner.key() == order.owner @ StableFunError::InvalidTokenOwner
+    )]
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [REDEEM_ORDER_SEED, stablecoin_mint.key().as_ref(), order.owner.as_ref(), &order.order_id.to_le_bytes()],
+        bump = order.bump
+    )]
+    pub order: Box<Account<'info, RedeemOrder>>,
+
+    #[account(mut)]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        mut,
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        mut,
+        constraint = escrow_token_account.key() == order.token_account @ StableFunError::InvalidTokenAccount
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = owner_stablebond_account.mint == stablecoin_mint.stablebond_mint @ StableFunError::InvalidStablebond,
+        constraint = owner_stablebond_account.owner == order.owner @ StableFunError::InvalidStablebond
+    )]
+    pub owner_stablebond_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = keeper_stablebond_account.mint == stablecoin_mint.stablebond_mint @ StableFunError::InvalidStablebond
+    )]
+    pub keeper_stablebond_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = vault_stablebond_account.key() == vault.collateral_account @ StableFunError::InvalidVaultAccount
+    )]
+    pub vault_stablebond_account: Box<Account<'info, TokenAccount>>,
+
+    /// The Switchboard V3 aggregator account
+    #[account(
+        constraint = price_feed.key() == stablecoin_mint.price_feed @ StableFunError::InvalidOracle
+    )]
+    pub price_feed: AccountLoader<'info, AggregatorAccountData>,
+
+    /// FX feed composed with the bond NAV `price_feed`, on coins that set one
+    #[account(
+        constraint = fx_feed.key() == stablecoin_mint.fx_feed @ StableFunError::InvalidOracle
+    )]
+    pub fx_feed: Option<AccountLoader<'info, AggregatorAccountData>>,
+
+    /// CHECK: The coin's `BondTerms` PDA, used to price out the yield the
+    /// collateral has accrued; left uninitialized when no terms are set
+    #[account(seeds = [BOND_TERMS_SEED, stablecoin_mint.key().as_ref()], bump)]
+    pub bond_terms: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = UserPosition::LEN,
+        seeds = [POSITION_SEED, stablecoin_mint.key().as_ref(), order.owner.as_ref()],
+        bump
+    )]
+    pub position: Box<Account<'info, UserPosition>>,
+
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = SystemAccounting::LEN,
+        seeds = [ACCOUNTING_SEED, stablecoin_mint.key().as_ref()],
+        bump
+    )]
+    pub accounting: Box<Account<'info, SystemAccounting>>,
+
+    /// Only needed once the coin is registered with NTT
+    #[account(
+        seeds = [NTT_CONFIG_SEED, stablecoin_mint.key().as_ref()],
+        bump = ntt_config.bump
+    )]
+    pub ntt_config: Option<Box<Account<'info, NttConfig>>>,
+
+    /// Appended to when the stablecoin has opted into operation logging
+    #[account(
+        mut,
+        seeds = [OPERATION_LOG_SEED, stablecoin_mint.key().as_ref()],
+        bump = operation_log.load()?.bump
+    )]
+    pub operation_log: Option<AccountLoader<'info, OperationLog>>,
+
+    /// Required for basket-pegged coins
+    #[account(
+        seeds = [BASKET_SEED, stablecoin_mint.key().as_ref()],
+        bump = basket.bump
+    )]
+    pub basket: Option<Box<Account<'info, CurrencyBasket>>>,
+
+    /// Gates regulated coins and carries the protocol-wide pause
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_REDEEM) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    /// CHECK: Registry attestation of the owner, verified in the handler;
+    /// only needed when the protocol gates regulated coins on holders
+    pub holder_attestation: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Instructions sysvar, checked by address
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    /// Token program owning the stablecoin mint (SPL Token or Token-2022)
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// The same checks a redeem by the owner would face; kept out of the handler
+/// so its locals do not share the handler's stack frame.
+#[inline(never)]
+fn validate_fill(ctx: &Context<FillRedeemOrder>, amount: u64) -> Result<()> {
+    let stablecoin_mint = &ctx.accounts.stablecoin_mint;
+    let owner_key = ctx.accounts.order.owner;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(!stablecoin_mint.settings.redeem_paused, StableFunError::RedeemingPaused);
+    require!(amount > 0, StableFunError::InvalidAmount);
+    // Large redeems still have to go through commit-reveal
+    require!(
+        !stablecoin_mint.requires_redeem_commitment(amount),
+        StableFunError::RedeemCommitmentRequired
+    );
+
+    let bridged_supply = ctx.accounts.ntt_config
+        .as_ref()
+        .map_or(0, |config| config.bridged_supply);
+    stablecoin_mint.check_supply_drift(ctx.accounts.token_mint.supply, bridged_supply)?;
+
+    attestation::check_holder_attestation(
+        stablecoin_mint,
+        &ctx.accounts.protocol_config,
+        ctx.accounts.holder_attestation.as_ref().map(|account| account.as_ref()),
+        &owner_key,
+        now,
+    )?;
+
+    require!(
+        ctx.accounts.position.cooldown_elapsed(now, stablecoin_mint.settings.withdrawal_delay),
+        StableFunError::WithdrawalCooldownActive
+    );
+
+    // Optionally refuse to run inside another program's instruction, e.g. a flash loan
+    require!(
+        !stablecoin_mint.settings.top_level_only || introspection::is_top_level(),
+        StableFunError::CpiNotAllowed
+    );
+    let cpi_caller = introspection::cpi_caller(&ctx.accounts.instructions)?;
+    require!(
+        ctx.accounts.protocol_config.allows_cpi_caller(cpi_caller.as_ref()),
+        StableFunError::UnapprovedCpiCaller
+    );
+
+    if stablecoin_mint.settings.same_tx_guard {
+        let stablecoin_key = stablecoin_mint.key();
+        for discriminator in [
+            crate::instruction::Mint::DISCRIMINATOR,
+            crate::instruction::RelayedMint::DISCRIMINATOR,
+        ] {
+            require!(
+                !introspection::has_prior_instruction(
+                    &ctx.accounts.instructions,
+                    &discriminator,
+                    &stablecoin_key,
+                )?,
+                StableFunError::SameTransactionMintRedeem
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Permissionless crank: redeems the whole escrow for the owner once the
+/// order's price condition holds. The redeem fee stays in the vault, the
+/// collateral worth `keeper_fee` goes to the keeper and the rest to the owner.
+pub fn fill_redeem_order(ctx: Context<FillRedeemOrder>) -> Result<()> {
+    let clock = Clock::get()?;
+    ctx.accounts.stablecoin_mint.accrue_demurrage(clock.unix_timestamp)?;
+
+    let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+    let owner_key = ctx.accounts.order.owner;
+
+    let oracle_price = OracleService::collateral_price(
+        &ctx.accounts.stablecoin_mint,
+        &ctx.accounts.price_feed,
+        ctx.accounts.fx_feed.as_ref(),
+    )?;
+    let oracle_price = OracleService::basket_adjusted_price(
+        &ctx.accounts.stablecoin_mint,
+        ctx.accounts.basket.as_deref().map(|basket| &**basket),
+        oracle_price,
+        clock.unix_timestamp,
+    )?;
+    ctx.accounts.order.validate_fill(oracle_price, clock.unix_timestamp)?;
+
+    let raw_burn_amount = ctx.accounts.escrow_token_account.amount;
+    let amount = ctx.accounts.stablecoin_mint.to_underlying_amount(raw_burn_amount)?;
+    validate_fill(&ctx, amount)?;
+    let keeper_fee = ctx.accounts.order.keeper_fee.min(amount);
+
+    // Both shares are net of the yield the bonds have accrued
+    let decimals = ctx.accounts.token_mint.decimals;
+    let stablebond = StablebondService::get_bond_terms_data(
+        &ctx.accounts.bond_terms,
+        &ctx.accounts.stablecoin_mint.stablebond_mint,
+    )?;
+    let collateral_amount = StablebondService::yield_adjusted_collateral(
+        math::calculate_token_amount(amount, oracle_price, decimals)?,
+        stablebond.as_ref(),
+        clock.unix_timestamp,
+        math::Flow::ToUser,
+    )?;
+    let keeper_collateral = StablebondService::yield_adjusted_collateral(
+        math::calculate_token_amount(keeper_fee, oracle_price, decimals)?,
+        stablebond.as_ref(),
+        clock.unix_timestamp,
+        math::Flow::ToUser,
+    )?;
+    // Same fee semantics as `redeem`: it comes out of the collateral released
+    let fee_bps = ctx.accounts.stablecoin_mint.settings.fee_basis_points;
+    let fee_amount = math::bps_of(amount, fee_bps, math::Flow::FromUser)?;
+    let paid_out = split_redeem_collateral(collateral_amount, fee_bps)?.net_collateral;
+    let owner_collateral = paid_out
+        .checked_sub(keeper_collateral)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    require!(
+        !ctx.accounts.vault.breaches_liquidity_buffer(
+            ctx.accounts.vault_stablebond_account.amount,
+            paid_out,
+            ctx.accounts.stablecoin_mint.settings.liquidity_buffer_bps,
+        ),
+        StableFunError::LiquidityBufferBreached
+    );
+
+    let remaining_collateral = ctx.accounts.vault
+        .total_collateral
+        .checked_sub(paid_out)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    let remaining_supply = ctx.accounts.stablecoin_mint
+        .current_supply
+        .checked_sub(raw_burn_amount)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    let remaining_value_locked = ctx.accounts.vault
+        .total_value_locked
+        .checked_sub(amount)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    ValidationService::validate_collateral_ratio(
+        math::calculate_collateral_value(remaining_collateral, oracle_price, decimals)?,
+        remaining_value_locked,
+        ctx.accounts.stablecoin_mint.settings.min_collateral_ratio,
+    )?;
+
+    let order = &ctx.accounts.order;
+    let order_id = order.order_id.to_le_bytes();
+    let order_seeds: &[&[u8]] = &[
+        REDEEM_ORDER_SEED,
+        stablecoin_key.as_ref(),
+        owner_key.as_ref(),
+        &order_id,
+        std::slice::from_ref(&order.bump),
+    ];
+    token_interface::burn(
+        CpiContext::new_with_signer(
+            ctx.accounts.stablecoin_token_program.to_account_info(),
+            token_interface::Burn {
+                mint: ctx.accounts.token_mint.to_account_info(),
+                from: ctx.accounts.escrow_token_account.to_account_info(),
+                authority: order.to_account_info(),
+            },
+            &[order_seeds],
+        ),
+        raw_burn_amount,
+    )?;
+    token_interface::close_account(CpiContext::new_with_signer(
+        ctx.accounts.stablecoin_token_program.to_account_info(),
+        token_interface::CloseAccount {
+            account: ctx.accounts.escrow_token_account.to_account_info(),
+            destination: ctx.accounts.owner.to_account_info(),
+            authority: order.to_account_info(),
+        },
+        &[order_seeds],
+    ))?;
+
+    for (destination, amount) in [
+        (ctx.accounts.owner_stablebond_account.to_account_info(), owner_collateral),
+        (ctx.accounts.keeper_stablebond_account.to_account_info(), keeper_collateral),
+    ] {
+        if amount > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::Transfer {
+                        from: ctx.accounts.vault_stablebond_account.to_account_info(),
+                        to: destination,
+                        authority: ctx.accounts.vault.to_account_info(),
+                    },
+                    &[&ctx.accounts.vault.signer_seeds()],
+                ),
+                amount,
+            )?;
+        }
+    }
+
+    let vault = &mut ctx.accounts.vault;
+    vault.total_collateral = remaining_collateral;
+    vault.total_value_locked = remaining_value_locked;
+    vault.withdrawal_count = vault
+        .withdrawal_count
+        .checked_add(1)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    vault.last_withdrawal_time = clock.unix_timestamp;
+    ValidationService::update_collateral_ratio(vault, oracle_price, decimals)?;
+
+    let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
+    stablecoin_mint.consume_throughput(clock.unix_timestamp)?;
+    stablecoin_mint.current_supply = remaining_supply;
+    stablecoin_mint.stats.total_burned = stablecoin_mint
+        .stats
+        .total_burned
+        .checked_add(amount)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    stablecoin_mint.stats.total_fees = stablecoin_mint
+        .stats
+        .total_fees
+        .checked_add(fee_amount)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    stablecoin_mint.last_updated = clock.unix_timestamp;
+
+    let position = &mut ctx.accounts.position;
+    if !position.is_initialized() {
+        position.initialize(stablecoin_key, owner_key, ctx.bumps.position);
+    }
+    position.record_redeem(collateral_amount, raw_burn_amount, clock.unix_timestamp)?;
+
+    let accounting = &mut ctx.accounts.accounting;
+    if !accounting.is_initialized() {
+        accounting.initialize(stablecoin_key, ctx.bumps.accounting);
+    }
+    accounting.record_fee(fee_amount)?;
+
+    if let Some(operation_log) = &ctx.accounts.operation_log {
+        operation_log.load_mut()?.append(OperationRecord::new(
+            OperationType::Redeem,
+            owner_key,
+            amount,
+            oracle_price,
+            fee_amount,
+            clock.slot,
+        ));
+    }
+
+    if ctx.accounts.stablecoin_mint.exceeds_reporting_threshold(amount) {
+        emit!(LargeOperation {
+            stablecoin_mint: stablecoin_key,
+            operation: OperationType::Redeem as u8,
+            user: owner_key,
+            token_account: ctx.accounts.escrow_token_account.key(),
+            collateral_account: ctx.accounts.owner_stablebond_account.key(),
+            amount,
+            collateral_amount: paid_out,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+    emit!(RedeemEvent {
+        stablecoin_mint: stablecoin_key,
+        user: owner_key,
+        amount,
+        fee_amount,
+        collateral_amount: paid_out,
+        client_order_id: None,
+        timestamp: clock.unix_timestamp,
+    });
+    emit!(RedeemOrderFilled {
+        stablecoin_mint: stablecoin_key,
+        order: ctx.accounts.order.key(),
+        owner: owner_key,
+        keeper: ctx.accounts.keeper.key(),
+        amount,
+        oracle_price,
+        keeper_fee,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelRedeemOrder<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [REDEEM_ORDER_SEED, order.stablecoin_mint.as_ref(), owner.key().as_ref(), &order.order_id.to_le_bytes()],
+        bump = order.bump
+    )]
+    pub order: Box<Account<'info, RedeemOrder>>,
+
+    #[account(
+        constraint = stablecoin_mint.key() == order.stablecoin_mint @ StableFunError::InvalidMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        mut,
+        constraint = escrow_token_account.key() == order.token_account @ StableFunError::InvalidTokenAccount
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = owner_token_account.mint == token_mint.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub owner_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_REDEEM) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    /// Token program owning the stablecoin mint (SPL Token or Token-2022)
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Returns the escrowed coins to the owner and closes the order, expired or
+/// not.
+pub fn cancel_redeem_order(ctx: Context<CancelRedeemOrder>) -> Result<()> {
+    let order = &ctx.accounts.order;
+    let order_id = order.order_id.to_le_bytes();
+    let order_seeds: &[&[u8]] = &[
+        REDEEM_ORDER_SEED,
+        order.stablecoin_mint.as_ref(),
+        order.owner.as_ref(),
+        &order_id,
+        std::slice::from_ref(&order.bump),
+    ];
+
+    let escrowed = ctx.accounts.escrow_token_account.amount;
+    if escrowed > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.stablecoin_token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.escrow_token_account.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.owner_token_account.to_account_info(),
+                    authority: order.to_account_info(),
+                },
+                &[order_seeds],
+            ),
+            escrowed,
+            ctx.accounts.token_mint.decimals,
+        )?;
+    }
+    token_interface::close_account(CpiContext::new_with_signer(
+        ctx.accounts.stablecoin_token_program.to_account_info(),
+        token_interface::CloseAccount {
+            account: ctx.accounts.escrow_token_account.to_account_info(),
+            destination: ctx.accounts.owner.to_account_info(),
+            authority: order.to_account_info(),
+        },
+        &[order_seeds],
+    ))?;
+
+    emit!(RedeemOrderCancelled {
+        stablecoin_mint: order.stablecoin_mint,
+        order: order.key(),
+        owner: order.owner,
+        returned: escrowed,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct RedeemOrderPlaced {
+    pub stablecoin_mint: Pubkey,
+    pub order: Pubkey,
+    pub owner: Pubkey,
+    pub order_id: u64,
+    pub amount: u64,
+    pub min_price: u64,
+    pub keeper_fee: u64,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RedeemOrderFilled {
+    pub stablecoin_mint: Pubkey,
+    pub order: Pubkey,
+    pub owner: Pubkey,
+    pub keeper: Pubkey,
+    pub amount: u64,
+    pub oracle_price: u64,
+    pub keeper_fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RedeemOrderCancelled {
+    pub stablecoin_mint: Pubkey,
+    pub order: Pubkey,
+    pub owner: Pubkey,
+    /// Escrowed tokens handed back
+    pub returned: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{OperationType, StablecoinMint};
+use crate::error::StableFunError;
+
+/// Mint or redeem is paused
+pub const REJECTED_PAUSED: u8 = 0;
+/// Mint would exceed `max_supply`
+pub const REJECTED_SUPPLY_CAP: u8 = 1;
+/// Oracle price is stale or unreadable
+pub const REJECTED_ORACLE: u8 = 2;
+
+/// The reason code for business-relevant rejections monitoring cares about;
+/// every other error stays a plain failure.
+pub fn rejection_reason(error: &Error) -> Option<u8> {
+    let Error::AnchorError(anchor_error) = error else {
+        return None;
+    };
+    [
+        (StableFunError::MintingPaused, REJECTED_PAUSED),
+        (StableFunError::RedeemingPaused, REJECTED_PAUSED),
+        (StableFunError::MaxSupplyExceeded, REJECTED_SUPPLY_CAP),
+        (StableFunError::StaleOraclePrice, REJECTED_ORACLE),
+        (StableFunError::InvalidOraclePrice, REJECTED_ORACLE),
+    ]
+    .into_iter()
+    .find(|(code, _)| u32::from(*code) == anchor_error.error_code_number)
+    .map(|(_, reason)| reason)
+}
+
+/// With `soft_fail_rejections` on, turns a business rejection into an
+/// `OperationRejected` event and `Ok(None)` so the transaction still lands.
+/// Only call this before anything has been written. When the instruction
+/// `creates_accounts`, landing would leave them behind, so the rejection
+/// stays a plain failure.
+#[allow(clippy::too_many_arguments)]
+pub fn soft_fail<T>(
+    result: Result<T>,
+    stablecoin_key: Pubkey,
+    stablecoin_mint: &StablecoinMint,
+    user: Pubkey,
+    op_type: OperationType,
+    amount: u64,
+    client_order_id: Option<[u8; 32]>,
+    creates_accounts: bool,
+) -> Result<Option<T>> {
+    let error = match result {
+        Ok(value) => return Ok(Some(value)),
+        Err(error) => error,
+    };
+    let reason = match rejection_reason(&error) {
+        Some(reason) if stablecoin_mint.settings.soft_fail_rejections && !creates_accounts => reason,
+        _ => return Err(error),
+    };
+
+    let error_code = match &error {
+        Error::AnchorError(anchor_error) => anchor_error.error_code_number,
+        Error::ProgramError(_) => 0,
+    };
+    emit!(OperationRejected {
+        stablecoin_mint: stablecoin_key,
+        user,
+        op_type: op_type as u8,
+        amount,
+        reason,
+        error_code,
+        client_order_id,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(None)
+}
+
+#[event]
+pub struct OperationRejected {
+    pub stablecoin_mint: Pubkey,
+    pub user: Pubkey,
+    /// `OperationType` discriminant
+    pub op_type: u8,
+    pub amount: u64,
+    /// One of the `REJECTED_*` codes
+    pub reason: u8,
+    /// The error the operation would otherwise have failed with
+    pub error_code: u32,
+    pub client_order_id: Option<[u8; 32]>,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rejection_reason() {
+        assert_eq!(rejection_reason(&error!(StableFunError::MintingPaused)), Some(REJECTED_PAUSED));
+        assert_eq!(rejection_reason(&error!(StableFunError::MaxSupplyExceeded)), Some(REJECTED_SUPPLY_CAP));
+        assert_eq!(rejection_reason(&error!(StableFunError::StaleOraclePrice)), Some(REJECTED_ORACLE));
+        assert_eq!(rejection_reason(&error!(StableFunError::InsufficientBalance)), None);
+    }
+
+    #[test]
+    fn test_soft_fail_never_lands_created_accounts() {
+        let mut stablecoin_mint = StablecoinMint::default();
+        stablecoin_mint.settings.soft_fail_rejections = true;
+        let soft_fail = |result: Result<u64>, creates_accounts| soft_fail(
+            result,
+            Pubkey::default(),
+            &stablecoin_mint,
+            Pubkey::default(),
+            OperationType::Mint,
+            1_000,
+            None,
+            creates_accounts,
+        );
+
+        assert_eq!(soft_fail(Ok(7), true).unwrap(), Some(7));
+        // A rejection that would land freshly created accounts fails instead
+        assert!(soft_fail(Err(error!(StableFunError::MintingPaused)), true).is_err());
+        assert!(soft_fail(Err(error!(StableFunError::InsufficientBalance)), false).is_err());
+    }
+}
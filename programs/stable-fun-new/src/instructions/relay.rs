@@ -0,0 +1,920 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_spl::token::{self, Token, TokenAccount};
+use anchor_spl::token_interface::{self, TokenInterface};
+use switchboard_solana::AggregatorAccountData;
+
+use crate::state::{
+    CurrencyBasket, NttConfig, OperationLog, OperationRecord, OperationType, ProtocolConfig,
+    RelayAccount, StablecoinMint, StablecoinVault, StateAccount, SystemAccounting, UserPosition,
+    PAUSE_MINT, PAUSE_REDEEM,
+};
+use crate::constants::{
+    ACCOUNTING_SEED, BASKET_SEED, BOND_TERMS_SEED, MINT_AUTHORITY_SEED, NTT_CONFIG_SEED,
+    OPERATION_LOG_SEED, POSITION_SEED, PROTOCOL_CONFIG_SEED, RELAY_SEED, VAULT_SEED,
+};
+use crate::error::StableFunError;
+use crate::instructions::compliance::LargeOperation;
+use crate::instructions::mint::{quote_mint, MintEvent, MintQuote};
+use crate::instructions::redeem::{split_redeem_collateral, RedeemEvent};
+use crate::utils::oracle::OracleService;
+use crate::utils::validation::ValidationService;
+use crate::utils::attestation;
+use crate::utils::ed25519::{self, RELAYED_MINT, RELAYED_REDEEM};
+use crate::utils::introspection;
+use crate::utils::math;
+use crate::utils::stablebond::StablebondService;
+use crate::utils::token::delegated_for;
+
+/// Terms of an order the user signed off-chain; `fee` is what the relayer
+/// takes, in stablecoin units, and may not exceed `max_fee`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RelayedOrder {
+    pub amount: u64,
+    pub max_fee: u64,
+    pub fee: u64,
+    pub nonce: u64,
+    pub expiry: i64,
+}
+
+#[derive(Accounts)]
+pub struct RelayedMint<'info> {
+    /// Submits the order and pays for any accounts it creates
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_mint.authority == user.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    /// CHECK: Authorizes the order through the ed25519 signature checked in the handler
+    pub user: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        mut,
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == token_mint.key() @ StableFunError::InvalidTokenAccount,
+        constraint = user_token_account.owner == user.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub user_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = relayer_token_account.mint == token_mint.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub relayer_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// Must have delegated at least the collateral to `relay_account`
+    #[account(
+        mut,
+        constraint = user_stablebond_account.mint == stablecoin_mint.stablebond_mint @ StableFunError::InvalidStablebond,
+        constraint = user_stablebond_account.owner == user.key() @ StableFunError::InvalidStablebond
+    )]
+    pub user_stablebond_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = vault_stablebond_account.key() == vault.collateral_account @ StableFunError::InvalidVaultAccount
+    )]
+    pub vault_stablebond_account: Box<Account<'info, TokenAccount>>,
+
+    /// The Switchboard V3 aggregator account
+    #[account(
+        constraint = price_feed.key() == stablecoin_mint.price_feed @ StableFunError::InvalidOracle
+    )]
+    pub price_feed: AccountLoader<'info, AggregatorAccountData>,
+
+    /// FX feed composed with the bond NAV `price_feed`, on coins that set one
+    #[account(
+        constraint = fx_feed.key() == stablecoin_mint.fx_feed @ StableFunError::InvalidOracle
+    )]
+    pub fx_feed: Option<AccountLoader<'info, AggregatorAccountData>>,
+
+    /// CHECK: The coin's `BondTerms` PDA, used to price out the yield the
+    /// collateral has accrued; left uninitialized when no terms are set
+    #[account(seeds = [BOND_TERMS_SEED, stablecoin_mint.key().as_ref()], bump)]
+    pub bond_terms: UncheckedAccount<'info>,
+
+    /// CHECK: PDA used as mint authority
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, stablecoin_mint.key().as_ref()],
+        bump = stablecoin_mint.mint_authority_bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = RelayAccount::LEN,
+        seeds = [RELAY_SEED, stablecoin_mint.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub relay_account: Box<Account<'info, RelayAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = UserPosition::LEN,
+        seeds = [POSITION_SEED, stablecoin_mint.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub position: Box<Account<'info, UserPosition>>,
+
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = SystemAccounting::LEN,
+        seeds = [ACCOUNTING_SEED, stablecoin_mint.key().as_ref()],
+        bump
+    )]
+    pub accounting: Box<Account<'info, SystemAccounting>>,
+
+    /// Only needed once the coin is registered with NTT
+    #[account(
+        seeds = [NTT_CONFIG_SEED, stablecoin_mint.key().as_ref()],
+        bump = ntt_config.bump
+    )]
+    pub ntt_config: Option<Box<Account<'info, NttConfig>>>,
+
+    /// Appended to when the stablecoin has opted into operation logging
+    #[account(
+        mut,
+        seeds = [OPERATION_LOG_SEED, stablecoin_mint.key().as_ref()],
+        bump = operation_log.load()?.bump
+    )]
+    pub operation_log: Option<AccountLoader<'info, OperationLog>>,
+
+    /// Required for basket-pegged coins
+    #[account(
+        seeds = [BASKET_SEED, stablecoin_mint.key().as_ref()],
+        bump = basket.bump
+    )]
+    pub basket: Option<Box<Account<'info, CurrencyBasket>>>,
+
+    /// Gates regulated coins and carries the protocol-wide pause
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_MINT) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    /// CHECK: Registry attestation of the user, verified in the handler;
+    /// only needed when the protocol gates regulated coins on holders
+    pub holder_attestation: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Instructions sysvar, checked by address
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    /// Token program owning the stablecoin mint (SPL Token or Token-2022)
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Signature, replay and mint checks; kept out of the handler so its locals
+/// do not share the handler's stack frame.
+#[inline(never)]
+fn validate_relayed_mint(ctx: &mut Context<RelayedMint>, order: &RelayedOrder) -> Result<()> {
+    let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+    let user_key = ctx.accounts.user.key();
+    let message = ed25519::relayed_order_message(
+        &stablecoin_key,
+        &user_key,
+        RELAYED_MINT,
+        order.amount,
+        order.max_fee,
+        order.nonce,
+        order.expiry,
+    );
+    ed25519::verify_prior_ed25519_signature(&ctx.accounts.instructions, &user_key, &message)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let relay_account = &mut ctx.accounts.relay_account;
+    if !relay_account.is_initialized() {
+        relay_account.initialize(stablecoin_key, user_key, ctx.bumps.relay_account);
+    }
+    relay_account.record_order(order.nonce, order.expiry, now, order.fee, order.max_fee)?;
+
+    let stablecoin_mint = &ctx.accounts.stablecoin_mint;
+    require!(!stablecoin_mint.settings.mint_paused, StableFunError::MintingPaused);
+    require!(order.amount > 0, StableFunError::InvalidAmount);
+    // The first mint locks liquidity, which only `mint` carries the accounts for
+    require!(stablecoin_mint.liquidity_lock_amount() == 0, StableFunError::MissingLiquidityLock);
+    let new_supply = stablecoin_mint.current_supply
+        .checked_add(stablecoin_mint.to_raw_amount(order.amount, false)?)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    require!(
+        new_supply <= stablecoin_mint.settings.max_supply,
+        StableFunError::MaxSupplyExceeded
+    );
+
+    let bridged_supply = ctx.accounts.ntt_config
+        .as_ref()
+        .map_or(0, |config| config.bridged_supply);
+    stablecoin_mint.check_supply_drift(ctx.accounts.token_mint.supply, bridged_supply)?;
+
+    attestation::check_holder_attestation(
+        stablecoin_mint,
+        &ctx.accounts.protocol_config,
+        ctx.accounts.holder_attestation.as_ref().map(|account| account.as_ref()),
+        &user_key,
+        now,
+    )?;
+
+    // Optionally refuse to run inside another program's instruction, e.g. a flash loan
+    require!(
+        !stablecoin_mint.settings.top_level_only || introspection::is_top_level(),
+        StableFunError::CpiNotAllowed
+    );
+    let cpi_caller = introspection::cpi_caller(&ctx.accounts.instructions)?;
+    require!(
+        ctx.accounts.protocol_config.allows_cpi_caller(cpi_caller.as_ref()),
+        StableFunError::UnapprovedCpiCaller
+    );
+
+    if stablecoin_mint.settings.same_tx_guard {
+        for discriminator in [
+            crate::instruction::Redeem::DISCRIMINATOR,
+            crate::instruction::RevealRedeem::DISCRIMINATOR,
+            crate::instruction::RelayedRedeem::DISCRIMINATOR,
+        ] {
+            require!(
+                !introspection::has_prior_instruction(
+                    &ctx.accounts.instructions,
+                    &discriminator,
+                    &stablecoin_key,
+                )?,
+                StableFunError::SameTransactionMintRedeem
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Mints on behalf of the issuer from an order they signed off-chain. The
+/// collateral is pulled through the delegation to their relay account, and
+/// `fee` of the minted stablecoins goes to the relayer instead of the user.
+pub fn relayed_mint(mut ctx: Context<RelayedMint>, order: RelayedOrder) -> Result<()> {
+    validate_relayed_mint(&mut ctx, &order)?;
+
+    let clock = Clock::get()?;
+    ctx.accounts.stablecoin_mint.accrue_demurrage(clock.unix_timestamp)?;
+    let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+    let user_key = ctx.accounts.user.key();
+
+    let oracle_price = OracleService::collateral_price(
+        &ctx.accounts.stablecoin_mint,
+        &ctx.accounts.price_feed,
+        ctx.accounts.fx_feed.as_ref(),
+    )?;
+    let oracle_price = OracleService::basket_adjusted_price(
+        &ctx.accounts.stablecoin_mint,
+        ctx.accounts.basket.as_deref().map(|basket| &**basket),
+        oracle_price,
+        clock.unix_timestamp,
+    )?;
+
+    let MintQuote {
+        collateral_amount,
+        fee_amount,
+        total_amount,
+    } = quote_mint(
+        order.amount,
+        oracle_price,
+        ctx.accounts.token_mint.decimals,
+        ctx.accounts.stablecoin_mint.settings.fee_basis_points,
+        ctx.accounts.stablecoin_mint.settings.min_collateral_ratio,
+    )?;
+    ValidationService::validate_collateral_ratio(
+        math::calculate_collateral_value(collateral_amount, oracle_price, ctx.accounts.token_mint.decimals)?,
+        total_amount,
+        ctx.accounts.stablecoin_mint.settings.min_collateral_ratio,
+    )?;
+    let stablebond = StablebondService::get_bond_terms_data(
+        &ctx.accounts.bond_terms,
+        &ctx.accounts.stablecoin_mint.stablebond_mint,
+    )?;
+    let collateral_amount = StablebondService::yield_adjusted_collateral(
+        collateral_amount,
+        stablebond.as_ref(),
+        clock.unix_timestamp,
+        math::Flow::FromUser,
+    )?;
+    require!(order.fee <= total_amount, StableFunError::RelayerFeeTooHigh);
+    let raw_amount = ctx.accounts.stablecoin_mint.to_raw_amount(total_amount, false)?;
+    let raw_fee = ctx.accounts.stablecoin_mint.to_raw_amount(order.fee, false)?;
+    let raw_user_amount = raw_amount
+        .checked_sub(raw_fee)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+
+    let relay_account = &ctx.accounts.relay_account;
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.user_stablebond_account.to_account_info(),
+                to: ctx.accounts.vault_stablebond_account.to_account_info(),
+                authority: relay_account.to_account_info(),
+            },
+            &[&[
+                RELAY_SEED,
+                stablecoin_key.as_ref(),
+                user_key.as_ref(),
+                &[relay_account.bump],
+            ]],
+        ),
+        collateral_amount,
+    )?;
+
+    for (destination, amount) in [
+        (ctx.accounts.user_token_account.to_account_info(), raw_user_amount),
+        (ctx.accounts.relayer_token_account.to_account_info(), raw_fee),
+    ] {
+        if amount > 0 {
+            token_interface::mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.stablecoin_token_program.to_account_info(),
+                    token_interface::MintTo {
+                        mint: ctx.accounts.token_mint.to_account_info(),
+                        to: destination,
+                        authority: ctx.accounts.mint_authority.to_account_info(),
+                    },
+                    &[&[
+                        MINT_AUTHORITY_SEED,
+                        stablecoin_key.as_ref(),
+                        &[ctx.accounts.stablecoin_mint.mint_authority_bump],
+                    ]],
+                ),
+                amount,
+            )?;
+        }
+    }
+
+    let vault = &mut ctx.accounts.vault;
+    vault.total_collateral = vault
+        .total_collateral
+        .checked_add(collateral_amount)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    vault.total_value_locked = vault
+        .total_value_locked
+        .checked_add(order.amount)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    vault.deposit_count = vault
+        .deposit_count
+        .checked_add(1)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    vault.last_deposit_time = clock.unix_timestamp;
+    ValidationService::update_collateral_ratio(vault, oracle_price, ctx.accounts.token_mint.decimals)?;
+
+    let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
+    stablecoin_mint.consume_throughput(clock.unix_timestamp)?;
+    stablecoin_mint.current_supply = stablecoin_mint
+        .current_supply
+        .checked_add(raw_amount)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    stablecoin_mint.stats.total_minted = stablecoin_mint
+        .stats
+        .total_minted
+        .checked_add(order.amount)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    stablecoin_mint.stats.total_fees = stablecoin_mint
+        .stats
+        .total_fees
+        .checked_add(fee_amount)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    stablecoin_mint.record_admin_action(clock.unix_timestamp);
+
+    // The user signed for the whole mint, relayer fee included
+    let position = &mut ctx.accounts.position;
+    if !position.is_initialized() {
+        position.initialize(stablecoin_key, user_key, ctx.bumps.position);
+    }
+    position.record_mint(collateral_amount, raw_amount, clock.unix_timestamp)?;
+
+    // As on a direct mint, the fee is owed rather than collected
+    let accounting = &mut ctx.accounts.accounting;
+    if !accounting.is_initialized() {
+        accounting.initialize(stablecoin_key, ctx.bumps.accounting);
+    }
+
+    if let Some(operation_log) = &ctx.accounts.operation_log {
+        operation_log.load_mut()?.append(OperationRecord::new(
+            OperationType::Mint,
+            user_key,
+            order.amount,
+            oracle_price,
+            fee_amount,
+            clock.slot,
+        ));
+    }
+
+    if ctx.accounts.stablecoin_mint.exceeds_reporting_threshold(order.amount) {
+        emit!(LargeOperation {
+            stablecoin_mint: stablecoin_key,
+            operation: OperationType::Mint as u8,
+            user: user_key,
+            token_account: ctx.accounts.user_token_account.key(),
+            collateral_account: ctx.accounts.user_stablebond_account.key(),
+            amount: order.amount,
+            collateral_amount,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+    emit!(MintEvent {
+        stablecoin_mint: stablecoin_key,
+        user: user_key,
+        amount: order.amount,
+        fee_amount,
+        collateral_amount,
+        client_order_id: None,
+        maturity_timestamp: stablebond.as_ref().map_or(0, |stablebond| stablebond.maturity_timestamp),
+        timestamp: clock.unix_timestamp,
+    });
+    emit!(RelayedOrderExecuted {
+        stablecoin_mint: stablecoin_key,
+        user: user_key,
+        relayer: ctx.accounts.relayer.key(),
+        action: RELAYED_MINT,
+        nonce: order.nonce,
+        amount: order.amount,
+        relayer_fee: order.fee,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RelayedRedeem<'info> {
+    /// Submits the order and pays for any accounts it creates
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    #[account(mut)]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    /// CHECK: Authorizes the order through the ed25519 signature checked in the handler
+    pub user: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        mut,
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == token_mint.key() @ StableFunError::InvalidTokenAccount,
+        constraint = user_token_account.owner == user.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub user_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_stablebond_account.mint == stablecoin_mint.stablebond_mint @ StableFunError::InvalidStablebond,
+        constraint = user_stablebond_account.owner == user.key() @ StableFunError::InvalidStablebond
+    )]
+    pub user_stablebond_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = relayer_stablebond_account.mint == stablecoin_mint.stablebond_mint @ StableFunError::InvalidStablebond
+    )]
+    pub relayer_stablebond_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = vault_stablebond_account.key() == vault.collateral_account @ StableFunError::InvalidVaultAccount
+    )]
+    pub vault_stablebond_account: Box<Account<'info, TokenAccount>>,
+
+    /// The Switchboard V3 aggregator account
+    #[account(
+        constraint = price_feed.key() == stablecoin_mint.price_feed @ StableFunError::InvalidOracle
+    )]
+    pub price_feed: AccountLoader<'info, AggregatorAccountData>,
+
+    /// FX feed composed with the bond NAV `price_feed`, on coins that set one
+    #[account(
+        constraint = fx_feed.key() == stablecoin_mint.fx_feed @ StableFunError::InvalidOracle
+    )]
+    pub fx_feed: Option<AccountLoader<'info, AggregatorAccountData>>,
+
+    /// CHECK: The coin's `BondTerms` PDA, used to price out the yield the
+    /// collateral has accrued; left uninitialized when no terms are set
+    #[account(seeds = [BOND_TERMS_SEED, stablecoin_mint.key().as_ref()], bump)]
+    pub bond_terms: UncheckedAccount<'info>,
+
+    /// CHECK: PDA used as burn authority; the user approves it as the
+    /// delegate of `user_token_account` for at least the burn amount
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, stablecoin_mint.key().as_ref()],
+        bump = stablecoin_mint.mint_authority_bump
+    )]
+    pub burn_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = RelayAccount::LEN,
+        seeds = [RELAY_SEED, stablecoin_mint.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub relay_account: Box<Account<'info, RelayAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = UserPosition::LEN,
+        seeds = [POSITION_SEED, stablecoin_mint.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub position: Box<Account<'info, UserPosition>>,
+
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = SystemAccounting::LEN,
+        seeds = [ACCOUNTING_SEED, stablecoin_mint.key().as_ref()],
+        bump
+    )]
+    pub accounting: Box<Account<'info, SystemAccounting>>,
+
+    /// Only needed once the coin is registered with NTT
+    #[account(
+        seeds = [NTT_CONFIG_SEED, stablecoin_mint.key().as_ref()],
+        bump = ntt_config.bump
+    )]
+    pub ntt_config: Option<Box<Account<'info, NttConfig>>>,
+
+    /// Appended to when the stablecoin has opted into operation logging
+    #[account(
+        mut,
+        seeds = [OPERATION_LOG_SEED, stablecoin_mint.key().as_ref()],
+        bump = operation_log.load()?.bump
+    )]
+    pub operation_log: Option<AccountLoader<'info, OperationLog>>,
+
+    /// Required for basket-pegged coins
+    #[account(
+        seeds = [BASKET_SEED, stablecoin_mint.key().as_ref()],
+        bump = basket.bump
+    )]
+    pub basket: Option<Box<Account<'info, CurrencyBasket>>>,
+
+    /// Gates regulated coins and carries the protocol-wide pause
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_REDEEM) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    /// CHECK: Registry attestation of the user, verified in the handler;
+    /// only needed when the protocol gates regulated coins on holders
+    pub holder_attestation: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Instructions sysvar, checked by address
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    /// Token program owning the stablecoin mint (SPL Token or Token-2022)
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Signature, replay and redeem checks; kept out of the handler so its locals
+/// do not share the handler's stack frame.
+#[inline(never)]
+fn validate_relayed_redeem(ctx: &mut Context<RelayedRedeem>, order: &RelayedOrder) -> Result<()> {
+    let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+    let user_key = ctx.accounts.user.key();
+    let message = ed25519::relayed_order_message(
+        &stablecoin_key,
+        &user_key,
+        RELAYED_REDEEM,
+        order.amount,
+        order.max_fee,
+        order.nonce,
+        order.expiry,
+    );
+    ed25519::verify_prior_ed25519_signature(&ctx.accounts.instructions, &user_key, &message)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let relay_account = &mut ctx.accounts.relay_account;
+    if !relay_account.is_initialized() {
+        relay_account.initialize(stablecoin_key, user_key, ctx.bumps.relay_account);
+    }
+    relay_account.record_order(order.nonce, order.expiry, now, order.fee, order.max_fee)?;
+
+    let stablecoin_mint = &ctx.accounts.stablecoin_mint;
+    require!(!stablecoin_mint.settings.redeem_paused, StableFunError::RedeemingPaused);
+    require!(order.amount > 0, StableFunError::InvalidAmount);
+    require!(order.fee <= order.amount, StableFunError::RelayerFeeTooHigh);
+    // Large redeems still have to go through commit-reveal
+    require!(
+        !stablecoin_mint.requires_redeem_commitment(order.amount),
+        StableFunError::RedeemCommitmentRequired
+    );
+    ValidationService::validate_amount(order.amount)?;
+
+    let bridged_supply = ctx.accounts.ntt_config
+        .as_ref()
+        .map_or(0, |config| config.bridged_supply);
+    stablecoin_mint.check_supply_drift(ctx.accounts.token_mint.supply, bridged_supply)?;
+
+    attestation::check_holder_attestation(
+        stablecoin_mint,
+        &ctx.accounts.protocol_config,
+        ctx.accounts.holder_attestation.as_ref().map(|account| account.as_ref()),
+        &user_key,
+        now,
+    )?;
+
+    require!(
+        ctx.accounts.position.cooldown_elapsed(now, stablecoin_mint.settings.withdrawal_delay),
+        StableFunError::WithdrawalCooldownActive
+    );
+
+    // Optionally refuse to run inside another program's instruction, e.g. a flash loan
+    require!(
+        !stablecoin_mint.settings.top_level_only || introspection::is_top_level(),
+        StableFunError::CpiNotAllowed
+    );
+    let cpi_caller = introspection::cpi_caller(&ctx.accounts.instructions)?;
+    require!(
+        ctx.accounts.protocol_config.allows_cpi_caller(cpi_caller.as_ref()),
+        StableFunError::UnapprovedCpiCaller
+    );
+
+    if stablecoin_mint.settings.same_tx_guard {
+        for discriminator in [
+            crate::instruction::Mint::DISCRIMINATOR,
+            crate::instruction::RelayedMint::DISCRIMINATOR,
+        ] {
+            require!(
+                !introspection::has_prior_instruction(
+                    &ctx.accounts.instructions,
+                    &discriminator,
+                    &stablecoin_key,
+                )?,
+                StableFunError::SameTransactionMintRedeem
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Redeems for a user from an order they signed off-chain. The redeem fee
+/// stays in the vault, the collateral worth `fee` stablecoins goes to the
+/// relayer and the rest to the user.
+pub fn relayed_redeem(mut ctx: Context<RelayedRedeem>, order: RelayedOrder) -> Result<()> {
+    let clock = Clock::get()?;
+    ctx.accounts.stablecoin_mint.accrue_demurrage(clock.unix_timestamp)?;
+    validate_relayed_redeem(&mut ctx, &order)?;
+
+    let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+    let user_key = ctx.accounts.user.key();
+
+    let oracle_price = OracleService::collateral_price(
+        &ctx.accounts.stablecoin_mint,
+        &ctx.accounts.price_feed,
+        ctx.accounts.fx_feed.as_ref(),
+    )?;
+    let oracle_price = OracleService::basket_adjusted_price(
+        &ctx.accounts.stablecoin_mint,
+        ctx.accounts.basket.as_deref().map(|basket| &**basket),
+        oracle_price,
+        clock.unix_timestamp,
+    )?;
+
+    // Both shares are net of the yield the bonds have accrued
+    let decimals = ctx.accounts.token_mint.decimals;
+    let stablebond = StablebondService::get_bond_terms_data(
+        &ctx.accounts.bond_terms,
+        &ctx.accounts.stablecoin_mint.stablebond_mint,
+    )?;
+    let collateral_amount = StablebondService::yield_adjusted_collateral(
+        math::calculate_token_amount(order.amount, oracle_price, decimals)?,
+        stablebond.as_ref(),
+        clock.unix_timestamp,
+        math::Flow::ToUser,
+    )?;
+    let relayer_collateral = StablebondService::yield_adjusted_collateral(
+        math::calculate_token_amount(order.fee, oracle_price, decimals)?,
+        stablebond.as_ref(),
+        clock.unix_timestamp,
+        math::Flow::ToUser,
+    )?;
+    // Same fee semantics as `redeem`: it comes out of the collateral released
+    let fee_bps = ctx.accounts.stablecoin_mint.settings.fee_basis_points;
+    let fee_amount = math::bps_of(order.amount, fee_bps, math::Flow::FromUser)?;
+    let paid_out = split_redeem_collateral(collateral_amount, fee_bps)?.net_collateral;
+    let user_collateral = paid_out
+        .checked_sub(relayer_collateral)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    require!(
+        !ctx.accounts.vault.breaches_liquidity_buffer(
+            ctx.accounts.vault_stablebond_account.amount,
+            paid_out,
+            ctx.accounts.stablecoin_mint.settings.liquidity_buffer_bps,
+        ),
+        StableFunError::LiquidityBufferBreached
+    );
+
+    let raw_burn_amount = ctx.accounts.stablecoin_mint.to_raw_amount(order.amount, true)?;
+    require!(
+        raw_burn_amount <= ctx.accounts.user_token_account.amount,
+        StableFunError::InsufficientBalance
+    );
+    // The user is not a signer here, so they approve the burn authority as
+    // their token account's delegate before signing the order
+    require!(
+        delegated_for(
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.burn_authority.key(),
+            raw_burn_amount,
+        ),
+        StableFunError::BurnNotApproved
+    );
+
+    let remaining_collateral = ctx.accounts.vault
+        .total_collateral
+        .checked_sub(paid_out)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    let remaining_supply = ctx.accounts.stablecoin_mint
+        .current_supply
+        .checked_sub(raw_burn_amount)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    let remaining_value_locked = ctx.accounts.vault
+        .total_value_locked
+        .checked_sub(order.amount)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    ValidationService::validate_collateral_ratio(
+        math::calculate_collateral_value(remaining_collateral, oracle_price, decimals)?,
+        remaining_value_locked,
+        ctx.accounts.stablecoin_mint.settings.min_collateral_ratio,
+    )?;
+
+    token_interface::burn(
+        CpiContext::new_with_signer(
+            ctx.accounts.stablecoin_token_program.to_account_info(),
+            token_interface::Burn {
+                mint: ctx.accounts.token_mint.to_account_info(),
+                from: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.burn_authority.to_account_info(),
+            },
+            &[&[
+                MINT_AUTHORITY_SEED,
+                stablecoin_key.as_ref(),
+                &[ctx.accounts.stablecoin_mint.mint_authority_bump],
+            ]],
+        ),
+        raw_burn_amount,
+    )?;
+
+    for (destination, amount) in [
+        (ctx.accounts.user_stablebond_account.to_account_info(), user_collateral),
+        (ctx.accounts.relayer_stablebond_account.to_account_info(), relayer_collateral),
+    ] {
+        if amount > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::Transfer {
+                        from: ctx.accounts.vault_stablebond_account.to_account_info(),
+                        to: destination,
+                        authority: ctx.accounts.vault.to_account_info(),
+                    },
+                    &[&ctx.accounts.vault.signer_seeds()],
+                ),
+                amount,
+            )?;
+        }
+    }
+
+    let vault = &mut ctx.accounts.vault;
+    vault.total_collateral = remaining_collateral;
+    vault.total_value_locked = remaining_value_locked;
+    vault.withdrawal_count = vault
+        .withdrawal_count
+        .checked_add(1)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    vault.last_withdrawal_time = clock.unix_timestamp;
+    ValidationService::update_collateral_ratio(vault, oracle_price, decimals)?;
+
+    let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
+    stablecoin_mint.consume_throughput(clock.unix_timestamp)?;
+    stablecoin_mint.current_supply = remaining_supply;
+    stablecoin_mint.stats.total_burned = stablecoin_mint
+        .stats
+        .total_burned
+        .checked_add(order.amount)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    stablecoin_mint.stats.total_fees = stablecoin_mint
+        .stats
+        .total_fees
+        .checked_add(fee_amount)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    stablecoin_mint.last_updated = clock.unix_timestamp;
+
+    let position = &mut ctx.accounts.position;
+    if !position.is_initialized() {
+        position.initialize(stablecoin_key, user_key, ctx.bumps.position);
+    }
+    position.record_redeem(collateral_amount, raw_burn_amount, clock.unix_timestamp)?;
+
+    let accounting = &mut ctx.accounts.accounting;
+    if !accounting.is_initialized() {
+        accounting.initialize(stablecoin_key, ctx.bumps.accounting);
+    }
+    accounting.record_fee(fee_amount)?;
+
+    if let Some(operation_log) = &ctx.accounts.operation_log {
+        operation_log.load_mut()?.append(OperationRecord::new(
+            OperationType::Redeem,
+            user_key,
+            order.amount,
+            oracle_price,
+            fee_amount,
+            clock.slot,
+        ));
+    }
+
+    if ctx.accounts.stablecoin_mint.exceeds_reporting_threshold(order.amount) {
+        emit!(LargeOperation {
+            stablecoin_mint: stablecoin_key,
+            operation: OperationType::Redeem as u8,
+            user: user_key,
+            token_account: ctx.accounts.user_token_account.key(),
+            collateral_account: ctx.accounts.user_stablebond_account.key(),
+            amount: order.amount,
+            collateral_amount: paid_out,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+    emit!(RedeemEvent {
+        stablecoin_mint: stablecoin_key,
+        user: user_key,
+        amount: order.amount,
+        fee_amount,
+        collateral_amount: paid_out,
+        client_order_id: None,
+        timestamp: clock.unix_timestamp,
+    });
+    emit!(RelayedOrderExecuted {
+        stablecoin_mint: stablecoin_key,
+        user: user_key,
+        relayer: ctx.accounts.relayer.key(),
+        action: RELAYED_REDEEM,
+        nonce: order.nonce,
+        amount: order.amount,
+        relayer_fee: order.fee,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct RelayedOrderExecuted {
+    pub stablecoin_mint: Pubkey,
+    pub user: Pubkey,
+    pub relayer: Pubkey,
+    /// `RELAYED_MINT` or `RELAYED_REDEEM`
+    pub action: u8,
+    pub nonce: u64,
+    pub amount: u64,
+    /// Stablecoin-denominated fee taken by the relayer
+    pub relayer_fee: u64,
+    pub timestamp: i64,
+}
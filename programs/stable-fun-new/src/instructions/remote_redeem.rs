@@ -0,0 +1,303 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_spl::token::{self, Token, TokenAccount};
+use anchor_spl::token_interface::Mint;
+use switchboard_solana::AggregatorAccountData;
+
+use crate::state::{
+    NttConfig, OperationLog, OperationRecord, OperationType, ProtocolConfig, RemoteCustodian,
+    StablecoinMint, StablecoinVault, StateAccount, SystemAccounting, PAUSE_BRIDGE, PAUSE_REDEEM,
+};
+use crate::constants::{
+    ACCOUNTING_SEED, BOND_TERMS_SEED, NTT_CONFIG_SEED, OPERATION_LOG_SEED, PROTOCOL_CONFIG_SEED,
+    REMOTE_CUSTODIAN_SEED, VAULT_SEED,
+};
+use crate::error::StableFunError;
+use crate::instructions::redeem::{book_redeem, plan_redeem};
+use crate::utils::oracle::OracleService;
+use crate::utils::secp256k1;
+use crate::utils::math;
+use crate::utils::stablebond::StablebondService;
+
+#[derive(Accounts)]
+pub struct SetRemoteCustodian<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = RemoteCustodian::LEN,
+        seeds = [REMOTE_CUSTODIAN_SEED, stablecoin_mint.key().as_ref()],
+        bump
+    )]
+    pub remote_custodian: Box<Account<'info, RemoteCustodian>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_BRIDGE) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Registers the Ethereum address whose signed orders may redeem bridged
+/// supply. Nonces carry over when the address is rotated.
+pub fn set_remote_custodian(ctx: Context<SetRemoteCustodian>, eth_address: [u8; 20]) -> Result<()> {
+    require!(eth_address != [0u8; 20], StableFunError::InvalidRemoteCustodian);
+
+    let custodian = &mut ctx.accounts.remote_custodian;
+    custodian.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    custodian.eth_address = eth_address;
+    custodian.bump = ctx.bumps.remote_custodian;
+
+    emit!(RemoteCustodianSet {
+        stablecoin_mint: custodian.stablecoin_mint,
+        eth_address,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64, recipient: Pubkey)]
+pub struct RemoteRedeem<'info> {
+    /// Relayer submitting the signed order; needs no rights of its own
+    pub submitter: Signer<'info>,
+
+    #[account(mut)]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        mut,
+        seeds = [REMOTE_CUSTODIAN_SEED, stablecoin_mint.key().as_ref()],
+        bump = remote_custodian.bump
+    )]
+    pub remote_custodian: Box<Account<'info, RemoteCustodian>>,
+
+    #[account(
+        mut,
+        seeds = [NTT_CONFIG_SEED, stablecoin_mint.key().as_ref()],
+        bump = ntt_config.bump
+    )]
+    pub ntt_config: Box<Account<'info, NttConfig>>,
+
+    #[account(
+        mut,
+        seeds = [ACCOUNTING_SEED, stablecoin_mint.key().as_ref()],
+        bump = accounting.bump
+    )]
+    pub accounting: Box<Account<'info, SystemAccounting>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = recipient_stablebond_account.mint == stablecoin_mint.stablebond_mint @ StableFunError::InvalidStablebond,
+        constraint = recipient_stablebond_account.owner == recipient @ StableFunError::InvalidTokenOwner
+    )]
+    pub recipient_stablebond_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = vault_stablebond_account.key() == vault.collateral_account @ StableFunError::InvalidVaultAccount
+    )]
+    pub vault_stablebond_account: Box<Account<'info, TokenAccount>>,
+
+    /// The Switchboard V3 aggregator account
+    #[account(
+        constraint = price_feed.key() == stablecoin_mint.price_feed @ StableFunError::InvalidOracle
+    )]
+    pub price_feed: AccountLoader<'info, AggregatorAccountData>,
+
+    /// FX feed composed with the bond NAV `price_feed`, on coins that set one
+    #[account(
+        constraint = fx_feed.key() == stablecoin_mint.fx_feed @ StableFunError::InvalidOracle
+    )]
+    pub fx_feed: Option<AccountLoader<'info, AggregatorAccountData>>,
+
+    /// CHECK: The coin's `BondTerms` PDA, used to price out the yield the
+    /// collateral has accrued; left uninitialized when no terms are set
+    #[account(seeds = [BOND_TERMS_SEED, stablecoin_mint.key().as_ref()], bump)]
+    pub bond_terms: UncheckedAccount<'info>,
+
+    /// Appended to when the stablecoin has opted into operation logging
+    #[account(
+        mut,
+        seeds = [OPERATION_LOG_SEED, stablecoin_mint.key().as_ref()],
+        bump = operation_log.load()?.bump
+    )]
+    pub operation_log: Option<AccountLoader<'info, OperationLog>>,
+
+    /// CHECK: Instructions sysvar, checked by address
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// A remote redeem is both a bridge operation and a redeem; either
+    /// pause stops it
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_BRIDGE | PAUSE_REDEEM) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Redeems stablecoins the custodian already burned on an EVM chain. The order
+/// `(amount, recipient, nonce)` must be signed by the custodian and checked by
+/// a secp256k1 precompile instruction placed right before this one; collateral
+/// is paid to `recipient` on Solana. Like a local redeem, `amount` is in
+/// underlying units on rebasing coins, and the redeem is held to the
+/// liquidity buffer and the throughput guard.
+#[inline(never)]
+pub fn remote_redeem(
+    ctx: Context<RemoteRedeem>,
+    amount: u64,
+    recipient: Pubkey,
+    nonce: u64,
+) -> Result<()> {
+    require!(!ctx.accounts.stablecoin_mint.settings.redeem_paused, StableFunError::RedeemingPaused);
+    require!(amount > 0, StableFunError::InvalidAmount);
+
+    let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+    let message = secp256k1::remote_redeem_message(&stablecoin_key, amount, &recipient, nonce);
+    secp256k1::verify_prior_secp256k1_signature(
+        &ctx.accounts.instructions,
+        &ctx.accounts.remote_custodian.eth_address,
+        &message,
+    )?;
+    ctx.accounts.remote_custodian.record_redemption(nonce, amount)?;
+
+    // Price the redeem against the index as of now
+    ctx.accounts.stablecoin_mint.accrue_demurrage(Clock::get()?.unix_timestamp)?;
+
+    let oracle_price = OracleService::collateral_price(
+        &ctx.accounts.stablecoin_mint,
+        &ctx.accounts.price_feed,
+        ctx.accounts.fx_feed.as_ref(),
+    )?;
+    let decimals = ctx.accounts.token_mint.decimals;
+    let gross_collateral = StablebondService::yield_adjusted_collateral(
+        math::calculate_token_amount(amount, oracle_price, decimals)?,
+        StablebondService::get_bond_terms_data(
+            &ctx.accounts.bond_terms,
+            &ctx.accounts.stablecoin_mint.stablebond_mint,
+        )?.as_ref(),
+        Clock::get()?.unix_timestamp,
+        math::Flow::ToUser,
+    )?;
+
+    // Same split and checks as a local redeem: the burned amount retires
+    // its debt and the fee stays in the vault
+    let plan = plan_redeem(
+        &ctx.accounts.stablecoin_mint,
+        &ctx.accounts.vault,
+        amount,
+        gross_collateral,
+        oracle_price,
+        decimals,
+        ctx.accounts.stablecoin_mint.settings.fee_basis_points,
+    )?;
+    let collateral_amount = plan.payout.net_collateral;
+
+    // The custodian can only redeem supply that actually left Solana
+    ctx.accounts.ntt_config.record_inbound(plan.raw_burn_amount)?;
+
+    // There is no backstop to fall back on; the custodian retries later
+    require!(
+        !ctx.accounts.vault.breaches_liquidity_buffer(
+            ctx.accounts.vault_stablebond_account.amount,
+            collateral_amount,
+            ctx.accounts.stablecoin_mint.settings.liquidity_buffer_bps,
+        ),
+        StableFunError::LiquidityBufferBreached
+    );
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.vault_stablebond_account.to_account_info(),
+                to: ctx.accounts.recipient_stablebond_account.to_account_info(),
+                authority: ctx.accounts.vault.to_account_info(),
+            },
+            &[&ctx.accounts.vault.signer_seeds()],
+        ),
+        collateral_amount,
+    )?;
+
+    let clock = Clock::get()?;
+    book_redeem(
+        &mut ctx.accounts.stablecoin_mint,
+        &mut ctx.accounts.vault,
+        &mut ctx.accounts.accounting,
+        &plan,
+        oracle_price,
+        decimals,
+        clock.unix_timestamp,
+    )?;
+
+    if let Some(operation_log) = &ctx.accounts.operation_log {
+        operation_log.load_mut()?.append(OperationRecord::new(
+            OperationType::Redeem,
+            recipient,
+            amount,
+            oracle_price,
+            plan.fee_amount,
+            clock.slot,
+        ));
+    }
+
+    emit!(RemoteRedeemEvent {
+        stablecoin_mint: stablecoin_key,
+        custodian: ctx.accounts.remote_custodian.eth_address,
+        recipient,
+        nonce,
+        amount,
+        fee_amount: plan.fee_amount,
+        collateral_amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct RemoteCustodianSet {
+    pub stablecoin_mint: Pubkey,
+    pub eth_address: [u8; 20],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RemoteRedeemEvent {
+    pub stablecoin_mint: Pubkey,
+    pub custodian: [u8; 20],
+    pub recipient: Pubkey,
+    pub nonce: u64,
+    pub amount: u64,
+    pub fee_amount: u64,
+    pub collateral_amount: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+use crate::state::{ProtocolConfig, StablecoinMint, StablecoinVault};
+use crate::constants::{
+    COLLATERAL_MIGRATION_SEED, MINT_AUTHORITY_SEED, PROTOCOL_CONFIG_SEED, VAULT_SEED,
+};
+use crate::error::StableFunError;
+
+#[derive(Accounts)]
+pub struct RescueToken<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    /// CHECK: PDA owning the fee vaults
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, stablecoin_mint.key().as_ref()],
+        bump = stablecoin_mint.mint_authority_bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// Token account owned by the vault or fee PDA holding the stuck tokens
+    #[account(
+        mut,
+        constraint = source_token_account.owner == vault.key()
+            || source_token_account.owner == mint_authority.key() @ StableFunError::InvalidTokenAccount,
+        constraint = source_token_account.key() != vault.collateral_account @ StableFunError::ProtectedTokenRescue,
+        constraint = source_token_account.mint != stablecoin_mint.token_mint @ StableFunError::ProtectedTokenRescue,
+        constraint = source_token_account.mint != stablecoin_mint.stablebond_mint @ StableFunError::ProtectedTokenRescue
+    )]
+    pub source_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = destination_token_account.mint == source_token_account.mint @ StableFunError::InvalidTokenAccount
+    )]
+    pub destination_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: must not exist; the vault holds the incoming stablebond series
+    /// while a collateral migration is in progress
+    #[account(
+        seeds = [COLLATERAL_MIGRATION_SEED, stablecoin_mint.key().as_ref()],
+        bump,
+        constraint = collateral_migration.data_is_empty() @ StableFunError::CollateralMigrationInProgress
+    )]
+    pub collateral_migration: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Returns tokens of a foreign mint that were sent to the vault or fee PDA by
+/// mistake.
+pub fn rescue_token(ctx: Context<RescueToken>, amount: u64) -> Result<()> {
+    require!(
+        amount > 0 && amount <= ctx.accounts.source_token_account.amount,
+        StableFunError::InvalidAmount
+    );
+
+    let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+    let mint_authority_bump = [ctx.accounts.stablecoin_mint.mint_authority_bump];
+    let (authority, signer_seeds) = if ctx.accounts.source_token_account.owner == ctx.accounts.vault.key() {
+        (ctx.accounts.vault.to_account_info(), ctx.accounts.vault.signer_seeds())
+    } else {
+        (
+            ctx.accounts.mint_authority.to_account_info(),
+            [MINT_AUTHORITY_SEED, stablecoin_key.as_ref(), &mint_authority_bump],
+        )
+    };
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.source_token_account.to_account_info(),
+                to: ctx.accounts.destination_token_account.to_account_info(),
+                authority,
+            },
+            &[&signer_seeds],
+        ),
+        amount,
+    )?;
+
+    emit!(TokenRescued {
+        stablecoin_mint: stablecoin_key,
+        mint: ctx.accounts.source_token_account.mint,
+        destination: ctx.accounts.destination_token_account.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct TokenRescued {
+    pub stablecoin_mint: Pubkey,
+    pub mint: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use anchor_spl::token_interface;
+use switchboard_solana::AggregatorAccountData;
+
+use crate::state::{ProtocolConfig, ReserveReport, StablecoinMint, StablecoinVault, StateAccount};
+use crate::constants::{PROTOCOL_CONFIG_SEED, RESERVE_REPORT_SEED, VAULT_SEED};
+use crate::error::StableFunError;
+use crate::utils::oracle::OracleService;
+use crate::utils::math;
+
+#[derive(Accounts)]
+pub struct PublishReserveReport<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        constraint = vault_stablebond_account.key() == vault.collateral_account @ StableFunError::InvalidVaultAccount
+    )]
+    pub vault_stablebond_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// The Switchboard V3 aggregator account
+    #[account(
+        constraint = price_feed.key() == stablecoin_mint.price_feed @ StableFunError::InvalidOracle
+    )]
+    pub price_feed: AccountLoader<'info, AggregatorAccountData>,
+
+    /// FX feed composed with the bond NAV `price_feed`, on coins that set one
+    #[account(
+        constraint = fx_feed.key() == stablecoin_mint.fx_feed @ StableFunError::InvalidOracle
+    )]
+    pub fx_feed: Option<AccountLoader<'info, AggregatorAccountData>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = ReserveReport::LEN,
+        seeds = [RESERVE_REPORT_SEED, stablecoin_mint.key().as_ref()],
+        bump
+    )]
+    pub report: Box<Account<'info, ReserveReport>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless: snapshots the vault's actual reserves against outstanding supply.
+#[inline(never)]
+pub fn publish_reserve_report(ctx: Context<PublishReserveReport>) -> Result<()> {
+    let oracle_price = OracleService::collateral_price(
+        &ctx.accounts.stablecoin_mint,
+        &ctx.accounts.price_feed,
+        ctx.accounts.fx_feed.as_ref(),
+    )?;
+    let vault_balance = ctx.accounts.vault_stablebond_account.amount;
+    let outstanding_supply = ctx.accounts.token_mint.supply;
+    let reserve_value = math::calculate_collateral_value(
+        vault_balance,
+        oracle_price,
+        ctx.accounts.token_mint.decimals,
+    )?;
+    let clock = Clock::get()?;
+
+    let report = &mut ctx.accounts.report;
+    report.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    report.vault_balance = vault_balance;
+    report.recorded_collateral = ctx.accounts.vault.total_collateral;
+    report.price = oracle_price;
+    report.reserve_value = reserve_value;
+    report.outstanding_supply = outstanding_supply;
+    report.reserve_ratio = ReserveReport::calculate_reserve_ratio(reserve_value, outstanding_supply);
+    report.slot = clock.slot;
+    report.timestamp = clock.unix_timestamp;
+    report.report_count = report.report_count
+        .checked_add(1)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    report.bump = ctx.bumps.report;
+
+    emit!(ReserveReportPublished {
+        stablecoin_mint: report.stablecoin_mint,
+        publisher: ctx.accounts.payer.key(),
+        vault_balance,
+        recorded_collateral: report.recorded_collateral,
+        price: oracle_price,
+        reserve_value,
+        outstanding_supply,
+        reserve_ratio: report.reserve_ratio,
+        slot: clock.slot,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ReserveReportPublished {
+    pub stablecoin_mint: Pubkey,
+    pub publisher: Pubkey,
+    pub vault_balance: u64,
+    pub recorded_collateral: u64,
+    pub price: u64,
+    pub reserve_value: u64,
+    pub outstanding_supply: u64,
+    pub reserve_ratio: u64,
+    pub slot: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{ProtocolConfig, ReservedNames, StateAccount};
+use crate::constants::{PROTOCOL_CONFIG_SEED, RESERVED_NAMES_SEED};
+use crate::error::StableFunError;
+
+#[derive(Accounts)]
+pub struct InitializeReservedNames<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        has_one = admin @ StableFunError::UnauthorizedProtocolAdmin
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = ReservedNames::LEN,
+        seeds = [RESERVED_NAMES_SEED],
+        bump
+    )]
+    pub reserved_names: Box<Account<'info, ReservedNames>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_reserved_names(
+    ctx: Context<InitializeReservedNames>,
+    hashes: Vec<[u8; 32]>,
+) -> Result<()> {
+    let reserved_names = &mut ctx.accounts.reserved_names;
+    reserved_names.bump = ctx.bumps.reserved_names;
+    reserved_names.update(&hashes, &[])?;
+
+    emit!(ReservedNamesUpdated {
+        added: hashes,
+        removed: Vec::new(),
+        count: reserved_names.hashes.len() as u32,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateReservedNames<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        has_one = admin @ StableFunError::UnauthorizedProtocolAdmin
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [RESERVED_NAMES_SEED],
+        bump = reserved_names.bump
+    )]
+    pub reserved_names: Box<Account<'info, ReservedNames>>,
+}
+
+/// Adds and removes entries; hashes are `ReservedNames::name_hash` of the
+/// name to block.
+pub fn update_reserved_names(
+    ctx: Context<UpdateReservedNames>,
+    add: Vec<[u8; 32]>,
+    remove: Vec<[u8; 32]>,
+) -> Result<()> {
+    let reserved_names = &mut ctx.accounts.reserved_names;
+    reserved_names.update(&add, &remove)?;
+
+    emit!(ReservedNamesUpdated {
+        added: add,
+        removed: remove,
+        count: reserved_names.hashes.len() as u32,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ReservedNamesUpdated {
+    pub added: Vec<[u8; 32]>,
+    pub removed: Vec<[u8; 32]>,
+    pub count: u32,
+    pub timestamp: i64,
+}
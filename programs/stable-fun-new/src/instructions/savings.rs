@@ -0,0 +1,418 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{self, TokenInterface};
+
+use crate::state::{
+    ProtocolConfig, SavingsVault, StablecoinMint, StablecoinVault, StateAccount, SystemAccounting,
+    PAUSE_VAULTS,
+};
+use crate::constants::{
+    ACCOUNTING_SEED, MINT_AUTHORITY_SEED, PROTOCOL_CONFIG_SEED, SAVINGS_SHARE_MINT_SEED,
+    SAVINGS_TOKEN_SEED, SAVINGS_VAULT_SEED, VAULT_SEED,
+};
+use crate::error::StableFunError;
+use crate::utils::validation::ValidationService;
+
+#[derive(Accounts)]
+pub struct InitSavingsVault<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = SavingsVault::LEN,
+        seeds = [SAVINGS_VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump
+    )]
+    pub savings_vault: Box<Account<'info, SavingsVault>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [SAVINGS_SHARE_MINT_SEED, stablecoin_mint.key().as_ref()],
+        bump,
+        mint::decimals = token_mint.decimals,
+        mint::authority = savings_vault
+    )]
+    pub share_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [SAVINGS_TOKEN_SEED, stablecoin_mint.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = savings_vault,
+        token::token_program = stablecoin_token_program
+    )]
+    pub savings_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        seeds = [ACCOUNTING_SEED, stablecoin_mint.key().as_ref()],
+        bump = accounting.bump
+    )]
+    pub accounting: Box<Account<'info, SystemAccounting>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_VAULTS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token>,
+    /// Token program owning the stablecoin mint (SPL Token or Token-2022)
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Opens the savings pool of a stablecoin. Only yield harvested after this
+/// point compounds into it.
+pub fn init_savings_vault(ctx: Context<InitSavingsVault>) -> Result<()> {
+    let savings_vault = &mut ctx.accounts.savings_vault;
+    savings_vault.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    savings_vault.share_mint = ctx.accounts.share_mint.key();
+    savings_vault.token_account = ctx.accounts.savings_token_account.key();
+    savings_vault.yield_checkpoint = ctx.accounts.accounting.harvested_yield;
+    savings_vault.bump = ctx.bumps.savings_vault;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SavingsOperation<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SAVINGS_VAULT_SEED, savings_vault.stablecoin_mint.as_ref()],
+        bump = savings_vault.bump
+    )]
+    pub savings_vault: Box<Account<'info, SavingsVault>>,
+
+    #[account(
+        mut,
+        constraint = share_mint.key() == savings_vault.share_mint @ StableFunError::InvalidMint
+    )]
+    pub share_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        constraint = token_mint.key() == savings_token_account.mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        mut,
+        constraint = savings_token_account.key() == savings_vault.token_account @ StableFunError::InvalidTokenAccount
+    )]
+    pub savings_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == token_mint.key() @ StableFunError::InvalidTokenAccount,
+        constraint = user_token_account.owner == user.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub user_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_share_account.mint == share_mint.key() @ StableFunError::InvalidTokenAccount,
+        constraint = user_share_account.owner == user.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub user_share_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_VAULTS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token>,
+    /// Token program owning the stablecoin mint (SPL Token or Token-2022)
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Deposits `assets` stablecoins and mints shares at the current exchange rate.
+pub fn deposit_savings(ctx: Context<SavingsOperation>, assets: u64) -> Result<()> {
+    require!(assets > 0, StableFunError::InvalidAmount);
+
+    let balance_before = ctx.accounts.savings_token_account.amount;
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.stablecoin_token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.savings_token_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        assets,
+        ctx.accounts.token_mint.decimals,
+    )?;
+    // Transfer fees are withheld from the pool, so price what arrived
+    ctx.accounts.savings_token_account.reload()?;
+    let received = ctx.accounts.savings_token_account.amount.saturating_sub(balance_before);
+
+    let shares = ctx.accounts.savings_vault.shares_for(received)?;
+    require!(shares > 0, StableFunError::ZeroShares);
+
+    let stablecoin_key = ctx.accounts.savings_vault.stablecoin_mint;
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::MintTo {
+                mint: ctx.accounts.share_mint.to_account_info(),
+                to: ctx.accounts.user_share_account.to_account_info(),
+                authority: ctx.accounts.savings_vault.to_account_info(),
+            },
+            &[&[
+                SAVINGS_VAULT_SEED,
+                stablecoin_key.as_ref(),
+                &[ctx.accounts.savings_vault.bump],
+            ]],
+        ),
+        shares,
+    )?;
+
+    let savings_vault = &mut ctx.accounts.savings_vault;
+    savings_vault.total_assets = savings_vault.total_assets
+        .checked_add(received)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    savings_vault.total_shares = savings_vault.total_shares
+        .checked_add(shares)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+
+    emit!(SavingsDeposited {
+        stablecoin_mint: stablecoin_key,
+        user: ctx.accounts.user.key(),
+        assets: received,
+        shares,
+        exchange_rate: savings_vault.exchange_rate(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Burns `shares` and pays out the stablecoins they are worth.
+pub fn redeem_savings(ctx: Context<SavingsOperation>, shares: u64) -> Result<()> {
+    require!(shares > 0, StableFunError::InvalidAmount);
+
+    let assets = ctx.accounts.savings_vault.assets_for(shares)?;
+    require!(assets > 0, StableFunError::ZeroShares);
+
+    token::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Burn {
+                mint: ctx.accounts.share_mint.to_account_info(),
+                from: ctx.accounts.user_share_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        shares,
+    )?;
+
+    let stablecoin_key = ctx.accounts.savings_vault.stablecoin_mint;
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.stablecoin_token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.savings_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.savings_vault.to_account_info(),
+            },
+            &[&[
+                SAVINGS_VAULT_SEED,
+                stablecoin_key.as_ref(),
+                &[ctx.accounts.savings_vault.bump],
+            ]],
+        ),
+        assets,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    let savings_vault = &mut ctx.accounts.savings_vault;
+    savings_vault.total_assets -= assets;
+    savings_vault.total_shares -= shares;
+
+    emit!(SavingsRedeemed {
+        stablecoin_mint: stablecoin_key,
+        user: ctx.accounts.user.key(),
+        assets,
+        shares,
+        exchange_rate: savings_vault.exchange_rate(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct HarvestSavings<'info> {
+    #[account(mut)]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [SAVINGS_VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = savings_vault.bump
+    )]
+    pub savings_vault: Box<Account<'info, SavingsVault>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        mut,
+        seeds = [ACCOUNTING_SEED, stablecoin_mint.key().as_ref()],
+        bump = accounting.bump
+    )]
+    pub accounting: Box<Account<'info, SystemAccounting>>,
+
+    #[account(
+        mut,
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        mut,
+        constraint = savings_token_account.key() == savings_vault.token_account @ StableFunError::InvalidTokenAccount
+    )]
+    pub savings_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// CHECK: PDA used as mint authority
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, stablecoin_mint.key().as_ref()],
+        bump = stablecoin_mint.mint_authority_bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_VAULTS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    /// Token program owning the stablecoin mint (SPL Token or Token-2022)
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Permissionless: compounds the pool's pro-rata share of newly harvested
+/// yield into it. The coins are minted against surplus collateral already in
+/// the vault, so the issuer's surplus buffer and the supply cap still apply.
+pub fn harvest_savings(ctx: Context<HarvestSavings>) -> Result<()> {
+    let harvested_yield = ctx.accounts.accounting.harvested_yield;
+    let supply = ctx.accounts.stablecoin_mint.current_supply;
+    let earned = ctx.accounts.savings_vault.savers_yield(harvested_yield, supply)?;
+
+    let stablecoin_mint = &ctx.accounts.stablecoin_mint;
+    let max_mintable = stablecoin_mint.settings.max_supply.saturating_sub(supply);
+    let available = ctx.accounts.accounting.withdrawable(stablecoin_mint.settings.surplus_buffer);
+    let amount = earned.min(available).min(stablecoin_mint.to_underlying_amount(max_mintable)?);
+    let raw_amount = stablecoin_mint.to_raw_amount(amount, false)?;
+
+    let stablecoin_key = stablecoin_mint.key();
+    if raw_amount > 0 {
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.stablecoin_token_program.to_account_info(),
+                token_interface::MintTo {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.savings_token_account.to_account_info(),
+                    authority: ctx.accounts.mint_authority.to_account_info(),
+                },
+                &[&[
+                    MINT_AUTHORITY_SEED,
+                    stablecoin_key.as_ref(),
+                    &[stablecoin_mint.mint_authority_bump],
+                ]],
+            ),
+            raw_amount,
+        )?;
+
+        ctx.accounts.accounting.surplus -= amount;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.total_value_locked = vault
+            .total_value_locked
+            .checked_add(amount)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        // No fresh price here; re-rate at the last one
+        let ratio_price = vault.ratio_price;
+        ValidationService::update_collateral_ratio(vault, ratio_price, ctx.accounts.token_mint.decimals)?;
+
+        let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
+        stablecoin_mint.current_supply += raw_amount;
+        stablecoin_mint.last_updated = Clock::get()?.unix_timestamp;
+    }
+
+    // Yield that could not be paid out is forfeited rather than carried over
+    let savings_vault = &mut ctx.accounts.savings_vault;
+    savings_vault.yield_checkpoint = harvested_yield;
+    savings_vault.total_assets = savings_vault.total_assets
+        .checked_add(raw_amount)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    savings_vault.total_yield = savings_vault.total_yield
+        .checked_add(amount)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+
+    emit!(SavingsHarvested {
+        stablecoin_mint: stablecoin_key,
+        amount,
+        total_assets: savings_vault.total_assets,
+        exchange_rate: savings_vault.exchange_rate(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct SavingsDeposited {
+    pub stablecoin_mint: Pubkey,
+    pub user: Pubkey,
+    pub assets: u64,
+    pub shares: u64,
+    pub exchange_rate: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SavingsRedeemed {
+    pub stablecoin_mint: Pubkey,
+    pub user: Pubkey,
+    pub assets: u64,
+    pub shares: u64,
+    pub exchange_rate: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SavingsHarvested {
+    pub stablecoin_mint: Pubkey,
+    pub amount: u64,
+    pub total_assets: u64,
+    pub exchange_rate: u64,
+    pub timestamp: i64,
+}
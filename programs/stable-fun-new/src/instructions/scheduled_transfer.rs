@@ -0,0 +1,333 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+
+use crate::state::{
+    ProtocolConfig, ScheduledTransfer, StablecoinMint, StateAccount, PAUSE_TRANSFERS,
+};
+use crate::constants::{
+    PROTOCOL_CONFIG_SEED, SCHEDULED_TRANSFER_SEED, SCHEDULED_TRANSFER_VAULT_SEED,
+};
+use crate::error::StableFunError;
+
+#[derive(Accounts)]
+#[instruction(transfer_id: u64)]
+pub struct ScheduleTransfer<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    /// CHECK: Only recorded as the payee
+    pub recipient: UncheckedAccount<'info>,
+
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        init,
+        payer = sender,
+        space = ScheduledTransfer::LEN,
+        seeds = [SCHEDULED_TRANSFER_SEED, stablecoin_mint.key().as_ref(), sender.key().as_ref(), &transfer_id.to_le_bytes()],
+        bump
+    )]
+    pub scheduled_transfer: Box<Account<'info, ScheduledTransfer>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = sender,
+        seeds = [SCHEDULED_TRANSFER_VAULT_SEED, scheduled_transfer.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = scheduled_transfer,
+        token::token_program = token_program
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = sender_token_account.mint == token_mint.key() @ StableFunError::InvalidTokenAccount,
+        constraint = sender_token_account.owner == sender.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub sender_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_TRANSFERS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Escrows `amount` stablecoins for `recipient`, payable from `release_time`.
+pub fn schedule(
+    ctx: Context<ScheduleTransfer>,
+    transfer_id: u64,
+    amount: u64,
+    release_time: i64,
+) -> Result<()> {
+    require!(amount > 0, StableFunError::InvalidAmount);
+    let now = Clock::get()?.unix_timestamp;
+    require!(release_time > now, StableFunError::InvalidReleaseTime);
+
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.sender_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.escrow_token_account.to_account_info(),
+                authority: ctx.accounts.sender.to_account_info(),
+            },
+        ),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    let scheduled = &mut ctx.accounts.scheduled_transfer;
+    scheduled.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    scheduled.sender = ctx.accounts.sender.key();
+    scheduled.recipient = ctx.accounts.recipient.key();
+    scheduled.transfer_id = transfer_id;
+    scheduled.token_account = ctx.accounts.escrow_token_account.key();
+    scheduled.amount = amount;
+    scheduled.release_time = release_time;
+    scheduled.bump = ctx.bumps.scheduled_transfer;
+
+    emit!(TransferScheduled {
+        stablecoin_mint: scheduled.stablecoin_mint,
+        scheduled_transfer: scheduled.key(),
+        sender: scheduled.sender,
+        recipient: scheduled.recipient,
+        transfer_id,
+        amount,
+        release_time,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExecuteTransfer<'info> {
+    /// CHECK: Receives the escrow rent back; checked against the transfer
+    #[account(mut, address = scheduled_transfer.sender)]
+    pub sender: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = sender,
+        seeds = [SCHEDULED_TRANSFER_SEED, scheduled_transfer.stablecoin_mint.as_ref(), scheduled_transfer.sender.as_ref(), &scheduled_transfer.transfer_id.to_le_bytes()],
+        bump = scheduled_transfer.bump
+    )]
+    pub scheduled_transfer: Box<Account<'info, ScheduledTransfer>>,
+
+    #[account(
+        constraint = stablecoin_mint.key() == scheduled_transfer.stablecoin_mint @ StableFunError::InvalidMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = escrow_token_account.key() == scheduled_transfer.token_account @ StableFunError::InvalidTokenAccount
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = recipient_token_account.mint == token_mint.key() @ StableFunError::InvalidTokenAccount,
+        constraint = recipient_token_account.owner == scheduled_transfer.recipient @ StableFunError::InvalidTokenAccount
+    )]
+    pub recipient_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_TRANSFERS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Permissionless once released: pays the recipient and closes the escrow.
+pub fn execute(ctx: Context<ExecuteTransfer>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let scheduled = &ctx.accounts.scheduled_transfer;
+    require!(scheduled.is_released(now), StableFunError::TransferNotReleased);
+
+    let amount = ctx.accounts.escrow_token_account.amount;
+    release_escrow(
+        scheduled,
+        &ctx.accounts.token_program,
+        &ctx.accounts.escrow_token_account,
+        &ctx.accounts.token_mint,
+        ctx.accounts.recipient_token_account.to_account_info(),
+        ctx.accounts.sender.to_account_info(),
+        amount,
+    )?;
+
+    emit!(TransferExecuted {
+        scheduled_transfer: scheduled.key(),
+        recipient: scheduled.recipient,
+        amount,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelTransfer<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    #[account(
+        mut,
+        close = sender,
+        seeds = [SCHEDULED_TRANSFER_SEED, scheduled_transfer.stablecoin_mint.as_ref(), sender.key().as_ref(), &scheduled_transfer.transfer_id.to_le_bytes()],
+        bump = scheduled_transfer.bump
+    )]
+    pub scheduled_transfer: Box<Account<'info, ScheduledTransfer>>,
+
+    #[account(
+        constraint = stablecoin_mint.key() == scheduled_transfer.stablecoin_mint @ StableFunError::InvalidMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = escrow_token_account.key() == scheduled_transfer.token_account @ StableFunError::InvalidTokenAccount
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = sender_token_account.mint == token_mint.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub sender_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_TRANSFERS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Refunds the sender; only possible before the release time, after which
+/// the payment belongs to the recipient.
+pub fn cancel_transfer(ctx: Context<CancelTransfer>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let scheduled = &ctx.accounts.scheduled_transfer;
+    require!(!scheduled.is_released(now), StableFunError::TransferAlreadyReleased);
+
+    let refund = ctx.accounts.escrow_token_account.amount;
+    release_escrow(
+        scheduled,
+        &ctx.accounts.token_program,
+        &ctx.accounts.escrow_token_account,
+        &ctx.accounts.token_mint,
+        ctx.accounts.sender_token_account.to_account_info(),
+        ctx.accounts.sender.to_account_info(),
+        refund,
+    )?;
+
+    emit!(TransferCancelled {
+        scheduled_transfer: scheduled.key(),
+        sender: scheduled.sender,
+        refunded: refund,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+/// Empties the escrow into `destination` and closes it, rent to `rent_receiver`.
+fn release_escrow<'info>(
+    scheduled: &Account<'info, ScheduledTransfer>,
+    token_program: &Interface<'info, TokenInterface>,
+    escrow_token_account: &InterfaceAccount<'info, TokenAccount>,
+    token_mint: &InterfaceAccount<'info, Mint>,
+    destination: AccountInfo<'info>,
+    rent_receiver: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let transfer_id = scheduled.transfer_id.to_le_bytes();
+    let seeds: &[&[u8]] = &[
+        SCHEDULED_TRANSFER_SEED,
+        scheduled.stablecoin_mint.as_ref(),
+        scheduled.sender.as_ref(),
+        &transfer_id,
+        &[scheduled.bump],
+    ];
+
+    if amount > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: escrow_token_account.to_account_info(),
+                    mint: token_mint.to_account_info(),
+                    to: destination,
+                    authority: scheduled.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+            token_mint.decimals,
+        )?;
+    }
+
+    token_interface::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        token_interface::CloseAccount {
+            account: escrow_token_account.to_account_info(),
+            destination: rent_receiver,
+            authority: scheduled.to_account_info(),
+        },
+        &[seeds],
+    ))
+}
+
+#[event]
+pub struct TransferScheduled {
+    pub stablecoin_mint: Pubkey,
+    pub scheduled_transfer: Pubkey,
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+    pub transfer_id: u64,
+    pub amount: u64,
+    pub release_time: i64,
+}
+
+#[event]
+pub struct TransferExecuted {
+    pub scheduled_transfer: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TransferCancelled {
+    pub scheduled_transfer: Pubkey,
+    pub sender: Pubkey,
+    pub refunded: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,252 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+use crate::state::{ProtocolConfig, ShareVault, StablecoinMint, StateAccount, PAUSE_VAULTS};
+use crate::constants::{
+    PROTOCOL_CONFIG_SEED, SHARE_COLLATERAL_SEED, SHARE_MINT_SEED, SHARE_VAULT_SEED,
+};
+use crate::error::StableFunError;
+
+#[derive(Accounts)]
+pub struct InitShareVault<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = ShareVault::LEN,
+        seeds = [SHARE_VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump
+    )]
+    pub share_vault: Box<Account<'info, ShareVault>>,
+
+    #[account(
+        constraint = stablebond_mint.key() == stablecoin_mint.stablebond_mint @ StableFunError::InvalidStablebond
+    )]
+    pub stablebond_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [SHARE_MINT_SEED, stablecoin_mint.key().as_ref()],
+        bump,
+        mint::decimals = stablebond_mint.decimals,
+        mint::authority = share_vault
+    )]
+    pub share_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [SHARE_COLLATERAL_SEED, stablecoin_mint.key().as_ref()],
+        bump,
+        token::mint = stablebond_mint,
+        token::authority = share_vault
+    )]
+    pub collateral_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_VAULTS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Opens the optional share vault of a stablecoin.
+pub fn init_share_vault(ctx: Context<InitShareVault>) -> Result<()> {
+    let share_vault = &mut ctx.accounts.share_vault;
+    share_vault.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    share_vault.share_mint = ctx.accounts.share_mint.key();
+    share_vault.collateral_account = ctx.accounts.collateral_account.key();
+    share_vault.bump = ctx.bumps.share_vault;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ShareVaultOperation<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SHARE_VAULT_SEED, share_vault.stablecoin_mint.as_ref()],
+        bump = share_vault.bump
+    )]
+    pub share_vault: Box<Account<'info, ShareVault>>,
+
+    #[account(
+        mut,
+        constraint = share_mint.key() == share_vault.share_mint @ StableFunError::InvalidMint
+    )]
+    pub share_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = collateral_account.key() == share_vault.collateral_account @ StableFunError::InvalidVaultAccount
+    )]
+    pub collateral_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_stablebond_account.mint == collateral_account.mint @ StableFunError::InvalidStablebond,
+        constraint = user_stablebond_account.owner == user.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub user_stablebond_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_share_account.mint == share_mint.key() @ StableFunError::InvalidTokenAccount,
+        constraint = user_share_account.owner == user.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub user_share_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_VAULTS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Deposits `assets` stablebonds and mints the matching shares.
+pub fn deposit_shares(ctx: Context<ShareVaultOperation>, assets: u64) -> Result<()> {
+    require!(assets > 0, StableFunError::InvalidAmount);
+
+    let shares = ShareVault::convert_to_shares(
+        assets,
+        ctx.accounts.collateral_account.amount,
+        ctx.accounts.share_mint.supply,
+    )?;
+    require!(shares > 0, StableFunError::ZeroShares);
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.user_stablebond_account.to_account_info(),
+                to: ctx.accounts.collateral_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        assets,
+    )?;
+
+    let stablecoin_key = ctx.accounts.share_vault.stablecoin_mint;
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::MintTo {
+                mint: ctx.accounts.share_mint.to_account_info(),
+                to: ctx.accounts.user_share_account.to_account_info(),
+                authority: ctx.accounts.share_vault.to_account_info(),
+            },
+            &[&[
+                SHARE_VAULT_SEED,
+                stablecoin_key.as_ref(),
+                &[ctx.accounts.share_vault.bump],
+            ]],
+        ),
+        shares,
+    )?;
+
+    let share_vault = &mut ctx.accounts.share_vault;
+    share_vault.total_deposited = share_vault.total_deposited
+        .checked_add(assets)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+
+    emit!(SharesDeposited {
+        stablecoin_mint: stablecoin_key,
+        user: ctx.accounts.user.key(),
+        assets,
+        shares,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Burns `shares` and pays out their stablebonds, yield included.
+pub fn withdraw_shares(ctx: Context<ShareVaultOperation>, shares: u64) -> Result<()> {
+    require!(shares > 0, StableFunError::InvalidAmount);
+
+    let assets = ShareVault::convert_to_assets(
+        shares,
+        ctx.accounts.collateral_account.amount,
+        ctx.accounts.share_mint.supply,
+    )?;
+    require!(assets > 0, StableFunError::ZeroShares);
+
+    token::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Burn {
+                mint: ctx.accounts.share_mint.to_account_info(),
+                from: ctx.accounts.user_share_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        shares,
+    )?;
+
+    let stablecoin_key = ctx.accounts.share_vault.stablecoin_mint;
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.collateral_account.to_account_info(),
+                to: ctx.accounts.user_stablebond_account.to_account_info(),
+                authority: ctx.accounts.share_vault.to_account_info(),
+            },
+            &[&[
+                SHARE_VAULT_SEED,
+                stablecoin_key.as_ref(),
+                &[ctx.accounts.share_vault.bump],
+            ]],
+        ),
+        assets,
+    )?;
+
+    let share_vault = &mut ctx.accounts.share_vault;
+    share_vault.total_withdrawn = share_vault.total_withdrawn
+        .checked_add(assets)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+
+    emit!(SharesWithdrawn {
+        stablecoin_mint: stablecoin_key,
+        user: ctx.accounts.user.key(),
+        assets,
+        shares,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct SharesDeposited {
+    pub stablecoin_mint: Pubkey,
+    pub user: Pubkey,
+    pub assets: u64,
+    pub shares: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SharesWithdrawn {
+    pub stablecoin_mint: Pubkey,
+    pub user: Pubkey,
+    pub assets: u64,
+    pub shares: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{ProtocolConfig, Snapshot, StablecoinMint, StateAccount};
+use crate::constants::{PROTOCOL_CONFIG_SEED, SNAPSHOT_SEED};
+use crate::error::StableFunError;
+
+#[derive(Accounts)]
+#[instruction(slot: u64)]
+pub struct CommitSnapshot<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Account<'info, StablecoinMint>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Snapshot::LEN,
+        seeds = [SNAPSHOT_SEED, stablecoin_mint.key().as_ref(), &slot.to_le_bytes()],
+        bump
+    )]
+    pub snapshot: Account<'info, Snapshot>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn commit_snapshot(
+    ctx: Context<CommitSnapshot>,
+    slot: u64,
+    merkle_root: [u8; 32],
+    total_balance: u64,
+    holder_count: u32,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(slot <= clock.slot, StableFunError::InvalidSnapshotSlot);
+
+    let snapshot = &mut ctx.accounts.snapshot;
+    snapshot.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    snapshot.authority = ctx.accounts.authority.key();
+    snapshot.slot = slot;
+    snapshot.merkle_root = merkle_root;
+    snapshot.total_balance = total_balance;
+    snapshot.holder_count = holder_count;
+    snapshot.created_at = clock.unix_timestamp;
+    snapshot.bump = ctx.bumps.snapshot;
+
+    emit!(SnapshotCommitted {
+        stablecoin_mint: snapshot.stablecoin_mint,
+        snapshot: snapshot.key(),
+        slot,
+        merkle_root,
+        total_balance,
+        holder_count,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct SnapshotCommitted {
+    pub stablecoin_mint: Pubkey,
+    pub snapshot: Pubkey,
+    pub slot: u64,
+    pub merkle_root: [u8; 32],
+    pub total_balance: u64,
+    pub holder_count: u32,
+    pub timestamp: i64,
+}
@@ -0,0 +1,251 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_lang::system_program;
+
+use crate::state::{
+    ProtocolConfig, Sponsor, StablecoinMint, StateAccount, PAUSE_TRANSFERS, SPONSOR_ALL_ACTIONS,
+    SPONSOR_MINT, SPONSOR_REDEEM, SPONSOR_RELAYED_MINT, SPONSOR_RELAYED_REDEEM,
+};
+use crate::constants::{MAX_SPONSORED_LAMPORTS_PER_TX, PROTOCOL_CONFIG_SEED, SPONSOR_SEED};
+use crate::error::StableFunError;
+use crate::utils::introspection;
+
+#[derive(Accounts)]
+pub struct ConfigureSponsor<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = Sponsor::LEN,
+        seeds = [SPONSOR_SEED, stablecoin_mint.key().as_ref()],
+        bump
+    )]
+    pub sponsor: Box<Account<'info, Sponsor>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Sets which actions are sponsored and the flat refund per transaction.
+/// No actions disables sponsorship without touching the deposit.
+pub fn configure_sponsor(ctx: Context<ConfigureSponsor>, sponsored_actions: u8, lamports_per_tx: u64) -> Result<()> {
+    require!(
+        sponsored_actions & !SPONSOR_ALL_ACTIONS == 0
+            && lamports_per_tx <= MAX_SPONSORED_LAMPORTS_PER_TX,
+        StableFunError::InvalidSponsorConfig
+    );
+
+    let sponsor = &mut ctx.accounts.sponsor;
+    sponsor.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    sponsor.sponsored_actions = sponsored_actions;
+    sponsor.lamports_per_tx = lamports_per_tx;
+    sponsor.bump = ctx.bumps.sponsor;
+
+    emit!(SponsorConfigured {
+        stablecoin_mint: sponsor.stablecoin_mint,
+        sponsored_actions,
+        lamports_per_tx,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FundSponsor<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [SPONSOR_SEED, stablecoin_mint.key().as_ref()],
+        bump = sponsor.bump
+    )]
+    pub sponsor: Box<Account<'info, Sponsor>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_TRANSFERS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn fund_sponsor(ctx: Context<FundSponsor>, lamports: u64) -> Result<()> {
+    require!(lamports > 0, StableFunError::InvalidAmount);
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.authority.to_account_info(),
+                to: ctx.accounts.sponsor.to_account_info(),
+            },
+        ),
+        lamports,
+    )?;
+
+    emit!(SponsorBalanceChanged {
+        stablecoin_mint: ctx.accounts.sponsor.stablecoin_mint,
+        deposited: lamports,
+        withdrawn: 0,
+        balance: ctx.accounts.sponsor.get_lamports(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Takes unused SOL back; the rent-exempt minimum stays on the account.
+pub fn withdraw_sponsor(ctx: Context<FundSponsor>, lamports: u64) -> Result<()> {
+    let sponsor = ctx.accounts.sponsor.to_account_info();
+    let reserved = Rent::get()?.minimum_balance(sponsor.data_len());
+    require!(
+        lamports > 0 && lamports <= sponsor.lamports().saturating_sub(reserved),
+        StableFunError::InsufficientBalance
+    );
+
+    ctx.accounts.sponsor.sub_lamports(lamports)?;
+    ctx.accounts.authority.add_lamports(lamports)?;
+
+    emit!(SponsorBalanceChanged {
+        stablecoin_mint: ctx.accounts.sponsor.stablecoin_mint,
+        deposited: 0,
+        withdrawn: lamports,
+        balance: ctx.accounts.sponsor.get_lamports(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimSponsorship<'info> {
+    /// Paid back for a transaction they submitted and signed
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [SPONSOR_SEED, stablecoin_mint.key().as_ref()],
+        bump = sponsor.bump
+    )]
+    pub sponsor: Box<Account<'info, Sponsor>>,
+
+    /// CHECK: Instructions sysvar, checked by address
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_TRANSFERS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+}
+
+/// Placed after a sponsored mint or redeem of the same coin, refunds the
+/// fee payer. Only the first claim in a transaction pays out.
+pub fn claim_sponsorship(ctx: Context<ClaimSponsorship>) -> Result<()> {
+    let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+    let sponsored_actions = ctx.accounts.sponsor.sponsored_actions;
+
+    require!(
+        !introspection::has_prior_instruction(
+            &ctx.accounts.instructions,
+            &crate::instruction::ClaimSponsorship::DISCRIMINATOR,
+            &stablecoin_key,
+        )?,
+        StableFunError::SponsorshipAlreadyClaimed
+    );
+
+    let mut sponsored = false;
+    for (action, discriminator) in [
+        (SPONSOR_MINT, crate::instruction::Mint::DISCRIMINATOR),
+        (SPONSOR_REDEEM, crate::instruction::Redeem::DISCRIMINATOR),
+        (SPONSOR_REDEEM, crate::instruction::RevealRedeem::DISCRIMINATOR),
+        (SPONSOR_RELAYED_MINT, crate::instruction::RelayedMint::DISCRIMINATOR),
+        (SPONSOR_RELAYED_REDEEM, crate::instruction::RelayedRedeem::DISCRIMINATOR),
+    ] {
+        if sponsored_actions & action != 0
+            && introspection::has_prior_instruction(&ctx.accounts.instructions, &discriminator, &stablecoin_key)?
+        {
+            sponsored = true;
+            break;
+        }
+    }
+    require!(sponsored, StableFunError::NotSponsored);
+
+    let sponsor_info = ctx.accounts.sponsor.to_account_info();
+    let reserved = Rent::get()?.minimum_balance(sponsor_info.data_len());
+    let lamports = ctx.accounts.sponsor.reimbursement(sponsor_info.lamports(), reserved);
+    require!(lamports > 0, StableFunError::SponsorDepleted);
+
+    ctx.accounts.sponsor.sub_lamports(lamports)?;
+    ctx.accounts.fee_payer.add_lamports(lamports)?;
+
+    let sponsor = &mut ctx.accounts.sponsor;
+    sponsor.total_reimbursed = sponsor.total_reimbursed
+        .checked_add(lamports)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    sponsor.sponsored_count = sponsor.sponsored_count
+        .checked_add(1)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+
+    emit!(SponsorshipClaimed {
+        stablecoin_mint: stablecoin_key,
+        fee_payer: ctx.accounts.fee_payer.key(),
+        lamports,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct SponsorConfigured {
+    pub stablecoin_mint: Pubkey,
+    pub sponsored_actions: u8,
+    pub lamports_per_tx: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SponsorBalanceChanged {
+    pub stablecoin_mint: Pubkey,
+    pub deposited: u64,
+    pub withdrawn: u64,
+    pub balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SponsorshipClaimed {
+    pub stablecoin_mint: Pubkey,
+    pub fee_payer: Pubkey,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
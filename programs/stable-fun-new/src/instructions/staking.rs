@@ -0,0 +1,550 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{self, TokenInterface};
+
+use crate::state::{
+    ProtocolConfig, RewardStream, StablecoinMint, StablecoinVault, StakePosition, StakingPool,
+    StateAccount, SystemAccounting, PAUSE_STAKING,
+};
+use crate::constants::{
+    ACCOUNTING_SEED, MAX_STAKING_REWARDS, MINT_AUTHORITY_SEED, PROTOCOL_CONFIG_SEED,
+    STAKE_POSITION_SEED, STAKING_POOL_SEED, STAKING_REWARD_VAULT_SEED, STAKING_VAULT_SEED,
+    VAULT_SEED,
+};
+use crate::error::StableFunError;
+use crate::utils::validation::ValidationService;
+
+#[derive(Accounts)]
+pub struct InitStakingPool<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        has_one = admin @ StableFunError::UnauthorizedProtocolAdmin
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = StakingPool::LEN,
+        seeds = [STAKING_POOL_SEED],
+        bump
+    )]
+    pub staking_pool: Box<Account<'info, StakingPool>>,
+
+    /// The protocol token
+    pub stake_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [STAKING_VAULT_SEED],
+        bump,
+        token::mint = stake_mint,
+        token::authority = staking_pool
+    )]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Opens protocol token staking. Stakers wait `cooldown_seconds` between
+/// requesting an unstake and getting their tokens back.
+pub fn init_staking_pool(ctx: Context<InitStakingPool>, cooldown_seconds: i64) -> Result<()> {
+    require!(cooldown_seconds >= 0, StableFunError::InvalidAmount);
+
+    let staking_pool = &mut ctx.accounts.staking_pool;
+    staking_pool.stake_mint = ctx.accounts.stake_mint.key();
+    staking_pool.stake_vault = ctx.accounts.stake_vault.key();
+    staking_pool.cooldown_seconds = cooldown_seconds;
+    staking_pool.bump = ctx.bumps.staking_pool;
+
+    emit!(StakingPoolInitialized {
+        stake_mint: staking_pool.stake_mint,
+        cooldown_seconds,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AddStakingReward<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        has_one = admin @ StableFunError::UnauthorizedProtocolAdmin
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_POOL_SEED],
+        bump = staking_pool.bump
+    )]
+    pub staking_pool: Box<Account<'info, StakingPool>>,
+
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [STAKING_REWARD_VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = staking_pool,
+        token::token_program = stablecoin_token_program
+    )]
+    pub reward_vault: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// Token program owning the stablecoin mint (SPL Token or Token-2022)
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Starts sharing a stablecoin's fee revenue with stakers.
+pub fn add_staking_reward(ctx: Context<AddStakingReward>) -> Result<()> {
+    let staking_pool = &mut ctx.accounts.staking_pool;
+    require!(
+        staking_pool.rewards.len() < MAX_STAKING_REWARDS,
+        StableFunError::InvalidRewardStream
+    );
+    staking_pool.rewards.push(RewardStream {
+        stablecoin_mint: ctx.accounts.stablecoin_mint.key(),
+        reward_vault: ctx.accounts.reward_vault.key(),
+        bump: ctx.bumps.reward_vault,
+        ..Default::default()
+    });
+
+    emit!(StakingRewardAdded {
+        stablecoin_mint: ctx.accounts.stablecoin_mint.key(),
+        reward_vault: ctx.accounts.reward_vault.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct StakeOperation<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_POOL_SEED],
+        bump = staking_pool.bump
+    )]
+    pub staking_pool: Box<Account<'info, StakingPool>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = StakePosition::LEN,
+        seeds = [STAKE_POSITION_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub stake_position: Box<Account<'info, StakePosition>>,
+
+    #[account(
+        mut,
+        constraint = stake_vault.key() == staking_pool.stake_vault @ StableFunError::InvalidTokenAccount
+    )]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_stake_account.mint == staking_pool.stake_mint @ StableFunError::InvalidTokenAccount,
+        constraint = user_stake_account.owner == user.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub user_stake_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_STAKING) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Stakes `amount` protocol tokens; they earn from the next distribution on.
+pub fn stake(ctx: Context<StakeOperation>, amount: u64) -> Result<()> {
+    require!(amount > 0, StableFunError::InvalidAmount);
+
+    let stake_position = &mut ctx.accounts.stake_position;
+    if !stake_position.is_initialized() {
+        stake_position.owner = ctx.accounts.user.key();
+        stake_position.bump = ctx.bumps.stake_position;
+    }
+    stake_position.settle(&ctx.accounts.staking_pool)?;
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.user_stake_account.to_account_info(),
+                to: ctx.accounts.stake_vault.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let stake_position = &mut ctx.accounts.stake_position;
+    stake_position.staked = stake_position.staked
+        .checked_add(amount)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    let staking_pool = &mut ctx.accounts.staking_pool;
+    staking_pool.total_staked = staking_pool.total_staked
+        .checked_add(amount)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+
+    emit!(Staked {
+        user: ctx.accounts.user.key(),
+        amount,
+        staked: stake_position.staked,
+        total_staked: staking_pool.total_staked,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Stops `amount` staked tokens from earning and starts their cooldown. A
+/// new request restarts the cooldown of everything already cooling down.
+pub fn request_unstake(ctx: Context<StakeOperation>, amount: u64) -> Result<()> {
+    require!(amount > 0, StableFunError::InvalidAmount);
+
+    let staking_pool = &mut ctx.accounts.staking_pool;
+    let stake_position = &mut ctx.accounts.stake_position;
+    require!(amount <= stake_position.staked, StableFunError::InsufficientBalance);
+    stake_position.settle(staking_pool)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    stake_position.staked -= amount;
+    stake_position.cooling_amount += amount;
+    stake_position.cooldown_ends_at = now.saturating_add(staking_pool.cooldown_seconds);
+    staking_pool.total_staked -= amount;
+
+    emit!(UnstakeRequested {
+        user: ctx.accounts.user.key(),
+        amount,
+        cooling_amount: stake_position.cooling_amount,
+        cooldown_ends_at: stake_position.cooldown_ends_at,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+/// Withdraws every token whose cooldown has run out.
+pub fn unstake(ctx: Context<StakeOperation>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let amount = ctx.accounts.stake_position.cooling_amount;
+    require!(amount > 0, StableFunError::InvalidAmount);
+    require!(
+        now >= ctx.accounts.stake_position.cooldown_ends_at,
+        StableFunError::StakeCooldownActive
+    );
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.stake_vault.to_account_info(),
+                to: ctx.accounts.user_stake_account.to_account_info(),
+                authority: ctx.accounts.staking_pool.to_account_info(),
+            },
+            &[&[STAKING_POOL_SEED, &[ctx.accounts.staking_pool.bump]]],
+        ),
+        amount,
+    )?;
+    ctx.accounts.stake_position.cooling_amount = 0;
+
+    emit!(Unstaked {
+        user: ctx.accounts.user.key(),
+        amount,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimStakingRewards<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [STAKING_POOL_SEED],
+        bump = staking_pool.bump
+    )]
+    pub staking_pool: Box<Account<'info, StakingPool>>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_POSITION_SEED, user.key().as_ref()],
+        bump = stake_position.bump
+    )]
+    pub stake_position: Box<Account<'info, StakePosition>>,
+
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_REWARD_VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = staking_pool.reward_vault_bump(&stablecoin_mint.key())
+    )]
+    pub reward_vault: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == token_mint.key() @ StableFunError::InvalidTokenAccount,
+        constraint = user_token_account.owner == user.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub user_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_STAKING) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    /// Token program owning the stablecoin mint (SPL Token or Token-2022)
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Pays out everything the staker has earned in one stablecoin.
+pub fn claim_staking_rewards(ctx: Context<ClaimStakingRewards>) -> Result<()> {
+    let staking_pool = &ctx.accounts.staking_pool;
+    let index = staking_pool.reward_index(&ctx.accounts.stablecoin_mint.key())?;
+    let stake_position = &mut ctx.accounts.stake_position;
+    stake_position.settle(staking_pool)?;
+    let amount = stake_position.take_reward(index);
+    require!(amount > 0, StableFunError::InvalidAmount);
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.stablecoin_token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.reward_vault.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: staking_pool.to_account_info(),
+            },
+            &[&[STAKING_POOL_SEED, &[staking_pool.bump]]],
+        ),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    emit!(StakingRewardsClaimed {
+        user: ctx.accounts.user.key(),
+        stablecoin_mint: ctx.accounts.stablecoin_mint.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct DistributeStakingRewards<'info> {
+    #[account(mut)]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_POOL_SEED],
+        bump = staking_pool.bump
+    )]
+    pub staking_pool: Box<Account<'info, StakingPool>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        mut,
+        seeds = [ACCOUNTING_SEED, stablecoin_mint.key().as_ref()],
+        bump = accounting.bump
+    )]
+    pub accounting: Box<Account<'info, SystemAccounting>>,
+
+    #[account(
+        mut,
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_REWARD_VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = staking_pool.reward_vault_bump(&stablecoin_mint.key())
+    )]
+    pub reward_vault: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// CHECK: PDA used as mint authority
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, stablecoin_mint.key().as_ref()],
+        bump = stablecoin_mint.mint_authority_bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_STAKING) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    /// Token program owning the stablecoin mint (SPL Token or Token-2022)
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Permissionless: pays stakers the protocol's `staking_fee_share_bps` of
+/// the fees a coin collected since the last distribution. Like savings
+/// harvests, the coins are minted against surplus collateral already in the
+/// vault, within the issuer's surplus buffer and the supply cap.
+pub fn distribute_staking_rewards(ctx: Context<DistributeStakingRewards>) -> Result<()> {
+    let index = ctx.accounts.staking_pool.reward_index(&ctx.accounts.stablecoin_mint.key())?;
+    require!(ctx.accounts.staking_pool.total_staked > 0, StableFunError::InvalidAmount);
+
+    let share = ctx.accounts.accounting.staking_share(ctx.accounts.protocol_config.staking_fee_share_bps);
+    let stablecoin_mint = &ctx.accounts.stablecoin_mint;
+    let supply = stablecoin_mint.current_supply;
+    let max_mintable = stablecoin_mint.settings.max_supply.saturating_sub(supply);
+    let available = ctx.accounts.accounting.withdrawable(stablecoin_mint.settings.surplus_buffer);
+    let amount = share.min(available).min(stablecoin_mint.to_underlying_amount(max_mintable)?);
+    let raw_amount = stablecoin_mint.to_raw_amount(amount, false)?;
+
+    let stablecoin_key = stablecoin_mint.key();
+    if raw_amount > 0 {
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.stablecoin_token_program.to_account_info(),
+                token_interface::MintTo {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.reward_vault.to_account_info(),
+                    authority: ctx.accounts.mint_authority.to_account_info(),
+                },
+                &[&[
+                    MINT_AUTHORITY_SEED,
+                    stablecoin_key.as_ref(),
+                    &[stablecoin_mint.mint_authority_bump],
+                ]],
+            ),
+            raw_amount,
+        )?;
+
+        ctx.accounts.accounting.surplus -= amount;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.total_value_locked = vault
+            .total_value_locked
+            .checked_add(amount)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        // No fresh price here; re-rate at the last one
+        let ratio_price = vault.ratio_price;
+        ValidationService::update_collateral_ratio(vault, ratio_price, ctx.accounts.token_mint.decimals)?;
+
+        let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
+        stablecoin_mint.current_supply += raw_amount;
+        stablecoin_mint.last_updated = Clock::get()?.unix_timestamp;
+
+        ctx.accounts.staking_pool.distribute(index, raw_amount)?;
+    }
+
+    // Revenue that could not be paid out is forfeited rather than carried over
+    let accounting = &mut ctx.accounts.accounting;
+    accounting.staking_checkpoint = accounting.fees_collected;
+
+    emit!(StakingRewardsDistributed {
+        stablecoin_mint: stablecoin_key,
+        amount: raw_amount,
+        reward_per_token: ctx.accounts.staking_pool.rewards[index].reward_per_token,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct StakingPoolInitialized {
+    pub stake_mint: Pubkey,
+    pub cooldown_seconds: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakingRewardAdded {
+    pub stablecoin_mint: Pubkey,
+    pub reward_vault: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct Staked {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub staked: u64,
+    pub total_staked: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UnstakeRequested {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub cooling_amount: u64,
+    pub cooldown_ends_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct Unstaked {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakingRewardsClaimed {
+    pub user: Pubkey,
+    pub stablecoin_mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakingRewardsDistributed {
+    pub stablecoin_mint: Pubkey,
+    /// Raw stablecoin units paid into the reward vault
+    pub amount: u64,
+    pub reward_per_token: u128,
+    pub timestamp: i64,
+}
@@ -0,0 +1,352 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+
+use crate::state::{ProtocolConfig, StablecoinMint, StateAccount, Stream, PAUSE_TRANSFERS};
+use crate::constants::{PROTOCOL_CONFIG_SEED, STREAM_SEED, STREAM_VAULT_SEED};
+use crate::error::StableFunError;
+
+#[derive(Accounts)]
+#[instruction(stream_id: u64)]
+pub struct CreateStream<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    /// CHECK: Only recorded as the stream's beneficiary
+    pub recipient: UncheckedAccount<'info>,
+
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        init,
+        payer = sender,
+        space = Stream::LEN,
+        seeds = [STREAM_SEED, stablecoin_mint.key().as_ref(), sender.key().as_ref(), &stream_id.to_le_bytes()],
+        bump
+    )]
+    pub stream: Box<Account<'info, Stream>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = sender,
+        seeds = [STREAM_VAULT_SEED, stream.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = stream,
+        token::token_program = token_program
+    )]
+    pub stream_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = sender_token_account.mint == token_mint.key() @ StableFunError::InvalidTokenAccount,
+        constraint = sender_token_account.owner == sender.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub sender_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_TRANSFERS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Locks `total_amount` stablecoins to be released linearly to `recipient`.
+pub fn create_stream(
+    ctx: Context<CreateStream>,
+    stream_id: u64,
+    total_amount: u64,
+    start_time: i64,
+    end_time: i64,
+) -> Result<()> {
+    require!(total_amount > 0, StableFunError::InvalidAmount);
+    require!(
+        end_time > start_time && start_time >= 0,
+        StableFunError::InvalidStreamSchedule
+    );
+
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.sender_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.stream_token_account.to_account_info(),
+                authority: ctx.accounts.sender.to_account_info(),
+            },
+        ),
+        total_amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    let stream = &mut ctx.accounts.stream;
+    stream.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    stream.sender = ctx.accounts.sender.key();
+    stream.recipient = ctx.accounts.recipient.key();
+    stream.stream_id = stream_id;
+    stream.token_account = ctx.accounts.stream_token_account.key();
+    stream.total_amount = total_amount;
+    stream.start_time = start_time;
+    stream.end_time = end_time;
+    stream.bump = ctx.bumps.stream;
+
+    emit!(StreamCreated {
+        stablecoin_mint: stream.stablecoin_mint,
+        stream: stream.key(),
+        sender: stream.sender,
+        recipient: stream.recipient,
+        stream_id,
+        total_amount,
+        start_time,
+        end_time,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawStream<'info> {
+    pub recipient: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STREAM_SEED, stream.stablecoin_mint.as_ref(), stream.sender.as_ref(), &stream.stream_id.to_le_bytes()],
+        bump = stream.bump,
+        constraint = stream.recipient == recipient.key() @ StableFunError::UnauthorizedStreamRecipient
+    )]
+    pub stream: Box<Account<'info, Stream>>,
+
+    #[account(
+        constraint = stablecoin_mint.key() == stream.stablecoin_mint @ StableFunError::InvalidMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = stream_token_account.key() == stream.token_account @ StableFunError::InvalidTokenAccount
+    )]
+    pub stream_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = recipient_token_account.mint == token_mint.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub recipient_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_TRANSFERS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Pays the recipient everything released so far.
+pub fn withdraw(ctx: Context<WithdrawStream>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let amount = ctx.accounts.stream.withdrawable(now);
+    require!(amount > 0, StableFunError::InvalidAmount);
+    ctx.accounts.stream.record_withdrawal(amount)?;
+
+    let stream = &ctx.accounts.stream;
+    transfer_from_stream(
+        stream,
+        &ctx.accounts.token_program,
+        &ctx.accounts.stream_token_account,
+        &ctx.accounts.token_mint,
+        ctx.accounts.recipient_token_account.to_account_info(),
+        amount,
+    )?;
+
+    emit!(StreamWithdrawn {
+        stream: stream.key(),
+        recipient: stream.recipient,
+        amount,
+        withdrawn_amount: stream.withdrawn_amount,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelStream<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    #[account(
+        mut,
+        close = sender,
+        seeds = [STREAM_SEED, stream.stablecoin_mint.as_ref(), sender.key().as_ref(), &stream.stream_id.to_le_bytes()],
+        bump = stream.bump
+    )]
+    pub stream: Box<Account<'info, Stream>>,
+
+    #[account(
+        constraint = stablecoin_mint.key() == stream.stablecoin_mint @ StableFunError::InvalidMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = stream_token_account.key() == stream.token_account @ StableFunError::InvalidTokenAccount
+    )]
+    pub stream_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = recipient_token_account.mint == token_mint.key() @ StableFunError::InvalidTokenAccount,
+        constraint = recipient_token_account.owner == stream.recipient @ StableFunError::InvalidTokenAccount
+    )]
+    pub recipient_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = sender_token_account.mint == token_mint.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub sender_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_TRANSFERS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Stops a stream: whatever has already been released goes to the recipient,
+/// the rest back to the sender, and the escrow is closed.
+pub fn cancel_stream(ctx: Context<CancelStream>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let stream = &ctx.accounts.stream;
+    let to_recipient = stream.withdrawable(now);
+    let refund = ctx.accounts.stream_token_account.amount.saturating_sub(to_recipient);
+
+    for (destination, amount) in [
+        (ctx.accounts.recipient_token_account.to_account_info(), to_recipient),
+        (ctx.accounts.sender_token_account.to_account_info(), refund),
+    ] {
+        if amount > 0 {
+            transfer_from_stream(
+                stream,
+                &ctx.accounts.token_program,
+                &ctx.accounts.stream_token_account,
+                &ctx.accounts.token_mint,
+                destination,
+                amount,
+            )?;
+        }
+    }
+
+    let stream_id = stream.stream_id.to_le_bytes();
+    token_interface::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token_interface::CloseAccount {
+            account: ctx.accounts.stream_token_account.to_account_info(),
+            destination: ctx.accounts.sender.to_account_info(),
+            authority: stream.to_account_info(),
+        },
+        &[&[
+            STREAM_SEED,
+            stream.stablecoin_mint.as_ref(),
+            stream.sender.as_ref(),
+            &stream_id,
+            &[stream.bump],
+        ]],
+    ))?;
+
+    emit!(StreamCancelled {
+        stream: stream.key(),
+        sender: stream.sender,
+        recipient: stream.recipient,
+        paid_to_recipient: to_recipient,
+        refunded: refund,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+fn transfer_from_stream<'info>(
+    stream: &Account<'info, Stream>,
+    token_program: &Interface<'info, TokenInterface>,
+    stream_token_account: &InterfaceAccount<'info, TokenAccount>,
+    token_mint: &InterfaceAccount<'info, Mint>,
+    destination: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let stream_id = stream.stream_id.to_le_bytes();
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: stream_token_account.to_account_info(),
+                mint: token_mint.to_account_info(),
+                to: destination,
+                authority: stream.to_account_info(),
+            },
+            &[&[
+                STREAM_SEED,
+                stream.stablecoin_mint.as_ref(),
+                stream.sender.as_ref(),
+                &stream_id,
+                &[stream.bump],
+            ]],
+        ),
+        amount,
+        token_mint.decimals,
+    )
+}
+
+#[event]
+pub struct StreamCreated {
+    pub stablecoin_mint: Pubkey,
+    pub stream: Pubkey,
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+    pub stream_id: u64,
+    pub total_amount: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+#[event]
+pub struct StreamWithdrawn {
+    pub stream: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub withdrawn_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StreamCancelled {
+    pub stream: Pubkey,
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+    pub paid_to_recipient: u64,
+    pub refunded: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,264 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+
+use crate::state::{ProtocolConfig, StablecoinMint, StateAccount, Subscription, PAUSE_TRANSFERS};
+use crate::constants::{MIN_SUBSCRIPTION_INTERVAL, PROTOCOL_CONFIG_SEED, SUBSCRIPTION_SEED};
+use crate::error::StableFunError;
+
+#[derive(Accounts)]
+pub struct CreateSubscription<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Only recorded as the payee
+    pub merchant: UncheckedAccount<'info>,
+
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = Subscription::LEN,
+        seeds = [SUBSCRIPTION_SEED, stablecoin_mint.key().as_ref(), payer.key().as_ref(), merchant.key().as_ref()],
+        bump
+    )]
+    pub subscription: Box<Account<'info, Subscription>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = payer_token_account.mint == token_mint.key() @ StableFunError::InvalidTokenAccount,
+        constraint = payer_token_account.owner == payer.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub payer_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_TRANSFERS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Starts billing `amount` every `interval` seconds, first charge due
+/// immediately. `allowance` is delegated to the subscription and caps the
+/// total it can ever pull; a token account has a single delegate, so this
+/// replaces any earlier approval on `payer_token_account`.
+pub fn create_subscription(ctx: Context<CreateSubscription>, amount: u64, interval: i64, allowance: u64) -> Result<()> {
+    require!(amount > 0 && allowance >= amount, StableFunError::InvalidAmount);
+    require!(interval >= MIN_SUBSCRIPTION_INTERVAL, StableFunError::InvalidSubscription);
+
+    token_interface::approve(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::Approve {
+                to: ctx.accounts.payer_token_account.to_account_info(),
+                delegate: ctx.accounts.subscription.to_account_info(),
+                authority: ctx.accounts.payer.to_account_info(),
+            },
+        ),
+        allowance,
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let subscription = &mut ctx.accounts.subscription;
+    subscription.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    subscription.payer = ctx.accounts.payer.key();
+    subscription.merchant = ctx.accounts.merchant.key();
+    subscription.payer_token_account = ctx.accounts.payer_token_account.key();
+    subscription.amount = amount;
+    subscription.interval = interval;
+    subscription.next_billing_at = now;
+    subscription.bump = ctx.bumps.subscription;
+
+    emit!(SubscriptionCreated {
+        stablecoin_mint: subscription.stablecoin_mint,
+        subscription: subscription.key(),
+        payer: subscription.payer,
+        merchant: subscription.merchant,
+        amount,
+        interval,
+        allowance,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CollectSubscription<'info> {
+    #[account(
+        mut,
+        seeds = [SUBSCRIPTION_SEED, subscription.stablecoin_mint.as_ref(), subscription.payer.as_ref(), subscription.merchant.as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Box<Account<'info, Subscription>>,
+
+    #[account(
+        constraint = stablecoin_mint.key() == subscription.stablecoin_mint @ StableFunError::InvalidMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = payer_token_account.key() == subscription.payer_token_account @ StableFunError::InvalidTokenAccount
+    )]
+    pub payer_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = merchant_token_account.mint == token_mint.key() @ StableFunError::InvalidTokenAccount,
+        constraint = merchant_token_account.owner == subscription.merchant @ StableFunError::InvalidTokenAccount
+    )]
+    pub merchant_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_TRANSFERS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Permissionless crank: charges one due period to the merchant.
+pub fn collect(ctx: Context<CollectSubscription>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let billed_from = ctx.accounts.subscription.next_billing_at;
+    ctx.accounts.subscription.advance_period(now)?;
+
+    let subscription = &ctx.accounts.subscription;
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.payer_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.merchant_token_account.to_account_info(),
+                authority: subscription.to_account_info(),
+            },
+            &[&[
+                SUBSCRIPTION_SEED,
+                subscription.stablecoin_mint.as_ref(),
+                subscription.payer.as_ref(),
+                subscription.merchant.as_ref(),
+                &[subscription.bump],
+            ]],
+        ),
+        subscription.amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    emit!(SubscriptionCharged {
+        stablecoin_mint: subscription.stablecoin_mint,
+        subscription: subscription.key(),
+        payer: subscription.payer,
+        merchant: subscription.merchant,
+        amount: subscription.amount,
+        period: subscription.periods_collected,
+        period_start: billed_from,
+        period_end: billed_from.saturating_add(subscription.interval),
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelSubscription<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        close = payer,
+        seeds = [SUBSCRIPTION_SEED, subscription.stablecoin_mint.as_ref(), payer.key().as_ref(), subscription.merchant.as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Box<Account<'info, Subscription>>,
+
+    #[account(
+        mut,
+        constraint = payer_token_account.key() == subscription.payer_token_account @ StableFunError::InvalidTokenAccount
+    )]
+    pub payer_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_TRANSFERS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Ends the subscription and revokes whatever allowance is left.
+pub fn cancel_subscription(ctx: Context<CancelSubscription>) -> Result<()> {
+    if ctx.accounts.payer_token_account.delegate == Some(ctx.accounts.subscription.key()).into() {
+        token_interface::revoke(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::Revoke {
+                source: ctx.accounts.payer_token_account.to_account_info(),
+                authority: ctx.accounts.payer.to_account_info(),
+            },
+        ))?;
+    }
+
+    let subscription = &ctx.accounts.subscription;
+    emit!(SubscriptionCancelled {
+        subscription: subscription.key(),
+        payer: subscription.payer,
+        merchant: subscription.merchant,
+        periods_collected: subscription.periods_collected,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct SubscriptionCreated {
+    pub stablecoin_mint: Pubkey,
+    pub subscription: Pubkey,
+    pub payer: Pubkey,
+    pub merchant: Pubkey,
+    pub amount: u64,
+    pub interval: i64,
+    pub allowance: u64,
+}
+
+#[event]
+pub struct SubscriptionCharged {
+    pub stablecoin_mint: Pubkey,
+    pub subscription: Pubkey,
+    pub payer: Pubkey,
+    pub merchant: Pubkey,
+    pub amount: u64,
+    /// 1-based count of periods billed so far
+    pub period: u64,
+    pub period_start: i64,
+    pub period_end: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SubscriptionCancelled {
+    pub subscription: Pubkey,
+    pub payer: Pubkey,
+    pub merchant: Pubkey,
+    pub periods_collected: u64,
+    pub timestamp: i64,
+}
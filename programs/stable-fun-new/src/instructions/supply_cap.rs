@@ -0,0 +1,222 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use switchboard_solana::AggregatorAccountData;
+
+use crate::state::{
+    ElasticSupplyCap, IssuerBond, ProtocolConfig, ReserveReport, StablecoinMint, StablecoinVault,
+    StateAccount,
+};
+use crate::constants::{
+    ELASTIC_SUPPLY_CAP_SEED, ISSUER_BOND_SEED, PROTOCOL_CONFIG_SEED, RESERVE_REPORT_SEED, VAULT_SEED,
+};
+use crate::error::StableFunError;
+use crate::utils::oracle::OracleService;
+use crate::utils::math;
+
+#[derive(Accounts)]
+pub struct SetElasticSupplyCap<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = ElasticSupplyCap::LEN,
+        seeds = [ELASTIC_SUPPLY_CAP_SEED, stablecoin_mint.key().as_ref()],
+        bump
+    )]
+    pub elastic_supply_cap: Box<Account<'info, ElasticSupplyCap>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates or reconfigures the elastic supply cap; the next adjustment uses
+/// the new rules.
+pub fn set_elastic_supply_cap(
+    ctx: Context<SetElasticSupplyCap>,
+    growth_bps: u16,
+    shrink_bps: u16,
+    grow_above_ratio_bps: u16,
+    shrink_below_ratio_bps: u16,
+    min_cap: u64,
+    max_cap: u64,
+) -> Result<()> {
+    ElasticSupplyCap::validate_params(
+        growth_bps,
+        shrink_bps,
+        grow_above_ratio_bps,
+        shrink_below_ratio_bps,
+        min_cap,
+        max_cap,
+    )?;
+
+    let elastic_supply_cap = &mut ctx.accounts.elastic_supply_cap;
+    elastic_supply_cap.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    elastic_supply_cap.growth_bps = growth_bps;
+    elastic_supply_cap.shrink_bps = shrink_bps;
+    elastic_supply_cap.grow_above_ratio_bps = grow_above_ratio_bps;
+    elastic_supply_cap.shrink_below_ratio_bps = shrink_below_ratio_bps;
+    elastic_supply_cap.min_cap = min_cap;
+    elastic_supply_cap.max_cap = max_cap;
+    elastic_supply_cap.bump = ctx.bumps.elastic_supply_cap;
+
+    emit!(ElasticSupplyCapUpdated {
+        stablecoin_mint: elastic_supply_cap.stablecoin_mint,
+        growth_bps,
+        shrink_bps,
+        grow_above_ratio_bps,
+        shrink_below_ratio_bps,
+        min_cap,
+        max_cap,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AdjustSupplyCap<'info> {
+    #[account(mut)]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        mut,
+        seeds = [ELASTIC_SUPPLY_CAP_SEED, stablecoin_mint.key().as_ref()],
+        bump = elastic_supply_cap.bump
+    )]
+    pub elastic_supply_cap: Box<Account<'info, ElasticSupplyCap>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    /// Puts the coin in the reserve-attested supply tier when fresh and fully backed
+    #[account(
+        seeds = [RESERVE_REPORT_SEED, stablecoin_mint.key().as_ref()],
+        bump = reserve_report.bump
+    )]
+    pub reserve_report: Option<Box<Account<'info, ReserveReport>>>,
+
+    /// Lets the cap grow past the protocol's bond threshold
+    #[account(
+        seeds = [ISSUER_BOND_SEED, stablecoin_mint.key().as_ref()],
+        bump = issuer_bond.bump
+    )]
+    pub issuer_bond: Option<Box<Account<'info, IssuerBond>>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The Switchboard V3 aggregator account
+    #[account(
+        constraint = price_feed.key() == stablecoin_mint.price_feed @ StableFunError::InvalidOracle
+    )]
+    pub price_feed: AccountLoader<'info, AggregatorAccountData>,
+
+    /// FX feed composed with the bond NAV `price_feed`, on coins that set one
+    #[account(
+        constraint = fx_feed.key() == stablecoin_mint.fx_feed @ StableFunError::InvalidOracle
+    )]
+    pub fx_feed: Option<AccountLoader<'info, AggregatorAccountData>>,
+}
+
+/// Permissionless crank, once per epoch: prices the collateral and moves
+/// `max_supply` by the elastic rules for the resulting ratio.
+pub fn adjust_supply_cap(ctx: Context<AdjustSupplyCap>) -> Result<()> {
+    let clock = Clock::get()?;
+    let elastic_supply_cap = &mut ctx.accounts.elastic_supply_cap;
+    require!(
+        clock.epoch > elastic_supply_cap.last_epoch,
+        StableFunError::SupplyCapAlreadyAdjusted
+    );
+
+    let oracle_price = OracleService::collateral_price(
+        &ctx.accounts.stablecoin_mint,
+        &ctx.accounts.price_feed,
+        ctx.accounts.fx_feed.as_ref(),
+    )?;
+    let vault = &ctx.accounts.vault;
+    let collateral_value = math::calculate_collateral_value(
+        vault.total_collateral,
+        oracle_price,
+        ctx.accounts.token_mint.decimals,
+    )?;
+    let ratio_bps = math::collateral_ratio_bps(collateral_value, vault.total_value_locked);
+
+    let tier_cap = ctx.accounts.protocol_config.supply_cap(
+        ctx.accounts.reserve_report.as_deref().map(|report| &**report),
+        clock.unix_timestamp,
+    );
+    let bond_cap = ctx.accounts.protocol_config.bonded_supply_cap(
+        ctx.accounts.issuer_bond.as_deref().map(|bond| &**bond),
+    );
+    let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
+    let previous_cap = stablecoin_mint.settings.max_supply;
+    let new_cap = elastic_supply_cap.next_cap(
+        previous_cap,
+        ratio_bps,
+        stablecoin_mint.current_supply,
+        elastic_supply_cap.max_cap.min(tier_cap).min(bond_cap),
+    );
+    stablecoin_mint.settings.max_supply = new_cap;
+    stablecoin_mint.last_updated = clock.unix_timestamp;
+    elastic_supply_cap.last_epoch = clock.epoch;
+
+    emit!(SupplyCapAdjusted {
+        stablecoin_mint: stablecoin_mint.key(),
+        epoch: clock.epoch,
+        ratio_bps: ratio_bps.unwrap_or(0),
+        previous_cap,
+        new_cap,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ElasticSupplyCapUpdated {
+    pub stablecoin_mint: Pubkey,
+    pub growth_bps: u16,
+    pub shrink_bps: u16,
+    pub grow_above_ratio_bps: u16,
+    pub shrink_below_ratio_bps: u16,
+    pub min_cap: u64,
+    pub max_cap: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SupplyCapAdjusted {
+    pub stablecoin_mint: Pubkey,
+    pub epoch: u64,
+    /// Collateral ratio the adjustment used (0 without debt)
+    pub ratio_bps: u64,
+    pub previous_cap: u64,
+    pub new_cap: u64,
+    pub timestamp: i64,
+}
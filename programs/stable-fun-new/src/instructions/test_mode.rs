@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenInterface};
+
+use crate::state::{FreezeAuthorityMode, ProtocolConfig, StablecoinMint};
+use crate::constants::{FREEZE_AUTHORITY_SEED, MINT_AUTHORITY_SEED, PROTOCOL_CONFIG_SEED};
+use crate::error::StableFunError;
+use super::initialize::{create_token_mint, TokenExtensionParams, TokenMintAccounts};
+
+#[derive(Accounts)]
+pub struct PromoteToProduction<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint,
+        constraint = stablecoin_mint.test_mode @ StableFunError::NotInTestMode
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    /// The non-transferable test-mode mint being replaced
+    #[account(
+        constraint = old_token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub old_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// New mint keypair for the production coin
+    #[account(mut)]
+    pub new_token_mint: Signer<'info>,
+
+    /// CHECK: PDA used as mint authority
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, stablecoin_mint.key().as_ref()],
+        bump = stablecoin_mint.mint_authority_bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// CHECK: PDA used as freeze authority in `FreezeAuthorityMode::Program`
+    #[account(
+        seeds = [FREEZE_AUTHORITY_SEED, stablecoin_mint.key().as_ref()],
+        bump = stablecoin_mint.freeze_authority_bump
+    )]
+    pub freeze_authority: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub stablecoin_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// One-way switch out of test mode. The NonTransferable extension cannot be
+/// removed from a mint, so the coin is moved to a freshly created mint; this
+/// is only allowed while nothing has been issued on the test mint.
+pub fn promote_to_production(
+    ctx: Context<PromoteToProduction>,
+    token_extensions: TokenExtensionParams,
+) -> Result<()> {
+    require!(
+        ctx.accounts.stablecoin_mint.current_supply == 0 && ctx.accounts.old_token_mint.supply == 0,
+        StableFunError::PromotionBlockedBySupply
+    );
+    require!(
+        !token_extensions.non_transferable,
+        StableFunError::NonTransferableProductionMint
+    );
+
+    let freeze_authority = match ctx.accounts.stablecoin_mint.freeze_authority_mode {
+        FreezeAuthorityMode::None => None,
+        FreezeAuthorityMode::Program => Some(ctx.accounts.freeze_authority.key()),
+        FreezeAuthorityMode::Issuer => Some(ctx.accounts.authority.key()),
+    };
+    create_token_mint(
+        TokenMintAccounts {
+            payer: ctx.accounts.authority.to_account_info(),
+            token_mint: ctx.accounts.new_token_mint.to_account_info(),
+            token_program: ctx.accounts.stablecoin_token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        },
+        &token_extensions,
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.mint_authority.key(),
+        freeze_authority.as_ref(),
+    )?;
+
+    let clock = Clock::get()?;
+    let stablecoin_mint = &mut ctx.accounts.stablecoin_mint;
+    let old_token_mint = stablecoin_mint.token_mint;
+    stablecoin_mint.token_mint = ctx.accounts.new_token_mint.key();
+    stablecoin_mint.name = StablecoinMint::strip_watermark(&stablecoin_mint.name).to_string();
+    stablecoin_mint.test_mode = false;
+    stablecoin_mint.last_updated = clock.unix_timestamp;
+
+    emit!(PromotedToProduction {
+        stablecoin_mint: stablecoin_mint.key(),
+        old_token_mint,
+        new_token_mint: stablecoin_mint.token_mint,
+        name: stablecoin_mint.name.clone(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PromotedToProduction {
+    pub stablecoin_mint: Pubkey,
+    pub old_token_mint: Pubkey,
+    pub new_token_mint: Pubkey,
+    pub name: String,
+    pub timestamp: i64,
+}
@@ -0,0 +1,318 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_interface;
+use switchboard_solana::AggregatorAccountData;
+
+use crate::state::{
+    JuniorTranche, ProtocolConfig, ShareVault, StablecoinMint, StablecoinVault, StateAccount,
+    PAUSE_VAULTS,
+};
+use crate::constants::{
+    BASIS_POINTS_DIVISOR, JUNIOR_MINT_SEED, JUNIOR_TRANCHE_SEED, PROTOCOL_CONFIG_SEED, VAULT_SEED,
+};
+use crate::error::StableFunError;
+use crate::utils::oracle::OracleService;
+use crate::utils::validation::ValidationService;
+use crate::utils::math;
+
+#[derive(Accounts)]
+pub struct InitJuniorTranche<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = JuniorTranche::LEN,
+        seeds = [JUNIOR_TRANCHE_SEED, stablecoin_mint.key().as_ref()],
+        bump
+    )]
+    pub junior_tranche: Box<Account<'info, JuniorTranche>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// Junior claims are denominated in stablecoin units
+    #[account(
+        init,
+        payer = authority,
+        seeds = [JUNIOR_MINT_SEED, stablecoin_mint.key().as_ref()],
+        bump,
+        mint::decimals = token_mint.decimals,
+        mint::authority = junior_tranche
+    )]
+    pub junior_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_VAULTS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn init_junior_tranche(ctx: Context<InitJuniorTranche>, senior_yield_bps: u16) -> Result<()> {
+    require!(
+        senior_yield_bps <= BASIS_POINTS_DIVISOR,
+        StableFunError::InvalidTrancheConfig
+    );
+
+    let junior_tranche = &mut ctx.accounts.junior_tranche;
+    junior_tranche.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    junior_tranche.junior_mint = ctx.accounts.junior_mint.key();
+    junior_tranche.senior_yield_bps = senior_yield_bps;
+    junior_tranche.bump = ctx.bumps.junior_tranche;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct JuniorOperation<'info> {
+    pub user: Signer<'info>,
+
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.stablecoin_mint == stablecoin_mint.key() @ StableFunError::InvalidVault
+    )]
+    pub vault: Box<Account<'info, StablecoinVault>>,
+
+    #[account(
+        mut,
+        seeds = [JUNIOR_TRANCHE_SEED, stablecoin_mint.key().as_ref()],
+        bump = junior_tranche.bump
+    )]
+    pub junior_tranche: Box<Account<'info, JuniorTranche>>,
+
+    #[account(
+        mut,
+        constraint = junior_mint.key() == junior_tranche.junior_mint @ StableFunError::InvalidMint
+    )]
+    pub junior_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        mut,
+        constraint = user_stablebond_account.mint == stablecoin_mint.stablebond_mint @ StableFunError::InvalidStablebond,
+        constraint = user_stablebond_account.owner == user.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub user_stablebond_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = vault_stablebond_account.key() == vault.collateral_account @ StableFunError::InvalidVaultAccount
+    )]
+    pub vault_stablebond_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_junior_account.mint == junior_mint.key() @ StableFunError::InvalidTokenAccount,
+        constraint = user_junior_account.owner == user.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub user_junior_account: Box<Account<'info, TokenAccount>>,
+
+    /// The Switchboard V3 aggregator account
+    #[account(
+        constraint = price_feed.key() == stablecoin_mint.price_feed @ StableFunError::InvalidOracle
+    )]
+    pub price_feed: AccountLoader<'info, AggregatorAccountData>,
+
+    /// FX feed composed with the bond NAV `price_feed`, on coins that set one
+    #[account(
+        constraint = fx_feed.key() == stablecoin_mint.fx_feed @ StableFunError::InvalidOracle
+    )]
+    pub fx_feed: Option<AccountLoader<'info, AggregatorAccountData>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_VAULTS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Adds `collateral_amount` stablebonds to the vault as first-loss capital
+/// and mints junior tokens for their value.
+pub fn subscribe_junior(ctx: Context<JuniorOperation>, collateral_amount: u64) -> Result<()> {
+    require!(collateral_amount > 0, StableFunError::InvalidAmount);
+
+    let oracle_price = OracleService::collateral_price(
+        &ctx.accounts.stablecoin_mint,
+        &ctx.accounts.price_feed,
+        ctx.accounts.fx_feed.as_ref(),
+    )?;
+    let value = math::calculate_collateral_value(
+        collateral_amount,
+        oracle_price,
+        ctx.accounts.token_mint.decimals,
+    )?;
+    let junior_amount = ShareVault::convert_to_shares(
+        value,
+        ctx.accounts.junior_tranche.junior_capital,
+        ctx.accounts.junior_mint.supply,
+    )?;
+    require!(junior_amount > 0, StableFunError::ZeroShares);
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.user_stablebond_account.to_account_info(),
+                to: ctx.accounts.vault_stablebond_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        collateral_amount,
+    )?;
+
+    let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::MintTo {
+                mint: ctx.accounts.junior_mint.to_account_info(),
+                to: ctx.accounts.user_junior_account.to_account_info(),
+                authority: ctx.accounts.junior_tranche.to_account_info(),
+            },
+            &[&[
+                JUNIOR_TRANCHE_SEED,
+                stablecoin_key.as_ref(),
+                &[ctx.accounts.junior_tranche.bump],
+            ]],
+        ),
+        junior_amount,
+    )?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.total_collateral = vault
+        .total_collateral
+        .checked_add(collateral_amount)
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    ValidationService::update_collateral_ratio(vault, oracle_price, ctx.accounts.token_mint.decimals)?;
+
+    ctx.accounts.junior_tranche.record_subscription(value)?;
+
+    emit!(JuniorSubscribed {
+        stablecoin_mint: stablecoin_key,
+        user: ctx.accounts.user.key(),
+        collateral_amount,
+        value,
+        junior_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Burns junior tokens for their share of junior capital, paid in
+/// collateral, as long as the senior side stays at its minimum ratio.
+pub fn redeem_junior(ctx: Context<JuniorOperation>, junior_amount: u64) -> Result<()> {
+    require!(junior_amount > 0, StableFunError::InvalidAmount);
+
+    let oracle_price = OracleService::collateral_price(
+        &ctx.accounts.stablecoin_mint,
+        &ctx.accounts.price_feed,
+        ctx.accounts.fx_feed.as_ref(),
+    )?;
+    let decimals = ctx.accounts.token_mint.decimals;
+    let value = ShareVault::convert_to_assets(
+        junior_amount,
+        ctx.accounts.junior_tranche.junior_capital,
+        ctx.accounts.junior_mint.supply,
+    )?;
+    require!(value > 0, StableFunError::ZeroShares);
+    let collateral_amount = math::calculate_token_amount(value, oracle_price, decimals)?;
+
+    let remaining_collateral = ctx.accounts.vault.total_collateral
+        .checked_sub(collateral_amount)
+        .ok_or(error!(StableFunError::InsufficientCollateral))?;
+    let remaining_value = math::calculate_collateral_value(remaining_collateral, oracle_price, decimals)?;
+    let required_value = ctx.accounts.stablecoin_mint.current_supply as u128
+        * ctx.accounts.stablecoin_mint.settings.min_collateral_ratio as u128
+        / BASIS_POINTS_DIVISOR as u128;
+    require!(
+        remaining_value as u128 >= required_value,
+        StableFunError::JuniorRedemptionBlocked
+    );
+
+    token::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Burn {
+                mint: ctx.accounts.junior_mint.to_account_info(),
+                from: ctx.accounts.user_junior_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        junior_amount,
+    )?;
+
+    let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.vault_stablebond_account.to_account_info(),
+                to: ctx.accounts.user_stablebond_account.to_account_info(),
+                authority: ctx.accounts.vault.to_account_info(),
+            },
+            &[&ctx.accounts.vault.signer_seeds()],
+        ),
+        collateral_amount,
+    )?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.total_collateral = remaining_collateral;
+    ValidationService::update_collateral_ratio(vault, oracle_price, ctx.accounts.token_mint.decimals)?;
+
+    ctx.accounts.junior_tranche.record_redemption(value)?;
+
+    emit!(JuniorRedeemed {
+        stablecoin_mint: stablecoin_key,
+        user: ctx.accounts.user.key(),
+        collateral_amount,
+        value,
+        junior_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct JuniorSubscribed {
+    pub stablecoin_mint: Pubkey,
+    pub user: Pubkey,
+    pub collateral_amount: u64,
+    pub value: u64,
+    pub junior_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct JuniorRedeemed {
+    pub stablecoin_mint: Pubkey,
+    pub user: Pubkey,
+    pub collateral_amount: u64,
+    pub value: u64,
+    pub junior_amount: u64,
+    pub timestamp: i64,
+}
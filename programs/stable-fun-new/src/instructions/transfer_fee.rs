@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_2022_extensions::transfer_fee;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::state::{ProtocolConfig, StablecoinMint};
+use crate::constants::{FEE_VAULT_SEED, MINT_AUTHORITY_SEED, PROTOCOL_CONFIG_SEED};
+use crate::error::StableFunError;
+
+#[derive(Accounts)]
+pub struct HarvestTransferFees<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: PDA set as the mint's withdraw-withheld authority
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, stablecoin_mint.key().as_ref()],
+        bump = stablecoin_mint.mint_authority_bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// Stablecoin account collecting harvested transfer fees
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [FEE_VAULT_SEED, stablecoin_mint.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = mint_authority,
+        token::token_program = token_program
+    )]
+    pub fee_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless: sweeps fees withheld on the token accounts passed as
+/// remaining accounts into the mint, then withdraws everything withheld on the
+/// mint into the fee vault.
+pub fn harvest<'info>(
+    ctx: Context<'_, '_, 'info, 'info, HarvestTransferFees<'info>>,
+) -> Result<()> {
+    if !ctx.remaining_accounts.is_empty() {
+        transfer_fee::harvest_withheld_tokens_to_mint(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                transfer_fee::HarvestWithheldTokensToMint {
+                    token_program_id: ctx.accounts.token_program.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                },
+            ),
+            ctx.remaining_accounts.to_vec(),
+        )?;
+    }
+
+    let balance_before = ctx.accounts.fee_vault.amount;
+    let stablecoin_key = ctx.accounts.stablecoin_mint.key();
+    transfer_fee::withdraw_withheld_tokens_from_mint(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        transfer_fee::WithdrawWithheldTokensFromMint {
+            token_program_id: ctx.accounts.token_program.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+            destination: ctx.accounts.fee_vault.to_account_info(),
+            authority: ctx.accounts.mint_authority.to_account_info(),
+        },
+        &[&[
+            MINT_AUTHORITY_SEED,
+            stablecoin_key.as_ref(),
+            &[ctx.accounts.stablecoin_mint.mint_authority_bump],
+        ]],
+    ))?;
+
+    ctx.accounts.fee_vault.reload()?;
+    let harvested = ctx.accounts.fee_vault.amount.saturating_sub(balance_before);
+
+    emit!(TransferFeesHarvested {
+        stablecoin_mint: stablecoin_key,
+        fee_vault: ctx.accounts.fee_vault.key(),
+        amount: harvested,
+        source_accounts: ctx.remaining_accounts.len() as u32,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct TransferFeesHarvested {
+    pub stablecoin_mint: Pubkey,
+    pub fee_vault: Pubkey,
+    pub amount: u64,
+    pub source_accounts: u32,
+    pub timestamp: i64,
+}
@@ -0,0 +1,220 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+
+use crate::state::{ProtocolConfig, StablecoinMint, StateAccount, Treasury, PAUSE_TRANSFERS};
+use crate::constants::{PROTOCOL_CONFIG_SEED, TREASURY_SEED, TREASURY_TOKEN_SEED};
+use crate::error::StableFunError;
+
+#[derive(Accounts)]
+#[instruction(treasury_id: u64)]
+pub struct CreateTreasury<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Treasury::LEN,
+        seeds = [TREASURY_SEED, stablecoin_mint.key().as_ref(), &treasury_id.to_le_bytes()],
+        bump
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Funded by plain token transfers from the authority
+    #[account(
+        init,
+        payer = authority,
+        seeds = [TREASURY_TOKEN_SEED, treasury.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = treasury,
+        token::token_program = token_program
+    )]
+    pub treasury_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_treasury(
+    ctx: Context<CreateTreasury>,
+    treasury_id: u64,
+    operator: Pubkey,
+    daily_limit: u64,
+) -> Result<()> {
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.stablecoin_mint = ctx.accounts.stablecoin_mint.key();
+    treasury.treasury_id = treasury_id;
+    treasury.operator = operator;
+    treasury.token_account = ctx.accounts.treasury_token_account.key();
+    treasury.daily_limit = daily_limit;
+    treasury.bump = ctx.bumps.treasury;
+
+    emit!(TreasuryOperatorSet {
+        treasury: treasury.key(),
+        operator,
+        daily_limit,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetTreasuryOperator<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = stablecoin_mint.authority == authority.key() @ StableFunError::UnauthorizedMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, stablecoin_mint.key().as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+}
+
+/// Reassigns the treasury or changes its limit; the current window's
+/// spending still counts against a new limit.
+pub fn set_treasury_operator(ctx: Context<SetTreasuryOperator>, operator: Pubkey, daily_limit: u64) -> Result<()> {
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.operator = operator;
+    treasury.daily_limit = daily_limit;
+
+    emit!(TreasuryOperatorSet {
+        treasury: treasury.key(),
+        operator,
+        daily_limit,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct TreasuryTransfer<'info> {
+    pub operator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, treasury.stablecoin_mint.as_ref(), &treasury.treasury_id.to_le_bytes()],
+        bump = treasury.bump,
+        constraint = treasury.operator == operator.key() @ StableFunError::UnauthorizedTreasuryOperator
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+
+    #[account(
+        constraint = stablecoin_mint.key() == treasury.stablecoin_mint @ StableFunError::InvalidMint
+    )]
+    pub stablecoin_mint: Box<Account<'info, StablecoinMint>>,
+
+    #[account(
+        constraint = token_mint.key() == stablecoin_mint.token_mint @ StableFunError::InvalidMint
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = treasury_token_account.key() == treasury.token_account @ StableFunError::InvalidTokenAccount
+    )]
+    pub treasury_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = destination_token_account.mint == token_mint.key() @ StableFunError::InvalidTokenAccount
+    )]
+    pub destination_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_active(PAUSE_TRANSFERS) @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Operator spend from the treasury, capped by its daily limit.
+pub fn treasury_transfer(ctx: Context<TreasuryTransfer>, amount: u64) -> Result<()> {
+    require!(amount > 0, StableFunError::InvalidAmount);
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.treasury.record_spend(amount, now)?;
+
+    let treasury = &ctx.accounts.treasury;
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.treasury_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.destination_token_account.to_account_info(),
+                authority: treasury.to_account_info(),
+            },
+            &[&[
+                TREASURY_SEED,
+                treasury.stablecoin_mint.as_ref(),
+                &treasury.treasury_id.to_le_bytes(),
+                &[treasury.bump],
+            ]],
+        ),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    emit!(TreasuryTransferred {
+        treasury: treasury.key(),
+        operator: treasury.operator,
+        destination: ctx.accounts.destination_token_account.key(),
+        amount,
+        spent_in_window: treasury.spent_in_window,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct TreasuryOperatorSet {
+    pub treasury: Pubkey,
+    pub operator: Pubkey,
+    pub daily_limit: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryTransferred {
+    pub treasury: Pubkey,
+    pub operator: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub spent_in_window: u64,
+    pub timestamp: i64,
+}
@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
-use crate::state::{StablecoinMint, StablecoinSettings};
+use crate::state::{IssuerBond, ProtocolConfig, ReserveReport, ReservedNames, StablecoinMint, StablecoinSettings};
+use crate::constants::{ISSUER_BOND_SEED, PROTOCOL_CONFIG_SEED, RESERVED_NAMES_SEED, RESERVE_REPORT_SEED};
 use crate::error::*;
+use crate::utils::validation::ValidationService;
 
 #[derive(Accounts)]
 pub struct UpdateSettings<'info> {
@@ -9,9 +11,31 @@ pub struct UpdateSettings<'info> {
 
     #[account(
         mut,
-        constraint = stablecoin_mint.authority == authority.key() @ UpdateError::UnauthorizedUpdate
+        constraint = stablecoin_mint.authority == authority.key() @ UpdateError::UnauthorizedUpdate,
+        constraint = stablecoin_mint.accepts_settings_signer(authority.owner) @ StableFunError::GovernanceSignatureRequired
     )]
     pub stablecoin_mint: Account<'info, StablecoinMint>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    /// Puts the coin in the reserve-attested supply tier when fresh and fully backed
+    #[account(
+        seeds = [RESERVE_REPORT_SEED, stablecoin_mint.key().as_ref()],
+        bump = reserve_report.bump
+    )]
+    pub reserve_report: Option<Account<'info, ReserveReport>>,
+
+    /// Lets `max_supply` rise above the protocol's bond threshold
+    #[account(
+        seeds = [ISSUER_BOND_SEED, stablecoin_mint.key().as_ref()],
+        bump = issuer_bond.bump
+    )]
+    pub issuer_bond: Option<Account<'info, IssuerBond>>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -21,6 +45,29 @@ pub struct UpdateSettingsParams {
     pub max_supply: Option<u64>,
     pub mint_paused: Option<bool>,
     pub redeem_paused: Option<bool>,
+    pub withdrawal_delay: Option<i64>,
+    pub same_tx_guard: Option<bool>,
+    pub large_redeem_threshold_bps: Option<u16>,
+    pub reveal_delay_slots: Option<u64>,
+    pub liquidation_threshold_bps: Option<u16>,
+    pub liquidation_penalty_bps: Option<u16>,
+    pub liquidator_bonus_bps: Option<u16>,
+    pub liquidation_grace_seconds: Option<i64>,
+    pub grace_override_bps: Option<u16>,
+    pub surplus_buffer: Option<u64>,
+    pub oracle_failure_threshold: Option<u16>,
+    pub unpause_cooloff_seconds: Option<i64>,
+    pub supply_drift_tolerance: Option<u64>,
+    pub cached_price_max_amount: Option<u64>,
+    pub cached_price_max_age_slots: Option<u64>,
+    pub yield_fee_bps: Option<u16>,
+    pub liquidity_buffer_bps: Option<u16>,
+    pub soft_fail_rejections: Option<bool>,
+    pub min_position_debt: Option<u64>,
+    pub top_level_only: Option<bool>,
+    pub throughput_capacity: Option<u32>,
+    pub throughput_refill_per_second: Option<u32>,
+    pub reporting_threshold: Option<u64>,
 }
 
 pub fn handler(
@@ -38,7 +85,78 @@ pub fn handler(
         stablecoin_mint.settings.min_collateral_ratio = new_ratio;
     }
     
+    if let Some(new_threshold) = params.liquidation_threshold_bps {
+        stablecoin_mint.settings.liquidation_threshold_bps = new_threshold;
+    }
+
+    ValidationService::validate_liquidation_threshold(
+        stablecoin_mint.settings.liquidation_threshold_bps,
+        stablecoin_mint.settings.min_collateral_ratio,
+    )?;
+
+    if let Some(new_penalty) = params.liquidation_penalty_bps {
+        stablecoin_mint.settings.liquidation_penalty_bps = new_penalty;
+    }
+
+    if let Some(new_bonus) = params.liquidator_bonus_bps {
+        stablecoin_mint.settings.liquidator_bonus_bps = new_bonus;
+    }
+
+    ValidationService::validate_liquidation_incentives(
+        stablecoin_mint.settings.liquidation_penalty_bps,
+        stablecoin_mint.settings.liquidator_bonus_bps,
+        &ctx.accounts.protocol_config,
+    )?;
+
+    if let Some(grace) = params.liquidation_grace_seconds {
+        stablecoin_mint.settings.liquidation_grace_seconds = grace;
+    }
+
+    if let Some(override_bps) = params.grace_override_bps {
+        stablecoin_mint.settings.grace_override_bps = override_bps;
+    }
+
+    ValidationService::validate_liquidation_grace(
+        stablecoin_mint.settings.liquidation_grace_seconds,
+        stablecoin_mint.settings.grace_override_bps,
+    )?;
+
+    if let Some(new_buffer) = params.surplus_buffer {
+        stablecoin_mint.settings.surplus_buffer = new_buffer;
+    }
+
+    if let Some(threshold) = params.oracle_failure_threshold {
+        stablecoin_mint.settings.oracle_failure_threshold = threshold;
+    }
+
+    if let Some(cooloff) = params.unpause_cooloff_seconds {
+        ValidationService::validate_unpause_cooloff(cooloff)?;
+        stablecoin_mint.settings.unpause_cooloff_seconds = cooloff;
+    }
+
+    if let Some(tolerance) = params.supply_drift_tolerance {
+        stablecoin_mint.settings.supply_drift_tolerance = tolerance;
+    }
+
+    if let Some(max_amount) = params.cached_price_max_amount {
+        stablecoin_mint.settings.cached_price_max_amount = max_amount;
+    }
+
+    if let Some(max_age_slots) = params.cached_price_max_age_slots {
+        ValidationService::validate_cached_price_age(max_age_slots)?;
+        stablecoin_mint.settings.cached_price_max_age_slots = max_age_slots;
+    }
+
+    if let Some(fee_bps) = params.yield_fee_bps {
+        ValidationService::validate_yield_fee(fee_bps, &ctx.accounts.protocol_config)?;
+        stablecoin_mint.settings.yield_fee_bps = fee_bps;
+    }
+    
     if let Some(new_fee) = params.fee_basis_points {
+        require!(
+            !stablecoin_mint.fee_change_needs_council(new_fee),
+            StableFunError::CouncilApprovalRequired
+        );
         stablecoin_mint.settings.fee_basis_points = new_fee;
     }
     
@@ -47,18 +165,97 @@ pub fn handler(
             new_max_supply >= stablecoin_mint.current_supply,
             StableFunError::InvalidMaxSupply
         );
+        ValidationService::validate_max_supply(
+            new_max_supply,
+            &ctx.accounts.protocol_config,
+            ctx.accounts.reserve_report.as_deref(),
+            clock.unix_timestamp,
+        )?;
+        ValidationService::validate_bonded_supply(
+            new_max_supply,
+            &ctx.accounts.protocol_config,
+            ctx.accounts.issuer_bond.as_deref(),
+        )?;
         stablecoin_mint.settings.max_supply = new_max_supply;
     }
     
+    // Settings can only add pauses; lifting one goes through the checked unpause path
     if let Some(paused) = params.mint_paused {
-        stablecoin_mint.settings.mint_paused = paused;
+        require!(
+            paused || !stablecoin_mint.settings.mint_paused,
+            StableFunError::UnpauseChecksRequired
+        );
+        if paused {
+            stablecoin_mint.pause_mint(clock.unix_timestamp);
+        }
     }
     
     if let Some(paused) = params.redeem_paused {
-        stablecoin_mint.settings.redeem_paused = paused;
+        require!(
+            paused || !stablecoin_mint.settings.redeem_paused,
+            StableFunError::UnpauseChecksRequired
+        );
+        if paused {
+            stablecoin_mint.pause_redeem(clock.unix_timestamp);
+        }
+    }
+
+    if let Some(delay) = params.withdrawal_delay {
+        ValidationService::validate_withdrawal_delay(delay)?;
+        stablecoin_mint.settings.withdrawal_delay = delay;
+    }
+
+    if let Some(enabled) = params.same_tx_guard {
+        stablecoin_mint.settings.same_tx_guard = enabled;
+    }
+
+    if let Some(enabled) = params.soft_fail_rejections {
+        stablecoin_mint.settings.soft_fail_rejections = enabled;
+    }
+
+    if let Some(min_debt) = params.min_position_debt {
+        stablecoin_mint.settings.min_position_debt = min_debt;
+    }
+
+    if let Some(enabled) = params.top_level_only {
+        stablecoin_mint.settings.top_level_only = enabled;
+    }
+
+    if let Some(capacity) = params.throughput_capacity {
+        stablecoin_mint.settings.throughput_capacity = capacity;
+    }
+
+    if let Some(refill) = params.throughput_refill_per_second {
+        stablecoin_mint.settings.throughput_refill_per_second = refill;
+    }
+
+    if let Some(threshold) = params.reporting_threshold {
+        stablecoin_mint.settings.reporting_threshold = threshold;
+    }
+
+    if let Some(threshold) = params.large_redeem_threshold_bps {
+        stablecoin_mint.settings.large_redeem_threshold_bps = threshold;
+    }
+
+    if let Some(delay) = params.reveal_delay_slots {
+        stablecoin_mint.settings.reveal_delay_slots = delay;
+    }
+
+    ValidationService::validate_commit_reveal(
+        stablecoin_mint.settings.large_redeem_threshold_bps,
+        stablecoin_mint.settings.reveal_delay_slots,
+    )?;
+
+    if let Some(buffer_bps) = params.liquidity_buffer_bps {
+        stablecoin_mint.settings.liquidity_buffer_bps = buffer_bps;
     }
 
-    stablecoin_mint.last_updated = clock.unix_timestamp;
+    ValidationService::validate_liquidity_buffer(
+        stablecoin_mint.settings.liquidity_buffer_bps,
+        stablecoin_mint.settings.reveal_delay_slots,
+    )?;
+
+    stablecoin_mint.record_admin_action(clock.unix_timestamp);
 
     emit!(SettingsUpdateEvent {
         stablecoin_mint: stablecoin_mint.key(),
@@ -76,6 +273,8 @@ pub struct UpdateMetadataParams {
     pub name: Option<String>,
     pub symbol: Option<String>,
     pub icon_uri: Option<String>,
+    pub metadata_uri: Option<String>,
+    pub description: Option<String>,
 }
 
 
@@ -87,9 +286,26 @@ pub struct UpdateMetadata<'info> {
 
     #[account(
         mut,
-        constraint = stablecoin_mint.authority == authority.key() @ UpdateError::UnauthorizedUpdate
+        constraint = stablecoin_mint.authority == authority.key() @ UpdateError::UnauthorizedUpdate,
+        constraint = stablecoin_mint.accepts_settings_signer(authority.owner) @ StableFunError::GovernanceSignatureRequired
     )]
     pub stablecoin_mint: Account<'info, StablecoinMint>,
+
+    /// Names and symbols a coin can't be renamed to; skipped when omitted
+    #[account(
+        seeds = [RESERVED_NAMES_SEED],
+        bump = reserved_names.bump
+    )]
+    pub reserved_names: Option<Box<Account<'info, ReservedNames>>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.is_live() @ StableFunError::ProtocolPaused
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
 }
 
 pub fn update_metadata(
@@ -101,30 +317,58 @@ pub fn update_metadata(
 
     // Update name if provided
     if let Some(new_name) = params.name {
+        let new_name = ValidationService::normalize_name(&new_name)?;
         require!(
-            !new_name.is_empty() && new_name.len() <= 32,
-            UpdateError::InvalidName
+            !ctx.accounts.reserved_names
+                .as_ref()
+                .is_some_and(|reserved_names| reserved_names.is_reserved(&new_name)),
+            StableFunError::ReservedName
         );
-        stablecoin_mint.name = new_name;
+        stablecoin_mint.name = if stablecoin_mint.test_mode {
+            StablecoinMint::watermark_name(&new_name)?
+        } else {
+            new_name
+        };
     }
 
     // Update symbol if provided
     if let Some(new_symbol) = params.symbol {
+        let new_symbol = ValidationService::normalize_symbol(&new_symbol)?;
         require!(
-            !new_symbol.is_empty() && new_symbol.len() <= 10,
-            UpdateError::InvalidSymbol
+            !ctx.accounts.reserved_names
+                .as_ref()
+                .is_some_and(|reserved_names| reserved_names.is_reserved(&new_symbol)),
+            StableFunError::ReservedName
         );
         stablecoin_mint.symbol = new_symbol;
     }
 
+    if let Some(new_icon_uri) = params.icon_uri {
+        StablecoinMint::validate_uri(&new_icon_uri)?;
+        stablecoin_mint.icon_uri = new_icon_uri;
+    }
+
+    if let Some(new_metadata_uri) = params.metadata_uri {
+        StablecoinMint::validate_uri(&new_metadata_uri)?;
+        stablecoin_mint.metadata_uri = new_metadata_uri;
+    }
+
+    if let Some(new_description) = params.description {
+        StablecoinMint::validate_description(&new_description)?;
+        stablecoin_mint.description = new_description;
+    }
+
     // Update last updated timestamp
-    stablecoin_mint.last_updated = clock.unix_timestamp;
+    stablecoin_mint.record_admin_action(clock.unix_timestamp);
 
     emit!(MetadataUpdateEvent {
         stablecoin_mint: stablecoin_mint.key(),
         authority: ctx.accounts.authority.key(),
         name: stablecoin_mint.name.clone(),
         symbol: stablecoin_mint.symbol.clone(),
+        icon_uri: stablecoin_mint.icon_uri.clone(),
+        metadata_uri: stablecoin_mint.metadata_uri.clone(),
+        description: stablecoin_mint.description.clone(),
         timestamp: clock.unix_timestamp,
     });
 
@@ -146,6 +390,9 @@ pub struct MetadataUpdateEvent {
     pub authority: Pubkey,
     pub name: String,
     pub symbol: String,
+    pub icon_uri: String,
+    pub metadata_uri: String,
+    pub description: String,
     pub timestamp: i64,
 }
 
@@ -179,6 +426,29 @@ mod tests {
                 max_supply: 1_000_000,
                 mint_paused: false,
                 redeem_paused: false,
+                withdrawal_delay: 60,
+                same_tx_guard: false,
+                large_redeem_threshold_bps: 0,
+                reveal_delay_slots: 0,
+                liquidation_threshold_bps: 12000,
+                liquidation_penalty_bps: 300,
+                liquidator_bonus_bps: 500,
+                liquidation_grace_seconds: 0,
+                grace_override_bps: 0,
+                surplus_buffer: 0,
+                oracle_failure_threshold: 3,
+                unpause_cooloff_seconds: 3600,
+                supply_drift_tolerance: 0,
+                cached_price_max_amount: 0,
+                cached_price_max_age_slots: 0,
+                yield_fee_bps: 0,
+                liquidity_buffer_bps: 0,
+                soft_fail_rejections: false,
+                min_position_debt: 0,
+                top_level_only: false,
+                throughput_capacity: 0,
+                throughput_refill_per_second: 0,
+                reporting_threshold: 0,
             },
             ..Default::default()
         };
@@ -189,6 +459,29 @@ mod tests {
             max_supply: Some(2_000_000),
             mint_paused: Some(true),
             redeem_paused: Some(true),
+            withdrawal_delay: Some(300),
+            same_tx_guard: Some(true),
+            large_redeem_threshold_bps: None,
+            reveal_delay_slots: None,
+            liquidation_threshold_bps: Some(18000),
+            liquidation_penalty_bps: None,
+            liquidator_bonus_bps: None,
+            liquidation_grace_seconds: None,
+            grace_override_bps: None,
+            surplus_buffer: None,
+            oracle_failure_threshold: None,
+            unpause_cooloff_seconds: None,
+            supply_drift_tolerance: None,
+            cached_price_max_amount: None,
+            cached_price_max_age_slots: None,
+            yield_fee_bps: None,
+            liquidity_buffer_bps: None,
+            soft_fail_rejections: None,
+            min_position_debt: None,
+            top_level_only: None,
+            throughput_capacity: None,
+            throughput_refill_per_second: None,
+            reporting_threshold: None,
         };
 
         // Simulate update
@@ -197,12 +490,21 @@ mod tests {
         test_mint.settings.max_supply = params.max_supply.unwrap();
         test_mint.settings.mint_paused = params.mint_paused.unwrap();
         test_mint.settings.redeem_paused = params.redeem_paused.unwrap();
+        test_mint.settings.withdrawal_delay = params.withdrawal_delay.unwrap();
+        test_mint.settings.liquidation_threshold_bps = params.liquidation_threshold_bps.unwrap();
+        test_mint.settings.same_tx_guard = params.same_tx_guard.unwrap();
 
         assert_eq!(test_mint.settings.min_collateral_ratio, 20000);
         assert_eq!(test_mint.settings.fee_basis_points, 50);
         assert_eq!(test_mint.settings.max_supply, 2_000_000);
         assert_eq!(test_mint.settings.mint_paused, true);
         assert_eq!(test_mint.settings.redeem_paused, true);
+        assert_eq!(test_mint.settings.withdrawal_delay, 300);
+        assert!(ValidationService::validate_liquidation_threshold(
+            test_mint.settings.liquidation_threshold_bps,
+            test_mint.settings.min_collateral_ratio,
+        ).is_ok());
+        assert!(test_mint.settings.same_tx_guard);
     }
 
     #[test]
@@ -217,7 +519,9 @@ mod tests {
         let params = UpdateMetadataParams {
             name: Some("New Name".to_string()),
             symbol: Some("NEW".to_string()),
-            icon_uri: None,
+            icon_uri: Some("https://example.com/icon.png".to_string()),
+            metadata_uri: None,
+            description: Some("New description".to_string()),
         };
 
         // Simulate update
@@ -227,8 +531,17 @@ mod tests {
         if let Some(symbol) = params.symbol {
             test_mint.symbol = symbol;
         }
+        if let Some(icon_uri) = params.icon_uri {
+            test_mint.icon_uri = icon_uri;
+        }
+        if let Some(description) = params.description {
+            test_mint.description = description;
+        }
 
         assert_eq!(test_mint.name, "New Name");
         assert_eq!(test_mint.symbol, "NEW");
+        assert_eq!(test_mint.icon_uri, "https://example.com/icon.png");
+        assert!(test_mint.metadata_uri.is_empty());
+        assert_eq!(test_mint.description, "New description");
     }
 }
\ No newline at end of file
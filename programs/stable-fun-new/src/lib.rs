@@ -10,7 +10,8 @@ pub mod constants;
 
 use instructions::*;
 use error::StableFunError;
-use constants::{MIN_NAME_LENGTH, MIN_SYMBOL_LENGTH, MIN_COLLATERAL_RATIO};
+use state::{DeadManAction, FeeDiscountTier, MaturityBucket, ProposalAction, RebaseMode};
+use constants::MIN_COLLATERAL_RATIO;
 
 #[program]
 pub mod stable_fun_new {
@@ -23,25 +24,77 @@ pub mod stable_fun_new {
         symbol: String,
         target_currency: String,
         initial_supply: u64,
+        params: InitializeParams,
     ) -> Result<()> {
         msg!("Initializing with name: {}, symbol: {}", name, symbol);
-        require!(name.len() >= MIN_NAME_LENGTH, StableFunError::NameTooShort);
-        require!(symbol.len() >= MIN_SYMBOL_LENGTH, StableFunError::SymbolTooShort);
-        instructions::initialize::handler(ctx, name, symbol, target_currency, initial_supply)
+        instructions::initialize::handler(
+            ctx,
+            name,
+            symbol,
+            target_currency,
+            initial_supply,
+            params,
+        )
     }
 
     #[inline(never)]
-    pub fn mint(ctx: Context<MintStablecoin>, amount: u64) -> Result<()> {
+    pub fn close_stablecoin(ctx: Context<CloseStablecoin>) -> Result<()> {
+        msg!("Closing stablecoin");
+        instructions::close::close_stablecoin(ctx)
+    }
+
+    #[inline(never)]
+    pub fn mint(
+        ctx: Context<MintStablecoin>,
+        amount: u64,
+        client_order_id: Option<[u8; 32]>,
+        dry_run: bool,
+    ) -> Result<FeeReceipt> {
         msg!("Minting {} tokens", amount);
         require!(amount > 0, StableFunError::InvalidAmount);
-        instructions::mint::handler(ctx, amount)
+        instructions::mint::handler(ctx, amount, client_order_id, dry_run)
     }
 
     #[inline(never)]
-    pub fn redeem(ctx: Context<RedeemStablecoin>, amount: u64) -> Result<()> {
+    pub fn redeem(
+        ctx: Context<RedeemStablecoin>,
+        amount: u64,
+        client_order_id: Option<[u8; 32]>,
+        dry_run: bool,
+    ) -> Result<FeeReceipt> {
         msg!("Redeeming {} tokens", amount);
         require!(amount > 0, StableFunError::InvalidAmount);
-        instructions::redeem::handler(ctx, amount)
+        instructions::redeem::handler(ctx, amount, client_order_id, dry_run)
+    }
+
+    #[inline(never)]
+    pub fn commit_redeem(ctx: Context<CommitRedeem>, hash: [u8; 32]) -> Result<()> {
+        msg!("Committing redemption");
+        instructions::commit_redeem::commit_redeem(ctx, hash)
+    }
+
+    #[inline(never)]
+    pub fn cancel_redeem_commitment(ctx: Context<CancelRedeemCommitment>) -> Result<()> {
+        msg!("Cancelling redemption commitment");
+        instructions::commit_redeem::cancel_redeem_commitment(ctx)
+    }
+
+    #[inline(never)]
+    pub fn skip_expired_redeem(ctx: Context<SkipExpiredRedeem>) -> Result<()> {
+        msg!("Skipping expired redemption commitment");
+        instructions::commit_redeem::skip_expired_redeem(ctx)
+    }
+
+    #[inline(never)]
+    pub fn reveal_redeem(
+        ctx: Context<RedeemStablecoin>,
+        amount: u64,
+        client_order_id: Option<[u8; 32]>,
+        salt: [u8; 32],
+    ) -> Result<FeeReceipt> {
+        msg!("Revealing redemption of {} tokens", amount);
+        require!(amount > 0, StableFunError::InvalidAmount);
+        instructions::redeem::reveal(ctx, amount, client_order_id, salt)
     }
 
     #[inline(never)]
@@ -56,4 +109,1127 @@ pub mod stable_fun_new {
         );
         instructions::update::handler(ctx, params)
     }
+
+    #[inline(never)]
+    pub fn liquidate(ctx: Context<Liquidate>, repay_amount: u64) -> Result<()> {
+        msg!("Liquidating position, repaying {} tokens", repay_amount);
+        instructions::liquidate::liquidate(ctx, repay_amount)
+    }
+
+    #[inline(never)]
+    pub fn flag_position(ctx: Context<FlagPosition>) -> Result<()> {
+        msg!("Flagging position");
+        instructions::liquidate::flag(ctx)
+    }
+
+    #[inline(never)]
+    pub fn preview_liquidation(
+        ctx: Context<PreviewLiquidation>,
+        repay_amount: Option<u64>,
+    ) -> Result<LiquidationPreview> {
+        instructions::liquidate::preview(ctx, repay_amount)
+    }
+
+    #[inline(never)]
+    pub fn settle_bad_debt(ctx: Context<SettleBadDebt>) -> Result<()> {
+        msg!("Settling bad debt against surplus");
+        instructions::accounting::settle_bad_debt(ctx)
+    }
+
+    #[inline(never)]
+    pub fn sync_vault(ctx: Context<SyncVault>) -> Result<()> {
+        msg!("Syncing vault balance");
+        instructions::accounting::sync_vault(ctx)
+    }
+
+    #[inline(never)]
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+        msg!("Withdrawing {} in fees", amount);
+        instructions::accounting::withdraw_fees(ctx, amount)
+    }
+
+    #[inline(never)]
+    pub fn publish_reserve_report(ctx: Context<PublishReserveReport>) -> Result<()> {
+        msg!("Publishing reserve report");
+        instructions::reserve::publish_reserve_report(ctx)
+    }
+
+    #[inline(never)]
+    pub fn commit_snapshot(
+        ctx: Context<CommitSnapshot>,
+        slot: u64,
+        merkle_root: [u8; 32],
+        total_balance: u64,
+        holder_count: u32,
+    ) -> Result<()> {
+        msg!("Committing holder snapshot at slot {}", slot);
+        instructions::snapshot::commit_snapshot(ctx, slot, merkle_root, total_balance, holder_count)
+    }
+
+    #[inline(never)]
+    pub fn create_airdrop(
+        ctx: Context<CreateAirdrop>,
+        airdrop_id: u64,
+        merkle_root: [u8; 32],
+        total_amount: u64,
+        max_recipients: u32,
+    ) -> Result<()> {
+        msg!("Creating airdrop {} of {} tokens", airdrop_id, total_amount);
+        instructions::airdrop::create_airdrop(ctx, airdrop_id, merkle_root, total_amount, max_recipients)
+    }
+
+    #[inline(never)]
+    pub fn claim_airdrop(
+        ctx: Context<ClaimAirdrop>,
+        index: u64,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        msg!("Claiming airdrop index {}", index);
+        instructions::airdrop::claim_airdrop(ctx, index, amount, proof)
+    }
+
+    #[inline(never)]
+    pub fn rescue_token(ctx: Context<RescueToken>, amount: u64) -> Result<()> {
+        msg!("Rescuing {} foreign tokens", amount);
+        instructions::rescue::rescue_token(ctx, amount)
+    }
+
+    #[inline(never)]
+    pub fn migrate_vault_token_account(ctx: Context<MigrateVaultTokenAccount>) -> Result<()> {
+        msg!("Migrating vault token account to associated token account");
+        instructions::migrate::migrate_vault_token_account(ctx)
+    }
+
+    #[inline(never)]
+    pub fn migrate_stablecoin_layout(ctx: Context<MigrateStablecoinLayout>) -> Result<()> {
+        msg!("Migrating stablecoin accounts to the current layout");
+        instructions::migrate::migrate_stablecoin_layout(ctx)
+    }
+
+    #[inline(never)]
+    pub fn freeze_token_account(ctx: Context<SetTokenAccountFrozen>) -> Result<()> {
+        msg!("Freezing token account");
+        instructions::compliance::freeze(ctx)
+    }
+
+    #[inline(never)]
+    pub fn thaw_token_account(ctx: Context<SetTokenAccountFrozen>) -> Result<()> {
+        msg!("Thawing token account");
+        instructions::compliance::thaw(ctx)
+    }
+
+    #[inline(never)]
+    pub fn harvest_transfer_fees<'info>(
+        ctx: Context<'_, '_, 'info, 'info, HarvestTransferFees<'info>>,
+    ) -> Result<()> {
+        msg!("Harvesting transfer fees from {} accounts", ctx.remaining_accounts.len());
+        instructions::transfer_fee::harvest(ctx)
+    }
+
+    #[inline(never)]
+    pub fn promote_to_production(
+        ctx: Context<PromoteToProduction>,
+        token_extensions: TokenExtensionParams,
+    ) -> Result<()> {
+        msg!("Promoting test-mode stablecoin to production");
+        instructions::test_mode::promote_to_production(ctx, token_extensions)
+    }
+
+    #[inline(never)]
+    pub fn update_interest_rate(ctx: Context<UpdateInterestRate>) -> Result<()> {
+        msg!("Updating interest rate from harvested yield");
+        instructions::interest::update_interest_rate(ctx)
+    }
+
+    #[inline(never)]
+    pub fn set_ntt_manager(
+        ctx: Context<SetNttManager>,
+        ntt_manager: Pubkey,
+        outbound_limit: u64,
+        window_seconds: i64,
+    ) -> Result<()> {
+        msg!("Setting NTT manager to {}", ntt_manager);
+        instructions::ntt::set_ntt_manager(ctx, ntt_manager, outbound_limit, window_seconds)
+    }
+
+    #[inline(never)]
+    pub fn ntt_outbound(ctx: Context<NttOutbound>, amount: u64) -> Result<()> {
+        msg!("Burning {} tokens for outbound NTT transfer", amount);
+        instructions::ntt::outbound(ctx, amount)
+    }
+
+    #[inline(never)]
+    pub fn ntt_inbound(ctx: Context<NttInbound>, amount: u64) -> Result<()> {
+        msg!("Minting {} tokens for inbound NTT transfer", amount);
+        instructions::ntt::inbound(ctx, amount)
+    }
+
+    #[inline(never)]
+    pub fn set_remote_custodian(
+        ctx: Context<SetRemoteCustodian>,
+        eth_address: [u8; 20],
+    ) -> Result<()> {
+        msg!("Setting remote redemption custodian");
+        instructions::remote_redeem::set_remote_custodian(ctx, eth_address)
+    }
+
+    #[inline(never)]
+    pub fn remote_redeem(
+        ctx: Context<RemoteRedeem>,
+        amount: u64,
+        recipient: Pubkey,
+        nonce: u64,
+    ) -> Result<()> {
+        msg!("Executing remote redemption {} of {} tokens", nonce, amount);
+        instructions::remote_redeem::remote_redeem(ctx, amount, recipient, nonce)
+    }
+
+    #[inline(never)]
+    pub fn create_invoice(
+        ctx: Context<CreateInvoice>,
+        invoice_id: u64,
+        amount: u64,
+        expires_at: i64,
+        memo_hash: [u8; 32],
+    ) -> Result<()> {
+        msg!("Creating invoice {} for {} tokens", invoice_id, amount);
+        instructions::invoice::create_invoice(ctx, invoice_id, amount, expires_at, memo_hash)
+    }
+
+    #[inline(never)]
+    pub fn pay_invoice(ctx: Context<PayInvoice>) -> Result<()> {
+        msg!("Paying invoice");
+        instructions::invoice::pay(ctx)
+    }
+
+    #[inline(never)]
+    pub fn cancel_invoice(ctx: Context<CancelInvoice>) -> Result<()> {
+        msg!("Cancelling invoice");
+        instructions::invoice::cancel_invoice(ctx)
+    }
+
+    #[inline(never)]
+    pub fn create_stream(
+        ctx: Context<CreateStream>,
+        stream_id: u64,
+        total_amount: u64,
+        start_time: i64,
+        end_time: i64,
+    ) -> Result<()> {
+        msg!("Creating stream {} of {} tokens", stream_id, total_amount);
+        instructions::stream::create_stream(ctx, stream_id, total_amount, start_time, end_time)
+    }
+
+    #[inline(never)]
+    pub fn withdraw_stream(ctx: Context<WithdrawStream>) -> Result<()> {
+        msg!("Withdrawing from stream");
+        instructions::stream::withdraw(ctx)
+    }
+
+    #[inline(never)]
+    pub fn cancel_stream(ctx: Context<CancelStream>) -> Result<()> {
+        msg!("Cancelling stream");
+        instructions::stream::cancel_stream(ctx)
+    }
+
+    #[inline(never)]
+    pub fn set_pauser(ctx: Context<SetPauser>, pauser: Pubkey) -> Result<()> {
+        msg!("Setting pauser to {}", pauser);
+        instructions::pause::set_pauser(ctx, pauser)
+    }
+
+    #[inline(never)]
+    pub fn pause_mint(ctx: Context<SetPaused>) -> Result<()> {
+        msg!("Pausing minting");
+        instructions::pause::pause_mint(ctx)
+    }
+
+    #[inline(never)]
+    pub fn unpause_mint(ctx: Context<Unpause>) -> Result<()> {
+        msg!("Unpausing minting");
+        instructions::pause::unpause_mint(ctx)
+    }
+
+    #[inline(never)]
+    pub fn pause_redeem(ctx: Context<SetPaused>) -> Result<()> {
+        msg!("Pausing redemptions");
+        instructions::pause::pause_redeem(ctx)
+    }
+
+    #[inline(never)]
+    pub fn unpause_redeem(ctx: Context<Unpause>) -> Result<()> {
+        msg!("Unpausing redemptions");
+        instructions::pause::unpause_redeem(ctx)
+    }
+
+    #[inline(never)]
+    pub fn check_oracle(ctx: Context<CheckOracle>) -> Result<()> {
+        msg!("Checking oracle health");
+        instructions::oracle::check_oracle(ctx)
+    }
+
+    #[inline(never)]
+    pub fn verify_invariants(ctx: Context<VerifyInvariants>) -> Result<()> {
+        msg!("Verifying accounting invariants");
+        instructions::audit::verify_invariants(ctx)
+    }
+
+    #[inline(never)]
+    pub fn sync_supply(ctx: Context<SyncSupply>) -> Result<()> {
+        msg!("Syncing recorded supply with token mint");
+        instructions::audit::sync_supply(ctx)
+    }
+
+    #[inline(never)]
+    pub fn init_operation_log(ctx: Context<InitOperationLog>) -> Result<()> {
+        msg!("Initializing operation log");
+        instructions::operation_log::init_operation_log(ctx)
+    }
+
+    #[inline(never)]
+    pub fn get_state(ctx: Context<GetState>) -> Result<StablecoinSummary> {
+        instructions::get_state::get_state(ctx)
+    }
+
+    #[inline(never)]
+    pub fn set_interest_rate_model(
+        ctx: Context<SetInterestRateModel>,
+        base_rate_bps: u16,
+        slope1_bps: u16,
+        slope2_bps: u16,
+        optimal_utilization_bps: u16,
+    ) -> Result<()> {
+        msg!("Setting interest rate model");
+        instructions::rate_model::set_interest_rate_model(
+            ctx,
+            base_rate_bps,
+            slope1_bps,
+            slope2_bps,
+            optimal_utilization_bps,
+        )
+    }
+
+    #[inline(never)]
+    pub fn accrue_stability_fee(ctx: Context<AccrueStabilityFee>) -> Result<()> {
+        msg!("Accruing stability fee");
+        instructions::rate_model::accrue_stability_fee(ctx)
+    }
+
+    #[inline(never)]
+    pub fn set_elastic_supply_cap(
+        ctx: Context<SetElasticSupplyCap>,
+        growth_bps: u16,
+        shrink_bps: u16,
+        grow_above_ratio_bps: u16,
+        shrink_below_ratio_bps: u16,
+        min_cap: u64,
+        max_cap: u64,
+    ) -> Result<()> {
+        msg!("Setting elastic supply cap");
+        instructions::supply_cap::set_elastic_supply_cap(
+            ctx,
+            growth_bps,
+            shrink_bps,
+            grow_above_ratio_bps,
+            shrink_below_ratio_bps,
+            min_cap,
+            max_cap,
+        )
+    }
+
+    #[inline(never)]
+    pub fn adjust_supply_cap(ctx: Context<AdjustSupplyCap>) -> Result<()> {
+        msg!("Adjusting supply cap");
+        instructions::supply_cap::adjust_supply_cap(ctx)
+    }
+
+    #[inline(never)]
+    pub fn set_fee_discounts(ctx: Context<SetFeeDiscounts>, tiers: Vec<FeeDiscountTier>) -> Result<()> {
+        msg!("Setting {} fee discount tiers", tiers.len());
+        instructions::fee_discount::set_fee_discounts(ctx, tiers)
+    }
+
+    #[inline(never)]
+    pub fn set_bond_terms(
+        ctx: Context<SetBondTerms>,
+        annual_yield: u64,
+        issued_at: i64,
+        maturity_timestamp: i64,
+    ) -> Result<()> {
+        msg!("Setting bond terms: {} yield, maturing {}", annual_yield, maturity_timestamp);
+        instructions::bond_terms::set_bond_terms(ctx, annual_yield, issued_at, maturity_timestamp)
+    }
+
+    #[inline(never)]
+    pub fn configure_buyback(
+        ctx: Context<ConfigureBuyback>,
+        amm_program: Pubkey,
+        max_price_bps: u16,
+    ) -> Result<()> {
+        msg!("Configuring buyback");
+        instructions::buyback::configure_buyback(ctx, amm_program, max_price_bps)
+    }
+
+    #[inline(never)]
+    pub fn execute_buyback<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteBuyback<'info>>,
+        amount: u64,
+        swap_data: Vec<u8>,
+    ) -> Result<()> {
+        msg!("Executing buyback of {} surplus", amount);
+        instructions::buyback::execute_buyback(ctx, amount, swap_data)
+    }
+
+    #[inline(never)]
+    pub fn configure_collateral_registry(
+        ctx: Context<ConfigureCollateralRegistry>,
+        manager: Pubkey,
+        swap_program: Pubkey,
+        max_slippage_bps: u16,
+        epoch_volume_cap: u64,
+    ) -> Result<()> {
+        msg!("Configuring collateral registry, manager {}", manager);
+        instructions::collateral_registry::configure_collateral_registry(
+            ctx,
+            manager,
+            swap_program,
+            max_slippage_bps,
+            epoch_volume_cap,
+        )
+    }
+
+    #[inline(never)]
+    pub fn register_collateral_type(
+        ctx: Context<RegisterCollateralType>,
+        target_weight_bps: u16,
+        maturity_timestamp: i64,
+    ) -> Result<()> {
+        msg!("Registering collateral type at {} bps", target_weight_bps);
+        instructions::collateral_registry::register_collateral_type(ctx, target_weight_bps, maturity_timestamp)
+    }
+
+    #[inline(never)]
+    pub fn set_collateral_targets(ctx: Context<SetCollateralTargets>, targets: Vec<u16>) -> Result<()> {
+        msg!("Setting {} collateral targets", targets.len());
+        instructions::collateral_registry::set_collateral_targets(ctx, targets)
+    }
+
+    #[inline(never)]
+    pub fn rebalance_collateral<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RebalanceCollateral<'info>>,
+        from: u8,
+        to: u8,
+        amount: u64,
+        swap_data: Vec<u8>,
+    ) -> Result<()> {
+        msg!("Rebalancing {} collateral from type {} to {}", amount, from, to);
+        instructions::collateral_registry::rebalance_collateral(ctx, from, to, amount, swap_data)
+    }
+
+    #[inline(never)]
+    pub fn set_maturity_policy(
+        ctx: Context<SetMaturityPolicy>,
+        buckets: Vec<MaturityBucket>,
+        tolerance_bps: u16,
+    ) -> Result<()> {
+        msg!("Setting maturity policy with {} buckets", buckets.len());
+        instructions::maturity_policy::set_maturity_policy(ctx, buckets, tolerance_bps)
+    }
+
+    #[inline(never)]
+    pub fn rebalance_ladder<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RebalanceCollateral<'info>>,
+        from: u8,
+        to: u8,
+        amount: u64,
+        swap_data: Vec<u8>,
+    ) -> Result<()> {
+        msg!("Rebalancing ladder: {} from type {} to {}", amount, from, to);
+        instructions::maturity_policy::rebalance_ladder(ctx, from, to, amount, swap_data)
+    }
+
+    #[inline(never)]
+    pub fn list_seized_certificate<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ListSeizedCertificate<'info>>,
+        min_proceeds: u64,
+        listing_data: Vec<u8>,
+    ) -> Result<()> {
+        msg!("Listing seized certificate for at least {}", min_proceeds);
+        instructions::auction::list_seized_certificate(ctx, min_proceeds, listing_data)
+    }
+
+    #[inline(never)]
+    pub fn settle_certificate_auction(ctx: Context<SettleCertificateAuction>) -> Result<()> {
+        msg!("Settling certificate auction");
+        instructions::auction::settle_certificate_auction(ctx)
+    }
+
+    #[inline(never)]
+    pub fn cancel_certificate_listing(ctx: Context<CancelCertificateListing>) -> Result<()> {
+        msg!("Cancelling certificate listing");
+        instructions::auction::cancel_certificate_listing(ctx)
+    }
+
+    #[inline(never)]
+    pub fn begin_collateral_migration(
+        ctx: Context<BeginCollateralMigration>,
+        rollover_program: Pubkey,
+        min_out_bps: u16,
+    ) -> Result<()> {
+        msg!("Beginning collateral migration");
+        instructions::collateral_migration::begin_collateral_migration(ctx, rollover_program, min_out_bps)
+    }
+
+    #[inline(never)]
+    pub fn migrate_collateral<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MigrateCollateral<'info>>,
+        amount: u64,
+        rollover_data: Vec<u8>,
+    ) -> Result<()> {
+        msg!("Rolling over {} collateral", amount);
+        instructions::collateral_migration::migrate_collateral(ctx, amount, rollover_data)
+    }
+
+    #[inline(never)]
+    pub fn complete_collateral_migration(ctx: Context<CompleteCollateralMigration>) -> Result<()> {
+        msg!("Completing collateral migration");
+        instructions::collateral_migration::complete_collateral_migration(ctx)
+    }
+
+    #[inline(never)]
+    pub fn set_market_feed(ctx: Context<SetMarketFeed>) -> Result<()> {
+        msg!("Setting market price feed");
+        instructions::peg::set_market_feed(ctx)
+    }
+
+    #[inline(never)]
+    pub fn record_market_price(ctx: Context<RecordMarketPrice>) -> Result<()> {
+        msg!("Recording market price");
+        instructions::peg::record_market_price(ctx)
+    }
+
+    #[inline(never)]
+    pub fn roll_epoch_stats(ctx: Context<RollEpochStats>) -> Result<()> {
+        msg!("Rolling epoch stats");
+        instructions::epoch_stats::roll_epoch_stats(ctx)
+    }
+
+    #[inline(never)]
+    pub fn lock_for_collateral(ctx: Context<LockForCollateral>, amount: u64) -> Result<()> {
+        msg!("Locking {} as lending collateral", amount);
+        instructions::lending::lock_for_collateral(ctx, amount)
+    }
+
+    #[inline(never)]
+    pub fn release_collateral(ctx: Context<ReleaseCollateral>, amount: u64) -> Result<()> {
+        msg!("Releasing {} lending collateral", amount);
+        instructions::lending::release_collateral(ctx, amount)
+    }
+
+    #[inline(never)]
+    pub fn create_otc_offer(
+        ctx: Context<CreateOtcOffer>,
+        offer_id: u64,
+        offered_amount: u64,
+        requested_amount: u64,
+        counterparty: Option<Pubkey>,
+        expires_at: i64,
+    ) -> Result<()> {
+        msg!("Creating OTC offer {}", offer_id);
+        instructions::otc::create_otc_offer(
+            ctx,
+            offer_id,
+            offered_amount,
+            requested_amount,
+            counterparty,
+            expires_at,
+        )
+    }
+
+    #[inline(never)]
+    pub fn accept_offer(ctx: Context<AcceptOffer>) -> Result<()> {
+        msg!("Accepting OTC offer");
+        instructions::otc::accept_offer(ctx)
+    }
+
+    #[inline(never)]
+    pub fn cancel_offer(ctx: Context<CancelOffer>) -> Result<()> {
+        msg!("Cancelling OTC offer");
+        instructions::otc::cancel_offer(ctx)
+    }
+
+    #[inline(never)]
+    pub fn place_redeem_order(
+        ctx: Context<PlaceRedeemOrder>,
+        order_id: u64,
+        amount: u64,
+        min_price: u64,
+        keeper_fee: u64,
+        expires_at: i64,
+    ) -> Result<()> {
+        msg!("Placing redeem order {} for {} at {}", order_id, amount, min_price);
+        instructions::redeem_order::place_redeem_order(
+            ctx,
+            order_id,
+            amount,
+            min_price,
+            keeper_fee,
+            expires_at,
+        )
+    }
+
+    #[inline(never)]
+    pub fn fill_redeem_order(ctx: Context<FillRedeemOrder>) -> Result<()> {
+        msg!("Filling redeem order");
+        instructions::redeem_order::fill_redeem_order(ctx)
+    }
+
+    #[inline(never)]
+    pub fn cancel_redeem_order(ctx: Context<CancelRedeemOrder>) -> Result<()> {
+        msg!("Cancelling redeem order");
+        instructions::redeem_order::cancel_redeem_order(ctx)
+    }
+
+    #[inline(never)]
+    pub fn refresh_price(ctx: Context<RefreshPrice>) -> Result<()> {
+        msg!("Refreshing cached price");
+        instructions::oracle::refresh_price(ctx)
+    }
+
+    #[inline(never)]
+    pub fn set_price_feed(ctx: Context<SetPriceFeed>) -> Result<()> {
+        msg!("Queueing price feed change");
+        instructions::oracle::set_price_feed(ctx)
+    }
+
+    #[inline(never)]
+    pub fn apply_price_feed(ctx: Context<ApplyPriceFeed>) -> Result<()> {
+        msg!("Applying price feed change");
+        instructions::oracle::apply_price_feed(ctx)
+    }
+
+    #[inline(never)]
+    pub fn create_lookup_table(ctx: Context<CreateLookupTable>, recent_slot: u64) -> Result<()> {
+        msg!("Creating address lookup table");
+        instructions::lookup_table::create_lookup_table(ctx, recent_slot)
+    }
+
+    #[inline(never)]
+    pub fn init_share_vault(ctx: Context<InitShareVault>) -> Result<()> {
+        msg!("Initializing share vault");
+        instructions::share_vault::init_share_vault(ctx)
+    }
+
+    #[inline(never)]
+    pub fn deposit_shares(ctx: Context<ShareVaultOperation>, assets: u64) -> Result<()> {
+        msg!("Depositing {} into share vault", assets);
+        instructions::share_vault::deposit_shares(ctx, assets)
+    }
+
+    #[inline(never)]
+    pub fn withdraw_shares(ctx: Context<ShareVaultOperation>, shares: u64) -> Result<()> {
+        msg!("Withdrawing {} shares", shares);
+        instructions::share_vault::withdraw_shares(ctx, shares)
+    }
+
+    #[inline(never)]
+    pub fn init_savings_vault(ctx: Context<InitSavingsVault>) -> Result<()> {
+        msg!("Initializing savings vault");
+        instructions::savings::init_savings_vault(ctx)
+    }
+
+    #[inline(never)]
+    pub fn deposit_savings(ctx: Context<SavingsOperation>, assets: u64) -> Result<()> {
+        msg!("Depositing {} into savings", assets);
+        instructions::savings::deposit_savings(ctx, assets)
+    }
+
+    #[inline(never)]
+    pub fn redeem_savings(ctx: Context<SavingsOperation>, shares: u64) -> Result<()> {
+        msg!("Redeeming {} savings shares", shares);
+        instructions::savings::redeem_savings(ctx, shares)
+    }
+
+    #[inline(never)]
+    pub fn harvest_savings(ctx: Context<HarvestSavings>) -> Result<()> {
+        msg!("Harvesting savings yield");
+        instructions::savings::harvest_savings(ctx)
+    }
+
+    #[inline(never)]
+    pub fn init_staking_pool(ctx: Context<InitStakingPool>, cooldown_seconds: i64) -> Result<()> {
+        msg!("Initializing staking pool with a {}s cooldown", cooldown_seconds);
+        instructions::staking::init_staking_pool(ctx, cooldown_seconds)
+    }
+
+    #[inline(never)]
+    pub fn add_staking_reward(ctx: Context<AddStakingReward>) -> Result<()> {
+        msg!("Adding staking reward stream");
+        instructions::staking::add_staking_reward(ctx)
+    }
+
+    #[inline(never)]
+    pub fn stake(ctx: Context<StakeOperation>, amount: u64) -> Result<()> {
+        msg!("Staking {} tokens", amount);
+        instructions::staking::stake(ctx, amount)
+    }
+
+    #[inline(never)]
+    pub fn request_unstake(ctx: Context<StakeOperation>, amount: u64) -> Result<()> {
+        msg!("Requesting unstake of {} tokens", amount);
+        instructions::staking::request_unstake(ctx, amount)
+    }
+
+    #[inline(never)]
+    pub fn unstake(ctx: Context<StakeOperation>) -> Result<()> {
+        msg!("Unstaking cooled down tokens");
+        instructions::staking::unstake(ctx)
+    }
+
+    #[inline(never)]
+    pub fn claim_staking_rewards(ctx: Context<ClaimStakingRewards>) -> Result<()> {
+        msg!("Claiming staking rewards");
+        instructions::staking::claim_staking_rewards(ctx)
+    }
+
+    #[inline(never)]
+    pub fn distribute_staking_rewards(ctx: Context<DistributeStakingRewards>) -> Result<()> {
+        msg!("Distributing staking rewards");
+        instructions::staking::distribute_staking_rewards(ctx)
+    }
+
+    #[inline(never)]
+    pub fn init_backstop(ctx: Context<InitBackstop>, standing_fee_bps: u16) -> Result<()> {
+        msg!("Initializing backstop with a {} bps standing fee", standing_fee_bps);
+        instructions::backstop::init_backstop(ctx, standing_fee_bps)
+    }
+
+    #[inline(never)]
+    pub fn register_backstop_provider(
+        ctx: Context<RegisterBackstopProvider>,
+        provider: Pubkey,
+    ) -> Result<()> {
+        msg!("Registering backstop provider {}", provider);
+        instructions::backstop::register_backstop_provider(ctx, provider)
+    }
+
+    #[inline(never)]
+    pub fn commit_backstop(ctx: Context<BackstopOperation>, amount: u64) -> Result<()> {
+        msg!("Committing {} collateral to backstop", amount);
+        instructions::backstop::commit_backstop(ctx, amount)
+    }
+
+    #[inline(never)]
+    pub fn withdraw_backstop(ctx: Context<BackstopOperation>, amount: u64) -> Result<()> {
+        msg!("Withdrawing {} collateral from backstop", amount);
+        instructions::backstop::withdraw_backstop(ctx, amount)
+    }
+
+    #[inline(never)]
+    pub fn claim_backstop_fees(ctx: Context<ClaimBackstopFees>) -> Result<()> {
+        msg!("Claiming backstop fees");
+        instructions::backstop::claim_backstop_fees(ctx)
+    }
+
+    #[inline(never)]
+    pub fn pay_backstop_fee(ctx: Context<PayBackstopFee>) -> Result<()> {
+        msg!("Paying backstop standing fee");
+        instructions::backstop::pay_backstop_fee(ctx)
+    }
+
+    #[inline(never)]
+    pub fn repay_backstop(ctx: Context<RepayBackstop>) -> Result<()> {
+        msg!("Repaying backstop");
+        instructions::backstop::repay_backstop(ctx)
+    }
+
+    #[inline(never)]
+    pub fn open_portfolio(ctx: Context<OpenPortfolio>) -> Result<()> {
+        msg!("Opening portfolio");
+        instructions::portfolio::open_portfolio(ctx)
+    }
+
+    #[inline(never)]
+    pub fn close_portfolio(ctx: Context<ClosePortfolio>) -> Result<()> {
+        msg!("Closing portfolio");
+        instructions::portfolio::close_portfolio(ctx)
+    }
+
+    #[inline(never)]
+    pub fn init_junior_tranche(ctx: Context<InitJuniorTranche>, senior_yield_bps: u16) -> Result<()> {
+        msg!("Initializing junior tranche");
+        instructions::tranche::init_junior_tranche(ctx, senior_yield_bps)
+    }
+
+    #[inline(never)]
+    pub fn subscribe_junior(ctx: Context<JuniorOperation>, collateral_amount: u64) -> Result<()> {
+        msg!("Subscribing {} collateral to junior tranche", collateral_amount);
+        instructions::tranche::subscribe_junior(ctx, collateral_amount)
+    }
+
+    #[inline(never)]
+    pub fn redeem_junior(ctx: Context<JuniorOperation>, junior_amount: u64) -> Result<()> {
+        msg!("Redeeming {} junior tokens", junior_amount);
+        instructions::tranche::redeem_junior(ctx, junior_amount)
+    }
+
+    #[inline(never)]
+    pub fn create_basket(ctx: Context<CreateBasket>, components: Vec<BasketComponentParams>) -> Result<()> {
+        msg!("Creating currency basket with {} components", components.len());
+        instructions::basket::create_basket(ctx, components)
+    }
+
+    #[inline(never)]
+    pub fn update_basket_index<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpdateBasketIndex<'info>>,
+    ) -> Result<()> {
+        msg!("Updating basket index");
+        instructions::basket::update_basket_index(ctx)
+    }
+
+    #[inline(never)]
+    pub fn propose_basket_weights(ctx: Context<ProposeBasketWeights>, weights: Vec<u16>) -> Result<()> {
+        msg!("Proposing basket weights");
+        instructions::basket::propose_basket_weights(ctx, weights)
+    }
+
+    #[inline(never)]
+    pub fn apply_basket_weights(ctx: Context<ApplyBasketWeights>) -> Result<()> {
+        msg!("Applying basket weights");
+        instructions::basket::apply_basket_weights(ctx)
+    }
+
+    #[inline(never)]
+    pub fn enable_rebase(ctx: Context<EnableRebase>, mode: RebaseMode) -> Result<()> {
+        msg!("Enabling rebase mode {:?}", mode);
+        instructions::rebase::enable_rebase(ctx, mode)
+    }
+
+    #[inline(never)]
+    pub fn rebase(ctx: Context<Rebase>) -> Result<()> {
+        msg!("Rebasing supply");
+        instructions::rebase::rebase(ctx)
+    }
+
+    #[inline(never)]
+    pub fn collect_demurrage(ctx: Context<CollectDemurrage>) -> Result<()> {
+        msg!("Collecting demurrage");
+        instructions::demurrage::collect_demurrage(ctx)
+    }
+
+    #[inline(never)]
+    pub fn configure_dead_man_switch(
+        ctx: Context<ConfigureDeadManSwitch>,
+        recovery_key: Pubkey,
+        inactivity_days: u16,
+    ) -> Result<()> {
+        msg!("Configuring dead-man switch: {} days", inactivity_days);
+        instructions::dead_man::configure_dead_man_switch(ctx, recovery_key, inactivity_days)
+    }
+
+    #[inline(never)]
+    pub fn heartbeat(ctx: Context<Heartbeat>) -> Result<()> {
+        msg!("Issuer heartbeat");
+        instructions::dead_man::heartbeat(ctx)
+    }
+
+    #[inline(never)]
+    pub fn trigger_dead_man_switch(ctx: Context<TriggerDeadManSwitch>, action: DeadManAction) -> Result<()> {
+        msg!("Triggering dead-man switch: {:?}", action);
+        instructions::dead_man::trigger_dead_man_switch(ctx, action)
+    }
+
+    #[inline(never)]
+    pub fn propose_governance_handover(ctx: Context<ProposeGovernanceHandover>) -> Result<()> {
+        msg!("Proposing governance handover");
+        instructions::governance::propose_governance_handover(ctx)
+    }
+
+    #[inline(never)]
+    pub fn accept_governance(ctx: Context<AcceptGovernance>) -> Result<()> {
+        msg!("Accepting governance");
+        instructions::governance::accept_governance(ctx)
+    }
+
+    #[inline(never)]
+    pub fn release_governance(ctx: Context<ReleaseGovernance>, new_authority: Pubkey) -> Result<()> {
+        msg!("Releasing governance to {}", new_authority);
+        instructions::governance::release_governance(ctx, new_authority)
+    }
+
+    #[inline(never)]
+    pub fn create_council(ctx: Context<CreateCouncil>, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        msg!("Creating {}-of-{} approval council", threshold, signers.len());
+        instructions::council::create_council(ctx, signers, threshold)
+    }
+
+    #[inline(never)]
+    pub fn create_proposal(ctx: Context<CreateProposal>, action: ProposalAction) -> Result<()> {
+        msg!("Creating proposal: {:?}", action);
+        instructions::council::create_proposal(ctx, action)
+    }
+
+    #[inline(never)]
+    pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+        msg!("Approving proposal");
+        instructions::council::approve_proposal(ctx)
+    }
+
+    #[inline(never)]
+    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+        msg!("Executing proposal");
+        instructions::council::execute_proposal(ctx)
+    }
+
+    #[inline(never)]
+    pub fn create_treasury(
+        ctx: Context<CreateTreasury>,
+        treasury_id: u64,
+        operator: Pubkey,
+        daily_limit: u64,
+    ) -> Result<()> {
+        msg!("Creating treasury {} with daily limit {}", treasury_id, daily_limit);
+        instructions::treasury::create_treasury(ctx, treasury_id, operator, daily_limit)
+    }
+
+    #[inline(never)]
+    pub fn set_treasury_operator(ctx: Context<SetTreasuryOperator>, operator: Pubkey, daily_limit: u64) -> Result<()> {
+        msg!("Setting treasury operator, daily limit {}", daily_limit);
+        instructions::treasury::set_treasury_operator(ctx, operator, daily_limit)
+    }
+
+    #[inline(never)]
+    pub fn treasury_transfer(ctx: Context<TreasuryTransfer>, amount: u64) -> Result<()> {
+        msg!("Treasury transfer of {}", amount);
+        instructions::treasury::treasury_transfer(ctx, amount)
+    }
+
+    #[inline(never)]
+    pub fn schedule_transfer(
+        ctx: Context<ScheduleTransfer>,
+        transfer_id: u64,
+        amount: u64,
+        release_time: i64,
+    ) -> Result<()> {
+        msg!("Scheduling transfer {} of {} tokens", transfer_id, amount);
+        instructions::scheduled_transfer::schedule(ctx, transfer_id, amount, release_time)
+    }
+
+    #[inline(never)]
+    pub fn execute_transfer(ctx: Context<ExecuteTransfer>) -> Result<()> {
+        msg!("Executing scheduled transfer");
+        instructions::scheduled_transfer::execute(ctx)
+    }
+
+    #[inline(never)]
+    pub fn cancel_transfer(ctx: Context<CancelTransfer>) -> Result<()> {
+        msg!("Cancelling scheduled transfer");
+        instructions::scheduled_transfer::cancel_transfer(ctx)
+    }
+
+    #[inline(never)]
+    pub fn create_subscription(
+        ctx: Context<CreateSubscription>,
+        amount: u64,
+        interval: i64,
+        allowance: u64,
+    ) -> Result<()> {
+        msg!("Creating subscription of {} every {}s", amount, interval);
+        instructions::subscription::create_subscription(ctx, amount, interval, allowance)
+    }
+
+    #[inline(never)]
+    pub fn collect_subscription(ctx: Context<CollectSubscription>) -> Result<()> {
+        msg!("Collecting subscription payment");
+        instructions::subscription::collect(ctx)
+    }
+
+    #[inline(never)]
+    pub fn cancel_subscription(ctx: Context<CancelSubscription>) -> Result<()> {
+        msg!("Cancelling subscription");
+        instructions::subscription::cancel_subscription(ctx)
+    }
+
+    #[inline(never)]
+    pub fn relayed_mint(ctx: Context<RelayedMint>, order: RelayedOrder) -> Result<()> {
+        msg!("Executing relayed mint {} of {} tokens", order.nonce, order.amount);
+        instructions::relay::relayed_mint(ctx, order)
+    }
+
+    #[inline(never)]
+    pub fn relayed_redeem(ctx: Context<RelayedRedeem>, order: RelayedOrder) -> Result<()> {
+        msg!("Executing relayed redeem {} of {} tokens", order.nonce, order.amount);
+        instructions::relay::relayed_redeem(ctx, order)
+    }
+
+    #[inline(never)]
+    pub fn configure_sponsor(ctx: Context<ConfigureSponsor>, sponsored_actions: u8, lamports_per_tx: u64) -> Result<()> {
+        msg!("Configuring fee sponsorship: actions {:#b}, {} lamports per tx", sponsored_actions, lamports_per_tx);
+        instructions::sponsor::configure_sponsor(ctx, sponsored_actions, lamports_per_tx)
+    }
+
+    #[inline(never)]
+    pub fn fund_sponsor(ctx: Context<FundSponsor>, lamports: u64) -> Result<()> {
+        msg!("Funding sponsor with {} lamports", lamports);
+        instructions::sponsor::fund_sponsor(ctx, lamports)
+    }
+
+    #[inline(never)]
+    pub fn withdraw_sponsor(ctx: Context<FundSponsor>, lamports: u64) -> Result<()> {
+        msg!("Withdrawing {} lamports from sponsor", lamports);
+        instructions::sponsor::withdraw_sponsor(ctx, lamports)
+    }
+
+    #[inline(never)]
+    pub fn claim_sponsorship(ctx: Context<ClaimSponsorship>) -> Result<()> {
+        msg!("Claiming fee sponsorship");
+        instructions::sponsor::claim_sponsorship(ctx)
+    }
+
+    #[inline(never)]
+    pub fn create_dividend(
+        ctx: Context<CreateDividend>,
+        dividend_id: u64,
+        total_amount: u64,
+    ) -> Result<()> {
+        msg!("Creating dividend {} of {} tokens", dividend_id, total_amount);
+        instructions::dividend::create_dividend(ctx, dividend_id, total_amount)
+    }
+
+    #[inline(never)]
+    pub fn claim_dividend(
+        ctx: Context<ClaimDividend>,
+        index: u64,
+        balance: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        msg!("Claiming dividend for snapshot index {}", index);
+        instructions::dividend::claim_dividend(ctx, index, balance, proof)
+    }
+
+    #[inline(never)]
+    pub fn configure_liquidity(
+        ctx: Context<ConfigureLiquidity>,
+        amm_program: Pubkey,
+        pool: Pubkey,
+    ) -> Result<()> {
+        msg!("Configuring protocol-owned liquidity pool {}", pool);
+        instructions::liquidity::configure_liquidity(ctx, amm_program, pool)
+    }
+
+    #[inline(never)]
+    pub fn deploy_liquidity<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ManageLiquidity<'info>>,
+        amount: u64,
+        deposit_data: Vec<u8>,
+    ) -> Result<()> {
+        msg!("Deploying {} surplus as liquidity", amount);
+        instructions::liquidity::deploy_liquidity(ctx, amount, deposit_data)
+    }
+
+    #[inline(never)]
+    pub fn withdraw_liquidity<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ManageLiquidity<'info>>,
+        lp_amount: u64,
+        withdraw_data: Vec<u8>,
+    ) -> Result<()> {
+        msg!("Withdrawing {} LP tokens of protocol-owned liquidity", lp_amount);
+        instructions::liquidity::withdraw_liquidity(ctx, lp_amount, withdraw_data)
+    }
+
+    #[inline(never)]
+    pub fn initialize_protocol(
+        ctx: Context<InitializeProtocol>,
+        params: ProtocolConfigParams,
+    ) -> Result<()> {
+        msg!("Initializing protocol config");
+        instructions::protocol::initialize_protocol(ctx, params)
+    }
+
+    #[inline(never)]
+    pub fn update_protocol_config(
+        ctx: Context<UpdateProtocolConfig>,
+        params: ProtocolConfigParams,
+    ) -> Result<()> {
+        msg!("Updating protocol config");
+        instructions::protocol::update_protocol_config(ctx, params)
+    }
+
+    #[inline(never)]
+    pub fn verify_issuer(ctx: Context<VerifyIssuer>, verified: bool) -> Result<()> {
+        msg!("Setting issuer verification: {}", verified);
+        instructions::protocol::verify_issuer(ctx, verified)
+    }
+
+    #[inline(never)]
+    pub fn update_issuer_profile(
+        ctx: Context<UpdateIssuerProfile>,
+        params: IssuerProfileParams,
+    ) -> Result<()> {
+        msg!("Updating issuer profile");
+        instructions::issuer_profile::update_issuer_profile(ctx, params)
+    }
+
+    #[inline(never)]
+    pub fn post_bond(ctx: Context<PostBond>, amount: u64) -> Result<()> {
+        msg!("Posting issuer bond of {}", amount);
+        instructions::issuer_bond::post_bond(ctx, amount)
+    }
+
+    #[inline(never)]
+    pub fn request_bond_withdrawal(ctx: Context<RequestBondWithdrawal>, amount: u64) -> Result<()> {
+        msg!("Unbonding {} of the issuer bond", amount);
+        instructions::issuer_bond::request_bond_withdrawal(ctx, amount)
+    }
+
+    #[inline(never)]
+    pub fn withdraw_bond(ctx: Context<WithdrawBond>) -> Result<()> {
+        msg!("Withdrawing unbonded issuer bond");
+        instructions::issuer_bond::withdraw_bond(ctx)
+    }
+
+    #[inline(never)]
+    pub fn slash_bond(ctx: Context<SlashBond>, amount: u64, reason: [u8; 32]) -> Result<()> {
+        msg!("Slashing {} of the issuer bond", amount);
+        instructions::issuer_bond::slash_bond(ctx, amount, reason)
+    }
+
+    #[inline(never)]
+    pub fn set_protocol_pause(
+        ctx: Context<SetProtocolPause>,
+        paused: bool,
+        paused_features: u32,
+    ) -> Result<()> {
+        msg!("Setting protocol pause: {} (features {:#x})", paused, paused_features);
+        instructions::protocol::set_protocol_pause(ctx, paused, paused_features)
+    }
+
+    #[inline(never)]
+    pub fn initialize_reserved_names(
+        ctx: Context<InitializeReservedNames>,
+        hashes: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        msg!("Initializing reserved names");
+        instructions::reserved_names::initialize_reserved_names(ctx, hashes)
+    }
+
+    #[inline(never)]
+    pub fn update_reserved_names(
+        ctx: Context<UpdateReservedNames>,
+        add: Vec<[u8; 32]>,
+        remove: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        msg!("Updating reserved names");
+        instructions::reserved_names::update_reserved_names(ctx, add, remove)
+    }
+
+    #[inline(never)]
+    pub fn update_metadata(
+        ctx: Context<UpdateMetadata>,
+        params: UpdateMetadataParams,
+    ) -> Result<()> {
+        msg!("Updating metadata");
+        instructions::update::update_metadata(ctx, params)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,266 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::constants::{BASIS_POINTS_DIVISOR, SECONDS_PER_YEAR};
+use crate::error::StableFunError;
+
+/// Per-stablecoin ledger of realized surplus and bad debt, in stablecoin units.
+#[account]
+#[derive(Debug, Default)]
+pub struct SystemAccounting {
+    pub stablecoin_mint: Pubkey,
+    /// Fees and liquidation penalties not yet withdrawn or used to cover bad debt
+    pub surplus: u64,
+    /// Debt left on positions whose collateral was exhausted
+    pub bad_debt: u64,
+    pub total_fees_withdrawn: u64,
+    pub total_bad_debt_settled: u64,
+    /// Bond yield realized into the vault by `sync_vault`, cumulative
+    pub harvested_yield: u64,
+    /// `harvested_yield` as of the last interest rate update
+    pub yield_checkpoint: u64,
+    pub yield_checkpoint_at: i64,
+    /// Surplus currently deployed as protocol-owned liquidity
+    pub protocol_owned_liquidity: u64,
+    pub bump: u8,
+    /// Mint and redeem fees booked as surplus, cumulative
+    pub fees_collected: u64,
+    /// `fees_collected` as of the last staking reward distribution
+    pub staking_checkpoint: u64,
+}
+
+impl StateAccount for SystemAccounting {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        8 +               // surplus
+        8 +               // bad_debt
+        8 +               // total_fees_withdrawn
+        8 +               // total_bad_debt_settled
+        8 +               // harvested_yield
+        8 +               // yield_checkpoint
+        8 +               // yield_checkpoint_at
+        8 +               // protocol_owned_liquidity
+        1 +               // bump
+        8 +               // fees_collected
+        8;               // staking_checkpoint
+}
+
+impl SystemAccounting {
+    pub fn is_initialized(&self) -> bool {
+        self.stablecoin_mint != Pubkey::default()
+    }
+
+    pub fn initialize(&mut self, stablecoin_mint: Pubkey, bump: u8) {
+        self.stablecoin_mint = stablecoin_mint;
+        self.bump = bump;
+    }
+
+    pub fn record_surplus(&mut self, amount: u64) -> Result<()> {
+        self.surplus = self.surplus
+            .checked_add(amount)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        Ok(())
+    }
+
+    /// A mint or redeem fee: surplus that also counts towards the stakers'
+    /// revenue share.
+    pub fn record_fee(&mut self, amount: u64) -> Result<()> {
+        self.record_surplus(amount)?;
+        self.fees_collected = self.fees_collected
+            .checked_add(amount)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        Ok(())
+    }
+
+    /// Stakers' `share_bps` of the fees collected since the last distribution.
+    pub fn staking_share(&self, share_bps: u16) -> u64 {
+        let fees = self.fees_collected.saturating_sub(self.staking_checkpoint);
+        (fees as u128 * share_bps as u128 / BASIS_POINTS_DIVISOR as u128) as u64
+    }
+
+    pub fn record_bad_debt(&mut self, amount: u64) -> Result<()> {
+        self.bad_debt = self.bad_debt
+            .checked_add(amount)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        Ok(())
+    }
+
+    /// Harvested bond yield is surplus that also drives the savings rate.
+    pub fn record_harvested_yield(&mut self, amount: u64) -> Result<()> {
+        self.record_surplus(amount)?;
+        self.harvested_yield = self.harvested_yield
+            .checked_add(amount)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        Ok(())
+    }
+
+    /// Takes `fee_bps` of harvested yield as plain surplus for the issuer and
+    /// books the rest as harvested yield. Returns the fee.
+    pub fn record_harvested_yield_after_fee(&mut self, amount: u64, fee_bps: u16) -> Result<u64> {
+        let fee = (amount as u128 * fee_bps as u128 / BASIS_POINTS_DIVISOR as u128) as u64;
+        self.record_surplus(fee)?;
+        self.record_harvested_yield(amount - fee)?;
+        Ok(fee)
+    }
+
+    /// Annualized yield harvested since the last checkpoint, in basis points
+    /// of `supply`. `period_start` is used when no checkpoint exists yet.
+    pub fn annualized_yield_bps(&self, supply: u64, period_start: i64, now: i64) -> Result<u64> {
+        let since = if self.yield_checkpoint_at > 0 {
+            self.yield_checkpoint_at
+        } else {
+            period_start
+        };
+        let elapsed = now.saturating_sub(since);
+        require!(elapsed > 0, StableFunError::InterestRatePeriodTooShort);
+        if supply == 0 {
+            return Ok(0);
+        }
+
+        let harvested = self.harvested_yield.saturating_sub(self.yield_checkpoint) as u128;
+        let bps = harvested
+            .checked_mul(BASIS_POINTS_DIVISOR as u128 * SECONDS_PER_YEAR as u128)
+            .and_then(|v| v.checked_div(supply as u128 * elapsed as u128))
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        Ok(u64::try_from(bps).unwrap_or(u64::MAX))
+    }
+
+    pub fn checkpoint_yield(&mut self, now: i64) {
+        self.yield_checkpoint = self.harvested_yield;
+        self.yield_checkpoint_at = now;
+    }
+
+    /// Covers as much bad debt as possible out of surplus, returning the amount netted.
+    pub fn settle_bad_debt(&mut self) -> Result<u64> {
+        let settled = self.surplus.min(self.bad_debt);
+        self.surplus -= settled;
+        self.bad_debt -= settled;
+        self.total_bad_debt_settled = self.total_bad_debt_settled
+            .checked_add(settled)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        Ok(settled)
+    }
+
+    /// Surplus the issuer may withdraw: outstanding bad debt is netted first
+    /// and `buffer` always stays behind.
+    pub fn withdrawable(&self, buffer: u64) -> u64 {
+        self.surplus
+            .saturating_sub(self.bad_debt)
+            .saturating_sub(buffer)
+    }
+
+    pub fn withdraw_fees(&mut self, amount: u64, buffer: u64) -> Result<()> {
+        require!(
+            amount <= self.withdrawable(buffer),
+            StableFunError::InsufficientSurplus
+        );
+        self.surplus -= amount;
+        self.total_fees_withdrawn = self.total_fees_withdrawn
+            .checked_add(amount)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        Ok(())
+    }
+
+    /// Moves withdrawable surplus into protocol-owned liquidity.
+    pub fn deploy_liquidity(&mut self, amount: u64, buffer: u64) -> Result<()> {
+        require!(
+            amount <= self.withdrawable(buffer),
+            StableFunError::InsufficientSurplus
+        );
+        self.surplus -= amount;
+        self.protocol_owned_liquidity = self.protocol_owned_liquidity
+            .checked_add(amount)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        Ok(())
+    }
+
+    /// Books stablecoins pulled back out of the pool as surplus again. More
+    /// can come back than was deployed once the pool has taken fees.
+    pub fn recall_liquidity(&mut self, amount: u64) -> Result<()> {
+        self.protocol_owned_liquidity = self.protocol_owned_liquidity.saturating_sub(amount);
+        self.record_surplus(amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settle_bad_debt() {
+        let mut accounting = SystemAccounting::default();
+        accounting.record_surplus(1_000).unwrap();
+        accounting.record_bad_debt(1_500).unwrap();
+
+        assert_eq!(accounting.settle_bad_debt().unwrap(), 1_000);
+        assert_eq!(accounting.surplus, 0);
+        assert_eq!(accounting.bad_debt, 500);
+        assert_eq!(accounting.total_bad_debt_settled, 1_000);
+    }
+
+    #[test]
+    fn test_withdraw_respects_buffer() {
+        let mut accounting = SystemAccounting::default();
+        accounting.record_surplus(10_000).unwrap();
+        accounting.record_bad_debt(2_000).unwrap();
+
+        assert_eq!(accounting.withdrawable(5_000), 3_000);
+        assert!(accounting.withdraw_fees(3_001, 5_000).is_err());
+        accounting.withdraw_fees(3_000, 5_000).unwrap();
+        assert_eq!(accounting.surplus, 7_000);
+        assert_eq!(accounting.withdrawable(5_000), 0);
+    }
+
+    #[test]
+    fn test_annualized_yield() {
+        let mut accounting = SystemAccounting::default();
+        accounting.record_harvested_yield(50_000).unwrap();
+        assert_eq!(accounting.surplus, 50_000);
+
+        // 50k harvested on 1M supply over half a year is 10% APR
+        let half_year = SECONDS_PER_YEAR / 2;
+        assert_eq!(accounting.annualized_yield_bps(1_000_000, 0, half_year).unwrap(), 1_000);
+        assert_eq!(accounting.annualized_yield_bps(0, 0, half_year).unwrap(), 0);
+
+        accounting.checkpoint_yield(half_year);
+        assert!(accounting.annualized_yield_bps(1_000_000, 0, half_year).is_err());
+        assert_eq!(accounting.annualized_yield_bps(1_000_000, 0, SECONDS_PER_YEAR).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_protocol_owned_liquidity() {
+        let mut accounting = SystemAccounting::default();
+        accounting.record_surplus(1_000).unwrap();
+
+        assert!(accounting.deploy_liquidity(901, 100).is_err());
+        accounting.deploy_liquidity(900, 100).unwrap();
+        assert_eq!(accounting.surplus, 100);
+        assert_eq!(accounting.protocol_owned_liquidity, 900);
+
+        accounting.recall_liquidity(950).unwrap();
+        assert_eq!(accounting.surplus, 1_050);
+        assert_eq!(accounting.protocol_owned_liquidity, 0);
+    }
+
+    #[test]
+    fn test_yield_fee() {
+        let mut accounting = SystemAccounting::default();
+        assert_eq!(accounting.record_harvested_yield_after_fee(10_000, 1_500).unwrap(), 1_500);
+
+        // The fee is surplus but does not count towards the savings rate
+        assert_eq!(accounting.surplus, 10_000);
+        assert_eq!(accounting.harvested_yield, 8_500);
+    }
+
+    #[test]
+    fn test_staking_share() {
+        let mut accounting = SystemAccounting::default();
+        accounting.record_fee(10_000).unwrap();
+        accounting.record_surplus(5_000).unwrap();
+        assert_eq!(accounting.surplus, 15_000);
+
+        // Penalties and other surplus are not fee revenue
+        assert_eq!(accounting.staking_share(2_000), 2_000);
+        accounting.staking_checkpoint = accounting.fees_collected;
+        assert_eq!(accounting.staking_share(2_000), 0);
+    }
+}
@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::error::StableFunError;
+use crate::utils::merkle;
+
+/// A funded stablecoin distribution claimable against a Merkle root.
+#[account]
+#[derive(Debug, Default)]
+pub struct Airdrop {
+    pub stablecoin_mint: Pubkey,
+    pub authority: Pubkey,
+    pub airdrop_id: u64,
+    /// Root over `merkle::leaf_hash(index, recipient, amount)` leaves
+    pub merkle_root: [u8; 32],
+    /// Distributor token account holding the undistributed stablecoins
+    pub token_account: Pubkey,
+    pub max_recipients: u32,
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub claimed_count: u32,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl StateAccount for Airdrop {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        PUBKEY_LENGTH +    // authority
+        8 +               // airdrop_id
+        32 +              // merkle_root
+        PUBKEY_LENGTH +    // token_account
+        4 +               // max_recipients
+        8 +               // total_amount
+        8 +               // claimed_amount
+        4 +               // claimed_count
+        8 +               // created_at
+        1;               // bump
+}
+
+impl Airdrop {
+    pub fn verify_claim(&self, index: u64, recipient: &Pubkey, amount: u64, proof: &[[u8; 32]]) -> bool {
+        merkle::verify_proof(
+            proof,
+            &self.merkle_root,
+            merkle::leaf_hash(index, recipient, amount),
+        )
+    }
+
+    pub fn record_claim(&mut self, amount: u64) -> Result<()> {
+        let claimed_amount = self.claimed_amount
+            .checked_add(amount)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        require!(claimed_amount <= self.total_amount, StableFunError::InsufficientBalance);
+        self.claimed_amount = claimed_amount;
+        self.claimed_count = self.claimed_count
+            .checked_add(1)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        Ok(())
+    }
+}
+
+/// One bit per airdrop leaf index, set once the leaf has been claimed.
+/// Dividends reuse it over their snapshot's leaf indices.
+#[account]
+#[derive(Debug, Default)]
+pub struct ClaimBitmap {
+    /// The airdrop or dividend the bits belong to
+    pub airdrop: Pubkey,
+    pub bump: u8,
+    pub bits: Vec<u8>,
+}
+
+impl ClaimBitmap {
+    pub fn space(max_recipients: u32) -> usize {
+        DISCRIMINATOR_LENGTH +
+            PUBKEY_LENGTH +    // airdrop
+            1 +               // bump
+            4 +               // bits length prefix
+            Self::byte_len(max_recipients)
+    }
+
+    pub fn byte_len(max_recipients: u32) -> usize {
+        (max_recipients as usize).div_ceil(8)
+    }
+
+    pub fn is_claimed(&self, index: u64) -> bool {
+        self.bits
+            .get((index / 8) as usize)
+            .is_some_and(|byte| byte & (1 << (index % 8)) != 0)
+    }
+
+    /// Marks `index` as claimed, failing if it is out of range or already set.
+    pub fn set_claimed(&mut self, index: u64) -> Result<()> {
+        let byte = self.bits
+            .get_mut((index / 8) as usize)
+            .ok_or(error!(StableFunError::InvalidAirdropIndex))?;
+        let mask = 1 << (index % 8);
+        require!(*byte & mask == 0, StableFunError::AirdropAlreadyClaimed);
+        *byte |= mask;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_claim_bitmap() {
+        let mut bitmap = ClaimBitmap {
+            airdrop: Pubkey::new_unique(),
+            bump: 255,
+            bits: vec![0; ClaimBitmap::byte_len(10)],
+        };
+        assert_eq!(bitmap.bits.len(), 2);
+
+        assert!(!bitmap.is_claimed(9));
+        bitmap.set_claimed(9).unwrap();
+        assert!(bitmap.is_claimed(9));
+        assert!(!bitmap.is_claimed(8));
+        assert!(bitmap.set_claimed(9).is_err()); // double claim
+        assert!(bitmap.set_claimed(16).is_err()); // out of range
+    }
+
+    #[test]
+    fn test_record_claim() {
+        let mut airdrop = Airdrop {
+            total_amount: 1_000,
+            ..Default::default()
+        };
+        airdrop.record_claim(600).unwrap();
+        assert!(airdrop.record_claim(401).is_err());
+        airdrop.record_claim(400).unwrap();
+        assert_eq!(airdrop.claimed_count, 2);
+    }
+}
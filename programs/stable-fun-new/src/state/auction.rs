@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+
+/// A seized non-fungible bond certificate up for auction. The PDA holds the
+/// certificate in escrow and receives the proceeds, so the auction program
+/// never sees the vault.
+#[account]
+#[derive(Debug, Default)]
+pub struct AuctionListing {
+    pub stablecoin_mint: Pubkey,
+    /// Mint of the certificate (zero decimals, supply of one)
+    pub certificate_mint: Pubkey,
+    /// Auction program the listing instruction was sent to
+    pub auction_program: Pubkey,
+    /// Escrow holding the certificate while it is listed
+    pub escrow_account: Pubkey,
+    /// Collateral account the winning bid is paid into
+    pub proceeds_account: Pubkey,
+    /// Lowest proceeds, in collateral units, the listing settles for
+    pub min_proceeds: u64,
+    pub listed_at: i64,
+    pub bump: u8,
+}
+
+impl StateAccount for AuctionListing {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        PUBKEY_LENGTH +    // certificate_mint
+        PUBKEY_LENGTH +    // auction_program
+        PUBKEY_LENGTH +    // escrow_account
+        PUBKEY_LENGTH +    // proceeds_account
+        8 +               // min_proceeds
+        8 +               // listed_at
+        1;               // bump
+}
+
+impl AuctionListing {
+    /// Whether the auction has run: the certificate left escrow and at
+    /// least `min_proceeds` came back for it.
+    pub fn is_sold(&self, escrow_balance: u64, proceeds: u64) -> bool {
+        escrow_balance == 0 && proceeds >= self.min_proceeds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_sold() {
+        let listing = AuctionListing {
+            min_proceeds: 1_000,
+            ..Default::default()
+        };
+        assert!(listing.is_sold(0, 1_000));
+        assert!(!listing.is_sold(0, 999));
+        // Still in escrow: the auction has not run, whatever was paid in
+        assert!(!listing.is_sold(1, 5_000));
+    }
+}
@@ -0,0 +1,176 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::constants::{BASIS_POINTS_DIVISOR, SECONDS_PER_YEAR};
+use crate::error::StableFunError;
+
+/// Scale of `Backstop::fee_per_unit`
+pub const FEE_PER_UNIT_SCALE: u128 = 1_000_000_000_000;
+
+/// Collateral designated LPs pre-commit to a coin. Instant redeems the
+/// vault's liquidity buffer cannot honor are paid from it; the vault owes
+/// the draw back and repays it as collateral sales restore its liquidity.
+#[account]
+#[derive(Debug, Default)]
+pub struct Backstop {
+    pub stablecoin_mint: Pubkey,
+    /// Stablebond account owned by this PDA
+    pub collateral_account: Pubkey,
+    /// Stablecoin account holding standing fees not yet claimed
+    pub fee_vault: Pubkey,
+    /// Annual fee on committed collateral, paid from the coin's surplus
+    pub standing_fee_bps: u16,
+    /// Collateral committed by providers, including what is drawn
+    pub total_committed: u64,
+    /// Paid out to redeemers and not yet repaid by the vault
+    pub drawn: u64,
+    /// Raw stablecoin fee units earned per committed collateral unit,
+    /// scaled by `FEE_PER_UNIT_SCALE`
+    pub fee_per_unit: u128,
+    pub last_fee_at: i64,
+    pub total_fees_paid: u64,
+    pub bump: u8,
+}
+
+impl StateAccount for Backstop {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        PUBKEY_LENGTH +    // collateral_account
+        PUBKEY_LENGTH +    // fee_vault
+        2 +               // standing_fee_bps
+        8 +               // total_committed
+        8 +               // drawn
+        16 +              // fee_per_unit
+        8 +               // last_fee_at
+        8 +               // total_fees_paid
+        1;               // bump
+}
+
+impl Backstop {
+    /// Committed collateral still in the backstop account.
+    pub fn liquid(&self) -> u64 {
+        self.total_committed.saturating_sub(self.drawn)
+    }
+
+    pub fn draw(&mut self, amount: u64) -> Result<()> {
+        require!(amount <= self.liquid(), StableFunError::InsufficientBackstop);
+        self.drawn += amount;
+        Ok(())
+    }
+
+    /// Books up to `amount` repaid by the vault, returning what was owed.
+    pub fn repay(&mut self, amount: u64) -> u64 {
+        let repaid = amount.min(self.drawn);
+        self.drawn -= repaid;
+        repaid
+    }
+
+    /// Standing fee owed since `last_fee_at` on committed collateral worth
+    /// `committed_value` stablecoin units.
+    pub fn standing_fee(&self, committed_value: u64, now: i64) -> u64 {
+        let elapsed = now.saturating_sub(self.last_fee_at).max(0) as u128;
+        let fee = committed_value as u128 * self.standing_fee_bps as u128 * elapsed
+            / (BASIS_POINTS_DIVISOR as u128 * SECONDS_PER_YEAR as u128);
+        u64::try_from(fee).unwrap_or(u64::MAX)
+    }
+
+    /// Spreads `amount` of fees over the collateral committed now.
+    pub fn distribute(&mut self, amount: u64) -> Result<()> {
+        require!(self.total_committed > 0, StableFunError::InvalidAmount);
+        self.fee_per_unit = (amount as u128 * FEE_PER_UNIT_SCALE / self.total_committed as u128)
+            .checked_add(self.fee_per_unit)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        self.total_fees_paid = self.total_fees_paid
+            .checked_add(amount)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        Ok(())
+    }
+}
+
+/// An LP designated by the issuer, and what it has committed and earned.
+#[account]
+#[derive(Debug, Default)]
+pub struct BackstopProvider {
+    pub stablecoin_mint: Pubkey,
+    pub provider: Pubkey,
+    pub committed: u64,
+    /// The backstop's `fee_per_unit` when last settled
+    pub fee_per_unit_paid: u128,
+    /// Settled but not yet claimed
+    pub accrued_fees: u64,
+    pub bump: u8,
+}
+
+impl StateAccount for BackstopProvider {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        PUBKEY_LENGTH +    // provider
+        8 +               // committed
+        16 +              // fee_per_unit_paid
+        8 +               // accrued_fees
+        1;               // bump
+}
+
+impl BackstopProvider {
+    /// Accrues what `committed` earned since the last settlement. Must run
+    /// before `committed` changes.
+    pub fn settle(&mut self, backstop: &Backstop) -> Result<()> {
+        let earned = (backstop.fee_per_unit - self.fee_per_unit_paid)
+            * self.committed as u128
+            / FEE_PER_UNIT_SCALE;
+        self.accrued_fees = u64::try_from(earned)
+            .ok()
+            .and_then(|earned| self.accrued_fees.checked_add(earned))
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        self.fee_per_unit_paid = backstop.fee_per_unit;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_draw_and_repay() {
+        let mut backstop = Backstop {
+            total_committed: 1_000,
+            ..Default::default()
+        };
+        backstop.draw(700).unwrap();
+        assert_eq!(backstop.liquid(), 300);
+        assert!(backstop.draw(301).is_err());
+
+        assert_eq!(backstop.repay(500), 500);
+        assert_eq!(backstop.repay(500), 200);
+        assert_eq!(backstop.liquid(), 1_000);
+    }
+
+    #[test]
+    fn test_standing_fee_accrual() {
+        let mut backstop = Backstop {
+            standing_fee_bps: 200,
+            ..Default::default()
+        };
+        // 2% a year on 1,000,000 for half a year
+        assert_eq!(backstop.standing_fee(1_000_000, SECONDS_PER_YEAR / 2), 10_000);
+        assert!(backstop.distribute(10_000).is_err());
+
+        let mut alice = BackstopProvider::default();
+        let mut bob = BackstopProvider::default();
+        alice.settle(&backstop).unwrap();
+        alice.committed = 300;
+        backstop.total_committed = 300;
+        backstop.distribute(600).unwrap();
+
+        bob.settle(&backstop).unwrap();
+        bob.committed = 100;
+        backstop.total_committed = 400;
+        backstop.distribute(400).unwrap();
+
+        alice.settle(&backstop).unwrap();
+        bob.settle(&backstop).unwrap();
+        assert_eq!(alice.accrued_fees, 900);
+        assert_eq!(bob.accrued_fees, 100);
+        assert_eq!(backstop.total_fees_paid, 1_000);
+    }
+}
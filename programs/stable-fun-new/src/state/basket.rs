@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::constants::{BASIS_POINTS_DIVISOR, MAX_BASKET_COMPONENTS, PRICE_SCALE};
+use crate::error::StableFunError;
+use crate::utils::currency::CURRENCY_CODE_LENGTH;
+use crate::utils::oracle::MAX_PRICE_STALENESS;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct BasketComponent {
+    /// ISO-4217 code
+    pub currency: [u8; CURRENCY_CODE_LENGTH],
+    pub weight_bps: u16,
+    /// Feed quoting one unit of `currency` in the collateral feed's quote
+    /// currency; the default pubkey marks that quote currency itself
+    pub fx_feed: Pubkey,
+}
+
+impl BasketComponent {
+    pub const LEN: usize =
+        CURRENCY_CODE_LENGTH + // currency
+        2 + // weight_bps
+        PUBKEY_LENGTH; // fx_feed
+}
+
+/// Weighted currency basket a stablecoin is pegged to (e.g. 60% USD / 40% MXN).
+/// The collateral feed of a basket coin quotes in the basket's base currency.
+#[account]
+#[derive(Debug, Default)]
+pub struct CurrencyBasket {
+    pub stablecoin_mint: Pubkey,
+    pub components: Vec<BasketComponent>,
+    /// Base-currency value of one basket unit, with `PRICE_DECIMALS` decimals
+    pub index_price: u64,
+    pub index_updated_at: i64,
+    /// Weights waiting out the rebalance timelock (empty when none)
+    pub pending_weights: Vec<u16>,
+    pub pending_effective_at: i64,
+    pub bump: u8,
+}
+
+impl StateAccount for CurrencyBasket {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        4 + MAX_BASKET_COMPONENTS * BasketComponent::LEN + // components
+        8 +               // index_price
+        8 +               // index_updated_at
+        4 + MAX_BASKET_COMPONENTS * 2 + // pending_weights
+        8 +               // pending_effective_at
+        1;               // bump
+}
+
+impl CurrencyBasket {
+    pub fn validate_weights(weights: &[u16]) -> Result<()> {
+        require!(
+            !weights.is_empty()
+                && weights.len() <= MAX_BASKET_COMPONENTS
+                && weights.iter().all(|weight| *weight > 0)
+                && weights.iter().map(|weight| *weight as u32).sum::<u32>() == BASIS_POINTS_DIVISOR as u32,
+            StableFunError::InvalidBasket
+        );
+        Ok(())
+    }
+
+    /// Base-currency value of one basket unit given each component's FX
+    /// price, in component order.
+    pub fn compute_index(&self, fx_prices: &[u64]) -> Result<u64> {
+        require!(
+            fx_prices.len() == self.components.len(),
+            StableFunError::InvalidBasket
+        );
+        let index = self.components
+            .iter()
+            .zip(fx_prices)
+            .map(|(component, price)| component.weight_bps as u128 * *price as u128)
+            .sum::<u128>()
+            / BASIS_POINTS_DIVISOR as u128;
+        require!(index > 0, StableFunError::InvalidOraclePrice);
+        u64::try_from(index).map_err(|_| error!(StableFunError::MathOverflow))
+    }
+
+    /// Converts collateral per base-currency unit into collateral per basket
+    /// unit, the price the `math` amount helpers take.
+    pub fn price_in_basket(&self, base_price: u64, now: i64) -> Result<u64> {
+        require!(
+            self.index_price > 0 && now.saturating_sub(self.index_updated_at) <= MAX_PRICE_STALENESS,
+            StableFunError::StaleBasketIndex
+        );
+        let price = base_price as u128 * self.index_price as u128 / PRICE_SCALE as u128;
+        u64::try_from(price).map_err(|_| error!(StableFunError::MathOverflow))
+    }
+
+    pub fn apply_pending_weights(&mut self, now: i64) -> Result<()> {
+        require!(!self.pending_weights.is_empty(), StableFunError::InvalidBasket);
+        require!(now >= self.pending_effective_at, StableFunError::BasketTimelockActive);
+        for (component, weight) in self.components.iter_mut().zip(&self.pending_weights) {
+            component.weight_bps = *weight;
+        }
+        self.pending_weights.clear();
+        self.pending_effective_at = 0;
+        // The index must be recomputed with the new weights before use
+        self.index_price = 0;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usd_mxn_basket() -> CurrencyBasket {
+        CurrencyBasket {
+            components: vec![
+                BasketComponent { currency: *b"USD", weight_bps: 6000, fx_feed: Pubkey::default() },
+                BasketComponent { currency: *b"MXN", weight_bps: 4000, fx_feed: Pubkey::new_unique() },
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_basket_index() {
+        let mut basket = usd_mxn_basket();
+        // 0.6 * 1.00 + 0.4 * 0.05 USD
+        let index = basket.compute_index(&[1_000_000, 50_000]).unwrap();
+        assert_eq!(index, 620_000);
+        assert!(basket.compute_index(&[1_000_000]).is_err());
+
+        basket.index_price = index;
+        basket.index_updated_at = 100;
+        // At 2 collateral per USD a basket unit worth 0.62 USD takes 1.24
+        assert_eq!(basket.price_in_basket(2_000_000, 100).unwrap(), 1_240_000);
+        assert!(basket.price_in_basket(1_240_000, 100 + MAX_PRICE_STALENESS + 1).is_err());
+    }
+
+    #[test]
+    fn test_weight_rebalance() {
+        assert!(CurrencyBasket::validate_weights(&[6000, 4000]).is_ok());
+        assert!(CurrencyBasket::validate_weights(&[6000, 3000]).is_err());
+        assert!(CurrencyBasket::validate_weights(&[10000, 0]).is_err());
+
+        let mut basket = usd_mxn_basket();
+        basket.pending_weights = vec![5000, 5000];
+        basket.pending_effective_at = 1_000;
+        assert!(basket.apply_pending_weights(999).is_err());
+        basket.apply_pending_weights(1_000).unwrap();
+        assert_eq!(basket.components[1].weight_bps, 5000);
+        assert!(basket.pending_weights.is_empty());
+    }
+}
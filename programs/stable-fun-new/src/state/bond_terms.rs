@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::error::StableFunError;
+use crate::utils::stablebond::{MAX_BOND_DURATION, MIN_BOND_DURATION};
+
+/// Yield terms of the stablebond backing a coin, set once per bond by the
+/// coin's authority. Bond tokens appreciate at `annual_yield` from
+/// `issued_at` until maturity, and mints and redeems price collateral net
+/// of what they have accrued.
+#[account]
+#[derive(Debug, Default)]
+pub struct BondTerms {
+    pub stablecoin_mint: Pubkey,
+    /// Bond the terms describe; terms for a bond the coin no longer holds
+    /// are ignored
+    pub stablebond_mint: Pubkey,
+    /// With `YIELD_DECIMALS` decimals
+    pub annual_yield: u64,
+    pub issued_at: i64,
+    pub maturity_timestamp: i64,
+    /// Decimals of the bond token
+    pub decimals: u8,
+    pub bump: u8,
+}
+
+impl StateAccount for BondTerms {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        PUBKEY_LENGTH +    // stablebond_mint
+        8 +               // annual_yield
+        8 +               // issued_at
+        8 +               // maturity_timestamp
+        1 +               // decimals
+        1;               // bump
+}
+
+impl BondTerms {
+    /// Whether terms were already set for `stablebond_mint`.
+    pub fn is_set_for(&self, stablebond_mint: &Pubkey) -> bool {
+        self.stablebond_mint == *stablebond_mint
+    }
+
+    pub fn validate(annual_yield: u64, issued_at: i64, maturity_timestamp: i64) -> Result<()> {
+        let duration = maturity_timestamp.saturating_sub(issued_at);
+        require!(
+            annual_yield > 0 && (MIN_BOND_DURATION..=MAX_BOND_DURATION).contains(&duration),
+            StableFunError::InvalidBondTerms
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_terms() {
+        assert!(BondTerms::validate(50_000, 0, MAX_BOND_DURATION).is_ok());
+        assert!(BondTerms::validate(50_000, 0, MIN_BOND_DURATION).is_ok());
+        assert!(BondTerms::validate(0, 0, MAX_BOND_DURATION).is_err());
+        assert!(BondTerms::validate(50_000, 0, MIN_BOND_DURATION - 1).is_err());
+        assert!(BondTerms::validate(50_000, 0, MAX_BOND_DURATION + 1).is_err());
+        assert!(BondTerms::validate(50_000, 100, 0).is_err());
+    }
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::constants::BASIS_POINTS_DIVISOR;
+
+/// Buyback-and-burn settings for one stablecoin. The PDA owns the escrow
+/// token accounts the swap runs through, so the AMM never sees the vault.
+#[account]
+#[derive(Debug, Default)]
+pub struct BuybackConfig {
+    pub stablecoin_mint: Pubkey,
+    /// AMM program the swap instruction is sent to
+    pub amm_program: Pubkey,
+    /// Highest price paid per stablecoin, in bps of peg
+    pub max_price_bps: u16,
+    /// Escrow for the collateral being sold
+    pub collateral_account: Pubkey,
+    /// Escrow receiving the bought stablecoins before they are burned
+    pub token_account: Pubkey,
+    /// Surplus (stablecoin units) spent on buybacks, cumulative
+    pub total_spent: u64,
+    pub total_burned: u64,
+    pub bump: u8,
+}
+
+impl StateAccount for BuybackConfig {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        PUBKEY_LENGTH +    // amm_program
+        2 +               // max_price_bps
+        PUBKEY_LENGTH +    // collateral_account
+        PUBKEY_LENGTH +    // token_account
+        8 +               // total_spent
+        8 +               // total_burned
+        1;               // bump
+}
+
+impl BuybackConfig {
+    /// Whether buying `received` stablecoins for `spent_value` of collateral
+    /// paid no more than `max_price_bps` of peg per coin.
+    pub fn within_band(spent_value: u64, received: u64, max_price_bps: u16) -> bool {
+        received > 0
+            && spent_value as u128 * BASIS_POINTS_DIVISOR as u128
+                <= received as u128 * max_price_bps as u128
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_price_band() {
+        // 995 of collateral for 1,000 coins is exactly $0.995
+        assert!(BuybackConfig::within_band(995, 1_000, 9950));
+        assert!(!BuybackConfig::within_band(996, 1_000, 9950));
+        assert!(BuybackConfig::within_band(900, 1_000, 9950));
+        assert!(!BuybackConfig::within_band(0, 0, 9950));
+    }
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::constants::LENDER_AUTHORITY_SEED;
+use crate::error::StableFunError;
+
+/// Stablecoins an owner has locked as collateral with an approved lending
+/// program. Only that program can release them.
+#[account]
+#[derive(Debug, Default)]
+pub struct CollateralLock {
+    pub stablecoin_mint: Pubkey,
+    pub owner: Pubkey,
+    pub lending_program: Pubkey,
+    /// Escrow holding the locked coins, owned by this account
+    pub token_account: Pubkey,
+    pub amount: u64,
+    pub bump: u8,
+}
+
+impl StateAccount for CollateralLock {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        PUBKEY_LENGTH +    // owner
+        PUBKEY_LENGTH +    // lending_program
+        PUBKEY_LENGTH +    // token_account
+        8 +               // amount
+        1;               // bump
+}
+
+impl CollateralLock {
+    /// PDA of `lending_program` that has to sign its lock and release CPIs.
+    pub fn lender_authority(lending_program: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[LENDER_AUTHORITY_SEED], lending_program).0
+    }
+
+    pub fn record_release(&mut self, amount: u64) -> Result<()> {
+        self.amount = self.amount
+            .checked_sub(amount)
+            .ok_or(error!(StableFunError::InsufficientBalance))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_release_bounded_by_locked() {
+        let mut lock = CollateralLock {
+            amount: 1_000,
+            ..Default::default()
+        };
+        assert!(lock.record_release(600).is_ok());
+        assert!(lock.record_release(401).is_err());
+        assert!(lock.record_release(400).is_ok());
+        assert_eq!(lock.amount, 0);
+    }
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::constants::BASIS_POINTS_DIVISOR;
+
+/// An in-progress move of a stablecoin's collateral from its stablebond
+/// series to a newer one. The PDA owns the escrow old bonds are rolled over
+/// from, so the rollover program never sees the vault.
+#[account]
+#[derive(Debug, Default)]
+pub struct CollateralMigration {
+    pub stablecoin_mint: Pubkey,
+    /// Stablebond series the collateral moves to
+    pub new_stablebond_mint: Pubkey,
+    /// Vault token account for the new series, the collateral account once done
+    pub new_collateral_account: Pubkey,
+    /// Escrow holding old bonds while they are rolled over
+    pub escrow_account: Pubkey,
+    /// Program the rollover instruction is sent to
+    pub rollover_program: Pubkey,
+    /// Fewest new bonds accepted per old bond, in bps
+    pub min_out_bps: u16,
+    /// Old bonds rolled over so far
+    pub total_rolled: u64,
+    /// New bonds received for them
+    pub total_received: u64,
+    pub started_at: i64,
+    pub bump: u8,
+}
+
+impl StateAccount for CollateralMigration {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        PUBKEY_LENGTH +    // new_stablebond_mint
+        PUBKEY_LENGTH +    // new_collateral_account
+        PUBKEY_LENGTH +    // escrow_account
+        PUBKEY_LENGTH +    // rollover_program
+        2 +               // min_out_bps
+        8 +               // total_rolled
+        8 +               // total_received
+        8 +               // started_at
+        1;               // bump
+}
+
+impl CollateralMigration {
+    /// Whether `received` new bonds for `rolled` old ones meets `min_out_bps`.
+    pub fn within_min_out(rolled: u64, received: u64, min_out_bps: u16) -> bool {
+        received as u128 * BASIS_POINTS_DIVISOR as u128 >= rolled as u128 * min_out_bps as u128
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_min_out() {
+        assert!(CollateralMigration::within_min_out(1_000, 995, 9950));
+        assert!(!CollateralMigration::within_min_out(1_000, 994, 9950));
+        // A series trading at a premium rolls into fewer bonds
+        assert!(CollateralMigration::within_min_out(1_000, 960, 9500));
+        assert!(!CollateralMigration::within_min_out(1_000, 0, 1));
+    }
+}
@@ -0,0 +1,305 @@
+use anchor_lang::prelude::*;
+use super::{MaturityHolding, StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::constants::{BASIS_POINTS_DIVISOR, MAX_COLLATERAL_TYPES};
+use crate::error::StableFunError;
+use crate::utils::math;
+
+/// Index of the coin's stablebond in rebalances; registered types follow
+/// from 1.
+pub const PRIMARY_COLLATERAL: u8 = 0;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CollateralType {
+    pub mint: Pubkey,
+    /// Vault-owned token account holding this type
+    pub vault_account: Pubkey,
+    /// Registry-owned escrow the rebalancing swaps run through
+    pub escrow_account: Pubkey,
+    /// Feed quoting this type per stablecoin unit, like the coin's `price_feed`
+    pub price_feed: Pubkey,
+    pub target_weight_bps: u16,
+    /// Held in `vault_account`
+    pub balance: u64,
+    /// What `balance` counts for in the vault's `total_collateral`, in
+    /// stablebond units as of the swaps that bought it
+    pub book_value: u64,
+    /// When the type matures; zero when it does not
+    pub maturity_timestamp: i64,
+}
+
+impl CollateralType {
+    pub const LEN: usize =
+        PUBKEY_LENGTH + // mint
+        PUBKEY_LENGTH + // vault_account
+        PUBKEY_LENGTH + // escrow_account
+        PUBKEY_LENGTH + // price_feed
+        2 + // target_weight_bps
+        8 + // balance
+        8 + // book_value
+        8; // maturity_timestamp
+}
+
+/// Collateral types a coin's vault may hold besides its stablebond, and the
+/// limits its manager rebalances between them under. The vault's
+/// `total_collateral` is the stablebond it holds plus the book value of
+/// every other type; redeems still pay out in the stablebond only.
+#[account]
+#[derive(Debug, Default)]
+pub struct CollateralRegistry {
+    pub stablecoin_mint: Pubkey,
+    /// May rebalance between types; set by the coin's authority
+    pub manager: Pubkey,
+    /// Swap adapter program the rebalancing swaps are sent to
+    pub swap_program: Pubkey,
+    /// Escrow the stablebond side of a swap runs through
+    pub primary_escrow: Pubkey,
+    /// Most value a swap may lose against the oracles, in bps
+    pub max_slippage_bps: u16,
+    /// Most value (stablecoin units) sold per Solana epoch
+    pub epoch_volume_cap: u64,
+    pub epoch: u64,
+    pub epoch_volume: u64,
+    pub collateral_types: Vec<CollateralType>,
+    pub total_rebalanced: u64,
+    pub bump: u8,
+}
+
+impl StateAccount for CollateralRegistry {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        PUBKEY_LENGTH +    // manager
+        PUBKEY_LENGTH +    // swap_program
+        PUBKEY_LENGTH +    // primary_escrow
+        2 +               // max_slippage_bps
+        8 +               // epoch_volume_cap
+        8 +               // epoch
+        8 +               // epoch_volume
+        4 + MAX_COLLATERAL_TYPES * CollateralType::LEN + // collateral_types
+        8 +               // total_rebalanced
+        1;               // bump
+}
+
+impl CollateralRegistry {
+    /// Registered target weights may leave no less than zero for the stablebond.
+    pub fn validate_targets(targets: &[u16]) -> Result<()> {
+        require!(
+            targets.len() <= MAX_COLLATERAL_TYPES
+                && targets.iter().map(|target| *target as u32).sum::<u32>() <= BASIS_POINTS_DIVISOR as u32,
+            StableFunError::InvalidCollateralRegistry
+        );
+        Ok(())
+    }
+
+    /// The stablebond's target: whatever the registered types leave.
+    pub fn primary_target_bps(&self) -> u16 {
+        let registered: u32 = self.collateral_types
+            .iter()
+            .map(|collateral| collateral.target_weight_bps as u32)
+            .sum();
+        (BASIS_POINTS_DIVISOR as u32).saturating_sub(registered) as u16
+    }
+
+    pub fn collateral_type(&self, index: u8) -> Result<&CollateralType> {
+        (index as usize)
+            .checked_sub(1)
+            .and_then(|index| self.collateral_types.get(index))
+            .ok_or(error!(StableFunError::InvalidCollateralType))
+    }
+
+    pub fn target_bps(&self, index: u8) -> Result<u16> {
+        if index == PRIMARY_COLLATERAL {
+            return Ok(self.primary_target_bps());
+        }
+        Ok(self.collateral_type(index)?.target_weight_bps)
+    }
+
+    /// Book value of type `index` within `total_collateral`.
+    pub fn book_value(&self, index: u8, total_collateral: u64) -> Result<u64> {
+        if index == PRIMARY_COLLATERAL {
+            let registered: u64 = self.collateral_types.iter().map(|collateral| collateral.book_value).sum();
+            return Ok(total_collateral.saturating_sub(registered));
+        }
+        Ok(self.collateral_type(index)?.book_value)
+    }
+
+    /// Share of `total_collateral` type `index` is booked at, in bps.
+    pub fn weight_bps(&self, index: u8, total_collateral: u64) -> Result<u64> {
+        let book_value = self.book_value(index, total_collateral)?;
+        Ok(math::collateral_ratio_bps(book_value, total_collateral).unwrap_or(0))
+    }
+
+    /// Every holding of the vault tagged with its maturity, the stablebond
+    /// first at `primary_maturity`.
+    pub fn maturity_holdings(&self, total_collateral: u64, primary_maturity: i64) -> Result<Vec<MaturityHolding>> {
+        let mut holdings = Vec::with_capacity(self.collateral_types.len() + 1);
+        holdings.push(MaturityHolding {
+            book_value: self.book_value(PRIMARY_COLLATERAL, total_collateral)?,
+            maturity_timestamp: primary_maturity,
+        });
+        holdings.extend(self.collateral_types.iter().map(|collateral| MaturityHolding {
+            book_value: collateral.book_value,
+            maturity_timestamp: collateral.maturity_timestamp,
+        }));
+        Ok(holdings)
+    }
+
+    /// Counts `value` against this epoch's cap, starting a new window when
+    /// the Solana epoch has moved on.
+    pub fn consume_volume(&mut self, value: u64, epoch: u64) -> Result<()> {
+        if epoch != self.epoch {
+            self.epoch = epoch;
+            self.epoch_volume = 0;
+        }
+        let volume = self.epoch_volume
+            .checked_add(value)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        require!(volume <= self.epoch_volume_cap, StableFunError::RebalanceVolumeExceeded);
+        self.epoch_volume = volume;
+        Ok(())
+    }
+
+    /// Whether `received_value` for `sold_value`, both priced by the oracles,
+    /// lost no more than `max_slippage_bps`.
+    pub fn within_slippage(sold_value: u64, received_value: u64, max_slippage_bps: u16) -> bool {
+        received_value as u128 * BASIS_POINTS_DIVISOR as u128
+            >= sold_value as u128 * (BASIS_POINTS_DIVISOR - max_slippage_bps.min(BASIS_POINTS_DIVISOR)) as u128
+    }
+
+    /// Books `sold` units of type `from` swapped for `received` units of
+    /// type `to`, worth `received_book` stablebond units, and returns the
+    /// vault's new `total_collateral`. A sale takes its share of the type's
+    /// book value with it.
+    pub fn book_rebalance(
+        &mut self,
+        from: u8,
+        to: u8,
+        sold: u64,
+        received: u64,
+        received_book: u64,
+        total_collateral: u64,
+    ) -> Result<u64> {
+        require!(from != to, StableFunError::InvalidCollateralType);
+        for index in [from, to] {
+            if index != PRIMARY_COLLATERAL {
+                self.collateral_type(index)?;
+            }
+        }
+        let sold_book = if from == PRIMARY_COLLATERAL {
+            require!(
+                sold <= self.book_value(PRIMARY_COLLATERAL, total_collateral)?,
+                StableFunError::InsufficientCollateral
+            );
+            sold
+        } else {
+            let collateral = &mut self.collateral_types[from as usize - 1];
+            require!(sold <= collateral.balance, StableFunError::InsufficientCollateral);
+            let sold_book = math::mul_div(collateral.book_value, sold, collateral.balance, math::Flow::FromUser)?
+                .min(collateral.book_value);
+            collateral.balance -= sold;
+            collateral.book_value -= sold_book;
+            sold_book
+        };
+        if to != PRIMARY_COLLATERAL {
+            let collateral = &mut self.collateral_types[to as usize - 1];
+            collateral.balance = collateral.balance
+                .checked_add(received)
+                .ok_or(error!(StableFunError::MathOverflow))?;
+            collateral.book_value = collateral.book_value
+                .checked_add(received_book)
+                .ok_or(error!(StableFunError::MathOverflow))?;
+        }
+        total_collateral
+            .checked_sub(sold_book)
+            .and_then(|total| total.checked_add(received_book))
+            .ok_or(error!(StableFunError::MathOverflow))
+    }
+
+    /// After a rebalance, whether it moved `from` and `to` toward their
+    /// targets without carrying either past it.
+    pub fn within_targets(&self, from: u8, to: u8, total_collateral: u64) -> Result<bool> {
+        Ok(self.weight_bps(from, total_collateral)? >= self.target_bps(from)? as u64
+            && self.weight_bps(to, total_collateral)? <= self.target_bps(to)? as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry_with_target(target_weight_bps: u16) -> CollateralRegistry {
+        CollateralRegistry {
+            max_slippage_bps: 50,
+            epoch_volume_cap: 1_000,
+            collateral_types: vec![CollateralType {
+                target_weight_bps,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_targets() {
+        assert!(CollateralRegistry::validate_targets(&[3000, 7000]).is_ok());
+        assert!(CollateralRegistry::validate_targets(&[3000, 7001]).is_err());
+        assert!(CollateralRegistry::validate_targets(&[1; MAX_COLLATERAL_TYPES + 1]).is_err());
+        assert_eq!(registry_with_target(3000).primary_target_bps(), 7000);
+        assert!(registry_with_target(3000).collateral_type(2).is_err());
+    }
+
+    #[test]
+    fn test_epoch_volume_cap() {
+        let mut registry = registry_with_target(3000);
+        registry.consume_volume(600, 10).unwrap();
+        assert!(registry.consume_volume(401, 10).is_err());
+        registry.consume_volume(400, 10).unwrap();
+        // A new epoch opens a fresh window
+        registry.consume_volume(1_000, 11).unwrap();
+        assert_eq!(registry.epoch_volume, 1_000);
+    }
+
+    #[test]
+    fn test_slippage() {
+        assert!(CollateralRegistry::within_slippage(10_000, 9_950, 50));
+        assert!(!CollateralRegistry::within_slippage(10_000, 9_949, 50));
+        assert!(CollateralRegistry::within_slippage(10_000, 10_100, 0));
+    }
+
+    #[test]
+    fn test_book_rebalance() {
+        let mut registry = registry_with_target(3000);
+
+        // Sell 3,000 of 10,000 stablebonds for 1,500 units worth 2,990
+        let total = registry.book_rebalance(0, 1, 3_000, 1_500, 2_990, 10_000).unwrap();
+        assert_eq!(total, 9_990);
+        assert_eq!(registry.book_value(0, total).unwrap(), 7_000);
+        assert_eq!(registry.collateral_types[0].book_value, 2_990);
+        assert!(registry.within_targets(0, 1, total).unwrap());
+
+        // Overshooting the type's target is caught after booking
+        let total = registry.book_rebalance(0, 1, 1_000, 500, 1_000, total).unwrap();
+        assert!(!registry.within_targets(0, 1, total).unwrap());
+
+        // Selling back takes the type's book value with it
+        let total = registry.book_rebalance(1, 0, 1_000, 1_950, 1_950, total).unwrap();
+        assert_eq!(registry.collateral_types[0].balance, 1_000);
+        assert_eq!(registry.collateral_types[0].book_value, 1_995);
+        assert_eq!(total, 9_945);
+        assert!(registry.book_rebalance(1, 0, 1_001, 0, 0, total).is_err());
+        assert!(registry.book_rebalance(1, 1, 1, 1, 1, total).is_err());
+    }
+
+    #[test]
+    fn test_maturity_holdings() {
+        let mut registry = registry_with_target(3000);
+        registry.collateral_types[0].book_value = 2_500;
+        registry.collateral_types[0].maturity_timestamp = 900;
+        assert_eq!(
+            registry.maturity_holdings(10_000, 300).unwrap(),
+            vec![
+                MaturityHolding { book_value: 7_500, maturity_timestamp: 300 },
+                MaturityHolding { book_value: 2_500, maturity_timestamp: 900 },
+            ]
+        );
+    }
+}
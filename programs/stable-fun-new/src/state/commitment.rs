@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::error::StableFunError;
+
+/// A hidden redemption order, revealed and executed in a later slot.
+/// Commitments are revealed in the order they were made.
+#[account]
+#[derive(Debug, Default)]
+pub struct RedeemCommitment {
+    pub stablecoin_mint: Pubkey,
+    pub owner: Pubkey,
+    pub hash: [u8; 32],
+    pub commit_slot: u64,
+    pub bump: u8,
+    /// Place in the vault's redeem queue
+    pub ticket: u64,
+}
+
+impl StateAccount for RedeemCommitment {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        PUBKEY_LENGTH +    // owner
+        32 +              // hash
+        8 +               // commit_slot
+        1 +               // bump
+        8;               // ticket
+}
+
+impl RedeemCommitment {
+    /// Hash a client has to commit to for redeeming `amount` with `salt`.
+    pub fn compute_hash(
+        stablecoin_mint: &Pubkey,
+        owner: &Pubkey,
+        amount: u64,
+        salt: &[u8; 32],
+    ) -> [u8; 32] {
+        hashv(&[
+            stablecoin_mint.as_ref(),
+            owner.as_ref(),
+            &amount.to_le_bytes(),
+            salt,
+        ])
+        .to_bytes()
+    }
+
+    /// First and last slot the commitment can be revealed in. The window
+    /// opens `delay_slots` after the commit, or once the commitment reached
+    /// the front of the queue at `head_since` if that was later.
+    pub fn reveal_window(
+        &self,
+        delay_slots: u64,
+        window_slots: u64,
+        head_since: u64,
+    ) -> Result<(u64, u64)> {
+        let opens_at = self.commit_slot
+            .checked_add(delay_slots)
+            .ok_or(error!(StableFunError::MathOverflow))?
+            .max(head_since);
+        let closes_at = opens_at
+            .checked_add(window_slots)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        Ok((opens_at, closes_at))
+    }
+
+    /// Checks the revealed order against the commitment and that `slot` falls
+    /// inside its reveal window.
+    pub fn verify_reveal(
+        &self,
+        amount: u64,
+        salt: &[u8; 32],
+        slot: u64,
+        delay_slots: u64,
+        window_slots: u64,
+        head_since: u64,
+    ) -> Result<()> {
+        require!(
+            Self::compute_hash(&self.stablecoin_mint, &self.owner, amount, salt) == self.hash,
+            StableFunError::InvalidRedeemCommitment
+        );
+
+        let (opens_at, closes_at) = self.reveal_window(delay_slots, window_slots, head_since)?;
+        require!(slot >= opens_at, StableFunError::RevealTooEarly);
+        require!(slot <= closes_at, StableFunError::RevealWindowExpired);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commitment(amount: u64, salt: &[u8; 32]) -> RedeemCommitment {
+        let stablecoin_mint = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        RedeemCommitment {
+            stablecoin_mint,
+            owner,
+            hash: RedeemCommitment::compute_hash(&stablecoin_mint, &owner, amount, salt),
+            commit_slot: 100,
+            bump: 255,
+            ticket: 0,
+        }
+    }
+
+    #[test]
+    fn test_reveal_window() {
+        let salt = [7u8; 32];
+        let commitment = commitment(1_000_000, &salt);
+
+        assert!(commitment.verify_reveal(1_000_000, &salt, 109, 10, 50, 0).is_err()); // too early
+        assert!(commitment.verify_reveal(1_000_000, &salt, 110, 10, 50, 0).is_ok());
+        assert!(commitment.verify_reveal(1_000_000, &salt, 160, 10, 50, 0).is_ok());
+        assert!(commitment.verify_reveal(1_000_000, &salt, 161, 10, 50, 0).is_err()); // expired
+    }
+
+    #[test]
+    fn test_reveal_window_starts_at_queue_head() {
+        let salt = [7u8; 32];
+        let commitment = commitment(1_000_000, &salt);
+
+        // Queued behind others until slot 300, it gets a full window from there
+        assert_eq!(commitment.reveal_window(10, 50, 300).unwrap(), (300, 350));
+        assert!(commitment.verify_reveal(1_000_000, &salt, 299, 10, 50, 300).is_err());
+        assert!(commitment.verify_reveal(1_000_000, &salt, 350, 10, 50, 300).is_ok());
+        assert!(commitment.verify_reveal(1_000_000, &salt, 351, 10, 50, 300).is_err());
+    }
+
+    #[test]
+    fn test_reveal_must_match_commitment() {
+        let salt = [7u8; 32];
+        let commitment = commitment(1_000_000, &salt);
+
+        assert!(commitment.verify_reveal(1_000_001, &salt, 110, 10, 50, 0).is_err());
+        assert!(commitment.verify_reveal(1_000_000, &[8u8; 32], 110, 10, 50, 0).is_err());
+    }
+}
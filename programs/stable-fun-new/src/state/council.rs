@@ -0,0 +1,134 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::constants::MAX_COUNCIL_SIGNERS;
+use crate::error::StableFunError;
+
+/// M-of-N signer set whose approval critical instructions need once a coin
+/// has one.
+#[account]
+#[derive(Debug, Default)]
+pub struct ApprovalCouncil {
+    pub stablecoin_mint: Pubkey,
+    pub signers: Vec<Pubkey>,
+    /// Approvals (M) a proposal needs before it can execute
+    pub threshold: u8,
+    /// Index of the next proposal, part of its PDA seeds
+    pub proposal_count: u64,
+    pub bump: u8,
+}
+
+impl StateAccount for ApprovalCouncil {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        4 + MAX_COUNCIL_SIGNERS * PUBKEY_LENGTH + // signers
+        1 +               // threshold
+        8 +               // proposal_count
+        1;               // bump
+}
+
+impl ApprovalCouncil {
+    pub fn validate(signers: &[Pubkey], threshold: u8) -> Result<()> {
+        let unique = signers
+            .iter()
+            .enumerate()
+            .all(|(i, signer)| !signers[..i].contains(signer));
+        require!(
+            !signers.is_empty()
+                && signers.len() <= MAX_COUNCIL_SIGNERS
+                && unique
+                && threshold > 0
+                && threshold as usize <= signers.len(),
+            StableFunError::InvalidCouncil
+        );
+        Ok(())
+    }
+
+    pub fn signer_index(&self, key: &Pubkey) -> Option<usize> {
+        self.signers.iter().position(|signer| signer == key)
+    }
+}
+
+/// Critical changes that can only run through an approved proposal.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProposalAction {
+    /// Hand the coin to a new authority
+    TransferAuthority { new_authority: Pubkey },
+    /// Change the mint/redeem fee by more than `COUNCIL_FEE_CHANGE_BPS`
+    SetFee { fee_basis_points: u16 },
+    /// Stop minting for good and let holders redeem fee-free
+    Settle,
+}
+
+impl ProposalAction {
+    pub const LEN: usize = 1 + PUBKEY_LENGTH; // largest variant
+}
+
+#[account]
+#[derive(Debug)]
+pub struct Proposal {
+    pub council: Pubkey,
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub action: ProposalAction,
+    /// Bit `i` set once `council.signers[i]` approved
+    pub approvals: u16,
+    pub created_at: i64,
+    pub executed: bool,
+    pub bump: u8,
+}
+
+impl StateAccount for Proposal {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // council
+        8 +               // index
+        PUBKEY_LENGTH +    // proposer
+        ProposalAction::LEN + // action
+        2 +               // approvals
+        8 +               // created_at
+        1 +               // executed
+        1;               // bump
+}
+
+impl Proposal {
+    pub fn approve(&mut self, signer_index: usize) {
+        self.approvals |= 1 << signer_index;
+    }
+
+    pub fn approval_count(&self) -> u32 {
+        self.approvals.count_ones()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_council_validation() {
+        let signers: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        assert!(ApprovalCouncil::validate(&signers, 2).is_ok());
+        assert!(ApprovalCouncil::validate(&signers, 0).is_err());
+        assert!(ApprovalCouncil::validate(&signers, 4).is_err());
+        assert!(ApprovalCouncil::validate(&[signers[0], signers[0]], 1).is_err());
+        assert!(ApprovalCouncil::validate(&[], 1).is_err());
+    }
+
+    #[test]
+    fn test_proposal_approvals() {
+        let mut proposal = Proposal {
+            council: Pubkey::default(),
+            index: 0,
+            proposer: Pubkey::default(),
+            action: ProposalAction::Settle,
+            approvals: 0,
+            created_at: 0,
+            executed: false,
+            bump: 0,
+        };
+        proposal.approve(0);
+        proposal.approve(2);
+        // Approving twice does not count twice
+        proposal.approve(2);
+        assert_eq!(proposal.approval_count(), 2);
+    }
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+
+/// What the recovery key or guardian does once the issuer has gone quiet.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeadManAction {
+    /// The caller becomes the coin's authority
+    AssumeControl,
+    /// Minting stops for good and holders redeem fee-free
+    Settle,
+}
+
+/// Issuer inactivity guard: after `inactivity_period` seconds without an
+/// admin action, `recovery_key` or the protocol guardian may step in.
+#[account]
+#[derive(Debug, Default)]
+pub struct DeadManSwitch {
+    pub stablecoin_mint: Pubkey,
+    pub recovery_key: Pubkey,
+    pub inactivity_period: i64,
+    /// When the switch last fired (0 if never)
+    pub triggered_at: i64,
+    pub bump: u8,
+}
+
+impl StateAccount for DeadManSwitch {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        PUBKEY_LENGTH +    // recovery_key
+        8 +               // inactivity_period
+        8 +               // triggered_at
+        1;               // bump
+}
+
+impl DeadManSwitch {
+    pub fn is_expired(&self, last_admin_action: i64, now: i64) -> bool {
+        now.saturating_sub(last_admin_action) >= self.inactivity_period
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::SECONDS_PER_DAY;
+
+    #[test]
+    fn test_is_expired() {
+        let switch = DeadManSwitch {
+            inactivity_period: 90 * SECONDS_PER_DAY,
+            ..Default::default()
+        };
+        assert!(!switch.is_expired(1_000, 1_000 + 90 * SECONDS_PER_DAY - 1));
+        assert!(switch.is_expired(1_000, 1_000 + 90 * SECONDS_PER_DAY));
+    }
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::error::StableFunError;
+
+/// Marks a user's client order id as filled, so a retried mint or redeem
+/// carrying the same id is rejected instead of executing twice.
+#[account]
+#[derive(Debug, Default)]
+pub struct OrderDedupe {
+    pub stablecoin_mint: Pubkey,
+    pub user: Pubkey,
+    pub client_order_id: [u8; 32],
+    /// Slot the order was filled in
+    pub filled_slot: u64,
+    pub bump: u8,
+}
+
+impl StateAccount for OrderDedupe {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        PUBKEY_LENGTH +    // user
+        32 +              // client_order_id
+        8 +               // filled_slot
+        1;               // bump
+}
+
+impl OrderDedupe {
+    pub fn is_filled(&self) -> bool {
+        self.stablecoin_mint != Pubkey::default()
+    }
+
+    /// Fails if the order id is missing or was already used. The guard only
+    /// makes sense with an id to key it on.
+    pub fn check_fill(&self, client_order_id: Option<[u8; 32]>) -> Result<[u8; 32]> {
+        let client_order_id = client_order_id.ok_or(error!(StableFunError::MissingClientOrderId))?;
+        require!(!self.is_filled(), StableFunError::DuplicateClientOrder);
+        Ok(client_order_id)
+    }
+
+    /// Records the fill, failing if `check_fill` does.
+    pub fn record_fill(
+        &mut self,
+        stablecoin_mint: Pubkey,
+        user: Pubkey,
+        client_order_id: Option<[u8; 32]>,
+        slot: u64,
+        bump: u8,
+    ) -> Result<()> {
+        let client_order_id = self.check_fill(client_order_id)?;
+        self.stablecoin_mint = stablecoin_mint;
+        self.user = user;
+        self.client_order_id = client_order_id;
+        self.filled_slot = slot;
+        self.bump = bump;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_fill() {
+        let mut dedupe = OrderDedupe::default();
+        let stablecoin_mint = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+
+        assert!(dedupe.record_fill(stablecoin_mint, user, None, 10, 255).is_err());
+        assert!(dedupe.check_fill(Some([1; 32])).is_ok());
+        dedupe.record_fill(stablecoin_mint, user, Some([1; 32]), 10, 255).unwrap();
+        assert!(dedupe.is_filled());
+        assert_eq!(dedupe.filled_slot, 10);
+        // A retry with the same id lands on the same PDA
+        assert!(dedupe.check_fill(Some([1; 32])).is_err());
+        assert!(dedupe.record_fill(stablecoin_mint, user, Some([1; 32]), 11, 255).is_err());
+    }
+}
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::error::StableFunError;
+
+/// A pot of reference assets paid out pro rata to the holders in a snapshot.
+#[account]
+#[derive(Debug, Default)]
+pub struct Dividend {
+    pub stablecoin_mint: Pubkey,
+    pub authority: Pubkey,
+    pub dividend_id: u64,
+    /// Snapshot whose balances the payout is split over
+    pub snapshot: Pubkey,
+    /// Asset being distributed
+    pub reward_mint: Pubkey,
+    /// Distributor token account holding the unclaimed rewards
+    pub token_account: Pubkey,
+    pub total_amount: u64,
+    /// `Snapshot::total_balance` at funding time
+    pub total_balance: u64,
+    pub claimed_amount: u64,
+    pub claimed_count: u32,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl StateAccount for Dividend {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        PUBKEY_LENGTH +    // authority
+        8 +               // dividend_id
+        PUBKEY_LENGTH +    // snapshot
+        PUBKEY_LENGTH +    // reward_mint
+        PUBKEY_LENGTH +    // token_account
+        8 +               // total_amount
+        8 +               // total_balance
+        8 +               // claimed_amount
+        4 +               // claimed_count
+        8 +               // created_at
+        1;               // bump
+}
+
+impl Dividend {
+    /// Payout owed to a holder of `balance` at the snapshot, rounded down so
+    /// the claims can never exceed the funded amount.
+    pub fn share_of(&self, balance: u64) -> Result<u64> {
+        require!(balance <= self.total_balance, StableFunError::InvalidDividend);
+        Ok((self.total_amount as u128 * balance as u128 / self.total_balance as u128) as u64)
+    }
+
+    pub fn record_claim(&mut self, amount: u64) -> Result<()> {
+        let claimed_amount = self.claimed_amount
+            .checked_add(amount)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        require!(claimed_amount <= self.total_amount, StableFunError::InsufficientBalance);
+        self.claimed_amount = claimed_amount;
+        self.claimed_count = self.claimed_count
+            .checked_add(1)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_share_of() {
+        let mut dividend = Dividend {
+            total_amount: 1_000,
+            total_balance: 3_000,
+            ..Default::default()
+        };
+        assert_eq!(dividend.share_of(1_000).unwrap(), 333);
+        assert_eq!(dividend.share_of(3_000).unwrap(), 1_000);
+        assert!(dividend.share_of(3_001).is_err());
+
+        // Three equal holders leave the rounding dust in the distributor
+        for _ in 0..3 {
+            let share = dividend.share_of(1_000).unwrap();
+            dividend.record_claim(share).unwrap();
+        }
+        assert_eq!(dividend.claimed_amount, 999);
+        assert_eq!(dividend.claimed_count, 3);
+    }
+}
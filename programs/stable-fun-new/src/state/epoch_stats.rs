@@ -0,0 +1,166 @@
+use anchor_lang::prelude::*;
+use super::{StablecoinStats, StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::constants::EPOCH_STATS_HISTORY;
+
+/// Figures of one closed epoch.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EpochSnapshot {
+    pub epoch: u64,
+    /// Supply and TVL when the epoch was rolled over
+    pub supply: u64,
+    pub total_value_locked: u64,
+    /// Coins minted plus burned during the epoch
+    pub volume: u64,
+    pub fee_revenue: u64,
+    /// Mean of the collateral ratios sampled during the epoch, in bps
+    pub average_ratio: u16,
+}
+
+impl EpochSnapshot {
+    pub const LEN: usize =
+        8 + // epoch
+        8 + // supply
+        8 + // total_value_locked
+        8 + // volume
+        8 + // fee_revenue
+        2; // average_ratio
+}
+
+/// Ring buffer of the last `EPOCH_STATS_HISTORY` epochs of a stablecoin,
+/// rolled over by a permissionless crank.
+#[account]
+#[derive(Debug, Default)]
+pub struct EpochStats {
+    pub stablecoin_mint: Pubkey,
+    /// Epoch currently being accumulated
+    pub epoch: u64,
+    /// Lifetime stats counters at the start of `epoch`
+    pub minted_at_start: u64,
+    pub burned_at_start: u64,
+    pub fees_at_start: u64,
+    /// Collateral ratios sampled so far this epoch
+    pub ratio_sum: u64,
+    pub ratio_samples: u32,
+    /// Closed epochs; once full, `next_index` is the oldest entry
+    pub history: Vec<EpochSnapshot>,
+    pub next_index: u16,
+    pub bump: u8,
+}
+
+impl StateAccount for EpochStats {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        8 +               // epoch
+        8 +               // minted_at_start
+        8 +               // burned_at_start
+        8 +               // fees_at_start
+        8 +               // ratio_sum
+        4 +               // ratio_samples
+        4 + EPOCH_STATS_HISTORY * EpochSnapshot::LEN + // history
+        2 +               // next_index
+        1;               // bump
+}
+
+impl EpochStats {
+    /// Starts accumulating `epoch` from the current lifetime counters.
+    pub fn open_epoch(&mut self, epoch: u64, stats: &StablecoinStats) {
+        self.epoch = epoch;
+        self.minted_at_start = stats.total_minted;
+        self.burned_at_start = stats.total_burned;
+        self.fees_at_start = stats.total_fees;
+        self.ratio_sum = 0;
+        self.ratio_samples = 0;
+    }
+
+    pub fn sample_ratio(&mut self, ratio: u16) {
+        self.ratio_sum = self.ratio_sum.saturating_add(ratio as u64);
+        self.ratio_samples = self.ratio_samples.saturating_add(1);
+    }
+
+    /// Closes the open epoch into the history and opens `epoch`. Returns the
+    /// closed snapshot, or `None` while still inside the open epoch.
+    pub fn roll(
+        &mut self,
+        epoch: u64,
+        stats: &StablecoinStats,
+        supply: u64,
+        total_value_locked: u64,
+    ) -> Option<EpochSnapshot> {
+        if epoch <= self.epoch {
+            return None;
+        }
+        let volume = stats.total_minted.saturating_sub(self.minted_at_start)
+            .saturating_add(stats.total_burned.saturating_sub(self.burned_at_start));
+        let average_ratio = self.ratio_sum
+            .checked_div(self.ratio_samples as u64)
+            .unwrap_or(0);
+        let snapshot = EpochSnapshot {
+            epoch: self.epoch,
+            supply,
+            total_value_locked,
+            volume,
+            fee_revenue: stats.total_fees.saturating_sub(self.fees_at_start),
+            average_ratio: u16::try_from(average_ratio).unwrap_or(u16::MAX),
+        };
+
+        if self.history.len() < EPOCH_STATS_HISTORY {
+            self.history.push(snapshot);
+        } else {
+            self.history[self.next_index as usize] = snapshot;
+        }
+        self.next_index = ((self.next_index as usize + 1) % EPOCH_STATS_HISTORY) as u16;
+
+        self.open_epoch(epoch, stats);
+        Some(snapshot)
+    }
+
+    /// The retained snapshot of `epoch`, if it is still in the buffer.
+    pub fn snapshot(&self, epoch: u64) -> Option<&EpochSnapshot> {
+        self.history.iter().find(|snapshot| snapshot.epoch == epoch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_epoch_rollup() {
+        let mut stats = StablecoinStats::default();
+        let mut epoch_stats = EpochStats::default();
+        epoch_stats.open_epoch(10, &stats);
+
+        stats.total_minted = 1_000;
+        stats.total_burned = 200;
+        stats.total_fees = 30;
+        epoch_stats.sample_ratio(15_000);
+        epoch_stats.sample_ratio(16_000);
+        assert_eq!(epoch_stats.roll(10, &stats, 800, 800), None);
+
+        let snapshot = epoch_stats.roll(11, &stats, 800, 820).unwrap();
+        assert_eq!(snapshot.epoch, 10);
+        assert_eq!(snapshot.volume, 1_200);
+        assert_eq!(snapshot.fee_revenue, 30);
+        assert_eq!(snapshot.average_ratio, 15_500);
+        assert_eq!(epoch_stats.epoch, 11);
+        assert_eq!(epoch_stats.snapshot(10), Some(&snapshot));
+
+        // No samples and no activity in the next epoch
+        let snapshot = epoch_stats.roll(12, &stats, 800, 820).unwrap();
+        assert_eq!((snapshot.volume, snapshot.average_ratio), (0, 0));
+    }
+
+    #[test]
+    fn test_history_wraps() {
+        let stats = StablecoinStats::default();
+        let mut epoch_stats = EpochStats::default();
+        for epoch in 1..=(EPOCH_STATS_HISTORY as u64 + 2) {
+            epoch_stats.roll(epoch, &stats, 0, 0);
+        }
+
+        assert_eq!(epoch_stats.history.len(), EPOCH_STATS_HISTORY);
+        assert!(epoch_stats.snapshot(1).is_none());
+        assert!(epoch_stats.snapshot(EPOCH_STATS_HISTORY as u64 + 1).is_some());
+        assert_eq!(epoch_stats.history[epoch_stats.next_index as usize].epoch, 2);
+    }
+}
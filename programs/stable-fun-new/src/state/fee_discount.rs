@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::constants::{BASIS_POINTS_DIVISOR, MAX_FEE_DISCOUNT_TIERS};
+use crate::error::StableFunError;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeeDiscountTier {
+    /// Discount token balance needed for the tier, in base units
+    pub min_balance: u64,
+    /// Share of the mint/redeem fee waived, in bps
+    pub discount_bps: u16,
+}
+
+impl FeeDiscountTier {
+    pub const LEN: usize =
+        8 + // min_balance
+        2; // discount_bps
+}
+
+/// Mint/redeem fee discounts for holders of a governance or partner token.
+#[account]
+#[derive(Debug, Default)]
+pub struct FeeDiscountConfig {
+    pub stablecoin_mint: Pubkey,
+    /// Token whose balance selects the tier
+    pub discount_mint: Pubkey,
+    /// Ascending by `min_balance`, with non-decreasing discounts
+    pub tiers: Vec<FeeDiscountTier>,
+    pub bump: u8,
+}
+
+impl StateAccount for FeeDiscountConfig {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        PUBKEY_LENGTH +    // discount_mint
+        4 + MAX_FEE_DISCOUNT_TIERS * FeeDiscountTier::LEN + // tiers
+        1;               // bump
+}
+
+impl FeeDiscountConfig {
+    /// An empty schedule switches discounts off.
+    pub fn validate_tiers(tiers: &[FeeDiscountTier]) -> Result<()> {
+        require!(
+            tiers.len() <= MAX_FEE_DISCOUNT_TIERS
+                && tiers.iter().all(|tier| tier.min_balance > 0 && tier.discount_bps <= BASIS_POINTS_DIVISOR)
+                && tiers.windows(2).all(|pair| {
+                    pair[0].min_balance < pair[1].min_balance && pair[0].discount_bps <= pair[1].discount_bps
+                }),
+            StableFunError::InvalidFeeDiscount
+        );
+        Ok(())
+    }
+
+    /// Discount earned by holding `balance` of the discount token.
+    pub fn discount_bps(&self, balance: u64) -> u16 {
+        self.tiers
+            .iter()
+            .rev()
+            .find(|tier| balance >= tier.min_balance)
+            .map_or(0, |tier| tier.discount_bps)
+    }
+
+    /// `fee_bps` less the discount earned by `balance`, rounding the
+    /// discount down.
+    pub fn discounted_fee_bps(&self, fee_bps: u16, balance: u64) -> u16 {
+        let discount = fee_bps as u32 * self.discount_bps(balance) as u32 / BASIS_POINTS_DIVISOR as u32;
+        fee_bps - discount as u16
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discount_tiers() {
+        let tier = |min_balance, discount_bps| FeeDiscountTier { min_balance, discount_bps };
+        let config = FeeDiscountConfig {
+            tiers: vec![tier(1_000, 2_500), tier(10_000, 5_000)],
+            ..Default::default()
+        };
+        assert!(FeeDiscountConfig::validate_tiers(&config.tiers).is_ok());
+        assert!(FeeDiscountConfig::validate_tiers(&[]).is_ok());
+        assert!(FeeDiscountConfig::validate_tiers(&[tier(10_000, 2_500), tier(1_000, 5_000)]).is_err());
+        assert!(FeeDiscountConfig::validate_tiers(&[tier(1_000, 5_000), tier(10_000, 2_500)]).is_err());
+        assert!(FeeDiscountConfig::validate_tiers(&[tier(0, 2_500)]).is_err());
+        assert!(FeeDiscountConfig::validate_tiers(&[tier(1_000, 10_001)]).is_err());
+
+        assert_eq!(config.discounted_fee_bps(30, 999), 30);
+        assert_eq!(config.discounted_fee_bps(30, 1_000), 23);
+        assert_eq!(config.discounted_fee_bps(30, 50_000), 15);
+    }
+}
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::error::StableFunError;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InvoiceStatus {
+    #[default]
+    Open,
+    Paid,
+}
+
+/// Payment request from a merchant, kept after payment as an on-chain receipt.
+#[account]
+#[derive(Debug, Default)]
+pub struct Invoice {
+    pub stablecoin_mint: Pubkey,
+    pub merchant: Pubkey,
+    pub invoice_id: u64,
+    /// Stablecoin account the payment is sent to
+    pub merchant_token_account: Pubkey,
+    pub amount: u64,
+    /// Hash of the off-chain memo (order details, line items)
+    pub memo_hash: [u8; 32],
+    pub created_at: i64,
+    pub expires_at: i64,
+    pub status: InvoiceStatus,
+    pub payer: Pubkey,
+    pub paid_at: i64,
+    pub bump: u8,
+}
+
+impl StateAccount for Invoice {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        PUBKEY_LENGTH +    // merchant
+        8 +               // invoice_id
+        PUBKEY_LENGTH +    // merchant_token_account
+        8 +               // amount
+        32 +              // memo_hash
+        8 +               // created_at
+        8 +               // expires_at
+        1 +               // status
+        PUBKEY_LENGTH +    // payer
+        8 +               // paid_at
+        1;               // bump
+}
+
+impl Invoice {
+    pub fn is_open(&self) -> bool {
+        self.status == InvoiceStatus::Open
+    }
+
+    pub fn mark_paid(&mut self, payer: Pubkey, now: i64) -> Result<()> {
+        require!(self.is_open(), StableFunError::InvoiceNotOpen);
+        require!(now < self.expires_at, StableFunError::InvoiceExpired);
+        self.status = InvoiceStatus::Paid;
+        self.payer = payer;
+        self.paid_at = now;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invoice_payment() {
+        let mut invoice = Invoice {
+            amount: 1_000,
+            expires_at: 100,
+            ..Default::default()
+        };
+        assert!(invoice.is_open());
+        assert!(invoice.mark_paid(Pubkey::new_unique(), 100).is_err());
+
+        let payer = Pubkey::new_unique();
+        invoice.mark_paid(payer, 99).unwrap();
+        assert_eq!(invoice.status, InvoiceStatus::Paid);
+        assert_eq!(invoice.payer, payer);
+        assert!(invoice.mark_paid(payer, 50).is_err());
+    }
+}
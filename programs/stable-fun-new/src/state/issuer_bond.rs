@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+
+/// Collateral an issuer stakes on keeping their coin's peg and reserve
+/// commitments. The protocol admin slashes it into the insurance fund on
+/// misconduct.
+#[account]
+#[derive(Debug, Default)]
+pub struct IssuerBond {
+    pub stablecoin_mint: Pubkey,
+    /// `ProtocolConfig::bond_mint` when the bond was first posted
+    pub bond_mint: Pubkey,
+    /// Token account holding `bonded` and `unbonding`
+    pub bond_vault: Pubkey,
+    /// Counts towards the coin's supply tier
+    pub bonded: u64,
+    /// Requested for withdrawal; still slashable until withdrawn
+    pub unbonding: u64,
+    pub unbonding_ends_at: i64,
+    pub total_slashed: u64,
+    pub bump: u8,
+}
+
+impl StateAccount for IssuerBond {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        PUBKEY_LENGTH +    // bond_mint
+        PUBKEY_LENGTH +    // bond_vault
+        8 +               // bonded
+        8 +               // unbonding
+        8 +               // unbonding_ends_at
+        8 +               // total_slashed
+        1;               // bump
+}
+
+impl IssuerBond {
+    pub fn is_initialized(&self) -> bool {
+        self.stablecoin_mint != Pubkey::default()
+    }
+
+    /// Takes up to `amount` out of the bond, unbonding tokens first so a
+    /// pending withdrawal cannot dodge the slash. Returns what was taken.
+    pub fn slash(&mut self, amount: u64) -> u64 {
+        let from_unbonding = amount.min(self.unbonding);
+        let from_bonded = (amount - from_unbonding).min(self.bonded);
+        self.unbonding -= from_unbonding;
+        self.bonded -= from_bonded;
+        let slashed = from_unbonding + from_bonded;
+        self.total_slashed = self.total_slashed.saturating_add(slashed);
+        slashed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slash_takes_unbonding_first() {
+        let mut bond = IssuerBond {
+            bonded: 1_000,
+            unbonding: 300,
+            ..Default::default()
+        };
+        assert_eq!(bond.slash(500), 500);
+        assert_eq!((bond.unbonding, bond.bonded), (0, 800));
+
+        assert_eq!(bond.slash(5_000), 800);
+        assert_eq!(bond.bonded, 0);
+        assert_eq!(bond.total_slashed, 1_300);
+    }
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, MAX_URI_LENGTH, PUBKEY_LENGTH};
+
+/// Branding and contact details of an issuer, shared by all of its coins, so
+/// wallets can render trust information straight from chain.
+#[account]
+#[derive(Debug, Default)]
+pub struct IssuerProfile {
+    pub issuer: Pubkey,
+    pub website: String,
+    /// SHA-256 of the lowercased Twitter/X handle, without the `@`
+    pub twitter_handle_hash: [u8; 32],
+    pub logo_uri: String,
+    /// Where holders reach the issuer (a `mailto:` or `https:` URI)
+    pub contact: String,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl StateAccount for IssuerProfile {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // issuer
+        4 + MAX_URI_LENGTH + // website (string)
+        32 +              // twitter_handle_hash
+        4 + MAX_URI_LENGTH + // logo_uri (string)
+        4 + MAX_URI_LENGTH + // contact (string)
+        8 +               // updated_at
+        1;               // bump
+}
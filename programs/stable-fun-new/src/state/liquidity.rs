@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+
+/// Protocol-owned liquidity in one registered stablecoin/quote pool. The PDA
+/// owns the escrows the AMM deposits from and withdraws into, and holds the
+/// LP tokens itself.
+#[account]
+#[derive(Debug, Default)]
+pub struct LiquidityPosition {
+    pub stablecoin_mint: Pubkey,
+    /// AMM program the deposit and withdraw instructions are sent to
+    pub amm_program: Pubkey,
+    /// Pool the liquidity is registered for
+    pub pool: Pubkey,
+    pub lp_mint: Pubkey,
+    /// Escrow holding the LP tokens
+    pub lp_token_account: Pubkey,
+    /// Escrow the stablecoin side is minted into and withdrawn to
+    pub token_account: Pubkey,
+    /// Escrow for the quote side (e.g. USDC), funded by the treasury
+    pub quote_token_account: Pubkey,
+    /// Stablecoins deployed from surplus, cumulative
+    pub total_deployed: u64,
+    /// Stablecoins withdrawn from the pool and burned, cumulative
+    pub total_withdrawn: u64,
+    pub bump: u8,
+}
+
+impl StateAccount for LiquidityPosition {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        PUBKEY_LENGTH +    // amm_program
+        PUBKEY_LENGTH +    // pool
+        PUBKEY_LENGTH +    // lp_mint
+        PUBKEY_LENGTH +    // lp_token_account
+        PUBKEY_LENGTH +    // token_account
+        PUBKEY_LENGTH +    // quote_token_account
+        8 +               // total_deployed
+        8 +               // total_withdrawn
+        1;               // bump
+}
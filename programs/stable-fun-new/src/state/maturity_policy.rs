@@ -0,0 +1,197 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::constants::{BASIS_POINTS_DIVISOR, MAX_MATURITY_BUCKETS};
+use crate::error::StableFunError;
+use crate::utils::math;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct MaturityBucket {
+    /// Longest time to maturity the bucket takes, in seconds
+    pub max_time_to_maturity: i64,
+    pub target_weight_bps: u16,
+}
+
+impl MaturityBucket {
+    pub const LEN: usize =
+        8 + // max_time_to_maturity
+        2; // target_weight_bps
+}
+
+/// Collateral the vault holds in one maturity, at its booked value.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MaturityHolding {
+    pub book_value: u64,
+    /// Zero for collateral that does not mature
+    pub maturity_timestamp: i64,
+}
+
+impl MaturityHolding {
+    pub fn time_to_maturity(&self, now: i64) -> i64 {
+        self.maturity_timestamp.saturating_sub(now).max(0)
+    }
+}
+
+/// A coin's bond ladder: how its collateral should spread over time to
+/// maturity (e.g. 30/90/180 days). `rebalance_ladder` moves collateral
+/// between buckets whenever one drifts further than `tolerance_bps` from
+/// its target.
+#[account]
+#[derive(Debug, Default)]
+pub struct MaturityPolicy {
+    pub stablecoin_mint: Pubkey,
+    /// Ascending by `max_time_to_maturity`; collateral maturing later than
+    /// the last bucket counts toward it
+    pub buckets: Vec<MaturityBucket>,
+    pub tolerance_bps: u16,
+    pub last_rebalanced_at: i64,
+    pub bump: u8,
+}
+
+impl StateAccount for MaturityPolicy {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        4 + MAX_MATURITY_BUCKETS * MaturityBucket::LEN + // buckets
+        2 +               // tolerance_bps
+        8 +               // last_rebalanced_at
+        1;               // bump
+}
+
+impl MaturityPolicy {
+    pub fn validate(buckets: &[MaturityBucket], tolerance_bps: u16) -> Result<()> {
+        require!(
+            !buckets.is_empty()
+                && buckets.len() <= MAX_MATURITY_BUCKETS
+                && buckets[0].max_time_to_maturity > 0
+                && buckets.windows(2).all(|pair| pair[0].max_time_to_maturity < pair[1].max_time_to_maturity)
+                && buckets.iter().map(|bucket| bucket.target_weight_bps as u32).sum::<u32>()
+                    == BASIS_POINTS_DIVISOR as u32
+                && tolerance_bps < BASIS_POINTS_DIVISOR,
+            StableFunError::InvalidMaturityPolicy
+        );
+        Ok(())
+    }
+
+    /// The bucket collateral `time_to_maturity` seconds from maturity falls in.
+    pub fn bucket_of(&self, time_to_maturity: i64) -> usize {
+        self.buckets
+            .iter()
+            .position(|bucket| time_to_maturity <= bucket.max_time_to_maturity)
+            .unwrap_or(self.buckets.len() - 1)
+    }
+
+    /// Share of the holdings' booked value in each bucket, in bps.
+    pub fn allocation(&self, holdings: &[MaturityHolding], now: i64) -> Vec<u64> {
+        let total: u64 = holdings.iter().map(|holding| holding.book_value).sum();
+        let mut booked = vec![0u64; self.buckets.len()];
+        for holding in holdings {
+            booked[self.bucket_of(holding.time_to_maturity(now))] += holding.book_value;
+        }
+        booked
+            .into_iter()
+            .map(|value| math::collateral_ratio_bps(value, total).unwrap_or(0))
+            .collect()
+    }
+
+    /// Whether every bucket is within `tolerance_bps` of its target.
+    pub fn is_within(&self, allocation: &[u64]) -> bool {
+        self.buckets
+            .iter()
+            .zip(allocation)
+            .all(|(bucket, weight)| weight.abs_diff(bucket.target_weight_bps as u64) <= self.tolerance_bps as u64)
+    }
+
+    /// Whether moving collateral from bucket `from` to bucket `to`, which
+    /// turned `before` into `after`, took it from an overweight bucket to an
+    /// underweight one without carrying either past its target.
+    pub fn moves_toward_targets(&self, from: usize, to: usize, before: &[u64], after: &[u64]) -> bool {
+        let from_target = self.buckets[from].target_weight_bps as u64;
+        let to_target = self.buckets[to].target_weight_bps as u64;
+        from != to
+            && before[from] > from_target
+            && before[to] < to_target
+            && after[from] >= from_target
+            && after[to] <= to_target
+    }
+}
+
+/// Booked-value weighted average time to maturity of the holdings, in
+/// seconds; collateral that does not mature counts as zero.
+pub fn weighted_average_maturity(holdings: &[MaturityHolding], now: i64) -> i64 {
+    let total: u128 = holdings.iter().map(|holding| holding.book_value as u128).sum();
+    if total == 0 {
+        return 0;
+    }
+    let weighted: u128 = holdings
+        .iter()
+        .map(|holding| holding.book_value as u128 * holding.time_to_maturity(now) as u128)
+        .sum();
+    i64::try_from(weighted / total).unwrap_or(i64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::SECONDS_PER_DAY;
+
+    fn ladder() -> MaturityPolicy {
+        MaturityPolicy {
+            buckets: vec![
+                MaturityBucket { max_time_to_maturity: 30 * SECONDS_PER_DAY, target_weight_bps: 2000 },
+                MaturityBucket { max_time_to_maturity: 90 * SECONDS_PER_DAY, target_weight_bps: 3000 },
+                MaturityBucket { max_time_to_maturity: 180 * SECONDS_PER_DAY, target_weight_bps: 5000 },
+            ],
+            tolerance_bps: 500,
+            ..Default::default()
+        }
+    }
+
+    fn holding(book_value: u64, days: i64) -> MaturityHolding {
+        MaturityHolding { book_value, maturity_timestamp: days * SECONDS_PER_DAY }
+    }
+
+    #[test]
+    fn test_validate_policy() {
+        let policy = ladder();
+        assert!(MaturityPolicy::validate(&policy.buckets, 500).is_ok());
+        assert!(MaturityPolicy::validate(&policy.buckets, BASIS_POINTS_DIVISOR).is_err());
+        assert!(MaturityPolicy::validate(&policy.buckets[..2], 500).is_err());
+        let mut unordered = policy.buckets.clone();
+        unordered.swap(0, 1);
+        assert!(MaturityPolicy::validate(&unordered, 500).is_err());
+        assert!(MaturityPolicy::validate(&[], 500).is_err());
+    }
+
+    #[test]
+    fn test_allocation_and_rebalance() {
+        let policy = ladder();
+        // Matured and non-maturing collateral sits in the shortest bucket,
+        // anything past the ladder in the longest
+        assert_eq!(policy.bucket_of(0), 0);
+        assert_eq!(policy.bucket_of(365 * SECONDS_PER_DAY), 2);
+
+        let before = policy.allocation(&[holding(6_000, 20), holding(1_000, 60), holding(3_000, 400)], 0);
+        assert_eq!(before, vec![6000, 1000, 3000]);
+        assert!(!policy.is_within(&before));
+
+        // Selling 2,000 of the short bond into the 60 day one is a step toward the ladder
+        let after = policy.allocation(&[holding(4_000, 20), holding(3_000, 60), holding(3_000, 400)], 0);
+        assert!(policy.moves_toward_targets(0, 1, &before, &after));
+        // Selling 3,000 overshoots the middle bucket
+        let overshoot = policy.allocation(&[holding(3_000, 20), holding(4_000, 60), holding(3_000, 400)], 0);
+        assert!(!policy.moves_toward_targets(0, 1, &before, &overshoot));
+        // Nothing moves out of an underweight bucket
+        assert!(!policy.moves_toward_targets(2, 1, &before, &after));
+
+        let laddered = policy.allocation(&[holding(2_000, 20), holding(3_000, 60), holding(5_000, 150)], 0);
+        assert!(policy.is_within(&laddered));
+    }
+
+    #[test]
+    fn test_weighted_average_maturity() {
+        assert_eq!(weighted_average_maturity(&[], 0), 0);
+        let holdings = [holding(1_000, 30), holding(3_000, 90), MaturityHolding { book_value: 1_000, maturity_timestamp: 0 }];
+        assert_eq!(weighted_average_maturity(&holdings, 0), 60 * SECONDS_PER_DAY);
+        // Time passing shortens it; matured holdings count as zero
+        assert_eq!(weighted_average_maturity(&holdings, 60 * SECONDS_PER_DAY), 18 * SECONDS_PER_DAY);
+    }
+}
@@ -1,9 +1,105 @@
 use anchor_lang::prelude::*;
 
+pub mod accounting;
+pub mod airdrop;
+pub mod auction;
+pub mod backstop;
+pub mod basket;
+pub mod bond_terms;
+pub mod buyback;
+pub mod collateral_lock;
+pub mod collateral_migration;
+pub mod collateral_registry;
+pub mod commitment;
+pub mod council;
+pub mod dead_man;
+pub mod dedupe;
+pub mod dividend;
+pub mod epoch_stats;
+pub mod fee_discount;
+pub mod invoice;
+pub mod issuer_bond;
+pub mod issuer_profile;
+pub mod liquidity;
+pub mod maturity_policy;
+pub mod ntt;
+pub mod operation_log;
+pub mod otc;
+pub mod peg;
+pub mod portfolio;
+pub mod position;
+pub mod protocol;
+pub mod rate_model;
+pub mod rebase;
+pub mod redeem_order;
+pub mod relay;
+pub mod remote;
+pub mod reserved_names;
+pub mod reserve;
+pub mod savings;
+pub mod scheduled_transfer;
+pub mod share_vault;
+pub mod snapshot;
+pub mod sponsor;
+pub mod staking;
 pub mod stablecoin;
+pub mod stream;
+pub mod subscription;
+pub mod supply_cap;
+pub mod throughput;
+pub mod tranche;
+pub mod treasury;
 pub mod vault;
 
+pub use accounting::*;
+pub use airdrop::*;
+pub use auction::*;
+pub use backstop::*;
+pub use basket::*;
+pub use bond_terms::*;
+pub use buyback::*;
+pub use collateral_lock::*;
+pub use collateral_migration::*;
+pub use collateral_registry::*;
+pub use commitment::*;
+pub use council::*;
+pub use dead_man::*;
+pub use dedupe::*;
+pub use dividend::*;
+pub use epoch_stats::*;
+pub use fee_discount::*;
+pub use invoice::*;
+pub use issuer_bond::*;
+pub use issuer_profile::*;
+pub use liquidity::*;
+pub use maturity_policy::*;
+pub use ntt::*;
+pub use operation_log::*;
+pub use otc::*;
+pub use peg::*;
+pub use portfolio::*;
+pub use position::*;
+pub use protocol::*;
+pub use rate_model::*;
+pub use rebase::*;
+pub use redeem_order::*;
+pub use relay::*;
+pub use remote::*;
+pub use reserved_names::*;
+pub use reserve::*;
+pub use savings::*;
+pub use scheduled_transfer::*;
+pub use share_vault::*;
+pub use snapshot::*;
+pub use sponsor::*;
+pub use staking::*;
 pub use stablecoin::*;
+pub use stream::*;
+pub use subscription::*;
+pub use supply_cap::*;
+pub use throughput::*;
+pub use tranche::*;
+pub use treasury::*;
 pub use vault::*;
 
 // Common constants shared across modules
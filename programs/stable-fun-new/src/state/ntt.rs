@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::error::StableFunError;
+
+/// Registration with a Wormhole Native Token Transfer manager in burn-and-mint
+/// mode. Collateral never leaves Solana: supply burned here for an outbound
+/// transfer is still backed and counted in `current_supply`.
+#[account]
+#[derive(Debug, Default)]
+pub struct NttConfig {
+    pub stablecoin_mint: Pubkey,
+    /// Signing authority of the NTT manager; the only caller of the hooks
+    pub ntt_manager: Pubkey,
+    /// Supply currently living on other chains
+    pub bridged_supply: u64,
+    pub outbound_limit: OutboundRateLimit,
+    pub total_outbound: u64,
+    pub total_inbound: u64,
+    pub bump: u8,
+}
+
+/// Fixed-window cap on the amount that may leave Solana.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct OutboundRateLimit {
+    /// Maximum amount per window; zero blocks outbound transfers
+    pub limit: u64,
+    pub window_seconds: i64,
+    pub window_start: i64,
+    pub used: u64,
+}
+
+impl OutboundRateLimit {
+    pub const LEN: usize = 8 + 8 + 8 + 8;
+
+    pub fn consume(&mut self, amount: u64, now: i64) -> Result<()> {
+        if now.saturating_sub(self.window_start) >= self.window_seconds {
+            self.window_start = now;
+            self.used = 0;
+        }
+        let used = self.used
+            .checked_add(amount)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        require!(used <= self.limit, StableFunError::NttRateLimitExceeded);
+        self.used = used;
+        Ok(())
+    }
+}
+
+impl StateAccount for NttConfig {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        PUBKEY_LENGTH +    // ntt_manager
+        8 +               // bridged_supply
+        OutboundRateLimit::LEN + // outbound_limit
+        8 +               // total_outbound
+        8 +               // total_inbound
+        1;               // bump
+}
+
+impl NttConfig {
+    pub fn record_outbound(&mut self, amount: u64, now: i64) -> Result<()> {
+        self.outbound_limit.consume(amount, now)?;
+        self.bridged_supply = self.bridged_supply
+            .checked_add(amount)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        self.total_outbound = self.total_outbound
+            .checked_add(amount)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        Ok(())
+    }
+
+    /// Only supply that previously left Solana can come back.
+    pub fn record_inbound(&mut self, amount: u64) -> Result<()> {
+        self.bridged_supply = self.bridged_supply
+            .checked_sub(amount)
+            .ok_or(error!(StableFunError::BridgedSupplyExceeded))?;
+        self.total_inbound = self.total_inbound
+            .checked_add(amount)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outbound_rate_limit() {
+        let mut config = NttConfig {
+            outbound_limit: OutboundRateLimit {
+                limit: 1_000,
+                window_seconds: 3_600,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        config.record_outbound(600, 10).unwrap();
+        assert!(config.record_outbound(401, 20).is_err());
+        config.record_outbound(400, 20).unwrap();
+
+        // A new window frees the capacity again
+        config.record_outbound(1_000, 3_610).unwrap();
+        assert_eq!(config.bridged_supply, 2_000);
+
+        config.record_inbound(1_500).unwrap();
+        assert!(config.record_inbound(501).is_err());
+        assert_eq!(config.bridged_supply, 500);
+        assert_eq!(config.total_inbound, 1_500);
+    }
+}
@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use super::DISCRIMINATOR_LENGTH;
+
+/// Number of operations kept before the oldest entry is overwritten
+pub const OPERATION_LOG_CAPACITY: usize = 64;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum OperationType {
+    Mint = 0,
+    Redeem = 1,
+    Liquidate = 2,
+}
+
+#[zero_copy]
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct OperationRecord {
+    pub user: Pubkey,
+    pub amount: u64,
+    /// Oracle price the operation executed at
+    pub price: u64,
+    pub fee: u64,
+    pub slot: u64,
+    /// Caller-supplied reference for reconciliation (zeroes when none)
+    pub client_order_id: [u8; 32],
+    /// `OperationType` discriminant
+    pub op_type: u8,
+    pub _padding: [u8; 7],
+}
+
+impl OperationRecord {
+    pub fn new(op_type: OperationType, user: Pubkey, amount: u64, price: u64, fee: u64, slot: u64) -> Self {
+        Self {
+            user,
+            amount,
+            price,
+            fee,
+            slot,
+            client_order_id: [0; 32],
+            op_type: op_type as u8,
+            _padding: [0; 7],
+        }
+    }
+
+    pub fn with_client_order_id(mut self, client_order_id: Option<[u8; 32]>) -> Self {
+        self.client_order_id = client_order_id.unwrap_or_default();
+        self
+    }
+}
+
+/// Ring buffer of the most recent mint/redeem/liquidate operations of one
+/// stablecoin, kept on-chain so disputes can be answered without an indexer.
+#[account(zero_copy)]
+pub struct OperationLog {
+    pub stablecoin_mint: Pubkey,
+    /// Index the next record is written to
+    pub head: u64,
+    /// Operations appended over the log's lifetime
+    pub total_operations: u64,
+    pub bump: u8,
+    pub _padding: [u8; 7],
+    pub records: [OperationRecord; OPERATION_LOG_CAPACITY],
+}
+
+impl OperationLog {
+    pub const LEN: usize = DISCRIMINATOR_LENGTH + std::mem::size_of::<OperationLog>();
+
+    pub fn append(&mut self, record: OperationRecord) {
+        let head = self.head as usize % OPERATION_LOG_CAPACITY;
+        self.records[head] = record;
+        self.head = ((head + 1) % OPERATION_LOG_CAPACITY) as u64;
+        self.total_operations = self.total_operations.saturating_add(1);
+    }
+
+    /// Stored records, oldest first.
+    pub fn recent(&self) -> Vec<OperationRecord> {
+        let len = (self.total_operations as usize).min(OPERATION_LOG_CAPACITY);
+        let start = (self.head as usize + OPERATION_LOG_CAPACITY - len) % OPERATION_LOG_CAPACITY;
+        (0..len)
+            .map(|i| self.records[(start + i) % OPERATION_LOG_CAPACITY])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_buffer_wraps() {
+        let mut log: OperationLog = bytemuck::Zeroable::zeroed();
+        let user = Pubkey::new_unique();
+        for i in 0..(OPERATION_LOG_CAPACITY as u64 + 3) {
+            log.append(OperationRecord::new(OperationType::Mint, user, i, 1_000_000, 0, i));
+        }
+
+        let recent = log.recent();
+        assert_eq!(recent.len(), OPERATION_LOG_CAPACITY);
+        assert_eq!(recent[0].amount, 3);
+        assert_eq!(recent.last().unwrap().amount, OPERATION_LOG_CAPACITY as u64 + 2);
+        assert_eq!(log.total_operations, OPERATION_LOG_CAPACITY as u64 + 3);
+    }
+
+    #[test]
+    fn test_client_order_id() {
+        let record = OperationRecord::new(OperationType::Redeem, Pubkey::new_unique(), 1, 1, 0, 1);
+        assert_eq!(record.with_client_order_id(None).client_order_id, [0; 32]);
+        assert_eq!(record.with_client_order_id(Some([7; 32])).client_order_id, [7; 32]);
+    }
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::error::StableFunError;
+
+/// A maker's escrowed offer to swap a fixed amount of one stablecoin for a
+/// fixed amount of another.
+#[account]
+#[derive(Debug, Default)]
+pub struct OtcOffer {
+    pub maker: Pubkey,
+    pub offer_id: u64,
+    /// Stablecoin (program `StablecoinMint` account) the maker gives
+    pub offered_stablecoin: Pubkey,
+    /// Amount sitting in the escrow
+    pub offered_amount: u64,
+    /// Stablecoin the maker wants in return
+    pub requested_stablecoin: Pubkey,
+    pub requested_amount: u64,
+    /// Only taker allowed to accept (default pubkey: anyone)
+    pub counterparty: Pubkey,
+    /// Unix time after which the offer can no longer be accepted (0: never)
+    pub expires_at: i64,
+    /// Escrow token account owned by this offer
+    pub token_account: Pubkey,
+    pub bump: u8,
+}
+
+impl StateAccount for OtcOffer {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // maker
+        8 +               // offer_id
+        PUBKEY_LENGTH +    // offered_stablecoin
+        8 +               // offered_amount
+        PUBKEY_LENGTH +    // requested_stablecoin
+        8 +               // requested_amount
+        PUBKEY_LENGTH +    // counterparty
+        8 +               // expires_at
+        PUBKEY_LENGTH +    // token_account
+        1;               // bump
+}
+
+impl OtcOffer {
+    pub fn validate_acceptance(&self, taker: &Pubkey, now: i64) -> Result<()> {
+        require!(
+            self.counterparty == Pubkey::default() || self.counterparty == *taker,
+            StableFunError::UnauthorizedCounterparty
+        );
+        require!(
+            self.expires_at == 0 || now <= self.expires_at,
+            StableFunError::OfferExpired
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offer_acceptance() {
+        let taker = Pubkey::new_unique();
+        let mut offer = OtcOffer {
+            expires_at: 1_000,
+            ..Default::default()
+        };
+        assert!(offer.validate_acceptance(&taker, 1_000).is_ok());
+        assert!(offer.validate_acceptance(&taker, 1_001).is_err());
+
+        offer.expires_at = 0;
+        let counterparty = Pubkey::new_unique();
+        offer.counterparty = counterparty;
+        assert!(offer.validate_acceptance(&taker, 1_001).is_err());
+        assert!(offer.validate_acceptance(&counterparty, 1_001).is_ok());
+    }
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::constants::{BASIS_POINTS_DIVISOR, PRICE_SCALE, SECONDS_PER_DAY};
+
+/// Market price of the stablecoin itself, as reported by a secondary feed
+/// (e.g. an AMM TWAP), and how far it sits from the peg.
+#[account]
+#[derive(Debug, Default)]
+pub struct PegMonitor {
+    pub stablecoin_mint: Pubkey,
+    /// Feed quoting the stablecoin in its target currency
+    pub market_feed: Pubkey,
+    /// Last recorded market price with `PRICE_DECIMALS` decimals
+    pub market_price: u64,
+    /// Signed distance from peg in bps (negative below peg)
+    pub deviation_bps: i32,
+    /// Largest absolute deviation seen since `window_start`
+    pub worst_deviation_bps: u32,
+    /// Start of the current daily window
+    pub window_start: i64,
+    pub last_updated: i64,
+    pub bump: u8,
+}
+
+impl StateAccount for PegMonitor {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        PUBKEY_LENGTH +    // market_feed
+        8 +               // market_price
+        4 +               // deviation_bps
+        4 +               // worst_deviation_bps
+        8 +               // window_start
+        8 +               // last_updated
+        1;               // bump
+}
+
+impl PegMonitor {
+    pub fn deviation_from_peg(market_price: u64) -> i32 {
+        let deviation = (market_price as i128 - PRICE_SCALE as i128) * BASIS_POINTS_DIVISOR as i128
+            / PRICE_SCALE as i128;
+        deviation.clamp(i32::MIN as i128, i32::MAX as i128) as i32
+    }
+
+    /// Stores a new observation, rolling the worst-deviation window over
+    /// once a day has passed since it started.
+    pub fn record(&mut self, market_price: u64, now: i64) {
+        if now.saturating_sub(self.window_start) >= SECONDS_PER_DAY {
+            self.window_start = now;
+            self.worst_deviation_bps = 0;
+        }
+        self.market_price = market_price;
+        self.deviation_bps = Self::deviation_from_peg(market_price);
+        self.worst_deviation_bps = self.worst_deviation_bps.max(self.deviation_bps.unsigned_abs());
+        self.last_updated = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deviation_window() {
+        let mut monitor = PegMonitor::default();
+        monitor.record(990_000, 1_000);
+        assert_eq!(monitor.deviation_bps, -100);
+        assert_eq!(monitor.worst_deviation_bps, 100);
+
+        monitor.record(1_002_000, 2_000);
+        assert_eq!(monitor.deviation_bps, 20);
+        assert_eq!(monitor.worst_deviation_bps, 100);
+
+        // A day later the window starts over
+        monitor.record(1_002_000, 1_000 + SECONDS_PER_DAY);
+        assert_eq!(monitor.worst_deviation_bps, 20);
+        assert_eq!(monitor.window_start, 1_000 + SECONDS_PER_DAY);
+    }
+}
@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::constants::MAX_PORTFOLIO_HOLDINGS;
+use crate::error::StableFunError;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PortfolioHolding {
+    pub stablecoin_mint: Pubkey,
+    /// Minted through the portfolio and not yet redeemed through it
+    pub balance: u64,
+    pub total_minted: u64,
+    pub total_redeemed: u64,
+    /// Mint-weighted average oracle price, with `PRICE_DECIMALS` decimals
+    pub average_mint_price: u64,
+    /// Mint and redeem fees paid on this coin
+    pub fees_paid: u64,
+}
+
+impl PortfolioHolding {
+    pub const LEN: usize =
+        PUBKEY_LENGTH + // stablecoin_mint
+        8 + // balance
+        8 + // total_minted
+        8 + // total_redeemed
+        8 + // average_mint_price
+        8; // fees_paid
+}
+
+/// A treasury's consolidated record of its positions across several
+/// stablecoins, updated by the mints and redeems it is passed to.
+#[account]
+#[derive(Debug, Default)]
+pub struct Portfolio {
+    pub owner: Pubkey,
+    pub holdings: Vec<PortfolioHolding>,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl StateAccount for Portfolio {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // owner
+        4 + MAX_PORTFOLIO_HOLDINGS * PortfolioHolding::LEN + // holdings
+        8 +               // created_at
+        1;               // bump
+}
+
+impl Portfolio {
+    /// The holding of `stablecoin_mint`, added on the coin's first operation.
+    fn holding_mut(&mut self, stablecoin_mint: Pubkey) -> Result<&mut PortfolioHolding> {
+        let index = match self.holdings.iter().position(|holding| holding.stablecoin_mint == stablecoin_mint) {
+            Some(index) => index,
+            None => {
+                require!(
+                    self.holdings.len() < MAX_PORTFOLIO_HOLDINGS,
+                    StableFunError::PortfolioFull
+                );
+                self.holdings.push(PortfolioHolding {
+                    stablecoin_mint,
+                    ..Default::default()
+                });
+                self.holdings.len() - 1
+            }
+        };
+        Ok(&mut self.holdings[index])
+    }
+
+    pub fn record_mint(
+        &mut self,
+        stablecoin_mint: Pubkey,
+        amount: u64,
+        price: u64,
+        fee: u64,
+    ) -> Result<()> {
+        let holding = self.holding_mut(stablecoin_mint)?;
+        let total_minted = holding.total_minted
+            .checked_add(amount)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        if total_minted > 0 {
+            let weighted = holding.average_mint_price as u128 * holding.total_minted as u128
+                + price as u128 * amount as u128;
+            holding.average_mint_price = u64::try_from(weighted / total_minted as u128)
+                .map_err(|_| error!(StableFunError::MathOverflow))?;
+        }
+        holding.total_minted = total_minted;
+        holding.balance = holding.balance
+            .checked_add(amount)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        holding.fees_paid = holding.fees_paid
+            .checked_add(fee)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        Ok(())
+    }
+
+    /// Redeeming coins bought elsewhere floors the balance at zero.
+    pub fn record_redeem(&mut self, stablecoin_mint: Pubkey, amount: u64, fee: u64) -> Result<()> {
+        let holding = self.holding_mut(stablecoin_mint)?;
+        holding.total_redeemed = holding.total_redeemed
+            .checked_add(amount)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        holding.balance = holding.balance.saturating_sub(amount);
+        holding.fees_paid = holding.fees_paid
+            .checked_add(fee)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_portfolio_holdings() {
+        let coin = Pubkey::new_unique();
+        let mut portfolio = Portfolio::default();
+
+        portfolio.record_mint(coin, 1_000, 1_000_000, 5).unwrap();
+        portfolio.record_mint(coin, 3_000, 1_004_000, 15).unwrap();
+        portfolio.record_redeem(coin, 5_000, 10).unwrap();
+
+        let holding = &portfolio.holdings[0];
+        assert_eq!(holding.average_mint_price, 1_003_000);
+        assert_eq!(holding.total_minted, 4_000);
+        assert_eq!(holding.total_redeemed, 5_000);
+        assert_eq!(holding.balance, 0);
+        assert_eq!(holding.fees_paid, 30);
+
+        for _ in 1..MAX_PORTFOLIO_HOLDINGS {
+            portfolio.record_mint(Pubkey::new_unique(), 1, 1, 0).unwrap();
+        }
+        assert!(portfolio.record_redeem(Pubkey::new_unique(), 1, 0).is_err());
+        assert!(portfolio.record_redeem(coin, 1, 0).is_ok());
+    }
+}
@@ -0,0 +1,204 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::utils::math;
+use crate::error::StableFunError;
+
+/// Per-user record for a stablecoin, created on the user's first mint or redeem.
+#[account]
+#[derive(Debug, Default)]
+pub struct UserPosition {
+    pub stablecoin_mint: Pubkey,
+    pub owner: Pubkey,
+    /// Collateral deposited through this position's mints
+    pub collateral_amount: u64,
+    /// Stablecoins minted through this position and not yet redeemed
+    pub debt_amount: u64,
+    pub last_mint_time: i64,
+    pub last_redeem_time: i64,
+    pub mint_count: u32,
+    pub redeem_count: u32,
+    pub bump: u8,
+    /// When the position was first flagged below the liquidation threshold (0 if not flagged)
+    pub flagged_at: i64,
+    /// Collateral ratio (bps) recorded when the position was flagged
+    pub flagged_ratio: u64,
+}
+
+impl StateAccount for UserPosition {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        PUBKEY_LENGTH +    // owner
+        8 +               // collateral_amount
+        8 +               // debt_amount
+        8 +               // last_mint_time
+        8 +               // last_redeem_time
+        4 +               // mint_count
+        4 +               // redeem_count
+        1 +               // bump
+        8 +               // flagged_at
+        8;               // flagged_ratio
+}
+
+impl UserPosition {
+    pub fn is_initialized(&self) -> bool {
+        self.owner != Pubkey::default()
+    }
+
+    pub fn initialize(&mut self, stablecoin_mint: Pubkey, owner: Pubkey, bump: u8) {
+        self.stablecoin_mint = stablecoin_mint;
+        self.owner = owner;
+        self.bump = bump;
+    }
+
+    /// Whether `delay` seconds have passed since the owner's last mint.
+    pub fn cooldown_elapsed(&self, now: i64, delay: i64) -> bool {
+        self.last_mint_time == 0 || now.saturating_sub(self.last_mint_time) >= delay
+    }
+
+    pub fn record_mint(&mut self, collateral: u64, debt: u64, now: i64) -> Result<()> {
+        self.collateral_amount = self.collateral_amount
+            .checked_add(collateral)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        self.debt_amount = self.debt_amount
+            .checked_add(debt)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        self.mint_count = self.mint_count
+            .checked_add(1)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        self.last_mint_time = now;
+        Ok(())
+    }
+
+    /// Collateral ratio in bps given the current value of the position's
+    /// collateral, or `None` when the position has no debt.
+    pub fn collateral_ratio(&self, collateral_value: u64) -> Option<u64> {
+        math::collateral_ratio_bps(collateral_value, self.debt_amount)
+    }
+
+    pub fn is_liquidatable(&self, collateral_value: u64, liquidation_threshold_bps: u16) -> bool {
+        self.collateral_ratio(collateral_value)
+            .is_some_and(|ratio| ratio < liquidation_threshold_bps as u64)
+    }
+
+    pub fn is_flagged(&self) -> bool {
+        self.flagged_at != 0
+    }
+
+    pub fn flag(&mut self, now: i64, ratio: u64) {
+        self.flagged_at = now;
+        self.flagged_ratio = ratio;
+    }
+
+    pub fn clear_flag(&mut self) {
+        self.flagged_at = 0;
+        self.flagged_ratio = 0;
+    }
+
+    /// Whether a flagged position may be liquidated: either the grace period
+    /// has passed, or the ratio has fallen `override_bps` below the flagged ratio.
+    pub fn grace_elapsed(
+        &self,
+        now: i64,
+        current_ratio: u64,
+        grace_seconds: i64,
+        override_bps: u16,
+    ) -> bool {
+        if grace_seconds == 0 {
+            return true;
+        }
+        if !self.is_flagged() {
+            return false;
+        }
+        now.saturating_sub(self.flagged_at) >= grace_seconds
+            || (override_bps > 0
+                && current_ratio.saturating_add(override_bps as u64) <= self.flagged_ratio)
+    }
+
+    /// Whether repaying `debt` would leave the position with nonzero debt
+    /// below `min_debt`, too small to be worth liquidating.
+    pub fn leaves_dust(&self, debt: u64, min_debt: u64) -> bool {
+        let remaining = self.debt_amount.saturating_sub(debt);
+        remaining > 0 && remaining < min_debt
+    }
+
+    /// Redeemers may hold coins minted by someone else, so the position's own
+    /// collateral and debt are only reduced down to zero.
+    pub fn record_redeem(&mut self, collateral: u64, debt: u64, now: i64) -> Result<()> {
+        self.collateral_amount = self.collateral_amount.saturating_sub(collateral);
+        self.debt_amount = self.debt_amount.saturating_sub(debt);
+        self.redeem_count = self.redeem_count
+            .checked_add(1)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        self.last_redeem_time = now;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cooldown() {
+        let mut position = UserPosition::default();
+        assert!(position.cooldown_elapsed(1000, 60)); // never minted
+
+        position.record_mint(1_000, 1_000, 1000).unwrap();
+        assert!(!position.cooldown_elapsed(1000, 60));
+        assert!(!position.cooldown_elapsed(1059, 60));
+        assert!(position.cooldown_elapsed(1060, 60));
+        assert!(position.cooldown_elapsed(1000, 0));
+    }
+
+    #[test]
+    fn test_liquidation_buffer() {
+        let mut position = UserPosition::default();
+        assert!(!position.is_liquidatable(0, 12000)); // no debt
+
+        position.record_mint(0, 1_000, 10).unwrap();
+        assert_eq!(position.collateral_ratio(1_300), Some(13000));
+        // Below a 150% minimum ratio but above a 120% threshold: not liquidatable
+        assert!(!position.is_liquidatable(1_300, 12000));
+        assert!(position.is_liquidatable(1_199, 12000));
+    }
+
+    #[test]
+    fn test_liquidation_grace_period() {
+        let mut position = UserPosition::default();
+        assert!(position.grace_elapsed(1000, 11000, 0, 0)); // grace disabled
+        assert!(!position.grace_elapsed(1000, 11000, 600, 500)); // not flagged
+
+        position.flag(1000, 11500);
+        assert!(!position.grace_elapsed(1599, 11400, 600, 500));
+        assert!(position.grace_elapsed(1600, 11400, 600, 500));
+        // Deteriorated by 500 bps since flagging
+        assert!(position.grace_elapsed(1100, 11000, 600, 500));
+        assert!(!position.grace_elapsed(1100, 11000, 600, 0));
+
+        position.clear_flag();
+        assert!(!position.is_flagged());
+    }
+
+    #[test]
+    fn test_leaves_dust() {
+        let mut position = UserPosition::default();
+        position.record_mint(0, 5_000, 10).unwrap();
+
+        assert!(!position.leaves_dust(3_000, 1_000));
+        assert!(position.leaves_dust(4_500, 1_000));
+        // Full closes and over-repayment leave nothing behind
+        assert!(!position.leaves_dust(5_000, 1_000));
+        assert!(!position.leaves_dust(6_000, 1_000));
+    }
+
+    #[test]
+    fn test_redeem_saturates() {
+        let mut position = UserPosition::default();
+        position.record_mint(500, 1_000, 10).unwrap();
+        position.record_redeem(800, 2_000, 20).unwrap();
+
+        assert_eq!(position.collateral_amount, 0);
+        assert_eq!(position.debt_amount, 0);
+        assert_eq!(position.redeem_count, 1);
+    }
+}
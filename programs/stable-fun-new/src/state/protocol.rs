@@ -0,0 +1,245 @@
+use anchor_lang::prelude::*;
+use super::{IssuerBond, ReserveReport, StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::constants::{MAX_CPI_CALLERS, MAX_LENDING_PROGRAMS};
+
+// Feature bits of `ProtocolConfig::paused_features`
+pub const PAUSE_MINT: u32 = 1 << 0;
+pub const PAUSE_REDEEM: u32 = 1 << 1;
+pub const PAUSE_LIQUIDATION: u32 = 1 << 2;
+/// Escrows and payments: streams, invoices, offers, airdrops and the like
+pub const PAUSE_TRANSFERS: u32 = 1 << 3;
+/// Share, savings, tranche and backstop vaults
+pub const PAUSE_VAULTS: u32 = 1 << 4;
+pub const PAUSE_BRIDGE: u32 = 1 << 5;
+pub const PAUSE_STAKING: u32 = 1 << 6;
+
+/// Program-wide configuration and the caps issuers' settings are held to.
+#[account]
+#[derive(Debug, Default)]
+pub struct ProtocolConfig {
+    /// Protocol admin (the program upgrade authority at creation)
+    pub admin: Pubkey,
+    /// Upper bound for `StablecoinSettings::liquidation_penalty_bps`
+    pub max_liquidation_penalty_bps: u16,
+    /// Upper bound for `StablecoinSettings::liquidator_bonus_bps`
+    pub max_liquidator_bonus_bps: u16,
+    pub bump: u8,
+    /// Upper bound for a coin's `demurrage_rate_bps`, fixed at initialize
+    pub max_demurrage_bps: u16,
+    /// Attestation registry program holding KYC attestations
+    pub attestation_program: Pubkey,
+    /// Credential the registry attestations must be issued under
+    pub attestation_credential: Pubkey,
+    /// Schema issuers must be attested under to initialize (default pubkey: not required)
+    pub issuer_schema: Pubkey,
+    /// Schema holders must be attested under on regulated coins
+    pub holder_schema: Pubkey,
+    /// Gate mint/redeem of regulated coins on a holder attestation
+    pub require_holder_attestation: bool,
+    /// Upper bound for `StablecoinSettings::yield_fee_bps`
+    pub max_yield_fee_bps: u16,
+    /// Highest `max_supply` of a coin without a reserve attestation (0: uncapped)
+    pub unverified_supply_cap: u64,
+    /// Highest `max_supply` of a coin with a fresh, fully backed reserve
+    /// report (0: uncapped)
+    pub reserve_attested_supply_cap: u64,
+    /// How old a reserve report may be and still attest the reserves
+    pub reserve_attestation_max_age: i64,
+    /// Account launch fees are paid to
+    pub protocol_treasury: Pubkey,
+    /// Lamports an issuer pays the treasury to initialize a coin
+    pub launch_fee_lamports: u64,
+    /// Lamports held on a new coin's account, refunded by `close_stablecoin`
+    pub launch_deposit_lamports: u64,
+    /// Lending programs allowed to lock holders' coins as collateral
+    pub approved_lending_programs: Vec<Pubkey>,
+    /// Kill switch halting every state-changing instruction except the
+    /// protocol admin's and the coins' own pause controls
+    pub paused: bool,
+    /// Bitmask of `PAUSE_*` features halted on every coin
+    pub paused_features: u32,
+    /// Programs allowed to CPI into mint and redeem for their users; while
+    /// empty, any program may
+    pub approved_cpi_callers: Vec<Pubkey>,
+    /// Share of every coin's mint/redeem fees paid to protocol token stakers
+    pub staking_fee_share_bps: u16,
+    /// Token issuer bonds are posted in (the protocol token or USDC)
+    pub bond_mint: Pubkey,
+    /// `max_supply` above which a coin needs `min_issuer_bond` posted (0: no bonds)
+    pub bond_supply_threshold: u64,
+    pub min_issuer_bond: u64,
+    /// Token account of `bond_mint` slashed bonds are paid into
+    pub insurance_fund: Pubkey,
+}
+
+impl ProtocolConfig {
+    pub fn requires_issuer_attestation(&self) -> bool {
+        self.issuer_schema != Pubkey::default()
+    }
+
+    pub fn is_approved_lender(&self, lending_program: &Pubkey) -> bool {
+        self.approved_lending_programs.contains(lending_program)
+    }
+
+    /// Whether a mint or redeem may run when invoked by `caller`: top-level
+    /// calls (`None`) always may, CPIs only from approved callers once the
+    /// allowlist is in use.
+    pub fn allows_cpi_caller(&self, caller: Option<&Pubkey>) -> bool {
+        match caller {
+            None => true,
+            Some(program) => {
+                self.approved_cpi_callers.is_empty() || self.approved_cpi_callers.contains(program)
+            }
+        }
+    }
+
+    /// Whether the kill switch is off.
+    pub fn is_live(&self) -> bool {
+        !self.paused
+    }
+
+    /// Whether instructions of `feature` may run.
+    pub fn is_active(&self, feature: u32) -> bool {
+        self.is_live() && self.paused_features & feature == 0
+    }
+
+    /// The `max_supply` ceiling of the tier `reserve_report` puts the coin in.
+    pub fn supply_cap(&self, reserve_report: Option<&ReserveReport>, now: i64) -> u64 {
+        let attested = reserve_report.is_some_and(|report| {
+            report.attests_full_reserves(now, self.reserve_attestation_max_age)
+        });
+        let cap = if attested {
+            self.reserve_attested_supply_cap
+        } else {
+            self.unverified_supply_cap
+        };
+        if cap == 0 { u64::MAX } else { cap }
+    }
+
+    /// The `max_supply` ceiling of a coin with `issuer_bond` posted.
+    pub fn bonded_supply_cap(&self, issuer_bond: Option<&IssuerBond>) -> u64 {
+        let bonded = issuer_bond.is_some_and(|bond| {
+            bond.bond_mint == self.bond_mint && bond.bonded >= self.min_issuer_bond
+        });
+        if self.bond_supply_threshold == 0 || bonded { u64::MAX } else { self.bond_supply_threshold }
+    }
+}
+
+impl StateAccount for ProtocolConfig {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // admin
+        2 +               // max_liquidation_penalty_bps
+        2 +               // max_liquidator_bonus_bps
+        1 +               // bump
+        2 +               // max_demurrage_bps
+        PUBKEY_LENGTH +    // attestation_program
+        PUBKEY_LENGTH +    // attestation_credential
+        PUBKEY_LENGTH +    // issuer_schema
+        PUBKEY_LENGTH +    // holder_schema
+        1 +                // require_holder_attestation
+        2 +                // max_yield_fee_bps
+        8 +                // unverified_supply_cap
+        8 +                // reserve_attested_supply_cap
+        8 +                // reserve_attestation_max_age
+        PUBKEY_LENGTH +    // protocol_treasury
+        8 +                // launch_fee_lamports
+        8 +                // launch_deposit_lamports
+        4 + MAX_LENDING_PROGRAMS * PUBKEY_LENGTH + // approved_lending_programs
+        1 +                // paused
+        4 +                // paused_features
+        4 + MAX_CPI_CALLERS * PUBKEY_LENGTH + // approved_cpi_callers
+        2 +                // staking_fee_share_bps
+        PUBKEY_LENGTH +    // bond_mint
+        8 +                // bond_supply_threshold
+        8 +                // min_issuer_bond
+        PUBKEY_LENGTH;     // insurance_fund
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supply_cap_tiers() {
+        let config = ProtocolConfig {
+            unverified_supply_cap: 1_000_000,
+            reserve_attested_supply_cap: 50_000_000,
+            reserve_attestation_max_age: 86_400,
+            ..Default::default()
+        };
+        let mut report = ReserveReport {
+            reserve_ratio: 10_000,
+            timestamp: 1_000,
+            report_count: 1,
+            ..Default::default()
+        };
+        assert_eq!(config.supply_cap(None, 1_000), 1_000_000);
+        assert_eq!(config.supply_cap(Some(&report), 87_400), 50_000_000);
+        // A stale report falls back to the unverified tier
+        assert_eq!(config.supply_cap(Some(&report), 87_401), 1_000_000);
+
+        report.reserve_ratio = 9_999;
+        assert_eq!(config.supply_cap(Some(&report), 1_000), 1_000_000);
+
+        assert_eq!(ProtocolConfig::default().supply_cap(None, 1_000), u64::MAX);
+    }
+
+    #[test]
+    fn test_bonded_supply_cap() {
+        let bond_mint = Pubkey::new_unique();
+        let mut config = ProtocolConfig {
+            bond_mint,
+            bond_supply_threshold: 1_000_000,
+            min_issuer_bond: 50_000,
+            ..Default::default()
+        };
+        let mut bond = IssuerBond {
+            bond_mint,
+            bonded: 50_000,
+            ..Default::default()
+        };
+        assert_eq!(config.bonded_supply_cap(None), 1_000_000);
+        assert_eq!(config.bonded_supply_cap(Some(&bond)), u64::MAX);
+
+        bond.bonded = 49_999;
+        assert_eq!(config.bonded_supply_cap(Some(&bond)), 1_000_000);
+
+        // A bond in a retired bond token no longer counts
+        bond.bonded = 50_000;
+        config.bond_mint = Pubkey::new_unique();
+        assert_eq!(config.bonded_supply_cap(Some(&bond)), 1_000_000);
+
+        config.bond_supply_threshold = 0;
+        assert_eq!(config.bonded_supply_cap(None), u64::MAX);
+    }
+
+    #[test]
+    fn test_cpi_caller_allowlist() {
+        let aggregator = Pubkey::new_unique();
+        let mut config = ProtocolConfig::default();
+        assert!(config.allows_cpi_caller(None));
+        assert!(config.allows_cpi_caller(Some(&aggregator)));
+
+        config.approved_cpi_callers = vec![aggregator];
+        assert!(config.allows_cpi_caller(None));
+        assert!(config.allows_cpi_caller(Some(&aggregator)));
+        assert!(!config.allows_cpi_caller(Some(&Pubkey::new_unique())));
+        // Callers nested deeper than one CPI cannot be identified
+        assert!(!config.allows_cpi_caller(Some(&Pubkey::default())));
+    }
+
+    #[test]
+    fn test_pause_switches() {
+        let mut config = ProtocolConfig {
+            paused_features: PAUSE_MINT | PAUSE_BRIDGE,
+            ..Default::default()
+        };
+        assert!(config.is_live());
+        assert!(!config.is_active(PAUSE_MINT));
+        assert!(config.is_active(PAUSE_REDEEM));
+
+        config.paused = true;
+        assert!(!config.is_live());
+        assert!(!config.is_active(PAUSE_REDEEM));
+    }
+}
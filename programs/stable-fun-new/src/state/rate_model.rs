@@ -0,0 +1,163 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::constants::{BASIS_POINTS_DIVISOR, MAX_STABILITY_FEE_BPS, SECONDS_PER_YEAR};
+use crate::error::StableFunError;
+
+/// Fixed-point scale of `InterestRateModel::cumulative_index`
+pub const RATE_INDEX_SCALE: u128 = 1_000_000_000_000;
+
+/// Kinked stability-fee curve for one stablecoin. Utilization is the share
+/// of the collateral's minting capacity (at `min_collateral_ratio`) in use:
+/// the fee grows along `slope1` up to `optimal_utilization_bps` and along
+/// the steeper `slope2` beyond it, as the collateral buffer runs thin.
+#[account]
+#[derive(Debug, Default)]
+pub struct InterestRateModel {
+    pub stablecoin_mint: Pubkey,
+    /// Annual fee (bps) at zero utilization
+    pub base_rate_bps: u16,
+    /// Fee (bps) added between zero and optimal utilization
+    pub slope1_bps: u16,
+    /// Fee (bps) added between optimal and full utilization
+    pub slope2_bps: u16,
+    pub optimal_utilization_bps: u16,
+    /// Rate applied by the last accrual
+    pub current_rate_bps: u16,
+    /// Growth of one unit of debt since the model was created, scaled by `RATE_INDEX_SCALE`
+    pub cumulative_index: u128,
+    /// Stability fees accrued on the outstanding supply
+    pub total_accrued_fees: u64,
+    pub last_accrual: i64,
+    pub bump: u8,
+}
+
+impl StateAccount for InterestRateModel {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        2 +               // base_rate_bps
+        2 +               // slope1_bps
+        2 +               // slope2_bps
+        2 +               // optimal_utilization_bps
+        2 +               // current_rate_bps
+        16 +              // cumulative_index
+        8 +               // total_accrued_fees
+        8 +               // last_accrual
+        1;               // bump
+}
+
+impl InterestRateModel {
+    pub fn validate_params(
+        base_rate_bps: u16,
+        slope1_bps: u16,
+        slope2_bps: u16,
+        optimal_utilization_bps: u16,
+    ) -> Result<()> {
+        require!(
+            optimal_utilization_bps > 0 && optimal_utilization_bps < BASIS_POINTS_DIVISOR,
+            StableFunError::InvalidInterestRateModel
+        );
+        let max_rate = base_rate_bps as u32 + slope1_bps as u32 + slope2_bps as u32;
+        require!(max_rate <= MAX_STABILITY_FEE_BPS, StableFunError::InvalidInterestRateModel);
+        Ok(())
+    }
+
+    /// Share (bps) of the minting capacity of `collateral_value` taken by `supply`.
+    pub fn utilization_bps(supply: u64, collateral_value: u64, min_collateral_ratio: u16) -> u16 {
+        if supply == 0 {
+            return 0;
+        }
+        if collateral_value == 0 {
+            return BASIS_POINTS_DIVISOR;
+        }
+        let utilization = supply as u128 * min_collateral_ratio as u128 / collateral_value as u128;
+        utilization.min(BASIS_POINTS_DIVISOR as u128) as u16
+    }
+
+    pub fn rate_bps(&self, utilization_bps: u16) -> u16 {
+        let utilization = utilization_bps.min(BASIS_POINTS_DIVISOR) as u32;
+        let optimal = self.optimal_utilization_bps as u32;
+        let rate = if utilization <= optimal {
+            self.base_rate_bps as u32 + self.slope1_bps as u32 * utilization / optimal
+        } else {
+            let excess = utilization - optimal;
+            let excess_range = BASIS_POINTS_DIVISOR as u32 - optimal;
+            self.base_rate_bps as u32
+                + self.slope1_bps as u32
+                + self.slope2_bps as u32 * excess / excess_range
+        };
+        rate as u16
+    }
+
+    /// Charges `rate_bps` on `supply` for the time since the last accrual and
+    /// returns the fee accrued. The first call only starts the clock.
+    pub fn accrue(&mut self, supply: u64, rate_bps: u16, now: i64) -> Result<u64> {
+        if self.cumulative_index == 0 {
+            self.cumulative_index = RATE_INDEX_SCALE;
+        }
+        let elapsed = if self.last_accrual == 0 {
+            0
+        } else {
+            now.saturating_sub(self.last_accrual).max(0) as u128
+        };
+        self.last_accrual = now;
+        self.current_rate_bps = rate_bps;
+
+        let denominator = BASIS_POINTS_DIVISOR as u128 * SECONDS_PER_YEAR as u128;
+        let index_growth = self.cumulative_index
+            .checked_mul(rate_bps as u128 * elapsed)
+            .ok_or(error!(StableFunError::MathOverflow))?
+            / denominator;
+        self.cumulative_index = self.cumulative_index
+            .checked_add(index_growth)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+
+        let fee = supply as u128 * rate_bps as u128 * elapsed / denominator;
+        let fee = u64::try_from(fee).map_err(|_| error!(StableFunError::MathOverflow))?;
+        self.total_accrued_fees = self.total_accrued_fees
+            .checked_add(fee)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        Ok(fee)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kinked_curve() {
+        let model = InterestRateModel {
+            base_rate_bps: 100,
+            slope1_bps: 400,
+            slope2_bps: 5000,
+            optimal_utilization_bps: 8000,
+            ..Default::default()
+        };
+        assert_eq!(model.rate_bps(0), 100);
+        assert_eq!(model.rate_bps(4000), 300);
+        assert_eq!(model.rate_bps(8000), 500);
+        assert_eq!(model.rate_bps(9000), 3000);
+        assert_eq!(model.rate_bps(10000), 5500);
+
+        // 1,000 supply against 3,000 of collateral at 150% uses half the capacity
+        assert_eq!(InterestRateModel::utilization_bps(1_000, 3_000, 15000), 5000);
+        assert_eq!(InterestRateModel::utilization_bps(1_000, 0, 15000), 10000);
+        assert_eq!(InterestRateModel::utilization_bps(0, 0, 15000), 0);
+
+        assert!(InterestRateModel::validate_params(100, 400, 5000, 8000).is_ok());
+        assert!(InterestRateModel::validate_params(100, 400, 5000, 10000).is_err());
+        assert!(InterestRateModel::validate_params(5000, 5000, 5000, 8000).is_err());
+    }
+
+    #[test]
+    fn test_accrue() {
+        let mut model = InterestRateModel::default();
+        assert_eq!(model.accrue(1_000_000, 500, 1_000).unwrap(), 0);
+        assert_eq!(model.cumulative_index, RATE_INDEX_SCALE);
+
+        let fee = model.accrue(1_000_000, 500, 1_000 + SECONDS_PER_YEAR).unwrap();
+        assert_eq!(fee, 50_000);
+        assert_eq!(model.total_accrued_fees, 50_000);
+        assert_eq!(model.cumulative_index, RATE_INDEX_SCALE * 105 / 100);
+    }
+}
@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use crate::constants::{BASIS_POINTS_DIVISOR, MAX_REBASE_STEP_BPS, SECONDS_PER_YEAR};
+
+/// Scale of `StablecoinMint::rebase_index`; an index of exactly this value
+/// makes one token worth one unit of the peg.
+pub const REBASE_INDEX_SCALE: u128 = 1_000_000_000_000;
+
+/// What drives the rebasing index of an elastic-supply coin.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RebaseMode {
+    /// Balances are plain token amounts
+    #[default]
+    Disabled,
+    /// Expands above the peg and contracts below it, from the peg monitor
+    PegDeviation,
+    /// Only grows, passing collateral yield above the minimum ratio to holders
+    Yield,
+    /// Only shrinks, at the holding fee chosen at initialize
+    Demurrage,
+}
+
+/// Next index for a peg-deviation rebase: moves by the market deviation,
+/// capped at `MAX_REBASE_STEP_BPS` either way.
+pub fn peg_rebase_index(index: u128, deviation_bps: i32) -> u128 {
+    let step = deviation_bps.clamp(-(MAX_REBASE_STEP_BPS as i32), MAX_REBASE_STEP_BPS as i32);
+    let factor = (BASIS_POINTS_DIVISOR as i32 + step) as u128;
+    index * factor / BASIS_POINTS_DIVISOR as u128
+}
+
+/// Next index for a yield rebase: the index at which the underlying supply
+/// is exactly backed at `min_collateral_ratio`, never decreasing and capped
+/// at `MAX_REBASE_STEP_BPS` of growth per rebase.
+pub fn yield_rebase_index(
+    index: u128,
+    raw_supply: u64,
+    collateral_value: u64,
+    min_collateral_ratio: u16,
+) -> u128 {
+    if raw_supply == 0 || min_collateral_ratio == 0 {
+        return index;
+    }
+    let target = collateral_value as u128 * BASIS_POINTS_DIVISOR as u128 * REBASE_INDEX_SCALE
+        / (raw_supply as u128 * min_collateral_ratio as u128);
+    let cap = index * (BASIS_POINTS_DIVISOR + MAX_REBASE_STEP_BPS) as u128 / BASIS_POINTS_DIVISOR as u128;
+    target.clamp(index, cap)
+}
+
+/// Index after `elapsed` seconds of demurrage at `rate_bps` per year (simple
+/// interest between accruals).
+pub fn demurrage_index(index: u128, rate_bps: u16, elapsed: i64) -> u128 {
+    if elapsed <= 0 {
+        return index;
+    }
+    let decay = index * rate_bps as u128 * elapsed as u128
+        / (SECONDS_PER_YEAR as u128 * BASIS_POINTS_DIVISOR as u128);
+    index.saturating_sub(decay)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_peg_rebase_index() {
+        // 0.3% above peg expands by 0.3%, 5% below contracts by the 1% cap
+        assert_eq!(peg_rebase_index(REBASE_INDEX_SCALE, 30), REBASE_INDEX_SCALE * 10030 / 10000);
+        assert_eq!(peg_rebase_index(REBASE_INDEX_SCALE, -500), REBASE_INDEX_SCALE * 9900 / 10000);
+        assert_eq!(peg_rebase_index(REBASE_INDEX_SCALE, 0), REBASE_INDEX_SCALE);
+    }
+
+    #[test]
+    fn test_demurrage_index() {
+        // 1% a year for half a year
+        assert_eq!(
+            demurrage_index(REBASE_INDEX_SCALE, 100, SECONDS_PER_YEAR / 2),
+            REBASE_INDEX_SCALE * 9950 / 10000
+        );
+        assert_eq!(demurrage_index(REBASE_INDEX_SCALE, 100, 0), REBASE_INDEX_SCALE);
+        assert_eq!(demurrage_index(REBASE_INDEX_SCALE, 0, SECONDS_PER_YEAR), REBASE_INDEX_SCALE);
+    }
+
+    #[test]
+    fn test_yield_rebase_index() {
+        // 1,000 tokens backed by 1,506 at a 150% minimum: 0.4% of yield
+        let index = yield_rebase_index(REBASE_INDEX_SCALE, 1_000, 1_506, 15000);
+        assert_eq!(index, REBASE_INDEX_SCALE * 1004 / 1000);
+        // Undercollateralized books never shrink balances
+        assert_eq!(yield_rebase_index(REBASE_INDEX_SCALE, 1_000, 1_000, 15000), REBASE_INDEX_SCALE);
+        // Large surpluses are paid out gradually
+        assert_eq!(
+            yield_rebase_index(REBASE_INDEX_SCALE, 1_000, 3_000, 15000),
+            REBASE_INDEX_SCALE * 10100 / 10000
+        );
+    }
+}
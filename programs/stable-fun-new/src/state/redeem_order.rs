@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::error::StableFunError;
+
+/// Stablecoins escrowed for a redemption any keeper may execute once the
+/// oracle price reaches `min_price`.
+#[account]
+#[derive(Debug, Default)]
+pub struct RedeemOrder {
+    pub stablecoin_mint: Pubkey,
+    pub owner: Pubkey,
+    pub order_id: u64,
+    /// Escrow token account owned by this order
+    pub token_account: Pubkey,
+    /// Lowest oracle price, with `PRICE_DECIMALS` decimals, the order fills at
+    pub min_price: u64,
+    /// Stablecoin-denominated reward for the keeper that fills the order
+    pub keeper_fee: u64,
+    /// Unix time after which the order can no longer be filled (0: never)
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+impl StateAccount for RedeemOrder {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        PUBKEY_LENGTH +    // owner
+        8 +               // order_id
+        PUBKEY_LENGTH +    // token_account
+        8 +               // min_price
+        8 +               // keeper_fee
+        8 +               // expires_at
+        1;               // bump
+}
+
+impl RedeemOrder {
+    pub fn validate_fill(&self, oracle_price: u64, now: i64) -> Result<()> {
+        require!(
+            self.expires_at == 0 || now <= self.expires_at,
+            StableFunError::RedeemOrderExpired
+        );
+        require!(oracle_price >= self.min_price, StableFunError::RedeemOrderPriceNotReached);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fill_conditions() {
+        let mut order = RedeemOrder {
+            min_price: 1_010_000,
+            expires_at: 5_000,
+            ..Default::default()
+        };
+        assert!(order.validate_fill(1_009_999, 1_000).is_err());
+        assert!(order.validate_fill(1_010_000, 5_000).is_ok());
+        assert!(order.validate_fill(1_020_000, 5_001).is_err());
+
+        order.expires_at = 0;
+        assert!(order.validate_fill(1_020_000, i64::MAX).is_ok());
+    }
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::error::StableFunError;
+
+/// Replay protection for a user's relayed orders on one stablecoin. Also the
+/// delegate a user approves on their collateral account for relayed mints.
+#[account]
+#[derive(Debug, Default)]
+pub struct RelayAccount {
+    pub stablecoin_mint: Pubkey,
+    pub user: Pubkey,
+    /// Orders must use strictly increasing nonces
+    pub last_nonce: u64,
+    /// Stablecoin tips paid to relayers so far
+    pub total_fees: u64,
+    pub bump: u8,
+}
+
+impl StateAccount for RelayAccount {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        PUBKEY_LENGTH +    // user
+        8 +               // last_nonce
+        8 +               // total_fees
+        1;               // bump
+}
+
+impl RelayAccount {
+    pub fn is_initialized(&self) -> bool {
+        self.user != Pubkey::default()
+    }
+
+    pub fn initialize(&mut self, stablecoin_mint: Pubkey, user: Pubkey, bump: u8) {
+        self.stablecoin_mint = stablecoin_mint;
+        self.user = user;
+        self.bump = bump;
+    }
+
+    /// Consumes an order's nonce once it is known to be live and within the
+    /// fee the user signed for.
+    pub fn record_order(&mut self, nonce: u64, expiry: i64, now: i64, fee: u64, max_fee: u64) -> Result<()> {
+        require!(now <= expiry, StableFunError::RelayedOrderExpired);
+        require!(nonce > self.last_nonce, StableFunError::RelayedOrderNonceUsed);
+        require!(fee <= max_fee, StableFunError::RelayerFeeTooHigh);
+        self.last_nonce = nonce;
+        self.total_fees = self.total_fees
+            .checked_add(fee)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_order() {
+        let mut relay = RelayAccount::default();
+        relay.record_order(1, 100, 50, 5, 10).unwrap();
+        assert!(relay.record_order(1, 100, 50, 5, 10).is_err());
+        assert!(relay.record_order(2, 100, 101, 5, 10).is_err());
+        assert!(relay.record_order(2, 100, 50, 11, 10).is_err());
+        relay.record_order(7, 100, 100, 10, 10).unwrap();
+        assert_eq!(relay.last_nonce, 7);
+        assert_eq!(relay.total_fees, 15);
+    }
+}
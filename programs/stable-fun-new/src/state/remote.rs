@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::error::StableFunError;
+
+/// EVM-side settlement desk allowed to sign redemption orders against the
+/// supply bridged out through NTT.
+#[account]
+#[derive(Debug, Default)]
+pub struct RemoteCustodian {
+    pub stablecoin_mint: Pubkey,
+    /// Ethereum address recovered from the custodian's secp256k1 signatures
+    pub eth_address: [u8; 20],
+    /// Orders must use strictly increasing nonces
+    pub last_nonce: u64,
+    pub total_redeemed: u64,
+    pub bump: u8,
+}
+
+impl StateAccount for RemoteCustodian {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        20 +              // eth_address
+        8 +               // last_nonce
+        8 +               // total_redeemed
+        1;               // bump
+}
+
+impl RemoteCustodian {
+    pub fn record_redemption(&mut self, nonce: u64, amount: u64) -> Result<()> {
+        require!(nonce > self.last_nonce, StableFunError::RemoteRedemptionNonceUsed);
+        self.last_nonce = nonce;
+        self.total_redeemed = self.total_redeemed
+            .checked_add(amount)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nonce_replay() {
+        let mut custodian = RemoteCustodian::default();
+        custodian.record_redemption(1, 500).unwrap();
+        assert!(custodian.record_redemption(1, 500).is_err());
+        custodian.record_redemption(5, 500).unwrap();
+        assert!(custodian.record_redemption(3, 500).is_err());
+        assert_eq!(custodian.total_redeemed, 1_000);
+    }
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::constants::BASIS_POINTS_DIVISOR;
+
+/// Latest proof-of-reserves snapshot for a stablecoin, overwritten on each report.
+#[account]
+#[derive(Debug, Default)]
+pub struct ReserveReport {
+    pub stablecoin_mint: Pubkey,
+    /// Actual balance of the vault collateral token account
+    pub vault_balance: u64,
+    /// Collateral recorded by the vault
+    pub recorded_collateral: u64,
+    /// Oracle price used for the valuation
+    pub price: u64,
+    /// Stablecoin value of `vault_balance` at `price`
+    pub reserve_value: u64,
+    /// Token supply outstanding at report time
+    pub outstanding_supply: u64,
+    /// `reserve_value / outstanding_supply` in bps (0 with no supply)
+    pub reserve_ratio: u64,
+    pub slot: u64,
+    pub timestamp: i64,
+    pub report_count: u64,
+    pub bump: u8,
+}
+
+impl StateAccount for ReserveReport {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        8 +               // vault_balance
+        8 +               // recorded_collateral
+        8 +               // price
+        8 +               // reserve_value
+        8 +               // outstanding_supply
+        8 +               // reserve_ratio
+        8 +               // slot
+        8 +               // timestamp
+        8 +               // report_count
+        1;               // bump
+}
+
+impl ReserveReport {
+    /// Whether this report, published within `max_age` of `now`, shows the
+    /// supply fully backed.
+    pub fn attests_full_reserves(&self, now: i64, max_age: i64) -> bool {
+        self.report_count > 0
+            && now.saturating_sub(self.timestamp) <= max_age
+            && self.reserve_ratio >= BASIS_POINTS_DIVISOR as u64
+    }
+
+    pub fn calculate_reserve_ratio(reserve_value: u64, outstanding_supply: u64) -> u64 {
+        if outstanding_supply == 0 {
+            return 0;
+        }
+        let ratio = (reserve_value as u128) * (BASIS_POINTS_DIVISOR as u128)
+            / (outstanding_supply as u128);
+        u64::try_from(ratio).unwrap_or(u64::MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reserve_ratio() {
+        assert_eq!(ReserveReport::calculate_reserve_ratio(1_500, 1_000), 15000);
+        assert_eq!(ReserveReport::calculate_reserve_ratio(900, 1_000), 9000);
+        assert_eq!(ReserveReport::calculate_reserve_ratio(900, 0), 0);
+    }
+}
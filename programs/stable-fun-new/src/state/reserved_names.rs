@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use super::{StateAccount, DISCRIMINATOR_LENGTH};
+use crate::constants::MAX_RESERVED_NAMES;
+use crate::error::StableFunError;
+
+/// Protocol-wide deny-list of names and symbols (e.g. "USDC", "Circle") no
+/// coin may launch under, stored as hashes of their canonical form.
+#[account]
+#[derive(Debug, Default)]
+pub struct ReservedNames {
+    pub hashes: Vec<[u8; 32]>,
+    pub bump: u8,
+}
+
+impl StateAccount for ReservedNames {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        4 + MAX_RESERVED_NAMES * 32 + // hashes
+        1;               // bump
+}
+
+impl ReservedNames {
+    /// Hash of `name` with case, spacing and punctuation stripped, so
+    /// "usdc", "U.S.D.C" and "USD C" all land on the same entry.
+    pub fn name_hash(name: &str) -> [u8; 32] {
+        let canonical: Vec<u8> = name
+            .bytes()
+            .filter(u8::is_ascii_alphanumeric)
+            .map(|byte| byte.to_ascii_uppercase())
+            .collect();
+        hashv(&[&canonical]).to_bytes()
+    }
+
+    /// Whether `name`, or any word in it, is reserved.
+    pub fn is_reserved(&self, name: &str) -> bool {
+        std::iter::once(name)
+            .chain(name.split_whitespace())
+            .any(|part| self.hashes.contains(&Self::name_hash(part)))
+    }
+
+    pub fn check(&self, name: &str, symbol: &str) -> Result<()> {
+        require!(
+            !self.is_reserved(name) && !self.is_reserved(symbol),
+            StableFunError::ReservedName
+        );
+        Ok(())
+    }
+
+    /// Applies an admin edit, skipping hashes already in (or already out of)
+    /// the list.
+    pub fn update(&mut self, add: &[[u8; 32]], remove: &[[u8; 32]]) -> Result<()> {
+        self.hashes.retain(|hash| !remove.contains(hash));
+        for hash in add {
+            if !self.hashes.contains(hash) {
+                self.hashes.push(*hash);
+            }
+        }
+        require!(
+            self.hashes.len() <= MAX_RESERVED_NAMES,
+            StableFunError::TooManyReservedNames
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reserved_name_matching() {
+        let mut reserved = ReservedNames::default();
+        reserved.update(
+            &[ReservedNames::name_hash("USDC"), ReservedNames::name_hash("Circle")],
+            &[],
+        ).unwrap();
+
+        assert!(reserved.is_reserved("usdc"));
+        assert!(reserved.is_reserved("U.S.D.C"));
+        assert!(reserved.is_reserved("Circle Dollar"));
+        assert!(!reserved.is_reserved("Circular"));
+        assert!(reserved.check("Fun Dollar", "FUSD").is_ok());
+        assert!(reserved.check("Fun Dollar", "USDC").is_err());
+
+        reserved.update(&[], &[ReservedNames::name_hash("usdc")]).unwrap();
+        assert!(!reserved.is_reserved("USDC"));
+        assert_eq!(reserved.hashes.len(), 1);
+    }
+}
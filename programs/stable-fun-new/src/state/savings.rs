@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use super::{ShareVault, StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::error::StableFunError;
+
+/// Fixed-point scale of `SavingsVault::exchange_rate`
+pub const SAVINGS_RATE_SCALE: u64 = 1_000_000_000;
+
+/// Auto-compounding savings pool of a stablecoin. Savers hold `share_mint`
+/// tokens; harvested bond yield is minted into the pool as stablecoins, so
+/// each share redeems for more over time instead of accruing claims.
+#[account]
+#[derive(Debug, Default)]
+pub struct SavingsVault {
+    pub stablecoin_mint: Pubkey,
+    pub share_mint: Pubkey,
+    /// Stablecoin account owned by this PDA
+    pub token_account: Pubkey,
+    /// Stablecoin tokens the shares are backed by; donations are ignored
+    pub total_assets: u64,
+    pub total_shares: u64,
+    /// `SystemAccounting::harvested_yield` as of the last harvest
+    pub yield_checkpoint: u64,
+    /// Yield compounded into the pool so far, in underlying units
+    pub total_yield: u64,
+    pub bump: u8,
+}
+
+impl StateAccount for SavingsVault {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        PUBKEY_LENGTH +    // share_mint
+        PUBKEY_LENGTH +    // token_account
+        8 +               // total_assets
+        8 +               // total_shares
+        8 +               // yield_checkpoint
+        8 +               // total_yield
+        1;               // bump
+}
+
+impl SavingsVault {
+    /// The pool's pro-rata cut of the yield harvested since the last
+    /// harvest: the share of `supply` it holds.
+    pub fn savers_yield(&self, harvested_yield: u64, supply: u64) -> Result<u64> {
+        if supply == 0 {
+            return Ok(0);
+        }
+        let harvested = harvested_yield.saturating_sub(self.yield_checkpoint) as u128;
+        let share = harvested * self.total_assets.min(supply) as u128 / supply as u128;
+        u64::try_from(share).map_err(|_| error!(StableFunError::MathOverflow))
+    }
+
+    pub fn shares_for(&self, assets: u64) -> Result<u64> {
+        ShareVault::convert_to_shares(assets, self.total_assets, self.total_shares)
+    }
+
+    pub fn assets_for(&self, shares: u64) -> Result<u64> {
+        ShareVault::convert_to_assets(shares, self.total_assets, self.total_shares)
+    }
+
+    /// Stablecoin tokens per share scaled by `SAVINGS_RATE_SCALE`. Shares
+    /// use the stablecoin's decimals, so this starts at exactly the scale.
+    pub fn exchange_rate(&self) -> u64 {
+        self.assets_for(SAVINGS_RATE_SCALE).unwrap_or(u64::MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_yield_compounds_into_rate() {
+        let mut savings = SavingsVault {
+            total_assets: 1_000_000,
+            total_shares: 1_000_000,
+            ..Default::default()
+        };
+        assert_eq!(savings.exchange_rate(), SAVINGS_RATE_SCALE);
+
+        // Savers hold a quarter of the supply, so they get a quarter of the yield
+        let earned = savings.savers_yield(40_000, 4_000_000).unwrap();
+        assert_eq!(earned, 10_000);
+        savings.total_assets += earned;
+        savings.yield_checkpoint = 40_000;
+        assert_eq!(savings.exchange_rate(), 1_009_999_990);
+        assert_eq!(savings.savers_yield(40_000, 4_000_000).unwrap(), 0);
+
+        // New deposits buy in at the higher rate
+        assert_eq!(savings.shares_for(1_010_000).unwrap(), 1_000_000);
+    }
+}
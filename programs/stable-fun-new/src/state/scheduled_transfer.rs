@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+
+/// Stablecoins escrowed by `sender` that anyone can release to `recipient`
+/// once `release_time` has passed.
+#[account]
+#[derive(Debug, Default)]
+pub struct ScheduledTransfer {
+    pub stablecoin_mint: Pubkey,
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+    pub transfer_id: u64,
+    /// Escrow token account owned by this transfer
+    pub token_account: Pubkey,
+    pub amount: u64,
+    pub release_time: i64,
+    pub bump: u8,
+}
+
+impl StateAccount for ScheduledTransfer {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        PUBKEY_LENGTH +    // sender
+        PUBKEY_LENGTH +    // recipient
+        8 +               // transfer_id
+        PUBKEY_LENGTH +    // token_account
+        8 +               // amount
+        8 +               // release_time
+        1;               // bump
+}
+
+impl ScheduledTransfer {
+    pub fn is_released(&self, now: i64) -> bool {
+        now >= self.release_time
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_release_time() {
+        let transfer = ScheduledTransfer {
+            amount: 1_000,
+            release_time: 100,
+            ..Default::default()
+        };
+        assert!(!transfer.is_released(99));
+        assert!(transfer.is_released(100));
+    }
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::error::StableFunError;
+
+/// ERC-4626 style pool of a stablecoin's collateral. Depositors receive
+/// `share_mint` tokens; yield paid into `collateral_account` raises the
+/// assets behind every share. Kept apart from the stablecoin backing vault.
+#[account]
+#[derive(Debug, Default)]
+pub struct ShareVault {
+    pub stablecoin_mint: Pubkey,
+    pub share_mint: Pubkey,
+    /// Stablebond account owned by this PDA
+    pub collateral_account: Pubkey,
+    pub total_deposited: u64,
+    pub total_withdrawn: u64,
+    pub bump: u8,
+}
+
+impl StateAccount for ShareVault {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        PUBKEY_LENGTH +    // share_mint
+        PUBKEY_LENGTH +    // collateral_account
+        8 +               // total_deposited
+        8 +               // total_withdrawn
+        1;               // bump
+}
+
+impl ShareVault {
+    /// Shares minted for `assets`, rounded down. The +1 virtual share and
+    /// asset keep the first depositor from inflating the share price.
+    pub fn convert_to_shares(assets: u64, total_assets: u64, total_shares: u64) -> Result<u64> {
+        let shares = assets as u128 * (total_shares as u128 + 1) / (total_assets as u128 + 1);
+        u64::try_from(shares).map_err(|_| error!(StableFunError::MathOverflow))
+    }
+
+    /// Assets paid out for `shares`, rounded down.
+    pub fn convert_to_assets(shares: u64, total_assets: u64, total_shares: u64) -> Result<u64> {
+        let assets = shares as u128 * (total_assets as u128 + 1) / (total_shares as u128 + 1);
+        u64::try_from(assets).map_err(|_| error!(StableFunError::MathOverflow))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_share_conversion() {
+        // First deposit is one to one
+        assert_eq!(ShareVault::convert_to_shares(1_000, 0, 0).unwrap(), 1_000);
+
+        // 10% yield makes each share worth more
+        assert_eq!(ShareVault::convert_to_assets(1_000, 1_100, 1_000).unwrap(), 1_099);
+        assert_eq!(ShareVault::convert_to_shares(1_100, 1_100, 1_000).unwrap(), 1_000);
+
+        // After a donation, deposits worth less than a share round to zero
+        // (and are rejected), while the donor only recovers half through the
+        // virtual share
+        assert_eq!(ShareVault::convert_to_shares(1_000, 1_000_001, 1).unwrap(), 0);
+        assert_eq!(ShareVault::convert_to_assets(1, 1_000_001, 1).unwrap(), 500_001);
+    }
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::utils::merkle;
+
+/// Merkle root of holder balances at a past slot, posted by the issuer.
+#[account]
+#[derive(Debug, Default)]
+pub struct Snapshot {
+    pub stablecoin_mint: Pubkey,
+    pub authority: Pubkey,
+    /// Slot the balances were taken at
+    pub slot: u64,
+    /// Root over `merkle::leaf_hash(index, holder, balance)` leaves
+    pub merkle_root: [u8; 32],
+    pub total_balance: u64,
+    pub holder_count: u32,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl StateAccount for Snapshot {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        PUBKEY_LENGTH +    // authority
+        8 +               // slot
+        32 +              // merkle_root
+        8 +               // total_balance
+        4 +               // holder_count
+        8 +               // created_at
+        1;               // bump
+}
+
+impl Snapshot {
+    /// Whether `holder` held `balance` at the snapshot slot.
+    pub fn verify_snapshot_claim(
+        &self,
+        index: u64,
+        holder: &Pubkey,
+        balance: u64,
+        proof: &[[u8; 32]],
+    ) -> bool {
+        merkle::verify_proof(
+            proof,
+            &self.merkle_root,
+            merkle::leaf_hash(index, holder, balance),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_snapshot_claim() {
+        let alice = Pubkey::new_unique();
+        let bob = Pubkey::new_unique();
+        let alice_leaf = merkle::leaf_hash(0, &alice, 1_000);
+        let bob_leaf = merkle::leaf_hash(1, &bob, 2_500);
+        let snapshot = Snapshot {
+            merkle_root: merkle::node_hash(&alice_leaf, &bob_leaf),
+            ..Default::default()
+        };
+
+        assert!(snapshot.verify_snapshot_claim(0, &alice, 1_000, &[bob_leaf]));
+        assert!(snapshot.verify_snapshot_claim(1, &bob, 2_500, &[alice_leaf]));
+        assert!(!snapshot.verify_snapshot_claim(1, &bob, 5_000, &[alice_leaf]));
+    }
+}
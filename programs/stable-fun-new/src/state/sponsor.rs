@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+
+/// `mint` is sponsored
+pub const SPONSOR_MINT: u8 = 1 << 0;
+/// `redeem` and `reveal_redeem` are sponsored
+pub const SPONSOR_REDEEM: u8 = 1 << 1;
+/// `relayed_mint` is sponsored
+pub const SPONSOR_RELAYED_MINT: u8 = 1 << 2;
+/// `relayed_redeem` is sponsored
+pub const SPONSOR_RELAYED_REDEEM: u8 = 1 << 3;
+pub const SPONSOR_ALL_ACTIONS: u8 =
+    SPONSOR_MINT | SPONSOR_REDEEM | SPONSOR_RELAYED_MINT | SPONSOR_RELAYED_REDEEM;
+
+/// SOL the issuer set aside to refund fee payers of sponsored instructions.
+/// The lamports sit on this account itself.
+#[account]
+#[derive(Debug, Default)]
+pub struct Sponsor {
+    pub stablecoin_mint: Pubkey,
+    /// Bitmask of the `SPONSOR_*` actions
+    pub sponsored_actions: u8,
+    /// Flat refund per sponsored transaction
+    pub lamports_per_tx: u64,
+    pub total_reimbursed: u64,
+    pub sponsored_count: u64,
+    pub bump: u8,
+}
+
+impl StateAccount for Sponsor {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        1 +               // sponsored_actions
+        8 +               // lamports_per_tx
+        8 +               // total_reimbursed
+        8 +               // sponsored_count
+        1;               // bump
+}
+
+impl Sponsor {
+    /// Refund owed for one transaction, never dipping into the rent-exempt
+    /// minimum `reserved`.
+    pub fn reimbursement(&self, balance: u64, reserved: u64) -> u64 {
+        self.lamports_per_tx.min(balance.saturating_sub(reserved))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reimbursement() {
+        let sponsor = Sponsor {
+            lamports_per_tx: 10_000,
+            ..Default::default()
+        };
+        assert_eq!(sponsor.reimbursement(1_000_000, 2_000), 10_000);
+        assert_eq!(sponsor.reimbursement(7_000, 2_000), 5_000);
+        assert_eq!(sponsor.reimbursement(1_000, 2_000), 0);
+    }
+}
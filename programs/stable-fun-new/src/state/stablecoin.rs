@@ -1,6 +1,15 @@
 use anchor_lang::prelude::*;
 use crate::error::StableFunError;
-use crate::state::StateAccount; 
+use crate::state::{
+    demurrage_index, ProtocolConfig, RebaseMode, StateAccount, ThroughputBucket, REBASE_INDEX_SCALE,
+};
+use crate::constants::{
+    BASIS_POINTS_DIVISOR, COUNCIL_FEE_CHANGE_BPS, DEFAULT_LIQUIDATION_PENALTY_BPS,
+    DEFAULT_LIQUIDATION_THRESHOLD, DEFAULT_LIQUIDATOR_BONUS_BPS, MIN_WITHDRAWAL_DELAY,
+};
+use crate::utils::MINIMUM_LIQUIDITY;
+use crate::utils::validation::MIN_TRANSACTION_AMOUNT;
+use crate::utils::math::Flow;
 
 // Constants
 pub const MAX_NAME_LENGTH: usize = 32;
@@ -8,6 +17,15 @@ pub const MAX_SYMBOL_LENGTH: usize = 10;
 pub const MAX_CURRENCY_LENGTH: usize = 10;
 pub const DISCRIMINATOR_LENGTH: usize = 8;
 pub const PUBKEY_LENGTH: usize = 32;
+pub const MAX_URI_LENGTH: usize = 200;
+pub const MAX_DESCRIPTION_LENGTH: usize = 200;
+/// Prefix marking the name of a non-transferable test-mode coin
+pub const TEST_MODE_NAME_PREFIX: &str = "[TEST] ";
+
+/// Layout version written by this program. Accounts in an older layout must
+/// go through `migrate_stablecoin_layout` before any other instruction can
+/// load them.
+pub const STABLECOIN_VERSION: u8 = 2;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
 pub struct StablecoinSettings {
@@ -21,6 +39,92 @@ pub struct StablecoinSettings {
     pub mint_paused: bool,
     /// Whether redeeming is paused
     pub redeem_paused: bool,
+    /// Seconds a user must wait after minting before redeeming (0 disables)
+    pub withdrawal_delay: i64,
+    /// Reject mint and redeem of this coin within the same transaction
+    pub same_tx_guard: bool,
+    /// Redemptions above this share of supply (bps) must use commit-reveal (0 disables)
+    pub large_redeem_threshold_bps: u16,
+    /// Slots that must pass between `commit_redeem` and `reveal_redeem`
+    pub reveal_delay_slots: u64,
+    /// Collateral ratio (bps) below which a position can be liquidated;
+    /// kept strictly below `min_collateral_ratio` to leave a buffer
+    pub liquidation_threshold_bps: u16,
+    /// Share of repaid collateral (bps) a liquidated position pays to the protocol
+    pub liquidation_penalty_bps: u16,
+    /// Share of repaid collateral (bps) paid to the liquidator on top
+    pub liquidator_bonus_bps: u16,
+    /// Seconds a flagged position is protected from liquidation (0 disables)
+    pub liquidation_grace_seconds: i64,
+    /// Ratio drop (bps) since flagging that ends the grace period early (0 disables)
+    pub grace_override_bps: u16,
+    /// Surplus that must remain after issuer fee withdrawals
+    pub surplus_buffer: u64,
+    /// Consecutive failed oracle checks that auto-pause minting (0 disables)
+    pub oracle_failure_threshold: u16,
+    /// Seconds that must pass after a pause before it can be lifted (0 disables)
+    pub unpause_cooloff_seconds: i64,
+    /// Allowed gap between current_supply and the SPL (plus bridged) supply
+    pub supply_drift_tolerance: u64,
+    /// Mints and redeems up to this amount may use the vault's cached price (0 disables)
+    pub cached_price_max_amount: u64,
+    /// Slots a cached price stays usable after refresh_price
+    pub cached_price_max_age_slots: u64,
+    /// Share of harvested bond yield (bps) kept as issuer fees instead of
+    /// feeding the savings rate
+    pub yield_fee_bps: u16,
+    /// Share of booked collateral (bps) a redeem must leave liquid in the
+    /// vault; redeems breaching it queue through commit-reveal until
+    /// liquidity returns, unless the backstop pays them (0 disables)
+    pub liquidity_buffer_bps: u16,
+    /// Report paused, supply-cap and oracle rejections of instant mints and
+    /// redeems as `OperationRejected` events instead of failing
+    pub soft_fail_rejections: bool,
+    /// Smallest nonzero debt a position may be left with; never below
+    /// `MIN_TRANSACTION_AMOUNT`
+    pub min_position_debt: u64,
+    /// Reject mint and redeem of this coin when invoked through CPI, keeping
+    /// it out of flash-loan style compositions
+    pub top_level_only: bool,
+    /// Mint and redeem operations that may run back to back (0 disables)
+    pub throughput_capacity: u32,
+    /// Operations the throughput bucket regains per second
+    pub throughput_refill_per_second: u32,
+    /// Mint and redeem amounts above this emit a `LargeOperation` event for
+    /// AML monitoring (0 disables)
+    pub reporting_threshold: u64,
+}
+
+impl StablecoinSettings {
+    pub const LEN: usize =
+        2 + // fee_basis_points
+        8 + // max_supply
+        2 + // min_collateral_ratio
+        1 + // mint_paused
+        1 + // redeem_paused
+        8 + // withdrawal_delay
+        1 + // same_tx_guard
+        2 + // large_redeem_threshold_bps
+        8 + // reveal_delay_slots
+        2 + // liquidation_threshold_bps
+        2 + // liquidation_penalty_bps
+        2 + // liquidator_bonus_bps
+        8 + // liquidation_grace_seconds
+        2 + // grace_override_bps
+        8 + // surplus_buffer
+        2 + // oracle_failure_threshold
+        8 + // unpause_cooloff_seconds
+        8 + // supply_drift_tolerance
+        8 + // cached_price_max_amount
+        8 + // cached_price_max_age_slots
+        2 + // yield_fee_bps
+        2 + // liquidity_buffer_bps
+        1 + // soft_fail_rejections
+        8 + // min_position_debt
+        1 + // top_level_only
+        4 + // throughput_capacity
+        4 + // throughput_refill_per_second
+        8; // reporting_threshold
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
@@ -33,8 +137,35 @@ pub struct StablecoinStats {
     pub total_fees: u64,
     /// Number of unique holders
     pub holder_count: u32,
+    /// Harvested yield taken as yield fees
+    pub total_yield_fees: u64,
+    /// Supply locked on the first mint, never redeemable
+    pub locked_liquidity: u64,
     /// Reserved for future use
-    pub reserved: [u8; 24],
+    pub reserved: [u8; 8],
+}
+
+impl StablecoinStats {
+    pub const LEN: usize =
+        8 + // total_minted
+        8 + // total_burned
+        8 + // total_fees
+        4 + // holder_count
+        8 + // total_yield_fees
+        8 + // locked_liquidity
+        8;  // reserved
+}
+
+/// Who, if anyone, holds the freeze authority of the issued SPL mint.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FreezeAuthorityMode {
+    /// Token accounts can never be frozen
+    #[default]
+    None,
+    /// The program's freeze authority PDA, driven by compliance instructions
+    Program,
+    /// The issuer's authority key, used directly against the token program
+    Issuer,
 }
 
 #[account]
@@ -58,7 +189,8 @@ pub struct StablecoinMint {
     /// The stablebond token mint used as collateral
     pub stablebond_mint: Pubkey,
     
-    /// The oracle feed for price data
+    /// The oracle feed for price data: collateral per stablecoin unit, or the
+    /// bond's NAV in its underlying currency when `fx_feed` is set
     pub price_feed: Pubkey,
     
     /// Vault holding the collateral
@@ -78,6 +210,125 @@ pub struct StablecoinMint {
     
     /// Last time settings were updated
     pub last_updated: i64,
+
+    /// Account layout version (0 for accounts created before versioning)
+    pub version: u8,
+
+    /// Icon image URI for wallets and explorers
+    pub icon_uri: String,
+
+    /// URI of the off-chain metadata JSON
+    pub metadata_uri: String,
+
+    /// Short human readable description of the issuer/coin
+    pub description: String,
+
+    /// Freeze authority configuration chosen at initialization
+    pub freeze_authority_mode: FreezeAuthorityMode,
+
+    /// Non-transferable test-mode coin, until promoted to production
+    pub test_mode: bool,
+
+    /// Key allowed to flip the pause flags besides the authority
+    /// (default pubkey when no pauser is delegated)
+    pub pauser: Pubkey,
+
+    /// Consecutive slots in which `check_oracle` saw the feed fail validation
+    pub oracle_failure_streak: u16,
+
+    /// Slot of the last failure counted in `oracle_failure_streak`
+    pub last_oracle_failure_slot: u64,
+
+    /// When mint or redeem was last paused, for the unpause cool-off
+    pub paused_at: i64,
+
+    /// Address lookup table holding this coin's static accounts
+    /// (default pubkey until `create_lookup_table` runs)
+    pub lookup_table: Pubkey,
+
+    /// Currency basket the coin is pegged to instead of `target_currency`
+    /// (default pubkey for single-currency coins)
+    pub basket: Pubkey,
+
+    /// Elastic-supply mode; anything but `Disabled` makes balances scaled
+    pub rebase_mode: RebaseMode,
+
+    /// Underlying units per token, scaled by `REBASE_INDEX_SCALE`
+    pub rebase_index: u128,
+
+    pub last_rebase: i64,
+
+    /// Yearly holding fee charged through the rebase index, set at initialize
+    pub demurrage_rate_bps: u16,
+
+    /// Demurrage accrued in underlying units but not yet minted to the fee vault
+    pub pending_demurrage: u64,
+
+    /// Issued under a verified issuer attestation; holders of regulated coins
+    /// may be required to be attested too
+    pub regulated: bool,
+
+    /// Jurisdictions (one bit per region) whose residents may not hold the
+    /// coin, checked against holder attestations; set at initialize
+    pub jurisdiction_flags: u32,
+
+    /// Last authority action, watched by the dead-man switch
+    pub last_admin_action: i64,
+
+    /// Approval council gating critical changes (default pubkey when none)
+    pub council: Pubkey,
+
+    /// Bump of this account's own PDA
+    pub bump: u8,
+
+    /// Bump of the mint-authority PDA, which also signs burns and fee vault mints
+    pub mint_authority_bump: u8,
+
+    /// Bump of the fee vault PDA, stored before the vault itself exists
+    pub fee_vault_bump: u8,
+
+    /// Feed queued by `set_price_feed` to replace `price_feed`
+    /// (default pubkey when none is queued)
+    pub pending_price_feed: Pubkey,
+
+    /// When `pending_price_feed` may be applied
+    pub pending_price_feed_at: i64,
+
+    /// Lamports paid in at initialize, refunded when the coin is closed
+    pub launch_deposit: u64,
+
+    /// Set by the protocol admin once the issuer has been vetted; cleared
+    /// whenever the authority changes hands
+    pub verified: bool,
+
+    /// Mint and redeem operations left before the throughput guard trips
+    pub throughput: ThroughputBucket,
+
+    /// The authority is an SPL-Governance governance account; settings
+    /// changes must be signed by it through an executed proposal
+    pub governance_enabled: bool,
+
+    /// SPL-Governance program owning the governance account
+    /// (default pubkey when no handover was ever proposed)
+    pub governance_program: Pubkey,
+
+    /// Governance account queued by `propose_governance_handover`
+    /// (default pubkey when none is queued)
+    pub pending_governance: Pubkey,
+
+    /// FX feed from the collateral's underlying currency to the target
+    /// currency, composed with the NAV `price_feed` (default pubkey:
+    /// `price_feed` quotes the target currency directly)
+    pub fx_feed: Pubkey,
+
+    /// FX feed queued together with `pending_price_feed`
+    pub pending_fx_feed: Pubkey,
+
+    /// Bump of the freeze-authority PDA
+    pub freeze_authority_bump: u8,
+
+    /// Bump of the PDA owning the supply locked on the first mint
+    pub liquidity_lock_bump: u8,
 }
 
 impl StablecoinMint {
@@ -91,10 +342,46 @@ impl StablecoinMint {
         PUBKEY_LENGTH + // price_feed
         PUBKEY_LENGTH + // vault
         8 + // current_supply
-        32 + // settings
-        40 + // stats
+        StablecoinSettings::LEN + // settings
+        StablecoinStats::LEN + // stats
         8 + // created_at
-        8; // last_updated
+        8 + // last_updated
+        1 + // version
+        4 + MAX_URI_LENGTH + // icon_uri (string)
+        4 + MAX_URI_LENGTH + // metadata_uri (string)
+        4 + MAX_DESCRIPTION_LENGTH + // description (string)
+        1 + // freeze_authority_mode
+        1 + // test_mode
+        PUBKEY_LENGTH + // pauser
+        2 + // oracle_failure_streak
+        8 + // last_oracle_failure_slot
+        8 + // paused_at
+        PUBKEY_LENGTH + // lookup_table
+        PUBKEY_LENGTH + // basket
+        1 + // rebase_mode
+        16 + // rebase_index
+        8 + // last_rebase
+        2 + // demurrage_rate_bps
+        8 + // pending_demurrage
+        1 + // regulated
+        4 + // jurisdiction_flags
+        8 + // last_admin_action
+        PUBKEY_LENGTH + // council
+        1 + // bump
+        1 + // mint_authority_bump
+        1 + // fee_vault_bump
+        PUBKEY_LENGTH + // pending_price_feed
+        8 + // pending_price_feed_at
+        8 + // launch_deposit
+        1 + // verified
+        ThroughputBucket::LEN + // throughput
+        1 + // governance_enabled
+        PUBKEY_LENGTH + // governance_program
+        PUBKEY_LENGTH + // pending_governance
+        PUBKEY_LENGTH + // fx_feed
+        PUBKEY_LENGTH + // pending_fx_feed
+        1 + // freeze_authority_bump
+        1; // liquidity_lock_bump
 
     pub fn validate_name(name: &str) -> Result<()> {
         require!(
@@ -120,6 +407,218 @@ impl StablecoinMint {
         Ok(())
     }
 
+    pub fn validate_uri(uri: &str) -> Result<()> {
+        require!(
+            uri.len() <= MAX_URI_LENGTH && !uri.chars().any(char::is_whitespace),
+            StableFunError::InvalidUri
+        );
+        Ok(())
+    }
+
+    pub fn validate_description(description: &str) -> Result<()> {
+        require!(
+            description.len() <= MAX_DESCRIPTION_LENGTH,
+            StableFunError::InvalidDescription
+        );
+        Ok(())
+    }
+
+    pub fn watermark_name(name: &str) -> Result<String> {
+        let watermarked = format!("{}{}", TEST_MODE_NAME_PREFIX, Self::strip_watermark(name));
+        require!(watermarked.len() <= MAX_NAME_LENGTH, StableFunError::InvalidName);
+        Ok(watermarked)
+    }
+
+    pub fn strip_watermark(name: &str) -> &str {
+        name.strip_prefix(TEST_MODE_NAME_PREFIX).unwrap_or(name)
+    }
+
+    /// Whether a settings change signed by an account owned by `owner` is
+    /// acceptable: once governed, only the governance program's accounts sign.
+    pub fn accepts_settings_signer(&self, owner: &Pubkey) -> bool {
+        !self.governance_enabled || *owner == self.governance_program
+    }
+
+    /// Whether `key` may flip the pause flags: the authority or the delegated pauser.
+    pub fn is_pauser(&self, key: &Pubkey) -> bool {
+        *key == self.authority || (*key == self.pauser && self.pauser != Pubkey::default())
+    }
+
+    /// Counts a failed oracle check (at most once per slot) and returns true
+    /// when the streak has just reached the auto-pause threshold.
+    pub fn record_oracle_failure(&mut self, slot: u64, now: i64) -> bool {
+        if self.oracle_failure_streak > 0 && slot <= self.last_oracle_failure_slot {
+            return false;
+        }
+        self.oracle_failure_streak = self.oracle_failure_streak.saturating_add(1);
+        self.last_oracle_failure_slot = slot;
+
+        let threshold = self.settings.oracle_failure_threshold;
+        if threshold == 0 || self.oracle_failure_streak < threshold || self.settings.mint_paused {
+            return false;
+        }
+        self.pause_mint(now);
+        true
+    }
+
+    pub fn pause_mint(&mut self, now: i64) {
+        self.settings.mint_paused = true;
+        self.paused_at = now;
+    }
+
+    pub fn pause_redeem(&mut self, now: i64) {
+        self.settings.redeem_paused = true;
+        self.paused_at = now;
+    }
+
+    /// A healthy read ends the streak; minting stays paused until unpaused explicitly.
+    pub fn record_oracle_success(&mut self) {
+        self.oracle_failure_streak = 0;
+    }
+
+    /// Swaps in the queued price and FX feeds once their timelock has elapsed
+    /// and returns the old price feed. The failure streak belonged to the old feeds.
+    pub fn apply_pending_price_feed(&mut self, now: i64) -> Result<Pubkey> {
+        require!(
+            self.pending_price_feed != Pubkey::default(),
+            StableFunError::NoPendingPriceFeed
+        );
+        require!(now >= self.pending_price_feed_at, StableFunError::PriceFeedTimelockActive);
+        let old_feed = std::mem::replace(&mut self.price_feed, self.pending_price_feed);
+        self.fx_feed = std::mem::take(&mut self.pending_fx_feed);
+        self.pending_price_feed = Pubkey::default();
+        self.pending_price_feed_at = 0;
+        self.oracle_failure_streak = 0;
+        Ok(old_feed)
+    }
+
+    /// Supply the token program accounts for: SPL supply plus supply bridged out.
+    pub fn expected_supply(spl_supply: u64, bridged_supply: u64) -> Result<u64> {
+        spl_supply
+            .checked_add(bridged_supply)
+            .ok_or(error!(StableFunError::MathOverflow))
+    }
+
+    pub fn check_supply_drift(&self, spl_supply: u64, bridged_supply: u64) -> Result<()> {
+        let expected = Self::expected_supply(spl_supply, bridged_supply)?;
+        require!(
+            self.current_supply.abs_diff(expected) <= self.settings.supply_drift_tolerance,
+            StableFunError::SupplyDrift
+        );
+        Ok(())
+    }
+
+    /// Underlying units to lock on this mint: `MINIMUM_LIQUIDITY` for the
+    /// coin's first mint, so supply can never be rounded down to a sliver
+    /// a first depositor could manipulate; zero afterwards.
+    pub fn liquidity_lock_amount(&self) -> u64 {
+        if self.current_supply == 0 && self.stats.locked_liquidity == 0 {
+            MINIMUM_LIQUIDITY
+        } else {
+            0
+        }
+    }
+
+    /// Takes one mint or redeem operation from the throughput bucket; a
+    /// no-op while the guard is disabled.
+    pub fn consume_throughput(&mut self, now: i64) -> Result<()> {
+        let capacity = self.settings.throughput_capacity;
+        if capacity == 0 {
+            return Ok(());
+        }
+        self.throughput.consume(capacity, self.settings.throughput_refill_per_second, now)
+    }
+
+    /// Whether a mint or redeem of `amount` must be reported as a
+    /// `LargeOperation`.
+    pub fn exceeds_reporting_threshold(&self, amount: u64) -> bool {
+        let threshold = self.settings.reporting_threshold;
+        threshold > 0 && amount > threshold
+    }
+
+    /// Positions with less debt than this are dust nobody would liquidate.
+    pub fn min_position_debt(&self) -> u64 {
+        self.settings.min_position_debt.max(MIN_TRANSACTION_AMOUNT)
+    }
+
+    pub fn is_rebasing(&self) -> bool {
+        self.rebase_mode != RebaseMode::Disabled
+    }
+
+    /// Tokens representing `amount` underlying units, rounded down for
+    /// mints or up for burns so rounding never favours the user.
+    pub fn to_raw_amount(&self, amount: u64, round_up: bool) -> Result<u64> {
+        if !self.is_rebasing() {
+            return Ok(amount);
+        }
+        let scaled = amount as u128 * REBASE_INDEX_SCALE;
+        let raw = if round_up {
+            scaled.div_ceil(self.rebase_index)
+        } else {
+            scaled / self.rebase_index
+        };
+        #[cfg(debug_assertions)]
+        crate::utils::math::rounding_audit::record(
+            if round_up { Flow::FromUser } else { Flow::ToUser },
+            round_up,
+            scaled % self.rebase_index,
+        );
+        u64::try_from(raw).map_err(|_| error!(StableFunError::MathOverflow))
+    }
+
+    /// Underlying units currently represented by `raw` tokens.
+    pub fn to_underlying_amount(&self, raw: u64) -> Result<u64> {
+        if !self.is_rebasing() {
+            return Ok(raw);
+        }
+        let amount = raw as u128 * self.rebase_index / REBASE_INDEX_SCALE;
+        u64::try_from(amount).map_err(|_| error!(StableFunError::MathOverflow))
+    }
+
+    /// Shrinks the index for the time since the last accrual and books the
+    /// value taken from holders as pending demurrage. Returns that value.
+    pub fn accrue_demurrage(&mut self, now: i64) -> Result<u64> {
+        if self.rebase_mode != RebaseMode::Demurrage {
+            return Ok(0);
+        }
+        let supply_before = self.to_underlying_amount(self.current_supply)?;
+        self.rebase_index = demurrage_index(
+            self.rebase_index,
+            self.demurrage_rate_bps,
+            now.saturating_sub(self.last_rebase),
+        );
+        self.last_rebase = now;
+        let charged = supply_before.saturating_sub(self.to_underlying_amount(self.current_supply)?);
+        self.pending_demurrage = self.pending_demurrage
+            .checked_add(charged)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        Ok(charged)
+    }
+
+    /// Permissioned coins only mint and redeem for attested holders.
+    pub fn is_permissioned(&self, config: &ProtocolConfig) -> bool {
+        self.regulated && (config.require_holder_attestation || self.jurisdiction_flags != 0)
+    }
+
+    /// Stamps an authority action: resets the dead-man switch timer.
+    pub fn record_admin_action(&mut self, now: i64) {
+        self.last_updated = now;
+        self.last_admin_action = now;
+    }
+
+    /// Winds the coin down: no more minting, fee-free redemption.
+    pub fn enter_settlement(&mut self) {
+        self.settings.mint_paused = true;
+        self.settings.redeem_paused = false;
+        self.settings.fee_basis_points = 0;
+    }
+
+    /// Fee moves beyond `COUNCIL_FEE_CHANGE_BPS` must go through the council.
+    pub fn fee_change_needs_council(&self, new_fee_bps: u16) -> bool {
+        self.council != Pubkey::default()
+            && self.settings.fee_basis_points.abs_diff(new_fee_bps) > COUNCIL_FEE_CHANGE_BPS
+    }
+
     pub fn is_paused(&self) -> bool {
         self.settings.mint_paused || self.settings.redeem_paused
     }
@@ -140,10 +639,7 @@ impl StablecoinMint {
     }
 
     pub fn calculate_fee(&self, amount: u64) -> Result<u64> {
-        amount
-            .checked_mul(self.settings.fee_basis_points as u64)
-            .and_then(|product| product.checked_div(10000))
-            .ok_or(error!(StableFunError::MathOverflow))
+        crate::utils::math::bps_of(amount, self.settings.fee_basis_points, Flow::FromUser)
     }
 
 
@@ -155,6 +651,14 @@ impl StablecoinMint {
         self.settings.redeem_paused
     }
 
+    /// Whether redeeming `amount` is large enough to require commit-reveal.
+    pub fn requires_redeem_commitment(&self, amount: u64) -> bool {
+        let threshold = self.settings.large_redeem_threshold_bps;
+        threshold != 0
+            && (amount as u128) * (BASIS_POINTS_DIVISOR as u128)
+                > (self.current_supply as u128) * (threshold as u128)
+    }
+
     pub fn can_mint(&self, amount: u64) -> bool {
         if self.is_mint_paused() {
             return false;
@@ -163,7 +667,7 @@ impl StablecoinMint {
         // Check against max supply
         self.current_supply
             .checked_add(amount)
-            .map_or(false, |new_supply| new_supply <= self.settings.max_supply)
+            .is_some_and(|new_supply| new_supply <= self.settings.max_supply)
     }
 }
 
@@ -172,9 +676,111 @@ impl StateAccount for StablecoinMint {
     const LEN: usize = StablecoinMint::LEN;
 }
 
+/// `StablecoinSettings` as laid out before versioning
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct LegacyStablecoinSettings {
+    pub fee_basis_points: u16,
+    pub max_supply: u64,
+    pub min_collateral_ratio: u16,
+    pub mint_paused: bool,
+    pub redeem_paused: bool,
+}
+
+/// `StablecoinStats` as laid out before versioning
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct LegacyStablecoinStats {
+    pub total_minted: u64,
+    pub total_burned: u64,
+    pub total_fees: u64,
+    pub holder_count: u32,
+    pub reserved: [u8; 24],
+}
+
+/// `StablecoinMint` as created before versioning (version 0). Version 1
+/// appended `version` and the metadata strings to it; version 2 grew the
+/// nested settings and stats, so neither older layout deserializes as the
+/// current one and `migrate_stablecoin_layout` has to rewrite them.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct LegacyStablecoinMint {
+    pub authority: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub target_currency: String,
+    pub token_mint: Pubkey,
+    pub stablebond_mint: Pubkey,
+    pub price_feed: Pubkey,
+    pub vault: Pubkey,
+    pub current_supply: u64,
+    pub settings: LegacyStablecoinSettings,
+    pub stats: LegacyStablecoinStats,
+    pub created_at: i64,
+    pub last_updated: i64,
+}
+
+impl StablecoinMint {
+    /// Reads account data (after the discriminator) written in layout version
+    /// 0 or 1 as the current layout. Fields the old layout lacks get the
+    /// values `initialize` gives a new coin; bumps are left to the caller.
+    pub fn from_legacy_layout(mut data: &[u8]) -> Result<Self> {
+        let legacy = LegacyStablecoinMint::deserialize(&mut data)
+            .map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))?;
+        let mut stablecoin_mint = StablecoinMint {
+            authority: legacy.authority,
+            name: legacy.name,
+            symbol: legacy.symbol,
+            target_currency: legacy.target_currency,
+            token_mint: legacy.token_mint,
+            stablebond_mint: legacy.stablebond_mint,
+            price_feed: legacy.price_feed,
+            vault: legacy.vault,
+            current_supply: legacy.current_supply,
+            settings: StablecoinSettings {
+                fee_basis_points: legacy.settings.fee_basis_points,
+                max_supply: legacy.settings.max_supply,
+                min_collateral_ratio: legacy.settings.min_collateral_ratio,
+                mint_paused: legacy.settings.mint_paused,
+                redeem_paused: legacy.settings.redeem_paused,
+                withdrawal_delay: MIN_WITHDRAWAL_DELAY,
+                liquidation_threshold_bps: DEFAULT_LIQUIDATION_THRESHOLD,
+                liquidation_penalty_bps: DEFAULT_LIQUIDATION_PENALTY_BPS,
+                liquidator_bonus_bps: DEFAULT_LIQUIDATOR_BONUS_BPS,
+                ..Default::default()
+            },
+            stats: StablecoinStats {
+                total_minted: legacy.stats.total_minted,
+                total_burned: legacy.stats.total_burned,
+                total_fees: legacy.stats.total_fees,
+                holder_count: legacy.stats.holder_count,
+                ..Default::default()
+            },
+            created_at: legacy.created_at,
+            last_updated: legacy.last_updated,
+            last_admin_action: legacy.last_updated,
+            ..Default::default()
+        };
+
+        // Version 0 accounts are zero padded where version 1 stored its version
+        match data.first().copied().unwrap_or(0) {
+            0 => {}
+            1 => {
+                data = &data[1..];
+                let mut read_string = || String::deserialize(&mut data)
+                    .map_err(|_| error!(ErrorCode::AccountDidNotDeserialize));
+                stablecoin_mint.icon_uri = read_string()?;
+                stablecoin_mint.metadata_uri = read_string()?;
+                stablecoin_mint.description = read_string()?;
+            }
+            _ => return err!(StableFunError::UnsupportedLayoutVersion),
+        }
+        stablecoin_mint.version = STABLECOIN_VERSION;
+        Ok(stablecoin_mint)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::constants::SECONDS_PER_YEAR;
 
     #[test]
     fn test_validate_name() {
@@ -190,6 +796,38 @@ mod tests {
         assert!(StablecoinMint::validate_symbol(&"U".repeat(MAX_SYMBOL_LENGTH + 1)).is_err());
     }
 
+    #[test]
+    fn test_validate_uri_and_description() {
+        assert!(StablecoinMint::validate_uri("").is_ok());
+        assert!(StablecoinMint::validate_uri("https://example.com/icon.png").is_ok());
+        assert!(StablecoinMint::validate_uri("https://example.com/my icon.png").is_err());
+        assert!(StablecoinMint::validate_uri(&"a".repeat(MAX_URI_LENGTH + 1)).is_err());
+        assert!(StablecoinMint::validate_description("Backed by USTB stablebonds").is_ok());
+        assert!(StablecoinMint::validate_description(&"a".repeat(MAX_DESCRIPTION_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn test_name_watermark() {
+        let name = StablecoinMint::watermark_name("Peso Coin").unwrap();
+        assert_eq!(name, "[TEST] Peso Coin");
+        assert_eq!(StablecoinMint::watermark_name(&name).unwrap(), name);
+        assert_eq!(StablecoinMint::strip_watermark(&name), "Peso Coin");
+        assert!(StablecoinMint::watermark_name(&"a".repeat(MAX_NAME_LENGTH - 6)).is_err());
+    }
+
+    #[test]
+    fn test_requires_redeem_commitment() {
+        let mut mint = StablecoinMint {
+            current_supply: 1_000_000,
+            ..Default::default()
+        };
+        assert!(!mint.requires_redeem_commitment(1_000_000)); // disabled
+
+        mint.settings.large_redeem_threshold_bps = 1000; // 10% of supply
+        assert!(!mint.requires_redeem_commitment(100_000));
+        assert!(mint.requires_redeem_commitment(100_001));
+    }
+
     #[test]
     fn test_fee_calculation() {
         let mint = StablecoinMint {
@@ -203,4 +841,219 @@ mod tests {
         assert_eq!(mint.calculate_fee(1000).unwrap(), 3); // 0.3% of 1000
         assert_eq!(mint.calculate_fee(10000).unwrap(), 30); // 0.3% of 10000
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_is_pauser() {
+        let authority = Pubkey::new_unique();
+        let mut mint = StablecoinMint {
+            authority,
+            ..Default::default()
+        };
+        assert!(mint.is_pauser(&authority));
+        assert!(!mint.is_pauser(&Pubkey::default()));
+
+        let pauser = Pubkey::new_unique();
+        mint.pauser = pauser;
+        assert!(mint.is_pauser(&pauser));
+        assert!(!mint.is_pauser(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_accepts_settings_signer() {
+        let governance_program = Pubkey::new_unique();
+        let mut mint = StablecoinMint {
+            governance_program,
+            ..Default::default()
+        };
+        assert!(mint.accepts_settings_signer(&System::id()));
+
+        mint.governance_enabled = true;
+        assert!(!mint.accepts_settings_signer(&System::id()));
+        assert!(mint.accepts_settings_signer(&governance_program));
+    }
+
+    #[test]
+    fn test_oracle_failure_auto_pause() {
+        let mut mint = StablecoinMint::default();
+        mint.settings.oracle_failure_threshold = 3;
+
+        assert!(!mint.record_oracle_failure(10, 100));
+        assert!(!mint.record_oracle_failure(10, 100)); // same slot is not counted twice
+        assert!(!mint.record_oracle_failure(11, 101));
+        assert_eq!(mint.oracle_failure_streak, 2);
+        assert!(mint.record_oracle_failure(12, 102));
+        assert!(mint.settings.mint_paused);
+        assert_eq!(mint.paused_at, 102);
+
+        mint.record_oracle_success();
+        assert_eq!(mint.oracle_failure_streak, 0);
+        assert!(mint.settings.mint_paused);
+    }
+
+    #[test]
+    fn test_supply_drift() {
+        let mut mint = StablecoinMint {
+            current_supply: 1_000,
+            ..Default::default()
+        };
+        assert!(mint.check_supply_drift(800, 200).is_ok());
+        assert!(mint.check_supply_drift(1_001, 0).is_err());
+
+        mint.settings.supply_drift_tolerance = 5;
+        assert!(mint.check_supply_drift(995, 0).is_ok());
+        assert!(mint.check_supply_drift(1_005, 0).is_ok());
+        assert!(mint.check_supply_drift(994, 0).is_err());
+    }
+
+    #[test]
+    fn test_liquidity_lock_amount() {
+        let mut mint = StablecoinMint::default();
+        assert_eq!(mint.liquidity_lock_amount(), MINIMUM_LIQUIDITY);
+
+        mint.current_supply = 5_000 + MINIMUM_LIQUIDITY;
+        mint.stats.locked_liquidity = MINIMUM_LIQUIDITY;
+        assert_eq!(mint.liquidity_lock_amount(), 0);
+
+        // Redeeming everyone else out does not re-arm the lock
+        mint.current_supply = MINIMUM_LIQUIDITY;
+        assert_eq!(mint.liquidity_lock_amount(), 0);
+    }
+
+    #[test]
+    fn test_exceeds_reporting_threshold() {
+        let mut mint = StablecoinMint::default();
+        assert!(!mint.exceeds_reporting_threshold(u64::MAX));
+
+        mint.settings.reporting_threshold = 10_000;
+        assert!(!mint.exceeds_reporting_threshold(10_000));
+        assert!(mint.exceeds_reporting_threshold(10_001));
+    }
+
+    #[test]
+    fn test_apply_pending_price_feed() {
+        let old_feed = Pubkey::new_unique();
+        let new_feed = Pubkey::new_unique();
+        let mut mint = StablecoinMint {
+            price_feed: old_feed,
+            oracle_failure_streak: 2,
+            ..Default::default()
+        };
+        assert!(mint.apply_pending_price_feed(1_000).is_err());
+
+        let fx_feed = Pubkey::new_unique();
+        mint.pending_price_feed = new_feed;
+        mint.pending_fx_feed = fx_feed;
+        mint.pending_price_feed_at = 1_000;
+        assert!(mint.apply_pending_price_feed(999).is_err());
+        assert_eq!(mint.apply_pending_price_feed(1_000).unwrap(), old_feed);
+        assert_eq!(mint.price_feed, new_feed);
+        assert_eq!(mint.fx_feed, fx_feed);
+        assert_eq!(mint.pending_price_feed, Pubkey::default());
+        assert_eq!(mint.pending_fx_feed, Pubkey::default());
+        assert_eq!(mint.oracle_failure_streak, 0);
+    }
+
+    #[test]
+    fn test_from_legacy_layout() {
+        let legacy = LegacyStablecoinMint {
+            authority: Pubkey::new_unique(),
+            name: "Peso".to_string(),
+            symbol: "MXNX".to_string(),
+            target_currency: "MXN".to_string(),
+            current_supply: 5_000,
+            settings: LegacyStablecoinSettings {
+                fee_basis_points: 30,
+                max_supply: 1_000_000,
+                min_collateral_ratio: 15000,
+                redeem_paused: true,
+                ..Default::default()
+            },
+            stats: LegacyStablecoinStats {
+                total_minted: 6_000,
+                total_burned: 1_000,
+                holder_count: 3,
+                ..Default::default()
+            },
+            last_updated: 42,
+            ..Default::default()
+        };
+
+        // Version 0: the legacy fields followed by zero padding
+        let mut data = legacy.try_to_vec().unwrap();
+        data.resize(data.len() + 64, 0);
+        let mint = StablecoinMint::from_legacy_layout(&data).unwrap();
+        assert_eq!(mint.version, STABLECOIN_VERSION);
+        assert_eq!(mint.authority, legacy.authority);
+        assert_eq!(mint.symbol, "MXNX");
+        assert_eq!(mint.current_supply, 5_000);
+        assert_eq!(mint.settings.max_supply, 1_000_000);
+        assert!(mint.settings.redeem_paused);
+        assert_eq!(mint.settings.withdrawal_delay, MIN_WITHDRAWAL_DELAY);
+        assert_eq!(mint.settings.liquidation_threshold_bps, DEFAULT_LIQUIDATION_THRESHOLD);
+        assert_eq!(mint.stats.total_burned, 1_000);
+        assert_eq!(mint.last_admin_action, 42);
+        assert!(mint.icon_uri.is_empty());
+
+        // Version 1 appended the version and the metadata strings
+        let mut data = legacy.try_to_vec().unwrap();
+        data.push(1);
+        for field in ["https://icon", "https://meta", "Mexican peso"] {
+            data.extend(field.to_string().try_to_vec().unwrap());
+        }
+        let mint = StablecoinMint::from_legacy_layout(&data).unwrap();
+        assert_eq!(mint.version, STABLECOIN_VERSION);
+        assert_eq!(mint.icon_uri, "https://icon");
+        assert_eq!(mint.metadata_uri, "https://meta");
+        assert_eq!(mint.description, "Mexican peso");
+
+        let mut data = legacy.try_to_vec().unwrap();
+        data.push(7);
+        assert!(StablecoinMint::from_legacy_layout(&data).is_err());
+        assert!(StablecoinMint::from_legacy_layout(&data[..40]).is_err());
+    }
+
+    #[test]
+    fn test_rebase_rounding_audit() {
+        let stablecoin_mint = StablecoinMint {
+            rebase_mode: RebaseMode::Demurrage,
+            rebase_index: REBASE_INDEX_SCALE * 3,
+            ..Default::default()
+        };
+        crate::utils::math::rounding_audit::take();
+        // Mints round down, burns up: 1_000 / 3 leaves a remainder both ways
+        assert_eq!(stablecoin_mint.to_raw_amount(1_000, false).unwrap(), 333);
+        assert_eq!(stablecoin_mint.to_raw_amount(1_000, true).unwrap(), 334);
+        crate::utils::math::rounding_audit::assert_never_favours_user();
+    }
+
+    #[test]
+    fn test_rebase_amounts() {
+        let mut stablecoin_mint = StablecoinMint::default();
+        assert_eq!(stablecoin_mint.to_raw_amount(1_000, false).unwrap(), 1_000);
+        assert_eq!(stablecoin_mint.to_underlying_amount(1_000).unwrap(), 1_000);
+
+        // After a 2% expansion each token is worth 1.02 units
+        stablecoin_mint.rebase_mode = RebaseMode::Yield;
+        stablecoin_mint.rebase_index = REBASE_INDEX_SCALE * 102 / 100;
+        assert_eq!(stablecoin_mint.to_underlying_amount(1_000).unwrap(), 1_020);
+        assert_eq!(stablecoin_mint.to_raw_amount(1_000, false).unwrap(), 980);
+        assert_eq!(stablecoin_mint.to_raw_amount(1_000, true).unwrap(), 981);
+    }
+
+    #[test]
+    fn test_accrue_demurrage() {
+        let mut stablecoin_mint = StablecoinMint {
+            current_supply: 1_000_000,
+            rebase_mode: RebaseMode::Demurrage,
+            rebase_index: REBASE_INDEX_SCALE,
+            demurrage_rate_bps: 200,
+            ..Default::default()
+        };
+        // 2% a year for a quarter year: holders lose 0.5%
+        let charged = stablecoin_mint.accrue_demurrage(SECONDS_PER_YEAR / 4).unwrap();
+        assert_eq!(charged, 5_000);
+        assert_eq!(stablecoin_mint.pending_demurrage, 5_000);
+        assert_eq!(stablecoin_mint.to_underlying_amount(1_000_000).unwrap(), 995_000);
+        assert_eq!(stablecoin_mint.accrue_demurrage(SECONDS_PER_YEAR / 4).unwrap(), 0);
+    }
+}
@@ -0,0 +1,191 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::constants::MAX_STAKING_REWARDS;
+use crate::error::StableFunError;
+
+/// Scale of `RewardStream::reward_per_token`
+pub const REWARD_PER_TOKEN_SCALE: u128 = 1_000_000_000_000;
+
+/// Fee revenue of one stablecoin paid out to stakers.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct RewardStream {
+    pub stablecoin_mint: Pubkey,
+    /// Stablecoin account holding the rewards not yet claimed
+    pub reward_vault: Pubkey,
+    /// Raw stablecoin units earned per staked token, scaled by
+    /// `REWARD_PER_TOKEN_SCALE`
+    pub reward_per_token: u128,
+    pub total_distributed: u64,
+    /// Bump of the `reward_vault` PDA
+    pub bump: u8,
+}
+
+impl RewardStream {
+    pub const LEN: usize =
+        PUBKEY_LENGTH + // stablecoin_mint
+        PUBKEY_LENGTH + // reward_vault
+        16 + // reward_per_token
+        8 + // total_distributed
+        1; // bump
+}
+
+/// Protocol-wide staking of the protocol token. Stakers share the fee
+/// revenue of every stablecoin registered as a reward stream.
+#[account]
+#[derive(Debug, Default)]
+pub struct StakingPool {
+    pub stake_mint: Pubkey,
+    pub stake_vault: Pubkey,
+    /// Tokens earning rewards; cooling-down tokens no longer count
+    pub total_staked: u64,
+    /// Wait between requesting an unstake and withdrawing the tokens
+    pub cooldown_seconds: i64,
+    pub rewards: Vec<RewardStream>,
+    pub bump: u8,
+}
+
+impl StateAccount for StakingPool {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stake_mint
+        PUBKEY_LENGTH +    // stake_vault
+        8 +               // total_staked
+        8 +               // cooldown_seconds
+        4 + MAX_STAKING_REWARDS * RewardStream::LEN + // rewards
+        1;               // bump
+}
+
+impl StakingPool {
+    pub fn reward_index(&self, stablecoin_mint: &Pubkey) -> Result<usize> {
+        self.rewards
+            .iter()
+            .position(|stream| stream.stablecoin_mint == *stablecoin_mint)
+            .ok_or(error!(StableFunError::InvalidRewardStream))
+    }
+
+    /// Bump of the reward vault of `stablecoin_mint`'s stream (0, which no
+    /// seeds check accepts, when there is none).
+    pub fn reward_vault_bump(&self, stablecoin_mint: &Pubkey) -> u8 {
+        self.reward_index(stablecoin_mint)
+            .map_or(0, |index| self.rewards[index].bump)
+    }
+
+    /// Spreads `amount` of stream `index` over the tokens staked now.
+    pub fn distribute(&mut self, index: usize, amount: u64) -> Result<()> {
+        require!(self.total_staked > 0, StableFunError::InvalidAmount);
+        let stream = &mut self.rewards[index];
+        stream.reward_per_token = (amount as u128 * REWARD_PER_TOKEN_SCALE / self.total_staked as u128)
+            .checked_add(stream.reward_per_token)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        stream.total_distributed = stream.total_distributed
+            .checked_add(amount)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct StakerReward {
+    /// The stream's `reward_per_token` when last settled
+    pub reward_per_token_paid: u128,
+    /// Settled but not yet claimed
+    pub accrued: u64,
+}
+
+impl StakerReward {
+    pub const LEN: usize =
+        16 + // reward_per_token_paid
+        8; // accrued
+}
+
+/// A staker's tokens and rewards, one `StakerReward` per pool reward stream.
+#[account]
+#[derive(Debug, Default)]
+pub struct StakePosition {
+    pub owner: Pubkey,
+    pub staked: u64,
+    /// Requested for unstaking and no longer earning
+    pub cooling_amount: u64,
+    pub cooldown_ends_at: i64,
+    pub rewards: Vec<StakerReward>,
+    pub bump: u8,
+}
+
+impl StateAccount for StakePosition {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // owner
+        8 +               // staked
+        8 +               // cooling_amount
+        8 +               // cooldown_ends_at
+        4 + MAX_STAKING_REWARDS * StakerReward::LEN + // rewards
+        1;               // bump
+}
+
+impl StakePosition {
+    pub fn is_initialized(&self) -> bool {
+        self.owner != Pubkey::default()
+    }
+
+    /// Accrues what `staked` earned on every stream since the last
+    /// settlement. Must run before `staked` changes.
+    pub fn settle(&mut self, pool: &StakingPool) -> Result<()> {
+        // Streams registered since the last settlement start from zero
+        self.rewards.resize(pool.rewards.len(), StakerReward::default());
+        for (reward, stream) in self.rewards.iter_mut().zip(&pool.rewards) {
+            let earned = (stream.reward_per_token - reward.reward_per_token_paid)
+                * self.staked as u128
+                / REWARD_PER_TOKEN_SCALE;
+            reward.accrued = u64::try_from(earned)
+                .ok()
+                .and_then(|earned| reward.accrued.checked_add(earned))
+                .ok_or(error!(StableFunError::MathOverflow))?;
+            reward.reward_per_token_paid = stream.reward_per_token;
+        }
+        Ok(())
+    }
+
+    /// Takes everything accrued on stream `index`.
+    pub fn take_reward(&mut self, index: usize) -> u64 {
+        std::mem::take(&mut self.rewards[index].accrued)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reward_index_accrual() {
+        let coin = Pubkey::new_unique();
+        let mut pool = StakingPool {
+            rewards: vec![RewardStream {
+                stablecoin_mint: coin,
+                bump: 254,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        assert_eq!(pool.reward_vault_bump(&coin), 254);
+        assert_eq!(pool.reward_vault_bump(&Pubkey::new_unique()), 0);
+        let mut alice = StakePosition::default();
+        let mut bob = StakePosition::default();
+        assert!(pool.distribute(0, 1_000).is_err());
+
+        alice.settle(&pool).unwrap();
+        alice.staked = 300;
+        pool.total_staked = 300;
+        pool.distribute(0, 600).unwrap();
+
+        bob.settle(&pool).unwrap();
+        bob.staked = 100;
+        pool.total_staked = 400;
+        pool.distribute(0, 400).unwrap();
+
+        alice.settle(&pool).unwrap();
+        bob.settle(&pool).unwrap();
+        assert_eq!(alice.take_reward(0), 900);
+        assert_eq!(bob.take_reward(0), 100);
+        assert_eq!(alice.rewards[0].accrued, 0);
+        assert_eq!(pool.reward_index(&coin).unwrap(), 0);
+        assert!(pool.reward_index(&Pubkey::new_unique()).is_err());
+    }
+}
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::error::StableFunError;
+
+/// Stablecoins locked by `sender` and released linearly to `recipient`
+/// between `start_time` and `end_time`.
+#[account]
+#[derive(Debug, Default)]
+pub struct Stream {
+    pub stablecoin_mint: Pubkey,
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+    pub stream_id: u64,
+    /// Escrow token account owned by this stream
+    pub token_account: Pubkey,
+    pub total_amount: u64,
+    pub withdrawn_amount: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub bump: u8,
+}
+
+impl StateAccount for Stream {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        PUBKEY_LENGTH +    // sender
+        PUBKEY_LENGTH +    // recipient
+        8 +               // stream_id
+        PUBKEY_LENGTH +    // token_account
+        8 +               // total_amount
+        8 +               // withdrawn_amount
+        8 +               // start_time
+        8 +               // end_time
+        1;               // bump
+}
+
+impl Stream {
+    /// Amount released to the recipient by `now`, withdrawn or not.
+    pub fn vested_amount(&self, now: i64) -> u64 {
+        if now <= self.start_time {
+            return 0;
+        }
+        if now >= self.end_time {
+            return self.total_amount;
+        }
+        let elapsed = (now - self.start_time) as u128;
+        let duration = (self.end_time - self.start_time) as u128;
+        (self.total_amount as u128 * elapsed / duration) as u64
+    }
+
+    pub fn withdrawable(&self, now: i64) -> u64 {
+        self.vested_amount(now).saturating_sub(self.withdrawn_amount)
+    }
+
+    pub fn record_withdrawal(&mut self, amount: u64) -> Result<()> {
+        self.withdrawn_amount = self.withdrawn_amount
+            .checked_add(amount)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        require!(
+            self.withdrawn_amount <= self.total_amount,
+            StableFunError::InsufficientBalance
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linear_vesting() {
+        let mut stream = Stream {
+            total_amount: 1_000,
+            start_time: 100,
+            end_time: 200,
+            ..Default::default()
+        };
+        assert_eq!(stream.vested_amount(50), 0);
+        assert_eq!(stream.vested_amount(150), 500);
+        assert_eq!(stream.vested_amount(250), 1_000);
+
+        stream.record_withdrawal(stream.withdrawable(125)).unwrap();
+        assert_eq!(stream.withdrawn_amount, 250);
+        assert_eq!(stream.withdrawable(150), 250);
+        assert!(stream.record_withdrawal(751).is_err());
+    }
+}
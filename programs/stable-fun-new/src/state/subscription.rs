@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::error::StableFunError;
+
+/// Recurring `amount` pulled from `payer_token_account` to `merchant` every
+/// `interval` seconds, through a delegation to this account.
+#[account]
+#[derive(Debug, Default)]
+pub struct Subscription {
+    pub stablecoin_mint: Pubkey,
+    pub payer: Pubkey,
+    pub merchant: Pubkey,
+    /// Token account that delegated to this subscription
+    pub payer_token_account: Pubkey,
+    pub amount: u64,
+    pub interval: i64,
+    /// First time the next period can be collected
+    pub next_billing_at: i64,
+    pub periods_collected: u64,
+    pub bump: u8,
+}
+
+impl StateAccount for Subscription {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        PUBKEY_LENGTH +    // payer
+        PUBKEY_LENGTH +    // merchant
+        PUBKEY_LENGTH +    // payer_token_account
+        8 +               // amount
+        8 +               // interval
+        8 +               // next_billing_at
+        8 +               // periods_collected
+        1;               // bump
+}
+
+impl Subscription {
+    /// Books one billing period. Periods missed while nobody cranked are
+    /// still owed and can be collected back to back.
+    pub fn advance_period(&mut self, now: i64) -> Result<()> {
+        require!(now >= self.next_billing_at, StableFunError::SubscriptionNotDue);
+        self.next_billing_at = self.next_billing_at
+            .checked_add(self.interval)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        self.periods_collected = self.periods_collected
+            .checked_add(1)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_billing_periods() {
+        let mut subscription = Subscription {
+            amount: 10,
+            interval: 100,
+            next_billing_at: 1_000,
+            ..Default::default()
+        };
+        assert!(subscription.advance_period(999).is_err());
+        subscription.advance_period(1_000).unwrap();
+        assert_eq!(subscription.next_billing_at, 1_100);
+
+        // Late cranks catch up one period at a time
+        subscription.advance_period(1_250).unwrap();
+        subscription.advance_period(1_250).unwrap();
+        assert!(subscription.advance_period(1_250).is_err());
+        assert_eq!(subscription.periods_collected, 3);
+    }
+}
@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::constants::BASIS_POINTS_DIVISOR;
+use crate::error::StableFunError;
+
+/// Rules letting `max_supply` follow the collateral ratio: once per epoch it
+/// grows by `growth_bps` while the ratio is at or above `grow_above_ratio_bps`
+/// and shrinks by `shrink_bps` while it is below `shrink_below_ratio_bps`.
+/// A shrunk cap only blocks new mints; it never goes below the supply.
+#[account]
+#[derive(Debug, Default)]
+pub struct ElasticSupplyCap {
+    pub stablecoin_mint: Pubkey,
+    pub growth_bps: u16,
+    pub shrink_bps: u16,
+    pub grow_above_ratio_bps: u16,
+    pub shrink_below_ratio_bps: u16,
+    /// Bounds the cap is kept within; the protocol tier cap still applies
+    pub min_cap: u64,
+    pub max_cap: u64,
+    /// Epoch of the last adjustment
+    pub last_epoch: u64,
+    pub bump: u8,
+}
+
+impl StateAccount for ElasticSupplyCap {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        2 +               // growth_bps
+        2 +               // shrink_bps
+        2 +               // grow_above_ratio_bps
+        2 +               // shrink_below_ratio_bps
+        8 +               // min_cap
+        8 +               // max_cap
+        8 +               // last_epoch
+        1;               // bump
+}
+
+impl ElasticSupplyCap {
+    pub fn validate_params(
+        growth_bps: u16,
+        shrink_bps: u16,
+        grow_above_ratio_bps: u16,
+        shrink_below_ratio_bps: u16,
+        min_cap: u64,
+        max_cap: u64,
+    ) -> Result<()> {
+        require!(
+            growth_bps <= BASIS_POINTS_DIVISOR && shrink_bps < BASIS_POINTS_DIVISOR,
+            StableFunError::InvalidElasticSupplyCap
+        );
+        require!(
+            shrink_below_ratio_bps <= grow_above_ratio_bps && min_cap <= max_cap,
+            StableFunError::InvalidElasticSupplyCap
+        );
+        Ok(())
+    }
+
+    /// The cap for the next epoch. `ratio_bps` is `None` without debt, which
+    /// counts as healthy; `ceiling` is the lower of `max_cap` and the tier cap.
+    pub fn next_cap(&self, cap: u64, ratio_bps: Option<u64>, supply: u64, ceiling: u64) -> u64 {
+        let ratio = ratio_bps.unwrap_or(u64::MAX);
+        if ratio >= self.grow_above_ratio_bps as u64 {
+            let growth = cap as u128 * self.growth_bps as u128 / BASIS_POINTS_DIVISOR as u128;
+            let grown = (cap as u128 + growth).min(ceiling as u128) as u64;
+            grown.max(cap)
+        } else if ratio < self.shrink_below_ratio_bps as u64 {
+            let cut = cap as u128 * self.shrink_bps as u128 / BASIS_POINTS_DIVISOR as u128;
+            let shrunk = cap - cut as u64;
+            shrunk.max(self.min_cap).max(supply).min(cap)
+        } else {
+            cap
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_cap() {
+        let rules = ElasticSupplyCap {
+            growth_bps: 1_000,
+            shrink_bps: 2_000,
+            grow_above_ratio_bps: 20_000,
+            shrink_below_ratio_bps: 15_000,
+            min_cap: 500_000,
+            max_cap: 1_500_000,
+            ..Default::default()
+        };
+
+        // Healthy: grows, but never past the ceiling
+        assert_eq!(rules.next_cap(1_000_000, Some(25_000), 0, rules.max_cap), 1_100_000);
+        assert_eq!(rules.next_cap(1_400_000, Some(25_000), 0, rules.max_cap), 1_500_000);
+        assert_eq!(rules.next_cap(1_000_000, None, 0, rules.max_cap), 1_100_000);
+        // A ceiling below the cap freezes it rather than cutting it
+        assert_eq!(rules.next_cap(1_000_000, Some(25_000), 0, 800_000), 1_000_000);
+
+        // Inside the band nothing changes
+        assert_eq!(rules.next_cap(1_000_000, Some(17_000), 0, rules.max_cap), 1_000_000);
+
+        // Unhealthy: shrinks down to the floor or the outstanding supply
+        assert_eq!(rules.next_cap(1_000_000, Some(14_000), 0, rules.max_cap), 800_000);
+        assert_eq!(rules.next_cap(1_000_000, Some(14_000), 900_000, rules.max_cap), 900_000);
+        assert_eq!(rules.next_cap(550_000, Some(14_000), 0, rules.max_cap), 500_000);
+    }
+
+    #[test]
+    fn test_validate_params() {
+        assert!(ElasticSupplyCap::validate_params(500, 500, 20_000, 15_000, 0, 1).is_ok());
+        assert!(ElasticSupplyCap::validate_params(500, 500, 15_000, 20_000, 0, 1).is_err());
+        assert!(ElasticSupplyCap::validate_params(500, 10_000, 20_000, 15_000, 0, 1).is_err());
+        assert!(ElasticSupplyCap::validate_params(500, 500, 20_000, 15_000, 2, 1).is_err());
+    }
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::error::StableFunError;
+
+/// Token bucket pacing the mint and redeem operations of one stablecoin:
+/// each operation takes a token, and tokens come back at a fixed rate up to
+/// the bucket's capacity.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct ThroughputBucket {
+    pub tokens: u64,
+    /// When `tokens` was last topped up (0: never, the bucket starts full)
+    pub last_refill: i64,
+}
+
+impl ThroughputBucket {
+    pub const LEN: usize = 8 + 8;
+
+    pub fn consume(&mut self, capacity: u32, refill_per_second: u32, now: i64) -> Result<()> {
+        let capacity = capacity as u64;
+        self.tokens = if self.last_refill == 0 {
+            capacity
+        } else {
+            let elapsed = now.saturating_sub(self.last_refill).max(0) as u64;
+            self.tokens
+                .saturating_add(elapsed.saturating_mul(refill_per_second as u64))
+                .min(capacity)
+        };
+        self.last_refill = now;
+
+        require!(self.tokens > 0, StableFunError::ThroughputLimitExceeded);
+        self.tokens -= 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_drains_and_refills() {
+        let mut bucket = ThroughputBucket::default();
+        for _ in 0..3 {
+            bucket.consume(3, 1, 100).unwrap();
+        }
+        assert!(bucket.consume(3, 1, 100).is_err());
+
+        // Two seconds buy two more operations
+        bucket.consume(3, 1, 102).unwrap();
+        bucket.consume(3, 1, 102).unwrap();
+        assert!(bucket.consume(3, 1, 102).is_err());
+
+        // A long idle period refills only up to capacity
+        for _ in 0..3 {
+            bucket.consume(3, 1, 10_000).unwrap();
+        }
+        assert!(bucket.consume(3, 1, 10_000).is_err());
+    }
+}
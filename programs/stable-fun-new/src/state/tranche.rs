@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::constants::BASIS_POINTS_DIVISOR;
+use crate::error::StableFunError;
+
+/// First-loss capital sitting in the stablecoin vault beneath the senior
+/// stablecoin. Junior holders absorb bad debt before the senior side and
+/// take the part of harvested yield the senior side does not keep.
+#[account]
+#[derive(Debug, Default)]
+pub struct JuniorTranche {
+    pub stablecoin_mint: Pubkey,
+    /// SPL token representing junior claims
+    pub junior_mint: Pubkey,
+    /// Value (stablecoin units) of the junior claim on the vault
+    pub junior_capital: u64,
+    /// Share of harvested yield (bps) kept by the senior side as surplus
+    pub senior_yield_bps: u16,
+    pub total_subscribed: u64,
+    pub total_redeemed: u64,
+    pub losses_absorbed: u64,
+    pub yield_captured: u64,
+    pub bump: u8,
+}
+
+impl StateAccount for JuniorTranche {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        PUBKEY_LENGTH +    // junior_mint
+        8 +               // junior_capital
+        2 +               // senior_yield_bps
+        8 +               // total_subscribed
+        8 +               // total_redeemed
+        8 +               // losses_absorbed
+        8 +               // yield_captured
+        1;               // bump
+}
+
+impl JuniorTranche {
+    /// Covers `loss` out of junior capital and returns what is left for the
+    /// senior side to carry as bad debt.
+    pub fn absorb_loss(&mut self, loss: u64) -> Result<u64> {
+        let absorbed = loss.min(self.junior_capital);
+        self.junior_capital -= absorbed;
+        self.losses_absorbed = self.losses_absorbed
+            .checked_add(absorbed)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        Ok(loss - absorbed)
+    }
+
+    /// Splits harvested yield and returns the senior share; the rest is
+    /// added to junior capital.
+    pub fn capture_yield(&mut self, amount: u64) -> Result<u64> {
+        let senior = (amount as u128 * self.senior_yield_bps as u128
+            / BASIS_POINTS_DIVISOR as u128) as u64;
+        let junior = amount - senior;
+        self.junior_capital = self.junior_capital
+            .checked_add(junior)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        self.yield_captured = self.yield_captured
+            .checked_add(junior)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        Ok(senior)
+    }
+
+    pub fn record_subscription(&mut self, value: u64) -> Result<()> {
+        self.junior_capital = self.junior_capital
+            .checked_add(value)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        self.total_subscribed = self.total_subscribed
+            .checked_add(value)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        Ok(())
+    }
+
+    pub fn record_redemption(&mut self, value: u64) -> Result<()> {
+        self.junior_capital = self.junior_capital
+            .checked_sub(value)
+            .ok_or(error!(StableFunError::InsufficientBalance))?;
+        self.total_redeemed = self.total_redeemed
+            .checked_add(value)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_waterfall() {
+        let mut tranche = JuniorTranche {
+            senior_yield_bps: 4000,
+            ..Default::default()
+        };
+        tranche.record_subscription(1_000).unwrap();
+
+        // 40% of the yield stays senior, the rest accrues to junior capital
+        assert_eq!(tranche.capture_yield(500).unwrap(), 200);
+        assert_eq!(tranche.junior_capital, 1_300);
+
+        // Junior capital takes losses first, the senior side only the excess
+        assert_eq!(tranche.absorb_loss(300).unwrap(), 0);
+        assert_eq!(tranche.absorb_loss(1_500).unwrap(), 500);
+        assert_eq!(tranche.junior_capital, 0);
+        assert_eq!(tranche.losses_absorbed, 1_300);
+    }
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::constants::SECONDS_PER_DAY;
+use crate::error::StableFunError;
+
+/// Issuer-funded stablecoin sub-account an operator may spend from, at most
+/// `daily_limit` per day.
+#[account]
+#[derive(Debug, Default)]
+pub struct Treasury {
+    pub stablecoin_mint: Pubkey,
+    pub treasury_id: u64,
+    pub operator: Pubkey,
+    /// Token account owned by this treasury
+    pub token_account: Pubkey,
+    pub daily_limit: u64,
+    /// Spent since `window_start`
+    pub spent_in_window: u64,
+    /// Start of the current daily window
+    pub window_start: i64,
+    pub total_spent: u64,
+    pub bump: u8,
+}
+
+impl StateAccount for Treasury {
+    const LEN: usize = DISCRIMINATOR_LENGTH +
+        PUBKEY_LENGTH +    // stablecoin_mint
+        8 +               // treasury_id
+        PUBKEY_LENGTH +    // operator
+        PUBKEY_LENGTH +    // token_account
+        8 +               // daily_limit
+        8 +               // spent_in_window
+        8 +               // window_start
+        8 +               // total_spent
+        1;               // bump
+}
+
+impl Treasury {
+    /// What the operator may still spend at `now`.
+    pub fn remaining_limit(&self, now: i64) -> u64 {
+        if now.saturating_sub(self.window_start) >= SECONDS_PER_DAY {
+            self.daily_limit
+        } else {
+            self.daily_limit.saturating_sub(self.spent_in_window)
+        }
+    }
+
+    /// Books a spend against the daily limit, opening a new window once a day
+    /// has passed since the current one started.
+    pub fn record_spend(&mut self, amount: u64, now: i64) -> Result<()> {
+        require!(amount <= self.remaining_limit(now), StableFunError::SpendingLimitExceeded);
+        if now.saturating_sub(self.window_start) >= SECONDS_PER_DAY {
+            self.window_start = now;
+            self.spent_in_window = 0;
+        }
+        self.spent_in_window = self.spent_in_window
+            .checked_add(amount)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        self.total_spent = self.total_spent
+            .checked_add(amount)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_daily_limit() {
+        let mut treasury = Treasury {
+            daily_limit: 1_000,
+            ..Default::default()
+        };
+        treasury.record_spend(600, SECONDS_PER_DAY).unwrap();
+        treasury.record_spend(400, SECONDS_PER_DAY + 10).unwrap();
+        assert!(treasury.record_spend(1, SECONDS_PER_DAY + 20).is_err());
+
+        // A day after the window opened the full limit is back
+        assert_eq!(treasury.remaining_limit(2 * SECONDS_PER_DAY), 1_000);
+        treasury.record_spend(1_000, 2 * SECONDS_PER_DAY).unwrap();
+        assert_eq!(treasury.total_spent, 2_000);
+    }
+}
@@ -1,6 +1,21 @@
 use anchor_lang::prelude::*;
 use super::{StateAccount, DISCRIMINATOR_LENGTH, PUBKEY_LENGTH};
+use crate::constants::{BASIS_POINTS_DIVISOR, VAULT_SEED};
 use crate::error::StableFunError;
+use crate::utils::math;
+
+/// Oracle price stored by `refresh_price`, stamped with the slot it was read in.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CachedPrice {
+    pub price: u64,
+    pub slot: u64,
+}
+
+impl CachedPrice {
+    pub const LEN: usize =
+        8 + // price
+        8; // slot
+}
 
 #[account]
 #[derive(Debug)]
@@ -16,6 +31,19 @@ pub struct StablecoinVault {
     pub deposit_count: u32,
     pub withdrawal_count: u32,
     pub bump: u8,
+    /// Validated oracle price small operations may reuse instead of the feed
+    pub cached_price: CachedPrice,
+    /// Price `current_ratio` was last computed at
+    pub ratio_price: u64,
+    /// Collateral the backstop paid out on the vault's behalf. It is off
+    /// `total_collateral` but still in the token account until repaid.
+    pub backstop_owed: u64,
+    /// Ticket handed to the next redeem commitment
+    pub redeem_queue_tail: u64,
+    /// Ticket of the oldest commitment still queued; only it may be revealed
+    pub redeem_queue_head: u64,
+    /// Slot the head ticket reached the front of the queue
+    pub redeem_queue_head_since: u64,
 }
 
 impl StateAccount for StablecoinVault {
@@ -30,7 +58,13 @@ impl StateAccount for StablecoinVault {
         8 +               // last_withdrawal_time
         4 +               // deposit_count
         4 +               // withdrawal_count
-        1;               // bump
+        1 +               // bump
+        CachedPrice::LEN + // cached_price
+        8 +               // ratio_price
+        8 +               // backstop_owed
+        8 +               // redeem_queue_tail
+        8 +               // redeem_queue_head
+        8;               // redeem_queue_head_since
 }
 
 impl StablecoinVault {
@@ -52,6 +86,12 @@ impl StablecoinVault {
             deposit_count: 0,
             withdrawal_count: 0,
             bump,
+            cached_price: CachedPrice::default(),
+            ratio_price: 0,
+            backstop_owed: 0,
+            redeem_queue_tail: 0,
+            redeem_queue_head: 0,
+            redeem_queue_head_since: 0,
         }
     }
 
@@ -59,6 +99,7 @@ impl StablecoinVault {
         &mut self,
         amount: u64,
         value: u64,
+        decimals: u8,
         clock: &Sysvar<Clock>,
     ) -> Result<()> {
         self.total_collateral = self.total_collateral
@@ -74,7 +115,7 @@ impl StablecoinVault {
             .checked_add(1)
             .ok_or(error!(StableFunError::MathOverflow))?;
 
-        self.update_collateral_ratio()?;
+        self.update_collateral_ratio(self.ratio_price, decimals)?;
         Ok(())
     }
 
@@ -82,6 +123,7 @@ impl StablecoinVault {
         &mut self,
         amount: u64,
         value: u64,
+        decimals: u8,
         clock: &Sysvar<Clock>,
     ) -> Result<()> {
         require!(
@@ -102,52 +144,105 @@ impl StablecoinVault {
             .checked_add(1)
             .ok_or(error!(StableFunError::MathOverflow))?;
 
-        self.update_collateral_ratio()?;
+        self.update_collateral_ratio(self.ratio_price, decimals)?;
         Ok(())
     }
 
-    pub fn update_collateral_ratio(&mut self) -> Result<()> {
-        if self.total_value_locked == 0 || self.total_collateral == 0 {
-            self.current_ratio = 0;
-            return Ok(());
+    /// The canonical collateral ratio of `collateral` at `price`, against
+    /// `total_value_locked` as the debt (0 with no debt or no price).
+    pub fn collateral_ratio(&self, collateral: u64, price: u64, decimals: u8) -> Result<u64> {
+        if price == 0 {
+            return Ok(0);
         }
+        let value = math::calculate_collateral_value(collateral, price, decimals)?;
+        Ok(math::collateral_ratio_bps(value, self.total_value_locked).unwrap_or(0))
+    }
 
-        let ratio = (self.total_value_locked as u128)
-            .checked_mul(10000)
-            .ok_or(error!(StableFunError::MathOverflow))?
-            .checked_div(self.total_collateral as u128)
-            .ok_or(error!(StableFunError::MathOverflow))?;
-
-        self.current_ratio = u16::try_from(ratio)
-            .map_err(|_| error!(StableFunError::MathOverflow))?;
-
+    /// Stores the ratio at `price`, saturating at `u16::MAX` bps.
+    pub fn update_collateral_ratio(&mut self, price: u64, decimals: u8) -> Result<()> {
+        let ratio = self.collateral_ratio(self.total_collateral, price, decimals)?;
+        self.current_ratio = u16::try_from(ratio).unwrap_or(u16::MAX);
+        self.ratio_price = price;
         Ok(())
     }
 
-    pub fn can_withdraw(&self, amount: u64, min_ratio: u16) -> bool {
-        if amount >= self.total_collateral {
+    /// Whether `amount` collateral can leave while keeping `min_ratio` at
+    /// the last ratio price.
+    pub fn can_withdraw(&self, amount: u64, min_ratio: u16, decimals: u8) -> bool {
+        let Some(new_collateral) = self.total_collateral.checked_sub(amount) else {
             return false;
+        };
+        if self.total_value_locked == 0 {
+            return true;
         }
+        self.collateral_ratio(new_collateral, self.ratio_price, decimals)
+            .is_ok_and(|ratio| ratio >= min_ratio as u64)
+    }
 
-        let new_collateral = match self.total_collateral.checked_sub(amount) {
-            Some(val) if val > 0 => val,
-            _ => return false,
-        };
+    /// Collateral sitting in the vault token account that was never recorded
+    /// (direct transfers, airdrops, mistakes). Collateral owed to the
+    /// backstop is not the vault's to book.
+    pub fn untracked_collateral(&self, token_balance: u64) -> u64 {
+        token_balance
+            .saturating_sub(self.backstop_owed)
+            .saturating_sub(self.total_collateral)
+    }
 
-        let new_ratio = match (self.total_value_locked as u128)
-            .checked_mul(10000)
-            .and_then(|v| v.checked_div(new_collateral as u128))
-            .and_then(|r| u16::try_from(r).ok())
-        {
-            Some(ratio) => ratio,
-            None => return false,
-        };
+    /// Whether paying out `collateral_amount` from a vault token balance of
+    /// `liquid_balance` leaves less than `buffer_bps` of the booked collateral.
+    pub fn breaches_liquidity_buffer(&self, liquid_balance: u64, collateral_amount: u64, buffer_bps: u16) -> bool {
+        buffer_bps != 0
+            && (liquid_balance.saturating_sub(collateral_amount) as u128) * (BASIS_POINTS_DIVISOR as u128)
+                < (self.total_collateral as u128) * (buffer_bps as u128)
+    }
+
+    /// How much of `backstop_owed` a vault token balance of `liquid_balance`
+    /// can repay while keeping `buffer_bps` of the booked collateral.
+    pub fn backstop_repayable(&self, liquid_balance: u64, buffer_bps: u16) -> u64 {
+        let buffer = (self.total_collateral as u128 * buffer_bps as u128)
+            .div_ceil(BASIS_POINTS_DIVISOR as u128);
+        let headroom = (liquid_balance as u128).saturating_sub(buffer) as u64;
+        headroom.min(self.backstop_owed)
+    }
+
+    /// Hands out the ticket placing a new redeem commitment at the back of
+    /// the queue.
+    pub fn enqueue_redeem(&mut self) -> Result<u64> {
+        let ticket = self.redeem_queue_tail;
+        self.redeem_queue_tail = ticket
+            .checked_add(1)
+            .ok_or(error!(StableFunError::MathOverflow))?;
+        Ok(ticket)
+    }
+
+    /// Removes the head ticket, `ticket`, from the queue at `slot`, putting
+    /// the next one at the front.
+    pub fn dequeue_redeem(&mut self, ticket: u64, slot: u64) -> Result<()> {
+        require!(ticket == self.redeem_queue_head, StableFunError::RedeemNotAtQueueHead);
+        self.redeem_queue_head += 1;
+        self.redeem_queue_head_since = slot;
+        Ok(())
+    }
 
-        new_ratio >= min_ratio
+    /// The cached price, when an operation of `amount` is small enough to use
+    /// it and it was refreshed within `max_age_slots` of `slot`.
+    pub fn usable_cached_price(&self, amount: u64, max_amount: u64, max_age_slots: u64, slot: u64) -> Option<u64> {
+        let cached = self.cached_price;
+        let usable = max_amount > 0
+            && amount <= max_amount
+            && cached.price > 0
+            && slot.saturating_sub(cached.slot) <= max_age_slots;
+        usable.then_some(cached.price)
     }
 
-    pub fn get_vault_seeds<'a>(vault_bump: &'a u8) -> [&'a [u8]; 2] {
-        [b"vault", std::slice::from_ref(vault_bump)]
+    /// Seeds the vault signs its transfers with: the stablecoin mint it
+    /// belongs to and its stored bump.
+    pub fn signer_seeds(&self) -> [&[u8]; 3] {
+        [
+            VAULT_SEED,
+            self.stablecoin_mint.as_ref(),
+            std::slice::from_ref(&self.bump),
+        ]
     }
 }
 
@@ -178,11 +273,21 @@ mod tests {
             255,
         );
 
-        vault.total_collateral = 1000;
-        vault.total_value_locked = 1500;
+        vault.total_collateral = 1500;
+        vault.total_value_locked = 1000;
 
-        assert!(vault.update_collateral_ratio().is_ok());
-        assert_eq!(vault.current_ratio, 15000); // 150% = 15000 basis points
+        // 1500 collateral at 1.0 backs 1000 coins: 150%
+        assert!(vault.update_collateral_ratio(1_000_000, 6).is_ok());
+        assert_eq!(vault.current_ratio, 15000);
+        assert_eq!(vault.ratio_price, 1_000_000);
+
+        // At 1.25 collateral per coin the same collateral is worth 1200
+        vault.update_collateral_ratio(1_250_000, 6).unwrap();
+        assert_eq!(vault.current_ratio, 12000);
+
+        vault.total_value_locked = 0;
+        vault.update_collateral_ratio(1_000_000, 6).unwrap();
+        assert_eq!(vault.current_ratio, 0);
     }
 
     #[test]
@@ -194,11 +299,109 @@ mod tests {
             255,
         );
 
+        vault.total_collateral = 1500;
+        vault.total_value_locked = 1000;
+        vault.update_collateral_ratio(1_000_000, 6).unwrap();
+
+        assert!(vault.can_withdraw(100, 14000, 6));  // Should allow withdrawal maintaining 140% ratio
+        assert!(!vault.can_withdraw(900, 14000, 6)); // Should prevent withdrawal below 140% ratio
+        assert!(!vault.can_withdraw(1600, 14000, 6));
+    }
+
+    #[test]
+    fn test_untracked_collateral() {
+        let mut vault = StablecoinVault::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            255,
+        );
+
         vault.total_collateral = 1000;
-        vault.total_value_locked = 1500;
-        vault.update_collateral_ratio().unwrap();
+        assert_eq!(vault.untracked_collateral(1250), 250);
+        assert_eq!(vault.untracked_collateral(900), 0);
 
-        assert!(vault.can_withdraw(100, 14000));  // Should allow withdrawal maintaining 140% ratio
-        assert!(!vault.can_withdraw(900, 14000)); // Should prevent withdrawal below 140% ratio
+        vault.backstop_owed = 200;
+        assert_eq!(vault.untracked_collateral(1250), 50);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_liquidity_buffer() {
+        let mut vault = StablecoinVault::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            255,
+        );
+
+        vault.total_collateral = 1000;
+        assert!(!vault.breaches_liquidity_buffer(1000, 1000, 0)); // disabled
+        assert!(!vault.breaches_liquidity_buffer(1000, 800, 2000));
+        assert!(vault.breaches_liquidity_buffer(1000, 801, 2000));
+        // Collateral missing from the vault counts against the buffer
+        assert!(vault.breaches_liquidity_buffer(900, 701, 2000));
+
+        // Repaying the backstop never takes the vault below its buffer
+        vault.backstop_owed = 300;
+        assert_eq!(vault.backstop_repayable(1000, 2000), 300);
+        assert_eq!(vault.backstop_repayable(350, 2000), 150);
+        assert_eq!(vault.backstop_repayable(150, 2000), 0);
+        assert_eq!(vault.backstop_repayable(150, 0), 150);
+    }
+
+    #[test]
+    fn test_redeem_queue_is_fifo() {
+        let mut vault = StablecoinVault::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            255,
+        );
+
+        let first = vault.enqueue_redeem().unwrap();
+        let second = vault.enqueue_redeem().unwrap();
+        assert_eq!((first, second), (0, 1));
+
+        // The later ticket waits for the one ahead of it
+        assert!(vault.dequeue_redeem(second, 10).is_err());
+        vault.dequeue_redeem(first, 10).unwrap();
+        assert_eq!(vault.redeem_queue_head_since, 10);
+        assert!(vault.dequeue_redeem(first, 11).is_err());
+        vault.dequeue_redeem(second, 12).unwrap();
+        assert_eq!(vault.redeem_queue_head, vault.redeem_queue_tail);
+    }
+
+    #[test]
+    fn test_signer_seeds_derive_vault_pda() {
+        let stablecoin_mint = Pubkey::new_unique();
+        let (vault_key, bump) = crate::utils::pda::find_vault_address(&crate::ID, &stablecoin_mint);
+        let vault = StablecoinVault::new(
+            stablecoin_mint,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            bump,
+        );
+
+        assert_eq!(
+            Pubkey::create_program_address(&vault.signer_seeds(), &crate::ID).unwrap(),
+            vault_key
+        );
+    }
+
+    #[test]
+    fn test_usable_cached_price() {
+        let mut vault = StablecoinVault::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            255,
+        );
+        assert_eq!(vault.usable_cached_price(100, 1_000, 10, 50), None);
+
+        vault.cached_price = CachedPrice { price: 1_000_000, slot: 40 };
+        assert_eq!(vault.usable_cached_price(100, 1_000, 10, 50), Some(1_000_000));
+        assert_eq!(vault.usable_cached_price(100, 1_000, 10, 51), None);
+        assert_eq!(vault.usable_cached_price(1_001, 1_000, 10, 50), None);
+        assert_eq!(vault.usable_cached_price(100, 0, 10, 50), None);
+    }
+}
@@ -0,0 +1,178 @@
+use anchor_lang::prelude::*;
+use crate::error::StableFunError;
+use crate::state::{ProtocolConfig, StablecoinMint};
+
+/// Account type tag of an attestation in the attestation registry
+/// (Solana Attestation Service layout).
+pub const ATTESTATION_DISCRIMINATOR: u8 = 2;
+
+/// The fields of a registry attestation the program relies on.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Attestation {
+    /// Subject of the attestation: the attested wallet
+    pub nonce: Pubkey,
+    /// Credential (issuing authority) the attestation was made under
+    pub credential: Pubkey,
+    pub schema: Pubkey,
+    pub signer: Pubkey,
+    /// Unix timestamp after which the attestation is void (0 = never)
+    pub expiry: i64,
+    /// Jurisdiction claims (one bit per region), the first four bytes of the
+    /// attestation data under the holder schema; `None` if the data is shorter
+    pub jurisdictions: Option<u32>,
+}
+
+impl Attestation {
+    /// Decodes `discriminator | nonce | credential | schema | data (u32 len +
+    /// bytes) | signer | expiry | token_account`, ignoring the trailing fields.
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self> {
+        let mut reader = data;
+        let discriminator = u8::deserialize(&mut reader)?;
+        require!(
+            discriminator == ATTESTATION_DISCRIMINATOR,
+            StableFunError::InvalidAttestation
+        );
+        let nonce = Pubkey::deserialize(&mut reader)?;
+        let credential = Pubkey::deserialize(&mut reader)?;
+        let schema = Pubkey::deserialize(&mut reader)?;
+        let data = Vec::<u8>::deserialize(&mut reader)?;
+        let signer = Pubkey::deserialize(&mut reader)?;
+        let expiry = i64::deserialize(&mut reader)?;
+        Ok(Self {
+            nonce,
+            credential,
+            schema,
+            signer,
+            expiry,
+            jurisdictions: data
+                .get(..4)
+                .map(|claims| u32::from_le_bytes(claims.try_into().unwrap())),
+        })
+    }
+
+    pub fn is_valid_for(&self, subject: &Pubkey, credential: &Pubkey, schema: &Pubkey, now: i64) -> bool {
+        self.nonce == *subject
+            && self.credential == *credential
+            && self.schema == *schema
+            && (self.expiry == 0 || now < self.expiry)
+    }
+}
+
+/// Requires `account` to be a live attestation of `subject` under
+/// `credential`/`schema`, owned by the configured registry program.
+/// Returns the decoded attestation.
+pub fn verify_attestation(
+    account: &AccountInfo,
+    registry_program: &Pubkey,
+    credential: &Pubkey,
+    schema: &Pubkey,
+    subject: &Pubkey,
+    now: i64,
+) -> Result<Attestation> {
+    require_keys_eq!(*account.owner, *registry_program, StableFunError::InvalidAttestation);
+    let attestation = Attestation::try_from_bytes(&account.try_borrow_data()?)?;
+    require!(
+        attestation.expiry == 0 || now < attestation.expiry,
+        StableFunError::AttestationExpired
+    );
+    require!(
+        attestation.is_valid_for(subject, credential, schema, now),
+        StableFunError::InvalidAttestation
+    );
+    Ok(attestation)
+}
+
+/// Holder gate for mint/redeem: regulated coins must be given the protocol
+/// config, and once the coin is permissioned, the holder's attestation,
+/// whose jurisdiction claims must avoid every excluded jurisdiction.
+pub fn check_holder_attestation(
+    stablecoin_mint: &StablecoinMint,
+    config: &ProtocolConfig,
+    attestation: Option<&AccountInfo>,
+    holder: &Pubkey,
+    now: i64,
+) -> Result<()> {
+    if !stablecoin_mint.regulated {
+        return Ok(());
+    }
+    if !stablecoin_mint.is_permissioned(config) {
+        return Ok(());
+    }
+    let attestation = attestation.ok_or(StableFunError::MissingAttestation)?;
+    let attestation = verify_attestation(
+        attestation,
+        &config.attestation_program,
+        &config.attestation_credential,
+        &config.holder_schema,
+        holder,
+        now,
+    )?;
+    require!(
+        jurisdiction_allowed(stablecoin_mint.jurisdiction_flags, attestation.jurisdictions),
+        StableFunError::JurisdictionExcluded
+    );
+    Ok(())
+}
+
+/// Holders without jurisdiction claims are only accepted while no
+/// jurisdiction is excluded.
+pub fn jurisdiction_allowed(excluded: u32, claims: Option<u32>) -> bool {
+    match claims {
+        Some(claims) => claims & excluded == 0,
+        None => excluded == 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(attestation: &Attestation) -> Vec<u8> {
+        let mut data = vec![ATTESTATION_DISCRIMINATOR];
+        data.extend_from_slice(attestation.nonce.as_ref());
+        data.extend_from_slice(attestation.credential.as_ref());
+        data.extend_from_slice(attestation.schema.as_ref());
+        let claims = attestation.jurisdictions.map_or(Vec::new(), |claims| claims.to_le_bytes().to_vec());
+        data.extend_from_slice(&(claims.len() as u32).to_le_bytes());
+        data.extend_from_slice(&claims);
+        data.extend_from_slice(attestation.signer.as_ref());
+        data.extend_from_slice(&attestation.expiry.to_le_bytes());
+        data.extend_from_slice(Pubkey::default().as_ref());
+        data
+    }
+
+    #[test]
+    fn test_parse_attestation() {
+        let attestation = Attestation {
+            nonce: Pubkey::new_unique(),
+            credential: Pubkey::new_unique(),
+            schema: Pubkey::new_unique(),
+            signer: Pubkey::new_unique(),
+            expiry: 1_000,
+            jurisdictions: Some(0b101),
+        };
+        let parsed = Attestation::try_from_bytes(&encode(&attestation)).unwrap();
+        assert_eq!(parsed, attestation);
+
+        let (subject, credential, schema) = (attestation.nonce, attestation.credential, attestation.schema);
+        assert!(parsed.is_valid_for(&subject, &credential, &schema, 999));
+        assert!(!parsed.is_valid_for(&subject, &credential, &schema, 1_000));
+        assert!(!parsed.is_valid_for(&Pubkey::new_unique(), &credential, &schema, 999));
+        assert!(!parsed.is_valid_for(&subject, &credential, &Pubkey::new_unique(), 999));
+
+        let mut data = encode(&attestation);
+        data[0] = 1;
+        assert!(Attestation::try_from_bytes(&data).is_err());
+
+        let without_claims = Attestation { jurisdictions: None, ..attestation };
+        assert_eq!(Attestation::try_from_bytes(&encode(&without_claims)).unwrap(), without_claims);
+    }
+
+    #[test]
+    fn test_jurisdiction_allowed() {
+        assert!(jurisdiction_allowed(0, None));
+        assert!(jurisdiction_allowed(0b010, Some(0b101)));
+        assert!(!jurisdiction_allowed(0b100, Some(0b101)));
+        assert!(!jurisdiction_allowed(0b010, None));
+    }
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use crate::error::StableFunError;
+
+pub const CURRENCY_CODE_LENGTH: usize = 3;
+
+/// Active ISO-4217 alphabetic codes, sorted for binary search. The testing
+/// (`XTS`) and "no currency" (`XXX`) codes are deliberately left out.
+pub const ISO_4217_CODES: &[&str] = &[
+    "AED", "AFN", "ALL", "AMD", "ANG", "AOA", "ARS", "AUD", "AWG", "AZN",
+    "BAM", "BBD", "BDT", "BGN", "BHD", "BIF", "BMD", "BND", "BOB", "BOV",
+    "BRL", "BSD", "BTN", "BWP", "BYN", "BZD", "CAD", "CDF", "CHE", "CHF",
+    "CHW", "CLF", "CLP", "CNY", "COP", "COU", "CRC", "CUP", "CVE", "CZK",
+    "DJF", "DKK", "DOP", "DZD", "EGP", "ERN", "ETB", "EUR", "FJD", "FKP",
+    "GBP", "GEL", "GHS", "GIP", "GMD", "GNF", "GTQ", "GYD", "HKD", "HNL",
+    "HTG", "HUF", "IDR", "ILS", "INR", "IQD", "IRR", "ISK", "JMD", "JOD",
+    "JPY", "KES", "KGS", "KHR", "KMF", "KPW", "KRW", "KWD", "KYD", "KZT",
+    "LAK", "LBP", "LKR", "LRD", "LSL", "LYD", "MAD", "MDL", "MGA", "MKD",
+    "MMK", "MNT", "MOP", "MRU", "MUR", "MVR", "MWK", "MXN", "MXV", "MYR",
+    "MZN", "NAD", "NGN", "NIO", "NOK", "NPR", "NZD", "OMR", "PAB", "PEN",
+    "PGK", "PHP", "PKR", "PLN", "PYG", "QAR", "RON", "RSD", "RUB", "RWF",
+    "SAR", "SBD", "SCR", "SDG", "SEK", "SGD", "SHP", "SLE", "SLL", "SOS",
+    "SRD", "SSP", "STN", "SVC", "SYP", "SZL", "THB", "TJS", "TMT", "TND",
+    "TOP", "TRY", "TTD", "TWD", "TZS", "UAH", "UGX", "USD", "USN", "UYI",
+    "UYU", "UYW", "UZS", "VED", "VES", "VND", "VUV", "WST", "XAF", "XAG",
+    "XAU", "XBA", "XBB", "XBC", "XBD", "XCD", "XCG", "XDR", "XOF", "XPD",
+    "XPF", "XPT", "XSU", "XUA", "YER", "ZAR", "ZMW", "ZWG", "ZWL",
+];
+
+/// Returns the canonical (uppercase) form of `currency` if it is a known
+/// ISO-4217 code, so "usd" and "USD" resolve to the same currency.
+pub fn normalize_currency(currency: &str) -> Result<String> {
+    require!(
+        currency.len() == CURRENCY_CODE_LENGTH
+            && currency.chars().all(|c| c.is_ascii_alphabetic()),
+        StableFunError::InvalidCurrency
+    );
+
+    let canonical = currency.to_ascii_uppercase();
+    require!(
+        is_iso_4217(&canonical),
+        StableFunError::InvalidCurrency
+    );
+
+    Ok(canonical)
+}
+
+#[inline(always)]
+pub fn is_iso_4217(code: &str) -> bool {
+    ISO_4217_CODES.binary_search(&code).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_is_sorted() {
+        assert!(ISO_4217_CODES.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_normalize_currency() {
+        assert_eq!(normalize_currency("USD").unwrap(), "USD");
+        assert_eq!(normalize_currency("usd").unwrap(), "USD");
+        assert_eq!(normalize_currency("mXn").unwrap(), "MXN");
+        assert!(normalize_currency("US Dollar").is_err());
+        assert!(normalize_currency("XYZ").is_err());
+        assert!(normalize_currency("XTS").is_err());
+        assert!(normalize_currency("").is_err());
+    }
+}
@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked,
+    load_instruction_at_checked,
+};
+
+use crate::error::StableFunError;
+
+/// Domain prefix for relayed orders, so a user signature can never be
+/// replayed as some other message.
+pub const RELAYED_ORDER_DOMAIN: &[u8] = b"stable-fun:relayed-order";
+
+pub const RELAYED_MINT: u8 = 0;
+pub const RELAYED_REDEEM: u8 = 1;
+
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_LEN: usize = 14;
+const PUBKEY_LEN: usize = 32;
+/// Instruction index the precompile reads as "this instruction"
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Bytes a user signs to let a relayer mint or redeem on their behalf.
+pub fn relayed_order_message(
+    stablecoin_mint: &Pubkey,
+    user: &Pubkey,
+    action: u8,
+    amount: u64,
+    max_fee: u64,
+    nonce: u64,
+    expiry: i64,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(RELAYED_ORDER_DOMAIN.len() + 97);
+    message.extend_from_slice(RELAYED_ORDER_DOMAIN);
+    message.extend_from_slice(stablecoin_mint.as_ref());
+    message.extend_from_slice(user.as_ref());
+    message.push(action);
+    message.extend_from_slice(&amount.to_le_bytes());
+    message.extend_from_slice(&max_fee.to_le_bytes());
+    message.extend_from_slice(&nonce.to_le_bytes());
+    message.extend_from_slice(&expiry.to_le_bytes());
+    message
+}
+
+/// Extracts the public key and message checked by an ed25519 precompile
+/// instruction at `own_index`. Only single-signature instructions whose
+/// offsets all point into their own data are accepted.
+pub fn parse_ed25519_instruction(data: &[u8], own_index: u16) -> Option<(Pubkey, &[u8])> {
+    if data.first() != Some(&1) || data.len() < SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN {
+        return None;
+    }
+
+    let offsets = &data[SIGNATURE_OFFSETS_START..SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN];
+    let read_u16 = |at: usize| u16::from_le_bytes([offsets[at], offsets[at + 1]]);
+    let signature_ix = read_u16(2);
+    let pubkey_offset = read_u16(4) as usize;
+    let pubkey_ix = read_u16(6);
+    let message_offset = read_u16(8) as usize;
+    let message_size = read_u16(10) as usize;
+    let message_ix = read_u16(12);
+
+    let is_own = |index: u16| index == own_index || index == CURRENT_INSTRUCTION;
+    if !is_own(signature_ix) || !is_own(pubkey_ix) || !is_own(message_ix) {
+        return None;
+    }
+
+    let pubkey = data.get(pubkey_offset..pubkey_offset + PUBKEY_LEN)?;
+    let message = data.get(message_offset..message_offset + message_size)?;
+    Some((Pubkey::try_from(pubkey).ok()?, message))
+}
+
+/// Requires the instruction right before the current one to be an ed25519
+/// precompile check of `message` signed by `signer`. The runtime rejects the
+/// transaction if that signature is invalid.
+pub fn verify_prior_ed25519_signature(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, StableFunError::InvalidRelayedOrderSignature);
+
+    let ed25519_index = current_index - 1;
+    let ix = load_instruction_at_checked(ed25519_index as usize, instructions_sysvar)?;
+    require!(
+        ix.program_id == ed25519_program::ID,
+        StableFunError::InvalidRelayedOrderSignature
+    );
+
+    let (signed_by, signed_message) = parse_ed25519_instruction(&ix.data, ed25519_index)
+        .ok_or(error!(StableFunError::InvalidRelayedOrderSignature))?;
+    require!(
+        signed_by == *signer && signed_message == message,
+        StableFunError::InvalidRelayedOrderSignature
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ed25519_data(own_index: u16, signer: &Pubkey, message: &[u8]) -> Vec<u8> {
+        let pubkey_offset = (SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN) as u16;
+        let signature_offset = pubkey_offset + PUBKEY_LEN as u16;
+        let message_offset = signature_offset + 64;
+
+        let mut data = vec![1u8, 0];
+        for value in [
+            signature_offset,
+            own_index,
+            pubkey_offset,
+            own_index,
+            message_offset,
+            message.len() as u16,
+            own_index,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(signer.as_ref());
+        data.extend_from_slice(&[0u8; 64]);
+        data.extend_from_slice(message);
+        data
+    }
+
+    #[test]
+    fn test_parse_ed25519_instruction() {
+        let signer = Pubkey::new_unique();
+        let message = relayed_order_message(&Pubkey::new_unique(), &signer, RELAYED_REDEEM, 1_000, 10, 1, 100);
+        let data = ed25519_data(2, &signer, &message);
+
+        let (signed_by, signed) = parse_ed25519_instruction(&data, 2).unwrap();
+        assert_eq!(signed_by, signer);
+        assert_eq!(signed, &message[..]);
+
+        // "Current instruction" offsets are as good as the explicit index
+        let current = ed25519_data(CURRENT_INSTRUCTION, &signer, &message);
+        assert!(parse_ed25519_instruction(&current, 2).is_some());
+
+        // Offsets pointing at another instruction are rejected
+        assert!(parse_ed25519_instruction(&data, 1).is_none());
+
+        let mut two_signatures = data.clone();
+        two_signatures[0] = 2;
+        assert!(parse_ed25519_instruction(&two_signatures, 2).is_none());
+        assert!(parse_ed25519_instruction(&data[..data.len() - 1], 2).is_none());
+    }
+}
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT};
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked,
+    load_instruction_at_checked,
+};
+
+/// Position of the `stablecoin_mint` account in the mint and redeem instructions.
+pub const STABLECOIN_ACCOUNT_INDEX: usize = 1;
+
+/// Whether `data`/`accounts` describe a call into this program with the given
+/// instruction discriminator acting on `stablecoin_mint`.
+#[inline(always)]
+pub fn is_matching_instruction(
+    program_id: &Pubkey,
+    data: &[u8],
+    stablecoin_account: Option<&Pubkey>,
+    discriminator: &[u8; 8],
+    stablecoin_mint: &Pubkey,
+) -> bool {
+    *program_id == crate::ID
+        && data.len() >= discriminator.len()
+        && data[..discriminator.len()] == discriminator[..]
+        && stablecoin_account == Some(stablecoin_mint)
+}
+
+/// Whether the current instruction came straight from the transaction
+/// rather than through a CPI from another program.
+#[inline(always)]
+pub fn is_top_level() -> bool {
+    get_stack_height() == TRANSACTION_LEVEL_STACK_HEIGHT
+}
+
+/// The program that invoked the current instruction: `None` at the top
+/// level, the top-level instruction's program for a direct CPI, and the
+/// default pubkey when nested deeper, where the caller cannot be identified.
+pub fn cpi_caller(instructions_sysvar: &AccountInfo) -> Result<Option<Pubkey>> {
+    let stack_height = get_stack_height();
+    if stack_height == TRANSACTION_LEVEL_STACK_HEIGHT {
+        return Ok(None);
+    }
+    if stack_height > TRANSACTION_LEVEL_STACK_HEIGHT + 1 {
+        return Ok(Some(Pubkey::default()));
+    }
+    let current_index = load_current_index_checked(instructions_sysvar)? as usize;
+    let ix = load_instruction_at_checked(current_index, instructions_sysvar)?;
+    Ok(Some(ix.program_id))
+}
+
+/// Scans the top-level instructions that ran before the current one for a
+/// call of `discriminator` on the same stablecoin.
+pub fn has_prior_instruction(
+    instructions_sysvar: &AccountInfo,
+    discriminator: &[u8; 8],
+    stablecoin_mint: &Pubkey,
+) -> Result<bool> {
+    let current_index = load_current_index_checked(instructions_sysvar)? as usize;
+
+    for index in 0..current_index {
+        let ix = load_instruction_at_checked(index, instructions_sysvar)?;
+        if is_matching_instruction(
+            &ix.program_id,
+            &ix.data,
+            ix.accounts.get(STABLECOIN_ACCOUNT_INDEX).map(|meta| &meta.pubkey),
+            discriminator,
+            stablecoin_mint,
+        ) {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_instruction_matching() {
+        let stablecoin = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let discriminator = [1u8, 2, 3, 4, 5, 6, 7, 8];
+        let mut data = discriminator.to_vec();
+        data.extend_from_slice(&42u64.to_le_bytes());
+
+        assert!(is_matching_instruction(&crate::ID, &data, Some(&stablecoin), &discriminator, &stablecoin));
+        assert!(!is_matching_instruction(&crate::ID, &data, Some(&other), &discriminator, &stablecoin));
+        assert!(!is_matching_instruction(&Pubkey::new_unique(), &data, Some(&stablecoin), &discriminator, &stablecoin));
+        assert!(!is_matching_instruction(&crate::ID, &data[..4], Some(&stablecoin), &discriminator, &stablecoin));
+        assert!(!is_matching_instruction(&crate::ID, &data, None, &discriminator, &stablecoin));
+    }
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use crate::utils::math::{self, Flow};
+
+/// Collateral movements for a single liquidation.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LiquidationPayout {
+    /// Collateral equivalent of the repaid debt, sent to the liquidator
+    pub base_collateral: u64,
+    /// Extra collateral sent to the liquidator
+    pub liquidator_bonus: u64,
+    /// Collateral kept by the protocol as a penalty
+    pub protocol_penalty: u64,
+}
+
+impl LiquidationPayout {
+    /// Collateral sent to the liquidator.
+    pub fn liquidator_total(&self) -> u64 {
+        self.base_collateral.saturating_add(self.liquidator_bonus)
+    }
+
+    /// Collateral removed from the position.
+    pub fn seized_total(&self) -> u64 {
+        self.liquidator_total().saturating_add(self.protocol_penalty)
+    }
+}
+
+/// Splits the collateral seized for repaying `base_collateral` worth of debt.
+/// When the position holds less than the full amount, the liquidator is paid
+/// first and the protocol penalty absorbs the shortfall.
+pub fn calculate_liquidation_payout(
+    base_collateral: u64,
+    penalty_bps: u16,
+    bonus_bps: u16,
+    available_collateral: u64,
+) -> Result<LiquidationPayout> {
+    // The bonus is paid to the liquidator, the penalty by the liquidated
+    let liquidator_bonus = math::bps_of(base_collateral, bonus_bps, Flow::ToUser)?;
+    let protocol_penalty = math::bps_of(base_collateral, penalty_bps, Flow::FromUser)?;
+
+    let base_collateral = base_collateral.min(available_collateral);
+    let remaining = available_collateral - base_collateral;
+    let liquidator_bonus = liquidator_bonus.min(remaining);
+    let protocol_penalty = protocol_penalty.min(remaining - liquidator_bonus);
+
+    Ok(LiquidationPayout {
+        base_collateral,
+        liquidator_bonus,
+        protocol_penalty,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_payout() {
+        let payout = calculate_liquidation_payout(1_000_000, 300, 500, 10_000_000).unwrap();
+        assert_eq!(payout.base_collateral, 1_000_000);
+        assert_eq!(payout.liquidator_bonus, 50_000);
+        assert_eq!(payout.protocol_penalty, 30_000);
+        assert_eq!(payout.liquidator_total(), 1_050_000);
+        assert_eq!(payout.seized_total(), 1_080_000);
+    }
+
+    #[test]
+    fn test_shortfall_hits_penalty_first() {
+        let payout = calculate_liquidation_payout(1_000_000, 300, 500, 1_060_000).unwrap();
+        assert_eq!(payout.liquidator_total(), 1_050_000);
+        assert_eq!(payout.protocol_penalty, 10_000);
+
+        let payout = calculate_liquidation_payout(1_000_000, 300, 500, 900_000).unwrap();
+        assert_eq!(payout.base_collateral, 900_000);
+        assert_eq!(payout.liquidator_bonus, 0);
+        assert_eq!(payout.protocol_penalty, 0);
+    }
+}
@@ -9,13 +9,179 @@ pub fn checked_div(a: u64, b: u64) -> Result<u64> {
     a.checked_div(b).ok_or(error!(StableFunError::MathOverflow))
 }
 
+/// Which way an amount moves: paid out to the user, or paid in by them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Flow {
+    ToUser,
+    FromUser,
+}
+
+/// `a * b / divisor`, rounded against the user: down for amounts paid out,
+/// up for amounts paid in.
+pub fn mul_div(a: u64, b: u64, divisor: u64, flow: Flow) -> Result<u64> {
+    require!(divisor > 0, StableFunError::MathOverflow);
+    let product = a as u128 * b as u128;
+    let remainder = product % divisor as u128;
+    let round_up = flow == Flow::FromUser && remainder > 0;
+    #[cfg(debug_assertions)]
+    rounding_audit::record(flow, round_up, remainder);
+    u64::try_from(product / divisor as u128 + round_up as u128)
+        .map_err(|_| error!(StableFunError::MathOverflow))
+}
+
+/// Collateral paid out for `amount` stablecoins, rounded down.
 pub fn calculate_token_amount(
     amount: u64,
     price: u64,
     decimals: u8,
 ) -> Result<u64> {
-    amount
-        .checked_mul(price)
-        .and_then(|v| v.checked_div(10u64.pow(decimals as u32)))
-        .ok_or(error!(StableFunError::MathOverflow))
-}
\ No newline at end of file
+    mul_div(amount, price, 10u64.pow(decimals as u32), Flow::ToUser)
+}
+
+/// Collateral a user deposits for `amount` stablecoins, rounded up.
+pub fn calculate_deposit_amount(
+    amount: u64,
+    price: u64,
+    decimals: u8,
+) -> Result<u64> {
+    mul_div(amount, price, 10u64.pow(decimals as u32), Flow::FromUser)
+}
+
+/// `bps` basis points of `amount`, rounded against the user like `mul_div`:
+/// fees they pay round up. The product is taken in u128 so large-decimal
+/// amounts cannot overflow it.
+pub fn bps_of(amount: u64, bps: u16, flow: Flow) -> Result<u64> {
+    mul_div(amount, bps as u64, 10_000, flow)
+}
+
+/// The collateral ratio, in bps: the value of the collateral over the debt
+/// it backs, both in stablecoin units. Every ratio the program stores or
+/// checks is this one. `None` when there is no debt.
+pub fn collateral_ratio_bps(collateral_value: u64, debt: u64) -> Option<u64> {
+    if debt == 0 {
+        return None;
+    }
+    let ratio = collateral_value as u128 * 10_000 / debt as u128;
+    Some(u64::try_from(ratio).unwrap_or(u64::MAX))
+}
+
+/// Inverse of `calculate_token_amount`: the stablecoin value of a collateral amount.
+pub fn calculate_collateral_value(
+    collateral: u64,
+    price: u64,
+    decimals: u8,
+) -> Result<u64> {
+    let value = (collateral as u128)
+        .checked_mul(10u128.pow(decimals as u32))
+        .and_then(|v| v.checked_div(price as u128))
+        .ok_or(error!(StableFunError::MathOverflow))?;
+    u64::try_from(value).map_err(|_| error!(StableFunError::MathOverflow))
+}
+
+/// Debug-build ledger of the remainders every rounding leaves, booked to the
+/// side it favoured. The protocol should take all of them; tests call
+/// `assert_never_favours_user` after driving an operation through.
+#[cfg(debug_assertions)]
+pub mod rounding_audit {
+    use std::cell::Cell;
+    use super::Flow;
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct RoundingLedger {
+        pub roundings: u64,
+        /// Remainders kept by the protocol, each in its own operation's units
+        pub protocol_remainder: u128,
+        /// Remainders that went the user's way; should stay zero
+        pub user_remainder: u128,
+    }
+
+    thread_local! {
+        static LEDGER: Cell<RoundingLedger> = Cell::new(RoundingLedger::default());
+    }
+
+    pub fn record(flow: Flow, rounded_up: bool, remainder: u128) {
+        LEDGER.with(|ledger| {
+            let mut entry = ledger.get();
+            entry.roundings += 1;
+            // Rounding a payout up, or a payment down, hands the remainder to the user
+            if rounded_up == (flow == Flow::ToUser) {
+                entry.user_remainder += remainder;
+            } else {
+                entry.protocol_remainder += remainder;
+            }
+            ledger.set(entry);
+        });
+    }
+
+    /// The ledger so far, resetting it for the next operation.
+    pub fn take() -> RoundingLedger {
+        LEDGER.with(Cell::take)
+    }
+
+    pub fn assert_never_favours_user() {
+        let ledger = take();
+        assert_eq!(ledger.user_remainder, 0, "rounding favoured the user: {ledger:?}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mul_div_rounds_against_user() {
+        rounding_audit::take();
+        assert_eq!(mul_div(10, 1, 3, Flow::ToUser).unwrap(), 3);
+        assert_eq!(mul_div(10, 1, 3, Flow::FromUser).unwrap(), 4);
+        assert_eq!(mul_div(9, 1, 3, Flow::FromUser).unwrap(), 3);
+        assert!(mul_div(1, 1, 0, Flow::ToUser).is_err());
+        assert!(mul_div(u64::MAX, 2, 1, Flow::ToUser).is_err());
+
+        let ledger = rounding_audit::take();
+        assert_eq!(ledger.roundings, 4);
+        assert_eq!(ledger.protocol_remainder, 2);
+        assert_eq!(ledger.user_remainder, 0);
+    }
+
+    #[test]
+    fn test_round_trip_conserves_collateral() {
+        rounding_audit::take();
+        // An awkward price so every conversion leaves a remainder
+        let price = 1_000_003;
+        for amount in [1_000, 7_777, 123_456_789] {
+            let deposit = calculate_deposit_amount(amount, price, 6).unwrap();
+            let payout = calculate_token_amount(amount, price, 6).unwrap();
+            assert!(payout <= deposit);
+        }
+        rounding_audit::assert_never_favours_user();
+    }
+
+    #[test]
+    fn test_bps_at_extreme_values() {
+        assert_eq!(bps_of(1_000_000, 30, Flow::FromUser).unwrap(), 3_000);
+        // A fee on an amount too small to divide evenly is never rounded away
+        assert_eq!(bps_of(333, 30, Flow::FromUser).unwrap(), 1);
+        assert_eq!(bps_of(333, 30, Flow::ToUser).unwrap(), 0);
+        // u64 arithmetic would overflow on the multiplication
+        assert_eq!(bps_of(u64::MAX, 10_000, Flow::FromUser).unwrap(), u64::MAX);
+        assert_eq!(bps_of(u64::MAX, 5_000, Flow::ToUser).unwrap(), u64::MAX / 2);
+        assert_eq!(bps_of(u64::MAX, 5_000, Flow::FromUser).unwrap(), u64::MAX / 2 + 1);
+        assert!(bps_of(u64::MAX, 10_001, Flow::FromUser).is_err());
+    }
+
+    #[test]
+    fn test_collateral_ratio_bps() {
+        assert_eq!(collateral_ratio_bps(1_500, 1_000), Some(15_000));
+        assert_eq!(collateral_ratio_bps(900, 1_000), Some(9_000));
+        assert_eq!(collateral_ratio_bps(1_000, 0), None);
+        assert_eq!(collateral_ratio_bps(u64::MAX, u64::MAX), Some(10_000));
+        assert_eq!(collateral_ratio_bps(u64::MAX, 1), Some(u64::MAX));
+    }
+
+    #[test]
+    fn test_audit_flags_user_favoured_rounding() {
+        rounding_audit::take();
+        rounding_audit::record(Flow::FromUser, false, 1);
+        assert_eq!(rounding_audit::take().user_remainder, 1);
+    }
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+/// Domain separators so a leaf can never be passed off as an inner node.
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+/// Leaf for `amount` owed to (or held by) `account`.
+pub fn leaf_hash(index: u64, account: &Pubkey, amount: u64) -> [u8; 32] {
+    hashv(&[
+        LEAF_PREFIX,
+        &index.to_le_bytes(),
+        account.as_ref(),
+        &amount.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// Hashes a pair of nodes in sorted order, so proofs need no direction bits.
+pub fn node_hash(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, left, right]).to_bytes()
+}
+
+pub fn verify_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| node_hash(&node, sibling));
+    computed == *root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_proof() {
+        let holders: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let leaves: Vec<[u8; 32]> = holders
+            .iter()
+            .enumerate()
+            .map(|(i, holder)| leaf_hash(i as u64, holder, 100 * (i as u64 + 1)))
+            .collect();
+        let left = node_hash(&leaves[0], &leaves[1]);
+        let right = node_hash(&leaves[2], &leaves[3]);
+        let root = node_hash(&left, &right);
+
+        assert!(verify_proof(&[leaves[3], left], &root, leaves[2]));
+        assert!(verify_proof(&[leaves[0], right], &root, leaves[1]));
+        // Wrong amount
+        assert!(!verify_proof(&[leaves[3], left], &root, leaf_hash(2, &holders[2], 301)));
+        // Leaf claimed under another index
+        assert!(!verify_proof(&[leaves[3], left], &root, leaf_hash(3, &holders[2], 300)));
+    }
+}
@@ -1,5 +1,12 @@
+pub mod attestation;
+pub mod currency;
+pub mod ed25519;
+pub mod introspection;
+pub mod liquidation;
 pub mod math;
+pub mod merkle;
 pub mod oracle;
+pub mod secp256k1;
 pub mod stablebond;
 pub mod token;
 pub mod validation;
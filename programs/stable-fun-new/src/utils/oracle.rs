@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
-use switchboard_solana::AggregatorAccountData;
+use switchboard_solana::{AggregatorAccountData, SWITCHBOARD_PROGRAM_ID};
+use crate::constants::BASIS_POINTS_DIVISOR;
 use crate::error::StableFunError;
+use crate::state::{CurrencyBasket, StablecoinMint};
 
 // Constants
 pub const MAX_PRICE_STALENESS: i64 = 300; // 5 minutes
@@ -106,6 +108,24 @@ impl OracleService {
         Ok(())
     }
 
+    /// Checks a feed is a Switchboard account before its key is stored, so
+    /// later handlers matching against the stored key trust a real feed.
+    pub fn verify_feed_owner(feed: &AccountInfo) -> Result<()> {
+        require_keys_eq!(*feed.owner, SWITCHBOARD_PROGRAM_ID, StableFunError::InvalidOracleOwner);
+        Ok(())
+    }
+
+    /// Rejects a replacement feed whose price strays more than `max_bps`
+    /// from the current feed's.
+    pub fn check_feed_deviation(current_price: u64, new_price: u64, max_bps: u16) -> Result<()> {
+        let deviation = current_price.abs_diff(new_price) as u128 * BASIS_POINTS_DIVISOR as u128;
+        require!(
+            deviation <= current_price as u128 * max_bps as u128,
+            StableFunError::PriceFeedDeviation
+        );
+        Ok(())
+    }
+
     pub fn verify_oracle_price(
         feed: &AccountLoader<AggregatorAccountData>
     ) -> Result<u64> {
@@ -114,6 +134,68 @@ impl OracleService {
         price.standardize()
     }
 
+    /// Collateral per stablecoin unit with `PRICE_DECIMALS` decimals, the
+    /// price the `math` amount helpers take, and when the oldest feed it was
+    /// read from last updated. Coins with an FX feed compose it with the
+    /// bond NAV `price_feed`.
+    pub fn read_collateral_price(
+        stablecoin_mint: &StablecoinMint,
+        price_feed: &AccountLoader<AggregatorAccountData>,
+        fx_feed: Option<&AccountLoader<AggregatorAccountData>>,
+    ) -> Result<(u64, i64)> {
+        let price = Self::get_price(price_feed)?;
+        Self::validate_price(&price, None)?;
+        if stablecoin_mint.fx_feed == Pubkey::default() {
+            return Ok((price.standardize()?, price.last_updated));
+        }
+
+        let fx_feed = fx_feed.ok_or(StableFunError::InvalidOracle)?;
+        require_keys_eq!(fx_feed.key(), stablecoin_mint.fx_feed, StableFunError::InvalidOracle);
+        let fx_rate = Self::get_price(fx_feed)?;
+        Self::validate_price(&fx_rate, None)?;
+        Ok((
+            Self::compose_price(price.standardize()?, fx_rate.standardize()?)?,
+            price.last_updated.min(fx_rate.last_updated),
+        ))
+    }
+
+    /// The pricing engine: see `read_collateral_price`.
+    pub fn collateral_price(
+        stablecoin_mint: &StablecoinMint,
+        price_feed: &AccountLoader<AggregatorAccountData>,
+        fx_feed: Option<&AccountLoader<AggregatorAccountData>>,
+    ) -> Result<u64> {
+        Self::read_collateral_price(stablecoin_mint, price_feed, fx_feed).map(|(price, _)| price)
+    }
+
+    /// Collateral per stablecoin unit for a bond worth `nav` of its
+    /// underlying currency, at `fx_rate` target units per underlying unit:
+    /// the inverse of the bond's value in the target currency.
+    pub fn compose_price(nav: u64, fx_rate: u64) -> Result<u64> {
+        let price = (nav as u128)
+            .checked_mul(fx_rate as u128)
+            .filter(|value| *value > 0)
+            .map(|value| 10u128.pow(3 * PRICE_DECIMALS as u32) / value)
+            .ok_or(error!(StableFunError::InvalidOraclePrice))?;
+        require!(price > 0, StableFunError::InvalidOraclePrice);
+        u64::try_from(price).map_err(|_| error!(StableFunError::MathOverflow))
+    }
+
+    /// Prices collateral in basket units for basket-pegged coins and passes
+    /// `oracle_price` through unchanged for single-currency coins.
+    pub fn basket_adjusted_price(
+        stablecoin_mint: &StablecoinMint,
+        basket: Option<&CurrencyBasket>,
+        oracle_price: u64,
+        now: i64,
+    ) -> Result<u64> {
+        if stablecoin_mint.basket == Pubkey::default() {
+            return Ok(oracle_price);
+        }
+        let basket = basket.ok_or(StableFunError::InvalidBasket)?;
+        basket.price_in_basket(oracle_price, now)
+    }
+
     #[inline(always)]
     pub fn get_median_price(
         oracle_accounts: &[AccountLoader<AggregatorAccountData>]
@@ -170,6 +252,36 @@ impl OracleService {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_check_feed_deviation() {
+        assert!(OracleService::check_feed_deviation(1_000_000, 1_010_000, 100).is_ok());
+        assert!(OracleService::check_feed_deviation(1_000_000, 990_000, 100).is_ok());
+        assert!(OracleService::check_feed_deviation(1_000_000, 1_010_001, 100).is_err());
+        assert!(OracleService::check_feed_deviation(1_000_000, 989_999, 100).is_err());
+        assert!(OracleService::check_feed_deviation(u64::MAX, 0, 100).is_err());
+    }
+
+    #[test]
+    fn test_verify_feed_owner() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = [];
+        for (owner, valid) in [(SWITCHBOARD_PROGRAM_ID, true), (Pubkey::new_unique(), false)] {
+            let feed = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+            assert_eq!(OracleService::verify_feed_owner(&feed).is_ok(), valid);
+        }
+    }
+
+    #[test]
+    fn test_compose_price() {
+        // A bond at 1.02 USD is worth 0.9384 EUR at 0.92 EUR per USD, so a
+        // EUR coin takes 1/0.9384 bonds
+        assert_eq!(OracleService::compose_price(1_020_000, 920_000).unwrap(), 1_065_643);
+        assert_eq!(OracleService::compose_price(1_000_000, 1_000_000).unwrap(), 1_000_000);
+        assert!(OracleService::compose_price(0, 1_000_000).is_err());
+        assert!(OracleService::compose_price(u64::MAX, u64::MAX).is_err());
+    }
+
     #[test]
     fn test_price_standardization() {
         let price = OraclePrice::new(1_000_000_000, 9, 0, 0);
@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::secp256k1_program;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked,
+    load_instruction_at_checked,
+};
+
+use crate::error::StableFunError;
+
+/// Domain prefix for remote redemption orders, so a custodian signature can
+/// never be replayed as some other message.
+pub const REMOTE_REDEEM_DOMAIN: &[u8] = b"stable-fun:remote-redeem";
+
+const SIGNATURE_OFFSETS_START: usize = 1;
+const SIGNATURE_OFFSETS_LEN: usize = 11;
+const ETH_ADDRESS_LEN: usize = 20;
+
+/// Bytes the EVM custodian signs for a redemption order.
+pub fn remote_redeem_message(
+    stablecoin_mint: &Pubkey,
+    amount: u64,
+    recipient: &Pubkey,
+    nonce: u64,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(REMOTE_REDEEM_DOMAIN.len() + 80);
+    message.extend_from_slice(REMOTE_REDEEM_DOMAIN);
+    message.extend_from_slice(stablecoin_mint.as_ref());
+    message.extend_from_slice(&amount.to_le_bytes());
+    message.extend_from_slice(recipient.as_ref());
+    message.extend_from_slice(&nonce.to_le_bytes());
+    message
+}
+
+/// Extracts the Ethereum address and message checked by a secp256k1 precompile
+/// instruction at `own_index`. Only single-signature instructions whose
+/// offsets all point into their own data are accepted.
+pub fn parse_secp256k1_instruction(data: &[u8], own_index: u8) -> Option<([u8; 20], &[u8])> {
+    if data.first() != Some(&1) || data.len() < SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN {
+        return None;
+    }
+
+    let offsets = &data[SIGNATURE_OFFSETS_START..SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN];
+    let read_u16 = |at: usize| u16::from_le_bytes([offsets[at], offsets[at + 1]]) as usize;
+    let signature_ix = offsets[2];
+    let eth_address_offset = read_u16(3);
+    let eth_address_ix = offsets[5];
+    let message_offset = read_u16(6);
+    let message_size = read_u16(8);
+    let message_ix = offsets[10];
+
+    if signature_ix != own_index || eth_address_ix != own_index || message_ix != own_index {
+        return None;
+    }
+
+    let eth_address = data.get(eth_address_offset..eth_address_offset + ETH_ADDRESS_LEN)?;
+    let message = data.get(message_offset..message_offset + message_size)?;
+    Some((eth_address.try_into().ok()?, message))
+}
+
+/// Requires the instruction right before the current one to be a secp256k1
+/// precompile check of `message` signed by `eth_address`. The runtime rejects
+/// the transaction if that signature is invalid.
+pub fn verify_prior_secp256k1_signature(
+    instructions_sysvar: &AccountInfo,
+    eth_address: &[u8; 20],
+    message: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, StableFunError::InvalidRemoteRedemptionSignature);
+
+    let secp_index = current_index - 1;
+    let ix = load_instruction_at_checked(secp_index as usize, instructions_sysvar)?;
+    require!(
+        ix.program_id == secp256k1_program::ID,
+        StableFunError::InvalidRemoteRedemptionSignature
+    );
+
+    let own_index = u8::try_from(secp_index)
+        .map_err(|_| error!(StableFunError::InvalidRemoteRedemptionSignature))?;
+    let (signer, signed_message) = parse_secp256k1_instruction(&ix.data, own_index)
+        .ok_or(error!(StableFunError::InvalidRemoteRedemptionSignature))?;
+    require!(
+        signer == *eth_address && signed_message == message,
+        StableFunError::InvalidRemoteRedemptionSignature
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secp_data(own_index: u8, eth_address: &[u8; 20], message: &[u8]) -> Vec<u8> {
+        let eth_address_offset = (SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN) as u16;
+        let signature_offset = eth_address_offset + ETH_ADDRESS_LEN as u16;
+        let message_offset = signature_offset + 65;
+
+        let mut data = vec![1u8];
+        data.extend_from_slice(&signature_offset.to_le_bytes());
+        data.push(own_index);
+        data.extend_from_slice(&eth_address_offset.to_le_bytes());
+        data.push(own_index);
+        data.extend_from_slice(&message_offset.to_le_bytes());
+        data.extend_from_slice(&(message.len() as u16).to_le_bytes());
+        data.push(own_index);
+        data.extend_from_slice(eth_address);
+        data.extend_from_slice(&[0u8; 65]);
+        data.extend_from_slice(message);
+        data
+    }
+
+    #[test]
+    fn test_parse_secp256k1_instruction() {
+        let eth_address = [7u8; 20];
+        let message = remote_redeem_message(&Pubkey::new_unique(), 1_000, &Pubkey::new_unique(), 1);
+        let data = secp_data(2, &eth_address, &message);
+
+        let (signer, signed) = parse_secp256k1_instruction(&data, 2).unwrap();
+        assert_eq!(signer, eth_address);
+        assert_eq!(signed, &message[..]);
+
+        // Offsets pointing at another instruction are rejected
+        assert!(parse_secp256k1_instruction(&data, 1).is_none());
+
+        let mut two_signatures = data.clone();
+        two_signatures[0] = 2;
+        assert!(parse_secp256k1_instruction(&two_signatures, 2).is_none());
+        assert!(parse_secp256k1_instruction(&data[..data.len() - 1], 2).is_none());
+    }
+}
@@ -1,6 +1,9 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount};
 
+use crate::state::BondTerms;
+use crate::utils::math;
+
 // Define the error enum
 #[error_code]
 pub enum StablebondError {
@@ -49,6 +52,8 @@ pub struct StablebondData {
     pub maturity_timestamp: i64,
     pub total_supply: u64,
     pub decimals: u8,
+    /// Time the bond started accruing at `current_yield`
+    pub accrual_start: i64,
 }
 
 /// Service for interacting with stablebonds
@@ -66,9 +71,35 @@ impl StablebondService {
             maturity_timestamp: stablebond_mint.maturity_timestamp,
             total_supply: stablebond_mint.supply,
             decimals: stablebond_mint.decimals,
+            accrual_start: stablebond_mint.last_yield_update,
         })
     }
 
+    /// Bond data from the coin's `BondTerms` PDA, or `None` when no terms
+    /// are set for `stablebond_mint`, the bond the coin currently holds.
+    pub fn get_bond_terms_data(
+        bond_terms: &AccountInfo,
+        stablebond_mint: &Pubkey,
+    ) -> Result<Option<StablebondData>> {
+        if bond_terms.data_is_empty() {
+            return Ok(None);
+        }
+        require_keys_eq!(*bond_terms.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+        let terms = BondTerms::try_deserialize(&mut &bond_terms.try_borrow_data()?[..])?;
+        if !terms.is_set_for(stablebond_mint) {
+            return Ok(None);
+        }
+        Ok(Some(StablebondData {
+            mint: terms.stablebond_mint,
+            underlying_mint: Pubkey::default(),
+            current_yield: terms.annual_yield,
+            maturity_timestamp: terms.maturity_timestamp,
+            total_supply: 0,
+            decimals: terms.decimals,
+            accrual_start: terms.issued_at,
+        }))
+    }
+
     /// Validate stablebond for use as collateral
     pub fn validate_stablebond(
         stablebond_mint: &Account<StablebondMint>,
@@ -100,6 +131,7 @@ impl StablebondService {
         amount: u64,
         stablebond: &StablebondData,
         price: u64,
+        current_timestamp: i64,
     ) -> Result<u64> {
         let base_value = amount
             .checked_mul(price)
@@ -108,33 +140,56 @@ impl StablebondService {
             .ok_or(StablebondError::MathOverflow)?;
 
         // Add accrued yield
-        let yield_value = Self::calculate_accrued_yield(amount, stablebond)?;
+        let yield_value = Self::calculate_accrued_yield(amount, stablebond, current_timestamp)?;
         
         base_value
             .checked_add(yield_value)
             .ok_or(StablebondError::MathOverflow.into())
     }
 
-    /// Calculate accrued yield
+    /// Yield `amount` bond tokens have accrued since the bond started
+    /// accruing, up to `current_timestamp` or maturity, whichever is first
     pub fn calculate_accrued_yield(
         amount: u64,
         stablebond: &StablebondData,
+        current_timestamp: i64,
     ) -> Result<u64> {
-        let current_timestamp = Clock::get()?.unix_timestamp;
-        let time_to_maturity = stablebond
-            .maturity_timestamp
-            .checked_sub(current_timestamp)
-            .ok_or(StablebondError::MathOverflow)?;
+        // Matured bonds accrue nothing further
+        let accrued_until = current_timestamp.min(stablebond.maturity_timestamp);
+        let elapsed = accrued_until.saturating_sub(stablebond.accrual_start).max(0);
 
-        // Calculate yield based on remaining time
-        let yield_amount = amount
-            .checked_mul(stablebond.current_yield)
-            .and_then(|v| v.checked_mul(time_to_maturity as u64))
+        let yield_amount = (amount as u128)
+            .checked_mul(stablebond.current_yield as u128)
+            .and_then(|v| v.checked_mul(elapsed as u128))
             .and_then(|v| v.checked_div(365 * 24 * 60 * 60)) // Annualized yield
-            .and_then(|v| v.checked_div(10u64.pow(YIELD_DECIMALS as u32)))
+            .and_then(|v| v.checked_div(10u128.pow(YIELD_DECIMALS as u32)))
+            .ok_or(StablebondError::MathOverflow)?;
+
+        u64::try_from(yield_amount).map_err(|_| StablebondError::MathOverflow.into())
+    }
+
+    /// Scales collateral priced off the raw oracle price down by the yield
+    /// each bond token has accrued, so mints take in and redemptions pay out
+    /// the same backing per coin however much the bonds have appreciated.
+    /// Collateral is left as is for bonds without terms.
+    pub fn yield_adjusted_collateral(
+        collateral_amount: u64,
+        stablebond: Option<&StablebondData>,
+        current_timestamp: i64,
+        flow: math::Flow,
+    ) -> Result<u64> {
+        let Some(stablebond) = stablebond else {
+            return Ok(collateral_amount);
+        };
+        let unit = 10u64
+            .checked_pow(stablebond.decimals as u32)
+            .ok_or(StablebondError::MathOverflow)?;
+        let accrued_per_unit = Self::calculate_accrued_yield(unit, stablebond, current_timestamp)?;
+        let unit_value = unit
+            .checked_add(accrued_per_unit)
             .ok_or(StablebondError::MathOverflow)?;
 
-        Ok(yield_amount)
+        math::mul_div(collateral_amount, unit, unit_value, flow)
     }
 
     /// Transfer stablebonds between accounts
@@ -175,6 +230,8 @@ impl StablebondService {
 mod tests {
     use super::*;
 
+    const HALF_YEAR_BEFORE_MATURITY: i64 = 1735689600 - 180 * 24 * 60 * 60;
+
     // Add test helper function
     fn create_test_stablebond() -> StablebondData {
         StablebondData {
@@ -184,6 +241,7 @@ mod tests {
             maturity_timestamp: 1735689600, // 2025-01-01
             total_supply: 1_000_000,
             decimals: 6,
+            accrual_start: 1735689600 - MAX_BOND_DURATION, // issued a year before
         }
     }
 
@@ -197,6 +255,7 @@ mod tests {
             amount,
             &stablebond,
             price,
+            HALF_YEAR_BEFORE_MATURITY,
         ).unwrap();
 
         assert!(value > amount); // Value should include yield
@@ -210,9 +269,85 @@ mod tests {
         let yield_amount = StablebondService::calculate_accrued_yield(
             amount,
             &stablebond,
+            HALF_YEAR_BEFORE_MATURITY,
         ).unwrap();
 
         assert!(yield_amount > 0);
         assert!(yield_amount < amount); // Yield should be less than principal
+
+        // Yield grows with time since issuance
+        let later = StablebondService::calculate_accrued_yield(
+            amount,
+            &stablebond,
+            HALF_YEAR_BEFORE_MATURITY + 1_000,
+        ).unwrap();
+        assert!(later > yield_amount);
+
+        // Past maturity nothing more accrues, and before issuance nothing has
+        let at_maturity = StablebondService::calculate_accrued_yield(
+            amount,
+            &stablebond,
+            stablebond.maturity_timestamp,
+        ).unwrap();
+        let matured = StablebondService::calculate_accrued_yield(
+            amount,
+            &stablebond,
+            stablebond.maturity_timestamp + 1,
+        ).unwrap();
+        assert_eq!(matured, at_maturity);
+        let unissued = StablebondService::calculate_accrued_yield(
+            amount,
+            &stablebond,
+            stablebond.accrual_start - 1,
+        ).unwrap();
+        assert_eq!(unissued, 0);
+    }
+
+    #[test]
+    fn test_yield_adjusted_collateral() {
+        let stablebond = create_test_stablebond();
+
+        // Appreciated bonds: the same redemption takes fewer of them,
+        // rounded in the vault's favour either way
+        let adjusted = StablebondService::yield_adjusted_collateral(
+            1_000_000,
+            Some(&stablebond),
+            HALF_YEAR_BEFORE_MATURITY,
+            math::Flow::ToUser,
+        ).unwrap();
+        assert_eq!(adjusted, 797_814);
+        let deposited = StablebondService::yield_adjusted_collateral(
+            1_000_000,
+            Some(&stablebond),
+            HALF_YEAR_BEFORE_MATURITY,
+            math::Flow::FromUser,
+        ).unwrap();
+        assert_eq!(deposited, 797_815);
+
+        // The longer the bonds have accrued, the fewer back each coin
+        let matured = StablebondService::yield_adjusted_collateral(
+            1_000_000,
+            Some(&stablebond),
+            stablebond.maturity_timestamp,
+            math::Flow::ToUser,
+        ).unwrap();
+        assert_eq!(matured, 666_666);
+
+        let unissued = StablebondService::yield_adjusted_collateral(
+            1_000_000,
+            Some(&stablebond),
+            stablebond.accrual_start,
+            math::Flow::ToUser,
+        ).unwrap();
+        assert_eq!(unissued, 1_000_000);
+
+        // Without terms the oracle price alone prices the collateral
+        let unadjusted = StablebondService::yield_adjusted_collateral(
+            1_000_000,
+            None,
+            HALF_YEAR_BEFORE_MATURITY,
+            math::Flow::ToUser,
+        ).unwrap();
+        assert_eq!(unadjusted, 1_000_000);
     }
 }
\ No newline at end of file
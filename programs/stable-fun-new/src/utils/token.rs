@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token_2022::spl_token_2022;
 use anchor_spl::token::{self, Token, TokenAccount};
 
 pub fn transfer_tokens<'info>(
@@ -19,4 +21,35 @@ pub fn transfer_tokens<'info>(
         ),
         amount,
     )
-}
\ No newline at end of file
+}
+
+/// Whether `delegate` may burn `amount` from `token_account`: the owner must
+/// have approved it for at least that much beforehand.
+pub fn delegated_for(
+    token_account: &spl_token_2022::state::Account,
+    delegate: &Pubkey,
+    amount: u64,
+) -> bool {
+    token_account.delegate == COption::Some(*delegate) && token_account.delegated_amount >= amount
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delegated_for() {
+        let delegate = Pubkey::new_unique();
+        let mut account = spl_token_2022::state::Account {
+            amount: 1_000,
+            ..Default::default()
+        };
+        assert!(!delegated_for(&account, &delegate, 500));
+
+        account.delegate = COption::Some(delegate);
+        account.delegated_amount = 500;
+        assert!(delegated_for(&account, &delegate, 500));
+        assert!(!delegated_for(&account, &delegate, 501));
+        assert!(!delegated_for(&account, &Pubkey::new_unique(), 500));
+    }
+}
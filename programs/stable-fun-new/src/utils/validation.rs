@@ -1,9 +1,27 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, TokenAccount};
-
+use unicode_normalization::UnicodeNormalization;
+
+use crate::constants::{
+    BASIS_POINTS_DIVISOR,
+    MAX_LIQUIDATION_GRACE_SECONDS,
+    MAX_DEMURRAGE_BPS,
+    MAX_CPI_CALLERS,
+    MAX_LENDING_PROGRAMS,
+    MAX_LIQUIDATION_INCENTIVE_BPS,
+    MIN_LIQUIDATION_THRESHOLD,
+    MAX_REVEAL_DELAY_SLOTS,
+    MAX_UNPAUSE_COOLOFF_SECONDS,
+    MAX_YIELD_FEE_BPS,
+    MAX_CACHED_PRICE_AGE_SLOTS,
+    MAX_WITHDRAWAL_DELAY,
+    MIN_WITHDRAWAL_DELAY,
+};
 use crate::error::StableFunError;
-use crate::state::{StablecoinMint, StablecoinVault};
+use crate::state::{IssuerBond, ProtocolConfig, ReserveReport, StablecoinMint, StablecoinVault};
+use crate::utils::currency::normalize_currency;
 use crate::utils::oracle::OraclePrice;
+use crate::utils::math;
 
 // Constants for validation
 pub const MIN_TRANSACTION_AMOUNT: u64 = 1_000;
@@ -16,6 +34,22 @@ pub const MAX_NAME_LENGTH: usize = 32;
 pub const MIN_SYMBOL_LENGTH: usize = 2;
 pub const MAX_SYMBOL_LENGTH: usize = 10;
 
+/// Returns true for characters that render invisibly or reorder text and
+/// can be used to make two visually identical names compare unequal.
+#[inline(always)]
+pub fn is_disallowed_char(c: char) -> bool {
+    c.is_control()
+        || matches!(
+            c,
+            '\u{00AD}'
+                | '\u{200B}'..='\u{200F}'
+                | '\u{202A}'..='\u{202E}'
+                | '\u{2060}'..='\u{2064}'
+                | '\u{2066}'..='\u{2069}'
+                | '\u{FEFF}'
+        )
+}
+
 #[derive(Default)]
 pub struct ValidationService;
 
@@ -29,75 +63,286 @@ impl ValidationService {
         Ok(())
     }
 
+    /// Checks the canonical ratio (`math::collateral_ratio_bps`) of
+    /// `collateral_value` against `debt`; nothing to check without debt.
     #[inline(always)]
     pub fn validate_collateral_ratio(
-        collateral: u64,
-        supply: u64,
+        collateral_value: u64,
+        debt: u64,
         min_ratio: u16,
     ) -> Result<()> {
-        if supply == 0 {
+        let Some(ratio) = math::collateral_ratio_bps(collateral_value, debt) else {
             return Ok(());
-        }
+        };
+        require!(ratio >= min_ratio as u64, StableFunError::CollateralRatioTooLow);
+        Ok(())
+    }
 
-        let ratio = (collateral as u128)
-            .checked_mul(10000)
-            .and_then(|v| v.checked_div(supply as u128))
-            .map(|v| v as u16)
-            .ok_or(error!(StableFunError::MathOverflow))?;
+    #[inline(always)]
+    pub fn update_collateral_ratio(
+        vault: &mut Account<StablecoinVault>,
+        price: u64,
+        decimals: u8,
+    ) -> Result<()> {
+        vault.update_collateral_ratio(price, decimals)
+    }
+
+    #[inline(always)]
+    pub fn validate_fee(fee_bps: u16) -> Result<()> {
+        require!(fee_bps <= MAX_FEE_BPS, StableFunError::FeeTooHigh);
+        Ok(())
+    }
 
+    #[inline(always)]
+    pub fn validate_withdrawal_delay(delay: i64) -> Result<()> {
         require!(
-            (min_ratio..=MAX_COLLATERAL_RATIO_BPS).contains(&ratio),
-            StableFunError::CollateralRatioTooLow
+            delay == 0 || (MIN_WITHDRAWAL_DELAY..=MAX_WITHDRAWAL_DELAY).contains(&delay),
+            StableFunError::InvalidWithdrawalDelay
         );
+        Ok(())
+    }
 
+    #[inline(always)]
+    pub fn validate_liquidation_threshold(threshold_bps: u16, min_ratio: u16) -> Result<()> {
+        require!(
+            threshold_bps >= MIN_LIQUIDATION_THRESHOLD && threshold_bps < min_ratio,
+            StableFunError::InvalidLiquidationThreshold
+        );
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn validate_liquidation_incentives(
+        penalty_bps: u16,
+        bonus_bps: u16,
+        config: &ProtocolConfig,
+    ) -> Result<()> {
+        require!(
+            penalty_bps <= config.max_liquidation_penalty_bps
+                && bonus_bps <= config.max_liquidator_bonus_bps,
+            StableFunError::LiquidationIncentiveTooHigh
+        );
+        Ok(())
+    }
+
+    /// Demurrage is opt-in per coin but bounded by the protocol.
+    #[inline(always)]
+    pub fn validate_demurrage(rate_bps: u16, config: &ProtocolConfig) -> Result<()> {
+        require!(rate_bps <= config.max_demurrage_bps, StableFunError::DemurrageTooHigh);
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn validate_yield_fee(fee_bps: u16, config: &ProtocolConfig) -> Result<()> {
+        require!(fee_bps <= config.max_yield_fee_bps, StableFunError::YieldFeeTooHigh);
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn validate_max_supply(
+        max_supply: u64,
+        config: &ProtocolConfig,
+        reserve_report: Option<&ReserveReport>,
+        now: i64,
+    ) -> Result<()> {
+        require!(
+            max_supply <= config.supply_cap(reserve_report, now),
+            StableFunError::SupplyCapExceedsTier
+        );
         Ok(())
     }
 
     #[inline(always)]
-    pub fn update_collateral_ratio(vault: &mut Account<StablecoinVault>) -> Result<()> {
-        if vault.total_value_locked == 0 || vault.total_collateral == 0 {
-            vault.current_ratio = 0;
+    pub fn validate_bonded_supply(
+        max_supply: u64,
+        config: &ProtocolConfig,
+        issuer_bond: Option<&IssuerBond>,
+    ) -> Result<()> {
+        require!(
+            max_supply <= config.bonded_supply_cap(issuer_bond),
+            StableFunError::IssuerBondRequired
+        );
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn validate_liquidation_grace(grace_seconds: i64, override_bps: u16) -> Result<()> {
+        require!(
+            (0..=MAX_LIQUIDATION_GRACE_SECONDS).contains(&grace_seconds)
+                && override_bps <= BASIS_POINTS_DIVISOR,
+            StableFunError::InvalidLiquidationGrace
+        );
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn validate_unpause_cooloff(cooloff_seconds: i64) -> Result<()> {
+        require!(
+            (0..=MAX_UNPAUSE_COOLOFF_SECONDS).contains(&cooloff_seconds),
+            StableFunError::InvalidUnpauseCooloff
+        );
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn validate_cached_price_age(max_age_slots: u64) -> Result<()> {
+        require!(
+            max_age_slots <= MAX_CACHED_PRICE_AGE_SLOTS,
+            StableFunError::InvalidCachedPriceAge
+        );
+        Ok(())
+    }
+
+    /// Preconditions for lifting a pause: the cool-off since `paused_at` has
+    /// passed and the collateral (valued at a fresh price) covers the minimum ratio.
+    pub fn validate_unpause(
+        collateral_value: u64,
+        supply: u64,
+        min_ratio: u16,
+        paused_at: i64,
+        cooloff_seconds: i64,
+        now: i64,
+    ) -> Result<()> {
+        require!(
+            now.saturating_sub(paused_at) >= cooloff_seconds,
+            StableFunError::UnpauseCooloffActive
+        );
+        if supply == 0 {
             return Ok(());
         }
 
-        let ratio = (vault.total_value_locked as u128)
-            .checked_mul(10000)
-            .and_then(|v| v.checked_div(vault.total_collateral as u128))
-            .map(|v| v as u16)
+        let ratio = (collateral_value as u128)
+            .checked_mul(BASIS_POINTS_DIVISOR as u128)
+            .and_then(|v| v.checked_div(supply as u128))
             .ok_or(error!(StableFunError::MathOverflow))?;
+        require!(ratio >= min_ratio as u128, StableFunError::CollateralRatioTooLow);
+        Ok(())
+    }
 
-        vault.current_ratio = ratio;
+    #[inline(always)]
+    pub fn validate_protocol_config(config: &ProtocolConfig) -> Result<()> {
+        require!(
+            config.max_liquidation_penalty_bps <= MAX_LIQUIDATION_INCENTIVE_BPS
+                && config.max_liquidator_bonus_bps <= MAX_LIQUIDATION_INCENTIVE_BPS
+                && config.max_demurrage_bps <= MAX_DEMURRAGE_BPS
+                && config.max_yield_fee_bps <= MAX_YIELD_FEE_BPS,
+            StableFunError::InvalidProtocolConfig
+        );
+        // Attestation gating needs a registry and credential to check against
+        let gated = config.requires_issuer_attestation() || config.require_holder_attestation;
+        require!(
+            !gated
+                || (config.attestation_program != Pubkey::default()
+                    && config.attestation_credential != Pubkey::default()),
+            StableFunError::InvalidProtocolConfig
+        );
+        require!(
+            !config.require_holder_attestation || config.holder_schema != Pubkey::default(),
+            StableFunError::InvalidProtocolConfig
+        );
+        // Attesting the reserves may only raise the ceiling (0 is uncapped)
+        let attested_tier_higher = config.reserve_attested_supply_cap == 0
+            || (config.unverified_supply_cap != 0
+                && config.unverified_supply_cap <= config.reserve_attested_supply_cap);
+        require!(
+            config.reserve_attestation_max_age >= 0 && attested_tier_higher,
+            StableFunError::InvalidProtocolConfig
+        );
+        require!(
+            config.launch_fee_lamports == 0 || config.protocol_treasury != Pubkey::default(),
+            StableFunError::InvalidProtocolConfig
+        );
+        require!(
+            config.approved_lending_programs.len() <= MAX_LENDING_PROGRAMS,
+            StableFunError::InvalidProtocolConfig
+        );
+        require!(
+            config.approved_cpi_callers.len() <= MAX_CPI_CALLERS
+                && config.staking_fee_share_bps <= BASIS_POINTS_DIVISOR,
+            StableFunError::InvalidProtocolConfig
+        );
+        // Requiring bonds needs something to post and somewhere to slash to
+        require!(
+            config.bond_supply_threshold == 0
+                || (config.bond_mint != Pubkey::default()
+                    && config.insurance_fund != Pubkey::default()
+                    && config.min_issuer_bond > 0),
+            StableFunError::InvalidProtocolConfig
+        );
         Ok(())
     }
 
     #[inline(always)]
-    pub fn validate_fee(fee_bps: u16) -> Result<()> {
-        require!(fee_bps <= MAX_FEE_BPS, StableFunError::FeeTooHigh);
+    pub fn validate_commit_reveal(threshold_bps: u16, delay_slots: u64) -> Result<()> {
+        require!(
+            threshold_bps <= BASIS_POINTS_DIVISOR && delay_slots <= MAX_REVEAL_DELAY_SLOTS,
+            StableFunError::InvalidCommitRevealSettings
+        );
+        require!(
+            threshold_bps == 0 || delay_slots > 0,
+            StableFunError::InvalidCommitRevealSettings
+        );
         Ok(())
     }
 
+    /// Redeems turned away by the liquidity buffer wait in the commit-reveal
+    /// queue, so the buffer needs a reveal delay to smooth anything.
     #[inline(always)]
-    pub fn validate_metadata(
-        name: &str,
-        symbol: &str,
-        currency: &str,
-    ) -> Result<()> {
+    pub fn validate_liquidity_buffer(buffer_bps: u16, delay_slots: u64) -> Result<()> {
         require!(
-            (MIN_NAME_LENGTH..=MAX_NAME_LENGTH).contains(&name.len()),
-            StableFunError::InvalidName
+            buffer_bps <= BASIS_POINTS_DIVISOR && (buffer_bps == 0 || delay_slots > 0),
+            StableFunError::InvalidLiquidityBuffer
         );
+        Ok(())
+    }
+
+    /// NFC-normalizes a name and checks its character count. The byte length
+    /// is bounded separately since that is what the account space is sized for.
+    #[inline(always)]
+    pub fn normalize_name(name: &str) -> Result<String> {
+        let normalized: String = name.nfc().collect();
 
         require!(
-            (MIN_SYMBOL_LENGTH..=MAX_SYMBOL_LENGTH).contains(&symbol.len()),
-            StableFunError::InvalidSymbol
+            !normalized.chars().any(is_disallowed_char),
+            StableFunError::InvalidName
+        );
+        let char_count = normalized.chars().count();
+        require!(char_count >= MIN_NAME_LENGTH, StableFunError::NameTooShort);
+        require!(
+            char_count <= MAX_NAME_LENGTH && normalized.len() <= MAX_NAME_LENGTH,
+            StableFunError::InvalidName
         );
 
+        Ok(normalized)
+    }
+
+    /// Uppercases a symbol, which must consist of ASCII letters and digits only.
+    #[inline(always)]
+    pub fn normalize_symbol(symbol: &str) -> Result<String> {
         require!(
-            !currency.is_empty() && currency.len() <= 5,
-            StableFunError::InvalidCurrency
+            symbol.chars().all(|c| c.is_ascii_alphanumeric()),
+            StableFunError::InvalidSymbol
         );
+        let char_count = symbol.chars().count();
+        require!(char_count >= MIN_SYMBOL_LENGTH, StableFunError::SymbolTooShort);
+        require!(char_count <= MAX_SYMBOL_LENGTH, StableFunError::InvalidSymbol);
 
-        Ok(())
+        Ok(symbol.to_ascii_uppercase())
+    }
+
+    /// Validates metadata and returns the normalized `(name, symbol, currency)`
+    /// triple that should be stored on chain.
+    #[inline(always)]
+    pub fn validate_metadata(
+        name: &str,
+        symbol: &str,
+        currency: &str,
+    ) -> Result<(String, String, String)> {
+        let name = Self::normalize_name(name)?;
+        let symbol = Self::normalize_symbol(symbol)?;
+        let currency = normalize_currency(currency)?;
+
+        Ok((name, symbol, currency))
     }
 
     #[inline(always)]
@@ -130,7 +375,7 @@ impl ValidationService {
         stablecoin_mint: &Account<StablecoinMint>,
         amount: u64,
         oracle_price: &OraclePrice,
-        current_collateral: u64,
+        collateral_value: u64,
     ) -> Result<()> {
         require!(!stablecoin_mint.settings.mint_paused, StableFunError::MintingPaused);
         Self::validate_amount(amount)?;
@@ -148,7 +393,7 @@ impl ValidationService {
         require!(oracle_price.value > 0, StableFunError::InvalidOraclePrice);
 
         Self::validate_collateral_ratio(
-            current_collateral,
+            collateral_value,
             new_supply,
             stablecoin_mint.settings.min_collateral_ratio,
         )?;
@@ -162,7 +407,7 @@ impl ValidationService {
         vault: &Account<StablecoinVault>,
         amount: u64,
         token_account: &Account<TokenAccount>,
-        remaining_collateral: u64,
+        remaining_collateral_value: u64,
     ) -> Result<()> {
         require!(!stablecoin_mint.settings.redeem_paused, StableFunError::RedeemingPaused);
         Self::validate_amount(amount)?;
@@ -178,7 +423,7 @@ impl ValidationService {
             .ok_or(error!(StableFunError::MathOverflow))?;
 
         Self::validate_collateral_ratio(
-            remaining_collateral,
+            remaining_collateral_value,
             new_supply,
             stablecoin_mint.settings.min_collateral_ratio,
         )?;
@@ -202,16 +447,20 @@ mod tests {
     #[test]
     fn test_collateral_ratio_validation() {
         assert!(ValidationService::validate_collateral_ratio(
-            15000000, // 150% collateral
-            10000000, // supply
+            15000000, // 150% collateral value
+            10000000, // debt
             10000     // min ratio 100%
         ).is_ok());
 
         assert!(ValidationService::validate_collateral_ratio(
-            9000000,  // 90% collateral
-            10000000, // supply
+            9000000,  // 90% collateral value
+            10000000, // debt
             10000     // min ratio 100%
         ).is_err());
+
+        // Heavily overcollateralized coins pass rather than wrap around
+        assert!(ValidationService::validate_collateral_ratio(u64::MAX, 1, 10000).is_ok());
+        assert!(ValidationService::validate_collateral_ratio(0, 0, 10000).is_ok());
     }
 
     #[test]
@@ -233,6 +482,47 @@ mod tests {
             "T",   // Too short
             "USD"
         ).is_err());
+
+        assert!(ValidationService::validate_metadata(
+            "Test Coin",
+            "TEST",
+            "US Dollar" // Not an ISO-4217 code
+        ).is_err());
+    }
+
+    #[test]
+    fn test_metadata_normalization() {
+        let (name, symbol, currency) = ValidationService::validate_metadata(
+            "Cafe\u{301} Coin", // decomposed e + combining acute
+            "usdx",
+            "usd",
+        ).unwrap();
+        assert_eq!(name, "Caf\u{e9} Coin");
+        assert_eq!(symbol, "USDX");
+        assert_eq!(currency, "USD");
+    }
+
+    #[test]
+    fn test_metadata_rejects_unsafe_characters() {
+        // Two 3-byte characters pass a byte-length minimum but not a char-count one
+        assert_eq!(
+            ValidationService::validate_metadata("\u{20AC}\u{20AC}", "TEST", "USD").unwrap_err(),
+            error!(StableFunError::NameTooShort)
+        );
+        assert_eq!(
+            ValidationService::validate_metadata("Test Coin", "T", "USD").unwrap_err(),
+            error!(StableFunError::SymbolTooShort)
+        );
+        assert!(ValidationService::normalize_name("\u{20AC}\u{20AC}\u{20AC}").is_ok());
+        // 11 characters, 33 bytes: exceeds the stored byte budget
+        assert!(ValidationService::normalize_name(&"\u{20AC}".repeat(11)).is_err());
+        assert!(ValidationService::normalize_name("Test\u{200B}Coin").is_err());
+        assert!(ValidationService::normalize_name("Test\nCoin").is_err());
+        assert!(ValidationService::normalize_name("Test\u{202E}Coin").is_err());
+
+        assert!(ValidationService::normalize_symbol("US\u{200D}D").is_err());
+        assert!(ValidationService::normalize_symbol("\u{0423}SD").is_err()); // Cyrillic U
+        assert!(ValidationService::normalize_symbol("US-D").is_err());
     }
 
     #[test]
@@ -240,4 +530,189 @@ mod tests {
         assert!(ValidationService::validate_fee(500).is_ok()); // 5%
         assert!(ValidationService::validate_fee(1100).is_err()); // 11%
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_liquidation_threshold_validation() {
+        assert!(ValidationService::validate_liquidation_threshold(12000, 15000).is_ok());
+        assert!(ValidationService::validate_liquidation_threshold(15000, 15000).is_err());
+        assert!(ValidationService::validate_liquidation_threshold(9999, 15000).is_err());
+    }
+
+    #[test]
+    fn test_liquidation_incentive_caps() {
+        let config = ProtocolConfig {
+            max_liquidation_penalty_bps: 500,
+            max_liquidator_bonus_bps: 1000,
+            ..Default::default()
+        };
+        assert!(ValidationService::validate_protocol_config(&config).is_ok());
+        assert!(ValidationService::validate_liquidation_incentives(300, 500, &config).is_ok());
+        assert!(ValidationService::validate_liquidation_incentives(600, 500, &config).is_err());
+        assert!(ValidationService::validate_liquidation_incentives(300, 1100, &config).is_err());
+
+        let config = ProtocolConfig {
+            max_liquidation_penalty_bps: MAX_LIQUIDATION_INCENTIVE_BPS + 1,
+            ..Default::default()
+        };
+        assert!(ValidationService::validate_protocol_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_attestation_config() {
+        let mut config = ProtocolConfig {
+            require_holder_attestation: true,
+            ..Default::default()
+        };
+        assert!(ValidationService::validate_protocol_config(&config).is_err());
+
+        config.attestation_program = Pubkey::new_unique();
+        config.attestation_credential = Pubkey::new_unique();
+        assert!(ValidationService::validate_protocol_config(&config).is_err());
+
+        config.holder_schema = Pubkey::new_unique();
+        assert!(ValidationService::validate_protocol_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_issuer_bond_config() {
+        let mut config = ProtocolConfig {
+            bond_supply_threshold: 1_000_000,
+            min_issuer_bond: 50_000,
+            bond_mint: Pubkey::new_unique(),
+            ..Default::default()
+        };
+        assert!(ValidationService::validate_protocol_config(&config).is_err());
+
+        config.insurance_fund = Pubkey::new_unique();
+        assert!(ValidationService::validate_protocol_config(&config).is_ok());
+        assert!(ValidationService::validate_bonded_supply(1_000_000, &config, None).is_ok());
+        assert!(ValidationService::validate_bonded_supply(1_000_001, &config, None).is_err());
+
+        config.min_issuer_bond = 0;
+        assert!(ValidationService::validate_protocol_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_demurrage_cap() {
+        let config = ProtocolConfig {
+            max_demurrage_bps: 200,
+            ..Default::default()
+        };
+        assert!(ValidationService::validate_demurrage(0, &ProtocolConfig::default()).is_ok());
+        assert!(ValidationService::validate_demurrage(100, &ProtocolConfig::default()).is_err());
+        assert!(ValidationService::validate_demurrage(200, &config).is_ok());
+        assert!(ValidationService::validate_demurrage(201, &config).is_err());
+
+        let config = ProtocolConfig {
+            max_demurrage_bps: MAX_DEMURRAGE_BPS + 1,
+            ..Default::default()
+        };
+        assert!(ValidationService::validate_protocol_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_max_supply_tier() {
+        let config = ProtocolConfig {
+            unverified_supply_cap: 1_000,
+            reserve_attested_supply_cap: 5_000,
+            reserve_attestation_max_age: 100,
+            ..Default::default()
+        };
+        let report = ReserveReport {
+            reserve_ratio: 15_000,
+            timestamp: 50,
+            report_count: 1,
+            ..Default::default()
+        };
+        assert!(ValidationService::validate_protocol_config(&config).is_ok());
+        assert!(ValidationService::validate_max_supply(1_000, &config, None, 100).is_ok());
+        assert!(ValidationService::validate_max_supply(1_001, &config, None, 100).is_err());
+        assert!(ValidationService::validate_max_supply(5_000, &config, Some(&report), 100).is_ok());
+        assert!(ValidationService::validate_max_supply(5_001, &config, Some(&report), 100).is_err());
+
+        // The attested tier can't sit below the unverified one
+        let config = ProtocolConfig {
+            unverified_supply_cap: 5_000,
+            reserve_attested_supply_cap: 1_000,
+            ..Default::default()
+        };
+        assert!(ValidationService::validate_protocol_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_launch_fee_needs_treasury() {
+        let mut config = ProtocolConfig {
+            launch_fee_lamports: 1_000_000_000,
+            launch_deposit_lamports: 500_000_000,
+            ..Default::default()
+        };
+        assert!(ValidationService::validate_protocol_config(&config).is_err());
+
+        config.protocol_treasury = Pubkey::new_unique();
+        assert!(ValidationService::validate_protocol_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_yield_fee_cap() {
+        let config = ProtocolConfig {
+            max_yield_fee_bps: 2000,
+            ..Default::default()
+        };
+        assert!(ValidationService::validate_yield_fee(0, &ProtocolConfig::default()).is_ok());
+        assert!(ValidationService::validate_yield_fee(2000, &config).is_ok());
+        assert!(ValidationService::validate_yield_fee(2001, &config).is_err());
+
+        let config = ProtocolConfig {
+            max_yield_fee_bps: MAX_YIELD_FEE_BPS + 1,
+            ..Default::default()
+        };
+        assert!(ValidationService::validate_protocol_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_liquidation_grace_validation() {
+        assert!(ValidationService::validate_liquidation_grace(0, 0).is_ok());
+        assert!(ValidationService::validate_liquidation_grace(3600, 500).is_ok());
+        assert!(ValidationService::validate_liquidation_grace(-1, 0).is_err());
+        assert!(ValidationService::validate_liquidation_grace(MAX_LIQUIDATION_GRACE_SECONDS + 1, 0).is_err());
+        assert!(ValidationService::validate_liquidation_grace(3600, 10001).is_err());
+    }
+
+    #[test]
+    fn test_commit_reveal_validation() {
+        assert!(ValidationService::validate_commit_reveal(0, 0).is_ok());
+        assert!(ValidationService::validate_commit_reveal(1000, 10).is_ok());
+        assert!(ValidationService::validate_commit_reveal(1000, 0).is_err());
+        assert!(ValidationService::validate_commit_reveal(10001, 10).is_err());
+        assert!(ValidationService::validate_commit_reveal(1000, MAX_REVEAL_DELAY_SLOTS + 1).is_err());
+    }
+
+    #[test]
+    fn test_liquidity_buffer_validation() {
+        assert!(ValidationService::validate_liquidity_buffer(0, 0).is_ok());
+        assert!(ValidationService::validate_liquidity_buffer(2000, 10).is_ok());
+        assert!(ValidationService::validate_liquidity_buffer(2000, 0).is_err());
+        assert!(ValidationService::validate_liquidity_buffer(10001, 10).is_err());
+    }
+
+    #[test]
+    fn test_withdrawal_delay_validation() {
+        assert!(ValidationService::validate_withdrawal_delay(0).is_ok()); // disabled
+        assert!(ValidationService::validate_withdrawal_delay(MIN_WITHDRAWAL_DELAY).is_ok());
+        assert!(ValidationService::validate_withdrawal_delay(MAX_WITHDRAWAL_DELAY).is_ok());
+        assert!(ValidationService::validate_withdrawal_delay(MIN_WITHDRAWAL_DELAY - 1).is_err());
+        assert!(ValidationService::validate_withdrawal_delay(MAX_WITHDRAWAL_DELAY + 1).is_err());
+    }
+
+    #[test]
+    fn test_unpause_validation() {
+        // 150% collateralized, cool-off of an hour since pausing at t=1000
+        assert!(ValidationService::validate_unpause(1_500, 1_000, 15000, 1_000, 3_600, 4_600).is_ok());
+        assert!(ValidationService::validate_unpause(1_500, 1_000, 15000, 1_000, 3_600, 4_599).is_err());
+        assert!(ValidationService::validate_unpause(1_499, 1_000, 15000, 1_000, 0, 4_600).is_err());
+        assert!(ValidationService::validate_unpause(0, 0, 15000, 1_000, 0, 1_000).is_ok());
+
+        assert!(ValidationService::validate_unpause_cooloff(0).is_ok());
+        assert!(ValidationService::validate_unpause_cooloff(MAX_UNPAUSE_COOLOFF_SECONDS + 1).is_err());
+    }
+}
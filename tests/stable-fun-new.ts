@@ -1,5 +1,32 @@
 import * as anchor from "@coral-xyz/anchor";
-import { Program } from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import {
+  AccountLayout,
+  MINT_SIZE,
+  MintLayout,
+  TOKEN_PROGRAM_ID,
+  createApproveInstruction,
+  createAssociatedTokenAccountInstruction,
+  createInitializeMint2Instruction,
+  createMintToInstruction,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import {
+  ComputeBudgetProgram,
+  Ed25519Program,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import { BankrunProvider } from "anchor-bankrun";
+import { expect } from "chai";
+import { createHash } from "crypto";
+import * as fs from "fs";
+import { Clock, ProgramTestContext, start } from "solana-bankrun";
 import { StableFunNew } from "../target/types/stable_fun_new";
 
 describe("stable-fun-new", () => {
@@ -14,3 +41,493 @@ describe("stable-fun-new", () => {
     console.log("Your transaction signature", tx);
   });
 });
+
+const BPF_LOADER_UPGRADEABLE = new PublicKey(
+  "BPFLoaderUpgradeab1e11111111111111111111111"
+);
+const SWITCHBOARD_PROGRAM_ID = new PublicKey(
+  "SW1TCH7qEPTdLsDHRgPuMQjbQxKdH2aBStViMFnt64f"
+);
+/** `AggregatorAccountData::size()`, discriminator included */
+const AGGREGATOR_SIZE = 3851;
+/** Offsets into a Switchboard V2 aggregator, discriminator included */
+const MIN_ORACLE_RESULTS_OFFSET = 236;
+const LATEST_ROUND_OFFSET = 341;
+
+const RELAYED_ORDER_DOMAIN = Buffer.from("stable-fun:relayed-order");
+const RELAYED_REDEEM = 1;
+
+const seed = (value: string) => Buffer.from(value);
+
+/**
+ * A Switchboard aggregator whose latest confirmed round reports
+ * `mantissa * 10^-scale`, opened at `timestamp`.
+ */
+function priceFeedData(mantissa: number, scale: number, timestamp: bigint) {
+  const data = Buffer.alloc(AGGREGATOR_SIZE);
+  createHash("sha256")
+    .update("account:AggregatorAccountData")
+    .digest()
+    .copy(data, 0, 0, 8);
+  data.writeUInt32LE(1, MIN_ORACLE_RESULTS_OFFSET);
+  // num_success, num_error, is_closed, round_open_slot, round_open_timestamp
+  data.writeUInt32LE(1, LATEST_ROUND_OFFSET);
+  data.writeBigInt64LE(timestamp, LATEST_ROUND_OFFSET + 17);
+  // result: i128 mantissa and u32 scale
+  data.writeBigInt64LE(BigInt(mantissa), LATEST_ROUND_OFFSET + 25);
+  data.writeUInt32LE(scale, LATEST_ROUND_OFFSET + 41);
+  return data;
+}
+
+/** Bytes the user signs to let a relayer redeem for them. */
+function relayedRedeemMessage(
+  stablecoinMint: PublicKey,
+  user: PublicKey,
+  order: { amount: BN; maxFee: BN; nonce: BN; expiry: BN }
+) {
+  return Buffer.concat([
+    RELAYED_ORDER_DOMAIN,
+    stablecoinMint.toBuffer(),
+    user.toBuffer(),
+    Buffer.from([RELAYED_REDEEM]),
+    order.amount.toArrayLike(Buffer, "le", 8),
+    order.maxFee.toArrayLike(Buffer, "le", 8),
+    order.nonce.toArrayLike(Buffer, "le", 8),
+    order.expiry.toTwos(64).toArrayLike(Buffer, "le", 8),
+  ]);
+}
+
+describe("relayed redeem", () => {
+  const programId = new PublicKey(anchor.workspace.StableFunNew.idl.address);
+  const admin = Keypair.generate();
+  const user = Keypair.generate();
+  const tokenMint = Keypair.generate();
+  const stablebondMint = Keypair.generate();
+  const priceFeed = Keypair.generate().publicKey;
+
+  const pda = (...seeds: Buffer[]) =>
+    PublicKey.findProgramAddressSync(seeds, programId)[0];
+  const stablecoinMint = pda(
+    seed("stablecoin"),
+    user.publicKey.toBuffer(),
+    seed("RUSD")
+  );
+  const vault = pda(seed("vault"), stablecoinMint.toBuffer());
+  const mintAuthority = pda(seed("mint-authority"), stablecoinMint.toBuffer());
+  const protocolConfig = pda(seed("protocol-config"));
+  const reservedNames = pda(seed("reserved-names"));
+  const bondTerms = pda(seed("bond-terms"), stablecoinMint.toBuffer());
+  const position = pda(
+    seed("position"),
+    stablecoinMint.toBuffer(),
+    user.publicKey.toBuffer()
+  );
+  const accounting = pda(seed("accounting"), stablecoinMint.toBuffer());
+  const vaultStablebondAccount = getAssociatedTokenAddressSync(
+    stablebondMint.publicKey,
+    vault,
+    true
+  );
+  const userTokenAccount = getAssociatedTokenAddressSync(
+    tokenMint.publicKey,
+    user.publicKey
+  );
+  const userStablebondAccount = getAssociatedTokenAddressSync(
+    stablebondMint.publicKey,
+    user.publicKey
+  );
+
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<StableFunNew>;
+  let relayerStablebondAccount: PublicKey;
+
+  const computeBudget = ComputeBudgetProgram.setComputeUnitLimit({
+    units: 1_400_000,
+  });
+
+  async function send(
+    instructions: TransactionInstruction[],
+    signers: Keypair[] = []
+  ) {
+    await provider.sendAndConfirm(
+      new Transaction().add(...instructions),
+      signers
+    );
+  }
+
+  async function tokenAccount(address: PublicKey) {
+    const account = await context.banksClient.getAccount(address);
+    return AccountLayout.decode(Buffer.from(account.data));
+  }
+
+  async function supply() {
+    const account = await context.banksClient.getAccount(tokenMint.publicKey);
+    return MintLayout.decode(Buffer.from(account.data)).supply;
+  }
+
+  async function setPriceFeed(mantissa: number, scale: number) {
+    const clock = await context.banksClient.getClock();
+    context.setAccount(priceFeed, {
+      lamports: LAMPORTS_PER_SOL,
+      data: priceFeedData(mantissa, scale, clock.unixTimestamp),
+      owner: SWITCHBOARD_PROGRAM_ID,
+      executable: false,
+    });
+  }
+
+  async function warp(seconds: number) {
+    const clock = await context.banksClient.getClock();
+    context.setClock(
+      new Clock(
+        clock.slot,
+        clock.epochStartTimestamp,
+        clock.epoch,
+        clock.leaderScheduleEpoch,
+        clock.unixTimestamp + BigInt(seconds)
+      )
+    );
+  }
+
+  function errorName(err: any): string | undefined {
+    if (err instanceof anchor.AnchorError) {
+      return err.error.errorCode.code;
+    }
+    const hex = /custom program error: (0x[0-9a-f]+)/.exec(`${err}`)?.[1];
+    const code =
+      err instanceof anchor.ProgramError ? err.code : parseInt(hex, 16);
+    return program.idl.errors.find((error) => error.code === code)?.name;
+  }
+
+  before(async () => {
+    // Deployed as upgradeable with `admin` as the upgrade authority, which
+    // `initialize_protocol` requires of its caller
+    const programData = PublicKey.findProgramAddressSync(
+      [programId.toBuffer()],
+      BPF_LOADER_UPGRADEABLE
+    )[0];
+    const programDataHeader = Buffer.alloc(45);
+    programDataHeader.writeUInt32LE(3, 0);
+    programDataHeader.writeUInt8(1, 12);
+    admin.publicKey.toBuffer().copy(programDataHeader, 13);
+    const programAccount = Buffer.alloc(36);
+    programAccount.writeUInt32LE(2, 0);
+    programData.toBuffer().copy(programAccount, 4);
+
+    const funded = (address: PublicKey) => ({
+      address,
+      info: {
+        lamports: 100 * LAMPORTS_PER_SOL,
+        data: Buffer.alloc(0),
+        owner: SystemProgram.programId,
+        executable: false,
+      },
+    });
+    context = await start(
+      [],
+      [
+        {
+          address: programId,
+          info: {
+            lamports: LAMPORTS_PER_SOL,
+            data: programAccount,
+            owner: BPF_LOADER_UPGRADEABLE,
+            executable: true,
+          },
+        },
+        {
+          address: programData,
+          info: {
+            lamports: 100 * LAMPORTS_PER_SOL,
+            data: Buffer.concat([
+              programDataHeader,
+              fs.readFileSync("target/deploy/stable_fun_new.so"),
+            ]),
+            owner: BPF_LOADER_UPGRADEABLE,
+            executable: false,
+          },
+        },
+        funded(admin.publicKey),
+        funded(user.publicKey),
+      ]
+    );
+    provider = new BankrunProvider(context);
+    program = new Program<StableFunNew>(
+      anchor.workspace.StableFunNew.idl,
+      provider
+    );
+    await setPriceFeed(100_000, 5);
+
+    await program.methods
+      .initializeProtocol({
+        maxLiquidationPenaltyBps: null,
+        maxLiquidatorBonusBps: null,
+        maxDemurrageBps: null,
+        attestationProgram: null,
+        attestationCredential: null,
+        issuerSchema: null,
+        holderSchema: null,
+        requireHolderAttestation: null,
+        maxYieldFeeBps: null,
+        unverifiedSupplyCap: null,
+        reserveAttestedSupplyCap: null,
+        reserveAttestationMaxAge: null,
+        protocolTreasury: null,
+        launchFeeLamports: null,
+        launchDepositLamports: null,
+        approvedLendingPrograms: null,
+        approvedCpiCallers: null,
+        stakingFeeShareBps: null,
+        bondMint: null,
+        bondSupplyThreshold: null,
+        minIssuerBond: null,
+        insuranceFund: null,
+      })
+      .accountsPartial({
+        admin: admin.publicKey,
+        protocolConfig,
+        program: programId,
+        programData,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+    await program.methods
+      .initializeReservedNames([])
+      .accountsPartial({
+        admin: admin.publicKey,
+        protocolConfig,
+        reservedNames,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    // The stablebond is a plain SPL mint here; the user holds 1,000
+    const relayer = context.payer.publicKey;
+    relayerStablebondAccount = getAssociatedTokenAddressSync(
+      stablebondMint.publicKey,
+      relayer
+    );
+    await send(
+      [
+        SystemProgram.createAccount({
+          fromPubkey: relayer,
+          newAccountPubkey: stablebondMint.publicKey,
+          lamports: LAMPORTS_PER_SOL,
+          space: MINT_SIZE,
+          programId: TOKEN_PROGRAM_ID,
+        }),
+        createInitializeMint2Instruction(
+          stablebondMint.publicKey,
+          6,
+          relayer,
+          null
+        ),
+        createAssociatedTokenAccountInstruction(
+          relayer,
+          userStablebondAccount,
+          user.publicKey,
+          stablebondMint.publicKey
+        ),
+        createAssociatedTokenAccountInstruction(
+          relayer,
+          relayerStablebondAccount,
+          relayer,
+          stablebondMint.publicKey
+        ),
+        createMintToInstruction(
+          stablebondMint.publicKey,
+          userStablebondAccount,
+          relayer,
+          1_000_000_000
+        ),
+      ],
+      [stablebondMint]
+    );
+
+    await program.methods
+      .initialize("Relay Dollar", "RUSD", "USD", new BN(0), {
+        iconUri: "",
+        metadataUri: "",
+        description: "",
+        freezeAuthorityMode: { none: {} },
+        tokenExtensions: {
+          transferFee: null,
+          nonTransferable: false,
+          interestRateBps: null,
+        },
+        demurrageRateBps: 0,
+        jurisdictionFlags: 0,
+      })
+      .accountsPartial({
+        authority: user.publicKey,
+        stablecoinMint,
+        tokenMint: tokenMint.publicKey,
+        mintAuthority,
+        freezeAuthority: pda(
+          seed("freeze-authority"),
+          stablecoinMint.toBuffer()
+        ),
+        stablebondMint: stablebondMint.publicKey,
+        vault,
+        vaultTokenAccount: vaultStablebondAccount,
+        priceFeed,
+        protocolConfig,
+        protocolTreasury: null,
+        reservedNames,
+        issuerAttestation: null,
+        stablecoinTokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user, tokenMint])
+      .rpc();
+    await send([
+      createAssociatedTokenAccountInstruction(
+        relayer,
+        userTokenAccount,
+        user.publicKey,
+        tokenMint.publicKey
+      ),
+    ]);
+
+    // Mint 100 coins at 1 stablebond each, deposited at the 150% minimum
+    await program.methods
+      .mint(new BN(100_000_000), null, false)
+      .accountsPartial({
+        user: user.publicKey,
+        stablecoinMint,
+        vault,
+        tokenMint: tokenMint.publicKey,
+        userTokenAccount,
+        userStablebondAccount,
+        vaultStablebondAccount,
+        priceFeed,
+        fxFeed: null,
+        bondTerms,
+        mintAuthority,
+        position,
+        accounting,
+        nttConfig: null,
+        operationLog: null,
+        basket: null,
+        protocolConfig,
+        holderAttestation: null,
+        orderDedupe: null,
+        liquidityLock: pda(
+          seed("locked-liquidity"),
+          stablecoinMint.toBuffer()
+        ),
+        lockedLiquidityAccount: pda(
+          seed("locked-liquidity-token"),
+          stablecoinMint.toBuffer()
+        ),
+        feeDiscount: null,
+        discountTokenAccount: null,
+        portfolio: null,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        stablecoinTokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .preInstructions([computeBudget])
+      .signers([user])
+      .rpc();
+
+    // Past the withdrawal delay, with the stablebond now worth 2 coins
+    await warp(120);
+    await setPriceFeed(50_000, 5);
+  });
+
+  async function relayedRedeem(nonce: number) {
+    const clock = await context.banksClient.getClock();
+    const order = {
+      amount: new BN(50_000_000),
+      maxFee: new BN(20_000),
+      fee: new BN(10_000),
+      nonce: new BN(nonce),
+      expiry: new BN((clock.unixTimestamp + BigInt(600)).toString()),
+    };
+    const signature = Ed25519Program.createInstructionWithPrivateKey({
+      privateKey: user.secretKey,
+      message: relayedRedeemMessage(stablecoinMint, user.publicKey, order),
+    });
+
+    await program.methods
+      .relayedRedeem(order)
+      .accountsPartial({
+        relayer: context.payer.publicKey,
+        stablecoinMint,
+        user: user.publicKey,
+        vault,
+        tokenMint: tokenMint.publicKey,
+        userTokenAccount,
+        userStablebondAccount,
+        relayerStablebondAccount,
+        vaultStablebondAccount,
+        priceFeed,
+        fxFeed: null,
+        bondTerms,
+        burnAuthority: mintAuthority,
+        relayAccount: pda(
+          seed("relay"),
+          stablecoinMint.toBuffer(),
+          user.publicKey.toBuffer()
+        ),
+        position,
+        accounting,
+        nttConfig: null,
+        operationLog: null,
+        basket: null,
+        protocolConfig,
+        holderAttestation: null,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        stablecoinTokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .preInstructions([computeBudget, signature])
+      .rpc();
+  }
+
+  it("rejects a burn the user has not delegated", async () => {
+    try {
+      await relayedRedeem(1);
+      expect.fail("relayed redeem burned without a delegation");
+    } catch (err) {
+      expect(errorName(err)).to.equal("BurnNotApproved");
+    }
+  });
+
+  it("burns through the mint-authority delegate", async () => {
+    await send(
+      [
+        createApproveInstruction(
+          userTokenAccount,
+          mintAuthority,
+          user.publicKey,
+          50_000_000
+        ),
+      ],
+      [user]
+    );
+    const before = await tokenAccount(userTokenAccount);
+    const supplyBefore = await supply();
+    const userBondsBefore = (await tokenAccount(userStablebondAccount)).amount;
+    const relayerBondsBefore = (await tokenAccount(relayerStablebondAccount))
+      .amount;
+
+    await relayedRedeem(2);
+
+    const after = await tokenAccount(userTokenAccount);
+    expect(before.amount - after.amount).to.equal(BigInt(50_000_000));
+    expect(after.delegatedAmount).to.equal(BigInt(0));
+    expect(after.delegateOption).to.equal(0);
+    expect(supplyBefore - (await supply())).to.equal(BigInt(50_000_000));
+
+    // 25 stablebonds less the 0.3% redeem fee, 0.005 of them to the relayer
+    const userBonds = (await tokenAccount(userStablebondAccount)).amount;
+    const relayerBonds = (await tokenAccount(relayerStablebondAccount)).amount;
+    expect(userBonds - userBondsBefore).to.equal(BigInt(24_920_000));
+    expect(relayerBonds - relayerBondsBefore).to.equal(BigInt(5_000));
+  });
+});